@@ -100,16 +100,18 @@ impl ZellijPlugin for State {
             EventType::FailedToWriteConfigToDisk,
             EventType::ModeUpdate,
         ]);
-        let own_plugin_id = get_plugin_ids().plugin_id;
-        if self.is_setup_wizard {
+        let pane_title = if self.is_setup_wizard {
             self.ui_size = 18;
             self.current_screen = Screen::new_reset_keybindings_screen(Some(0));
-            rename_plugin_pane(own_plugin_id, "First Run Setup Wizard (Step 1/1)");
             resize_focused_pane(Resize::Increase);
             resize_focused_pane(Resize::Increase);
             resize_focused_pane(Resize::Increase);
+            "First Run Setup Wizard (Step 1/1)"
         } else {
-            rename_plugin_pane(own_plugin_id, "Configuration");
+            "Configuration"
+        };
+        if let Ok(plugin_ids) = get_plugin_ids() {
+            rename_plugin_pane(plugin_ids.plugin_id, pane_title);
         }
     }
     fn update(&mut self, event: Event) -> bool {
@@ -140,6 +140,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Locked"
         }}
+        bind "k" {{
+            LaunchOrFocusPlugin "keybind-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Locked"
+        }}
     }}
     shared_except "locked" "renametab" "renamepane" {{
         bind "{primary_modifier} g" {{ SwitchToMode "Locked"; }}
@@ -183,6 +190,15 @@ keybinds clear-defaults=true {{
     shared_except "move" "locked" "renametab" "renamepane" "entersearch" {{
         bind "m" {{ SwitchToMode "Move"; }}
     }}
+    shared_except "locked" "renametab" "renamepane" "entersearch" {{
+        bind "?" {{
+            LaunchOrFocusPlugin "shortcut-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Locked"
+        }}
+    }}
 }}"#
     )
 }
@@ -339,6 +355,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "k" {{
+            LaunchOrFocusPlugin "keybind-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -403,6 +426,15 @@ keybinds clear-defaults=true {{
     shared_except "tmux" "locked" {{
         bind "{primary_modifier} b" {{ SwitchToMode "Tmux"; }}
     }}
+    shared_except "normal" "locked" "renametab" "renamepane" "entersearch" {{
+        bind "?" {{
+            LaunchOrFocusPlugin "shortcut-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
+    }}
 }}
 "#
     )
@@ -538,6 +570,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "k" {{
+            LaunchOrFocusPlugin "keybind-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -578,6 +617,15 @@ keybinds clear-defaults=true {{
     shared_except "normal" "locked" {{
         bind "Enter" "Esc" {{ SwitchToMode "Normal"; }}
     }}
+    shared_except "normal" "locked" "renametab" "renamepane" "entersearch" {{
+        bind "?" {{
+            LaunchOrFocusPlugin "shortcut-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
+    }}
 }}
 "#
     )
@@ -718,6 +766,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "k" {{
+            LaunchOrFocusPlugin "keybind-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -770,6 +825,15 @@ keybinds clear-defaults=true {{
     shared_except "tmux" "locked" {{
         bind "{primary_modifier} b" {{ SwitchToMode "Tmux"; }}
     }}
+    shared_except "normal" "locked" "renametab" "renamepane" "entersearch" {{
+        bind "?" {{
+            LaunchOrFocusPlugin "shortcut-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
+    }}
 }}
 "#
     )
@@ -900,6 +964,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "k" {{
+            LaunchOrFocusPlugin "keybind-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -926,6 +997,15 @@ keybinds clear-defaults=true {{
     shared_except "normal" "locked" {{
         bind "Enter" "Esc" {{ SwitchToMode "Normal"; }}
     }}
+    shared_except "normal" "locked" "renametab" "renamepane" "entersearch" {{
+        bind "?" {{
+            LaunchOrFocusPlugin "shortcut-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
+    }}
 }}
 "#
     )
@@ -1077,6 +1157,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "k" {{
+            LaunchOrFocusPlugin "keybind-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -1141,6 +1228,15 @@ keybinds clear-defaults=true {{
     shared_except "tmux" "locked" {{
         bind "{primary_modifier} b" {{ SwitchToMode "Tmux"; }}
     }}
+    shared_except "normal" "locked" "renametab" "renamepane" "entersearch" {{
+        bind "?" {{
+            LaunchOrFocusPlugin "shortcut-help" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
+    }}
 }}
 "#
     )
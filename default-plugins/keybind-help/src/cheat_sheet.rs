@@ -0,0 +1,311 @@
+use zellij_tile::prelude::actions::Action;
+use zellij_tile::prelude::*;
+
+use crate::{truncate_string_end, KeybindEntry, CATEGORIES};
+
+const MIN_COLUMN_WIDTH: usize = 40;
+const MAX_KEY_WIDTH: usize = 16;
+
+enum CardLine<'a> {
+    Category(&'static str),
+    Entry(&'a KeybindEntry),
+}
+
+// A read-only card of all the shortcuts reachable from the base mode, eg. "Ctrl p → n" for a new
+// pane, laid out in as many columns as fit and dismissed with any key other than the paging ones
+#[derive(Default)]
+pub struct CheatSheet {
+    entries: Vec<KeybindEntry>,
+    page: usize,
+}
+
+impl CheatSheet {
+    pub fn update_keybinds(&mut self, keybinds: &KeybindsVec, base_mode: InputMode) {
+        self.entries = cheat_sheet_entries(keybinds, base_mode);
+    }
+    pub fn handle_key(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Right | BareKey::PageDown if key.has_no_modifiers() => {
+                self.page += 1;
+                true
+            },
+            BareKey::Left | BareKey::PageUp if key.has_no_modifiers() => {
+                self.page = self.page.saturating_sub(1);
+                true
+            },
+            _ => {
+                close_self();
+                false
+            },
+        }
+    }
+    pub fn render(&mut self, rows: usize, cols: usize) {
+        let title = Text::new("SHORTCUTS").color_range(2, ..);
+        print_text_with_coordinates(title, 0, 0, None, None);
+        if self.entries.is_empty() {
+            let text = Text::new("No keybindings are configured").color_range(1, ..);
+            print_text_with_coordinates(text, 0, 2, Some(cols), None);
+            self.render_help(rows, 1);
+            return;
+        }
+        let (column_count, column_width) = column_layout(cols);
+        let rows_per_column = std::cmp::max(2, rows.saturating_sub(4)); // 2 top padding, 2 bottom padding
+        let key_width = self
+            .entries
+            .iter()
+            .map(|e| e.key.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(MAX_KEY_WIDTH);
+        let columns = layout_columns(&self.entries, rows_per_column);
+        let page_count = page_count(columns.len(), column_count);
+        self.page = std::cmp::min(self.page, page_count.saturating_sub(1));
+        let visible_columns = columns
+            .iter()
+            .skip(self.page * column_count)
+            .take(column_count);
+        for (column_index, column) in visible_columns.enumerate() {
+            let x = column_index * column_width;
+            for (line_index, line) in column.iter().enumerate() {
+                let text = match line {
+                    CardLine::Category(category) => Text::new(*category).color_range(2, ..),
+                    CardLine::Entry(entry) => {
+                        let key = format!(
+                            "{:width$}",
+                            truncate_string_end(&entry.key, key_width),
+                            width = key_width
+                        );
+                        let key_len = key.chars().count();
+                        let description = truncate_string_end(
+                            &entry.description,
+                            column_width.saturating_sub(key_len + 2), // 1 for the separator, 1 for the gap between columns
+                        );
+                        Text::new(format!("{} {}", key, description)).color_range(3, ..key_len)
+                    },
+                };
+                print_text_with_coordinates(text, x, line_index + 2, Some(column_width), None);
+            }
+        }
+        self.render_help(rows, page_count);
+    }
+    fn render_help(&self, rows: usize, page_count: usize) {
+        let text = if page_count > 1 {
+            let page = format!("Page {}/{}", self.page + 1, page_count);
+            let help_text = format!("{}, <←→> - Change page, <Any other key> - Close", page);
+            let page_len = page.chars().count();
+            Text::new(help_text)
+                .color_range(2, ..page_len)
+                .color_range(3, page_len + 2..page_len + 6)
+                .color_range(3, page_len + 22..page_len + 37)
+        } else {
+            Text::new("Help: <Any key> - Close").color_range(3, 6..15)
+        };
+        print_text_with_coordinates(text, 0, rows, None, None);
+    }
+}
+
+// the number of columns that fit and their width
+fn column_layout(cols: usize) -> (usize, usize) {
+    let column_count = std::cmp::max(1, cols / MIN_COLUMN_WIDTH);
+    (column_count, cols / column_count)
+}
+
+fn page_count(column_count: usize, columns_per_page: usize) -> usize {
+    (column_count + columns_per_page - 1) / columns_per_page
+}
+
+fn layout_columns(entries: &[KeybindEntry], rows_per_column: usize) -> Vec<Vec<CardLine>> {
+    let mut columns = vec![];
+    let mut column = vec![];
+    for category in CATEGORIES {
+        let entries: Vec<&KeybindEntry> =
+            entries.iter().filter(|e| e.category == *category).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        // do not leave a category title alone at the bottom of a column
+        if column.len() + 1 >= rows_per_column {
+            columns.push(std::mem::take(&mut column));
+        }
+        column.push(CardLine::Category(category));
+        for entry in entries {
+            if column.len() >= rows_per_column {
+                columns.push(std::mem::take(&mut column));
+            }
+            column.push(CardLine::Entry(entry));
+        }
+    }
+    if !column.is_empty() {
+        columns.push(column);
+    }
+    columns
+}
+
+fn mode_keybinds(keybinds: &KeybindsVec, mode: InputMode) -> &[(KeyWithModifier, Vec<Action>)] {
+    keybinds
+        .iter()
+        .find(|(keybinds_mode, _)| *keybinds_mode == mode)
+        .map(|(_, keybinds)| keybinds.as_slice())
+        .unwrap_or(&[])
+}
+
+fn cheat_sheet_entries(keybinds: &KeybindsVec, base_mode: InputMode) -> Vec<KeybindEntry> {
+    let base_mode_keybinds = mode_keybinds(keybinds, base_mode);
+    let mut entries = vec![];
+    // the key that enters each mode from the base mode, eg. "Ctrl p" for pane mode
+    let mut mode_prefixes: Vec<(InputMode, String)> = vec![];
+    for (key, actions) in base_mode_keybinds {
+        if let [Action::SwitchToMode(mode)] = actions.as_slice() {
+            if *mode != base_mode && !mode_prefixes.iter().any(|(m, _)| m == mode) {
+                mode_prefixes.push((*mode, key.to_string()));
+            }
+        }
+        entries.push(KeybindEntry::new(key, actions));
+    }
+    let back_to_base_mode = Action::SwitchToMode(base_mode);
+    for (mode, prefix) in mode_prefixes {
+        for (key, actions) in mode_keybinds(keybinds, mode) {
+            // the same shortcut is available without entering the mode
+            if base_mode_keybinds
+                .iter()
+                .any(|(base_key, base_actions)| base_key == key && base_actions == actions)
+            {
+                continue;
+            }
+            // leaving the mode is implied, and entering other modes is listed above
+            let actions: Vec<Action> = actions
+                .iter()
+                .filter(|a| **a != back_to_base_mode)
+                .cloned()
+                .collect();
+            if actions
+                .iter()
+                .all(|a| matches!(a, Action::SwitchToMode(..)))
+            {
+                continue;
+            }
+            entries.push(KeybindEntry::new(key, &actions).with_key_prefix(&prefix));
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, category: &'static str) -> KeybindEntry {
+        KeybindEntry {
+            key: key.to_owned(),
+            description: String::new(),
+            category,
+        }
+    }
+
+    fn column_lines(columns: Vec<Vec<CardLine>>) -> Vec<Vec<String>> {
+        columns
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .map(|line| match line {
+                        CardLine::Category(category) => format!("# {}", category),
+                        CardLine::Entry(entry) => entry.key.clone(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn columns_are_filled_in_category_order() {
+        let entries = vec![
+            entry("d", "Tabs"),
+            entry("a", "Panes"),
+            entry("b", "Panes"),
+            entry("c", "Panes"),
+        ];
+        let rows_per_column = 3;
+        assert_eq!(
+            column_lines(layout_columns(&entries, rows_per_column)),
+            vec![vec!["# Panes", "a", "b"], vec!["c", "# Tabs", "d"]]
+        );
+    }
+
+    #[test]
+    fn category_titles_are_not_left_alone_at_the_bottom_of_a_column() {
+        let entries = vec![entry("a", "Panes"), entry("d", "Tabs"), entry("e", "Tabs")];
+        let rows_per_column = 3;
+        assert_eq!(
+            column_lines(layout_columns(&entries, rows_per_column)),
+            vec![vec!["# Panes", "a"], vec!["# Tabs", "d", "e"]]
+        );
+    }
+
+    #[test]
+    fn as_many_columns_as_fit_share_the_width() {
+        assert_eq!(column_layout(30), (1, 30));
+        assert_eq!(column_layout(80), (2, 40));
+        assert_eq!(column_layout(130), (3, 43));
+    }
+
+    #[test]
+    fn columns_are_paged_by_the_number_that_fit() {
+        assert_eq!(page_count(0, 2), 0);
+        assert_eq!(page_count(1, 3), 1);
+        assert_eq!(page_count(4, 2), 2);
+        assert_eq!(page_count(5, 2), 3);
+    }
+
+    #[test]
+    fn shortcuts_of_other_modes_are_prefixed_with_the_key_that_enters_them() {
+        let new_pane = Action::NewPane(None, None, false);
+        let keybinds = vec![
+            (
+                InputMode::Normal,
+                vec![
+                    (
+                        KeyWithModifier::new(BareKey::Char('p')).with_ctrl_modifier(),
+                        vec![Action::SwitchToMode(InputMode::Pane)],
+                    ),
+                    (
+                        KeyWithModifier::new(BareKey::Char('n')).with_alt_modifier(),
+                        vec![new_pane.clone()],
+                    ),
+                ],
+            ),
+            (
+                InputMode::Pane,
+                vec![
+                    (
+                        KeyWithModifier::new(BareKey::Char('n')),
+                        vec![new_pane.clone(), Action::SwitchToMode(InputMode::Normal)],
+                    ),
+                    // leaving the mode is not a shortcut
+                    (
+                        KeyWithModifier::new(BareKey::Esc),
+                        vec![Action::SwitchToMode(InputMode::Normal)],
+                    ),
+                    // available without entering the mode
+                    (
+                        KeyWithModifier::new(BareKey::Char('n')).with_alt_modifier(),
+                        vec![new_pane.clone()],
+                    ),
+                ],
+            ),
+        ];
+        let entries = cheat_sheet_entries(&keybinds, InputMode::Normal);
+        let keys_and_categories: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.category))
+            .collect();
+        assert_eq!(
+            keys_and_categories,
+            vec![
+                ("Ctrl p", "Modes"),
+                ("Alt n", "Panes"),
+                ("Ctrl p → n", "Panes"),
+            ]
+        );
+    }
+}
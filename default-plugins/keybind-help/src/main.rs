@@ -0,0 +1,387 @@
+mod cheat_sheet;
+
+use zellij_tile::prelude::actions::Action;
+use zellij_tile::prelude::*;
+use zellij_tile_utils::{grapheme_count, safe_truncate, safe_truncate_start};
+
+use std::collections::BTreeMap;
+
+use cheat_sheet::CheatSheet;
+
+// the order in which categories are displayed
+const CATEGORIES: &[&str] = &[
+    "Modes", "Panes", "Tabs", "Scroll", "Search", "Session", "Other",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeybindEntry {
+    key: String,
+    description: String,
+    category: &'static str,
+}
+
+impl KeybindEntry {
+    pub fn new(key: &KeyWithModifier, actions: &[Action]) -> Self {
+        let description = actions
+            .iter()
+            .map(|a| describe_action(a))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let category = actions
+            .iter()
+            .map(|a| action_category(a))
+            .find(|c| *c != "Modes")
+            .or_else(|| actions.first().map(|a| action_category(a)))
+            .unwrap_or("Other");
+        KeybindEntry {
+            key: key.to_string(),
+            description,
+            category,
+        }
+    }
+    pub fn with_key_prefix(mut self, prefix: &str) -> Self {
+        self.key = format!("{} → {}", prefix, self.key);
+        self
+    }
+    pub fn matches(&self, search_term: &str) -> bool {
+        let search_term = search_term.to_lowercase();
+        self.key.to_lowercase().contains(&search_term)
+            || self.description.to_lowercase().contains(&search_term)
+            || self.category.to_lowercase().contains(&search_term)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    mode: InputMode,
+    keybinds: KeybindsVec,
+    entries: Vec<KeybindEntry>,
+    search_term: String,
+    scroll_offset: usize,
+    cheat_sheet: Option<CheatSheet>, // configured with "cheat_sheet true"
+}
+
+register_plugin!(State);
+
+impl ZellijPlugin for State {
+    fn load(&mut self, configuration: BTreeMap<String, String>) {
+        subscribe(&[EventType::ModeUpdate, EventType::Key]);
+        let pane_title = if configuration.get("cheat_sheet").map(|c| c.as_str()) == Some("true") {
+            self.cheat_sheet = Some(CheatSheet::default());
+            "Shortcuts"
+        } else {
+            "Keybind Help"
+        };
+        if let Ok(plugin_ids) = get_plugin_ids() {
+            rename_plugin_pane(plugin_ids.plugin_id, pane_title);
+        }
+    }
+    fn update(&mut self, event: Event) -> bool {
+        let mut should_render = false;
+        match event {
+            Event::ModeUpdate(mode_info) if self.cheat_sheet.is_some() => {
+                if self.keybinds != mode_info.keybinds {
+                    self.keybinds = mode_info.keybinds;
+                    let base_mode = mode_info.base_mode.unwrap_or(InputMode::Normal);
+                    if let Some(cheat_sheet) = self.cheat_sheet.as_mut() {
+                        cheat_sheet.update_keybinds(&self.keybinds, base_mode);
+                    }
+                    should_render = true;
+                }
+            },
+            Event::ModeUpdate(mode_info) => {
+                if self.mode != mode_info.mode || self.keybinds != mode_info.keybinds {
+                    self.keybinds = mode_info.keybinds;
+                    self.show_mode(mode_info.mode);
+                    should_render = true;
+                }
+            },
+            Event::Key(key) => {
+                should_render = match self.cheat_sheet.as_mut() {
+                    Some(cheat_sheet) => cheat_sheet.handle_key(key),
+                    None => self.handle_key(key),
+                };
+            },
+            _ => {},
+        }
+        should_render
+    }
+    fn render(&mut self, rows: usize, cols: usize) {
+        if let Some(cheat_sheet) = self.cheat_sheet.as_mut() {
+            cheat_sheet.render(rows, cols);
+            return;
+        }
+        self.render_search(cols);
+        let list_y = 2;
+        let max_list_items = rows.saturating_sub(4); // 2 top padding, 2 bottom padding
+        let items = self.render_entries(cols);
+        let more_above = std::cmp::min(self.scroll_offset, items.len());
+        let visible_items: Vec<NestedListItem> = items
+            .into_iter()
+            .skip(more_above)
+            .take(max_list_items + 1)
+            .collect();
+        let (more_below, visible_items) = if visible_items.len() > max_list_items {
+            let mut visible_items = visible_items;
+            visible_items.truncate(max_list_items);
+            (true, visible_items)
+        } else {
+            (false, visible_items)
+        };
+        if visible_items.is_empty() {
+            let text = if self.entries.is_empty() {
+                "No keybindings in this mode"
+            } else {
+                "No keybindings match your search"
+            };
+            print_text_with_coordinates(Text::new(text).color_range(1, ..), 0, list_y, None, None);
+        }
+        if more_above > 0 {
+            print_text_with_coordinates(
+                Text::new("↑").color_range(1, ..),
+                cols.saturating_sub(1),
+                list_y,
+                None,
+                None,
+            );
+        }
+        if more_below {
+            print_text_with_coordinates(
+                Text::new("↓").color_range(1, ..),
+                cols.saturating_sub(1),
+                list_y + max_list_items.saturating_sub(1),
+                None,
+                None,
+            );
+        }
+        print_nested_list_with_coordinates(visible_items, 0, list_y, Some(cols), None);
+        self.render_help(rows);
+    }
+}
+
+impl State {
+    fn show_mode(&mut self, mode: InputMode) {
+        self.mode = mode;
+        self.entries = self
+            .keybinds
+            .iter()
+            .find(|(keybinds_mode, _)| *keybinds_mode == mode)
+            .map(|(_, keybinds)| {
+                keybinds
+                    .iter()
+                    .map(|(key, actions)| KeybindEntry::new(key, actions))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.scroll_offset = 0;
+    }
+    fn cycle_mode(&mut self, forward: bool) {
+        let modes: Vec<InputMode> = self.keybinds.iter().map(|(mode, _)| *mode).collect();
+        if modes.is_empty() {
+            return;
+        }
+        let current_position = modes.iter().position(|m| *m == self.mode).unwrap_or(0);
+        let next_position = if forward {
+            (current_position + 1) % modes.len()
+        } else {
+            (current_position + modes.len() - 1) % modes.len()
+        };
+        self.show_mode(modes[next_position]);
+    }
+    fn handle_key(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Tab if key.has_no_modifiers() => {
+                self.cycle_mode(true);
+                true
+            },
+            BareKey::Tab if key.has_modifiers(&[KeyModifier::Shift]) => {
+                self.cycle_mode(false);
+                true
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                if self.search_term.is_empty() {
+                    close_self();
+                } else {
+                    self.search_term.clear();
+                    self.scroll_offset = 0;
+                }
+                true
+            },
+            BareKey::Down if key.has_no_modifiers() => {
+                self.scroll_offset += 1;
+                true
+            },
+            BareKey::Up if key.has_no_modifiers() => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                true
+            },
+            BareKey::Backspace if key.has_no_modifiers() => {
+                self.search_term.pop();
+                self.scroll_offset = 0;
+                true
+            },
+            BareKey::Char(character) if key.has_no_modifiers() => {
+                self.search_term.push(character);
+                self.scroll_offset = 0;
+                true
+            },
+            _ => false,
+        }
+    }
+    fn render_search(&self, cols: usize) {
+        let mode = format!("{:?}", self.mode).to_uppercase();
+        let search_term = truncate_string_start(
+            &self.search_term,
+            cols.saturating_sub(mode.chars().count() + 13), // 12 for the prompt + 1 for the cursor
+        );
+        let prompt = format!("{} - SEARCH: ", mode);
+        let prompt_len = prompt.chars().count();
+        let text = Text::new(format!("{}{}_", prompt, search_term))
+            .color_range(2, ..mode.chars().count())
+            .color_range(3, prompt_len..);
+        print_text_with_coordinates(text, 0, 0, None, None);
+    }
+    fn render_entries(&self, cols: usize) -> Vec<NestedListItem> {
+        let max_key_len = self
+            .entries
+            .iter()
+            .map(|e| e.key.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut items = vec![];
+        for category in CATEGORIES {
+            let entries: Vec<&KeybindEntry> = self
+                .entries
+                .iter()
+                .filter(|e| e.category == *category && e.matches(&self.search_term))
+                .collect();
+            if entries.is_empty() {
+                continue;
+            }
+            items.push(NestedListItem::new(*category).color_range(2, ..));
+            for entry in entries {
+                let key = format!("{:width$}", entry.key, width = max_key_len);
+                let key_len = key.chars().count();
+                let description = truncate_string_end(
+                    &entry.description,
+                    cols.saturating_sub(key_len + 8), // 3 for the separator + 5 for the bulletin
+                );
+                items.push(
+                    NestedListItem::new(format!("{} - {}", key, description))
+                        .color_range(3, ..key_len)
+                        .indent(1),
+                );
+            }
+        }
+        items
+    }
+    fn render_help(&self, rows: usize) {
+        let help_text = "Help: <↓↑> - Scroll, <TAB> - Next mode, <Esc> - Clear search / Close";
+        let text = Text::new(help_text)
+            .color_range(3, 6..=9)
+            .color_range(3, 21..=25)
+            .color_range(3, 40..=44);
+        print_text_with_coordinates(text, 0, rows, None, None);
+    }
+}
+
+fn action_name(action: &Action) -> String {
+    let debug = format!("{:?}", action);
+    debug
+        .split(|c: char| c == '(' || c == ' ' || c == '{')
+        .next()
+        .unwrap_or("")
+        .to_owned()
+}
+
+fn describe_action(action: &Action) -> String {
+    match action {
+        Action::SwitchToMode(input_mode) => format!("Switch to {:?} mode", input_mode),
+        Action::GoToTab(index) => format!("Go to tab {}", index),
+        Action::MoveFocus(direction) => format!("Move focus {}", direction_name(direction)),
+        Action::MovePane(Some(direction)) => format!("Move pane {}", direction_name(direction)),
+        Action::NewPane(Some(direction), ..) => {
+            format!("New pane {}", direction_name(direction))
+        },
+        Action::Resize(resize, direction) => match direction {
+            Some(direction) => format!("{:?} size {}", resize, direction_name(direction)),
+            None => format!("{:?} size", resize),
+        },
+        Action::LaunchOrFocusPlugin(run_plugin, ..) => {
+            format!("Open {}", run_plugin.location_string())
+        },
+        Action::Write(..) | Action::WriteChars(..) => "Send to terminal".to_owned(),
+        _ => split_camel_case(&action_name(action)),
+    }
+}
+
+fn direction_name(direction: &Direction) -> &'static str {
+    match direction {
+        Direction::Left => "left",
+        Direction::Right => "right",
+        Direction::Up => "up",
+        Direction::Down => "down",
+    }
+}
+
+fn action_category(action: &Action) -> &'static str {
+    let name = action_name(action);
+    if name == "SwitchToMode" {
+        "Modes"
+    } else if name.contains("Tab") {
+        "Tabs"
+    } else if name.contains("Scroll") || name.contains("Page") || name == "EditScrollback" {
+        "Scroll"
+    } else if name.contains("Search") {
+        "Search"
+    } else if name.contains("Pane")
+        || name.contains("Focus")
+        || name.contains("Resize")
+        || name.contains("Layout")
+        || name.contains("Fullscreen")
+        || name == "Clear"
+        || name == "TogglePaneFrames"
+    {
+        "Panes"
+    } else if name == "Detach"
+        || name == "Quit"
+        || name.contains("Plugin")
+        || name.contains("Session")
+    {
+        "Session"
+    } else {
+        "Other"
+    }
+}
+
+fn split_camel_case(name: &str) -> String {
+    let mut split = String::new();
+    for (i, character) in name.chars().enumerate() {
+        if i > 0 && character.is_uppercase() {
+            split.push(' ');
+            split.extend(character.to_lowercase());
+        } else {
+            split.push(character);
+        }
+    }
+    split
+}
+
+fn truncate_string_start(string: &str, max_len: usize) -> String {
+    if grapheme_count(string) <= max_len {
+        string.to_owned()
+    } else {
+        format!(
+            "...{}",
+            safe_truncate_start(string, max_len.saturating_sub(3))
+        )
+    }
+}
+
+fn truncate_string_end(string: &str, max_len: usize) -> String {
+    if grapheme_count(string) <= max_len {
+        string.to_owned()
+    } else {
+        format!("{}...", safe_truncate(string, max_len.saturating_sub(3)))
+    }
+}
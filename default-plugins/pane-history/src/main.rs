@@ -0,0 +1,212 @@
+use zellij_tile::prelude::*;
+use zellij_tile_utils::safe_truncate;
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+// how many closed panes to remember
+const MAX_CLOSED_PANES: usize = 20;
+const SHOW_HISTORY_ACTION_ID: &str = "show_pane_history";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ClosedPane {
+    title: String,
+    command: Option<String>,   // None for panes running the default shell
+    command_argv: Vec<String>, // the command and its arguments
+    cwd: Option<PathBuf>,
+    exit_status: Option<i32>,
+}
+
+impl ClosedPane {
+    fn from_pane_info(pane_info: &PaneInfo) -> Self {
+        ClosedPane {
+            title: pane_info.title.clone(),
+            command: pane_info.terminal_command.clone(),
+            command_argv: pane_info.terminal_command_argv.clone(),
+            cwd: pane_info.cwd.clone(),
+            exit_status: pane_info.exit_status,
+        }
+    }
+    fn reopen(&self, default_cwd: &Path) {
+        let cwd = self
+            .cwd
+            .clone()
+            .unwrap_or_else(|| default_cwd.to_path_buf());
+        match self.command_argv.split_first() {
+            Some((path, args)) => {
+                let command_to_run = CommandToRun {
+                    path: PathBuf::from(path),
+                    args: args.to_vec(),
+                    cwd: Some(cwd),
+                };
+                open_command_pane(command_to_run, BTreeMap::new());
+            },
+            None => open_terminal(cwd),
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    // the terminal panes of the session as of the last PaneUpdate, so that we know what a pane
+    // was once it is closed
+    panes: BTreeMap<u32, PaneInfo>,
+    closed_panes: VecDeque<ClosedPane>, // most recently closed first
+    selected: usize,
+    initial_cwd: PathBuf,
+}
+
+register_plugin!(State);
+
+impl ZellijPlugin for State {
+    fn load(&mut self, configuration: BTreeMap<String, String>) {
+        request_permission(&[
+            PermissionType::ReadApplicationState,
+            PermissionType::RunCommands,
+            PermissionType::OpenTerminalsOrPlugins,
+            PermissionType::Reconfigure,
+        ]);
+        subscribe(&[
+            EventType::PaneUpdate,
+            EventType::PaneClosed,
+            EventType::PermissionRequestResult,
+            EventType::Key,
+        ]);
+        if let Ok(plugin_ids) = get_plugin_ids() {
+            self.initial_cwd = plugin_ids.initial_cwd;
+            rename_plugin_pane(plugin_ids.plugin_id, "Pane History");
+        }
+        // eg. shortcut "Alt u" shows the history from anywhere, even when this plugin is hidden
+        if let Some(shortcut) = configuration.get("shortcut") {
+            match KeyWithModifier::from_str(shortcut) {
+                Ok(key) => register_global_hotkey(key, SHOW_HISTORY_ACTION_ID),
+                Err(e) => eprintln!("Invalid shortcut {:?}: {}", shortcut, e),
+            }
+        }
+    }
+    fn update(&mut self, event: Event) -> bool {
+        let mut should_render = false;
+        match event {
+            Event::PaneUpdate(pane_manifest) => {
+                for pane in pane_manifest.panes.into_values().flatten() {
+                    // plugin panes cannot be reopened as commands, and background plugins are
+                    // not something the user closed
+                    if !pane.is_plugin {
+                        self.panes.insert(pane.id, pane);
+                    }
+                }
+            },
+            Event::PaneClosed(PaneId::Terminal(terminal_id)) => {
+                if let Some(pane_info) = self.panes.remove(&terminal_id) {
+                    self.closed_panes
+                        .push_front(ClosedPane::from_pane_info(&pane_info));
+                    self.closed_panes.truncate(MAX_CLOSED_PANES);
+                    should_render = true;
+                }
+            },
+            Event::Key(key) => {
+                should_render = self.handle_key(key);
+            },
+            _ => {},
+        }
+        should_render
+    }
+    fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+        if pipe_message.name == "global_hotkey"
+            && pipe_message.payload.as_deref() == Some(SHOW_HISTORY_ACTION_ID)
+        {
+            self.selected = 0;
+            show_self(true);
+            return true;
+        }
+        false
+    }
+    fn render(&mut self, rows: usize, cols: usize) {
+        let title = Text::new("RECENTLY CLOSED PANES").color_range(2, ..);
+        print_text_with_coordinates(title, 0, 0, None, None);
+        if self.closed_panes.is_empty() {
+            let text = Text::new("No panes were closed yet");
+            print_text_with_coordinates(text, 0, 2, Some(cols), None);
+        } else {
+            if self.selected >= self.closed_panes.len() {
+                self.selected = self.closed_panes.len() - 1;
+            }
+            // 4 for the title, its padding and the help line
+            let max_list_items = rows.saturating_sub(4);
+            // keep the selected entry in view
+            let skip = (self.selected + 1).saturating_sub(max_list_items);
+            let items = self
+                .closed_panes
+                .iter()
+                .enumerate()
+                .skip(skip)
+                .take(max_list_items)
+                .map(|(i, closed_pane)| {
+                    let item = list_item(closed_pane, cols);
+                    if i == self.selected {
+                        item.selected()
+                    } else {
+                        item
+                    }
+                })
+                .collect();
+            print_nested_list_with_coordinates(items, 0, 2, Some(cols), None);
+        }
+        let help = Text::new("Help: <↓↑> - Navigate, <ENTER> - Reopen pane, <ESC> - Hide")
+            .color_range(3, 6..=9)
+            .color_range(3, 23..=29)
+            .color_range(3, 46..=50);
+        print_text_with_coordinates(help, 0, rows, None, None);
+    }
+}
+
+impl State {
+    fn handle_key(&mut self, key: KeyWithModifier) -> bool {
+        if !key.has_no_modifiers() {
+            return false;
+        }
+        match key.bare_key {
+            BareKey::Down => {
+                self.selected += 1;
+                true
+            },
+            BareKey::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                true
+            },
+            BareKey::Enter => {
+                if let Some(closed_pane) = self.closed_panes.remove(self.selected) {
+                    closed_pane.reopen(&self.initial_cwd);
+                    hide_self();
+                }
+                true
+            },
+            BareKey::Esc => {
+                // closing would lose the history, which is only kept while we are running
+                hide_self();
+                false
+            },
+            _ => false,
+        }
+    }
+}
+
+fn list_item(closed_pane: &ClosedPane, cols: usize) -> NestedListItem {
+    let what = closed_pane
+        .command
+        .clone()
+        .unwrap_or_else(|| "shell".to_owned());
+    let mut description = format!("{} ({}", closed_pane.title, what);
+    if let Some(cwd) = &closed_pane.cwd {
+        description.push_str(&format!(" in {}", cwd.display()));
+    }
+    if let Some(exit_status) = closed_pane.exit_status {
+        description.push_str(&format!(", exit code {}", exit_status));
+    }
+    description.push(')');
+    let title_len = closed_pane.title.chars().count();
+    // 2 for the bullet of the list
+    let description = safe_truncate(&description, cols.saturating_sub(2));
+    NestedListItem::new(description).color_range(0, ..title_len)
+}
@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, BTreeSet};
+use zellij_tile::prelude::*;
+
+/// Walks the dependency graph of a plugin about to be loaded, asking for the dependencies of
+/// every plugin found along the way, so that they can be loaded before it
+pub struct DependencyResolution {
+    plugin_url: String,
+    config: BTreeMap<String, String>,
+    load_in_background: bool,
+    dependencies: BTreeMap<String, Vec<PluginDependency>>, // url -> its dependencies
+    pending_urls: BTreeSet<String>,
+}
+
+impl DependencyResolution {
+    pub fn new(
+        plugin_url: String,
+        config: BTreeMap<String, String>,
+        load_in_background: bool,
+    ) -> Self {
+        let mut dependency_resolution = DependencyResolution {
+            plugin_url: plugin_url.clone(),
+            config,
+            load_in_background,
+            dependencies: BTreeMap::new(),
+            pending_urls: BTreeSet::new(),
+        };
+        dependency_resolution.request_dependencies(plugin_url);
+        dependency_resolution
+    }
+    fn request_dependencies(&mut self, url: String) {
+        if !self.dependencies.contains_key(&url) && !self.pending_urls.contains(&url) {
+            get_plugin_dependencies(&url);
+            self.pending_urls.insert(url);
+        }
+    }
+    // returns true if these are the dependencies of a plugin we asked about
+    pub fn handle_plugin_dependencies(&mut self, plugin_info: PluginInfo) -> bool {
+        if !self.pending_urls.remove(&plugin_info.location) {
+            return false;
+        }
+        for dependency in &plugin_info.dependencies {
+            self.request_dependencies(dependency.url.clone());
+        }
+        self.dependencies
+            .insert(plugin_info.location, plugin_info.dependencies);
+        true
+    }
+    pub fn is_resolved(&self) -> bool {
+        self.pending_urls.is_empty()
+    }
+    /// The dependencies that are not loaded yet, each one after its own dependencies
+    pub fn dependencies_to_install(
+        &self,
+        loaded_plugins: &BTreeMap<u32, PluginInfo>,
+    ) -> Result<Vec<PluginDependency>, String> {
+        let mut load_order = vec![];
+        let mut path = vec![];
+        self.visit(&self.plugin_url, &mut path, &mut load_order)?;
+        Ok(load_order
+            .into_iter()
+            .filter(|dependency| {
+                !loaded_plugins
+                    .values()
+                    .any(|plugin_info| plugin_info.location == dependency.url)
+            })
+            .collect())
+    }
+    // depth first, so that dependencies end up before the plugins depending on them
+    fn visit(
+        &self,
+        url: &str,
+        path: &mut Vec<String>,
+        load_order: &mut Vec<PluginDependency>,
+    ) -> Result<(), String> {
+        if let Some(cycle_start) = path.iter().position(|visited| visited == url) {
+            let mut cycle = path[cycle_start..].to_vec();
+            cycle.push(url.to_owned());
+            return Err(format!("Circular dependency: {}", cycle.join(" -> ")));
+        }
+        path.push(url.to_owned());
+        for dependency in self.dependencies.get(url).into_iter().flatten() {
+            self.visit(&dependency.url, path, load_order)?;
+            if !load_order.iter().any(|d| d.url == dependency.url) {
+                load_order.push(dependency.clone());
+            }
+        }
+        path.pop();
+        Ok(())
+    }
+    pub fn load(&self, dependencies_to_install: &[PluginDependency]) {
+        let skip_plugin_cache = true;
+        for dependency in dependencies_to_install {
+            let load_in_background = true;
+            load_new_plugin(
+                &dependency.url,
+                BTreeMap::new(),
+                load_in_background,
+                skip_plugin_cache,
+            );
+        }
+        load_new_plugin(
+            &self.plugin_url,
+            self.config.clone(),
+            self.load_in_background,
+            skip_plugin_cache,
+        );
+    }
+    pub fn render(&self, rows: usize, cols: usize, loaded_plugins: &BTreeMap<u32, PluginInfo>) {
+        let title_text = "INSTALL DEPENDENCIES";
+        let title = Text::new(title_text);
+        print_text_with_coordinates(
+            title,
+            (cols / 2).saturating_sub(title_text.chars().count() / 2),
+            0,
+            None,
+            None,
+        );
+        if !self.is_resolved() {
+            let text = format!("Resolving dependencies of {}...", self.plugin_url);
+            print_text_with_coordinates(Text::new(text), 0, 2, Some(cols), None);
+            return;
+        }
+        match self.dependencies_to_install(loaded_plugins) {
+            Ok(dependencies) => {
+                let text = format!(
+                    "{} needs these plugins to be loaded first:",
+                    self.plugin_url
+                );
+                print_text_with_coordinates(Text::new(text), 0, 2, Some(cols), None);
+                let items = dependencies
+                    .iter()
+                    .map(|dependency| NestedListItem::new(&dependency.url).color_range(0, ..))
+                    .collect();
+                print_nested_list_with_coordinates(items, 0, 4, Some(cols), None);
+                let help = Text::new("Help: <ENTER> - Install and Load Plugin, <ESC> - Cancel")
+                    .color_range(3, 6..=12)
+                    .color_range(3, 41..=45);
+                print_text_with_coordinates(help, 0, rows, None, None);
+            },
+            Err(e) => {
+                print_text_with_coordinates(
+                    Text::new(format!("Cannot load {}", self.plugin_url)).color_range(3, ..),
+                    0,
+                    2,
+                    Some(cols),
+                    None,
+                );
+                print_text_with_coordinates(Text::new(e), 0, 3, Some(cols), None);
+                let help = Text::new("Help: <ESC> - Cancel").color_range(3, 6..=10);
+                print_text_with_coordinates(help, 0, rows, None, None);
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(plugin_url: &str, graph: &[(&str, &[&str])]) -> DependencyResolution {
+        DependencyResolution {
+            plugin_url: plugin_url.to_owned(),
+            config: BTreeMap::new(),
+            load_in_background: false,
+            dependencies: graph
+                .iter()
+                .map(|(url, dependencies)| {
+                    (
+                        url.to_string(),
+                        dependencies
+                            .iter()
+                            .map(|url| PluginDependency {
+                                url: url.to_string(),
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+            pending_urls: BTreeSet::new(),
+        }
+    }
+
+    fn urls(dependencies: Vec<PluginDependency>) -> Vec<String> {
+        dependencies.into_iter().map(|d| d.url).collect()
+    }
+
+    #[test]
+    fn dependencies_are_installed_before_the_plugins_depending_on_them() {
+        let dependency_resolution = resolved(
+            "plugin",
+            &[
+                ("plugin", &["a", "b"]),
+                ("a", &["c"]),
+                ("b", &[]),
+                ("c", &[]),
+            ],
+        );
+        assert_eq!(
+            urls(
+                dependency_resolution
+                    .dependencies_to_install(&BTreeMap::new())
+                    .unwrap()
+            ),
+            vec!["c", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn shared_dependencies_are_installed_once() {
+        let dependency_resolution = resolved(
+            "plugin",
+            &[
+                ("plugin", &["a", "b"]),
+                ("a", &["shared"]),
+                ("b", &["shared"]),
+                ("shared", &[]),
+            ],
+        );
+        assert_eq!(
+            urls(
+                dependency_resolution
+                    .dependencies_to_install(&BTreeMap::new())
+                    .unwrap()
+            ),
+            vec!["shared", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn loaded_dependencies_are_not_installed_again() {
+        let dependency_resolution =
+            resolved("plugin", &[("plugin", &["a", "b"]), ("a", &[]), ("b", &[])]);
+        let mut loaded_plugins = BTreeMap::new();
+        loaded_plugins.insert(
+            1,
+            PluginInfo {
+                location: "a".to_owned(),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            urls(
+                dependency_resolution
+                    .dependencies_to_install(&loaded_plugins)
+                    .unwrap()
+            ),
+            vec!["b"]
+        );
+    }
+
+    #[test]
+    fn circular_dependencies_are_refused() {
+        let dependency_resolution = resolved(
+            "plugin",
+            &[("plugin", &["a"]), ("a", &["b"]), ("b", &["a"])],
+        );
+        assert_eq!(
+            dependency_resolution.dependencies_to_install(&BTreeMap::new()),
+            Err("Circular dependency: a -> b -> a".to_owned())
+        );
+    }
+
+    #[test]
+    fn plugins_depending_on_themselves_are_refused() {
+        let dependency_resolution = resolved("plugin", &[("plugin", &["plugin"])]);
+        assert_eq!(
+            dependency_resolution.dependencies_to_install(&BTreeMap::new()),
+            Err("Circular dependency: plugin -> plugin".to_owned())
+        );
+    }
+}
@@ -1,7 +1,11 @@
+mod dependency_resolution;
+
+use dependency_resolution::DependencyResolution;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use uuid::Uuid;
 use zellij_tile::prelude::*;
+use zellij_tile_utils::{grapheme_count, safe_truncate_start};
 
 use std::collections::{BTreeMap, HashMap};
 
@@ -34,6 +38,7 @@ pub struct NewPluginScreen {
     selected_config_index: Option<usize>,
     request_ids: Vec<String>,
     load_in_background: bool,
+    dependency_resolution: Option<DependencyResolution>,
     colors: Palette,
 }
 
@@ -50,6 +55,7 @@ impl Default for NewPluginScreen {
             selected_config_index: None,
             request_ids: vec![],
             load_in_background: false,
+            dependency_resolution: None,
             colors: Palette::default(),
         }
     }
@@ -63,7 +69,11 @@ impl NewPluginScreen {
         }
     }
 
-    pub fn render(&self, rows: usize, cols: usize) {
+    pub fn render(&self, rows: usize, cols: usize, loaded_plugins: &BTreeMap<u32, PluginInfo>) {
+        if let Some(dependency_resolution) = &self.dependency_resolution {
+            dependency_resolution.render(rows, cols, loaded_plugins);
+            return;
+        }
         self.render_title(cols);
         self.render_url_field(cols);
         self.render_configuration_title();
@@ -275,32 +285,12 @@ impl NewPluginScreen {
             key_shortcuts_text.chars().count() + 1,
             bg_color
         );
-        let load_in_background_text = format!("Load in Background");
-        let load_in_foreground_text = format!("Load in Foreground");
-        let (load_in_background_ribbon, load_in_foreground_ribbon) = if self.load_in_background {
-            (
-                Text::new(&load_in_background_text).selected(),
-                Text::new(&load_in_foreground_text),
-            )
-        } else {
-            (
-                Text::new(&load_in_background_text),
-                Text::new(&load_in_foreground_text).selected(),
-            )
-        };
-        print_ribbon_with_coordinates(
-            load_in_background_ribbon,
+        let selected_index = if self.load_in_background { 0 } else { 1 };
+        print_ribbon_group(
+            vec![("Load in Background", true), ("Load in Foreground", true)],
+            selected_index,
             key_shortcuts_text.chars().count() + 1,
             y_coordinates,
-            None,
-            None,
-        );
-        print_ribbon_with_coordinates(
-            load_in_foreground_ribbon,
-            key_shortcuts_text.chars().count() + 1 + load_in_background_text.chars().count() + 4,
-            y_coordinates,
-            None,
-            None,
         );
     }
     fn render_help(&self, rows: usize) {
@@ -349,7 +339,60 @@ impl NewPluginScreen {
             ));
         }
     }
-    pub fn handle_key(&mut self, key: KeyWithModifier) -> (bool, bool) {
+    // returns (should_render, should_close)
+    pub fn handle_plugin_dependencies(
+        &mut self,
+        plugin_info: PluginInfo,
+        loaded_plugins: &BTreeMap<u32, PluginInfo>,
+    ) -> (bool, bool) {
+        let Some(dependency_resolution) = self.dependency_resolution.as_mut() else {
+            return (false, false);
+        };
+        if !dependency_resolution.handle_plugin_dependencies(plugin_info) {
+            return (false, false);
+        }
+        if dependency_resolution.is_resolved() {
+            if let Ok(dependencies) = dependency_resolution.dependencies_to_install(loaded_plugins)
+            {
+                if dependencies.is_empty() {
+                    // nothing to confirm
+                    dependency_resolution.load(&dependencies);
+                    return (true, true);
+                }
+            }
+        }
+        (true, false)
+    }
+    fn handle_dependency_resolution_key(
+        &mut self,
+        key: KeyWithModifier,
+        loaded_plugins: &BTreeMap<u32, PluginInfo>,
+    ) -> (bool, bool) {
+        let Some(dependency_resolution) = self.dependency_resolution.as_ref() else {
+            return (false, false);
+        };
+        match key.bare_key {
+            BareKey::Enter if key.has_no_modifiers() && dependency_resolution.is_resolved() => {
+                match dependency_resolution.dependencies_to_install(loaded_plugins) {
+                    Ok(dependencies) => {
+                        dependency_resolution.load(&dependencies);
+                        (true, true)
+                    },
+                    Err(_) => (false, false),
+                }
+            },
+            BareKey::Esc if key.has_no_modifiers() => (true, true),
+            _ => (false, false),
+        }
+    }
+    pub fn handle_key(
+        &mut self,
+        key: KeyWithModifier,
+        loaded_plugins: &BTreeMap<u32, PluginInfo>,
+    ) -> (bool, bool) {
+        if self.dependency_resolution.is_some() {
+            return self.handle_dependency_resolution_key(key, loaded_plugins);
+        }
         let (mut should_render, mut should_close) = (false, false);
 
         match key.bare_key {
@@ -377,10 +420,13 @@ impl NewPluginScreen {
                     self.add_edit_buffer_to_config();
                     let config = self.new_plugin_config.drain(..).into_iter().collect();
                     let load_in_background = self.load_in_background;
-                    let skip_plugin_cache = true;
-                    load_new_plugin(plugin_url, config, load_in_background, skip_plugin_cache);
+                    // the plugin is loaded once we know which of its dependencies to load first
+                    self.dependency_resolution = Some(DependencyResolution::new(
+                        plugin_url,
+                        config,
+                        load_in_background,
+                    ));
                     should_render = true;
-                    should_close = true;
                 }
             },
             BareKey::Tab if key.has_no_modifiers() => {
@@ -510,9 +556,11 @@ impl ZellijPlugin for State {
             EventType::TabUpdate,
             EventType::Key,
             EventType::SessionUpdate,
+            EventType::PluginDependencies,
         ]);
-        let own_plugin_id = get_plugin_ids().plugin_id;
-        rename_plugin_pane(own_plugin_id, "Plugin Manager");
+        if let Ok(plugin_ids) = get_plugin_ids() {
+            rename_plugin_pane(plugin_ids.plugin_id, "Plugin Manager");
+        }
     }
     fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
         if pipe_message.name == "filepicker_result" {
@@ -580,7 +628,7 @@ impl ZellijPlugin for State {
             Event::Key(key) => match self.new_plugin_screen.as_mut() {
                 Some(new_plugin_screen) => {
                     let (should_render_new_plugin_screen, should_close_new_plugin_screen) =
-                        new_plugin_screen.handle_key(key);
+                        new_plugin_screen.handle_key(key, &self.plugins);
                     if should_close_new_plugin_screen {
                         self.new_plugin_screen = None;
                         should_render = true;
@@ -590,6 +638,16 @@ impl ZellijPlugin for State {
                 },
                 None => should_render = self.handle_main_screen_key(key),
             },
+            Event::PluginDependencies(plugin_info) => {
+                if let Some(new_plugin_screen) = self.new_plugin_screen.as_mut() {
+                    let (should_render_new_plugin_screen, should_close_new_plugin_screen) =
+                        new_plugin_screen.handle_plugin_dependencies(plugin_info, &self.plugins);
+                    if should_close_new_plugin_screen {
+                        self.new_plugin_screen = None;
+                    }
+                    should_render = should_render_new_plugin_screen;
+                }
+            },
             _ => (),
         };
         should_render
@@ -598,7 +656,7 @@ impl ZellijPlugin for State {
     fn render(&mut self, rows: usize, cols: usize) {
         match &self.new_plugin_screen {
             Some(new_plugin_screen) => {
-                new_plugin_screen.render(rows, cols);
+                new_plugin_screen.render(rows, cols, &self.plugins);
             },
             None => {
                 self.render_search(cols);
@@ -1073,12 +1131,14 @@ impl State {
 }
 
 fn truncate_string_start(string_to_truncate: &str, max_len: usize) -> String {
-    let mut truncated_string = string_to_truncate.to_owned();
-    let count_to_remove = truncated_string.chars().count().saturating_sub(max_len) + 5;
-    if truncated_string.chars().count() > max_len {
-        truncated_string.replace_range(0..count_to_remove, "[...]");
+    if grapheme_count(string_to_truncate) > max_len {
+        format!(
+            "[...]{}",
+            safe_truncate_start(string_to_truncate, max_len.saturating_sub(5))
+        )
+    } else {
+        string_to_truncate.to_owned()
     }
-    truncated_string
 }
 
 fn truncate_search_result(
@@ -1090,7 +1150,7 @@ fn truncate_search_result(
     let truncated_count = plugin_location
         .chars()
         .count()
-        .saturating_sub(max_location_len);
+        .saturating_sub(truncated_location.chars().count());
     let adjusted_indices = indices
         .iter()
         .filter_map(|i| {
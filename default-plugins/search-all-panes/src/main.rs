@@ -0,0 +1,345 @@
+use zellij_tile::prelude::*;
+use zellij_tile_utils::{grapheme_count, safe_truncate, safe_truncate_start};
+
+use std::collections::BTreeMap;
+
+// how long to wait after the last keystroke before searching
+const SEARCH_DEBOUNCE_SECS: f64 = 0.3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SearchedPane {
+    title: String,
+    content_rows: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SearchResultEntry {
+    pane_id: PaneId,
+    search_match: SearchMatch,
+}
+
+#[derive(Default)]
+struct State {
+    panes: BTreeMap<PaneId, SearchedPane>,
+    query: String,
+    case_sensitive: bool,
+    regex: bool,
+    // the query the results below belong to, None if we did not search yet
+    searched_query: Option<String>,
+    results: BTreeMap<PaneId, Vec<SearchMatch>>,
+    pending_timers: usize,
+    selected: usize,
+    scroll_offset: usize,
+}
+
+register_plugin!(State);
+
+impl ZellijPlugin for State {
+    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+        request_permission(&[
+            PermissionType::ReadApplicationState,
+            PermissionType::ChangeApplicationState,
+        ]);
+        subscribe(&[
+            EventType::PaneUpdate,
+            EventType::SearchResult,
+            EventType::Timer,
+            EventType::Key,
+        ]);
+        if let Ok(plugin_ids) = get_plugin_ids() {
+            rename_plugin_pane(plugin_ids.plugin_id, "Search All Panes");
+        }
+    }
+    fn update(&mut self, event: Event) -> bool {
+        let mut should_render = false;
+        match event {
+            Event::PaneUpdate(pane_manifest) => {
+                self.update_panes(pane_manifest);
+                should_render = true;
+            },
+            Event::SearchResult {
+                pane_id,
+                query,
+                matches,
+            } => {
+                // results of previous queries might still be arriving, we only want the latest
+                if self.searched_query.as_ref() == Some(&query) && self.panes.contains_key(&pane_id)
+                {
+                    self.results.insert(pane_id, matches);
+                    should_render = true;
+                }
+            },
+            Event::Timer(_) => {
+                self.pending_timers = self.pending_timers.saturating_sub(1);
+                if self.pending_timers == 0 {
+                    self.search();
+                    should_render = true;
+                }
+            },
+            Event::Key(key) => {
+                should_render = self.handle_key(key);
+            },
+            _ => {},
+        }
+        should_render
+    }
+    fn render(&mut self, rows: usize, cols: usize) {
+        self.render_search(cols);
+        self.render_status(cols);
+        let list_y = 3;
+        let max_list_items = rows.saturating_sub(5); // 3 top rows, 2 bottom padding
+        let entries = self.entries();
+        if self.selected >= entries.len() {
+            self.selected = entries.len().saturating_sub(1);
+        }
+        if self.selected < self.scroll_offset {
+            self.scroll_offset = self.selected;
+        } else if max_list_items > 0 && self.selected >= self.scroll_offset + max_list_items {
+            self.scroll_offset = self.selected + 1 - max_list_items;
+        }
+        for (i, entry) in entries
+            .iter()
+            .enumerate()
+            .skip(self.scroll_offset)
+            .take(max_list_items)
+        {
+            let text = self.render_entry(entry, i == self.selected, cols);
+            print_text_with_coordinates(text, 0, list_y + i - self.scroll_offset, None, None);
+        }
+        self.render_help(rows);
+    }
+}
+
+impl State {
+    fn update_panes(&mut self, pane_manifest: PaneManifest) {
+        let mut panes = BTreeMap::new();
+        for pane in pane_manifest.panes.values().flatten() {
+            if pane.is_plugin {
+                continue;
+            }
+            panes.insert(
+                PaneId::Terminal(pane.id),
+                SearchedPane {
+                    title: pane.title.clone(),
+                    content_rows: pane.pane_content_rows,
+                },
+            );
+        }
+        let new_panes: Vec<PaneId> = panes
+            .keys()
+            .filter(|pane_id| !self.panes.contains_key(pane_id))
+            .copied()
+            .collect();
+        self.results
+            .retain(|pane_id, _| panes.contains_key(pane_id));
+        self.panes = panes;
+        for pane_id in new_panes {
+            self.search_pane(pane_id);
+        }
+    }
+    fn search(&mut self) {
+        self.results.clear();
+        self.selected = 0;
+        self.scroll_offset = 0;
+        if self.query.is_empty() {
+            self.searched_query = None;
+            return;
+        }
+        self.searched_query = Some(self.query.clone());
+        let pane_ids: Vec<PaneId> = self.panes.keys().copied().collect();
+        for pane_id in pane_ids {
+            self.search_pane(pane_id);
+        }
+    }
+    fn search_pane(&self, pane_id: PaneId) {
+        if let Some(query) = &self.searched_query {
+            if self.regex {
+                search_pane_with_regex(pane_id, query, self.case_sensitive);
+            } else {
+                search_pane(pane_id, query, self.case_sensitive);
+            }
+        }
+    }
+    fn search_after_timeout(&mut self) {
+        self.pending_timers += 1;
+        set_timeout(SEARCH_DEBOUNCE_SECS);
+    }
+    fn entries(&self) -> Vec<SearchResultEntry> {
+        let mut entries = vec![];
+        for (pane_id, matches) in &self.results {
+            let mut matches = matches.clone();
+            matches.sort_by_key(|search_match| (search_match.row, search_match.col_start));
+            for search_match in matches {
+                entries.push(SearchResultEntry {
+                    pane_id: *pane_id,
+                    search_match,
+                });
+            }
+        }
+        entries
+    }
+    fn jump_to_selected(&mut self) {
+        let Some(entry) = self.entries().into_iter().nth(self.selected) else {
+            return;
+        };
+        // leave the match in the middle of the pane rather than on its first line, so that what
+        // came before it is visible as well
+        let content_rows = self
+            .panes
+            .get(&entry.pane_id)
+            .map(|pane| pane.content_rows)
+            .unwrap_or(0);
+        let line = entry.search_match.row.saturating_sub(content_rows / 2);
+        hide_self();
+        focus_pane_with_id(entry.pane_id, true);
+        scroll_to_line_in_pane_id(entry.pane_id, line);
+    }
+    fn handle_key(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Enter if key.has_no_modifiers() => {
+                self.jump_to_selected();
+                false
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                if self.query.is_empty() {
+                    close_self();
+                } else {
+                    self.query.clear();
+                    self.search_after_timeout();
+                }
+                true
+            },
+            BareKey::Down if key.has_no_modifiers() => {
+                self.selected += 1;
+                true
+            },
+            BareKey::Up if key.has_no_modifiers() => {
+                self.selected = self.selected.saturating_sub(1);
+                true
+            },
+            BareKey::Char('r') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.regex = !self.regex;
+                self.search_after_timeout();
+                true
+            },
+            BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.case_sensitive = !self.case_sensitive;
+                self.search_after_timeout();
+                true
+            },
+            BareKey::Backspace if key.has_no_modifiers() => {
+                self.query.pop();
+                self.search_after_timeout();
+                true
+            },
+            BareKey::Char(character) if key.has_no_modifiers() => {
+                self.query.push(character);
+                self.search_after_timeout();
+                true
+            },
+            _ => false,
+        }
+    }
+    fn render_search(&self, cols: usize) {
+        let prompt = if self.regex { "REGEX: " } else { "SEARCH: " };
+        let prompt_len = prompt.chars().count();
+        let query = truncate_string_start(
+            &self.query,
+            cols.saturating_sub(prompt_len + 1), // 1 for the cursor
+        );
+        let text = Text::new(format!("{}{}_", prompt, query)).color_range(2, ..prompt_len);
+        print_text_with_coordinates(text, 0, 0, None, None);
+    }
+    fn render_status(&self, cols: usize) {
+        let case = if self.case_sensitive {
+            "case sensitive"
+        } else {
+            "case insensitive"
+        };
+        let status = match &self.searched_query {
+            Some(_) => {
+                let match_count: usize = self.results.values().map(|m| m.len()).sum();
+                let pane_count = self.results.values().filter(|m| !m.is_empty()).count();
+                let pending = self.panes.len().saturating_sub(self.results.len());
+                if pending > 0 {
+                    format!(
+                        "{} matches in {} panes, waiting for {} more panes ({})",
+                        match_count, pane_count, pending, case
+                    )
+                } else {
+                    format!("{} matches in {} panes ({})", match_count, pane_count, case)
+                }
+            },
+            None => format!("Searching {} panes ({})", self.panes.len(), case),
+        };
+        let status = truncate_string_end(&status, cols);
+        print_text_with_coordinates(Text::new(status).color_range(1, ..), 0, 1, None, None);
+    }
+    fn render_entry(&self, entry: &SearchResultEntry, is_selected: bool, cols: usize) -> Text {
+        let title = self
+            .panes
+            .get(&entry.pane_id)
+            .map(|pane| pane.title.as_str())
+            .unwrap_or("");
+        let pane_id = match entry.pane_id {
+            PaneId::Terminal(id) | PaneId::Plugin(id) => id,
+        };
+        let location = format!(
+            "#{} {}:{} ",
+            pane_id,
+            truncate_string_end(title, 20),
+            entry.search_match.row + 1
+        );
+        let location_len = location.chars().count();
+        let available_cols = cols.saturating_sub(location_len);
+        let context_line = &entry.search_match.context_line;
+        let context_chars: Vec<char> = context_line.chars().collect();
+        let col_start = std::cmp::min(entry.search_match.col_start, context_chars.len());
+        let col_end = std::cmp::min(entry.search_match.col_end, context_chars.len());
+        // make sure the match itself is visible even in long lines
+        let skipped = col_start.saturating_sub(available_cols / 3);
+        let visible_line: String = context_chars[skipped..].iter().collect();
+        let visible_line = safe_truncate(&visible_line, available_cols);
+        let match_start = location_len + col_start - skipped;
+        let match_end = std::cmp::min(
+            location_len + col_end - skipped,
+            location_len + visible_line.chars().count(),
+        );
+        let mut text = Text::new(format!("{}{}", location, visible_line))
+            .color_range(0, ..location_len)
+            .color_range(3, match_start..match_end);
+        if is_selected {
+            text = text.selected();
+        }
+        text
+    }
+    fn render_help(&self, rows: usize) {
+        let help_text =
+            "Help: <↓↑> - Navigate, <ENTER> - Jump to match, <Ctrl r> - Regex, <Ctrl c> - Case";
+        let text = Text::new(help_text)
+            .color_range(3, 6..=9)
+            .color_range(3, 23..=29)
+            .color_range(3, 48..=55)
+            .color_range(3, 66..=73);
+        print_text_with_coordinates(text, 0, rows, None, None);
+    }
+}
+
+fn truncate_string_start(string: &str, max_len: usize) -> String {
+    if grapheme_count(string) <= max_len {
+        string.to_owned()
+    } else {
+        format!(
+            "...{}",
+            safe_truncate_start(string, max_len.saturating_sub(3))
+        )
+    }
+}
+
+fn truncate_string_end(string: &str, max_len: usize) -> String {
+    if grapheme_count(string) <= max_len {
+        string.to_owned()
+    } else {
+        format!("{}...", safe_truncate(string, max_len.saturating_sub(3)))
+    }
+}
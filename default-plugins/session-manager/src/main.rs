@@ -10,7 +10,8 @@ use new_session_info::NewSessionInfo;
 use ui::{
     components::{
         render_controls_line, render_error, render_new_session_block, render_prompt,
-        render_renaming_session_screen, render_screen_toggle, Colors,
+        render_renaming_session_screen, render_screen_toggle, render_tagging_session_screen,
+        Colors,
     },
     welcome_screen::{render_banner, render_welcome_boundaries},
     SessionUiInfo,
@@ -40,6 +41,7 @@ struct State {
     search_term: String,
     new_session_info: NewSessionInfo,
     renaming_session_name: Option<String>,
+    tagging_session: Option<(String, String)>, // session name, tags being edited
     error: Option<String>,
     active_screen: ActiveScreen,
     colors: Colors,
@@ -152,6 +154,8 @@ impl ZellijPlugin for State {
             ActiveScreen::AttachToSession => {
                 if let Some(new_session_name) = self.renaming_session_name.as_ref() {
                     render_renaming_session_screen(&new_session_name, height, width, x, y + 2);
+                } else if let Some((session_name, tags)) = self.tagging_session.as_ref() {
+                    render_tagging_session_screen(session_name, tags, height, width, x, y + 2);
                 } else if self.show_kill_all_sessions_warning {
                     self.render_kill_all_sessions_warning(height, width, x, y);
                 } else {
@@ -317,6 +321,8 @@ impl State {
                         self.handle_selection();
                     } else if let Some(new_session_name) = self.renaming_session_name.as_mut() {
                         new_session_name.push(character);
+                    } else if let Some((_session_name, tags)) = self.tagging_session.as_mut() {
+                        tags.push(character);
                     } else {
                         self.search_term.push(character);
                         self.sessions
@@ -331,6 +337,8 @@ impl State {
                         } else {
                             new_session_name.pop();
                         }
+                    } else if let Some((_session_name, tags)) = self.tagging_session.as_mut() {
+                        tags.pop();
                     } else {
                         self.search_term.pop();
                         self.sessions
@@ -346,6 +354,15 @@ impl State {
                     self.renaming_session_name = Some(String::new());
                     should_render = true;
                 },
+                BareKey::Char('t') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    if let Some(selected_session_name) = self.sessions.get_selected_session_name() {
+                        let tags = self.sessions.get_session_tags(&selected_session_name);
+                        self.tagging_session = Some((selected_session_name, tags.join(", ")));
+                    } else {
+                        self.show_error("Must select session before tagging it.");
+                    }
+                    should_render = true;
+                },
                 BareKey::Delete if key.has_no_modifiers() => {
                     if let Some(selected_session_name) = self.sessions.get_selected_session_name() {
                         kill_sessions(&[selected_session_name]);
@@ -390,6 +407,9 @@ impl State {
                     if self.renaming_session_name.is_some() {
                         self.renaming_session_name = None;
                         should_render = true;
+                    } else if self.tagging_session.is_some() {
+                        self.tagging_session = None;
+                        should_render = true;
                     } else if !self.is_welcome_screen {
                         hide_self();
                     }
@@ -469,6 +489,14 @@ impl State {
                 self.new_session_info.handle_selection(&self.session_name);
             },
             ActiveScreen::AttachToSession => {
+                if let Some((session_name, tags)) = self.tagging_session.take() {
+                    let tags: Vec<String> = tags.split(',').map(|t| t.to_owned()).collect();
+                    let tags = SessionInfo::normalize_tags(tags);
+                    self.sessions
+                        .update_session_tags(&session_name, tags.clone());
+                    set_session_tags(&session_name, tags);
+                    return; // so that we don't hide self
+                }
                 if let Some(renaming_session_name) = &self.renaming_session_name.take() {
                     if renaming_session_name.is_empty() {
                         self.show_error("New name must not be empty.");
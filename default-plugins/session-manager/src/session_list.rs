@@ -23,7 +23,10 @@ impl SessionList {
             } else if b.is_current_session {
                 std::cmp::Ordering::Greater
             } else {
-                a.name.cmp(&b.name)
+                // sessions nobody is attached to go after the live ones
+                a.is_zombie
+                    .cmp(&b.is_zombie)
+                    .then_with(|| a.name.cmp(&b.name))
             }
         });
         self.session_ui_infos = session_ui_infos;
@@ -317,6 +320,22 @@ impl SessionList {
     pub fn has_session(&self, session_name: &str) -> bool {
         self.session_ui_infos.iter().any(|s| s.name == session_name)
     }
+    pub fn get_session_tags(&self, session_name: &str) -> Vec<String> {
+        self.session_ui_infos
+            .iter()
+            .find(|s| s.name == session_name)
+            .map(|s| s.tags.clone())
+            .unwrap_or_default()
+    }
+    pub fn update_session_tags(&mut self, session_name: &str, tags: Vec<String>) {
+        if let Some(session_ui_info) = self
+            .session_ui_infos
+            .iter_mut()
+            .find(|s| s.name == session_name)
+        {
+            session_ui_info.tags = tags;
+        }
+    }
     pub fn update_session_name(&mut self, old_name: &str, new_name: &str) {
         self.session_ui_infos
             .iter_mut()
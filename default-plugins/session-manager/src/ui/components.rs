@@ -2,6 +2,7 @@ use std::path::PathBuf;
 use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::*;
+use zellij_tile_utils::safe_truncate;
 
 use crate::ui::{PaneUiInfo, SessionUiInfo, TabUiInfo};
 use crate::{ActiveScreen, NewSessionInfo};
@@ -407,6 +408,20 @@ pub fn build_session_ui_line(session_ui_info: &SessionUiInfo, colors: Colors) ->
     ui_spans.push(session_name_span);
     ui_spans.push(tab_and_pane_count);
     ui_spans.push(connected_users_count);
+    if !session_ui_info.tags.is_empty() {
+        let tags = session_ui_info.tags.join(", ");
+        let tags_styled = colors.magenta(&tags);
+        let tag_count = format!("{}", session_ui_info.tags.len());
+        let tag_count_styled = colors.magenta(&tag_count);
+        let tags_indication = UiSpan::UiSpanTelescope(UiSpanTelescope::new(vec![
+            StringAndLength::new(format!(" [{tags_styled}]"), 3 + tags.width()),
+            StringAndLength::new(
+                format!(" [{tag_count_styled} tags]"),
+                3 + tag_count.width() + 5,
+            ),
+        ]));
+        ui_spans.push(tags_indication);
+    }
     if session_ui_info.is_current_session {
         let current_session_indication = UiSpan::UiSpanTelescope(UiSpanTelescope::new(vec![
             StringAndLength::new(colors.orange(&format!(" <CURRENT SESSION>")), 18),
@@ -414,6 +429,13 @@ pub fn build_session_ui_line(session_ui_info: &SessionUiInfo, colors: Colors) ->
             StringAndLength::new(colors.orange(&format!(" <C>")), 4),
         ]));
         ui_spans.push(current_session_indication);
+    } else if session_ui_info.is_zombie {
+        let zombie_session_indication = UiSpan::UiSpanTelescope(UiSpanTelescope::new(vec![
+            StringAndLength::new(colors.red(&format!(" <NO CLIENTS ATTACHED>")), 22),
+            StringAndLength::new(colors.red(&format!(" <DETACHED>")), 11),
+            StringAndLength::new(colors.red(&format!(" <D>")), 4),
+        ]));
+        ui_spans.push(zombie_session_indication);
     }
     ui_spans
 }
@@ -903,6 +925,30 @@ pub fn render_renaming_session_screen(
     }
 }
 
+pub fn render_tagging_session_screen(
+    session_name: &str,
+    tags: &str,
+    rows: usize,
+    columns: usize,
+    x: usize,
+    y: usize,
+) {
+    if rows == 0 || columns == 0 {
+        return;
+    }
+    let prompt = format!("Tags for session {}:", session_name);
+    let prompt_len = prompt.width();
+    let text = Text::new(format!("{} {}_ (<ENTER> when done)", prompt, tags))
+        .color_range(2, ..prompt_len)
+        .color_range(
+            3,
+            prompt_len + 3 + tags.width()..prompt_len + 10 + tags.width(),
+        );
+    print_text_with_coordinates(text, x, y, None, None);
+    let help_text = "Separate tags with commas, leave empty to remove all tags.";
+    print_text_with_coordinates(Text::new(help_text), x, y + 2, None, None);
+}
+
 pub fn render_controls_line(
     active_screen: ActiveScreen,
     max_cols: usize,
@@ -921,6 +967,8 @@ pub fn render_controls_line(
         ActiveScreen::AttachToSession => {
             let rename = colors.magenta("<Ctrl r>");
             let rename_text = colors.bold("Rename");
+            let tag = colors.magenta("<Ctrl t>");
+            let tag_text = colors.bold("Tags");
             let disconnect = colors.magenta("<Ctrl x>");
             let disconnect_text = colors.bold("Disconnect others");
             let kill = colors.magenta("<Del>");
@@ -928,12 +976,12 @@ pub fn render_controls_line(
             let kill_all = colors.magenta("<Ctrl d>");
             let kill_all_text = colors.bold("Kill all");
 
-            if max_cols > 90 {
+            if max_cols > 106 {
                 print!(
-                    "\u{1b}[m\u{1b}[{y};{x}HHelp: {rename} - {rename_text}, {disconnect} - {disconnect_text}, {kill} - {kill_text}, {kill_all} - {kill_all_text}"
+                    "\u{1b}[m\u{1b}[{y};{x}HHelp: {rename} - {rename_text}, {tag} - {tag_text}, {disconnect} - {disconnect_text}, {kill} - {kill_text}, {kill_all} - {kill_all_text}"
                 );
-            } else if max_cols >= 28 {
-                print!("\u{1b}[m\u{1b}[{y};{x}H{rename}/{disconnect}/{kill}/{kill_all}");
+            } else if max_cols >= 37 {
+                print!("\u{1b}[m\u{1b}[{y};{x}H{rename}/{tag}/{disconnect}/{kill}/{kill_all}");
             }
         },
         ActiveScreen::ResurrectSession => {
@@ -1004,9 +1052,11 @@ fn truncate_path(path: PathBuf, mut char_count_to_remove: usize) -> String {
     let mut truncated = String::new();
     let component_count = path.iter().count();
     for (i, component) in path.iter().enumerate() {
-        let mut component_str = component.to_string_lossy().to_string();
+        let component_str = component.to_string_lossy().to_string();
         if char_count_to_remove > 0 {
-            truncated.push(component_str.remove(0));
+            let first_grapheme = safe_truncate(&component_str, 1);
+            truncated.push_str(first_grapheme);
+            let component_str = &component_str[first_grapheme.len()..];
             if i != 0 && i + 1 != component_count {
                 truncated.push('/');
             }
@@ -227,6 +227,8 @@ pub struct SessionUiInfo {
     pub tabs: Vec<TabUiInfo>,
     pub connected_users: usize,
     pub is_current_session: bool,
+    pub is_zombie: bool, // the session's server is running but no client is attached to it
+    pub tags: Vec<String>,
 }
 
 impl SessionUiInfo {
@@ -240,6 +242,8 @@ impl SessionUiInfo {
                 .collect(),
             connected_users: session_info.connected_clients,
             is_current_session: session_info.is_current_session,
+            is_zombie: session_info.connected_clients == 0 && !session_info.is_current_session,
+            tags: session_info.tags.clone(),
         }
     }
     pub fn line_count(&self, selected_index: &SelectedIndex) -> usize {
@@ -19,8 +19,8 @@ use first_line::first_line;
 use one_line_ui::one_line_ui;
 use second_line::{
     floating_panes_are_visible, fullscreen_panes_to_hide, keybinds,
-    locked_floating_panes_are_visible, locked_fullscreen_panes_to_hide, system_clipboard_error,
-    text_copied_hint,
+    locked_floating_panes_are_visible, locked_fullscreen_panes_to_hide, pane_count_exceeded_error,
+    system_clipboard_error, text_copied_hint,
 };
 use tip::utils::get_cached_tip_name;
 
@@ -37,6 +37,7 @@ struct State {
     mode_info: ModeInfo,
     text_copy_destination: Option<CopyDestination>,
     display_system_clipboard_failure: bool,
+    display_pane_count_exceeded: bool,
     classic_ui: bool,
     base_mode_is_locked: bool,
 }
@@ -195,6 +196,7 @@ impl ZellijPlugin for State {
             EventType::CopyToClipboard,
             EventType::InputReceived,
             EventType::SystemClipboardFailure,
+            EventType::PaneCountExceeded,
         ]);
     }
 
@@ -231,14 +233,20 @@ impl ZellijPlugin for State {
                 should_render = true;
                 self.display_system_clipboard_failure = true;
             },
+            Event::PaneCountExceeded => {
+                should_render = true;
+                self.display_pane_count_exceeded = true;
+            },
             Event::InputReceived => {
                 if self.text_copy_destination.is_some()
                     || self.display_system_clipboard_failure == true
+                    || self.display_pane_count_exceeded
                 {
                     should_render = true;
                 }
                 self.text_copy_destination = None;
                 self.display_system_clipboard_failure = false;
+                self.display_pane_count_exceeded = false;
             },
             _ => {},
         };
@@ -274,6 +282,7 @@ impl ZellijPlugin for State {
                     self.base_mode_is_locked,
                     self.text_copy_destination,
                     self.display_system_clipboard_failure,
+                    self.display_pane_count_exceeded,
                 ),
                 fill_bg,
             );
@@ -325,6 +334,8 @@ impl State {
             text_copied_hint(&self.mode_info.style.colors, copy_destination)
         } else if self.display_system_clipboard_failure {
             system_clipboard_error(&self.mode_info.style.colors)
+        } else if self.display_pane_count_exceeded {
+            pane_count_exceeded_error(&self.mode_info.style.colors)
         } else if let Some(active_tab) = active_tab {
             if active_tab.is_fullscreen_active {
                 match self.mode_info.mode {
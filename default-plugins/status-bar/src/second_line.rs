@@ -379,6 +379,15 @@ pub fn system_clipboard_error(palette: &Palette) -> LinePart {
     }
 }
 
+pub fn pane_count_exceeded_error(palette: &Palette) -> LinePart {
+    let hint = " Maximum pane count reached";
+    let red_color = palette_match!(palette.red);
+    LinePart {
+        part: Style::new().fg(red_color).bold().paint(hint).to_string(),
+        len: hint.len(),
+    }
+}
+
 pub fn fullscreen_panes_to_hide(palette: &Palette, panes_to_hide: usize) -> LinePart {
     let text_color = palette_match!(match palette.theme_hue {
         ThemeHue::Dark => palette.white,
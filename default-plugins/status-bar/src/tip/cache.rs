@@ -42,7 +42,7 @@ impl LocalCache {
             Err(err) => {
                 if json_cache.is_empty() {
                     return Ok(Metadata {
-                        zellij_version: get_zellij_version(),
+                        zellij_version: get_zellij_version().unwrap_or_default(),
                         cached_data: HashMap::new(),
                     });
                 }
@@ -43,7 +43,7 @@ pub fn get_cached_tip_name() -> String {
         Ok(cache) => cache,
     };
 
-    let zellij_version = get_zellij_version();
+    let zellij_version = get_zellij_version().unwrap_or_default();
     if zellij_version.ne(local_cache.get_version()) {
         local_cache.set_version(zellij_version);
         local_cache.clear().unwrap();
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use zellij_tile::prelude::*;
+
+pub const FILE_OPERATION_WORKER_NAME: &str = "file_operation";
+pub const START_OPERATION: &str = "start_operation";
+pub const CONTINUE_OPERATION: &str = "continue_operation";
+pub const CANCEL_OPERATION: &str = "cancel_operation";
+pub const OPERATION_PROGRESS: &str = "operation_progress";
+
+// how much to copy before reporting progress to the plugin, this is also how often the worker
+// gets a chance to notice the operation was cancelled
+const BYTES_PER_CHUNK: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileOperationKind {
+    Copy,
+    Move,
+}
+
+impl FileOperationKind {
+    pub fn description(&self) -> &'static str {
+        match self {
+            FileOperationKind::Copy => "Copying",
+            FileOperationKind::Move => "Moving",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileOperationRequest {
+    pub id: usize,
+    pub kind: FileOperationKind,
+    pub source: PathBuf,
+    pub destination_folder: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileOperationProgress {
+    pub id: usize,
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+    pub finished: bool,
+    pub error: Option<String>,
+}
+
+impl FileOperationProgress {
+    pub fn percent(&self) -> f64 {
+        if self.total_bytes == 0 {
+            f64::NAN
+        } else {
+            self.done_bytes as f64 / self.total_bytes as f64 * 100.0
+        }
+    }
+}
+
+/// Copies and moves files in the background, a chunk at a time so that the plugin can render
+/// the progress and cancel the operation between chunks
+#[derive(Default, Serialize, Deserialize)]
+pub struct FileOperationWorker {
+    request: Option<FileOperationRequest>,
+    // (source, destination) of the files left to copy, last one first
+    pending_files: Vec<(PathBuf, PathBuf)>,
+    done_bytes: u64,
+    total_bytes: u64,
+}
+
+impl ZellijWorker<'_> for FileOperationWorker {
+    fn on_message(&mut self, message: String, payload: String) {
+        match message.as_str() {
+            START_OPERATION => match serde_json::from_str::<FileOperationRequest>(&payload) {
+                Ok(request) => {
+                    let id = request.id;
+                    if let Err(e) = self.start(request) {
+                        self.fail(id, e);
+                    } else {
+                        self.copy_next_chunk();
+                    }
+                },
+                Err(e) => eprintln!("Failed to parse file operation request: {}", e),
+            },
+            CONTINUE_OPERATION => self.copy_next_chunk(),
+            CANCEL_OPERATION => self.clear(),
+            _ => {},
+        }
+    }
+}
+
+impl FileOperationWorker {
+    fn start(&mut self, request: FileOperationRequest) -> Result<(), String> {
+        self.clear();
+        let file_name = request
+            .source
+            .file_name()
+            .ok_or_else(|| format!("Cannot copy {}", request.source.display()))?;
+        let destination = request.destination_folder.join(file_name);
+        if destination.exists() {
+            return Err(format!("{} already exists", destination.display()));
+        }
+        if destination.starts_with(&request.source) {
+            return Err(format!(
+                "Cannot copy {} into itself",
+                request.source.display()
+            ));
+        }
+        // moving within the same file system does not need to copy anything
+        if request.kind == FileOperationKind::Move
+            && fs::rename(&request.source, &destination).is_ok()
+        {
+            self.request = Some(request);
+            return Ok(());
+        }
+        self.plan(&request.source, &destination)?;
+        self.pending_files.reverse();
+        self.request = Some(request);
+        Ok(())
+    }
+    // creates the folders at the destination and queues the files to copy into them
+    fn plan(&mut self, source: &Path, destination: &Path) -> Result<(), String> {
+        let metadata = fs::symlink_metadata(source).map_err(|e| error(source, e))?;
+        if metadata.is_dir() {
+            fs::create_dir(destination).map_err(|e| error(destination, e))?;
+            for entry in fs::read_dir(source).map_err(|e| error(source, e))? {
+                let entry = entry.map_err(|e| error(source, e))?;
+                self.plan(&entry.path(), &destination.join(entry.file_name()))?;
+            }
+        } else {
+            self.total_bytes += metadata.len();
+            self.pending_files
+                .push((source.to_path_buf(), destination.to_path_buf()));
+        }
+        Ok(())
+    }
+    fn copy_next_chunk(&mut self) {
+        let Some(id) = self.request.as_ref().map(|r| r.id) else {
+            // the operation was cancelled
+            return;
+        };
+        let mut copied_bytes = 0;
+        while copied_bytes < BYTES_PER_CHUNK {
+            let Some((source, destination)) = self.pending_files.pop() else {
+                break;
+            };
+            match fs::copy(&source, &destination) {
+                Ok(bytes) => copied_bytes += bytes,
+                Err(e) => return self.fail(id, error(&source, e)),
+            }
+        }
+        self.done_bytes += copied_bytes;
+        let finished = self.pending_files.is_empty();
+        if finished {
+            if let Err(e) = self.remove_moved_source() {
+                return self.fail(id, e);
+            }
+        }
+        self.report(FileOperationProgress {
+            id,
+            done_bytes: self.done_bytes,
+            total_bytes: self.total_bytes,
+            finished,
+            error: None,
+        });
+        if finished {
+            self.clear();
+        }
+    }
+    fn remove_moved_source(&self) -> Result<(), String> {
+        match &self.request {
+            Some(request) if request.kind == FileOperationKind::Move && request.source.exists() => {
+                if request.source.is_dir() {
+                    fs::remove_dir_all(&request.source)
+                } else {
+                    fs::remove_file(&request.source)
+                }
+                .map_err(|e| error(&request.source, e))
+            },
+            _ => Ok(()),
+        }
+    }
+    fn fail(&mut self, id: usize, error: String) {
+        self.report(FileOperationProgress {
+            id,
+            finished: true,
+            error: Some(error),
+            ..Default::default()
+        });
+        self.clear();
+    }
+    fn report(&self, progress: FileOperationProgress) {
+        if let Ok(payload) = serde_json::to_string(&progress) {
+            post_message_to_plugin(PluginMessage::new_to_plugin(OPERATION_PROGRESS, &payload));
+        }
+    }
+    fn clear(&mut self) {
+        self.request = None;
+        self.pending_files.clear();
+        self.done_bytes = 0;
+        self.total_bytes = 0;
+    }
+}
+
+fn error(path: &Path, e: std::io::Error) -> String {
+    format!("{}: {}", path.display(), e)
+}
@@ -1,21 +1,32 @@
 mod file_list_view;
+mod file_operation;
 mod search_view;
 mod shared;
 mod state;
 
 use crate::file_list_view::FsEntry;
-use shared::{render_current_path, render_instruction_line, render_search_term};
+use crate::file_operation::{FileOperationKind, FileOperationWorker, OPERATION_PROGRESS};
+use shared::{
+    render_alert, render_current_path, render_file_operation_progress, render_instruction_line,
+    render_marked_entry, render_search_term,
+};
 use state::{refresh_directory, State};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 use zellij_tile::prelude::*;
 
 register_plugin!(State);
+register_worker!(
+    FileOperationWorker,
+    file_operation_worker,
+    FILE_OPERATION_WORKER
+);
 
 impl ZellijPlugin for State {
     fn load(&mut self, configuration: BTreeMap<String, String>) {
-        let plugin_ids = get_plugin_ids();
-        self.initial_cwd = plugin_ids.initial_cwd;
+        if let Ok(plugin_ids) = get_plugin_ids() {
+            self.initial_cwd = plugin_ids.initial_cwd;
+        }
         let show_hidden_files = configuration
             .get("show_hidden_files")
             .map(|v| v == "true")
@@ -63,7 +74,32 @@ impl ZellijPlugin for State {
                 self.update_files(paths);
                 should_render = true;
             },
+            Event::CustomMessage(message, payload) if message == OPERATION_PROGRESS => {
+                self.update_file_operation_progress(&payload);
+                should_render = true;
+            },
+            Event::Key(_) if self.alert.is_some() => {
+                // any key dismisses the alert
+                self.alert = None;
+                should_render = true;
+            },
             Event::Key(key) => match key.bare_key {
+                BareKey::Esc if key.has_no_modifiers() && self.file_operation.is_some() => {
+                    self.cancel_file_operation();
+                    should_render = true;
+                },
+                BareKey::Char('y') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.mark_selected_entry(FileOperationKind::Copy);
+                    should_render = true;
+                },
+                BareKey::Char('x') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.mark_selected_entry(FileOperationKind::Move);
+                    should_render = true;
+                },
+                BareKey::Char('v') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.paste_marked_entry();
+                    should_render = true;
+                },
                 BareKey::Char(character) if key.has_no_modifiers() => {
                     self.update_search_term(character);
                     should_render = true;
@@ -162,6 +198,14 @@ impl ZellijPlugin for State {
         } else {
             self.file_list_view.render(rows_for_list, cols);
         }
+        let status_line = rows.saturating_sub(1);
+        if let Some(alert) = &self.alert {
+            render_alert(alert, status_line, cols);
+        } else if let Some((request, progress)) = &self.file_operation {
+            render_file_operation_progress(request, progress, status_line, cols);
+        } else if let Some((kind, entry)) = &self.marked_entry {
+            render_marked_entry(kind, entry, status_line, cols);
+        }
         render_instruction_line(rows, cols);
     }
 }
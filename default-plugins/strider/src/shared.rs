@@ -1,6 +1,9 @@
+use crate::file_list_view::FsEntry;
+use crate::file_operation::{FileOperationKind, FileOperationProgress, FileOperationRequest};
 use std::path::PathBuf;
 use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::*;
+use zellij_tile_utils::safe_truncate;
 
 pub fn render_instruction_line(y: usize, max_cols: usize) {
     if max_cols > 78 {
@@ -24,10 +27,56 @@ pub fn render_instruction_line(y: usize, max_cols: usize) {
     }
 }
 
+pub fn render_alert(alert: &str, y: usize, max_cols: usize) {
+    let text = Text::new(alert).color_range(3, ..);
+    print_text_with_coordinates(text, 0, y, Some(max_cols), None);
+}
+
+pub fn render_file_operation_progress(
+    request: &FileOperationRequest,
+    progress: &FileOperationProgress,
+    y: usize,
+    max_cols: usize,
+) {
+    let name = request
+        .source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let label = format!("{} {}", request.kind.description(), name);
+    let tip = " <ESC> - cancel";
+    let width = max_cols.saturating_sub(tip.width());
+    print_progress_bar(
+        progress.percent(),
+        &label,
+        0,
+        y,
+        width,
+        ProgressBarStyle::Filled,
+    );
+    let tip = Text::new(tip).color_range(3, 1..6);
+    print_text_with_coordinates(tip, width, y, None, None);
+}
+
+pub fn render_marked_entry(kind: &FileOperationKind, entry: &FsEntry, y: usize, max_cols: usize) {
+    let prefix = match kind {
+        FileOperationKind::Copy => "Copied",
+        FileOperationKind::Move => "Cut",
+    };
+    let marked = format!("{} {}, ", prefix, entry.name());
+    let tip_start = marked.chars().count();
+    let text = Text::new(format!("{}<Ctrl v> - paste here", marked))
+        .color_range(3, tip_start..tip_start + 8);
+    print_text_with_coordinates(text, 0, y, Some(max_cols), None);
+}
+
 pub fn render_list_tip(y: usize, max_cols: usize) {
-    let tip = Text::new(format!("(<↓↑> - Navigate, <TAB> - Select)"))
-        .color_range(3, 1..5)
-        .color_range(3, 18..23);
+    let tip = Text::new(format!(
+        "(<↓↑> - Navigate, <TAB> - Select, <Ctrl y/x> - Copy/Cut)"
+    ))
+    .color_range(3, 1..5)
+    .color_range(3, 18..23)
+    .color_range(3, 34..44);
     print_text_with_coordinates(tip, 0, y, Some(max_cols), None);
 }
 
@@ -138,9 +187,11 @@ fn truncate_path(path: PathBuf, mut char_count_to_remove: usize) -> String {
     let mut truncated = String::new();
     let component_count = path.iter().count();
     for (i, component) in path.iter().enumerate() {
-        let mut component_str = component.to_string_lossy().to_string();
+        let component_str = component.to_string_lossy().to_string();
         if char_count_to_remove > 0 {
-            truncated.push(component_str.remove(0));
+            let first_grapheme = safe_truncate(&component_str, 1);
+            truncated.push_str(first_grapheme);
+            let component_str = &component_str[first_grapheme.len()..];
             if i != 0 && i + 1 != component_count {
                 truncated.push('/');
             }
@@ -1,4 +1,8 @@
 use crate::file_list_view::{FileListView, FsEntry};
+use crate::file_operation::{
+    FileOperationKind, FileOperationProgress, FileOperationRequest, CANCEL_OPERATION,
+    CONTINUE_OPERATION, FILE_OPERATION_WORKER_NAME, START_OPERATION,
+};
 use crate::search_view::SearchView;
 use crate::shared::calculate_list_bounds;
 use std::{
@@ -23,6 +27,11 @@ pub struct State {
     pub is_searching: bool,
     pub search_term: String,
     pub close_on_selection: bool,
+    // the entry copied or cut, to be pasted in the current folder
+    pub marked_entry: Option<(FileOperationKind, FsEntry)>,
+    pub file_operation: Option<(FileOperationRequest, FileOperationProgress)>,
+    pub file_operation_count: usize,
+    pub alert: Option<String>,
 }
 
 impl State {
@@ -162,6 +171,100 @@ impl State {
             close_self();
         }
     }
+    pub fn mark_selected_entry(&mut self, kind: FileOperationKind) {
+        let entry = if self.is_searching {
+            self.search_view.get_selected_entry()
+        } else {
+            self.file_list_view.get_selected_entry()
+        };
+        self.marked_entry = entry.map(|entry| (kind, entry));
+    }
+    pub fn paste_marked_entry(&mut self) {
+        if self.file_operation.is_some() {
+            self.alert = Some("Wait for the current operation to finish or cancel it".to_owned());
+            return;
+        }
+        let Some((kind, entry)) = self.marked_entry.clone() else {
+            return;
+        };
+        if kind == FileOperationKind::Move {
+            // copied entries can be pasted more than once
+            self.marked_entry = None;
+        }
+        let current_folder = if self.file_list_view.path_is_dir {
+            self.file_list_view.path.clone()
+        } else {
+            self.file_list_view
+                .path
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_default()
+        };
+        self.file_operation_count += 1;
+        let request = FileOperationRequest {
+            id: self.file_operation_count,
+            kind,
+            source: entry.get_pathbuf(),
+            destination_folder: host_path(&current_folder),
+        };
+        match serde_json::to_string(&request) {
+            Ok(payload) => {
+                post_message_to(PluginMessage::new_to_worker(
+                    FILE_OPERATION_WORKER_NAME,
+                    START_OPERATION,
+                    &payload,
+                ));
+                let progress = FileOperationProgress {
+                    id: request.id,
+                    ..Default::default()
+                };
+                self.file_operation = Some((request, progress));
+            },
+            Err(e) => {
+                self.alert = Some(format!("Failed to start the operation: {}", e));
+            },
+        }
+    }
+    pub fn cancel_file_operation(&mut self) {
+        if self.file_operation.take().is_some() {
+            post_message_to(PluginMessage::new_to_worker(
+                FILE_OPERATION_WORKER_NAME,
+                CANCEL_OPERATION,
+                "",
+            ));
+            refresh_directory(&self.file_list_view.path);
+        }
+    }
+    pub fn update_file_operation_progress(&mut self, payload: &str) {
+        let progress = match serde_json::from_str::<FileOperationProgress>(payload) {
+            Ok(progress) => progress,
+            Err(e) => {
+                self.alert = Some(format!("Failed to read the operation progress: {}", e));
+                return;
+            },
+        };
+        let Some((request, current_progress)) = &mut self.file_operation else {
+            return;
+        };
+        if request.id != progress.id {
+            // a cancelled operation
+            return;
+        }
+        if let Some(error) = &progress.error {
+            self.alert = Some(format!("Failed: {}", error));
+        }
+        if progress.finished {
+            self.file_operation = None;
+            refresh_directory(&self.file_list_view.path);
+        } else {
+            *current_progress = progress;
+            post_message_to(PluginMessage::new_to_worker(
+                FILE_OPERATION_WORKER_NAME,
+                CONTINUE_OPERATION,
+                "",
+            ));
+        }
+    }
     pub fn send_filepick_response(&mut self) {
         let selected_path = self.initial_cwd.join(
             self.file_list_view
@@ -195,6 +298,9 @@ impl State {
 }
 
 pub(crate) fn refresh_directory(path: &Path) {
-    let path_on_host = Path::new(ROOT).join(path.strip_prefix("/").unwrap_or(path));
-    scan_host_folder(&path_on_host);
+    scan_host_folder(&host_path(path));
+}
+
+fn host_path(path: &Path) -> PathBuf {
+    Path::new(ROOT).join(path.strip_prefix("/").unwrap_or(path))
 }
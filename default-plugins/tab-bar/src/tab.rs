@@ -89,6 +89,9 @@ pub fn tab_style(
 ) -> LinePart {
     let separator = tab_separator(capabilities);
 
+    if tab.pinned {
+        tabname.push_str(" 📌");
+    }
     if tab.is_fullscreen_active {
         tabname.push_str(" (FULLSCREEN)");
     } else if tab.is_sync_panes_active {
@@ -6,7 +6,7 @@ use crate::sessions::{
     get_active_session, get_name_generator, get_resurrectable_session_names,
     get_resurrectable_sessions, get_sessions, get_sessions_sorted_by_mtime,
     kill_session as kill_session_impl, match_session_name, print_sessions,
-    print_sessions_with_index, resurrection_layout, session_exists, ActiveSession,
+    print_sessions_with_index, resurrection_layout, server_status, session_exists, ActiveSession,
     SessionNameMatch,
 };
 use zellij_client::{
@@ -19,13 +19,15 @@ use zellij_client::{
 use zellij_server::{os_input_output::get_server_os_input, start_server as start_server_impl};
 use zellij_utils::{
     cli::{CliArgs, Command, SessionCommand, Sessions},
+    consts::latest_saved_layout_file_name,
     data::{ConnectToSession, LayoutInfo},
     envs,
     input::{
         actions::Action,
         config::{Config, ConfigError},
-        layout::Layout,
+        layout::{Layout, Run, RunPluginLocation, SplitDirection, SplitSize, TiledPaneLayout},
         options::Options,
+        plugins::PluginAliases,
     },
     miette::{Report, Result},
     nix,
@@ -75,9 +77,8 @@ pub(crate) fn delete_all_sessions(yes: bool, force: bool) {
         resurrectable_sessions
     } else {
         resurrectable_sessions
-            .iter()
-            .filter(|(name, _, _)| !active_sessions.contains(name))
-            .cloned()
+            .into_iter()
+            .filter(|s| !active_sessions.contains(&s.name))
             .collect()
     };
     if !yes {
@@ -92,7 +93,7 @@ pub(crate) fn delete_all_sessions(yes: bool, force: bool) {
         }
     }
     for session in &dead_sessions {
-        delete_session_impl(&session.0, force);
+        delete_session_impl(&session.name, force);
     }
     process::exit(0);
 }
@@ -125,6 +126,36 @@ pub(crate) fn delete_session(target_session: &Option<String>, force: bool) {
     }
 }
 
+pub(crate) fn resurrect_session(mut opts: CliArgs, session_name: String, latest: bool) {
+    if latest {
+        match latest_saved_layout_file_name(&session_name) {
+            Some(saved_layout) => {
+                opts.command = None;
+                opts.session = Some(session_name);
+                opts.layout = Some(saved_layout);
+            },
+            None => {
+                eprintln!("No saved layout found for session {:?}", session_name);
+                process::exit(1);
+            },
+        }
+    } else {
+        if resurrection_layout(&session_name).is_none() {
+            eprintln!("No exited session named {:?} to resurrect", session_name);
+            process::exit(1);
+        }
+        opts.command = Some(Command::Sessions(Sessions::Attach {
+            session_name: Some(session_name),
+            create: false,
+            create_background: false,
+            index: None,
+            options: None,
+            force_run_commands: false,
+        }));
+    }
+    start_client(opts);
+}
+
 fn get_os_input<OsInputOutput>(
     fn_get_os_input: fn() -> Result<OsInputOutput, nix::Error>,
 ) -> OsInputOutput {
@@ -208,19 +239,50 @@ pub(crate) fn send_action_to_session(
                         "Session '{}' not found. The following sessions are active:",
                         session_name
                     );
-                    list_sessions(false, false, true);
+                    list_sessions(false, false, true, &[], false);
                     std::process::exit(1);
                 }
             } else if let Ok(session_name) = envs::get_session_name() {
                 attach_with_cli_client(cli_action, &session_name, config);
             } else {
                 eprintln!("Please specify the session name to send actions to. The following sessions are active:");
-                list_sessions(false, false, true);
+                list_sessions(false, false, true, &[], false);
                 std::process::exit(1);
             }
         },
     };
 }
+pub(crate) fn print_server_status(requested_session_name: Option<String>) {
+    let session_name = match get_active_session() {
+        ActiveSession::None => {
+            eprintln!("There is no active session!");
+            process::exit(1);
+        },
+        ActiveSession::One(session_name) => requested_session_name.unwrap_or(session_name),
+        ActiveSession::Many => {
+            match requested_session_name.or_else(|| envs::get_session_name().ok()) {
+                Some(session_name) => session_name,
+                None => {
+                    eprintln!("Please specify the session name of the server. The following sessions are active:");
+                    list_sessions(false, false, true, &[], false);
+                    process::exit(1);
+                },
+            }
+        },
+    };
+    assert_session(&session_name);
+    match server_status(&session_name) {
+        Some(status) => println!("{}", status),
+        None => {
+            eprintln!(
+                "Failed to get the status of the server of session '{}'",
+                session_name
+            );
+            process::exit(1);
+        },
+    }
+}
+
 pub(crate) fn convert_old_config_file(old_config_file: PathBuf) {
     match File::open(&old_config_file) {
         Ok(mut handle) => {
@@ -376,7 +438,7 @@ fn attach_with_session_name(
             ActiveSession::One(session_name) => ClientInfo::Attach(session_name, config_options),
             ActiveSession::Many => {
                 println!("Please specify the session to attach to, either by using the full name or a unique prefix.\nThe following sessions are active:");
-                list_sessions(false, false, true);
+                list_sessions(false, false, true, &[], false);
                 process::exit(1);
             },
         },
@@ -727,6 +789,252 @@ pub(crate) fn list_aliases(opts: CliArgs) {
     process::exit(0);
 }
 
+pub(crate) fn dry_run_layout(opts: CliArgs) {
+    let (
+        config,
+        mut layout,
+        _config_options,
+        _config_without_layout,
+        _config_options_without_layout,
+    ) = match Setup::from_cli_args(&opts) {
+        Ok(results) => results,
+        Err(e) => {
+            if let ConfigError::KdlError(error) = e {
+                let report: Report = error.into();
+                eprintln!("{:?}", report);
+            } else {
+                eprintln!("{}", e);
+            }
+            process::exit(1);
+        },
+    };
+    layout.populate_plugin_aliases_in_layout(&config.plugins);
+    let tabs = if layout.has_tabs() {
+        layout.tabs()
+    } else {
+        let (tiled_panes, floating_panes) = layout.new_tab();
+        vec![(None, tiled_panes, floating_panes)]
+    };
+    let mut errors = vec![];
+    for (tab_index, (tab_name, tiled_panes, floating_panes)) in tabs.iter().enumerate() {
+        let tab_name = tab_name
+            .clone()
+            .unwrap_or_else(|| format!("Tab #{}", tab_index + 1));
+        if layout.focused_tab_index() == Some(tab_index) {
+            println!("{} (focused)", tab_name);
+        } else {
+            println!("{}", tab_name);
+        }
+        let mut lines = vec![];
+        let has_floating_panes = !floating_panes.is_empty();
+        if tiled_panes.children.is_empty() {
+            // the tab itself is the only pane
+            let branch = if has_floating_panes {
+                "├──"
+            } else {
+                "└──"
+            };
+            lines.push(format!(
+                "{} {}",
+                branch,
+                dry_run_pane_description(&tiled_panes.name, &tiled_panes.run)
+            ));
+            dry_run_errors(&tiled_panes.run, &mut errors);
+        } else {
+            dry_run_tiled_panes(tiled_panes, "", has_floating_panes, &mut lines, &mut errors);
+        }
+        for (i, floating_pane) in floating_panes.iter().enumerate() {
+            let branch = if i + 1 == floating_panes.len() {
+                "└──"
+            } else {
+                "├──"
+            };
+            lines.push(format!(
+                "{} floating {}",
+                branch,
+                dry_run_pane_description(&floating_pane.name, &floating_pane.run)
+            ));
+            dry_run_errors(&floating_pane.run, &mut errors);
+        }
+        for line in lines {
+            println!("{}", line);
+        }
+    }
+    dry_run_swap_layout_errors(&layout, &config.plugins, &mut errors);
+    if !errors.is_empty() {
+        for error in errors {
+            eprintln!("Error: {}", error);
+        }
+        process::exit(1);
+    }
+}
+
+fn dry_run_tiled_panes(
+    tiled_panes: &TiledPaneLayout,
+    prefix: &str,
+    more_siblings_follow: bool, // eg. the floating panes after the tiled ones
+    lines: &mut Vec<String>,
+    errors: &mut Vec<String>,
+) {
+    for (i, child) in tiled_panes.children.iter().enumerate() {
+        let is_last = i + 1 == tiled_panes.children.len() && !more_siblings_follow;
+        let (branch, child_prefix) = if is_last {
+            ("└──", format!("{}    ", prefix))
+        } else {
+            ("├──", format!("{}│   ", prefix))
+        };
+        let size = child
+            .split_size
+            .map(|split_size| match split_size {
+                SplitSize::Percent(percent) => format!(" ({}%)", percent),
+                SplitSize::Fixed(fixed) => format!(" ({})", fixed),
+            })
+            .unwrap_or_default();
+        if child.children.is_empty() {
+            lines.push(format!(
+                "{}{} {}{}",
+                prefix,
+                branch,
+                dry_run_pane_description(&child.name, &child.run),
+                size
+            ));
+            dry_run_errors(&child.run, errors);
+        } else {
+            let direction = match child.children_split_direction {
+                SplitDirection::Horizontal => "horizontal",
+                SplitDirection::Vertical => "vertical",
+            };
+            let stacked = if child.children_are_stacked {
+                ", stacked"
+            } else {
+                ""
+            };
+            lines.push(format!(
+                "{}{} {} split{}{}",
+                prefix, branch, direction, stacked, size
+            ));
+            dry_run_tiled_panes(child, &child_prefix, false, lines, errors);
+        }
+    }
+}
+
+fn dry_run_pane_description(name: &Option<String>, run: &Option<Run>) -> String {
+    let name = name
+        .as_ref()
+        .map(|name| format!(" \"{}\"", name))
+        .unwrap_or_default();
+    let contents = match run {
+        Some(Run::Command(run_command)) => {
+            let mut command = vec![run_command.command.display().to_string()];
+            command.extend(run_command.args.iter().cloned());
+            format!("command: {}", command.join(" "))
+        },
+        Some(Run::Plugin(run_plugin_or_alias)) => {
+            format!("plugin: {}", run_plugin_or_alias.location_string())
+        },
+        Some(Run::EditFile(path_to_file, _line_number, _cwd)) => {
+            format!("edit: {}", path_to_file.display())
+        },
+        Some(Run::Cwd(cwd)) => format!("shell in {}", cwd.display()),
+        None => "shell".to_owned(),
+    };
+    format!("pane{}: {}", name, contents)
+}
+
+fn dry_run_errors(run: &Option<Run>, errors: &mut Vec<String>) {
+    match run {
+        Some(Run::Command(run_command)) => {
+            let command = &run_command.command;
+            let found = if command.components().count() > 1 {
+                // a path to the command, relative ones are resolved against the pane's cwd
+                match &run_command.cwd {
+                    Some(cwd) => cwd.join(command).is_file(),
+                    None => command.is_file(),
+                }
+            } else {
+                std::env::var_os("PATH")
+                    .map(|path| std::env::split_paths(&path).any(|dir| dir.join(command).is_file()))
+                    .unwrap_or(false)
+            };
+            if !found {
+                errors.push(format!("command not found: {}", command.display()));
+            }
+        },
+        Some(Run::Plugin(run_plugin_or_alias)) => match run_plugin_or_alias.get_run_plugin() {
+            Some(run_plugin) => {
+                if let RunPluginLocation::File(path) = &run_plugin.location {
+                    if !path.is_file() {
+                        errors.push(format!("plugin file not found: {}", path.display()));
+                    }
+                }
+            },
+            None => {
+                errors.push(format!(
+                    "unknown plugin alias: {}",
+                    run_plugin_or_alias.location_string()
+                ));
+            },
+        },
+        _ => {},
+    }
+}
+
+// swap layouts are only applied later on, so they are checked without being printed
+fn dry_run_swap_layout_errors(
+    layout: &Layout,
+    plugin_aliases: &PluginAliases,
+    errors: &mut Vec<String>,
+) {
+    let mut swap_layout_errors = vec![];
+    for (constrained_layouts, swap_layout_name) in &layout.swap_tiled_layouts {
+        for tiled_panes in constrained_layouts.values() {
+            let mut tiled_panes = tiled_panes.clone();
+            tiled_panes.populate_plugin_aliases_in_layout(plugin_aliases);
+            let mut pane_errors = vec![];
+            dry_run_tiled_pane_errors(&tiled_panes, &mut pane_errors);
+            swap_layout_errors.extend(
+                pane_errors
+                    .into_iter()
+                    .map(|error| (error, swap_layout_name.clone())),
+            );
+        }
+    }
+    for (constrained_layouts, swap_layout_name) in &layout.swap_floating_layouts {
+        for floating_panes in constrained_layouts.values() {
+            for floating_pane in floating_panes {
+                let mut run = floating_pane.run.clone();
+                if let Some(run) = run.as_mut() {
+                    run.populate_run_plugin_if_needed(plugin_aliases);
+                }
+                let mut pane_errors = vec![];
+                dry_run_errors(&run, &mut pane_errors);
+                swap_layout_errors.extend(
+                    pane_errors
+                        .into_iter()
+                        .map(|error| (error, swap_layout_name.clone())),
+                );
+            }
+        }
+    }
+    for (error, swap_layout_name) in swap_layout_errors {
+        // the same pane often appears in the layout of several constraints
+        let error = match swap_layout_name {
+            Some(swap_layout_name) => format!("{} (swap layout \"{}\")", error, swap_layout_name),
+            None => format!("{} (swap layout)", error),
+        };
+        if !errors.contains(&error) {
+            errors.push(error);
+        }
+    }
+}
+
+fn dry_run_tiled_pane_errors(tiled_panes: &TiledPaneLayout, errors: &mut Vec<String>) {
+    dry_run_errors(&tiled_panes.run, errors);
+    for child in &tiled_panes.children {
+        dry_run_tiled_pane_errors(child, errors);
+    }
+}
+
 fn reload_config_from_disk(
     config_without_layout: &mut Config,
     config_options_without_layout: &mut Options,
@@ -742,3 +1050,7 @@ fn reload_config_from_disk(
         },
     };
 }
+
+#[cfg(test)]
+#[path = "./unit/commands_tests.rs"]
+mod commands_tests;
@@ -5,18 +5,31 @@ mod tests;
 
 use zellij_utils::{
     clap::Parser,
-    cli::{CliAction, CliArgs, Command, Sessions},
+    cli::{CliAction, CliArgs, Command, ServerCommand, Sessions},
     consts::create_config_and_cache_folders,
     envs,
     input::config::Config,
     logging::*,
     setup::Setup,
+    uuid::Uuid,
 };
 
 fn main() {
     configure_logger();
     create_config_and_cache_folders();
-    let opts = CliArgs::parse();
+    let mut opts = CliArgs::parse();
+
+    if opts.print_token && opts.auth_token.is_none() {
+        opts.auth_token = Some(Uuid::new_v4().simple().to_string());
+    }
+    if let Some(auth_token) = opts.auth_token.as_ref() {
+        // connections made by this process (and the server it might spawn) pick the token up
+        // from the environment
+        envs::set_auth_token(auth_token.clone());
+        if opts.print_token {
+            println!("{}", auth_token);
+        }
+    }
 
     {
         let config = Config::try_from(&opts).ok();
@@ -153,6 +166,7 @@ fn main() {
             args,
             plugin,
             plugin_configuration,
+            plugin_id,
         })) = opts.command
         {
             let command_cli_action = CliAction::Pipe {
@@ -168,23 +182,40 @@ fn main() {
                 in_place_plugin: None,
                 plugin_cwd: None,
                 plugin_title: None,
+                plugin_id,
             };
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
         }
+        if let Some(Command::Sessions(Sessions::Suspend)) = opts.command {
+            commands::send_action_to_session(CliAction::SuspendSession, opts.session, config);
+            std::process::exit(0);
+        }
+        if let Some(Command::Sessions(Sessions::Resume)) = opts.command {
+            commands::send_action_to_session(CliAction::ResumeSession, opts.session, config);
+            std::process::exit(0);
+        }
     }
 
     if let Some(Command::Sessions(Sessions::ListSessions {
         no_formatting,
         short,
         reverse,
+        ref tag,
+        no_tag,
     })) = opts.command
     {
-        commands::list_sessions(no_formatting, short, reverse);
+        commands::list_sessions(no_formatting, short, reverse, tag, no_tag);
     } else if let Some(Command::Sessions(Sessions::ListAliases)) = opts.command {
         commands::list_aliases(opts);
     } else if let Some(Command::Sessions(Sessions::KillAllSessions { yes })) = opts.command {
         commands::kill_all_sessions(yes);
+    } else if let Some(Command::Sessions(Sessions::Resurrect {
+        ref session_name,
+        latest,
+    })) = opts.command
+    {
+        commands::resurrect_session(opts.clone(), session_name.clone(), latest);
     } else if let Some(Command::Sessions(Sessions::KillSession { ref target_session })) =
         opts.command
     {
@@ -198,6 +229,10 @@ fn main() {
     })) = opts.command
     {
         commands::delete_session(target_session, force);
+    } else if let Some(Command::Sessions(Sessions::Server(ServerCommand::Status))) = opts.command {
+        commands::print_server_status(opts.session.clone());
+    } else if opts.dry_run {
+        commands::dry_run_layout(opts);
     } else if let Some(path) = opts.server {
         commands::start_server(path, opts.debug);
     } else if let Some(layout) = &opts.layout {
@@ -6,9 +6,11 @@ use suggest::Suggest;
 use zellij_utils::{
     anyhow,
     consts::{
-        session_info_folder_for_session, session_layout_cache_file_name,
-        ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+        session_info_cache_file_name, session_info_folder_for_session,
+        session_layout_cache_file_name, session_tags_file_name, ZELLIJ_SESSION_INFO_CACHE_DIR,
+        ZELLIJ_SOCK_DIR,
     },
+    data::SessionInfo,
     envs,
     humantime::format_duration,
     input::layout::Layout,
@@ -16,6 +18,50 @@ use zellij_utils::{
     ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
 };
 
+// a session whose server is running with at least one client attached to it
+#[derive(Debug, Clone)]
+pub(crate) struct LiveSession {
+    pub name: String,
+    pub created: Duration,
+}
+
+// a session whose server is still running but that no client is attached to
+#[derive(Debug, Clone)]
+pub(crate) struct ZombieSession {
+    pub name: String,
+    pub created: Duration,
+}
+
+// a session whose server exited, that can be resurrected from its serialized layout
+#[derive(Debug, Clone)]
+pub(crate) struct ResurrectableSession {
+    pub name: String,
+    pub created: Duration,
+    pub layout: Layout,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SessionList {
+    pub live: Vec<LiveSession>,
+    pub zombies: Vec<ZombieSession>,
+    pub resurrectable: Vec<ResurrectableSession>,
+}
+
+impl SessionList {
+    // the sessions whose server is running, whether or not clients are attached to them
+    pub fn running_sessions(&self) -> Vec<(String, Duration)> {
+        self.live
+            .iter()
+            .map(|s| (s.name.clone(), s.created))
+            .chain(self.zombies.iter().map(|s| (s.name.clone(), s.created)))
+            .collect()
+    }
+    pub fn is_running(&self, session_name: &str) -> bool {
+        self.live.iter().any(|s| s.name == session_name)
+            || self.zombies.iter().any(|s| s.name == session_name)
+    }
+}
+
 pub(crate) fn get_sessions() -> Result<Vec<(String, Duration)>, io::ErrorKind> {
     match fs::read_dir(&*ZELLIJ_SOCK_DIR) {
         Ok(files) => {
@@ -40,7 +86,34 @@ pub(crate) fn get_sessions() -> Result<Vec<(String, Duration)>, io::ErrorKind> {
     }
 }
 
-pub(crate) fn get_resurrectable_sessions() -> Vec<(String, Duration, Layout)> {
+// unlike get_sessions, this reads the metadata and serialized layout of every session, so it is
+// only meant for listing them
+pub(crate) fn get_session_list() -> Result<SessionList, io::ErrorKind> {
+    let mut session_list = SessionList::default();
+    for (name, created) in get_sessions()? {
+        // sessions that did not write their metadata yet are considered live
+        if get_connected_client_count(&name) == Some(0) {
+            session_list.zombies.push(ZombieSession { name, created });
+        } else {
+            session_list.live.push(LiveSession { name, created });
+        }
+    }
+    // running sessions also have a serialized layout, they only become resurrectable once they exit
+    session_list.resurrectable = get_resurrectable_sessions()
+        .into_iter()
+        .filter(|s| !session_list.is_running(&s.name))
+        .collect();
+    Ok(session_list)
+}
+
+fn get_connected_client_count(session_name: &str) -> Option<usize> {
+    let raw_session_info = fs::read_to_string(session_info_cache_file_name(session_name)).ok()?;
+    SessionInfo::from_string(&raw_session_info, "")
+        .ok()
+        .map(|session_info| session_info.connected_clients)
+}
+
+pub(crate) fn get_resurrectable_sessions() -> Vec<ResurrectableSession> {
     match fs::read_dir(&*ZELLIJ_SESSION_INFO_CACHE_DIR) {
         Ok(files_in_session_info_folder) => {
             let files_that_are_folders = files_in_session_info_folder
@@ -82,7 +155,11 @@ pub(crate) fn get_resurrectable_sessions() -> Vec<(String, Duration, Layout)> {
                     let session_name = folder_name
                         .file_name()
                         .map(|f| std::path::PathBuf::from(f).display().to_string())?;
-                    Some((session_name, elapsed_duration, layout))
+                    Some(ResurrectableSession {
+                        name: session_name,
+                        created: elapsed_duration,
+                        layout,
+                    })
                 })
                 .collect()
         },
@@ -202,7 +279,13 @@ pub(crate) fn print_sessions(
                     String::new()
                 };
                 let timestamp = format!("[Created {} ago]", format_duration(*timestamp));
-                println!("{} {} {}", session_name, timestamp, suffix);
+                let tags = get_session_tags(session_name);
+                if tags.is_empty() {
+                    println!("{} {} {}", session_name, timestamp, suffix);
+                } else {
+                    let tags = format!("[tags: {}]", tags.join(", "));
+                    println!("{} {} {} {}", session_name, timestamp, tags, suffix);
+                }
             } else {
                 let formatted_session_name = format!("\u{1b}[32;1m{}\u{1b}[m", session_name);
                 let suffix = if curr_session == *session_name {
@@ -216,11 +299,27 @@ pub(crate) fn print_sessions(
                     "[Created \u{1b}[35;1m{}\u{1b}[m ago]",
                     format_duration(*timestamp)
                 );
-                println!("{} {} {}", formatted_session_name, timestamp, suffix);
+                let tags = get_session_tags(session_name);
+                if tags.is_empty() {
+                    println!("{} {} {}", formatted_session_name, timestamp, suffix);
+                } else {
+                    let tags = format!("[tags: \u{1b}[33;1m{}\u{1b}[m]", tags.join(", "));
+                    println!(
+                        "{} {} {} {}",
+                        formatted_session_name, timestamp, tags, suffix
+                    );
+                }
             }
         })
 }
 
+pub(crate) fn get_session_tags(session_name: &str) -> Vec<String> {
+    fs::read_to_string(session_tags_file_name(session_name))
+        .ok()
+        .and_then(|raw_tags| SessionInfo::tags_from_string(&raw_tags).ok())
+        .unwrap_or_default()
+}
+
 pub(crate) fn print_sessions_with_index(sessions: Vec<String>) {
     let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
     for (i, session) in sessions.iter().enumerate() {
@@ -255,7 +354,9 @@ pub(crate) fn kill_session(name: &str) {
     let path = &*ZELLIJ_SOCK_DIR.join(name);
     match LocalSocketStream::connect(path) {
         Ok(stream) => {
-            let _ = IpcSenderWithContext::new(stream).send(ClientToServerMsg::KillSession);
+            let mut sender = IpcSenderWithContext::new(stream);
+            let _ = sender.authenticate();
+            let _ = sender.send(ClientToServerMsg::KillSession);
         },
         Err(e) => {
             eprintln!("Error occurred: {:?}", e);
@@ -264,13 +365,38 @@ pub(crate) fn kill_session(name: &str) {
     };
 }
 
+const SERVER_STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) fn server_status(name: &str) -> Option<String> {
+    let path = &*ZELLIJ_SOCK_DIR.join(name);
+    let stream = LocalSocketStream::connect(path).ok()?;
+    let mut sender = IpcSenderWithContext::new(stream);
+    sender.authenticate().ok()?;
+    sender.send(ClientToServerMsg::ServerStatus).ok()?;
+    let mut receiver: IpcReceiverWithContext<ServerToClientMsg> = sender.get_receiver();
+    // a hung server is exactly what this is meant to diagnose, so we give up on it rather than
+    // waiting for a reply that might never come
+    let (status_sender, status_receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || loop {
+        match receiver.recv() {
+            Some((ServerToClientMsg::ServerStatus(status), _)) => {
+                let _ = status_sender.send(status);
+                break;
+            },
+            Some(_) => continue,
+            None => break,
+        }
+    });
+    status_receiver.recv_timeout(SERVER_STATUS_TIMEOUT).ok()
+}
+
 pub(crate) fn delete_session(name: &str, force: bool) {
     if force {
         let path = &*ZELLIJ_SOCK_DIR.join(name);
         let _ = LocalSocketStream::connect(path).map(|stream| {
-            IpcSenderWithContext::new(stream)
-                .send(ClientToServerMsg::KillSession)
-                .ok();
+            let mut sender = IpcSenderWithContext::new(stream);
+            sender.authenticate().ok();
+            sender.send(ClientToServerMsg::KillSession).ok();
         });
     }
     if let Err(e) = std::fs::remove_dir_all(session_info_folder_for_session(name)) {
@@ -285,18 +411,36 @@ pub(crate) fn delete_session(name: &str, force: bool) {
     }
 }
 
-pub(crate) fn list_sessions(no_formatting: bool, short: bool, reverse: bool) {
-    let exit_code = match get_sessions() {
-        Ok(running_sessions) => {
-            let resurrectable_sessions = get_resurrectable_sessions();
-            let mut all_sessions: HashMap<String, (Duration, bool)> = resurrectable_sessions
+pub(crate) fn list_sessions(
+    no_formatting: bool,
+    short: bool,
+    reverse: bool,
+    tags: &[String],
+    no_tag: bool,
+) {
+    let exit_code = match get_session_list() {
+        Ok(session_list) => {
+            let mut all_sessions: HashMap<String, (Duration, bool)> = session_list
+                .resurrectable
                 .iter()
-                .map(|(name, timestamp, _layout)| (name.clone(), (timestamp.clone(), true)))
+                .map(|s| (s.name.clone(), (s.created, true)))
                 .collect();
-            for (session_name, duration) in running_sessions {
-                all_sessions.insert(session_name.clone(), (duration, false));
+            for (session_name, duration) in session_list.running_sessions() {
+                all_sessions.insert(session_name, (duration, false));
             }
-            if all_sessions.is_empty() {
+            let is_filtered = no_tag || !tags.is_empty();
+            all_sessions.retain(|session_name, _| {
+                let session_tags = get_session_tags(session_name);
+                if no_tag {
+                    session_tags.is_empty()
+                } else {
+                    tags.iter().all(|t| session_tags.contains(t))
+                }
+            });
+            if all_sessions.is_empty() && is_filtered {
+                eprintln!("No zellij sessions matching the given tags found.");
+                1
+            } else if all_sessions.is_empty() {
                 eprintln!("No active zellij sessions found.");
                 1
             } else {
@@ -363,16 +507,10 @@ pub(crate) fn session_exists(name: &str) -> Result<bool, io::ErrorKind> {
 
 // if the session is resurrecable, the returned layout is the one to be used to resurrect it
 pub(crate) fn resurrection_layout(session_name_to_resurrect: &str) -> Option<Layout> {
-    let resurrectable_sessions = get_resurrectable_sessions();
-    resurrectable_sessions
-        .iter()
-        .find_map(|(name, _timestamp, layout)| {
-            if name == session_name_to_resurrect {
-                Some(layout.clone())
-            } else {
-                None
-            }
-        })
+    get_resurrectable_sessions()
+        .into_iter()
+        .find(|s| s.name == session_name_to_resurrect)
+        .map(|s| s.layout)
 }
 
 pub(crate) fn assert_session(name: &str) {
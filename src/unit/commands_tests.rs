@@ -0,0 +1,167 @@
+use super::*;
+use std::collections::BTreeMap;
+use zellij_utils::input::{
+    command::RunCommand,
+    layout::{FloatingPaneLayout, LayoutConstraint, PluginAlias, RunPlugin, RunPluginOrAlias},
+};
+
+const MISSING_COMMAND: &str = "zellij-dry-run-missing-command";
+
+fn command_pane(command: &str) -> TiledPaneLayout {
+    TiledPaneLayout {
+        run: Some(Run::Command(RunCommand {
+            command: PathBuf::from(command),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+}
+
+fn unknown_alias() -> Option<Run> {
+    Some(Run::Plugin(RunPluginOrAlias::Alias(PluginAlias::new(
+        "no-such-alias",
+        &None,
+        None,
+    ))))
+}
+
+#[test]
+fn dry_run_prints_tiled_panes_as_a_tree() {
+    let tiled_panes = TiledPaneLayout {
+        children: vec![
+            TiledPaneLayout {
+                children_split_direction: SplitDirection::Vertical,
+                children: vec![
+                    TiledPaneLayout {
+                        name: Some("editor".to_owned()),
+                        split_size: Some(SplitSize::Percent(70)),
+                        ..command_pane("vim")
+                    },
+                    TiledPaneLayout::default(),
+                ],
+                split_size: Some(SplitSize::Fixed(20)),
+                ..Default::default()
+            },
+            TiledPaneLayout {
+                run: Some(Run::Cwd(PathBuf::from("/tmp"))),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let mut lines = vec![];
+    let mut errors = vec![];
+    dry_run_tiled_panes(&tiled_panes, "", false, &mut lines, &mut errors);
+    assert_eq!(
+        lines,
+        vec![
+            "├── vertical split (20)",
+            "│   ├── pane \"editor\": command: vim (70%)",
+            "│   └── pane: shell",
+            "└── pane: shell in /tmp",
+        ]
+    );
+}
+
+#[test]
+fn dry_run_keeps_the_tree_open_for_the_floating_panes() {
+    let tiled_panes = TiledPaneLayout {
+        children: vec![TiledPaneLayout::default(), TiledPaneLayout::default()],
+        ..Default::default()
+    };
+    let mut lines = vec![];
+    let mut errors = vec![];
+    let more_siblings_follow = true;
+    dry_run_tiled_panes(
+        &tiled_panes,
+        "",
+        more_siblings_follow,
+        &mut lines,
+        &mut errors,
+    );
+    assert_eq!(lines, vec!["├── pane: shell", "├── pane: shell"]);
+}
+
+#[test]
+fn dry_run_reports_missing_commands_and_plugins() {
+    let missing_plugin_file = Some(Run::Plugin(RunPluginOrAlias::RunPlugin(RunPlugin {
+        location: RunPluginLocation::File(PathBuf::from("/no/such/plugin.wasm")),
+        ..Default::default()
+    })));
+    let mut errors = vec![];
+    dry_run_errors(&command_pane(MISSING_COMMAND).run, &mut errors);
+    dry_run_errors(&unknown_alias(), &mut errors);
+    dry_run_errors(&missing_plugin_file, &mut errors);
+    dry_run_errors(&None, &mut errors);
+    assert_eq!(
+        errors,
+        vec![
+            format!("command not found: {}", MISSING_COMMAND),
+            "unknown plugin alias: no-such-alias".to_owned(),
+            "plugin file not found: /no/such/plugin.wasm".to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn dry_run_resolves_command_paths_against_the_pane_cwd() {
+    let cwd = std::env::temp_dir().join(format!("zellij-dry-run-test-{}", process::id()));
+    std::fs::create_dir_all(cwd.join("scripts")).unwrap();
+    std::fs::write(cwd.join("scripts").join("build.sh"), "").unwrap();
+    let command = |cwd: Option<PathBuf>| {
+        Some(Run::Command(RunCommand {
+            command: PathBuf::from("scripts/build.sh"),
+            cwd,
+            ..Default::default()
+        }))
+    };
+    let mut errors = vec![];
+    dry_run_errors(&command(Some(cwd.clone())), &mut errors);
+    assert!(errors.is_empty(), "found relative to the pane's cwd");
+    dry_run_errors(&command(Some(std::env::temp_dir())), &mut errors);
+    assert_eq!(errors, vec!["command not found: scripts/build.sh"]);
+    std::fs::remove_dir_all(cwd).unwrap();
+}
+
+#[test]
+fn dry_run_validates_swap_layouts() {
+    let mut constrained_tiled_layouts = BTreeMap::new();
+    // the same pane in the layouts of several constraints is only reported once
+    for constraint in [
+        LayoutConstraint::MaxPanes(2),
+        LayoutConstraint::NoConstraint,
+    ] {
+        constrained_tiled_layouts.insert(
+            constraint,
+            TiledPaneLayout {
+                children: vec![command_pane(MISSING_COMMAND), TiledPaneLayout::default()],
+                ..Default::default()
+            },
+        );
+    }
+    let mut constrained_floating_layouts = BTreeMap::new();
+    constrained_floating_layouts.insert(
+        LayoutConstraint::NoConstraint,
+        vec![FloatingPaneLayout {
+            run: unknown_alias(),
+            ..Default::default()
+        }],
+    );
+    let layout = Layout {
+        swap_tiled_layouts: vec![(constrained_tiled_layouts, Some("stacked".to_owned()))],
+        swap_floating_layouts: vec![(constrained_floating_layouts, None)],
+        ..Default::default()
+    };
+    let mut errors = vec![];
+    dry_run_swap_layout_errors(&layout, &PluginAliases::default(), &mut errors);
+    assert_eq!(
+        errors,
+        vec![
+            format!(
+                "command not found: {} (swap layout \"stacked\")",
+                MISSING_COMMAND
+            ),
+            "unknown plugin alias: no-such-alias (swap layout)".to_owned(),
+        ]
+    );
+}
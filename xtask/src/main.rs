@@ -38,6 +38,10 @@ lazy_static::lazy_static! {
         WorkspaceMember{crate_name: "default-plugins/session-manager", build: true},
         WorkspaceMember{crate_name: "default-plugins/configuration", build: true},
         WorkspaceMember{crate_name: "default-plugins/plugin-manager", build: true},
+        WorkspaceMember{crate_name: "default-plugins/keybind-help", build: true},
+        WorkspaceMember{crate_name: "default-plugins/search-all-panes", build: true},
+        WorkspaceMember{crate_name: "default-plugins/pane-history", build: true},
+        WorkspaceMember{crate_name: "zellij-utils-derive", build: false},
         WorkspaceMember{crate_name: "zellij-utils", build: false},
         WorkspaceMember{crate_name: "zellij-tile-utils", build: false},
         WorkspaceMember{crate_name: "zellij-tile", build: false},
@@ -45,6 +45,8 @@ pub fn start_cli_client(
                 in_place,
                 cwd,
                 pane_title,
+                plugin_id,
+                is_last: _,
             } => {
                 pipe_client(
                     &mut os_input,
@@ -61,6 +63,7 @@ pub fn start_cli_client(
                     pane_id,
                     cwd,
                     pane_title,
+                    plugin_id,
                 );
             },
             action => {
@@ -86,6 +89,7 @@ fn pipe_client(
     pane_id: Option<u32>,
     cwd: Option<PathBuf>,
     pane_title: Option<String>,
+    plugin_id: Option<u32>,
 ) {
     let mut stdin = os_input.get_stdin_reader();
     let name = name
@@ -103,7 +107,7 @@ fn pipe_client(
             .get_or_insert_with(BTreeMap::new)
             .insert("_zellij_id".to_owned(), Uuid::new_v4().to_string());
     }
-    let create_msg = |payload: Option<String>| -> ClientToServerMsg {
+    let create_msg = |payload: Option<String>, is_last: bool| -> ClientToServerMsg {
         ClientToServerMsg::Action(
             Action::CliPipe {
                 pipe_id: pipe_id.clone(),
@@ -118,6 +122,8 @@ fn pipe_client(
                 skip_cache,
                 cwd: cwd.clone(),
                 pane_title: pane_title.clone(),
+                plugin_id,
+                is_last,
             },
             pane_id,
             None,
@@ -126,12 +132,12 @@ fn pipe_client(
     let is_piped = !os_input.stdin_is_terminal();
     loop {
         if let Some(payload) = payload.take() {
-            let msg = create_msg(Some(payload));
+            let msg = create_msg(Some(payload), true);
             os_input.send_to_server(msg);
         } else if !is_piped {
             // here we send an empty message to trigger the plugin, because we don't have any more
             // data
-            let msg = create_msg(None);
+            let msg = create_msg(None, true);
             os_input.send_to_server(msg);
         } else {
             // we didn't get payload from the command line, meaning we listen on STDIN because this
@@ -139,12 +145,12 @@ fn pipe_client(
             let mut buffer = String::new();
             let _ = stdin.read_line(&mut buffer);
             if buffer.is_empty() {
-                let msg = create_msg(None);
+                let msg = create_msg(None, true);
                 os_input.send_to_server(msg);
                 break;
             } else {
-                // we've got data! send it down the pipe (most common)
-                let msg = create_msg(Some(buffer));
+                // we've got data! send it down the pipe (most common), more of it might follow
+                let msg = create_msg(Some(buffer), false);
                 os_input.send_to_server(msg);
             }
         }
@@ -176,6 +182,19 @@ fn pipe_client(
                         stdout.flush().context(err_context).non_fatal();
                     }
                 },
+                Some((ServerToClientMsg::CliPipeChunk(pipe_name, chunk, is_last), _)) => {
+                    // chunks are written as they arrive so that the output of long running
+                    // plugins shows up incrementally
+                    let err_context = "Failed to write to stdout";
+                    if pipe_name == pipe_id {
+                        let mut stdout = os_input.get_stdout_writer();
+                        stdout.write_all(&chunk).context(err_context).non_fatal();
+                        stdout.flush().context(err_context).non_fatal();
+                        if is_last {
+                            process::exit(0);
+                        }
+                    }
+                },
                 Some((ServerToClientMsg::Log(log_lines), _)) => {
                     log_lines.iter().for_each(|line| println!("{line}"));
                     process::exit(0);
@@ -13,8 +13,10 @@ use std::env::current_exe;
 use std::io::{self, Write};
 use std::path::Path;
 use std::process::Command;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 use zellij_utils::errors::FatalError;
 
 use zellij_utils::notify_debouncer_full::notify::{self, Event, RecursiveMode, Watcher};
@@ -53,8 +55,9 @@ pub(crate) enum ClientInstruction {
     LogError(Vec<String>),
     SwitchSession(ConnectToSession),
     SetSynchronizedOutput(Option<SyncOutput>),
-    UnblockCliPipeInput(String),   // String -> pipe name
-    CliPipeOutput(String, String), // String -> pipe name, String -> output
+    UnblockCliPipeInput(String),         // String -> pipe name
+    CliPipeOutput(String, String),       // String -> pipe name, String -> output
+    CliPipeChunk(String, Vec<u8>, bool), // String -> pipe name, Vec<u8> -> chunk, bool -> is_last
     QueryTerminalSize,
     WriteConfigToDisk { config: String },
 }
@@ -78,10 +81,14 @@ impl From<ServerToClientMsg> for ClientInstruction {
             ServerToClientMsg::CliPipeOutput(pipe_name, output) => {
                 ClientInstruction::CliPipeOutput(pipe_name, output)
             },
+            ServerToClientMsg::CliPipeChunk(pipe_name, chunk, is_last) => {
+                ClientInstruction::CliPipeChunk(pipe_name, chunk, is_last)
+            },
             ServerToClientMsg::QueryTerminalSize => ClientInstruction::QueryTerminalSize,
             ServerToClientMsg::WriteConfigToDisk { config } => {
                 ClientInstruction::WriteConfigToDisk { config }
             },
+            ServerToClientMsg::ServerStatus(status) => ClientInstruction::Log(vec![status]),
         }
     }
 }
@@ -103,6 +110,7 @@ impl From<&ClientInstruction> for ClientContext {
             ClientInstruction::SetSynchronizedOutput(..) => ClientContext::SetSynchronisedOutput,
             ClientInstruction::UnblockCliPipeInput(..) => ClientContext::UnblockCliPipeInput,
             ClientInstruction::CliPipeOutput(..) => ClientContext::CliPipeOutput,
+            ClientInstruction::CliPipeChunk(..) => ClientContext::CliPipeChunk,
             ClientInstruction::QueryTerminalSize => ClientContext::QueryTerminalSize,
             ClientInstruction::WriteConfigToDisk { .. } => ClientContext::WriteConfigToDisk,
         }
@@ -115,6 +123,18 @@ impl ErrorInstruction for ClientInstruction {
     }
 }
 
+const SERVER_DISCONNECTED_ERROR: &str = "Received empty message from server";
+const RECONNECT_INITIAL_BACKOFF_MS: u64 = 100;
+const RECONNECT_MAX_BACKOFF_MS: u64 = 5000;
+
+// consecutive attempts to reconnect to a crashed server, reset once the server renders to us
+static RECONNECT_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+fn reconnect_backoff(attempt: u32) -> Duration {
+    let backoff_ms = RECONNECT_INITIAL_BACKOFF_MS.saturating_mul(2u64.saturating_pow(attempt));
+    Duration::from_millis(std::cmp::min(backoff_ms, RECONNECT_MAX_BACKOFF_MS))
+}
+
 fn spawn_server(socket_path: &Path, debug: bool) -> io::Result<()> {
     let mut cmd = Command::new(current_exe()?);
     cmd.arg("--server");
@@ -185,6 +205,7 @@ pub fn start_client(
         .support_kitty_keyboard_protocol
         .map(|e| !e)
         .unwrap_or(false);
+    let auto_reconnect = config_options.auto_reconnect.unwrap_or(false);
     let mut reconnect_to_session = None;
     let clear_client_terminal_attributes = "\u{1b}[?1l\u{1b}=\u{1b}[r\u{1b}[?1000l\u{1b}[?1002l\u{1b}[?1003l\u{1b}[?1005l\u{1b}[?1006l\u{1b}[?12l";
     let take_snapshot = "\u{1b}[?1049h";
@@ -226,6 +247,7 @@ pub fn start_client(
             rounded_corners: config.ui.pane_frames.rounded_corners,
             hide_session_name: config.ui.pane_frames.hide_session_name,
         },
+        is_web_client: false,
     };
 
     let create_ipc_pipe = || -> std::path::PathBuf {
@@ -325,12 +347,14 @@ pub fn start_client(
             let os_input = os_input.clone();
             let send_input_instructions = send_input_instructions.clone();
             let stdin_ansi_parser = stdin_ansi_parser.clone();
+            let keyboard_remapping = config.keyboard_remapping.clone();
             move || {
                 stdin_loop(
                     os_input,
                     send_input_instructions,
                     stdin_ansi_parser,
                     explicitly_disable_kitty_keyboard_protocol,
+                    keyboard_remapping,
                 )
             }
         });
@@ -414,7 +438,7 @@ pub fn start_client(
                         log::error!("Received empty message from server");
                         send_client_instructions
                             .send(ClientInstruction::Error(
-                                "Received empty message from server".to_string(),
+                                SERVER_DISCONNECTED_ERROR.to_string(),
                             ))
                             .unwrap();
                         break;
@@ -502,9 +526,34 @@ pub fn start_client(
                 break;
             },
             ClientInstruction::Error(backtrace) => {
+                if auto_reconnect && backtrace == SERVER_DISCONNECTED_ERROR {
+                    let attempt = RECONNECT_ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+                    let backoff = reconnect_backoff(attempt);
+                    log::error!(
+                        "Lost connection to the server, reconnecting in {}ms",
+                        backoff.as_millis()
+                    );
+                    let reconnecting_overlay = format!(
+                        "\u{1b}[{};{}H\u{1b}[m\u{1b}[1;7m Reconnecting... \u{1b}[m",
+                        full_screen_ws.rows / 2 + 1,
+                        (full_screen_ws.cols / 2).saturating_sub(8) + 1,
+                    );
+                    let mut stdout = os_input.get_stdout_writer();
+                    let _ = stdout.write_all(reconnecting_overlay.as_bytes());
+                    let _ = stdout.flush();
+                    thread::sleep(backoff);
+                    // the session is attached to (or created/resurrected if the server is gone)
+                    // by the same mechanism used to switch sessions
+                    reconnect_to_session = Some(ConnectToSession {
+                        name: envs::get_session_name().ok(),
+                        ..Default::default()
+                    });
+                    break;
+                }
                 handle_error(backtrace);
             },
             ClientInstruction::Render(output) => {
+                RECONNECT_ATTEMPTS.store(0, Ordering::SeqCst);
                 let mut stdout = os_input.get_stdout_writer();
                 if let Some(sync) = synchronised_output {
                     stdout
@@ -626,6 +675,7 @@ pub fn start_server_detached(
             rounded_corners: config.ui.pane_frames.rounded_corners,
             hide_session_name: config.ui.pane_frames.hide_session_name,
         },
+        is_web_client: false,
     };
 
     let create_ipc_pipe = || -> std::path::PathBuf {
@@ -4,6 +4,7 @@ use crate::stdin_ansi_parser::StdinAnsiParser;
 use crate::InputInstruction;
 use std::sync::{Arc, Mutex};
 use zellij_utils::channels::SenderWithContext;
+use zellij_utils::input::keyboard_remapping::KeyboardRemapping;
 use zellij_utils::termwiz::input::{InputEvent, InputParser, MouseButtons};
 
 fn send_done_parsing_after_query_timeout(
@@ -25,6 +26,7 @@ pub(crate) fn stdin_loop(
     send_input_instructions: SenderWithContext<InputInstruction>,
     stdin_ansi_parser: Arc<Mutex<StdinAnsiParser>>,
     explicitly_disable_kitty_keyboard_protocol: bool,
+    keyboard_remapping: KeyboardRemapping,
 ) {
     let mut holding_mouse = false;
     let mut input_parser = InputParser::new();
@@ -88,6 +90,18 @@ pub(crate) fn stdin_loop(
                 }
                 current_buffer.append(&mut buf.to_vec());
 
+                // sequences the user remapped in their config take precedence over how we would
+                // otherwise parse them
+                if let Some(key_with_modifier) = keyboard_remapping.get(&buf) {
+                    send_input_instructions
+                        .send(InputInstruction::KeyWithModifierEvent(
+                            key_with_modifier.clone(),
+                            current_buffer.drain(..).collect(),
+                        ))
+                        .unwrap();
+                    continue;
+                }
+
                 if !explicitly_disable_kitty_keyboard_protocol {
                     // first we try to parse with the KittyKeyboardParser
                     // if we fail, we try to parse normally
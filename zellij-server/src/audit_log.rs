@@ -0,0 +1,187 @@
+//! The audit log (the `audit_log_path` option): every action the session's clients send is
+//! appended to a file as a JSON object per line by a dedicated thread, so that the route threads
+//! never wait on the disk.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::SystemTime;
+
+use chrono::prelude::*;
+use zellij_utils::channels::{self, Sender, TrySendError};
+use zellij_utils::errors::prelude::*;
+use zellij_utils::input::actions::Action;
+
+use crate::ClientId;
+
+// entries beyond this are dropped rather than blocking the route thread on a slow disk
+const AUDIT_LOG_CHANNEL_SIZE: usize = 1024;
+const BYTES_IN_MEGABYTE: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditLogEntry {
+    pub timestamp: SystemTime,
+    pub client_id: ClientId,
+    pub action: Action,
+}
+
+impl AuditLogEntry {
+    pub fn to_json_line(&self, session_name: &str) -> String {
+        let timestamp: DateTime<Utc> = self.timestamp.into();
+        let line = serde_json::json!({
+            "timestamp": timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            "client_id": self.client_id,
+            "session_name": session_name,
+            "action_type": action_type(&self.action),
+            "action_details": action_details(&self.action),
+        });
+        format!("{}\n", line)
+    }
+}
+
+pub(crate) struct AuditLog {
+    sender: Option<Sender<AuditLogEntry>>,
+    writer_thread: Option<thread::JoinHandle<()>>,
+}
+
+impl AuditLog {
+    pub fn start(path: PathBuf, max_size_mb: Option<u64>, session_name: String) -> Self {
+        let (sender, receiver) = channels::bounded::<AuditLogEntry>(AUDIT_LOG_CHANNEL_SIZE);
+        let writer_thread = thread::Builder::new()
+            .name("audit_log".to_string())
+            .spawn(move || {
+                let mut writer = AuditLogWriter::new(path, max_size_mb);
+                // ends once the session is gone and the sender with it
+                while let Ok(entry) = receiver.recv() {
+                    if let Err(e) = writer.write(&entry.to_json_line(&session_name)) {
+                        log::error!("Failed to write to the audit log: {}", e);
+                    }
+                }
+            })
+            .ok();
+        AuditLog {
+            sender: Some(sender),
+            writer_thread,
+        }
+    }
+    pub fn log_action(&self, client_id: ClientId, action: &Action) {
+        let Some(sender) = self.sender.as_ref() else {
+            return;
+        };
+        let entry = AuditLogEntry {
+            timestamp: SystemTime::now(),
+            client_id,
+            action: action.clone(),
+        };
+        match sender.try_send(entry) {
+            Ok(()) => {},
+            Err(TrySendError::Full(_)) => {
+                log::warn!("Audit log is lagging behind, dropping an entry");
+            },
+            Err(TrySendError::Disconnected(_)) => {
+                log::error!("Audit log writer is gone, dropping an entry");
+            },
+        }
+    }
+}
+
+impl Drop for AuditLog {
+    fn drop(&mut self) {
+        // let the writer drain the entries still in the channel
+        self.sender.take();
+        if let Some(writer_thread) = self.writer_thread.take() {
+            let _ = writer_thread.join();
+        }
+    }
+}
+
+pub struct AuditLogWriter {
+    path: PathBuf,
+    max_size_bytes: Option<u64>,
+    file: Option<File>,
+    current_size: u64,
+}
+
+impl AuditLogWriter {
+    pub fn new(path: PathBuf, max_size_mb: Option<u64>) -> Self {
+        AuditLogWriter {
+            path,
+            max_size_bytes: max_size_mb.map(|mb| mb * BYTES_IN_MEGABYTE),
+            file: None,
+            current_size: 0,
+        }
+    }
+    pub fn write(&mut self, line: &str) -> Result<()> {
+        let path = self.path.clone();
+        let err_context = || format!("failed to write to audit log {}", path.display());
+        if self.file.is_none() {
+            self.open().with_context(err_context)?;
+        }
+        if let Some(max_size_bytes) = self.max_size_bytes {
+            if self.current_size > 0 && self.current_size + line.len() as u64 > max_size_bytes {
+                self.rotate().with_context(err_context)?;
+            }
+        }
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(line.as_bytes()).with_context(err_context)?;
+            self.current_size += line.len() as u64;
+        }
+        Ok(())
+    }
+    fn open(&mut self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        // a log from a previous session counts towards the size before rotation
+        self.current_size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        self.file = Some(file);
+        Ok(())
+    }
+    fn rotate(&mut self) -> Result<()> {
+        self.file = None;
+        fs::rename(&self.path, rotated_path(&self.path))?;
+        self.open()
+    }
+}
+
+pub fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+fn action_type(action: &Action) -> String {
+    let debug = format!("{:?}", action);
+    debug
+        .split(|c: char| c == '(' || c == ' ' || c == '{')
+        .next()
+        .unwrap_or("")
+        .to_owned()
+}
+
+// what is typed into panes or piped to plugins can be passwords, so only its length is recorded
+fn action_details(action: &Action) -> String {
+    match action {
+        Action::Write(_, bytes, _) => format!("{} bytes", bytes.len()),
+        Action::WriteChars(chars) => format!("{} characters", chars.chars().count()),
+        Action::CliPipe { .. } => {
+            let mut redacted_action = action.clone();
+            if let Action::CliPipe { payload, .. } = &mut redacted_action {
+                *payload = payload
+                    .as_ref()
+                    .map(|payload| format!("<{} bytes>", payload.len()));
+            }
+            format!("{:?}", redacted_action)
+        },
+        action => format!("{:?}", action),
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/audit_log_tests.rs"]
+mod audit_log_tests;
@@ -1,32 +1,43 @@
 use zellij_utils::async_std::task;
+use zellij_utils::channels::SenderWithContext;
 use zellij_utils::consts::{
     session_info_cache_file_name, session_info_folder_for_session, session_layout_cache_file_name,
-    ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+    session_tags_file_name, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
 };
 use zellij_utils::data::{Event, HttpVerb, SessionInfo};
-use zellij_utils::errors::{prelude::*, BackgroundJobContext, ContextType};
+use zellij_utils::envs;
+use zellij_utils::errors::{prelude::*, BackgroundJobContext, ContextType, ErrorContext};
+use zellij_utils::input::command::RunCommand;
 use zellij_utils::input::layout::RunPlugin;
+use zellij_utils::input::metrics::MetricsConfig;
 
 use zellij_utils::isahc::prelude::*;
 use zellij_utils::isahc::AsyncReadResponseExt;
 use zellij_utils::isahc::{config::RedirectPolicy, HttpClient, Request};
+use zellij_utils::nix::sys::signal::{killpg, Signal};
+use zellij_utils::nix::unistd::Pid;
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
 use std::fs;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::os::unix::fs::FileTypeExt;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
 use std::path::PathBuf;
+use std::process::Stdio;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, Mutex,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    mpsc, Arc, Mutex,
 };
 use std::time::{Duration, Instant};
 
-use crate::panes::PaneId;
+use crate::metrics::{Metric, MetricsRegistry, DEFAULT_METRICS_FLUSH_INTERVAL_SECONDS};
+use crate::panes::{find_matches_in_lines, find_regex_matches_in_lines, PaneId};
 use crate::plugins::{PluginId, PluginInstruction};
 use crate::screen::ScreenInstruction;
-use crate::thread_bus::Bus;
-use crate::ClientId;
+use crate::thread_bus::{thread_last_activity, Bus, ThreadSenders};
+use crate::{
+    run_session_hooks, wait_with_deadline, ClientId, ServerInstruction, SESSION_HOOK_TIMEOUT,
+};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum BackgroundJob {
@@ -46,6 +57,16 @@ pub enum BackgroundJob {
         PathBuf,
         BTreeMap<String, String>,
     ), // command, args, env_variables, cwd, context
+    PipeTextToCommand(
+        PluginId,
+        ClientId,
+        String,                   // text
+        PathBuf,                  // command
+        Vec<String>,              // args
+        PathBuf,                  // cwd
+        Duration,                 // timeout
+        BTreeMap<String, String>, // context
+    ),
     WebRequest(
         PluginId,
         ClientId,
@@ -55,6 +76,21 @@ pub enum BackgroundJob {
         Vec<u8>,                  // body
         BTreeMap<String, String>, // context
     ),
+    SearchPane(
+        PluginId,
+        ClientId,
+        PaneId,
+        String, // query
+        bool,   // case sensitive
+        bool,   // the query is a regular expression
+        String, // pane contents
+    ),
+    EmitMetric(Metric),
+    RunSessionHooks(Vec<String>, ClientId, Option<usize>), // Option<usize> - tab count, if known
+    ReportServerStatus(ClientId),                          // the client asking for it
+    // the periodic jobs skip their work while the session is suspended
+    SuspendSession,
+    ResumeSession,
     Exit,
 }
 
@@ -72,22 +108,125 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::ReportSessionInfo(..) => BackgroundJobContext::ReportSessionInfo,
             BackgroundJob::ReportLayoutInfo(..) => BackgroundJobContext::ReportLayoutInfo,
             BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
+            BackgroundJob::PipeTextToCommand(..) => BackgroundJobContext::PipeTextToCommand,
             BackgroundJob::WebRequest(..) => BackgroundJobContext::WebRequest,
             BackgroundJob::ReportPluginList(..) => BackgroundJobContext::ReportPluginList,
+            BackgroundJob::SearchPane(..) => BackgroundJobContext::SearchPane,
+            BackgroundJob::EmitMetric(..) => BackgroundJobContext::EmitMetric,
+            BackgroundJob::RunSessionHooks(..) => BackgroundJobContext::RunSessionHooks,
+            BackgroundJob::ReportServerStatus(..) => BackgroundJobContext::ReportServerStatus,
+            BackgroundJob::SuspendSession => BackgroundJobContext::SuspendSession,
+            BackgroundJob::ResumeSession => BackgroundJobContext::ResumeSession,
             BackgroundJob::Exit => BackgroundJobContext::Exit,
         }
     }
 }
 
+/// The order in which jobs waiting in the queue are picked up, jobs of the same priority are
+/// picked up in the order they were sent. Jobs of a lower priority only let a limited amount of
+/// jobs sent after them go first, so that they are not starved by a steady stream of more urgent
+/// ones.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum JobPriority {
+    Low,
+    Normal,
+    High,
+}
+
+impl JobPriority {
+    // how many of the jobs sent after a job of this priority may be picked up before it
+    fn max_overtaking_jobs(&self) -> u64 {
+        match self {
+            JobPriority::High => 0,
+            JobPriority::Normal => 100,
+            JobPriority::Low => 200,
+        }
+    }
+}
+
+impl BackgroundJob {
+    pub fn priority(&self) -> JobPriority {
+        match self {
+            // these drive the UI on a timer, so they should not wait behind slower jobs
+            BackgroundJob::DisplayPaneError(..)
+            | BackgroundJob::AnimatePluginLoading(..)
+            | BackgroundJob::StopPluginLoadingAnimation(..)
+            // this is used to debug a server that is not responding
+            | BackgroundJob::ReportServerStatus(..)
+            | BackgroundJob::SuspendSession
+            | BackgroundJob::ResumeSession => JobPriority::High,
+            BackgroundJob::ReportSessionInfo(..)
+            | BackgroundJob::ReportPluginList(..)
+            | BackgroundJob::ReportLayoutInfo(..)
+            | BackgroundJob::RunCommand(..)
+            | BackgroundJob::PipeTextToCommand(..)
+            | BackgroundJob::WebRequest(..)
+            | BackgroundJob::SearchPane(..)
+            | BackgroundJob::EmitMetric(..)
+            | BackgroundJob::RunSessionHooks(..) => JobPriority::Normal,
+            // exiting comes last so that the jobs sent before it still get to run
+            BackgroundJob::ReadAllSessionInfosOnMachine | BackgroundJob::Exit => JobPriority::Low,
+        }
+    }
+}
+
+// a job waiting in the queue, ordered by the latest point (in jobs received) it should be picked
+// up at and then by the order it was received in
+struct QueuedJob {
+    job: BackgroundJob,
+    err_ctx: ErrorContext,
+    sequence_number: u64,
+}
+
+impl QueuedJob {
+    fn deadline(&self) -> u64 {
+        self.sequence_number + self.job.priority().max_overtaking_jobs()
+    }
+}
+
+impl PartialEq for QueuedJob {
+    fn eq(&self, other: &Self) -> bool {
+        self.sequence_number == other.sequence_number
+    }
+}
+
+impl Eq for QueuedJob {}
+
+impl PartialOrd for QueuedJob {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedJob {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // the heap pops its greatest element, so earlier jobs are greater than later ones
+        other
+            .deadline()
+            .cmp(&self.deadline())
+            .then_with(|| other.sequence_number.cmp(&self.sequence_number))
+    }
+}
+
 static FLASH_DURATION_MS: u64 = 1000;
 static PLUGIN_ANIMATION_OFFSET_DURATION_MD: u64 = 500;
 static SESSION_READ_DURATION: u64 = 1000;
 static DEFAULT_SERIALIZATION_INTERVAL: u64 = 60000;
+static CLIENT_IDLE_CHECK_INTERVAL: u64 = 30000;
+pub const DEFAULT_COMMAND_PIPE_TIMEOUT: Duration = Duration::from_secs(5);
+pub const MAX_COMMAND_PIPE_TIMEOUT: Duration = Duration::from_secs(60);
+// each piped command takes up a few threads until it exits or times out
+const MAX_RUNNING_COMMAND_PIPES: usize = 16;
 
 pub(crate) fn background_jobs_main(
     bus: Bus<BackgroundJob>,
     serialization_interval: Option<u64>,
     disable_session_metadata: bool,
+    client_idle_timeout_seconds: Option<u64>,
+    channel_metrics_interval_seconds: Option<u64>,
+    metrics_config: MetricsConfig,
+    exit_hooks: Vec<String>,
+    to_screen_bounded: SenderWithContext<ScreenInstruction>,
 ) -> Result<()> {
     let err_context = || "failed to write to pty".to_string();
     let mut running_jobs: HashMap<BackgroundJob, Instant> = HashMap::new();
@@ -98,8 +237,19 @@ pub(crate) fn background_jobs_main(
         Arc::new(Mutex::new(BTreeMap::new()));
     let current_session_layout = Arc::new(Mutex::new((String::new(), BTreeMap::new())));
     let last_serialization_time = Arc::new(Mutex::new(Instant::now()));
-    let serialization_interval = serialization_interval.map(|s| s * 1000); // convert to
-                                                                           // milliseconds
+    let metrics_registry = Arc::new(Mutex::new(MetricsRegistry::default()));
+    let metrics_enabled = metrics_config.is_enabled();
+    let session_is_suspended = Arc::new(AtomicBool::new(false));
+    let running_command_pipes = Arc::new(AtomicUsize::new(0));
+    // None means the default interval, Some(0) disables periodic serialization and so does
+    // disabling session metadata altogether
+    let serialization_interval = match serialization_interval {
+        _ if disable_session_metadata => None,
+        Some(0) => None,
+        Some(serialization_interval) => Some(serialization_interval * 1000), // convert to
+        // milliseconds
+        None => Some(DEFAULT_SERIALIZATION_INTERVAL),
+    };
 
     let http_client = HttpClient::builder()
         // TODO: timeout?
@@ -107,8 +257,100 @@ pub(crate) fn background_jobs_main(
         .build()
         .ok();
 
+    if let Some(client_idle_timeout_seconds) = client_idle_timeout_seconds {
+        // the server itself knows when each client last sent input, we just periodically ask it
+        // to detach the ones that have been idle for too long
+        task::spawn({
+            let senders = bus.senders.clone();
+            let session_is_suspended = session_is_suspended.clone();
+            async move {
+                loop {
+                    task::sleep(Duration::from_millis(CLIENT_IDLE_CHECK_INTERVAL)).await;
+                    if session_is_suspended.load(Ordering::SeqCst) {
+                        continue;
+                    }
+                    let _ = senders.send_to_server(ServerInstruction::DetachIdleClients(
+                        Duration::from_secs(client_idle_timeout_seconds),
+                    ));
+                }
+            }
+        });
+    }
+
+    if let Some(channel_metrics_interval_seconds) =
+        channel_metrics_interval_seconds.filter(|interval| *interval > 0)
+    {
+        task::spawn({
+            let senders = bus.senders.clone();
+            let to_screen_bounded = to_screen_bounded.clone();
+            let session_is_suspended = session_is_suspended.clone();
+            async move {
+                loop {
+                    task::sleep(Duration::from_secs(channel_metrics_interval_seconds)).await;
+                    if session_is_suspended.load(Ordering::SeqCst) {
+                        continue;
+                    }
+                    log_channel_fill_levels(&senders, &to_screen_bounded);
+                }
+            }
+        });
+    }
+
+    if metrics_enabled {
+        task::spawn({
+            let senders = bus.senders.clone();
+            let to_screen_bounded = to_screen_bounded.clone();
+            let metrics_registry = metrics_registry.clone();
+            let current_session_name = current_session_name.clone();
+            let session_is_suspended = session_is_suspended.clone();
+            let flush_interval = metrics_config
+                .flush_interval_seconds
+                .unwrap_or(DEFAULT_METRICS_FLUSH_INTERVAL_SECONDS);
+            async move {
+                loop {
+                    task::sleep(Duration::from_secs(flush_interval)).await;
+                    if session_is_suspended.load(Ordering::SeqCst) {
+                        continue;
+                    }
+                    let mut metrics_registry = metrics_registry.lock().unwrap();
+                    record_channel_fill_levels(&mut metrics_registry, &senders, &to_screen_bounded);
+                    let session_name = current_session_name.lock().unwrap().clone();
+                    metrics_registry
+                        .flush(&metrics_config, &session_name)
+                        .non_fatal();
+                }
+            }
+        });
+    }
+
+    let mut queued_jobs: BinaryHeap<QueuedJob> = BinaryHeap::new();
+    let mut next_sequence_number: u64 = 0;
+    let mut queue_job = |queued_jobs: &mut BinaryHeap<QueuedJob>, (job, err_ctx)| {
+        queued_jobs.push(QueuedJob {
+            job,
+            err_ctx,
+            sequence_number: next_sequence_number,
+        });
+        next_sequence_number += 1;
+    };
+
     loop {
-        let (event, mut err_ctx) = bus.recv().with_context(err_context)?;
+        if queued_jobs.is_empty() {
+            queue_job(&mut queued_jobs, bus.recv().with_context(err_context)?);
+        }
+        // everything else that is already waiting is queued as well, so that the most urgent
+        // job is the one picked up
+        while let Some(received) = bus.try_recv() {
+            queue_job(&mut queued_jobs, received);
+        }
+        let Some(QueuedJob {
+            job: event,
+            mut err_ctx,
+            ..
+        }) = queued_jobs.pop()
+        else {
+            continue;
+        };
         err_ctx.add_call(ContextType::BackgroundJob((&event).into()));
         let job = event.clone();
         match event {
@@ -169,6 +411,13 @@ pub(crate) fn background_jobs_main(
             BackgroundJob::ReportLayoutInfo(session_layout) => {
                 *current_session_layout.lock().unwrap() = session_layout;
             },
+            BackgroundJob::EmitMetric(metric) => {
+                // metrics are emitted regardless of the configuration, we only keep them if they
+                // are going to be reported
+                if metrics_enabled {
+                    metrics_registry.lock().unwrap().record(metric);
+                }
+            },
             BackgroundJob::ReadAllSessionInfosOnMachine => {
                 // this job should only be run once and it keeps track of other sessions (as well
                 // as this one's) infos (metadata mostly) and sends it to the screen which in turn
@@ -184,8 +433,16 @@ pub(crate) fn background_jobs_main(
                     let current_session_layout = current_session_layout.clone();
                     let current_session_plugin_list = current_session_plugin_list.clone();
                     let last_serialization_time = last_serialization_time.clone();
+                    let session_is_suspended = session_is_suspended.clone();
                     async move {
                         loop {
+                            if session_is_suspended.load(Ordering::SeqCst) {
+                                task::sleep(std::time::Duration::from_millis(
+                                    SESSION_READ_DURATION,
+                                ))
+                                .await;
+                                continue;
+                            }
                             let current_session_name =
                                 current_session_name.lock().unwrap().to_string();
                             let current_session_info = current_session_info.lock().unwrap().clone();
@@ -214,17 +471,18 @@ pub(crate) fn background_jobs_main(
                                 session_infos_on_machine,
                                 resurrectable_sessions,
                             ));
-                            if last_serialization_time
-                                .lock()
-                                .unwrap()
-                                .elapsed()
-                                .as_millis()
-                                >= serialization_interval
-                                    .unwrap_or(DEFAULT_SERIALIZATION_INTERVAL)
-                                    .into()
-                            {
-                                let _ = senders.send_to_screen(ScreenInstruction::DumpLayoutToHd);
-                                *last_serialization_time.lock().unwrap() = Instant::now();
+                            if let Some(serialization_interval) = serialization_interval {
+                                if last_serialization_time
+                                    .lock()
+                                    .unwrap()
+                                    .elapsed()
+                                    .as_millis()
+                                    >= serialization_interval.into()
+                                {
+                                    let _ =
+                                        senders.send_to_screen(ScreenInstruction::DumpLayoutToHd);
+                                    *last_serialization_time.lock().unwrap() = Instant::now();
+                                }
                             }
                             task::sleep(std::time::Duration::from_millis(SESSION_READ_DURATION))
                                 .await;
@@ -278,6 +536,83 @@ pub(crate) fn background_jobs_main(
                     }
                 });
             },
+            BackgroundJob::PipeTextToCommand(
+                plugin_id,
+                client_id,
+                text,
+                command,
+                args,
+                cwd,
+                timeout,
+                context,
+            ) => {
+                if running_command_pipes.load(Ordering::SeqCst) >= MAX_RUNNING_COMMAND_PIPES {
+                    log::error!("Too many piped commands running, not running {:?}", command);
+                    let _ = bus.senders.send_to_plugin(PluginInstruction::Update(vec![(
+                        Some(plugin_id),
+                        Some(client_id),
+                        Event::CommandPipeResult {
+                            exit_code: COMMAND_FAILED_TO_START_EXIT_CODE,
+                            stdout: String::new(),
+                            stderr: format!(
+                                "Too many piped commands running (at most {})",
+                                MAX_RUNNING_COMMAND_PIPES
+                            ),
+                            context,
+                        },
+                    )]));
+                    continue;
+                }
+                running_command_pipes.fetch_add(1, Ordering::SeqCst);
+                std::thread::spawn({
+                    let senders = bus.senders.clone();
+                    let running_command_pipes = running_command_pipes.clone();
+                    move || {
+                        let (exit_code, stdout, stderr) =
+                            pipe_text_to_command(&text, &command, &args, &cwd, timeout);
+                        running_command_pipes.fetch_sub(1, Ordering::SeqCst);
+                        let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::CommandPipeResult {
+                                exit_code,
+                                stdout,
+                                stderr,
+                                context,
+                            },
+                        )]));
+                    }
+                });
+            },
+            BackgroundJob::SearchPane(
+                plugin_id,
+                client_id,
+                pane_id,
+                query,
+                case_sensitive,
+                regex,
+                pane_contents,
+            ) => {
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        let matches = if regex {
+                            find_regex_matches_in_lines(&pane_contents, &query, case_sensitive)
+                        } else {
+                            find_matches_in_lines(&pane_contents, &query, case_sensitive)
+                        };
+                        let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::SearchResult {
+                                pane_id: pane_id.into(),
+                                query,
+                                matches,
+                            },
+                        )]));
+                    }
+                });
+            },
             BackgroundJob::WebRequest(plugin_id, client_id, url, verb, headers, body, context) => {
                 task::spawn({
                     let senders = bus.senders.clone();
@@ -360,10 +695,45 @@ pub(crate) fn background_jobs_main(
                     }
                 });
             },
+            BackgroundJob::RunSessionHooks(commands, client_id, tab_count) => {
+                let tab_count =
+                    tab_count.unwrap_or_else(|| current_session_info.lock().unwrap().tabs.len());
+                let hooks = session_hooks(
+                    &commands,
+                    &current_session_name.lock().unwrap(),
+                    Some(client_id),
+                    tab_count,
+                );
+                run_session_hooks(&hooks, None);
+            },
+            BackgroundJob::ReportServerStatus(client_id) => {
+                let status = server_status(
+                    &bus.senders,
+                    &to_screen_bounded,
+                    current_session_plugin_list.lock().unwrap().len(),
+                );
+                let _ = bus
+                    .senders
+                    .send_to_server(ServerInstruction::ServerStatus(status, client_id));
+            },
+            BackgroundJob::SuspendSession => {
+                session_is_suspended.store(true, Ordering::SeqCst);
+            },
+            BackgroundJob::ResumeSession => {
+                session_is_suspended.store(false, Ordering::SeqCst);
+            },
             BackgroundJob::Exit => {
                 for loading_plugin in loading_plugins.values() {
                     loading_plugin.store(false, Ordering::SeqCst);
                 }
+                // the session is going away, so we wait for these to finish
+                let hooks = session_hooks(
+                    &exit_hooks,
+                    &current_session_name.lock().unwrap(),
+                    None,
+                    current_session_info.lock().unwrap().tabs.len(),
+                );
+                run_session_hooks(&hooks, Some(SESSION_HOOK_TIMEOUT));
 
                 let cache_file_name =
                     session_info_cache_file_name(&current_session_name.lock().unwrap().to_owned());
@@ -374,6 +744,34 @@ pub(crate) fn background_jobs_main(
     }
 }
 
+fn session_hooks(
+    commands: &[String],
+    session_name: &str,
+    client_id: Option<ClientId>,
+    tab_count: usize,
+) -> Vec<RunCommand> {
+    // the session name is only reported to us once the session info is first serialized
+    let session_name = if session_name.is_empty() {
+        envs::get_session_name().unwrap_or_default()
+    } else {
+        session_name.to_owned()
+    };
+    let mut env = HashMap::new();
+    env.insert("ZELLIJ_SESSION_NAME".to_owned(), session_name);
+    env.insert("ZELLIJ_TAB_COUNT".to_owned(), tab_count.to_string());
+    if let Some(client_id) = client_id {
+        env.insert("ZELLIJ_CLIENT_ID".to_owned(), client_id.to_string());
+    }
+    commands
+        .iter()
+        .map(|command| RunCommand {
+            command: PathBuf::from(command),
+            env: env.clone(),
+            ..Default::default()
+        })
+        .collect()
+}
+
 fn job_already_running(
     job: BackgroundJob,
     running_jobs: &mut HashMap<BackgroundJob, Instant>,
@@ -396,9 +794,11 @@ fn job_already_running(
 
 fn write_session_state_to_disk(
     current_session_name: String,
-    current_session_info: SessionInfo,
+    mut current_session_info: SessionInfo,
     current_session_layout: (String, BTreeMap<String, String>),
 ) {
+    // tags are set externally (eg. by plugins) so we make sure to keep them in the metadata
+    current_session_info.update_tags(read_session_tags(&current_session_name));
     let metadata_cache_file_name = session_info_cache_file_name(&current_session_name);
     let (current_session_layout, layout_files_to_write) = current_session_layout;
     let _wrote_metadata_file =
@@ -427,6 +827,13 @@ fn write_session_state_to_disk(
     }
 }
 
+fn read_session_tags(session_name: &str) -> Vec<String> {
+    fs::read_to_string(session_tags_file_name(session_name))
+        .ok()
+        .and_then(|raw_tags| SessionInfo::tags_from_string(&raw_tags).ok())
+        .unwrap_or_default()
+}
+
 fn read_other_live_session_states(current_session_name: &str) -> BTreeMap<String, SessionInfo> {
     let mut other_session_names = vec![];
     let mut session_infos_on_machine = BTreeMap::new();
@@ -505,3 +912,189 @@ fn find_resurrectable_sessions(
         },
     }
 }
+
+fn record_channel_fill_levels(
+    metrics_registry: &mut MetricsRegistry,
+    senders: &ThreadSenders,
+    to_screen_bounded: &SenderWithContext<ScreenInstruction>,
+) {
+    let mut fill_levels = senders.channel_fill_levels();
+    fill_levels.push((
+        "screen (pty output)",
+        to_screen_bounded.len(),
+        to_screen_bounded.capacity(),
+    ));
+    for (channel_name, len, _capacity) in fill_levels {
+        let mut labels = BTreeMap::new();
+        labels.insert("channel".to_owned(), channel_name.to_owned());
+        metrics_registry.record(Metric::new("zellij_event_queue_depth", len as f64, labels));
+    }
+}
+
+fn server_status(
+    senders: &ThreadSenders,
+    to_screen_bounded: &SenderWithContext<ScreenInstruction>,
+    loaded_plugin_count: usize,
+) -> String {
+    let mut fill_levels = senders.channel_fill_levels();
+    fill_levels.push((
+        "screen (pty output)",
+        to_screen_bounded.len(),
+        to_screen_bounded.capacity(),
+    ));
+    let channels: Vec<serde_json::Value> = fill_levels
+        .iter()
+        .map(|(name, len, capacity)| {
+            serde_json::json!({ "name": name, "len": len, "capacity": capacity })
+        })
+        .collect();
+    let threads: Vec<serde_json::Value> = thread_last_activity()
+        .iter()
+        .map(|(name, since_last_activity)| {
+            serde_json::json!({
+                "name": name,
+                "seconds_since_last_activity": since_last_activity.as_secs_f64(),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "pid": std::process::id(),
+        "memory_usage_bytes": memory_usage_bytes(),
+        "channels": channels,
+        "loaded_plugins": loaded_plugin_count,
+        "threads": threads,
+    })
+    .to_string()
+}
+
+// the resident set size of the server, only known where procfs is available
+fn memory_usage_bytes() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let resident_kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(resident_kilobytes * 1024)
+}
+
+fn log_channel_fill_levels(
+    senders: &ThreadSenders,
+    to_screen_bounded: &SenderWithContext<ScreenInstruction>,
+) {
+    let mut fill_levels = senders.channel_fill_levels();
+    // this is the channel terminal output goes through, the one that usually fills up
+    fill_levels.push((
+        "screen (pty output)",
+        to_screen_bounded.len(),
+        to_screen_bounded.capacity(),
+    ));
+    let fill_levels: Vec<String> = fill_levels
+        .iter()
+        .map(|(name, len, capacity)| match capacity {
+            Some(capacity) => format!("{}: {}/{}", name, len, capacity),
+            None => format!("{}: {}", name, len),
+        })
+        .collect();
+    log::info!("Channel fill levels - {}", fill_levels.join(", "));
+}
+
+// exit codes reported for commands that did not run to completion, the same ones a shell uses
+const COMMAND_TIMED_OUT_EXIT_CODE: i32 = 124;
+const COMMAND_FAILED_TO_START_EXIT_CODE: i32 = 127;
+const COMMAND_KILLED_BY_SIGNAL_EXIT_CODE_OFFSET: i32 = 128;
+// how long the output of a command is waited for once it exited, if it exited at its deadline
+const COMMAND_OUTPUT_GRACE_PERIOD: Duration = Duration::from_millis(100);
+
+// runs `command` outside of any pane with `text` as its stdin, killing it (and whatever it started)
+// if it is still running after `timeout`, returns its exit code, stdout and stderr
+pub(crate) fn pipe_text_to_command(
+    text: &str,
+    command: &PathBuf,
+    args: &[String],
+    cwd: &PathBuf,
+    timeout: Duration,
+) -> (i32, String, String) {
+    let child = std::process::Command::new(command)
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // so that killing the command also kills whatever it started
+        .process_group(0)
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            log::error!("Failed to run command {:?}: {}", command, e);
+            return (
+                COMMAND_FAILED_TO_START_EXIT_CODE,
+                String::new(),
+                e.to_string(),
+            );
+        },
+    };
+    // the pipes are handled in their own threads, otherwise a command filling up its stdout
+    // while we are still writing to its stdin would never finish
+    if let Some(mut stdin) = child.stdin.take() {
+        let text = text.to_owned();
+        std::thread::spawn(move || {
+            // the command might exit without reading all of its input
+            let _ = stdin.write_all(text.as_bytes());
+        });
+    }
+    let stdout_receiver = child.stdout.take().map(read_pipe_in_thread);
+    let stderr_receiver = child.stderr.take().map(read_pipe_in_thread);
+    let deadline = Instant::now() + timeout;
+    let exit_code = match wait_with_deadline(&mut child, deadline) {
+        Ok(status)
+            if status.signal() == Some(Signal::SIGKILL as i32) && Instant::now() >= deadline =>
+        {
+            log::error!("Command {:?} timed out after {:?}", command, timeout);
+            COMMAND_TIMED_OUT_EXIT_CODE
+        },
+        Ok(status) => status.code().unwrap_or_else(|| {
+            COMMAND_KILLED_BY_SIGNAL_EXIT_CODE_OFFSET + status.signal().unwrap_or(0)
+        }),
+        Err(e) => {
+            log::error!("Failed to wait for command {:?}: {}", command, e);
+            let _ = killpg(Pid::from_raw(child.id() as i32), Signal::SIGKILL);
+            COMMAND_FAILED_TO_START_EXIT_CODE
+        },
+    };
+    // something the command left running in the background (eg. in another process group) can
+    // keep its output open, so the output is not waited for past the deadline
+    let output_deadline = std::cmp::max(deadline, Instant::now() + COMMAND_OUTPUT_GRACE_PERIOD);
+    let read_output = |receiver: Option<mpsc::Receiver<String>>| {
+        receiver
+            .and_then(|receiver| {
+                receiver
+                    .recv_timeout(output_deadline.saturating_duration_since(Instant::now()))
+                    .ok()
+            })
+            .unwrap_or_default()
+    };
+    (
+        exit_code,
+        read_output(stdout_receiver),
+        read_output(stderr_receiver),
+    )
+}
+
+fn read_pipe_in_thread<R: Read + Send + 'static>(mut pipe: R) -> mpsc::Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = vec![];
+        let _ = pipe.read_to_end(&mut output);
+        let _ = sender.send(String::from_utf8_lossy(&output).into_owned());
+    });
+    receiver
+}
+
+#[cfg(test)]
+#[path = "./unit/background_jobs_tests.rs"]
+mod background_jobs_tests;
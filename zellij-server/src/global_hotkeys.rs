@@ -0,0 +1,68 @@
+//! Keys registered by plugins to be piped to them regardless of which pane is focused, these take
+//! precedence over both the user's keybindings and the focused pane.
+
+use zellij_utils::data::KeyWithModifier;
+use zellij_utils::input::actions::Action;
+
+#[derive(Debug, Clone)]
+struct GlobalHotkey {
+    key: KeyWithModifier,
+    plugin_id: u32,
+    action_id: String,
+}
+
+impl GlobalHotkey {
+    fn to_action(&self) -> Action {
+        Action::KeybindPipe {
+            name: Some("global_hotkey".to_owned()),
+            payload: Some(self.action_id.clone()),
+            args: None,
+            plugin: None,
+            plugin_id: Some(self.plugin_id),
+            configuration: None,
+            launch_new: false,
+            skip_cache: false,
+            floating: None,
+            in_place: None,
+            cwd: None,
+            pane_title: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GlobalHotkeys {
+    hotkeys: Vec<GlobalHotkey>, // in registration order
+}
+
+impl GlobalHotkeys {
+    pub fn register(&mut self, key: KeyWithModifier, plugin_id: u32, action_id: String) {
+        // re-registering a key changes its action but keeps its place in line
+        match self
+            .hotkeys
+            .iter_mut()
+            .find(|hotkey| hotkey.key == key && hotkey.plugin_id == plugin_id)
+        {
+            Some(hotkey) => hotkey.action_id = action_id,
+            None => self.hotkeys.push(GlobalHotkey {
+                key,
+                plugin_id,
+                action_id,
+            }),
+        }
+    }
+    pub fn unregister_plugin(&mut self, plugin_id: u32) {
+        self.hotkeys.retain(|hotkey| hotkey.plugin_id != plugin_id);
+    }
+    // when several plugins registered the same key, the first one to do so gets it
+    pub fn action_for_key(&self, key: &KeyWithModifier) -> Option<Action> {
+        self.hotkeys
+            .iter()
+            .find(|hotkey| &hotkey.key == key)
+            .map(|hotkey| hotkey.to_action())
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/global_hotkeys_tests.rs"]
+mod global_hotkeys_tests;
@@ -3,8 +3,11 @@ pub mod output;
 pub mod panes;
 pub mod tab;
 
+mod audit_log;
 mod background_jobs;
+mod global_hotkeys;
 mod logging_pipe;
+mod metrics;
 mod plugins;
 mod pty;
 mod pty_writer;
@@ -20,20 +23,27 @@ use log::info;
 use pty_writer::{pty_writer_main, PtyWriteInstruction};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::{
+    os::unix::process::CommandExt,
     path::PathBuf,
+    process::{Child, Command, ExitStatus, Stdio},
     sync::{Arc, RwLock},
     thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use zellij_utils::envs;
+use zellij_utils::nix::sys::signal::{killpg, Signal};
 use zellij_utils::nix::sys::stat::{umask, Mode};
+use zellij_utils::nix::unistd::Pid;
 use zellij_utils::pane_size::Size;
 
 use wasmtime::{Config as WasmtimeConfig, Engine, Strategy};
 
 use crate::{
+    audit_log::AuditLog,
+    global_hotkeys::GlobalHotkeys,
     os_input_output::ServerOsApi,
     plugins::{plugin_thread_main, PluginInstruction},
-    pty::{get_default_shell, pty_thread_main, Pty, PtyInstruction},
+    pty::{get_default_shell, pty_thread_main, Pty, PtyInstruction, DEFAULT_SESSION_MAX_PANES},
     screen::{screen_thread_main, ScreenInstruction},
     thread_bus::{Bus, ThreadSenders},
 };
@@ -41,8 +51,11 @@ use route::route_thread_main;
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     cli::CliArgs,
-    consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
-    data::{ConnectToSession, Event, InputMode, KeyWithModifier, PluginCapabilities},
+    consts::{
+        prune_saved_layouts, saved_layout_file_name, DEFAULT_SCREEN_CHANNEL_SIZE,
+        DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE, SIXEL_SUPPORT,
+    },
+    data::{ConfigDiff, ConnectToSession, Event, InputMode, KeyWithModifier, PluginCapabilities},
     errors::{prelude::*, ContextType, ErrorInstruction, FatalError, ServerContext},
     home::{default_layout_dir, get_default_data_dir},
     input::{
@@ -61,6 +74,13 @@ use zellij_utils::{
 
 pub type ClientId = u16;
 
+// how long the session waits for its stop and exit hooks before killing them
+pub(crate) const SESSION_HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+// how long the session waits for its layout to be saved as it exits
+const SAVE_LAYOUT_TIMEOUT: Duration = Duration::from_secs(5);
+// how many of the layouts saved as sessions of the same name exit are kept
+const MAX_SAVED_LAYOUTS_PER_SESSION: usize = 10;
+
 /// Instructions related to server-side application
 #[derive(Debug, Clone)]
 pub enum ServerInstruction {
@@ -81,6 +101,8 @@ pub enum ServerInstruction {
     Error(String),
     KillSession,
     DetachSession(Vec<ClientId>),
+    DetachIdleClients(Duration), // Duration - idle timeout
+    BroadcastEvent(Event),       // sent to all plugins of all clients
     AttachClient(
         ClientAttributes,
         Config,              // represents the saved config
@@ -90,12 +112,14 @@ pub enum ServerInstruction {
         ClientId,
     ),
     ConnStatus(ClientId),
+    ServerStatus(String, ClientId), // String - JSON
     ActiveClients(ClientId),
     Log(Vec<String>, ClientId),
     LogError(Vec<String>, ClientId),
     SwitchSession(ConnectToSession, ClientId),
-    UnblockCliPipeInput(String),   // String -> Pipe name
-    CliPipeOutput(String, String), // String -> Pipe name, String -> Output
+    UnblockCliPipeInput(String),         // String -> Pipe name
+    CliPipeOutput(String, String),       // String -> Pipe name, String -> Output
+    CliPipeChunk(String, Vec<u8>, bool), // String -> Pipe name, Vec<u8> -> chunk, bool -> is_last
     AssociatePipeWithClient {
         pipe_id: String,
         client_id: ClientId,
@@ -116,6 +140,11 @@ pub enum ServerInstruction {
         keys_to_unbind: Vec<(InputMode, KeyWithModifier)>,
         write_config_to_disk: bool,
     },
+    FlushThrottledRender(ClientId),
+    RegisterGlobalHotkey(KeyWithModifier, u32, String), // u32 - plugin_id, String - action_id
+    UnregisterGlobalHotkeys(u32),                       // u32 - plugin_id
+    SuspendSession(ClientId),                           // the client asking for it
+    ResumeSession(ClientId),                            // the client asking for it
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -129,14 +158,18 @@ impl From<&ServerInstruction> for ServerContext {
             ServerInstruction::Error(_) => ServerContext::Error,
             ServerInstruction::KillSession => ServerContext::KillSession,
             ServerInstruction::DetachSession(..) => ServerContext::DetachSession,
+            ServerInstruction::DetachIdleClients(..) => ServerContext::DetachIdleClients,
+            ServerInstruction::BroadcastEvent(..) => ServerContext::BroadcastEvent,
             ServerInstruction::AttachClient(..) => ServerContext::AttachClient,
             ServerInstruction::ConnStatus(..) => ServerContext::ConnStatus,
+            ServerInstruction::ServerStatus(..) => ServerContext::ServerStatus,
             ServerInstruction::ActiveClients(_) => ServerContext::ActiveClients,
             ServerInstruction::Log(..) => ServerContext::Log,
             ServerInstruction::LogError(..) => ServerContext::LogError,
             ServerInstruction::SwitchSession(..) => ServerContext::SwitchSession,
             ServerInstruction::UnblockCliPipeInput(..) => ServerContext::UnblockCliPipeInput,
             ServerInstruction::CliPipeOutput(..) => ServerContext::CliPipeOutput,
+            ServerInstruction::CliPipeChunk(..) => ServerContext::CliPipeChunk,
             ServerInstruction::AssociatePipeWithClient { .. } => {
                 ServerContext::AssociatePipeWithClient
             },
@@ -153,6 +186,13 @@ impl From<&ServerInstruction> for ServerContext {
                 ServerContext::FailedToWriteConfigToDisk
             },
             ServerInstruction::RebindKeys { .. } => ServerContext::RebindKeys,
+            ServerInstruction::FlushThrottledRender(..) => ServerContext::FlushThrottledRender,
+            ServerInstruction::RegisterGlobalHotkey(..) => ServerContext::RegisterGlobalHotkey,
+            ServerInstruction::UnregisterGlobalHotkeys(..) => {
+                ServerContext::UnregisterGlobalHotkeys
+            },
+            ServerInstruction::SuspendSession(..) => ServerContext::SuspendSession,
+            ServerInstruction::ResumeSession(..) => ServerContext::ResumeSession,
         }
     }
 }
@@ -302,6 +342,11 @@ pub(crate) struct SessionMetaData {
     pub layout: Box<Layout>,
     pub current_input_modes: HashMap<ClientId, InputMode>,
     pub session_configuration: SessionConfiguration,
+    pub auth_token: Option<String>, // clients must present this before being served
+    pub global_hotkeys: GlobalHotkeys,
+    auto_save_layout_on_exit: bool,
+    pub audit_log: Option<AuditLog>,
+    pub is_suspended: bool,
 
     screen_thread: Option<thread::JoinHandle<()>>,
     pty_thread: Option<thread::JoinHandle<()>>,
@@ -311,6 +356,25 @@ pub(crate) struct SessionMetaData {
 }
 
 impl SessionMetaData {
+    // stops (SIGSTOP) or continues (SIGCONT) the processes of all panes and pauses or resumes
+    // the periodic background jobs, the IPC socket stays open either way
+    pub fn set_suspended(&mut self, suspended: bool) {
+        if self.is_suspended == suspended {
+            return;
+        }
+        self.is_suspended = suspended;
+        if suspended {
+            let _ = self
+                .senders
+                .send_to_background_jobs(BackgroundJob::SuspendSession);
+            let _ = self.senders.send_to_pty(PtyInstruction::SuspendAll);
+        } else {
+            let _ = self.senders.send_to_pty(PtyInstruction::ResumeAll);
+            let _ = self
+                .senders
+                .send_to_background_jobs(BackgroundJob::ResumeSession);
+        }
+    }
     pub fn get_client_keybinds_and_mode(
         &self,
         client_id: &ClientId,
@@ -334,8 +398,21 @@ impl SessionMetaData {
             self.current_input_modes.insert(client_id, input_mode);
         }
     }
-    pub fn propagate_configuration_changes(&mut self, config_changes: Vec<(ClientId, Config)>) {
-        for (client_id, new_config) in config_changes {
+    pub fn broadcast_event(&self, event: Event) {
+        let _ = self
+            .senders
+            .send_to_plugin(PluginInstruction::Update(vec![(None, None, event)]));
+    }
+    pub fn propagate_configuration_changes(
+        &mut self,
+        config_changes: Vec<(ClientId, Config)>,
+        config_diff: ConfigDiff,
+    ) {
+        let config_changed = !config_changes.is_empty();
+        for (client_id, mut new_config) in config_changes {
+            if self.layout.default_shell.is_some() {
+                new_config.options.default_shell = self.layout.default_shell.clone();
+            }
             self.default_shell = new_config.options.default_shell.as_ref().map(|shell| {
                 TerminalAction::RunCommand(RunCommand {
                     command: shell.clone(),
@@ -380,11 +457,19 @@ impl SessionMetaData {
                 })
                 .unwrap();
         }
+        if config_changed {
+            self.broadcast_event(Event::ConfigUpdate(config_diff));
+        }
     }
 }
 
 impl Drop for SessionMetaData {
     fn drop(&mut self) {
+        if self.auto_save_layout_on_exit {
+            self.save_session_layout();
+        }
+        // stop hooks run to completion while the session (eg. its panes) still exists
+        run_session_hooks(&self.layout.stop_hooks, Some(SESSION_HOOK_TIMEOUT));
         let _ = self.senders.send_to_pty(PtyInstruction::Exit);
         let _ = self.senders.send_to_screen(ScreenInstruction::Exit);
         let _ = self.senders.send_to_plugin(PluginInstruction::Exit);
@@ -408,6 +493,41 @@ impl Drop for SessionMetaData {
     }
 }
 
+impl SessionMetaData {
+    // serializes the layout of the session as it is now, while its threads are still running, so
+    // that it outlives the resurrection cache of the session (eg. when it is deleted or
+    // overwritten by a new session of the same name)
+    fn save_session_layout(&self) {
+        let Ok(session_name) = envs::get_session_name() else {
+            return;
+        };
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let (completion_sender, completion_receiver) = channels::bounded(1);
+        let saved = self
+            .senders
+            .send_to_screen(ScreenInstruction::SaveLayout(
+                saved_layout_file_name(&session_name, timestamp),
+                completion_sender,
+            ))
+            .and_then(|_| {
+                completion_receiver
+                    .recv_timeout(SAVE_LAYOUT_TIMEOUT)
+                    .context("timed out waiting for the layout to be saved")
+            });
+        if let Err(e) = saved {
+            log::error!(
+                "Failed to save the layout of session {}: {:?}",
+                session_name,
+                e
+            );
+        }
+        prune_saved_layouts(&session_name, MAX_SAVED_LAYOUTS_PER_SESSION);
+    }
+}
+
 macro_rules! remove_client {
     ($client_id:expr, $os_input:expr, $session_state:expr) => {
         $os_input.remove_client($client_id).unwrap();
@@ -419,30 +539,96 @@ macro_rules! send_to_client {
     ($client_id:expr, $os_input:expr, $msg:expr, $session_state:expr) => {
         let send_to_client_res = $os_input.send_to_client($client_id, $msg);
         if let Err(e) = send_to_client_res {
-            // Try to recover the message
-            let context = match e.downcast_ref::<ZellijError>() {
-                Some(ZellijError::ClientTooSlow { .. }) => {
+            let client_too_slow = matches!(
+                e.downcast_ref::<ZellijError>(),
+                Some(ZellijError::ClientTooSlow { .. })
+            );
+            if client_too_slow
+                && $session_state
+                    .write()
+                    .unwrap()
+                    .throttle_slow_client($client_id)
+            {
+                log::warn!(
+                    "client {} is processing server messages too slow, throttling its rendering",
+                    $client_id
+                );
+            } else {
+                // Try to recover the message
+                let context = if client_too_slow {
                     format!(
                         "client {} is processing server messages too slow",
                         $client_id
                     )
-                },
-                _ => {
+                } else {
                     format!("failed to route server message to client {}", $client_id)
-                },
-            };
-            // Log it so it isn't lost
-            Err::<(), _>(e).context(context).non_fatal();
-            // failed to send to client, remove it
-            remove_client!($client_id, $os_input, $session_state);
+                };
+                // Log it so it isn't lost
+                Err::<(), _>(e).context(context).non_fatal();
+                // failed to send to client, remove it
+                remove_client!($client_id, $os_input, $session_state);
+            }
         }
     };
 }
 
+#[derive(Clone, Debug, PartialEq)]
+struct RenderThrottle {
+    max_fps: u8,
+    last_render: Instant,
+    pending_output: Option<String>,
+    flush_scheduled: bool,
+}
+
+impl RenderThrottle {
+    pub fn new(max_fps: u8) -> Self {
+        RenderThrottle {
+            max_fps: max_fps.max(1),
+            last_render: Instant::now(),
+            pending_output: None,
+            flush_scheduled: false,
+        }
+    }
+    fn frame_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.max_fps as u32
+    }
+    fn render_or_buffer(&mut self, output: String) -> Option<String> {
+        // each frame only contains what changed since the previous one, so rather than dropping
+        // the frames we skip, we concatenate them and send them all at once
+        self.pending_output
+            .get_or_insert_with(String::new)
+            .push_str(&output);
+        if self.last_render.elapsed() >= self.frame_interval() {
+            self.take_pending_output()
+        } else {
+            None
+        }
+    }
+    fn take_pending_output(&mut self) -> Option<String> {
+        self.last_render = Instant::now();
+        self.pending_output.take()
+    }
+    fn schedule_flush(&mut self) -> Option<Duration> {
+        if self.pending_output.is_some() && !self.flush_scheduled {
+            self.flush_scheduled = true;
+            Some(
+                self.frame_interval()
+                    .saturating_sub(self.last_render.elapsed()),
+            )
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct SessionState {
     clients: HashMap<ClientId, Option<Size>>,
     pipes: HashMap<String, ClientId>, // String => pipe_id
+    last_active: HashMap<ClientId, Instant>,
+    web_clients: HashSet<ClientId>,
+    render_throttles: HashMap<ClientId, RenderThrottle>,
+    slow_client_warnings: HashMap<ClientId, usize>,
 }
 
 impl SessionState {
@@ -450,6 +636,10 @@ impl SessionState {
         SessionState {
             clients: HashMap::new(),
             pipes: HashMap::new(),
+            last_active: HashMap::new(),
+            web_clients: HashSet::new(),
+            render_throttles: HashMap::new(),
+            slow_client_warnings: HashMap::new(),
         }
     }
     pub fn new_client(&mut self) -> ClientId {
@@ -463,6 +653,7 @@ impl SessionState {
             }
         }
         self.clients.insert(next_client_id, None);
+        self.last_active.insert(next_client_id, Instant::now());
         next_client_id
     }
     pub fn associate_pipe_with_client(&mut self, pipe_id: String, client_id: ClientId) {
@@ -471,6 +662,87 @@ impl SessionState {
     pub fn remove_client(&mut self, client_id: ClientId) {
         self.clients.remove(&client_id);
         self.pipes.retain(|_p_id, c_id| c_id != &client_id);
+        self.last_active.remove(&client_id);
+        self.web_clients.remove(&client_id);
+        self.render_throttles.remove(&client_id);
+        self.slow_client_warnings.remove(&client_id);
+    }
+    pub fn set_web_client(&mut self, client_id: ClientId) {
+        self.web_clients.insert(client_id);
+    }
+    pub fn set_client_render_throttle(&mut self, client_id: ClientId, max_fps: u8) {
+        self.render_throttles
+            .entry(client_id)
+            .and_modify(|render_throttle| render_throttle.max_fps = max_fps.max(1))
+            .or_insert_with(|| RenderThrottle::new(max_fps));
+    }
+    // returns the output to send to the client right away, or None if it was buffered because
+    // the client is throttled and its next frame is not due yet
+    pub fn throttle_render(&mut self, client_id: ClientId, output: String) -> Option<String> {
+        match self.render_throttles.get_mut(&client_id) {
+            Some(render_throttle) => render_throttle.render_or_buffer(output),
+            None => Some(output),
+        }
+    }
+    // returns how long to wait before flushing the output buffered for this client, if a flush
+    // is needed and was not already scheduled
+    pub fn schedule_throttled_flush(&mut self, client_id: ClientId) -> Option<Duration> {
+        self.render_throttles
+            .get_mut(&client_id)
+            .and_then(|render_throttle| render_throttle.schedule_flush())
+    }
+    pub fn flush_throttled_render(&mut self, client_id: ClientId) -> Option<String> {
+        self.render_throttles
+            .get_mut(&client_id)
+            .and_then(|render_throttle| {
+                render_throttle.flush_scheduled = false;
+                render_throttle.take_pending_output()
+            })
+    }
+    // web clients are throttled to 30fps and then to 15fps before being disconnected, terminal
+    // clients are disconnected right away
+    // returns true if the client was throttled and should be kept
+    pub fn throttle_slow_client(&mut self, client_id: ClientId) -> bool {
+        if !self.web_clients.contains(&client_id) {
+            return false;
+        }
+        let warning_count = self.slow_client_warnings.entry(client_id).or_insert(0);
+        *warning_count += 1;
+        match *warning_count {
+            1 => {
+                self.set_client_render_throttle(client_id, 30);
+                true
+            },
+            2 => {
+                self.set_client_render_throttle(client_id, 15);
+                true
+            },
+            _ => false,
+        }
+    }
+    pub fn set_last_active_client(&mut self, client_id: ClientId) {
+        if self.clients.contains_key(&client_id) {
+            self.last_active.insert(client_id, Instant::now());
+        }
+    }
+    pub fn idle_duration(&self, client_id: ClientId) -> Option<Duration> {
+        self.last_active
+            .get(&client_id)
+            .map(|last_active| last_active.elapsed())
+    }
+    pub fn idle_client_ids(&self, idle_timeout: Duration) -> Vec<ClientId> {
+        // pipe clients are short lived cli clients that do not send input
+        let ids_of_pipe_clients: HashSet<ClientId> = self.pipes.values().copied().collect();
+        self.clients
+            .keys()
+            .filter(|client_id| !ids_of_pipe_clients.contains(client_id))
+            .filter(|client_id| {
+                self.idle_duration(**client_id)
+                    .map(|idle_duration| idle_duration >= idle_timeout)
+                    .unwrap_or(false)
+            })
+            .copied()
+            .collect()
     }
     pub fn set_client_size(&mut self, client_id: ClientId, size: Size) {
         self.clients.insert(client_id, Some(size));
@@ -531,6 +803,9 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
         .expect("could not daemonize the server process");
 
     envs::set_zellij("0".to_string());
+    // the session's auth token reaches us through the cli arguments of the first client, dropping
+    // it from the environment keeps it out of the panes, hooks and commands spawned by the server
+    envs::remove_auth_token();
 
     let (to_server, server_receiver): ChannelWithContext<ServerInstruction> = channels::bounded(50);
     let to_server = SenderWithContext::new(to_server);
@@ -643,6 +918,9 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .write()
                     .unwrap()
                     .set_client_size(client_id, client_attributes.size);
+                if client_attributes.is_web_client {
+                    session_state.write().unwrap().set_web_client(client_id);
+                }
 
                 let default_shell = runtime_config_options.default_shell.map(|shell| {
                     TerminalAction::RunCommand(RunCommand {
@@ -677,6 +955,13 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         .unwrap()
                 };
 
+                // the tabs are still being created when the attach hooks run, so we count them
+                // here rather than leaving it to the background job
+                let tab_count = if layout.has_tabs() {
+                    layout.tabs().len()
+                } else {
+                    1
+                };
                 if layout.has_tabs() {
                     let focused_tab_index = layout.focused_tab_index().unwrap_or(0);
                     for (tab_index, (tab_name, tab_layout, floating_panes_layout)) in
@@ -697,7 +982,9 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 } else {
                     let mut floating_panes =
                         layout.template.map(|t| t.1).clone().unwrap_or_default();
-                    if should_launch_setup_wizard {
+                    if should_launch_setup_wizard
+                        && config.notifications.should_show_setup_wizard_on_first_run()
+                    {
                         // we only do this here (and only once) because otherwise it will be
                         // intrusive
                         let setup_wizard = setup_wizard_floating_pane();
@@ -722,6 +1009,18 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .senders
                     .send_to_plugin(PluginInstruction::AddClient(client_id))
                     .unwrap();
+                session_data
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::RunSessionHooks(
+                        config.hooks.on_attach.clone(),
+                        client_id,
+                        Some(tab_count),
+                    ))
+                    .unwrap();
             },
             ServerInstruction::AttachClient(
                 attrs,
@@ -733,6 +1032,8 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
             ) => {
                 let mut rlock = session_data.write().unwrap();
                 let session_data = rlock.as_mut().unwrap();
+                // attaching to a suspended session resumes it
+                session_data.set_suspended(false);
 
                 let mut runtime_configuration = config.clone();
                 runtime_configuration.options = runtime_config_options.clone();
@@ -752,6 +1053,9 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .write()
                     .unwrap()
                     .set_client_size(client_id, attrs.size);
+                if attrs.is_web_client {
+                    session_state.write().unwrap().set_web_client(client_id);
+                }
                 let min_size = session_state
                     .read()
                     .unwrap()
@@ -795,6 +1099,14 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         Event::ModeUpdate(mode_info),
                     )]))
                     .unwrap();
+                session_data
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::RunSessionHooks(
+                        config.hooks.on_attach.clone(),
+                        client_id,
+                        None,
+                    ))
+                    .unwrap();
             },
             ServerInstruction::UnblockInputThread => {
                 let client_ids = session_state.read().unwrap().client_ids();
@@ -857,6 +1169,22 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     },
                 }
             },
+            ServerInstruction::CliPipeChunk(pipe_name, chunk, is_last) => {
+                let pipe = session_state.read().unwrap().get_pipe(&pipe_name);
+                let client_ids = match pipe {
+                    Some(client_id) => vec![client_id],
+                    // send to all clients, this pipe might not have been associated yet
+                    None => session_state.read().unwrap().client_ids(),
+                };
+                for client_id in client_ids {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::CliPipeChunk(pipe_name.clone(), chunk.clone(), is_last),
+                        session_state
+                    );
+                }
+            },
             ServerInstruction::ClientExit(client_id) => {
                 let _ =
                     os_input.send_to_client(client_id, ServerToClientMsg::Exit(ExitReason::Normal));
@@ -990,21 +1318,56 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         .unwrap();
                 }
             },
+            ServerInstruction::DetachIdleClients(idle_timeout) => {
+                let idle_client_ids = session_state.read().unwrap().idle_client_ids(idle_timeout);
+                if !idle_client_ids.is_empty() {
+                    log::warn!(
+                        "Detaching clients {:?} after being idle for more than {:?}",
+                        idle_client_ids,
+                        idle_timeout
+                    );
+                    let _ = to_server.send(ServerInstruction::DetachSession(idle_client_ids));
+                }
+            },
+            ServerInstruction::BroadcastEvent(event) => {
+                if let Some(session_data) = session_data.read().unwrap().as_ref() {
+                    session_data.broadcast_event(event);
+                }
+            },
             ServerInstruction::Render(serialized_output) => {
                 let client_ids = session_state.read().unwrap().client_ids();
                 // If `Some(_)`- unwrap it and forward it to the clients to render.
                 // If `None`- Send an exit instruction. This is the case when a user closes the last Tab/Pane.
                 if let Some(output) = &serialized_output {
                     for (client_id, client_render_instruction) in output.iter() {
-                        // TODO: When a client is too slow or unresponsive, the channel fills up
-                        // and this call will disconnect the client in turn. Should this be
-                        // changed?
-                        send_to_client!(
-                            *client_id,
-                            os_input,
-                            ServerToClientMsg::Render(client_render_instruction.clone()),
-                            session_state
-                        );
+                        let (output_to_send, flush_in) = {
+                            let mut session_state = session_state.write().unwrap();
+                            let output_to_send = session_state
+                                .throttle_render(*client_id, client_render_instruction.clone());
+                            (
+                                output_to_send,
+                                session_state.schedule_throttled_flush(*client_id),
+                            )
+                        };
+                        if let Some(flush_in) = flush_in {
+                            let to_server = to_server.clone();
+                            let client_id = *client_id;
+                            thread::spawn(move || {
+                                thread::sleep(flush_in);
+                                let _ = to_server
+                                    .send(ServerInstruction::FlushThrottledRender(client_id));
+                            });
+                        }
+                        // When a client is too slow or unresponsive, the channel fills up and
+                        // this call will throttle (web clients) or disconnect it
+                        if let Some(output_to_send) = output_to_send {
+                            send_to_client!(
+                                *client_id,
+                                os_input,
+                                ServerToClientMsg::Render(output_to_send),
+                                session_state
+                            );
+                        }
                     }
                 } else {
                     for client_id in client_ids {
@@ -1015,6 +1378,32 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     break;
                 }
             },
+            ServerInstruction::RegisterGlobalHotkey(key, plugin_id, action_id) => {
+                if let Some(session_data) = session_data.write().unwrap().as_mut() {
+                    session_data
+                        .global_hotkeys
+                        .register(key, plugin_id, action_id);
+                }
+            },
+            ServerInstruction::UnregisterGlobalHotkeys(plugin_id) => {
+                if let Some(session_data) = session_data.write().unwrap().as_mut() {
+                    session_data.global_hotkeys.unregister_plugin(plugin_id);
+                }
+            },
+            ServerInstruction::FlushThrottledRender(client_id) => {
+                let pending_output = session_state
+                    .write()
+                    .unwrap()
+                    .flush_throttled_render(client_id);
+                if let Some(pending_output) = pending_output {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::Render(pending_output),
+                        session_state
+                    );
+                }
+            },
             ServerInstruction::Error(backtrace) => {
                 let client_ids = session_state.read().unwrap().client_ids();
                 for client_id in client_ids {
@@ -1030,6 +1419,10 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 let _ = os_input.send_to_client(client_id, ServerToClientMsg::Connected);
                 remove_client!(client_id, os_input, session_state);
             },
+            ServerInstruction::ServerStatus(status, client_id) => {
+                let _ = os_input.send_to_client(client_id, ServerToClientMsg::ServerStatus(status));
+                remove_client!(client_id, os_input, session_state);
+            },
             ServerInstruction::ActiveClients(client_id) => {
                 let client_ids = session_state.read().unwrap().client_ids();
                 send_to_client!(
@@ -1055,6 +1448,28 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     session_state
                 );
             },
+            ServerInstruction::SuspendSession(client_id) => {
+                if let Some(session_data) = session_data.write().unwrap().as_mut() {
+                    session_data.set_suspended(true);
+                }
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::Log(vec!["Session suspended".to_owned()]),
+                    session_state
+                );
+            },
+            ServerInstruction::ResumeSession(client_id) => {
+                if let Some(session_data) = session_data.write().unwrap().as_mut() {
+                    session_data.set_suspended(false);
+                }
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::Log(vec!["Session resumed".to_owned()]),
+                    session_state
+                );
+            },
             ServerInstruction::SwitchSession(mut connect_to_session, client_id) => {
                 let current_session_name = envs::get_session_name();
                 if connect_to_session.name == current_session_name.ok() {
@@ -1137,6 +1552,13 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 config,
                 write_config_to_disk,
             } => {
+                let previous_config = session_data
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .session_configuration
+                    .get_client_configuration(&client_id);
                 let (new_config, runtime_config_changed) = session_data
                     .write()
                     .unwrap()
@@ -1159,16 +1581,38 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     }
 
                     if runtime_config_changed {
+                        let config_diff = previous_config.diff(&new_config);
                         session_data
                             .write()
                             .unwrap()
                             .as_mut()
                             .unwrap()
-                            .propagate_configuration_changes(vec![(client_id, new_config)]);
+                            .propagate_configuration_changes(
+                                vec![(client_id, new_config)],
+                                config_diff,
+                            );
                     }
                 }
             },
             ServerInstruction::ConfigWrittenToDisk(client_id, new_config) => {
+                let is_suspended = session_data
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .map(|s| s.is_suspended)
+                    .unwrap_or(false);
+                if is_suspended {
+                    log::info!("Session is suspended, ignoring config change");
+                    continue;
+                }
+                let config_diff = session_data
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .session_configuration
+                    .get_client_configuration(&client_id)
+                    .diff(&new_config);
                 let changes = session_data
                     .write()
                     .unwrap()
@@ -1181,7 +1625,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .unwrap()
                     .as_mut()
                     .unwrap()
-                    .propagate_configuration_changes(changes);
+                    .propagate_configuration_changes(changes, config_diff);
             },
             ServerInstruction::FailedToWriteConfigToDisk(_client_id, file_path) => {
                 session_data
@@ -1199,6 +1643,13 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 keys_to_unbind,
                 write_config_to_disk,
             } => {
+                let previous_config = session_data
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .session_configuration
+                    .get_client_configuration(&client_id);
                 let (new_config, runtime_config_changed) = session_data
                     .write()
                     .unwrap()
@@ -1220,12 +1671,16 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     }
 
                     if runtime_config_changed {
+                        let config_diff = previous_config.diff(&new_config);
                         session_data
                             .write()
                             .unwrap()
                             .as_mut()
                             .unwrap()
-                            .propagate_configuration_changes(vec![(client_id, new_config)]);
+                            .propagate_configuration_changes(
+                                vec![(client_id, new_config)],
+                                config_diff,
+                            );
                     }
                 }
             },
@@ -1250,40 +1705,75 @@ fn init_session(
     client_attributes: ClientAttributes,
     options: SessionOptions,
     mut config: Config,
-    plugin_aliases: Box<PluginAliases>,
+    mut plugin_aliases: Box<PluginAliases>,
     client_id: ClientId,
 ) -> SessionMetaData {
     let SessionOptions {
         opts,
         config_options,
-        layout,
+        mut layout,
     } = options;
     config.options = config.options.merge(*config_options.clone());
+    plugin_aliases.search_paths = config
+        .options
+        .plugin_search_paths
+        .clone()
+        .unwrap_or_default();
+
+    let session_cwd = config_options
+        .default_cwd
+        .clone()
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."));
+    for hook in layout
+        .start_hooks
+        .iter_mut()
+        .chain(layout.stop_hooks.iter_mut())
+    {
+        hook.cwd = Some(
+            hook.cwd
+                .as_ref()
+                .map(|cwd| session_cwd.join(cwd))
+                .unwrap_or_else(|| session_cwd.clone()),
+        );
+    }
 
     let _ = SCROLL_BUFFER_SIZE.set(
         config_options
             .scroll_buffer_size
             .unwrap_or(DEFAULT_SCROLL_BUFFER_SIZE),
     );
+    let _ = SIXEL_SUPPORT.set(config_options.sixel_support.unwrap_or(true));
 
     let (to_screen, screen_receiver): ChannelWithContext<ScreenInstruction> = channels::unbounded();
     let to_screen = SenderWithContext::new(to_screen);
 
+    // the unbounded screen channel above and the plugin channel below are left as is because
+    // their threads send messages to themselves, bounding them could deadlock these threads
+    //
+    // sizes of 0 are rejected when parsing the configuration, but not when given as cli arguments
+    let channel_size = |size: Option<usize>| size.filter(|size| *size > 0);
+    let channel_buffer_size = channel_size(config_options.channel_buffer_size);
+    let screen_channel_size = channel_size(config_options.screen_channel_size)
+        .or(channel_buffer_size)
+        .unwrap_or(DEFAULT_SCREEN_CHANNEL_SIZE);
     let (to_screen_bounded, bounded_screen_receiver): ChannelWithContext<ScreenInstruction> =
-        channels::bounded(50);
+        channels::bounded(screen_channel_size);
     let to_screen_bounded = SenderWithContext::new(to_screen_bounded);
 
     let (to_plugin, plugin_receiver): ChannelWithContext<PluginInstruction> = channels::unbounded();
     let to_plugin = SenderWithContext::new(to_plugin);
-    let (to_pty, pty_receiver): ChannelWithContext<PtyInstruction> = channels::unbounded();
+    let (to_pty, pty_receiver): ChannelWithContext<PtyInstruction> = channels::bounded_or_unbounded(
+        channel_size(config_options.pty_channel_size).or(channel_buffer_size),
+    );
     let to_pty = SenderWithContext::new(to_pty);
 
     let (to_pty_writer, pty_writer_receiver): ChannelWithContext<PtyWriteInstruction> =
-        channels::unbounded();
+        channels::bounded_or_unbounded(channel_buffer_size);
     let to_pty_writer = SenderWithContext::new(to_pty_writer);
 
     let (to_background_jobs, background_jobs_receiver): ChannelWithContext<BackgroundJob> =
-        channels::unbounded();
+        channels::bounded_or_unbounded(channel_buffer_size);
     let to_background_jobs = SenderWithContext::new(to_background_jobs);
 
     // Determine and initialize the data directory
@@ -1294,18 +1784,25 @@ fn init_session(
     };
 
     let serialization_interval = config_options.serialization_interval;
+    let client_idle_timeout_seconds = config_options.client_idle_timeout_seconds;
+    let channel_metrics_interval_seconds = config_options.channel_metrics_interval_seconds;
     let disable_session_metadata = config_options.disable_session_metadata.unwrap_or(false);
+    let metrics_config = config.metrics.clone();
+    let exit_hooks = config.hooks.on_exit.clone();
 
-    let default_shell = config_options.default_shell.clone().map(|command| {
+    // a shell set in the layout takes precedence over the one in the configuration
+    let configured_default_shell = layout
+        .default_shell
+        .clone()
+        .or_else(|| config_options.default_shell.clone());
+    config.options.default_shell = configured_default_shell.clone();
+    let default_shell = configured_default_shell.clone().map(|command| {
         TerminalAction::RunCommand(RunCommand {
             command,
             ..Default::default()
         })
     });
-    let path_to_default_shell = config_options
-        .default_shell
-        .clone()
-        .unwrap_or_else(|| get_default_shell());
+    let path_to_default_shell = configured_default_shell.unwrap_or_else(|| get_default_shell());
 
     let default_mode = config_options.default_mode.unwrap_or_default();
     let default_keybinds = config.keybinds.clone();
@@ -1327,6 +1824,9 @@ fn init_session(
                 ),
                 opts.debug,
                 config_options.scrollback_editor.clone(),
+                config_options
+                    .session_max_panes
+                    .unwrap_or(DEFAULT_SESSION_MAX_PANES),
             );
 
             move || pty_thread_main(pty, layout.clone()).fatal()
@@ -1442,17 +1942,35 @@ fn init_session(
                 None,
                 Some(os_input.clone()),
             );
+            let to_screen_bounded = to_screen_bounded.clone();
             move || {
                 background_jobs_main(
                     background_jobs_bus,
                     serialization_interval,
                     disable_session_metadata,
+                    client_idle_timeout_seconds,
+                    channel_metrics_interval_seconds,
+                    metrics_config,
+                    exit_hooks,
+                    to_screen_bounded,
                 )
                 .fatal()
             }
         })
         .unwrap();
 
+    run_session_hooks(&layout.start_hooks, None);
+
+    let auth_token = opts.auth_token.clone();
+
+    let audit_log = config_options.audit_log_path.clone().map(|audit_log_path| {
+        AuditLog::start(
+            audit_log_path,
+            config_options.audit_log_max_size_mb,
+            envs::get_session_name().unwrap_or_default(),
+        )
+    });
+
     SessionMetaData {
         senders: ThreadSenders {
             to_screen: Some(to_screen),
@@ -1469,6 +1987,11 @@ fn init_session(
         layout,
         session_configuration: Default::default(),
         current_input_modes: HashMap::new(),
+        auth_token,
+        global_hotkeys: GlobalHotkeys::default(),
+        auto_save_layout_on_exit: config_options.auto_save_layout_on_exit.unwrap_or(false),
+        audit_log,
+        is_suspended: false,
         screen_thread: Some(screen_thread),
         pty_thread: Some(pty_thread),
         plugin_thread: Some(plugin_thread),
@@ -1477,6 +2000,86 @@ fn init_session(
     }
 }
 
+/// Run the given hooks one after the other. If `timeout` is given, wait for them to finish and
+/// kill the ones still running once it elapses (eg. so that a hook that hangs cannot keep a
+/// session from exiting), otherwise leave them running in the background
+pub(crate) fn run_session_hooks(hooks: &[RunCommand], timeout: Option<Duration>) {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    for hook in hooks {
+        let mut command = if hook.args.is_empty() {
+            // like tmux's run-shell, a bare command is interpreted by the shell
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(&hook.command);
+            command
+        } else {
+            let mut command = Command::new(&hook.command);
+            command.args(&hook.args);
+            command
+        };
+        if let Some(cwd) = &hook.cwd {
+            command.current_dir(cwd);
+        }
+        command
+            .envs(&hook.env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            // so that killing the hook also kills whatever it started
+            .process_group(0);
+        match command.spawn() {
+            Ok(mut child) => {
+                let hook_description = hook.to_string();
+                match deadline {
+                    Some(deadline) => {
+                        log_hook_exit(&hook_description, wait_with_deadline(&mut child, deadline))
+                    },
+                    None => {
+                        // reap the child so that it does not linger as a zombie
+                        thread::spawn(move || log_hook_exit(&hook_description, child.wait()));
+                    },
+                }
+            },
+            Err(e) => {
+                log::error!("Failed to run session hook {}: {}", hook, e);
+            },
+        }
+    }
+}
+
+fn log_hook_exit(hook_description: &str, exit_status: std::io::Result<ExitStatus>) {
+    match exit_status {
+        Ok(status) if !status.success() => {
+            log::error!("Session hook {} exited with {}", hook_description, status);
+        },
+        Err(e) => {
+            log::error!(
+                "Failed to wait for session hook {}: {}",
+                hook_description,
+                e
+            );
+        },
+        _ => {},
+    }
+}
+
+/// Wait for a child spawned in its own process group, killing the whole group if it is still
+/// running at `deadline`
+pub(crate) fn wait_with_deadline(
+    child: &mut Child,
+    deadline: Instant,
+) -> std::io::Result<ExitStatus> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            let _ = killpg(Pid::from_raw(child.id() as i32), Signal::SIGKILL);
+            return child.wait();
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
 fn setup_wizard_floating_pane() -> FloatingPaneLayout {
     let mut setup_wizard_pane = FloatingPaneLayout::new();
     let configuration = BTreeMap::from_iter([("is_setup_wizard".to_owned(), "true".to_owned())]);
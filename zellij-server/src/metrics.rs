@@ -0,0 +1,238 @@
+//! Metrics emitted by plugins and by Zellij itself. These are accumulated in the background jobs
+//! thread and periodically flushed in the format the `metrics` configuration block asks for.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::net::UdpSocket;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use zellij_utils::consts::ZELLIJ_CACHE_DIR;
+use zellij_utils::errors::prelude::*;
+use zellij_utils::input::metrics::{MetricsConfig, MetricsFormat};
+
+pub const DEFAULT_METRICS_FLUSH_INTERVAL_SECONDS: u64 = 10;
+const DEFAULT_STATSD_ENDPOINT: &str = "127.0.0.1:8125";
+// every series is kept until the session ends, so a plugin emitting metrics with ever changing
+// labels could otherwise grow the registry without bound
+const MAX_METRIC_SERIES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub name: String,
+    pub value: f64,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Metric {
+    pub fn new(name: &str, value: f64, labels: BTreeMap<String, String>) -> Self {
+        Metric {
+            name: name.to_owned(),
+            value,
+            labels,
+        }
+    }
+}
+
+// background jobs are hashed to tell whether they are already running, metric values are
+// compared by their bit pattern for that purpose
+impl Eq for Metric {}
+
+impl Hash for Metric {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.value.to_bits().hash(state);
+        self.labels.hash(state);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Series {
+    value: f64,
+    updated_since_last_flush: bool,
+}
+
+/// Keeps the latest value of every series (a metric name and its labels)
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    series: BTreeMap<(String, BTreeMap<String, String>), Series>,
+    reported_dropped_series: bool,
+}
+
+impl MetricsRegistry {
+    pub fn record(&mut self, metric: Metric) {
+        let series_key = (metric.name, metric.labels);
+        if !self.series.contains_key(&series_key) && self.series.len() >= MAX_METRIC_SERIES {
+            if !self.reported_dropped_series {
+                log::warn!(
+                    "Dropping metric {:?}, only {} metric series are kept",
+                    series_key.0,
+                    MAX_METRIC_SERIES
+                );
+                self.reported_dropped_series = true;
+            }
+            return;
+        }
+        self.series.insert(
+            series_key,
+            Series {
+                value: metric.value,
+                updated_since_last_flush: true,
+            },
+        );
+    }
+    /// statsd and json receive the series updated since the last flush, the prometheus file is
+    /// rewritten with all of them
+    pub fn flush(&mut self, metrics_config: &MetricsConfig, session_name: &str) -> Result<()> {
+        let err_context = || format!("failed to flush metrics as {}", metrics_config.format);
+        let endpoint = metrics_config.endpoint.clone();
+        let result = match metrics_config.format {
+            MetricsFormat::Disabled => Ok(()),
+            MetricsFormat::Statsd => {
+                let endpoint = endpoint.unwrap_or_else(|| DEFAULT_STATSD_ENDPOINT.to_owned());
+                self.send_to_statsd(&endpoint, session_name)
+            },
+            MetricsFormat::Prometheus => {
+                let path = endpoint
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| default_metrics_file(session_name, "prom"));
+                self.write_prometheus_file(&path, session_name)
+            },
+            MetricsFormat::Json => {
+                let path = endpoint
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| default_metrics_file(session_name, "json"));
+                self.append_to_json_file(&path, session_name)
+            },
+        };
+        for series in self.series.values_mut() {
+            series.updated_since_last_flush = false;
+        }
+        result.with_context(err_context)
+    }
+    fn updated_series(
+        &self,
+        session_name: &str,
+    ) -> impl Iterator<Item = (&String, BTreeMap<String, String>, f64)> {
+        self.all_series(session_name)
+            .filter(|(_, _, _, updated_since_last_flush)| *updated_since_last_flush)
+            .map(|(name, labels, value, _)| (name, labels, value))
+    }
+    fn all_series(
+        &self,
+        session_name: &str,
+    ) -> impl Iterator<Item = (&String, BTreeMap<String, String>, f64, bool)> {
+        let session_name = session_name.to_owned();
+        self.series.iter().map(move |((name, labels), series)| {
+            let mut labels = labels.clone();
+            labels
+                .entry("session".to_owned())
+                .or_insert_with(|| session_name.clone());
+            (name, labels, series.value, series.updated_since_last_flush)
+        })
+    }
+    fn send_to_statsd(&self, endpoint: &str, session_name: &str) -> Result<()> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        for (name, labels, value) in self.updated_series(session_name) {
+            socket.send_to(statsd_line(name, &labels, value).as_bytes(), endpoint)?;
+        }
+        Ok(())
+    }
+    fn write_prometheus_file(&self, path: &PathBuf, session_name: &str) -> Result<()> {
+        let contents: String = self
+            .all_series(session_name)
+            .map(|(name, labels, value, _)| prometheus_line(name, &labels, value))
+            .collect();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // scrapers might be reading this file, so we replace it atomically
+        let tmp_path = path.with_extension(format!("tmp-{}", std::process::id()));
+        fs::write(&tmp_path, contents)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+    fn append_to_json_file(&self, path: &PathBuf, session_name: &str) -> Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let mut contents = String::new();
+        for (name, labels, value) in self.updated_series(session_name) {
+            let line = serde_json::json!({
+                "timestamp": timestamp,
+                "name": name,
+                "value": value,
+                "labels": labels,
+            });
+            contents.push_str(&format!("{}\n", line));
+        }
+        if contents.is_empty() {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+}
+
+fn default_metrics_file(session_name: &str, extension: &str) -> PathBuf {
+    ZELLIJ_CACHE_DIR
+        .join("metrics")
+        .join(format!("{}.{}", session_name, extension))
+}
+
+// labels are sent as DogStatsD style tags, which most statsd servers understand
+fn statsd_line(name: &str, labels: &BTreeMap<String, String>, value: f64) -> String {
+    let sanitize = |s: &str| s.replace(|c: char| matches!(c, ':' | '|' | '@' | '#' | ','), "_");
+    let tags: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}:{}", sanitize(key), sanitize(value)))
+        .collect();
+    if tags.is_empty() {
+        format!("{}:{}|g", sanitize(name), value)
+    } else {
+        format!("{}:{}|g|#{}", sanitize(name), value, tags.join(","))
+    }
+}
+
+fn prometheus_line(name: &str, labels: &BTreeMap<String, String>, value: f64) -> String {
+    let sanitize_name = |s: &str| -> String {
+        let mut sanitized: String = s
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+            sanitized.insert(0, '_');
+        }
+        sanitized
+    };
+    let escape_value = |s: &str| {
+        s.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    };
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", sanitize_name(key), escape_value(value)))
+        .collect();
+    if labels.is_empty() {
+        format!("{} {}\n", sanitize_name(name), value)
+    } else {
+        format!(
+            "{}{{{}}} {}\n",
+            sanitize_name(name),
+            labels.join(","),
+            value
+        )
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/metrics_tests.rs"]
+mod metrics_tests;
@@ -183,6 +183,7 @@ fn handle_openpty(
             }
             command
                 .args(&cmd.args)
+                .envs(&cmd.env)
                 .env("ZELLIJ_PANE_ID", &format!("{}", terminal_id))
                 .pre_exec(move || -> std::io::Result<()> {
                     if libc::login_tty(pid_secondary) != 0 {
@@ -403,18 +404,19 @@ impl ClientSender {
             )
         };
 
-        self.client_buffer_sender
-            .try_send(msg)
-            .or_else(|err| {
-                if let TrySendError::Full(_) = err {
-                    log::warn!(
-                        "client {} is processing server messages too slow",
-                        self.client_id
-                    );
-                }
-                Err(err)
-            })
-            .with_context(err_context)
+        self.client_buffer_sender.try_send(msg).or_else(|err| {
+            if let TrySendError::Full(_) = err {
+                log::warn!(
+                    "client {} is processing server messages too slow",
+                    self.client_id
+                );
+                return Err(ZellijError::ClientTooSlow {
+                    client_id: self.client_id,
+                })
+                .with_context(err_context);
+            }
+            Err(err).with_context(err_context)
+        })
     }
 }
 
@@ -494,6 +496,11 @@ pub trait ServerOsApi: Send + Sync {
     fn kill(&self, pid: Pid) -> Result<()>;
     /// Terminate the process with process ID `pid`. (SIGKILL)
     fn force_kill(&self, pid: Pid) -> Result<()>;
+    /// Stop (SIGSTOP) or continue (SIGCONT) the process with process ID `pid` and all of its
+    /// descendants
+    fn set_process_tree_stopped(&self, _pid: Pid, _stopped: bool) -> Result<()> {
+        Ok(())
+    }
     /// Returns a [`Box`] pointer to this [`ServerOsApi`] struct.
     fn box_clone(&self) -> Box<dyn ServerOsApi>;
     fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg) -> Result<()>;
@@ -691,6 +698,22 @@ impl ServerOsApi for ServerOsInputOutput {
         let _ = kill(pid, Some(Signal::SIGKILL));
         Ok(())
     }
+    fn set_process_tree_stopped(&self, pid: Pid, stopped: bool) -> Result<()> {
+        let pids_in_tree = process_tree(pid);
+        if stopped {
+            // the shell is stopped before the processes it runs so that it does not see its
+            // foreground job stop and take the terminal back from it
+            for pid in pids_in_tree {
+                let _ = kill(pid, Some(Signal::SIGSTOP));
+            }
+        } else {
+            // for the same reason, the shell is continued only after everything it runs
+            for pid in pids_in_tree.into_iter().rev() {
+                let _ = kill(pid, Some(Signal::SIGCONT));
+            }
+        }
+        Ok(())
+    }
     fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg) -> Result<()> {
         let err_context = || format!("failed to send message to client {client_id}");
 
@@ -877,6 +900,24 @@ impl Clone for Box<dyn ServerOsApi> {
     }
 }
 
+/// The process with process ID `pid` (eg. the shell of a pane) followed by all of its descendants,
+/// every process coming after its parent
+fn process_tree(pid: Pid) -> Vec<Pid> {
+    let mut system_info = System::new();
+    system_info.refresh_processes_specifics(ProcessRefreshKind::default());
+    let mut pids_in_tree = vec![pid];
+    let mut next_parent_index = 0;
+    while let Some(parent_pid) = pids_in_tree.get(next_parent_index).copied() {
+        for (child_pid, process) in system_info.processes() {
+            if process.parent() == Some(parent_pid.into()) {
+                pids_in_tree.push(Pid::from_raw(*child_pid));
+            }
+        }
+        next_parent_index += 1;
+    }
+    pids_in_tree
+}
+
 pub fn get_server_os_input() -> Result<ServerOsInputOutput, nix::Error> {
     let current_termios = termios::tcgetattr(0).ok();
     if current_termios.is_none() {
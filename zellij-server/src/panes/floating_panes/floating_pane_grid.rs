@@ -72,6 +72,37 @@ impl<'a> FloatingPaneGrid<'a> {
             .with_context(err_context)
     }
 
+    pub fn move_pane_to(&mut self, pane_id: PaneId, x: usize, y: usize) -> Result<()> {
+        // x and y are relative to the viewport
+        let err_context = || format!("failed to move pane {pane_id:?} to ({x}, {y})");
+
+        let new_pane_position = {
+            let panes = self.panes.borrow();
+            let pane = panes
+                .iter()
+                .find(|(p_id, _p)| **p_id == pane_id)
+                .with_context(|| no_pane_id(&pane_id))
+                .with_context(err_context)?
+                .1;
+            let mut new_pane_position = pane.position_and_size();
+            let fits_horizontally = x + new_pane_position.cols.as_usize() <= self.viewport.cols;
+            let fits_vertically = y + new_pane_position.rows.as_usize() <= self.viewport.rows;
+            if !fits_horizontally || !fits_vertically {
+                return Err(anyhow!(
+                    "pane of size {}x{} does not fit in the viewport at these coordinates",
+                    new_pane_position.cols.as_usize(),
+                    new_pane_position.rows.as_usize()
+                ))
+                .with_context(err_context);
+            }
+            new_pane_position.x = self.viewport.x + x;
+            new_pane_position.y = self.viewport.y + y;
+            new_pane_position
+        };
+        self.set_pane_geom(pane_id, new_pane_position)
+            .with_context(err_context)
+    }
+
     fn set_pane_geom(&mut self, pane_id: PaneId, new_pane_geom: PaneGeom) -> Result<()> {
         let err_context = || {
             format!(
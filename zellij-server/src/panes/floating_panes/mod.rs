@@ -45,8 +45,11 @@ pub struct FloatingPanes {
     session_is_mirrored: bool,
     desired_pane_positions: HashMap<PaneId, PaneGeom>, // this represents the positions of panes the user moved with intention, rather than by resizing the terminal window
     z_indices: Vec<PaneId>,
+    pane_z_indices: HashMap<PaneId, usize>, // explicitly set stacking levels, panes on the same level are ordered by z_indices (last focused in front)
     active_panes: ActivePanes,
     show_panes: bool,
+    exclusive_panes: bool, // when true, focusing a pane shows it and hides all the others
+    exclusively_shown_pane: Option<PaneId>,
     pane_being_moved_with_mouse: Option<(PaneId, Position)>,
     senders: ThreadSenders,
 }
@@ -80,7 +83,10 @@ impl FloatingPanes {
             style,
             desired_pane_positions: HashMap::new(),
             z_indices: vec![],
+            pane_z_indices: HashMap::new(),
             show_panes: false,
+            exclusive_panes: false,
+            exclusively_shown_pane: None,
             active_panes: ActivePanes::new(&os_input),
             pane_being_moved_with_mouse: None,
             senders,
@@ -89,14 +95,15 @@ impl FloatingPanes {
     pub fn stack(&self) -> Option<FloatingPanesStack> {
         if self.panes_are_visible() {
             let layers = self
-                .z_indices
+                .pane_ids_in_stacking_order()
                 .iter()
+                .filter(|pane_id| !self.pane_is_hidden(pane_id))
                 .map(|pane_id| self.panes.get(pane_id).unwrap().position_and_size())
                 .collect();
             Some(FloatingPanesStack { layers })
         } else if self.has_pinned_panes() {
             let layers = self
-                .z_indices
+                .pane_ids_in_stacking_order()
                 .iter()
                 .filter_map(|pane_id| {
                     self.panes
@@ -115,6 +122,13 @@ impl FloatingPanes {
             .iter()
             .any(|(_, p)| p.position_and_size().is_pinned)
     }
+    pub fn pinned_pane_ids(&self) -> Vec<PaneId> {
+        self.panes
+            .iter()
+            .filter(|(_, p)| p.position_and_size().is_pinned)
+            .map(|(pane_id, _)| *pane_id)
+            .collect()
+    }
     pub fn pane_ids(&self) -> impl Iterator<Item = &PaneId> {
         self.panes.keys()
     }
@@ -163,6 +177,9 @@ impl FloatingPanes {
                     .with_context(err_context)?;
                 self.z_indices.remove(z_index);
                 self.z_indices.insert(z_index, with_pane_id);
+                if let Some(pane_z_index) = self.pane_z_indices.remove(&removed_pane_id) {
+                    self.pane_z_indices.insert(with_pane_id, pane_z_index);
+                }
                 Ok(removed_pane)
             });
 
@@ -179,7 +196,15 @@ impl FloatingPanes {
     }
     pub fn remove_pane(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
         self.z_indices.retain(|p_id| *p_id != pane_id);
+        self.pane_z_indices.remove(&pane_id);
         self.desired_pane_positions.remove(&pane_id);
+        if self.exclusively_shown_pane == Some(pane_id) {
+            self.exclusively_shown_pane = if self.exclusive_panes {
+                self.z_indices.last().copied()
+            } else {
+                None
+            };
+        }
         self.panes.remove(&pane_id)
     }
     pub fn hold_pane(
@@ -231,12 +256,91 @@ impl FloatingPanes {
     }
     pub fn toggle_show_panes(&mut self, should_show_floating_panes: bool) {
         self.show_panes = should_show_floating_panes;
+        if !self.exclusive_panes {
+            self.exclusively_shown_pane = None;
+        }
         if should_show_floating_panes {
             self.active_panes.focus_all_panes(&mut self.panes);
         } else {
             self.active_panes.unfocus_all_panes(&mut self.panes);
         }
     }
+    pub fn show_one_pane(&mut self, pane_id: PaneId) {
+        // shows only this pane (and any pinned panes) until another pane is shown or the panes
+        // are toggled while not in exclusive mode
+        if !self.panes.contains_key(&pane_id) {
+            return;
+        }
+        self.z_indices.retain(|p_id| *p_id != pane_id);
+        self.z_indices.push(pane_id);
+        self.exclusively_shown_pane = Some(pane_id);
+        self.set_force_render();
+    }
+    pub fn set_exclusive_panes(&mut self, exclusive_panes: bool) {
+        self.exclusive_panes = exclusive_panes;
+        if exclusive_panes {
+            let pane_to_show = self
+                .active_panes
+                .values()
+                .next()
+                .copied()
+                .or_else(|| self.z_indices.last().copied());
+            if let Some(pane_to_show) = pane_to_show {
+                self.show_one_pane(pane_to_show);
+            }
+        } else {
+            self.exclusively_shown_pane = None;
+            self.set_force_render();
+        }
+    }
+    pub fn set_pane_z_index(&mut self, pane_id: PaneId, z_index: usize) {
+        if !self.panes.contains_key(&pane_id) {
+            return;
+        }
+        if z_index == 0 {
+            self.pane_z_indices.remove(&pane_id);
+        } else {
+            self.pane_z_indices.insert(pane_id, z_index);
+        }
+        self.set_force_render();
+    }
+    pub fn pane_z_index(&self, pane_id: &PaneId) -> usize {
+        self.pane_z_indices.get(pane_id).copied().unwrap_or(0)
+    }
+    fn stacking_order(&self, pane_id: &PaneId) -> Option<(usize, usize)> {
+        // panes with a higher z-index are always in front, among panes with the same z-index the
+        // last focused one is in front
+        self.z_indices
+            .iter()
+            .position(|id| id == pane_id)
+            .map(|position| (self.pane_z_index(pane_id), position))
+    }
+    fn pane_ids_in_stacking_order(&self) -> Vec<PaneId> {
+        let mut pane_ids = self.z_indices.clone();
+        // sort_by_key is stable, so panes with the same z-index keep their focus order
+        pane_ids.sort_by_key(|pane_id| self.pane_z_index(pane_id));
+        pane_ids
+    }
+    fn pane_is_hidden(&self, pane_id: &PaneId) -> bool {
+        match self.exclusively_shown_pane {
+            Some(shown_pane_id) => {
+                shown_pane_id != *pane_id
+                    && !self
+                        .panes
+                        .get(pane_id)
+                        .map(|p| p.position_and_size().is_pinned)
+                        .unwrap_or(false)
+            },
+            None => false,
+        }
+    }
+    fn hidden_pane_ids(&self) -> HashSet<PaneId> {
+        self.panes
+            .keys()
+            .filter(|pane_id| self.pane_is_hidden(pane_id))
+            .copied()
+            .collect()
+    }
     pub fn active_panes_contain(&self, client_id: &ClientId) -> bool {
         self.active_panes.contains_key(client_id)
     }
@@ -345,8 +449,13 @@ impl FloatingPanes {
         } else {
             Default::default()
         };
+        let hidden_pane_ids = self.hidden_pane_ids();
+        let pane_ids_in_stacking_order = self.pane_ids_in_stacking_order();
         let mut floating_panes: Vec<_> = if self.panes_are_visible() {
-            self.panes.iter_mut().collect()
+            self.panes
+                .iter_mut()
+                .filter(|(pane_id, _)| !hidden_pane_ids.contains(pane_id))
+                .collect()
         } else if self.has_pinned_panes() {
             self.panes
                 .iter_mut()
@@ -356,14 +465,13 @@ impl FloatingPanes {
             vec![]
         };
         floating_panes.sort_by(|(a_id, _a_pane), (b_id, _b_pane)| {
-            self.z_indices
+            pane_ids_in_stacking_order
                 .iter()
                 .position(|id| id == *a_id)
                 .with_context(err_context)
                 .fatal()
                 .cmp(
-                    &self
-                        .z_indices
+                    &pane_ids_in_stacking_order
                         .iter()
                         .position(|id| id == *b_id)
                         .with_context(err_context)
@@ -751,6 +859,9 @@ impl FloatingPanes {
         }
         self.z_indices.retain(|p_id| *p_id != pane_id);
         self.z_indices.push(pane_id);
+        if self.exclusive_panes {
+            self.exclusively_shown_pane = Some(pane_id);
+        }
         self.set_pane_active_at(pane_id);
         self.set_force_render();
     }
@@ -794,15 +905,21 @@ impl FloatingPanes {
 
         // TODO: better - loop through z-indices and check each one if it contains the point
         let mut panes: Vec<_> = if search_selectable {
-            self.panes.iter().filter(|(_, p)| p.selectable()).collect()
+            self.panes
+                .iter()
+                .filter(|(pane_id, p)| p.selectable() && !self.pane_is_hidden(pane_id))
+                .collect()
         } else {
-            self.panes.iter().collect()
+            self.panes
+                .iter()
+                .filter(|(pane_id, _)| !self.pane_is_hidden(pane_id))
+                .collect()
         };
         panes.sort_by(|(a_id, _a_pane), (b_id, _b_pane)| {
             // TODO: continue
             Ord::cmp(
-                &self.z_indices.iter().position(|id| id == *b_id).unwrap(),
-                &self.z_indices.iter().position(|id| id == *a_id).unwrap(),
+                &self.stacking_order(b_id).unwrap(),
+                &self.stacking_order(a_id).unwrap(),
             )
         });
         Ok(panes
@@ -832,8 +949,8 @@ impl FloatingPanes {
         panes.sort_by(|(a_id, _a_pane), (b_id, _b_pane)| {
             // TODO: continue
             Ord::cmp(
-                &self.z_indices.iter().position(|id| id == *b_id).unwrap(),
-                &self.z_indices.iter().position(|id| id == *a_id).unwrap(),
+                &self.stacking_order(b_id).unwrap(),
+                &self.stacking_order(a_id).unwrap(),
             )
         });
         Ok(panes
@@ -850,8 +967,8 @@ impl FloatingPanes {
 
         panes.sort_by(|(a_id, _a_pane), (b_id, _b_pane)| {
             Ord::cmp(
-                &self.z_indices.iter().position(|id| id == *b_id).unwrap(),
-                &self.z_indices.iter().position(|id| id == *a_id).unwrap(),
+                &self.stacking_order(b_id).unwrap(),
+                &self.stacking_order(a_id).unwrap(),
             )
         });
         panes.iter().find(|(_, p)| p.contains(point)).is_some()
@@ -894,6 +1011,19 @@ impl FloatingPanes {
         self.set_force_render();
         true
     }
+    pub fn move_pane_to_coordinates(&mut self, pane_id: PaneId, x: usize, y: usize) -> Result<()> {
+        let display_area = *self.display_area.borrow();
+        let viewport = *self.viewport.borrow();
+        let mut floating_pane_grid = FloatingPaneGrid::new(
+            &mut self.panes,
+            &mut self.desired_pane_positions,
+            display_area,
+            viewport,
+        );
+        floating_pane_grid.move_pane_to(pane_id, x, y)?;
+        self.set_force_render();
+        Ok(())
+    }
     pub fn move_pane_with_mouse(&mut self, position: Position, search_selectable: bool) -> bool {
         // true => handled, false => not handled (eg. no pane at this position)
         let show_panes = self.show_panes;
@@ -933,8 +1063,15 @@ impl FloatingPanes {
     pub fn visible_panes_count(&self) -> usize {
         self.panes.len()
     }
+    pub fn visible_plugin_panes_count(&self) -> usize {
+        self.panes
+            .keys()
+            .filter(|p| matches!(p, PaneId::Plugin(_)))
+            .count()
+    }
     pub fn drain(&mut self) -> BTreeMap<PaneId, Box<dyn Pane>> {
         self.z_indices.clear();
+        self.pane_z_indices.clear();
         self.desired_pane_positions.clear();
         match self.panes.iter().next().map(|(pid, _p)| *pid) {
             Some(first_pid) => self.panes.split_off(&first_pid),
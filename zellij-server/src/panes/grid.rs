@@ -1,9 +1,11 @@
-use super::sixel::{PixelRect, SixelGrid, SixelImageStore};
+use super::sixel::{
+    PixelRect, SixelGrid, SixelImageStore, SixelRasterizer, FALLBACK_CHARACTER_CELL_SIZE,
+};
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
-use zellij_utils::data::Style;
+use zellij_utils::data::{Cell, Style};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::regex::Regex;
 
@@ -15,7 +17,7 @@ use std::{
 };
 
 use zellij_utils::{
-    consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
+    consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE, SIXEL_SUPPORT},
     data::{Palette, PaletteColor},
     input::mouse::{MouseEvent, MouseEventType},
     pane_size::SizeInPixels,
@@ -36,10 +38,14 @@ use crate::panes::search::SearchResult;
 use crate::panes::selection::Selection;
 use crate::panes::terminal_character::{
     AnsiCode, CharsetIndex, Cursor, CursorShape, RcCharacterStyles, StandardCharset,
-    TerminalCharacter, EMPTY_TERMINAL_CHARACTER,
+    TerminalCharacter, EMPTY_TERMINAL_CHARACTER, RESET_STYLES,
 };
 use crate::ui::components::UiComponentParser;
 
+fn sixel_support() -> bool {
+    SIXEL_SUPPORT.get().copied().unwrap_or(true)
+}
+
 fn get_top_non_canonical_rows(rows: &mut Vec<Row>) -> Vec<Row> {
     let mut index_of_last_non_canonical_row = None;
     for (i, row) in rows.iter().enumerate() {
@@ -122,6 +128,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     sixel_grid: &mut SixelGrid,
     count: usize,
     max_viewport_width: usize,
+    scrollback_size: usize,
 ) -> usize {
     let mut next_lines: Vec<Row> = vec![];
     let mut lines_added_to_viewport: isize = 0;
@@ -151,7 +158,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     }
     if !next_lines.is_empty() {
         let excess_row = Row::from_rows(next_lines);
-        bounded_push(lines_above, sixel_grid, excess_row);
+        bounded_push(lines_above, sixel_grid, excess_row, scrollback_size);
     }
     match usize::try_from(lines_added_to_viewport) {
         Ok(n) => n,
@@ -165,6 +172,7 @@ fn transfer_rows_from_viewport_to_lines_above(
     sixel_grid: &mut SixelGrid,
     count: usize,
     max_viewport_width: usize,
+    scrollback_size: usize,
 ) -> isize {
     let mut transferred_rows_count: isize = 0;
     let drained_lines = std::cmp::min(count, viewport.len());
@@ -178,7 +186,12 @@ fn transfer_rows_from_viewport_to_lines_above(
             next_lines.append(&mut bottom_canonical_row_and_wraps_in_dst);
         }
         next_lines.push(next_line);
-        let dropped_line_width = bounded_push(lines_above, sixel_grid, Row::from_rows(next_lines));
+        let dropped_line_width = bounded_push(
+            lines_above,
+            sixel_grid,
+            Row::from_rows(next_lines),
+            scrollback_size,
+        );
         if let Some(width) = dropped_line_width {
             transferred_rows_count -=
                 calculate_row_display_height(width, max_viewport_width) as isize;
@@ -227,9 +240,14 @@ fn transfer_rows_from_lines_below_to_viewport(
     }
 }
 
-fn bounded_push(vec: &mut VecDeque<Row>, sixel_grid: &mut SixelGrid, value: Row) -> Option<usize> {
+fn bounded_push(
+    vec: &mut VecDeque<Row>,
+    sixel_grid: &mut SixelGrid,
+    value: Row,
+    max_len: usize,
+) -> Option<usize> {
     let mut dropped_line_width = None;
-    if vec.len() >= *SCROLL_BUFFER_SIZE.get().unwrap() {
+    if vec.len() >= max_len {
         let line = vec.pop_front();
         if let Some(line) = line {
             sixel_grid.offset_grid_top();
@@ -274,6 +292,9 @@ macro_rules! dump_screen {
         let mut buf = "".to_owned();
 
         for line in &$lines {
+            if line.is_annotation {
+                continue;
+            }
             if line.is_canonical && !is_first {
                 buf.push_str("\n");
             }
@@ -333,6 +354,7 @@ pub struct Grid {
     title_stack: Vec<String>,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     sixel_grid: SixelGrid,
+    sixel_rasterizer: Option<SixelRasterizer>, // images we approximate with half-block characters
     pub changed_colors: Option<[Option<AnsiCode>; 256]>,
     pub should_render: bool,
     pub lock_renders: bool,
@@ -359,6 +381,7 @@ pub struct Grid {
     pub search_results: SearchResult,
     pub pending_clipboard_update: Option<String>,
     ui_component_bytes: Option<Vec<u8>>,
+    scrollback_size: Option<usize>, // None means the global SCROLL_BUFFER_SIZE
     style: Style,
     debug: bool,
     arrow_fonts: bool,
@@ -504,6 +527,7 @@ impl Grid {
             character_cell_size,
             search_results: Default::default(),
             sixel_grid,
+            sixel_rasterizer: None,
             pending_clipboard_update: None,
             ui_component_bytes: None,
             style,
@@ -513,6 +537,7 @@ impl Grid {
             lock_renders: false,
             supports_kitty_keyboard_protocol: false,
             explicitly_disable_kitty_keyboard_protocol,
+            scrollback_size: None,
         }
     }
     pub fn render_full_viewport(&mut self) {
@@ -567,6 +592,22 @@ impl Grid {
         )
     }
 
+    pub fn scrollback_size(&self) -> usize {
+        self.scrollback_size
+            .unwrap_or_else(|| *SCROLL_BUFFER_SIZE.get().unwrap())
+    }
+    pub fn set_scrollback_size(&mut self, scrollback_size: usize) {
+        self.scrollback_size = Some(scrollback_size);
+        if self.lines_above.len() > scrollback_size {
+            for _ in scrollback_size..self.lines_above.len() {
+                self.lines_above.pop_front();
+                self.sixel_grid.offset_grid_top();
+            }
+            self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+            self.output_buffer.update_all_lines();
+        }
+    }
+
     fn recalculate_scrollback_buffer_count(&self) -> usize {
         let mut scrollback_buffer_count = 0;
         for row in &self.lines_above {
@@ -676,12 +717,14 @@ impl Grid {
             let line_to_push_down = self.viewport.pop().unwrap();
             self.lines_below.insert(0, line_to_push_down);
 
+            let scrollback_size = self.scrollback_size();
             let transferred_rows_height = transfer_rows_from_lines_above_to_viewport(
                 &mut self.lines_above,
                 &mut self.viewport,
                 &mut self.sixel_grid,
                 1,
                 self.width,
+                scrollback_size,
             );
             self.scrollback_buffer_lines = self
                 .scrollback_buffer_lines
@@ -720,8 +763,13 @@ impl Grid {
                 }
             };
 
-            let dropped_line_width =
-                bounded_push(&mut self.lines_above, &mut self.sixel_grid, line_to_push_up);
+            let scrollback_size = self.scrollback_size();
+            let dropped_line_width = bounded_push(
+                &mut self.lines_above,
+                &mut self.sixel_grid,
+                line_to_push_up,
+                scrollback_size,
+            );
             if let Some(width) = dropped_line_width {
                 let dropped_line_height = calculate_row_display_height(width, self.width);
 
@@ -772,6 +820,7 @@ impl Grid {
         if new_columns == 0 || new_rows == 0 {
             return;
         }
+        let scrollback_size = self.scrollback_size();
         if self.alternate_screen_state.is_some() {
             // in alternate screen we do nothing but log the new size, the program in the terminal
             // is in control now...
@@ -943,6 +992,7 @@ impl Grid {
                         &mut self.sixel_grid,
                         row_count_to_transfer,
                         new_columns,
+                        scrollback_size,
                     );
                     let rows_pulled = self.viewport.len() - current_viewport_row_count;
                     new_cursor_y += rows_pulled;
@@ -970,6 +1020,7 @@ impl Grid {
                         &mut self.sixel_grid,
                         row_count_to_transfer,
                         new_columns,
+                        scrollback_size,
                     );
                 },
                 Ordering::Equal => {},
@@ -1067,9 +1118,11 @@ impl Grid {
                 };
                 let mut to_serialize = vec![];
                 for line in self.lines_above.iter().skip(first_index) {
-                    to_serialize.push(line.clone());
+                    if !line.is_annotation {
+                        to_serialize.push(line.clone());
+                    }
                 }
-                for line in &self.viewport {
+                for line in self.viewport.iter().filter(|line| !line.is_annotation) {
                     to_serialize.push(line.clone())
                 }
                 self.output_buffer.serialize(to_serialize.as_slice()).ok()
@@ -1177,6 +1230,54 @@ impl Grid {
         scrollback.push_str(&viewport);
         scrollback
     }
+    /// The cells currently displayed in the viewport, padded to the size of the grid
+    pub fn snapshot(&self, default_fg: PaletteColor, default_bg: PaletteColor) -> Vec<Vec<Cell>> {
+        let empty_cell = Cell {
+            character: ' ',
+            fg: default_fg,
+            bg: default_bg,
+            bold: false,
+        };
+        let mut rows = Vec::with_capacity(self.height);
+        for row_index in 0..self.height {
+            let mut cells = Vec::with_capacity(self.width);
+            if let Some(row) = self.viewport.get(row_index) {
+                for terminal_character in &row.columns {
+                    let styles = &terminal_character.styles;
+                    let mut fg = styles
+                        .foreground
+                        .and_then(|fg| fg.to_palette_color())
+                        .unwrap_or(default_fg);
+                    let mut bg = styles
+                        .background
+                        .and_then(|bg| bg.to_palette_color())
+                        .unwrap_or(default_bg);
+                    if let Some(AnsiCode::On) = styles.reverse {
+                        std::mem::swap(&mut fg, &mut bg);
+                    }
+                    let cell = Cell {
+                        character: terminal_character.character,
+                        fg,
+                        bg,
+                        bold: styles.bold == Some(AnsiCode::On),
+                    };
+                    cells.push(cell);
+                    // wide characters take up more than one column, we pad them so that each
+                    // cell is exactly one column
+                    for _ in 1..terminal_character.width() {
+                        cells.push(Cell {
+                            character: ' ',
+                            ..cell
+                        });
+                    }
+                }
+            }
+            cells.truncate(self.width);
+            cells.resize(self.width, empty_cell);
+            rows.push(cells);
+        }
+        rows
+    }
     pub fn move_viewport_up(&mut self, count: usize) {
         for _ in 0..count {
             self.scroll_up_one_line();
@@ -1190,7 +1291,7 @@ impl Grid {
         self.output_buffer.update_all_lines();
     }
     pub fn reset_viewport(&mut self) {
-        let max_lines_to_scroll = *SCROLL_BUFFER_SIZE.get().unwrap() * 2; // while not very elegant, this can prevent minor bugs from becoming showstoppers by sticking the whole app display in an endless loop
+        let max_lines_to_scroll = self.scrollback_size() * 2; // while not very elegant, this can prevent minor bugs from becoming showstoppers by sticking the whole app display in an endless loop
         let mut lines_scrolled = 0;
         let should_clear_output_buffer = self.is_scrolled;
         while self.is_scrolled && lines_scrolled < max_lines_to_scroll {
@@ -1201,6 +1302,91 @@ impl Grid {
             self.output_buffer.update_all_lines();
         }
     }
+    /// Scroll so that the given line is at the top of the viewport, lines are counted the same
+    /// way as in `dump_screen` (0 being the oldest line in the scrollback)
+    pub fn scroll_to_line(&mut self, line: usize) {
+        self.reset_viewport();
+        let lines_above_count = self.lines_above.len();
+        let row_index = self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .enumerate()
+            .filter(|(row_index, row)| {
+                !row.is_annotation
+                    && (*row_index == 0 || *row_index == lines_above_count || row.is_canonical)
+            })
+            .nth(line)
+            .map(|(row_index, _)| row_index);
+        if let Some(row_index) = row_index {
+            self.move_viewport_up(lines_above_count.saturating_sub(row_index));
+        }
+    }
+    /// Insert a read-only line above the given line of the scrollback (counted the same way as in
+    /// `scroll_to_line`), annotations are not part of the terminal output and so are left out of
+    /// `dump_screen`, `serialize` and copied selections
+    pub fn annotate_scrollback_line(&mut self, line: usize, annotation: &str) {
+        let top_line_when_scrolled = self.top_line_when_scrolled();
+        self.reset_viewport();
+        let row_index = self.scrollback_line_start_rows().nth(line);
+        match row_index {
+            Some(row_index) => {
+                let styles: RcCharacterStyles = RESET_STYLES
+                    .background(Some(self.style.colors.blue.into()))
+                    .foreground(Some(self.style.colors.black.into()))
+                    .into();
+                for annotation_line in annotation.lines().rev() {
+                    let mut columns: VecDeque<TerminalCharacter> = annotation_line
+                        .chars()
+                        .map(|c| TerminalCharacter::new_styled(c, styles.clone()))
+                        .collect();
+                    let width: usize = columns.iter().map(|c| c.width()).sum();
+                    for _ in width..self.width {
+                        columns.push_back(TerminalCharacter::new_styled(' ', styles.clone()));
+                    }
+                    let mut row = Row::from_columns(columns).canonical();
+                    row.is_annotation = true;
+                    self.lines_above.insert(row_index, row);
+                }
+            },
+            None => {
+                log::error!("Cannot annotate line {}, it is not in the scrollback", line);
+            },
+        }
+        self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+        if let Some(top_line) = top_line_when_scrolled {
+            self.scroll_to_line(top_line);
+        }
+        self.output_buffer.update_all_lines();
+    }
+    pub fn clear_scrollback_annotations(&mut self) {
+        let top_line_when_scrolled = self.top_line_when_scrolled();
+        self.reset_viewport();
+        self.lines_above.retain(|row| !row.is_annotation);
+        self.viewport.retain(|row| !row.is_annotation);
+        self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+        if let Some(top_line) = top_line_when_scrolled {
+            self.scroll_to_line(top_line);
+        }
+        self.output_buffer.update_all_lines();
+    }
+    // the indices of the rows in lines_above that start a line of the terminal output
+    fn scrollback_line_start_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.lines_above
+            .iter()
+            .enumerate()
+            .filter(|(row_index, row)| !row.is_annotation && (*row_index == 0 || row.is_canonical))
+            .map(|(row_index, _)| row_index)
+    }
+    // the line at the top of the viewport, so that the viewport can be scrolled back to it after
+    // changing the scrollback
+    fn top_line_when_scrolled(&self) -> Option<usize> {
+        if self.is_scrolled {
+            Some(self.scrollback_line_start_rows().count())
+        } else {
+            None
+        }
+    }
     pub fn rotate_scroll_region_up(&mut self, count: usize) {
         if let Some((scroll_region_top, scroll_region_bottom)) = self
             .scroll_region
@@ -1815,6 +2001,10 @@ impl Grid {
                 continue;
             };
 
+            if row.is_annotation {
+                continue;
+            }
+
             let mut terminal_col = 0;
             for terminal_character in &row.columns {
                 if (start_column..end_column).contains(&terminal_col) {
@@ -1872,12 +2062,14 @@ impl Grid {
         }
     }
     fn transfer_rows_to_lines_above(&mut self, count: usize) {
+        let scrollback_size = self.scrollback_size();
         let transferred_rows_count = transfer_rows_from_viewport_to_lines_above(
             &mut self.viewport,
             &mut self.lines_above,
             &mut self.sixel_grid,
             count,
             self.width,
+            scrollback_size,
         );
 
         self.scrollback_buffer_lines =
@@ -1934,6 +2126,35 @@ impl Grid {
             }
         }
     }
+    fn add_sixel_image_as_half_blocks(&mut self, sixel_rasterizer: SixelRasterizer) {
+        let image_left_edge = self.cursor.x;
+        for row in sixel_rasterizer.half_blocks(FALLBACK_CHARACTER_CELL_SIZE) {
+            self.move_cursor_to_column(image_left_edge);
+            for half_block in row {
+                let (character, styles) = match half_block {
+                    (Some(top), bottom) => (
+                        '▀',
+                        RESET_STYLES
+                            .foreground(Some(AnsiCode::RgbCode(top)))
+                            .background(bottom.map(AnsiCode::RgbCode)),
+                    ),
+                    (None, Some(bottom)) => (
+                        '▄',
+                        RESET_STYLES.foreground(Some(AnsiCode::RgbCode(bottom))),
+                    ),
+                    (None, None) => (' ', RESET_STYLES),
+                };
+                self.add_character(TerminalCharacter::new_singlewidth_styled(
+                    character,
+                    styles.into(),
+                ));
+            }
+            // like with sixel images, the cursor ends up below the image
+            self.add_canonical_line();
+        }
+        self.move_cursor_to_column(image_left_edge);
+        self.render_full_viewport();
+    }
     fn mouse_buttons_value_x10(&self, event: &MouseEvent) -> u8 {
         let mut value = 35; // Default to no buttons down.
         if event.event_type == MouseEventType::Release {
@@ -2327,8 +2548,12 @@ impl Perform for Grid {
 
     fn hook(&mut self, params: &Params, intermediates: &[u8], _ignore: bool, c: char) {
         if c == 'q' {
-            // we only process sixel images if we know the pixel size of each character cell,
-            // otherwise we can't reliably display them
+            if !sixel_support() {
+                return;
+            }
+            // we only display sixel images if we know the pixel size of each character cell,
+            // otherwise the terminal we run in most likely can't display them and we approximate
+            // them with half-block characters instead
             if self.current_cursor_pixel_coordinates().is_some() {
                 let max_sixel_height_in_pixels = if self.sixel_scrolling {
                     let character_cell_height = self.character_cell_size.borrow().unwrap().height; // unwrap here is safe because `current_cursor_pixel_coordinates` above is only Some if it exists
@@ -2341,6 +2566,11 @@ impl Perform for Grid {
                     intermediates.iter().collect(),
                     params.iter().collect(),
                 );
+            } else {
+                self.sixel_rasterizer = Some(SixelRasterizer::new(
+                    self.width.saturating_sub(self.cursor.x) * FALLBACK_CHARACTER_CELL_SIZE.width,
+                    self.height * FALLBACK_CHARACTER_CELL_SIZE.height,
+                ));
             }
         } else if c == 'z' {
             // UI-component (Zellij internal)
@@ -2354,6 +2584,9 @@ impl Perform for Grid {
             // we explicitly set this to false here because in the context of Sixel, we only render the
             // image when it's done, i.e. in the unhook method
             self.should_render = false;
+        } else if let Some(sixel_rasterizer) = self.sixel_rasterizer.as_mut() {
+            sixel_rasterizer.handle_byte(byte);
+            self.should_render = false;
         } else if let Some(ui_component_bytes) = self.ui_component_bytes.as_mut() {
             ui_component_bytes.push(byte);
         }
@@ -2362,6 +2595,8 @@ impl Perform for Grid {
     fn unhook(&mut self) {
         if self.sixel_grid.is_parsing() {
             self.create_sixel_image();
+        } else if let Some(sixel_rasterizer) = self.sixel_rasterizer.take() {
+            self.add_sixel_image_as_half_blocks(sixel_rasterizer);
         } else if let Some(mut ui_component_bytes) = self.ui_component_bytes.take() {
             let component_bytes = ui_component_bytes.drain(..);
             let style = self.style.clone();
@@ -2671,6 +2906,7 @@ impl Perform for Grid {
                                 alternate_screen_state.apply_contents_to(
                                     &mut self.lines_above,
                                     &mut self.viewport,
+                                    &mut self.lines_below,
                                     &mut self.cursor,
                                     &mut self.sixel_grid,
                                     &mut self.supports_kitty_keyboard_protocol,
@@ -2757,12 +2993,24 @@ impl Perform for Grid {
                         2004 => {
                             self.bracketed_paste_mode = true;
                         },
+                        1049 if self.alternate_screen_state.is_some() => {
+                            // already in the alternate buffer, entering it again must not replace
+                            // the primary buffer we saved with the alternate one
+                            self.viewport = vec![Row::new().canonical()];
+                            self.cursor.x = 0;
+                            self.cursor.y = 0;
+                            self.clear_viewport_before_rendering = true;
+                            self.output_buffer.update_all_lines();
+                        },
                         1049 => {
                             // enter alternate buffer
                             let current_lines_above =
                                 std::mem::replace(&mut self.lines_above, VecDeque::new());
                             let current_viewport =
                                 std::mem::replace(&mut self.viewport, vec![Row::new().canonical()]);
+                            // if the user was scrolled up, the lines below the viewport belong to
+                            // the primary screen as well and should be there when we come back
+                            let current_lines_below = std::mem::take(&mut self.lines_below);
                             let current_cursor = std::mem::replace(
                                 &mut self.cursor,
                                 Cursor::new(0, 0, self.styled_underlines),
@@ -2779,6 +3027,7 @@ impl Perform for Grid {
                             self.alternate_screen_state = Some(AlternateScreenState::new(
                                 current_lines_above,
                                 current_viewport,
+                                current_lines_below,
                                 current_cursor,
                                 alternate_sixelgrid,
                                 current_supports_kitty_keyboard_protocol,
@@ -3055,8 +3304,12 @@ impl Perform for Grid {
             // https://vt100.net/docs/vt510-rm/DA1.html
             match intermediates.get(0) {
                 None | Some(0) => {
-                    // primary device attributes - VT220 with sixel
-                    let terminal_capabilities = "\u{1b}[?62;4c";
+                    // primary device attributes - VT220, with sixel unless it was disabled
+                    let terminal_capabilities = if sixel_support() {
+                        "\u{1b}[?62;4c"
+                    } else {
+                        "\u{1b}[?62c"
+                    };
                     self.pending_messages_to_pty
                         .push(terminal_capabilities.as_bytes().to_vec());
                 },
@@ -3249,6 +3502,7 @@ impl Perform for Grid {
 pub struct AlternateScreenState {
     lines_above: VecDeque<Row>,
     viewport: Vec<Row>,
+    lines_below: Vec<Row>,
     cursor: Cursor,
     sixel_grid: SixelGrid,
     supports_kitty_keyboard_protocol: bool,
@@ -3257,6 +3511,7 @@ impl AlternateScreenState {
     pub fn new(
         lines_above: VecDeque<Row>,
         viewport: Vec<Row>,
+        lines_below: Vec<Row>,
         cursor: Cursor,
         sixel_grid: SixelGrid,
         supports_kitty_keyboard_protocol: bool,
@@ -3264,6 +3519,7 @@ impl AlternateScreenState {
         AlternateScreenState {
             lines_above,
             viewport,
+            lines_below,
             cursor,
             sixel_grid,
             supports_kitty_keyboard_protocol,
@@ -3273,12 +3529,14 @@ impl AlternateScreenState {
         &mut self,
         lines_above: &mut VecDeque<Row>,
         viewport: &mut Vec<Row>,
+        lines_below: &mut Vec<Row>,
         cursor: &mut Cursor,
         sixel_grid: &mut SixelGrid,
         supports_kitty_keyboard_protocol: &mut bool,
     ) {
         std::mem::swap(&mut self.lines_above, lines_above);
         std::mem::swap(&mut self.viewport, viewport);
+        std::mem::swap(&mut self.lines_below, lines_below);
         std::mem::swap(&mut self.cursor, cursor);
         std::mem::swap(&mut self.sixel_grid, sixel_grid);
         std::mem::swap(
@@ -3292,6 +3550,7 @@ impl AlternateScreenState {
 pub struct Row {
     pub columns: VecDeque<TerminalCharacter>,
     pub is_canonical: bool,
+    pub is_annotation: bool, // inserted by a plugin, not part of the terminal output
     width: Option<usize>,
 }
 
@@ -3309,6 +3568,7 @@ impl Row {
         Row {
             columns: VecDeque::new(),
             is_canonical: false,
+            is_annotation: false,
             width: None,
         }
     }
@@ -3316,6 +3576,7 @@ impl Row {
         Row {
             columns,
             is_canonical: false,
+            is_annotation: false,
             width: None,
         }
     }
@@ -3619,6 +3880,9 @@ impl Row {
         if parts.is_empty() {
             parts.push(self.clone());
         }
+        for part in parts.iter_mut() {
+            part.is_annotation = self.is_annotation;
+        }
         self.width = None;
         parts
     }
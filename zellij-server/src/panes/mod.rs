@@ -18,6 +18,7 @@ pub use floating_panes::*;
 pub use grid::*;
 pub use link_handler::*;
 pub(crate) use plugin_pane::*;
+pub(crate) use search::{find_matches_in_lines, find_regex_matches_in_lines};
 pub use sixel::*;
 pub(crate) use terminal_character::*;
 pub use terminal_pane::*;
@@ -10,7 +10,7 @@ use crate::panes::{
 };
 use crate::plugins::PluginInstruction;
 use crate::pty::VteBytes;
-use crate::tab::{AdjustedInput, Pane};
+use crate::tab::{AdjustedInput, Pane, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use crate::ui::{
     loading_indication::LoadingIndication,
     pane_boundaries_frame::{FrameParams, PaneFrame},
@@ -96,10 +96,14 @@ pub(crate) struct PluginPane {
     invoked_with: Option<Run>,
     loading_indication: LoadingIndication,
     requesting_permissions: Option<PluginPermission>,
+    // true between the start and the end of a bracketed paste
+    receiving_paste: bool,
     debug: bool,
     arrow_fonts: bool,
     styled_underlines: bool,
     should_be_suppressed: bool,
+    min_rows: usize,
+    min_cols: usize,
 }
 
 impl PluginPane {
@@ -150,10 +154,13 @@ impl PluginPane {
             invoked_with,
             loading_indication,
             requesting_permissions: None,
+            receiving_paste: false,
             debug,
             arrow_fonts,
             styled_underlines,
             should_be_suppressed: false,
+            min_rows: 0,
+            min_cols: 0,
         };
         for client_id in currently_connected_clients {
             plugin.handle_plugin_bytes(client_id, initial_loading_message.as_bytes().to_vec());
@@ -227,8 +234,10 @@ impl Pane for PluginPane {
 
         // this is part of the plugin contract, whenever we update the plugin and call its render function, we delete the existing viewport
         // and scroll, reset the cursor position and make sure all the viewport is rendered
+        // the cursor is hidden unless the plugin explicitly shows it (on every render)
         grid.delete_viewport_and_scroll();
         grid.reset_cursor_position();
+        grid.hide_cursor();
         grid.render_full_viewport();
 
         let vte_parser = self
@@ -245,6 +254,22 @@ impl Pane for PluginPane {
     fn cursor_coordinates(&self) -> Option<(usize, usize)> {
         None
     }
+    fn cursor_coordinates_for_client(&self, client_id: ClientId) -> Option<(usize, usize)> {
+        // (x, y)
+        if self.requesting_permissions.is_some()
+            || self.get_content_rows() < 1
+            || self.get_content_columns() < 1
+        {
+            return None;
+        }
+        // the grid only reports its cursor if it is visible, moving it past the edges of the grid
+        // clamps it to them
+        let Offset { top, left, .. } = self.content_offset;
+        self.grids
+            .get(&client_id)
+            .and_then(|grid| grid.cursor_coordinates())
+            .map(|(x, y)| (x + left, y + top))
+    }
     fn adjust_input_to_terminal(
         &mut self,
         key_with_modifier: &Option<KeyWithModifier>,
@@ -286,11 +311,16 @@ impl Pane for PluginPane {
             }
         } else if let Some(key_with_modifier) = key_with_modifier {
             Some(AdjustedInput::WriteKeyToPlugin(key_with_modifier.clone()))
-        } else if raw_input_bytes.as_slice() == BRACKETED_PASTE_BEGIN
-            || raw_input_bytes.as_slice() == BRACKETED_PASTE_END
-        {
-            // plugins do not need bracketed paste
+        } else if raw_input_bytes.as_slice() == BRACKETED_PASTE_BEGIN {
+            self.receiving_paste = true;
             None
+        } else if raw_input_bytes.as_slice() == BRACKETED_PASTE_END {
+            self.receiving_paste = false;
+            None
+        } else if self.receiving_paste {
+            Some(AdjustedInput::PasteToPlugin(
+                String::from_utf8_lossy(&raw_input_bytes).to_string(),
+            ))
         } else {
             Some(AdjustedInput::WriteBytesToTerminal(raw_input_bytes))
         }
@@ -327,6 +357,16 @@ impl Pane for PluginPane {
     fn set_selectable(&mut self, selectable: bool) {
         self.selectable = selectable;
     }
+    fn set_min_size(&mut self, min_rows: usize, min_cols: usize) {
+        self.min_rows = min_rows;
+        self.min_cols = min_cols;
+    }
+    fn min_width(&self) -> usize {
+        std::cmp::max(self.min_cols, MIN_TERMINAL_WIDTH)
+    }
+    fn min_height(&self) -> usize {
+        std::cmp::max(self.min_rows, MIN_TERMINAL_HEIGHT)
+    }
     fn request_permissions_from_user(&mut self, permissions: Option<PluginPermission>) {
         self.requesting_permissions = permissions;
     }
@@ -3,8 +3,10 @@ use crate::panes::terminal_character::TerminalCharacter;
 use crate::panes::{Grid, Row};
 use std::borrow::Cow;
 use std::fmt::Debug;
+use zellij_utils::data::SearchMatch;
 use zellij_utils::input::actions::SearchDirection;
 use zellij_utils::position::Position;
+use zellij_utils::regex::RegexBuilder;
 
 // If char is neither alphanumeric nor an underscore do we consider it a word-boundary
 fn is_word_boundary(x: &Option<char>) -> bool {
@@ -661,3 +663,82 @@ impl Grid {
         rows
     }
 }
+
+/// Finds all non-overlapping occurrences of `query` in each line of `contents` (eg. the output of
+/// `dump_screen`). Columns are counted in characters.
+pub(crate) fn find_matches_in_lines(
+    contents: &str,
+    query: &str,
+    case_sensitive: bool,
+) -> Vec<SearchMatch> {
+    let query: Vec<char> = query.chars().collect();
+    if query.is_empty() {
+        return vec![];
+    }
+    let chars_match = |a: &char, b: &char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+    let mut matches = vec![];
+    for (row, line) in contents.lines().enumerate() {
+        let line_chars: Vec<char> = line.chars().collect();
+        let mut col = 0;
+        while col + query.len() <= line_chars.len() {
+            let is_match = line_chars[col..col + query.len()]
+                .iter()
+                .zip(query.iter())
+                .all(|(a, b)| chars_match(a, b));
+            if is_match {
+                matches.push(SearchMatch {
+                    row,
+                    col_start: col,
+                    col_end: col + query.len(),
+                    context_line: line.to_owned(),
+                });
+                col += query.len();
+            } else {
+                col += 1;
+            }
+        }
+    }
+    matches
+}
+
+pub(crate) fn find_regex_matches_in_lines(
+    contents: &str,
+    pattern: &str,
+    case_sensitive: bool,
+) -> Vec<SearchMatch> {
+    let regex = match RegexBuilder::new(pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+    {
+        Ok(regex) => regex,
+        Err(e) => {
+            log::error!("Invalid search pattern {:?}: {}", pattern, e);
+            return vec![];
+        },
+    };
+    let mut matches = vec![];
+    for (row, line) in contents.lines().enumerate() {
+        for regex_match in regex.find_iter(line) {
+            // empty matches (eg. for "^") would not point at anything
+            if regex_match.start() == regex_match.end() {
+                continue;
+            }
+            // SearchMatch columns are in characters rather than in bytes
+            let col_start = line[..regex_match.start()].chars().count();
+            let col_end = col_start + regex_match.as_str().chars().count();
+            matches.push(SearchMatch {
+                row,
+                col_start,
+                col_end,
+                context_line: line.to_owned(),
+            });
+        }
+    }
+    matches
+}
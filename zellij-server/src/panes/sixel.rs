@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use sixel_image::{SixelDeserializer, SixelImage};
-use sixel_tokenizer::SixelEvent;
+use sixel_tokenizer::{ColorCoordinateSystem, SixelEvent};
 
 use std::fmt::Debug;
 
@@ -464,3 +464,229 @@ impl SixelImageStore {
         self.sixel_images.len()
     }
 }
+
+/// The size we assume character cells have when approximating sixel images with half-block
+/// characters, the terminal we run in usually does not tell us when it does not support sixel
+pub const FALLBACK_CHARACTER_CELL_SIZE: SizeInPixels = SizeInPixels {
+    height: 20,
+    width: 10,
+};
+
+// the default color registers of the VT340, in percent
+const DEFAULT_COLOR_REGISTERS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (20, 20, 80),
+    (80, 13, 13),
+    (20, 80, 20),
+    (80, 20, 80),
+    (20, 80, 80),
+    (80, 80, 20),
+    (53, 53, 53),
+    (26, 26, 26),
+    (33, 33, 60),
+    (60, 26, 26),
+    (33, 60, 33),
+    (60, 33, 60),
+    (33, 60, 60),
+    (60, 60, 33),
+    (80, 80, 80),
+];
+
+/// The colors of the top and bottom halves of a character cell, None where the image is
+/// transparent
+pub type HalfBlock = (Option<(u8, u8, u8)>, Option<(u8, u8, u8)>);
+
+/// Draws a sixel image into plain RGB pixels, for terminals that cannot display the image itself
+#[derive(Debug, Clone)]
+pub struct SixelRasterizer {
+    parser: sixel_tokenizer::Parser,
+    color_registers: HashMap<u16, (u8, u8, u8)>,
+    current_color: (u8, u8, u8),
+    cursor_x: usize,
+    cursor_y: usize,
+    max_width: usize,
+    max_height: usize,
+    pixels: Vec<Vec<Option<(u8, u8, u8)>>>,
+}
+
+impl SixelRasterizer {
+    /// Pixels beyond the maximum width and height are discarded
+    pub fn new(max_width: usize, max_height: usize) -> Self {
+        let mut parser = sixel_tokenizer::Parser::new();
+        // the DCS introducer was already consumed by the terminal parser
+        for byte in [27, b'P', b'q'] {
+            parser.advance(&byte, |_| {});
+        }
+        SixelRasterizer {
+            parser,
+            color_registers: HashMap::new(),
+            current_color: color_register_default(0),
+            cursor_x: 0,
+            cursor_y: 0,
+            max_width,
+            max_height,
+            pixels: vec![],
+        }
+    }
+    pub fn handle_byte(&mut self, byte: u8) {
+        let mut events = vec![];
+        self.parser
+            .advance(&byte, |sixel_event| events.push(sixel_event));
+        for sixel_event in events {
+            self.handle_event(sixel_event);
+        }
+    }
+    fn handle_event(&mut self, sixel_event: SixelEvent) {
+        match sixel_event {
+            SixelEvent::ColorIntroducer {
+                color_number,
+                color_coordinate_system,
+            } => {
+                if let Some(color_coordinate_system) = color_coordinate_system {
+                    self.color_registers
+                        .insert(color_number, to_rgb(color_coordinate_system));
+                }
+                self.current_color = self
+                    .color_registers
+                    .get(&color_number)
+                    .copied()
+                    .unwrap_or_else(|| color_register_default(color_number));
+            },
+            SixelEvent::Data { byte } => self.draw_sixel(byte, 1),
+            SixelEvent::Repeat {
+                repeat_count,
+                byte_to_repeat,
+            } => self.draw_sixel(byte_to_repeat, repeat_count),
+            SixelEvent::GotoBeginningOfLine => {
+                self.cursor_x = 0;
+            },
+            SixelEvent::GotoNextLine => {
+                self.cursor_x = 0;
+                self.cursor_y += 6;
+            },
+            _ => {},
+        }
+    }
+    fn draw_sixel(&mut self, byte: u8, repeat_count: usize) {
+        // each sixel byte is a column of 6 pixels, the least significant bit being the top one
+        let bits = byte.saturating_sub(63);
+        let x_start = std::cmp::min(self.cursor_x, self.max_width);
+        let x_end = std::cmp::min(self.cursor_x.saturating_add(repeat_count), self.max_width);
+        for bit_index in 0..6 {
+            let y = self.cursor_y + bit_index;
+            if bits & (1 << bit_index) == 0 || y >= self.max_height || x_start == x_end {
+                continue;
+            }
+            if self.pixels.len() <= y {
+                self.pixels.resize(y + 1, vec![]);
+            }
+            let row = &mut self.pixels[y];
+            if row.len() < x_end {
+                row.resize(x_end, None);
+            }
+            for pixel in &mut row[x_start..x_end] {
+                *pixel = Some(self.current_color);
+            }
+        }
+        self.cursor_x = self.cursor_x.saturating_add(repeat_count);
+    }
+    /// The image as rows of character cells, each split into a top and a bottom half
+    pub fn half_blocks(&self, character_cell_size: SizeInPixels) -> Vec<Vec<HalfBlock>> {
+        let cell_width = std::cmp::max(character_cell_size.width, 1);
+        let cell_height = std::cmp::max(character_cell_size.height, 2);
+        let image_width = self.pixels.iter().map(|row| row.len()).max().unwrap_or(0);
+        let image_height = self.pixels.len();
+        let columns = (image_width + cell_width - 1) / cell_width;
+        let rows = (image_height + cell_height - 1) / cell_height;
+        let half_cell_height = cell_height / 2;
+        (0..rows)
+            .map(|row| {
+                let top = row * cell_height;
+                (0..columns)
+                    .map(|column| {
+                        let left = column * cell_width;
+                        (
+                            self.average_color(left, top, cell_width, half_cell_height),
+                            self.average_color(
+                                left,
+                                top + half_cell_height,
+                                cell_width,
+                                cell_height - half_cell_height,
+                            ),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    // None if less than half of the area was drawn
+    fn average_color(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Option<(u8, u8, u8)> {
+        let mut drawn_pixels = 0;
+        let mut rgb_sums = (0, 0, 0);
+        for row in self.pixels.iter().skip(y).take(height) {
+            for (r, g, b) in row.iter().skip(x).take(width).flatten() {
+                drawn_pixels += 1;
+                rgb_sums.0 += *r as usize;
+                rgb_sums.1 += *g as usize;
+                rgb_sums.2 += *b as usize;
+            }
+        }
+        if drawn_pixels == 0 || drawn_pixels * 2 < width * height {
+            return None;
+        }
+        Some((
+            (rgb_sums.0 / drawn_pixels) as u8,
+            (rgb_sums.1 / drawn_pixels) as u8,
+            (rgb_sums.2 / drawn_pixels) as u8,
+        ))
+    }
+}
+
+fn color_register_default(color_number: u16) -> (u8, u8, u8) {
+    let (r, g, b) = DEFAULT_COLOR_REGISTERS
+        .get(color_number as usize)
+        .copied()
+        .unwrap_or(DEFAULT_COLOR_REGISTERS[15]);
+    (
+        percent_to_u8(r as usize),
+        percent_to_u8(g as usize),
+        percent_to_u8(b as usize),
+    )
+}
+
+fn percent_to_u8(percent: usize) -> u8 {
+    (std::cmp::min(percent, 100) * 255 / 100) as u8
+}
+
+fn to_rgb(color_coordinate_system: ColorCoordinateSystem) -> (u8, u8, u8) {
+    match color_coordinate_system {
+        ColorCoordinateSystem::RGB(r, g, b) => {
+            (percent_to_u8(r), percent_to_u8(g), percent_to_u8(b))
+        },
+        ColorCoordinateSystem::HLS(hue, lightness, saturation) => {
+            // sixel hues start at blue rather than at red
+            let hue = ((hue + 240) % 360) as f64;
+            let lightness = std::cmp::min(lightness, 100) as f64 / 100.0;
+            let saturation = std::cmp::min(saturation, 100) as f64 / 100.0;
+            let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+            let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+            let (r, g, b) = match hue as usize / 60 {
+                0 => (chroma, x, 0.0),
+                1 => (x, chroma, 0.0),
+                2 => (0.0, chroma, x),
+                3 => (0.0, x, chroma),
+                4 => (x, 0.0, chroma),
+                _ => (chroma, 0.0, x),
+            };
+            let m = lightness - chroma / 2.0;
+            let to_u8 = |c: f64| ((c + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+            (to_u8(r), to_u8(g), to_u8(b))
+        },
+    }
+}
@@ -152,6 +152,40 @@ impl NamedColor {
 
         v.to_string()
     }
+    fn to_color_index(self) -> u8 {
+        match self {
+            NamedColor::Black => 0,
+            NamedColor::Red => 1,
+            NamedColor::Green => 2,
+            NamedColor::Yellow => 3,
+            NamedColor::Blue => 4,
+            NamedColor::Magenta => 5,
+            NamedColor::Cyan => 6,
+            NamedColor::White => 7,
+            NamedColor::BrightBlack => 8,
+            NamedColor::BrightRed => 9,
+            NamedColor::BrightGreen => 10,
+            NamedColor::BrightYellow => 11,
+            NamedColor::BrightBlue => 12,
+            NamedColor::BrightMagenta => 13,
+            NamedColor::BrightCyan => 14,
+            NamedColor::BrightWhite => 15,
+        }
+    }
+}
+
+impl AnsiCode {
+    /// None for codes that do not describe a color (eg. the terminal's default color)
+    pub fn to_palette_color(&self) -> Option<PaletteColor> {
+        match self {
+            AnsiCode::NamedColor(named_color) => {
+                Some(PaletteColor::EightBit(named_color.to_color_index()))
+            },
+            AnsiCode::RgbCode(rgb) => Some(PaletteColor::Rgb(*rgb)),
+            AnsiCode::ColorIndex(index) => Some(PaletteColor::EightBit(*index)),
+            _ => None,
+        }
+    }
 }
 
 // This enum carefully only has two variants so
@@ -6,7 +6,7 @@ use crate::panes::{
     terminal_character::{render_first_run_banner, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
 };
 use crate::pty::VteBytes;
-use crate::tab::{AdjustedInput, Pane};
+use crate::tab::{AdjustedInput, Pane, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use crate::ClientId;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -18,8 +18,8 @@ use zellij_utils::input::mouse::{MouseEvent, MouseEventType};
 use zellij_utils::pane_size::Offset;
 use zellij_utils::{
     data::{
-        BareKey, InputMode, KeyWithModifier, Palette, PaletteColor, PaneId as ZellijUtilsPaneId,
-        Style,
+        BareKey, Cell, InputMode, KeyWithModifier, Palette, PaletteColor,
+        PaneId as ZellijUtilsPaneId, Style,
     },
     errors::prelude::*,
     input::layout::Run,
@@ -137,9 +137,14 @@ pub struct TerminalPane {
     banner: Option<String>, // a banner to be rendered inside this TerminalPane, used for panes
     // held on startup and can possibly be used to display some errors
     pane_frame_color_override: Option<(PaletteColor, Option<String>)>,
+    has_output: bool,
+    startup_command_timed_out: bool, // the frame color override is ours to clear once output arrives
+    synchronized_group: Option<String>, // the name of the group this pane shares its input with
     invoked_with: Option<Run>,
     #[allow(dead_code)]
     arrow_fonts: bool,
+    min_rows: usize,
+    min_cols: usize,
 }
 
 impl Pane for TerminalPane {
@@ -190,6 +195,11 @@ impl Pane for TerminalPane {
     }
     fn handle_pty_bytes(&mut self, bytes: VteBytes) {
         self.set_should_render(true);
+        self.has_output = true;
+        if self.startup_command_timed_out {
+            self.startup_command_timed_out = false;
+            self.clear_pane_frame_color_override();
+        }
         for &byte in &bytes {
             self.vte_parser.advance(&mut self.grid, byte);
         }
@@ -302,6 +312,16 @@ impl Pane for TerminalPane {
     fn set_selectable(&mut self, selectable: bool) {
         self.selectable = selectable;
     }
+    fn set_min_size(&mut self, min_rows: usize, min_cols: usize) {
+        self.min_rows = min_rows;
+        self.min_cols = min_cols;
+    }
+    fn min_width(&self) -> usize {
+        std::cmp::max(self.min_cols, MIN_TERMINAL_WIDTH)
+    }
+    fn min_height(&self) -> usize {
+        std::cmp::max(self.min_rows, MIN_TERMINAL_HEIGHT)
+    }
     fn render(
         &mut self,
         _client_id: Option<ClientId>,
@@ -386,7 +406,8 @@ impl Pane for TerminalPane {
             pane_title,
             frame_params,
         )
-        .is_pinned(is_pinned);
+        .is_pinned(is_pinned)
+        .synchronized_group(self.synchronized_group.clone());
         if let Some((exit_status, is_first_run, _run_command)) = &self.is_held {
             if *is_first_run {
                 frame.indicate_first_run();
@@ -523,6 +544,9 @@ impl Pane for TerminalPane {
     fn dump_screen(&self, full: bool) -> String {
         self.grid.dump_screen(full)
     }
+    fn snapshot(&self, default_fg: PaletteColor, default_bg: PaletteColor) -> Vec<Vec<Cell>> {
+        self.grid.snapshot(default_fg, default_bg)
+    }
     fn clear_screen(&mut self) {
         self.grid.clear_screen()
     }
@@ -538,6 +562,18 @@ impl Pane for TerminalPane {
         self.grid.reset_viewport();
         self.set_should_render(true);
     }
+    fn scroll_to_line(&mut self, line: usize) {
+        self.grid.scroll_to_line(line);
+        self.set_should_render(true);
+    }
+    fn annotate_scrollback_line(&mut self, line: usize, annotation: &str) {
+        self.grid.annotate_scrollback_line(line, annotation);
+        self.set_should_render(true);
+    }
+    fn clear_scrollback_annotations(&mut self) {
+        self.grid.clear_scrollback_annotations();
+        self.set_should_render(true);
+    }
     fn is_scrolled(&self) -> bool {
         self.grid.is_scrolled
     }
@@ -739,6 +775,17 @@ impl Pane for TerminalPane {
     fn clear_pane_frame_color_override(&mut self) {
         self.pane_frame_color_override = None;
     }
+    fn startup_command_timed_out(&mut self) {
+        if !self.has_output && self.pane_frame_color_override.is_none() {
+            self.startup_command_timed_out = true;
+            self.add_red_pane_frame_color_override(Some("Command timed out".to_owned()));
+            self.set_should_render(true);
+        }
+    }
+    fn set_synchronized_group(&mut self, group_name: Option<String>) {
+        self.synchronized_group = group_name;
+        self.set_should_render(true);
+    }
     fn frame_color_override(&self) -> Option<PaletteColor> {
         self.pane_frame_color_override
             .as_ref()
@@ -789,6 +836,13 @@ impl Pane for TerminalPane {
     fn serialize(&self, scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         self.grid.serialize(scrollback_lines_to_serialize)
     }
+    fn scrollback_size(&self) -> Option<usize> {
+        Some(self.grid.scrollback_size())
+    }
+    fn set_scrollback_size(&mut self, scrollback_size: usize) {
+        self.grid.set_scrollback_size(scrollback_size);
+        self.set_should_render(true);
+    }
     fn rerun(&mut self) -> Option<RunCommand> {
         // if this is a command pane that has exited or is waiting to be rerun, will return its
         // RunCommand, otherwise it is safe to assume this is not the right sort of pane or that it
@@ -926,8 +980,13 @@ impl TerminalPane {
             is_held: None,
             banner: None,
             pane_frame_color_override: None,
+            has_output: false,
+            startup_command_timed_out: false,
+            synchronized_group: None,
             invoked_with,
             arrow_fonts,
+            min_rows: 0,
+            min_cols: 0,
         }
     }
     pub fn get_x(&self) -> usize {
@@ -1778,6 +1778,12 @@ impl TiledPanes {
     pub fn visible_panes_count(&self) -> usize {
         self.panes.len().saturating_sub(self.panes_to_hide.len())
     }
+    pub fn visible_plugin_panes_count(&self) -> usize {
+        self.panes
+            .keys()
+            .filter(|p| matches!(p, PaneId::Plugin(_)) && !self.panes_to_hide.contains(p))
+            .count()
+    }
     pub fn add_to_hidden_panels(&mut self, pid: PaneId) {
         self.panes_to_hide.insert(pid);
     }
@@ -65,6 +65,22 @@ impl<'a> TiledPaneGrid<'a> {
         }
     }
 
+    fn get_pane_min_width(&self, pane_id: &PaneId) -> usize {
+        self.panes
+            .borrow()
+            .get(pane_id)
+            .map(|p| p.min_width())
+            .unwrap_or(MIN_TERMINAL_WIDTH)
+    }
+
+    fn get_pane_min_height(&self, pane_id: &PaneId) -> usize {
+        self.panes
+            .borrow()
+            .get(pane_id)
+            .map(|p| p.min_height())
+            .unwrap_or(MIN_TERMINAL_HEIGHT)
+    }
+
     fn pane_is_flexible(&self, direction: SplitDirection, pane_id: &PaneId) -> Result<bool> {
         let err_context =
             || format!("failed to determine if pane {pane_id:?} is flexible in {direction:?}");
@@ -523,7 +539,7 @@ impl<'a> TiledPaneGrid<'a> {
             .with_context(err_context)?;
         let current_fixed_cols = pane.cols.as_usize();
         let will_reduce_by = ((self.display_area.cols as f64 / 100.0) * reduce_by) as usize;
-        if current_fixed_cols.saturating_sub(will_reduce_by) < MIN_TERMINAL_WIDTH {
+        if current_fixed_cols.saturating_sub(will_reduce_by) < self.get_pane_min_width(pane_id) {
             Ok(false)
         } else if let Some(cols) = pane.cols.as_percent() {
             Ok(cols - reduce_by >= RESIZE_PERCENT)
@@ -543,7 +559,7 @@ impl<'a> TiledPaneGrid<'a> {
         let min_terminal_height = if pane.is_stacked {
             StackedPanes::new(self.panes.clone()).min_stack_height(pane_id)?
         } else {
-            MIN_TERMINAL_HEIGHT
+            self.get_pane_min_height(pane_id)
         };
         let current_fixed_rows = pane.rows.as_usize();
         let will_reduce_by = ((self.display_area.rows as f64 / 100.0) * reduce_by) as usize;
@@ -6,7 +6,7 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use zellij_utils::{
-    data::{Palette, Style},
+    data::{Cell, Palette, PaletteColor, Style},
     pane_size::SizeInPixels,
     position::Position,
     vte,
@@ -3863,6 +3863,50 @@ fn text_ui_component_with_coordinates() {
     assert_snapshot!(format!("{:?}", grid));
 }
 
+#[test]
+fn text_ui_component_with_url() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let link_handler = Rc::new(RefCell::new(LinkHandler::new()));
+    let mut grid = Grid::new(
+        41,
+        120,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        link_handler.clone(),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let fixture_name = "text-ui-component-with-url";
+    let content = read_fixture(fixture_name);
+    for byte in content {
+        vte_parser.advance(&mut grid, byte);
+    }
+    let first_line = &grid.viewport[0].columns;
+    let link_start = first_line[0].styles.link_anchor;
+    assert_eq!(
+        link_handler.borrow().output_osc8(link_start),
+        Some("\u{1b}]8;;https://zellij.dev\u{1b}\\".to_owned()),
+        "text is rendered as a link"
+    );
+    assert!(
+        first_line
+            .iter()
+            .all(|character| character.styles.link_anchor == link_start),
+        "link spans the whole text"
+    );
+}
+
 #[test]
 fn cannot_escape_scroll_region() {
     // this tests a fix for a bug where it would be possible to set the scroll region bounds beyond
@@ -3900,3 +3944,356 @@ fn cannot_escape_scroll_region() {
     }
     assert_snapshot!(format!("{:?}", grid));
 }
+
+#[test]
+fn set_scrollback_size_trims_lines_above() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    for i in 0..50 {
+        for byte in format!("line {}\n\r", i).as_bytes() {
+            vte_parser.advance(&mut grid, *byte);
+        }
+    }
+    assert_eq!(grid.scrollback_position_and_length(), (0, 41));
+    grid.set_scrollback_size(5);
+    assert_eq!(grid.scrollback_size(), 5);
+    assert_eq!(grid.scrollback_position_and_length(), (0, 5));
+    for byte in "one more line\n\r".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.scrollback_position_and_length(),
+        (0, 5),
+        "new lines do not grow the scrollback beyond the pane's size"
+    );
+}
+
+#[test]
+fn snapshot_of_viewport_with_colors() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        2,
+        4,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let content = "\u{1b}[1;31ma\u{1b}[0mb\n\r\u{1b}[7mc".as_bytes();
+    for byte in content {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let default_fg = PaletteColor::Rgb((255, 255, 255));
+    let default_bg = PaletteColor::Rgb((0, 0, 0));
+    let empty_cell = Cell {
+        character: ' ',
+        fg: default_fg,
+        bg: default_bg,
+        bold: false,
+    };
+    let snapshot = grid.snapshot(default_fg, default_bg);
+    assert_eq!(
+        snapshot,
+        vec![
+            vec![
+                Cell {
+                    character: 'a',
+                    fg: PaletteColor::EightBit(1),
+                    bg: default_bg,
+                    bold: true,
+                },
+                Cell {
+                    character: 'b',
+                    ..empty_cell
+                },
+                empty_cell,
+                empty_cell,
+            ],
+            vec![
+                Cell {
+                    character: 'c',
+                    fg: default_bg,
+                    bg: default_fg,
+                    bold: false,
+                },
+                empty_cell,
+                empty_cell,
+                empty_cell,
+            ],
+        ]
+    );
+}
+
+#[test]
+fn sixel_image_is_approximated_with_half_blocks_without_character_cell_size() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        3,
+        4,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    // a 20x20 pixel image, red on its top half and green on its bottom half
+    let content =
+        "\u{1b}Pq#1;2;100;0;0#2;2;0;100;0#1!20~-#1!20N$#2!20o-#2!20~-#2!20B\u{1b}\\x".as_bytes();
+    for byte in content {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let default_fg = PaletteColor::Rgb((255, 255, 255));
+    let default_bg = PaletteColor::Rgb((0, 0, 0));
+    let empty_cell = Cell {
+        character: ' ',
+        fg: default_fg,
+        bg: default_bg,
+        bold: false,
+    };
+    let half_block = Cell {
+        character: '▀',
+        fg: PaletteColor::Rgb((255, 0, 0)),
+        bg: PaletteColor::Rgb((0, 255, 0)),
+        bold: false,
+    };
+    let snapshot = grid.snapshot(default_fg, default_bg);
+    assert_eq!(
+        snapshot[0],
+        vec![half_block, half_block, empty_cell, empty_cell]
+    );
+    assert_eq!(
+        snapshot[1],
+        vec![
+            Cell {
+                character: 'x',
+                ..empty_cell
+            },
+            empty_cell,
+            empty_cell,
+            empty_cell,
+        ],
+        "cursor moved below the image"
+    );
+}
+
+#[test]
+fn scroll_to_line_in_scrollback() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        5,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    for i in 0..20 {
+        for byte in format!("line {}\n\r", i).as_bytes() {
+            vte_parser.advance(&mut grid, *byte);
+        }
+    }
+    let line_index = grid
+        .dump_screen(true)
+        .lines()
+        .position(|line| line == "line 3")
+        .unwrap();
+    grid.scroll_to_line(line_index);
+    assert_eq!(grid.dump_screen(false).lines().next(), Some("line 3"));
+    grid.scroll_to_line(0);
+    assert_eq!(grid.dump_screen(false).lines().next(), Some("line 0"));
+    grid.scroll_to_line(19);
+    assert!(
+        !grid.is_scrolled,
+        "lines that are already in the viewport do not scroll it"
+    );
+}
+
+#[test]
+fn scrollback_annotations_are_left_out_of_dump() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        5,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    for i in 0..20 {
+        for byte in format!("line {}\n\r", i).as_bytes() {
+            vte_parser.advance(&mut grid, *byte);
+        }
+    }
+    let dump_before_annotating = grid.dump_screen(true);
+    let scrollback_lines_before_annotating = grid.scrollback_buffer_lines;
+    grid.annotate_scrollback_line(3, "first annotation");
+    grid.annotate_scrollback_line(8, "second annotation");
+    assert_eq!(grid.dump_screen(true), dump_before_annotating);
+    assert_eq!(
+        grid.scrollback_buffer_lines,
+        scrollback_lines_before_annotating + 2,
+        "annotations are counted as scrollback lines"
+    );
+    grid.scroll_to_line(3);
+    assert_eq!(
+        grid.dump_screen(false).lines().next(),
+        Some("line 3"),
+        "annotations do not shift line indices"
+    );
+    grid.move_viewport_up(1);
+    let annotation_row = grid.viewport.get(0).unwrap();
+    assert!(annotation_row.is_annotation);
+    assert_eq!(
+        annotation_row
+            .columns
+            .iter()
+            .map(|c| c.character)
+            .collect::<String>()
+            .trim_end(),
+        "first annotation"
+    );
+    grid.clear_scrollback_annotations();
+    assert!(grid
+        .lines_above
+        .iter()
+        .chain(grid.viewport.iter())
+        .all(|row| !row.is_annotation));
+    grid.reset_viewport();
+    assert_eq!(grid.dump_screen(true), dump_before_annotating);
+    assert_eq!(
+        grid.scrollback_buffer_lines, scrollback_lines_before_annotating,
+        "cleared annotations are no longer counted as scrollback lines"
+    );
+}
+
+#[test]
+pub fn primary_scrollback_is_preserved_through_alternate_screen() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let character_cell_size = Rc::new(RefCell::new(Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    })));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        character_cell_size,
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    for i in 0..30 {
+        let line = format!("shell line {}\n\r", i);
+        for byte in line.as_bytes() {
+            vte_parser.advance(&mut grid, *byte);
+        }
+    }
+    grid.scroll_up_one_line();
+    grid.scroll_up_one_line();
+    let primary_scrollback = grid.scrollback_position_and_length();
+    assert_eq!(primary_scrollback.0, 2, "scrolled up in the primary screen");
+
+    // entering the alternate screen twice should not replace the saved primary screen
+    let move_to_alternate_screen = "\u{1b}[?1049h\u{1b}[?1049h";
+    for byte in move_to_alternate_screen.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    for i in 0..30 {
+        let line = format!("editor line {}\n\r", i);
+        for byte in line.as_bytes() {
+            vte_parser.advance(&mut grid, *byte);
+        }
+    }
+    assert_eq!(
+        grid.scrollback_position_and_length(),
+        (0, 0),
+        "no scrollback in the alternate screen"
+    );
+
+    let move_away_from_alternate_screen = "\u{1b}[?1049l";
+    for byte in move_away_from_alternate_screen.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.scrollback_position_and_length(),
+        primary_scrollback,
+        "primary scrollback restored after leaving the alternate screen"
+    );
+}
@@ -1,6 +1,6 @@
 use super::super::TerminalPane;
 use crate::panes::sixel::SixelImageStore;
-use crate::panes::LinkHandler;
+use crate::panes::{find_matches_in_lines, find_regex_matches_in_lines, LinkHandler};
 use crate::tab::Pane;
 use insta::assert_snapshot;
 use std::cell::RefCell;
@@ -421,3 +421,47 @@ pub fn searching_whole_word_case_insensitive() {
         format!("{:?}", terminal_pane.grid)
     );
 }
+
+#[test]
+pub fn finding_matches_in_pane_contents() {
+    let terminal_pane = create_pane();
+    let pane_contents = terminal_pane.dump_screen(true);
+    let case_sensitive_matches = find_matches_in_lines(&pane_contents, "Quam", true);
+    assert_snapshot!(
+        "grid_copy_quam_case_sensitive_matches",
+        format!("{:#?}", case_sensitive_matches)
+    );
+    let case_insensitive_matches = find_matches_in_lines(&pane_contents, "Quam", false);
+    assert_snapshot!(
+        "grid_copy_quam_case_insensitive_matches",
+        format!("{:#?}", case_insensitive_matches)
+    );
+}
+
+#[test]
+pub fn finding_regex_matches_in_pane_contents() {
+    let pane_contents = "error: 42 in résumé\nnothing to see here\nERROR: 7, error: 8";
+    let case_sensitive_matches = find_regex_matches_in_lines(pane_contents, r"error: \d+", true);
+    assert_eq!(
+        case_sensitive_matches
+            .iter()
+            .map(|m| (m.row, m.col_start, m.col_end))
+            .collect::<Vec<_>>(),
+        vec![(0, 0, 9), (2, 10, 18)]
+    );
+    let case_insensitive_matches = find_regex_matches_in_lines(pane_contents, r"error: \d+", false);
+    assert_eq!(
+        case_insensitive_matches
+            .iter()
+            .map(|m| (m.row, m.col_start, m.col_end))
+            .collect::<Vec<_>>(),
+        vec![(0, 0, 9), (2, 0, 8), (2, 10, 18)]
+    );
+    let multibyte_matches = find_regex_matches_in_lines(pane_contents, "sum", true);
+    assert_eq!(
+        multibyte_matches[0].col_start, 15,
+        "columns are counted in characters"
+    );
+    let invalid_pattern_matches = find_regex_matches_in_lines(pane_contents, "error: (", true);
+    assert!(invalid_pattern_matches.is_empty());
+}
@@ -24,6 +24,7 @@ use wasm_bridge::WasmBridge;
 
 use zellij_utils::{
     async_std::{channel, future::timeout, task},
+    channels::Sender,
     data::{
         ClientInfo, Event, EventType, InputMode, MessageToPlugin, PermissionStatus, PermissionType,
         PipeMessage, PipeSource, PluginCapabilities,
@@ -87,7 +88,8 @@ pub enum PluginInstruction {
     PostMessagesToPluginWorker(
         PluginId,
         ClientId,
-        String, // worker name
+        String,      // worker name
+        Option<u32>, // worker instance id, None to take turns between the instances of a pool
         Vec<(
             String, // serialized message name
             String, // serialized payload
@@ -111,6 +113,8 @@ pub enum PluginInstruction {
     ListClientsMetadata(SessionLayoutMetadata, ClientId),
     DumpLayoutToPlugin(SessionLayoutMetadata, PluginId),
     LogLayoutToHd(SessionLayoutMetadata),
+    // PathBuf is the file to save the layout to, the Sender is notified once it is written
+    SaveLayout(SessionLayoutMetadata, PathBuf, Sender<()>),
     CliPipe {
         pipe_id: String,
         name: String,
@@ -124,6 +128,8 @@ pub enum PluginInstruction {
         cwd: Option<PathBuf>,
         skip_cache: bool,
         cli_client_id: ClientId,
+        plugin_id: Option<PluginId>, // supercedes plugin if present
+        is_last: bool,
     },
     KeybindPipe {
         name: String,
@@ -159,6 +165,18 @@ pub enum PluginInstruction {
     WatchFilesystem,
     ListClientsToPlugin(SessionLayoutMetadata, PluginId, ClientId),
     ChangePluginHostDir(PathBuf, PluginId, ClientId),
+    GetPluginDependencies(String, PluginId, ClientId), // String - plugin url
+    SubscribeToChannel(PluginId, String),              // plugin_id, channel
+    PublishToChannel(String, Vec<u8>),                 // channel, payload
+    DuplicatePlugin {
+        original_id: PluginId,
+        run_plugin_or_alias: RunPluginOrAlias,
+        should_float: bool,
+        tab_index: usize,
+        size: Size,
+        plugin_id: PluginId, // the plugin that asked for the duplicate
+        client_id: ClientId,
+    },
     Exit,
 }
 
@@ -193,6 +211,7 @@ impl From<&PluginInstruction> for PluginContext {
             PluginInstruction::DumpLayout(..) => PluginContext::DumpLayout,
             PluginInstruction::ListClientsMetadata(..) => PluginContext::ListClientsMetadata,
             PluginInstruction::LogLayoutToHd(..) => PluginContext::LogLayoutToHd,
+            PluginInstruction::SaveLayout(..) => PluginContext::SaveLayout,
             PluginInstruction::CliPipe { .. } => PluginContext::CliPipe,
             PluginInstruction::CachePluginEvents { .. } => PluginContext::CachePluginEvents,
             PluginInstruction::MessageFromPlugin { .. } => PluginContext::MessageFromPlugin,
@@ -206,6 +225,10 @@ impl From<&PluginInstruction> for PluginContext {
             },
             PluginInstruction::ListClientsToPlugin(..) => PluginContext::ListClientsToPlugin,
             PluginInstruction::ChangePluginHostDir(..) => PluginContext::ChangePluginHostDir,
+            PluginInstruction::GetPluginDependencies(..) => PluginContext::GetPluginDependencies,
+            PluginInstruction::SubscribeToChannel(..) => PluginContext::SubscribeToChannel,
+            PluginInstruction::PublishToChannel(..) => PluginContext::PublishToChannel,
+            PluginInstruction::DuplicatePlugin { .. } => PluginContext::DuplicatePlugin,
         }
     }
 }
@@ -221,7 +244,7 @@ pub(crate) fn plugin_thread_main(
     capabilities: PluginCapabilities,
     client_attributes: ClientAttributes,
     default_shell: Option<TerminalAction>,
-    plugin_aliases: Box<PluginAliases>,
+    mut plugin_aliases: Box<PluginAliases>,
     default_mode: InputMode,
     default_keybinds: Keybinds,
     background_plugins: HashSet<RunPluginOrAlias>,
@@ -234,12 +257,17 @@ pub(crate) fn plugin_thread_main(
     info!("Wasm main thread starts");
     let plugin_dir = data_dir.join("plugins/");
     let plugin_global_data_dir = plugin_dir.join("data");
+    // plugins referred to by their bare name are looked for in the data dir last
+    plugin_aliases.search_paths.push(plugin_dir.clone());
     layout.populate_plugin_aliases_in_layout(&plugin_aliases);
 
     // use this channel to ensure that tasks spawned from this thread terminate before exiting
     // https://tokio.rs/tokio/topics/shutdown#waiting-for-things-to-finish-shutting-down
     let (shutdown_send, shutdown_receive) = channel::bounded::<()>(1);
 
+    // channel name => ids of the plugins subscribed to it
+    let mut channel_subscriptions: HashMap<String, Vec<PluginId>> = HashMap::new();
+
     let mut wasm_bridge = WasmBridge::new(
         bus.senders.clone(),
         engine,
@@ -326,6 +354,13 @@ pub(crate) fn plugin_thread_main(
                 wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
             },
             PluginInstruction::Unload(pid) => {
+                for subscribers in channel_subscriptions.values_mut() {
+                    subscribers.retain(|p| *p != pid);
+                }
+                channel_subscriptions.retain(|_, subscribers| !subscribers.is_empty());
+                let _ = bus
+                    .senders
+                    .send_to_server(ServerInstruction::UnregisterGlobalHotkeys(pid));
                 wasm_bridge.unload_plugin(pid)?;
             },
             PluginInstruction::Reload(
@@ -507,12 +542,14 @@ pub(crate) fn plugin_thread_main(
                 plugin_id,
                 client_id,
                 worker_name,
+                instance_id,
                 messages,
             ) => {
                 wasm_bridge.post_messages_to_plugin_worker(
                     plugin_id,
                     client_id,
                     worker_name,
+                    instance_id,
                     messages,
                 )?;
             },
@@ -650,6 +687,22 @@ pub(crate) fn plugin_thread_main(
                         .send_to_pty(PtyInstruction::LogLayoutToHd(session_layout_metadata)),
                 );
             },
+            PluginInstruction::SaveLayout(
+                mut session_layout_metadata,
+                file_name,
+                completion_sender,
+            ) => {
+                populate_session_layout_metadata(
+                    &mut session_layout_metadata,
+                    &wasm_bridge,
+                    &plugin_aliases,
+                );
+                drop(bus.senders.send_to_pty(PtyInstruction::SaveLayout(
+                    session_layout_metadata,
+                    file_name,
+                    completion_sender,
+                )));
+            },
             PluginInstruction::CliPipe {
                 pipe_id,
                 name,
@@ -663,11 +716,27 @@ pub(crate) fn plugin_thread_main(
                 cwd,
                 skip_cache,
                 cli_client_id,
+                plugin_id,
+                is_last,
             } => {
                 let should_float = floating.unwrap_or(true);
                 let mut pipe_messages = vec![];
-                match plugin {
-                    Some(plugin_url) => {
+                match (plugin_id, plugin) {
+                    (Some(plugin_id), _) => {
+                        // send to this plugin instance only
+                        pipe_to_plugin_id(
+                            PipeSource::Cli(pipe_id.clone()),
+                            plugin_id,
+                            cli_client_id,
+                            &mut pipe_messages,
+                            &name,
+                            &payload,
+                            &args,
+                            &bus,
+                            &wasm_bridge,
+                        );
+                    },
+                    (None, Some(plugin_url)) => {
                         // send to specific plugin(s)
                         pipe_to_specific_plugins(
                             PipeSource::Cli(pipe_id.clone()),
@@ -688,7 +757,7 @@ pub(crate) fn plugin_thread_main(
                             &plugin_aliases,
                         );
                     },
-                    None => {
+                    (None, None) => {
                         // no specific destination, send to all plugins
                         pipe_to_all_plugins(
                             PipeSource::Cli(pipe_id.clone()),
@@ -700,6 +769,10 @@ pub(crate) fn plugin_thread_main(
                         );
                     },
                 }
+                // payloads piped from STDIN arrive one line at a time
+                for (_, _, pipe_message) in pipe_messages.iter_mut() {
+                    pipe_message.is_last = is_last;
+                }
                 wasm_bridge.pipe_messages(pipe_messages, shutdown_send.clone())?;
             },
             PluginInstruction::KeybindPipe {
@@ -893,6 +966,98 @@ pub(crate) fn plugin_thread_main(
                     .change_plugin_host_dir(new_host_folder, plugin_id, client_id)
                     .non_fatal();
             },
+            PluginInstruction::GetPluginDependencies(url, plugin_id, client_id) => {
+                wasm_bridge.get_plugin_dependencies(url, plugin_id, client_id);
+            },
+            PluginInstruction::SubscribeToChannel(plugin_id, channel) => {
+                let subscribers = channel_subscriptions
+                    .entry(channel)
+                    .or_insert_with(Vec::new);
+                if !subscribers.contains(&plugin_id) {
+                    subscribers.push(plugin_id);
+                }
+            },
+            PluginInstruction::PublishToChannel(channel, payload) => {
+                let updates = channel_subscriptions
+                    .get(&channel)
+                    .map(|subscribers| {
+                        subscribers
+                            .iter()
+                            .map(|plugin_id| {
+                                (
+                                    Some(*plugin_id),
+                                    None,
+                                    Event::ChannelMessage {
+                                        channel: channel.clone(),
+                                        payload: payload.clone(),
+                                    },
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                if !updates.is_empty() {
+                    wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
+                }
+            },
+            PluginInstruction::DuplicatePlugin {
+                original_id,
+                mut run_plugin_or_alias,
+                should_float,
+                tab_index,
+                size,
+                plugin_id,
+                client_id,
+            } => {
+                run_plugin_or_alias.populate_run_plugin_if_needed(&plugin_aliases);
+                let cwd = run_plugin_or_alias.get_initial_cwd();
+                let run_plugin = run_plugin_or_alias.get_run_plugin();
+                let skip_cache = false;
+                match wasm_bridge.load_plugin(
+                    &run_plugin,
+                    Some(tab_index),
+                    size,
+                    cwd.clone(),
+                    skip_cache,
+                    Some(client_id),
+                    None,
+                ) {
+                    Ok((new_plugin_id, _client_id)) => {
+                        let should_be_open_in_place = false;
+                        let pane_title = None;
+                        let pane_id_to_replace = None;
+                        let start_suppressed = false;
+                        drop(bus.senders.send_to_screen(ScreenInstruction::AddPlugin(
+                            Some(should_float),
+                            should_be_open_in_place,
+                            run_plugin_or_alias,
+                            pane_title,
+                            Some(tab_index),
+                            new_plugin_id,
+                            pane_id_to_replace,
+                            cwd,
+                            start_suppressed,
+                            // the duplicate goes next to the original rather than in the tab
+                            // focused by the client
+                            None,
+                        )));
+                        wasm_bridge.update_plugins(
+                            vec![(
+                                Some(plugin_id),
+                                Some(client_id),
+                                Event::PaneDuplicated {
+                                    original_id: PaneId::Plugin(original_id).into(),
+                                    new_id: PaneId::Plugin(new_plugin_id).into(),
+                                },
+                            )],
+                            shutdown_send.clone(),
+                        )?;
+                    },
+                    Err(e) => {
+                        log::error!("Failed to duplicate plugin: {e}");
+                    },
+                }
+            },
             PluginInstruction::Exit => {
                 break;
             },
@@ -964,6 +1129,36 @@ fn pipe_to_all_plugins(
     }
 }
 
+fn pipe_to_plugin_id(
+    pipe_source: PipeSource,
+    plugin_id: PluginId,
+    cli_client_id: ClientId,
+    pipe_messages: &mut Vec<(Option<PluginId>, Option<ClientId>, PipeMessage)>,
+    name: &str,
+    payload: &Option<String>,
+    args: &Option<BTreeMap<String, String>>,
+    bus: &Bus<PluginInstruction>,
+    wasm_bridge: &WasmBridge,
+) {
+    let is_running = wasm_bridge
+        .all_plugin_ids()
+        .iter()
+        .any(|(running_plugin_id, _client_id)| *running_plugin_id == plugin_id);
+    if is_running {
+        let is_private = true;
+        pipe_messages.push((
+            Some(plugin_id),
+            None,
+            PipeMessage::new(pipe_source, name, payload, args, is_private),
+        ));
+    } else {
+        let _ = bus.senders.send_to_server(ServerInstruction::LogError(
+            vec![format!("No plugin with id {} is running", plugin_id)],
+            cli_client_id,
+        ));
+    }
+}
+
 fn pipe_to_specific_plugins(
     pipe_source: PipeSource,
     plugin_url: &str,
@@ -1,21 +1,23 @@
 use crate::plugins::plugin_map::{
     PluginEnv, PluginMap, RunningPlugin, VecDequeInputStream, WriteOutputStream,
 };
-use crate::plugins::plugin_worker::{plugin_worker, RunningWorker};
-use crate::plugins::zellij_exports::{wasi_write_object, zellij_exports};
+use crate::plugins::plugin_worker::{
+    clamp_worker_pool_size, plugin_worker, RunningWorker, WorkerPool,
+};
+use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object, zellij_exports};
 use crate::plugins::PluginId;
 use highway::{HighwayHash, PortableHash};
 use log::info;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 use url::Url;
 use wasmtime::{Engine, Instance, Linker, Module, Store};
 use wasmtime_wasi::{preview1::WasiP1Ctx, DirPerms, FilePerms, WasiCtxBuilder};
-use zellij_utils::consts::ZELLIJ_PLUGIN_ARTIFACT_DIR;
+use zellij_utils::consts::{VERSION, ZELLIJ_PLUGIN_ARTIFACT_DIR};
 use zellij_utils::prost::Message;
 
 use crate::{
@@ -36,6 +38,10 @@ use zellij_utils::{
     pane_size::Size,
 };
 
+#[cfg(test)]
+#[path = "./unit/plugin_loader_tests.rs"]
+mod plugin_loader_tests;
+
 macro_rules! display_loading_stage {
     ($loading_stage:ident, $loading_indication:expr, $senders:expr, $plugin_id:expr) => {{
         $loading_indication.$loading_stage();
@@ -569,7 +575,7 @@ impl<'a> PluginLoader<'a> {
             })
             .and_then(|m| {
                 // serialize module to HD cache for faster loading in the future
-                fs::write(&cached_path, m.serialize()?).map_err(anyError::new)?;
+                write_to_hd_cache(&cached_path, &m.serialize()?)?;
                 log::info!(
                     "Compiled plugin '{}' in {:?}",
                     self.plugin_path.display(),
@@ -612,6 +618,30 @@ impl<'a> PluginLoader<'a> {
         let (store, instance) = self.create_plugin_instance_env(&module)?;
         Ok((store, instance))
     }
+    // a worker pool registered with `register_worker_pool!` exports a `<worker>_pool` function
+    // returning its size, which can be overridden with a `<worker>_pool_size` configuration key
+    fn worker_pool_size(
+        &self,
+        store: &mut Store<PluginEnv>,
+        instance: &Instance,
+        worker_function_name: &str,
+    ) -> usize {
+        let pool_function_name = format!("{}_pool", worker_function_name);
+        let configured_pool_size = self
+            .plugin
+            .userspace_configuration
+            .inner()
+            .get(&format!("{}_size", pool_function_name))
+            .and_then(|pool_size| pool_size.parse::<usize>().ok());
+        let pool_size = configured_pool_size.or_else(|| {
+            instance
+                .get_typed_func::<(), u32>(&mut *store, &pool_function_name)
+                .and_then(|pool_function| pool_function.call(&mut *store, ()))
+                .ok()
+                .map(|pool_size| pool_size as usize)
+        });
+        clamp_worker_pool_size(pool_size)
+    }
     pub fn load_plugin_instance(
         &mut self,
         mut store: Store<PluginEnv>,
@@ -633,11 +663,16 @@ impl<'a> PluginLoader<'a> {
             .get_typed_func::<(), ()>(&mut store, "load")
             .with_context(err_context)?;
         let mut workers = HashMap::new();
-        for function_name in instance
+        let worker_function_names: Vec<String> = instance
             .exports(&mut store)
             .filter_map(|export| export.clone().into_func().map(|_| export.name()))
-        {
-            if function_name.ends_with("_worker") {
+            .filter(|function_name| function_name.ends_with("_worker"))
+            .map(|function_name| function_name.to_owned())
+            .collect();
+        for function_name in worker_function_names {
+            let pool_size = self.worker_pool_size(&mut store, &instance, &function_name);
+            let mut worker_instances = vec![];
+            for _ in 0..pool_size {
                 let plugin_config = self.plugin.clone();
                 let (mut store, instance) =
                     self.create_plugin_instance_and_wasi_env_for_worker()?;
@@ -650,9 +685,9 @@ impl<'a> PluginLoader<'a> {
                     .with_context(err_context)?;
 
                 let worker = RunningWorker::new(store, instance, &function_name, plugin_config);
-                let worker_sender = plugin_worker(worker);
-                workers.insert(function_name.into(), worker_sender);
+                worker_instances.push(plugin_worker(worker));
             }
+            workers.insert(function_name, WorkerPool::new(worker_instances));
         }
 
         let subscriptions = store.data().subscriptions.clone();
@@ -673,6 +708,7 @@ impl<'a> PluginLoader<'a> {
         start_function
             .call(&mut plugin.lock().unwrap().store, ())
             .with_context(err_context)?;
+        check_plugin_min_version(&mut plugin.lock().unwrap().store, instance)?;
 
         let protobuf_plugin_configuration: ProtobufPluginConfiguration = self
             .plugin
@@ -910,3 +946,62 @@ fn create_plugin_fs_entries(
         .with_context(err_context)?;
     Ok(())
 }
+
+fn write_to_hd_cache(cached_path: &Path, serialized_module: &[u8]) -> Result<()> {
+    // Other sessions might be deserializing (mmapping) this very file, so rather than truncating
+    // it in place we write to a temporary file and atomically move it over
+    let tmp_path = cached_path.with_extension(format!("tmp-{}", std::process::id()));
+    fs::write(&tmp_path, serialized_module)
+        .and_then(|_| fs::rename(&tmp_path, cached_path))
+        .or_else(|e| {
+            let _ = fs::remove_file(&tmp_path);
+            Err(e)
+        })
+        .with_context(|| format!("failed to write plugin cache to {cached_path:?}"))
+}
+
+// plugins registered with a `min_zellij_version` export a `plugin_min_version` function printing
+// it, we refuse to load them in older versions rather than have them misbehave
+fn check_plugin_min_version(store: &mut Store<PluginEnv>, instance: &Instance) -> Result<()> {
+    let Ok(min_version_function) =
+        instance.get_typed_func::<(), ()>(&mut *store, "plugin_min_version")
+    else {
+        return Ok(());
+    };
+    min_version_function
+        .call(&mut *store, ())
+        .context("failed to read the minimum Zellij version of the plugin")?;
+    let min_version = wasi_read_string(store.data())?;
+    check_zellij_version(VERSION, min_version.trim())
+}
+
+// only the major.minor.patch core is compared, so that pre-release builds (eg. 0.42.0-dev) can
+// load plugins requiring the version they are about to become
+fn check_zellij_version(running_version: &str, min_version: &str) -> Result<()> {
+    let running_version = semver_core(running_version)
+        .with_context(|| format!("failed to parse Zellij version {}", running_version))?;
+    let required_version = semver_core(min_version).with_context(|| {
+        format!(
+            "failed to parse the minimum Zellij version of the plugin: {}",
+            min_version
+        )
+    })?;
+    if running_version < required_version {
+        Err(anyhow!(
+            "Plugin requires Zellij >= {}, running {}",
+            required_version,
+            running_version
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn semver_core(version: &str) -> Result<semver::Version> {
+    let version = semver::Version::parse(version)?;
+    Ok(semver::Version::new(
+        version.major,
+        version.minor,
+        version.patch,
+    ))
+}
@@ -1,4 +1,5 @@
-use crate::plugins::plugin_worker::MessageToWorker;
+use crate::plugins::plugin_worker::{MessageToWorker, WorkerPool};
+use crate::plugins::zellij_exports::wasi_read_string;
 use crate::plugins::PluginId;
 use bytes::Bytes;
 use std::io::Write;
@@ -7,6 +8,7 @@ use std::{
     path::PathBuf,
     sync::{Arc, Mutex},
 };
+use unicode_width::UnicodeWidthChar;
 use wasmtime::{Instance, Store};
 use wasmtime_wasi::preview1::WasiP1Ctx;
 use wasmtime_wasi::{
@@ -21,6 +23,7 @@ use zellij_utils::{
     data::EventType,
     data::InputMode,
     data::PluginCapabilities,
+    data::RenderRegion,
     input::command::TerminalAction,
     input::keybinds::Keybinds,
     input::layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation},
@@ -41,7 +44,7 @@ pub struct PluginMap {
         (
             Arc<Mutex<RunningPlugin>>,
             Arc<Mutex<Subscriptions>>,
-            HashMap<String, Sender<MessageToWorker>>,
+            HashMap<String, WorkerPool>,
         ),
     >,
 }
@@ -53,7 +56,7 @@ impl PluginMap {
     ) -> Vec<(
         Arc<Mutex<RunningPlugin>>,
         Arc<Mutex<Subscriptions>>,
-        HashMap<String, Sender<MessageToWorker>>,
+        HashMap<String, WorkerPool>,
     )> {
         let mut removed = vec![];
         let ids_in_plugin_map: Vec<(PluginId, ClientId)> =
@@ -74,7 +77,7 @@ impl PluginMap {
     ) -> Option<(
         Arc<Mutex<RunningPlugin>>,
         Arc<Mutex<Subscriptions>>,
-        HashMap<String, Sender<MessageToWorker>>,
+        HashMap<String, WorkerPool>,
     )> {
         self.plugin_assets.remove(&(plugin_id, client_id))
     }
@@ -149,18 +152,16 @@ impl PluginMap {
         plugin_id: PluginId,
         client_id: ClientId,
         worker_name: &str,
+        instance_id: Option<u32>,
     ) -> Option<Sender<MessageToWorker>> {
         self.plugin_assets
             .iter()
             .find(|((p_id, c_id), _)| p_id == &plugin_id && c_id == &client_id)
             .and_then(|(_, (_running_plugin, _subscriptions, workers))| {
-                if let Some(worker) = workers.get(&format!("{}_worker", worker_name)) {
-                    Some(worker.clone())
-                } else {
-                    None
-                }
+                workers
+                    .get(&format!("{}_worker", worker_name))
+                    .and_then(|worker_pool| worker_pool.instance(instance_id))
             })
-            .clone()
     }
     pub fn all_plugin_ids_for_plugin_location(
         &self,
@@ -235,7 +236,7 @@ impl PluginMap {
         client_id: ClientId,
         running_plugin: Arc<Mutex<RunningPlugin>>,
         subscriptions: Arc<Mutex<Subscriptions>>,
-        running_workers: HashMap<String, Sender<MessageToWorker>>,
+        running_workers: HashMap<String, WorkerPool>,
     ) {
         self.plugin_assets.insert(
             (plugin_id, client_id),
@@ -437,6 +438,35 @@ impl RunningPlugin {
             current_event_id
         }
     }
+    /// Renders the plugin to a string of bytes to be sent to its pane. Plugins exporting
+    /// `render_regions` may return independent regions which are composited here, others (or
+    /// those choosing to fall back) are rendered through the plain `render` export.
+    pub fn render(&mut self, rows: usize, columns: usize) -> Result<String> {
+        let instance = self.instance.clone();
+        match instance.get_typed_func::<(i32, i32), i32>(&mut self.store, "render_regions") {
+            Ok(render_regions) => {
+                let rendered_regions = render_regions
+                    .call(&mut self.store, (rows as i32, columns as i32))
+                    .context("failed to render plugin regions")?;
+                let rendered_bytes = wasi_read_string(self.store.data())?;
+                if rendered_regions == 1 {
+                    let regions: Vec<RenderRegion> = serde_json::from_str(&rendered_bytes)
+                        .context("failed to deserialize plugin render regions")?;
+                    Ok(composite_render_regions(&regions, rows, columns))
+                } else {
+                    Ok(rendered_bytes)
+                }
+            },
+            Err(_e) => {
+                // older plugins do not export render_regions
+                instance
+                    .get_typed_func::<(i32, i32), ()>(&mut self.store, "render")
+                    .and_then(|render| render.call(&mut self.store, (rows as i32, columns as i32)))
+                    .and_then(|_| wasi_read_string(self.store.data()))
+                    .context("failed to render plugin")
+            },
+        }
+    }
     pub fn apply_event_id(&mut self, atomic_event: AtomicEvent, event_id: usize) -> bool {
         if &event_id >= self.last_applied_event_ids.get(&atomic_event).unwrap_or(&0) {
             self.last_applied_event_ids.insert(atomic_event, event_id);
@@ -455,3 +485,62 @@ impl RunningPlugin {
         self.store.data_mut().default_shell = default_shell;
     }
 }
+
+/// Composites the given regions into a single string of bytes, positioning each line of each
+/// region with the cursor and clipping it to both the region and the plugin's content area.
+pub fn composite_render_regions(regions: &[RenderRegion], rows: usize, columns: usize) -> String {
+    let mut composited = String::new();
+    for region in regions {
+        if region.x >= columns || region.y >= rows {
+            continue;
+        }
+        let width = std::cmp::min(region.width, columns - region.x);
+        let height = std::cmp::min(region.height, rows - region.y);
+        let mut lines = region.content.lines();
+        for line_index in 0..height {
+            let line = lines.next().unwrap_or("");
+            composited.push_str(&format!(
+                "\u{1b}[{};{}H",
+                region.y + line_index + 1,
+                region.x + 1
+            ));
+            composited.push_str(&clip_line_to_width(line, width));
+        }
+    }
+    composited
+}
+
+// truncates (or pads with spaces) a line to the given width, ignoring the width of ANSI escape
+// sequences and resetting styles at its end so they do not bleed into the next region
+fn clip_line_to_width(line: &str, width: usize) -> String {
+    let mut clipped = String::new();
+    let mut current_width = 0;
+    let mut chars = line.chars().peekable();
+    while let Some(character) = chars.next() {
+        if character == '\u{1b}' {
+            clipped.push(character);
+            if chars.peek() == Some(&'[') {
+                // CSI sequence, ends with a character in the range '@'..='~'
+                clipped.push(chars.next().unwrap());
+                while let Some(sequence_character) = chars.next() {
+                    clipped.push(sequence_character);
+                    if ('@'..='~').contains(&sequence_character) {
+                        break;
+                    }
+                }
+            } else if let Some(sequence_character) = chars.next() {
+                clipped.push(sequence_character);
+            }
+            continue;
+        }
+        let character_width = character.width().unwrap_or(0);
+        if current_width + character_width > width {
+            break;
+        }
+        current_width += character_width;
+        clipped.push(character);
+    }
+    clipped.push_str("\u{1b}[m");
+    clipped.push_str(&" ".repeat(width.saturating_sub(current_width)));
+    clipped
+}
@@ -1,5 +1,6 @@
 use crate::plugins::plugin_map::PluginEnv;
 use crate::plugins::zellij_exports::wasi_write_object;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use wasmtime::{Instance, Store};
 
 use zellij_utils::async_channel::{unbounded, Receiver, Sender};
@@ -9,6 +10,10 @@ use zellij_utils::input::plugins::PluginConfig;
 use zellij_utils::plugin_api::message::ProtobufMessage;
 use zellij_utils::prost::Message;
 
+#[cfg(test)]
+#[path = "./unit/plugin_worker_tests.rs"]
+mod plugin_worker_tests;
+
 pub struct RunningWorker {
     pub instance: Instance,
     pub name: String,
@@ -55,6 +60,55 @@ pub enum MessageToWorker {
     Exit,
 }
 
+const MAX_WORKER_POOL_SIZE: usize = 16;
+
+// every instance of a worker is a full copy of the plugin, so a plugin or its configuration can't
+// ask for an unbounded amount of them
+pub(crate) fn clamp_worker_pool_size(pool_size: Option<usize>) -> usize {
+    let requested_pool_size = pool_size.unwrap_or(1);
+    let pool_size = requested_pool_size.clamp(1, MAX_WORKER_POOL_SIZE);
+    if pool_size != requested_pool_size {
+        log::warn!(
+            "Worker pool size {} is out of bounds, using {} instead",
+            requested_pool_size,
+            pool_size
+        );
+    }
+    pool_size
+}
+
+/// The running instances of a worker, a worker registered with `register_worker!` is a pool of one
+pub struct WorkerPool {
+    instances: Vec<Sender<MessageToWorker>>,
+    next_instance: AtomicUsize,
+}
+
+impl WorkerPool {
+    pub fn new(instances: Vec<Sender<MessageToWorker>>) -> Self {
+        WorkerPool {
+            instances,
+            next_instance: AtomicUsize::new(0),
+        }
+    }
+    /// The instance with the given id (modulo the pool size), or the next one in turn if no id
+    /// was given
+    pub fn instance(&self, instance_id: Option<u32>) -> Option<Sender<MessageToWorker>> {
+        if self.instances.is_empty() {
+            return None;
+        }
+        let index = match instance_id {
+            Some(instance_id) => instance_id as usize,
+            None => self.next_instance.fetch_add(1, Ordering::Relaxed),
+        };
+        self.instances.get(index % self.instances.len()).cloned()
+    }
+    pub fn exit(&self) {
+        for instance in &self.instances {
+            let _ = instance.try_send(MessageToWorker::Exit);
+        }
+    }
+}
+
 pub fn plugin_worker(mut worker: RunningWorker) -> Sender<MessageToWorker> {
     let (sender, receiver): (Sender<MessageToWorker>, Receiver<MessageToWorker>) = unbounded();
     task::spawn({
@@ -0,0 +1,29 @@
+use super::*;
+
+#[test]
+fn plugins_load_in_the_version_they_require_or_newer() {
+    assert!(check_zellij_version("0.42.0", "0.42.0").is_ok());
+    assert!(check_zellij_version("0.42.1", "0.42.0").is_ok());
+    assert!(check_zellij_version("1.0.0", "0.42.0").is_ok());
+}
+
+#[test]
+fn plugins_are_refused_by_older_versions() {
+    let error = check_zellij_version("0.41.2", "0.42.0").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Plugin requires Zellij >= 0.42.0, running 0.41.2"
+    );
+}
+
+#[test]
+fn pre_release_builds_load_plugins_requiring_their_version() {
+    assert!(check_zellij_version("0.42.0-dev", "0.42.0").is_ok());
+    assert!(check_zellij_version("0.42.0", "0.42.0-rc.1").is_ok());
+    assert!(check_zellij_version("0.41.2-dev", "0.42.0").is_err());
+}
+
+#[test]
+fn unparsable_minimum_versions_are_refused() {
+    assert!(check_zellij_version("0.42.0", "latest").is_err());
+}
@@ -5987,6 +5987,8 @@ pub fn unblock_input_plugin_command() {
         cwd: None,
         skip_cache: false,
         cli_client_id: client_id,
+        plugin_id: None,
+        is_last: true,
     });
     screen_thread.join().unwrap(); // this might take a while if the cache is cold
     teardown();
@@ -6070,6 +6072,8 @@ pub fn block_input_plugin_command() {
         cwd: None,
         skip_cache: false,
         cli_client_id: client_id,
+        plugin_id: None,
+        is_last: true,
     });
     screen_thread.join().unwrap(); // this might take a while if the cache is cold
     teardown();
@@ -6159,6 +6163,8 @@ pub fn pipe_output_plugin_command() {
         cwd: None,
         skip_cache: false,
         cli_client_id: client_id,
+        plugin_id: None,
+        is_last: true,
     });
     std::thread::sleep(std::time::Duration::from_millis(500));
     teardown();
@@ -6241,6 +6247,8 @@ pub fn pipe_message_to_plugin_plugin_command() {
         cwd: None,
         skip_cache: false,
         cli_client_id: client_id,
+        plugin_id: None,
+        is_last: true,
     });
     std::thread::sleep(std::time::Duration::from_millis(500));
     teardown();
@@ -8622,3 +8630,164 @@ pub fn list_clients_plugin_command() {
         .unwrap();
     assert_snapshot!(format!("{:#?}", list_clients_instruction));
 }
+
+#[test]
+pub fn composite_render_regions_positions_and_clips_regions() {
+    use crate::plugins::plugin_map::composite_render_regions;
+    use zellij_utils::data::RenderRegion;
+    let regions = vec![
+        RenderRegion::new(0, 0, 4, 2, "abcdef\nxy"),
+        RenderRegion::new(6, 1, 10, 3, "\u{1b}[1mbold\u{1b}[m text"),
+        RenderRegion::new(20, 0, 2, 2, "out of bounds"),
+    ];
+    let composited = composite_render_regions(&regions, 3, 10);
+    assert_eq!(
+        composited,
+        "\u{1b}[1;1Habcd\u{1b}[m\
+         \u{1b}[2;1Hxy\u{1b}[m  \
+         \u{1b}[2;7H\u{1b}[1mbold\u{1b}[m\u{1b}[m\
+         \u{1b}[3;7H\u{1b}[m    "
+    );
+}
+
+#[test]
+#[ignore]
+pub fn pipe_message_to_plugin_by_id() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::PluginBytes,
+        screen_receiver,
+        3,
+        &PermissionType::ReadCliPipes,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        false,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: "pipe_message_to_plugin".to_owned(),
+        payload: Some("payload_sent_to_self".to_owned()),
+        plugin: None,
+        args: None,
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id: client_id,
+        plugin_id: Some(0), // the first plugin loaded
+        is_last: true,
+    });
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_id_that_got_the_message = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ScreenInstruction::PluginBytes(plugin_render_assets) = i {
+                for plugin_render_asset in plugin_render_assets {
+                    let plugin_bytes = plugin_render_asset.bytes.clone();
+                    let plugin_bytes = String::from_utf8_lossy(plugin_bytes.as_slice()).to_string();
+                    if plugin_bytes.contains("Payload from self:") {
+                        return Some(plugin_render_asset.plugin_id);
+                    }
+                }
+            }
+            None
+        });
+    assert_eq!(plugin_id_that_got_the_message, Some(0));
+}
+
+#[test]
+pub fn pipe_message_to_plugin_id_that_is_not_running() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let (plugin_thread_sender, server_receiver, _screen_receiver, teardown) =
+        create_plugin_thread_with_server_receiver(Some(plugin_host_folder));
+    let client_id = 1;
+    let received_server_instruction = Arc::new(Mutex::new(vec![]));
+    let server_thread = log_actions_in_thread!(
+        received_server_instruction,
+        ServerInstruction::LogError,
+        server_receiver,
+        1
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: "pipe_message_to_plugin".to_owned(),
+        payload: Some("payload".to_owned()),
+        plugin: None,
+        args: None,
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id: client_id,
+        plugin_id: Some(42),
+        is_last: true,
+    });
+    server_thread.join().unwrap();
+    teardown();
+    let logged_error = received_server_instruction
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ServerInstruction::LogError(lines, error_client_id) = i {
+                Some((lines.clone(), *error_client_id))
+            } else {
+                None
+            }
+        });
+    assert_eq!(
+        logged_error,
+        Some((
+            vec!["No plugin with id 42 is running".to_owned()],
+            client_id
+        ))
+    );
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+fn worker_pool(size: usize) -> (WorkerPool, Vec<Receiver<MessageToWorker>>) {
+    let (senders, receivers): (Vec<_>, Vec<_>) = (0..size).map(|_| unbounded()).unzip();
+    (WorkerPool::new(senders), receivers)
+}
+
+fn instance_index(
+    worker_pool: &WorkerPool,
+    receivers: &[Receiver<MessageToWorker>],
+    instance_id: Option<u32>,
+) -> usize {
+    let instance = worker_pool.instance(instance_id).unwrap();
+    instance.try_send(MessageToWorker::Exit).unwrap();
+    receivers
+        .iter()
+        .position(|receiver| receiver.try_recv().is_ok())
+        .unwrap()
+}
+
+#[test]
+fn worker_pool_size_defaults_to_one() {
+    assert_eq!(clamp_worker_pool_size(None), 1);
+}
+
+#[test]
+fn worker_pool_size_is_clamped() {
+    assert_eq!(clamp_worker_pool_size(Some(0)), 1);
+    assert_eq!(clamp_worker_pool_size(Some(4)), 4);
+    assert_eq!(
+        clamp_worker_pool_size(Some(MAX_WORKER_POOL_SIZE)),
+        MAX_WORKER_POOL_SIZE
+    );
+    assert_eq!(
+        clamp_worker_pool_size(Some(usize::MAX)),
+        MAX_WORKER_POOL_SIZE
+    );
+}
+
+#[test]
+fn worker_pool_round_robins_messages_without_an_instance_id() {
+    let (worker_pool, receivers) = worker_pool(3);
+    let indices: Vec<usize> = (0..6)
+        .map(|_| instance_index(&worker_pool, &receivers, None))
+        .collect();
+    assert_eq!(indices, vec![0, 1, 2, 0, 1, 2]);
+}
+
+#[test]
+fn worker_pool_targets_instance_ids_modulo_the_pool_size() {
+    let (worker_pool, receivers) = worker_pool(3);
+    assert_eq!(instance_index(&worker_pool, &receivers, Some(1)), 1);
+    assert_eq!(instance_index(&worker_pool, &receivers, Some(5)), 2);
+    assert_eq!(instance_index(&worker_pool, &receivers, Some(3)), 0);
+}
+
+#[test]
+fn empty_worker_pool_has_no_instances() {
+    let (worker_pool, _receivers) = worker_pool(0);
+    assert!(worker_pool.instance(None).is_none());
+    assert!(worker_pool.instance(Some(0)).is_none());
+}
@@ -0,0 +1,156 @@
+use super::*;
+
+#[test]
+fn writing_through_run_action_requires_write_to_stdin() {
+    assert_eq!(
+        permission_for_action(&Action::WriteChars("ls\n".to_owned())),
+        Some(PermissionType::WriteToStdin)
+    );
+    assert_eq!(
+        permission_for_action(&Action::Write(None, vec![b'a'], false)),
+        Some(PermissionType::WriteToStdin)
+    );
+}
+
+#[test]
+fn panes_and_tabs_that_run_commands_require_run_commands() {
+    let command = RunCommandAction {
+        command: PathBuf::from("htop"),
+        ..Default::default()
+    };
+    assert_eq!(
+        permission_for_action(&Action::NewTiledPane(None, Some(command.clone()), None)),
+        Some(PermissionType::RunCommands)
+    );
+    assert_eq!(
+        permission_for_action(&Action::NewTiledPane(None, None, None)),
+        Some(PermissionType::OpenTerminalsOrPlugins)
+    );
+    assert_eq!(
+        permission_for_action(&Action::NewTab(
+            Some(Default::default()),
+            vec![],
+            None,
+            None,
+            None,
+            true
+        )),
+        Some(PermissionType::RunCommands)
+    );
+    assert_eq!(
+        permission_for_action(&Action::NewTab(None, vec![], None, None, None, true)),
+        Some(PermissionType::ChangeApplicationState)
+    );
+}
+
+#[test]
+fn editing_files_through_run_action_requires_open_files() {
+    assert_eq!(
+        permission_for_action(&Action::EditFile(
+            OpenFilePayload::new(PathBuf::from("/tmp/file"), None, None),
+            None,
+            false,
+            false,
+            false,
+            None
+        )),
+        Some(PermissionType::OpenFiles)
+    );
+}
+
+#[test]
+fn skip_confirm_requires_the_permission_of_the_wrapped_action() {
+    assert_eq!(
+        permission_for_action(&Action::SkipConfirm(Box::new(Action::WriteChars(
+            "exit\n".to_owned()
+        )))),
+        Some(PermissionType::WriteToStdin)
+    );
+}
+
+#[test]
+fn actions_without_an_equivalent_plugin_command_are_refused() {
+    assert_eq!(permission_for_action(&Action::Copy), None);
+    assert_eq!(
+        permission_for_action(&Action::DumpScreen("/tmp/dump".to_owned(), true)),
+        None
+    );
+    assert_eq!(permission_for_action(&Action::ToggleMouseMode), None);
+}
+
+#[test]
+fn blob_hashes_depend_on_both_the_key_and_the_data() {
+    let hash = blob_hash("key", b"data");
+    assert!(is_valid_blob_hash(&hash));
+    assert_eq!(hash, blob_hash("key", b"data"));
+    assert_ne!(hash, blob_hash("other-key", b"data"));
+    assert_ne!(hash, blob_hash("key", b"other data"));
+    assert_ne!(blob_hash("ab", b"c"), blob_hash("a", b"bc"));
+}
+
+#[test]
+fn blob_hashes_that_could_leave_the_blob_folder_are_invalid() {
+    assert!(!is_valid_blob_hash("../../etc/passwd"));
+    assert!(!is_valid_blob_hash(""));
+    assert!(!is_valid_blob_hash(&"g".repeat(64)));
+    assert!(!is_valid_blob_hash(&"a".repeat(63)));
+}
+
+#[test]
+fn storing_blobs_is_limited_per_plugin() {
+    let blob_root = tempfile::tempdir().unwrap();
+    let first_plugin_dir = blob_root.path().join("first");
+    let second_plugin_dir = blob_root.path().join("second");
+    let first_hash = blob_hash("first", &[0; 6]);
+    let second_hash = blob_hash("second", &[0; 6]);
+    write_blob(&first_plugin_dir, &first_hash, &[0; 6], 10).unwrap();
+    // storing the same blob again takes no extra room
+    write_blob(&first_plugin_dir, &first_hash, &[0; 6], 10).unwrap();
+    assert!(matches!(
+        write_blob(&first_plugin_dir, &second_hash, &[0; 6], 10),
+        Err(PluginError::InvalidArgument(..))
+    ));
+    write_blob(&second_plugin_dir, &second_hash, &[0; 6], 10).unwrap();
+    assert_eq!(
+        blobs_in_dir(&first_plugin_dir)
+            .map(|blob| (blob.hash, blob.size))
+            .collect::<Vec<_>>(),
+        vec![(first_hash, 6)]
+    );
+}
+
+#[test]
+fn blobs_stored_by_one_plugin_can_be_found_by_all() {
+    let blob_root = tempfile::tempdir().unwrap();
+    let hash = blob_hash("key", b"data");
+    write_blob(&blob_root.path().join("first"), &hash, b"data", 10).unwrap();
+    assert_eq!(
+        find_blob(blob_root.path(), &hash),
+        Some(blob_root.path().join("first").join(&hash))
+    );
+    assert_eq!(
+        find_blob(blob_root.path(), &blob_hash("key", b"other")),
+        None
+    );
+}
+
+#[test]
+fn only_http_https_and_mailto_urls_can_be_opened() {
+    assert!(url_has_openable_scheme("https://zellij.dev"));
+    assert!(url_has_openable_scheme("http://localhost:8082"));
+    assert!(url_has_openable_scheme("HTTPS://zellij.dev"));
+    assert!(url_has_openable_scheme("mailto:someone@example.com"));
+    assert!(!url_has_openable_scheme("file:///etc/passwd"));
+    assert!(!url_has_openable_scheme("javascript:alert(1)"));
+    assert!(!url_has_openable_scheme("smb://host/share"));
+    assert!(!url_has_openable_scheme("zellij.dev"));
+}
+
+#[test]
+fn urls_with_whitespace_or_control_characters_are_refused() {
+    assert!(!url_has_special_characters(
+        "https://zellij.dev/documentation"
+    ));
+    assert!(url_has_special_characters("https://zellij.dev/ --help"));
+    assert!(url_has_special_characters("https://zellij.dev/\n"));
+}
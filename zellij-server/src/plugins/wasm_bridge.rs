@@ -7,7 +7,7 @@ use crate::plugins::plugin_map::{AtomicEvent, PluginEnv, PluginMap, RunningPlugi
 
 use crate::plugins::plugin_worker::MessageToWorker;
 use crate::plugins::watch_filesystem::watch_filesystem;
-use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
+use crate::plugins::zellij_exports::wasi_write_object;
 use highway::{HighwayHash, PortableHash};
 use log::info;
 use std::{
@@ -15,20 +15,26 @@ use std::{
     path::PathBuf,
     str::FromStr,
     sync::{Arc, Mutex},
+    time::Instant,
 };
 use wasmtime::{Engine, Module};
 use zellij_utils::async_channel::Sender;
 use zellij_utils::async_std::task::{self, JoinHandle};
 use zellij_utils::consts::{ZELLIJ_CACHE_DIR, ZELLIJ_TMP_DIR};
-use zellij_utils::data::{InputMode, PermissionStatus, PermissionType, PipeMessage, PipeSource};
+use zellij_utils::data::{
+    InputMode, PermissionStatus, PermissionType, PipeMessage, PipeSource, PluginDependency,
+    PluginInfo,
+};
 use zellij_utils::downloader::Downloader;
 use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::notify_debouncer_full::{notify::RecommendedWatcher, Debouncer, FileIdMap};
 use zellij_utils::plugin_api::event::ProtobufEvent;
+use zellij_utils::plugin_manifest::plugin_dependencies;
 
 use zellij_utils::prost::Message;
 
+use crate::metrics::Metric;
 use crate::panes::PaneId;
 use crate::{
     background_jobs::BackgroundJob, screen::ScreenInstruction, thread_bus::ThreadSenders,
@@ -40,6 +46,7 @@ use zellij_utils::{
     input::{
         command::TerminalAction,
         layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation, RunPluginOrAlias},
+        parse_keys,
         plugins::PluginConfig,
     },
     ipc::ClientAttributes,
@@ -87,8 +94,9 @@ pub struct WasmBridge {
     plugin_ids_waiting_for_permission_request: HashSet<PluginId>,
     cached_events_for_pending_plugins: HashMap<PluginId, Vec<EventOrPipeMessage>>,
     cached_resizes_for_pending_plugins: HashMap<PluginId, (usize, usize)>, // (rows, columns)
-    cached_worker_messages: HashMap<PluginId, Vec<(ClientId, String, String, String)>>, // Vec<clientid,
+    cached_worker_messages: HashMap<PluginId, Vec<(ClientId, String, Option<u32>, String, String)>>, // Vec<clientid,
     // worker_name,
+    // worker instance id,
     // message,
     // payload>
     loading_plugins: HashMap<(PluginId, RunPlugin), JoinHandle<()>>, // plugin_id to join-handle
@@ -229,17 +237,14 @@ impl WasmBridge {
                         .cloned()
                         .unwrap_or_else(|| self.default_keybinds.clone());
                     async move {
+                        let load_start_time = Instant::now();
                         let _ = senders.send_to_background_jobs(
                             BackgroundJob::AnimatePluginLoading(plugin_id),
                         );
                         let mut loading_indication = LoadingIndication::new(plugin_name.clone());
 
                         if let RunPluginLocation::Remote(url) = &plugin.location {
-                            let file_name: String = PortableHash::default()
-                                .hash128(url.as_bytes())
-                                .iter()
-                                .map(ToString::to_string)
-                                .collect();
+                            let file_name = cached_file_name_for_url(url);
 
                             // if the url is already in cache, we'll use that version, otherwise
                             // we'll download it, place it in cache and then use it
@@ -282,6 +287,7 @@ impl WasmBridge {
                             Ok(_) => {
                                 let plugin_list = plugin_map.lock().unwrap().list_plugins();
                                 handle_plugin_successful_loading(&senders, plugin_id, plugin_list);
+                                emit_plugin_load_time(&senders, &plugin, load_start_time);
                             },
                             Err(e) => handle_plugin_loading_failure(
                                 &senders,
@@ -319,8 +325,8 @@ impl WasmBridge {
         info!("Bye from plugin {}", &pid);
         let mut plugin_map = self.plugin_map.lock().unwrap();
         for (running_plugin, _, workers) in plugin_map.remove_plugins(pid) {
-            for (_worker_name, worker_sender) in workers {
-                drop(worker_sender.send(MessageToWorker::Exit));
+            for worker_pool in workers.values() {
+                worker_pool.exit();
             }
             let running_plugin = running_plugin.lock().unwrap();
             let cache_dir = running_plugin.store.data().plugin_own_data_dir.clone();
@@ -607,19 +613,7 @@ impl WasmBridge {
                             // make sure to always render on the first resize event
                             if old_rows != new_rows || old_columns != new_columns || event_id == 0 {
                                 let rendered_bytes = running_plugin
-                                    .instance
-                                    .clone()
-                                    .get_typed_func::<(i32, i32), ()>(
-                                        &mut running_plugin.store,
-                                        "render",
-                                    )
-                                    .and_then(|render| {
-                                        render.call(
-                                            &mut running_plugin.store,
-                                            (new_rows as i32, new_columns as i32),
-                                        )
-                                    })
-                                    .and_then(|_| wasi_read_string(running_plugin.store.data()))
+                                    .render(new_rows, new_columns)
                                     .with_context(err_context);
                                 match rendered_bytes {
                                     Ok(rendered_bytes) => {
@@ -683,42 +677,46 @@ impl WasmBridge {
                     for (plugin_id, client_id, running_plugin, subscriptions) in &plugins_to_update
                     {
                         let subs = subscriptions.lock().unwrap().clone();
-                        // FIXME: This is very janky... Maybe I should write my own macro for Event -> EventType?
-                        if let Ok(event_type) = EventType::from_str(&event.to_string()) {
-                            if (subs.contains(&event_type)
-                                || event_type == EventType::PermissionRequestResult)
-                                && Self::message_is_directed_at_plugin(
-                                    pid, cid, plugin_id, client_id,
-                                )
-                            {
-                                let mut running_plugin = running_plugin.lock().unwrap();
-                                let mut plugin_render_assets = vec![];
-                                match apply_event_to_plugin(
-                                    *plugin_id,
-                                    *client_id,
-                                    &mut running_plugin,
-                                    &event,
-                                    &mut plugin_render_assets,
-                                    senders.clone(),
-                                ) {
-                                    Ok(()) => {
-                                        let _ = senders.send_to_screen(
-                                            ScreenInstruction::PluginBytes(plugin_render_assets),
-                                        );
-                                    },
-                                    Err(e) => {
-                                        log::error!("{:?}", e);
+                        for event in events_for_subscriptions(&event, &subs) {
+                            // FIXME: This is very janky... Maybe I should write my own macro for Event -> EventType?
+                            if let Ok(event_type) = EventType::from_str(&event.to_string()) {
+                                if (subs.contains(&event_type)
+                                    || event_type == EventType::PermissionRequestResult)
+                                    && Self::message_is_directed_at_plugin(
+                                        pid, cid, plugin_id, client_id,
+                                    )
+                                {
+                                    let mut running_plugin = running_plugin.lock().unwrap();
+                                    let mut plugin_render_assets = vec![];
+                                    match apply_event_to_plugin(
+                                        *plugin_id,
+                                        *client_id,
+                                        &mut running_plugin,
+                                        &event,
+                                        &mut plugin_render_assets,
+                                        senders.clone(),
+                                    ) {
+                                        Ok(()) => {
+                                            let _ = senders.send_to_screen(
+                                                ScreenInstruction::PluginBytes(
+                                                    plugin_render_assets,
+                                                ),
+                                            );
+                                        },
+                                        Err(e) => {
+                                            log::error!("{:?}", e);
 
-                                        // https://stackoverflow.com/questions/66450942/in-rust-is-there-a-way-to-make-literal-newlines-in-r-using-windows-c
-                                        let stringified_error =
-                                            format!("{:?}", e).replace("\n", "\n\r");
+                                            // https://stackoverflow.com/questions/66450942/in-rust-is-there-a-way-to-make-literal-newlines-in-r-using-windows-c
+                                            let stringified_error =
+                                                format!("{:?}", e).replace("\n", "\n\r");
 
-                                        handle_plugin_crash(
-                                            *plugin_id,
-                                            stringified_error,
-                                            senders.clone(),
-                                        );
-                                    },
+                                            handle_plugin_crash(
+                                                *plugin_id,
+                                                stringified_error,
+                                                senders.clone(),
+                                            );
+                                        },
+                                    }
                                 }
                             }
                         }
@@ -737,6 +735,31 @@ impl WasmBridge {
         }
         Ok(())
     }
+    pub fn get_plugin_dependencies(&self, url: String, plugin_id: PluginId, client_id: ClientId) {
+        let senders = self.senders.clone();
+        let downloader = self.downloader.clone();
+        let zellij_cwd = self.zellij_cwd.clone();
+        task::spawn(async move {
+            let dependencies = match read_plugin_dependencies(&url, zellij_cwd, &downloader).await {
+                Ok(dependencies) => dependencies,
+                Err(e) => {
+                    // the plugin will fail to load with a proper error if it is loaded anyway
+                    log::error!("Failed to read the dependencies of plugin {}: {}", url, e);
+                    vec![]
+                },
+            };
+            let plugin_info = PluginInfo {
+                location: url,
+                configuration: BTreeMap::new(),
+                dependencies,
+            };
+            let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                Some(plugin_id),
+                Some(client_id),
+                Event::PluginDependencies(plugin_info),
+            )]));
+        });
+    }
     pub fn change_plugin_host_dir(
         &mut self,
         new_host_dir: PathBuf,
@@ -1089,39 +1112,41 @@ impl WasmBridge {
                             for event_or_pipe_message in events_or_pipe_messages {
                                 match event_or_pipe_message {
                                     EventOrPipeMessage::Event(event) => {
-                                        match EventType::from_str(&event.to_string())
-                                            .with_context(err_context)
-                                        {
-                                            Ok(event_type) => {
-                                                if !subs.contains(&event_type) {
-                                                    continue;
-                                                }
-                                                let mut running_plugin =
-                                                    running_plugin.lock().unwrap();
-                                                let mut plugin_render_assets = vec![];
-                                                match apply_event_to_plugin(
-                                                    plugin_id,
-                                                    client_id,
-                                                    &mut running_plugin,
-                                                    &event,
-                                                    &mut plugin_render_assets,
-                                                    senders.clone(),
-                                                ) {
-                                                    Ok(()) => {
-                                                        let _ = senders.send_to_screen(
-                                                            ScreenInstruction::PluginBytes(
-                                                                plugin_render_assets,
-                                                            ),
-                                                        );
-                                                    },
-                                                    Err(e) => {
-                                                        log::error!("{}", e);
-                                                    },
-                                                }
-                                            },
-                                            Err(e) => {
-                                                log::error!("Failed to apply event: {:?}", e);
-                                            },
+                                        for event in events_for_subscriptions(&event, &subs) {
+                                            match EventType::from_str(&event.to_string())
+                                                .with_context(err_context)
+                                            {
+                                                Ok(event_type) => {
+                                                    if !subs.contains(&event_type) {
+                                                        continue;
+                                                    }
+                                                    let mut running_plugin =
+                                                        running_plugin.lock().unwrap();
+                                                    let mut plugin_render_assets = vec![];
+                                                    match apply_event_to_plugin(
+                                                        plugin_id,
+                                                        client_id,
+                                                        &mut running_plugin,
+                                                        &event,
+                                                        &mut plugin_render_assets,
+                                                        senders.clone(),
+                                                    ) {
+                                                        Ok(()) => {
+                                                            let _ = senders.send_to_screen(
+                                                                ScreenInstruction::PluginBytes(
+                                                                    plugin_render_assets,
+                                                                ),
+                                                            );
+                                                        },
+                                                        Err(e) => {
+                                                            log::error!("{}", e);
+                                                        },
+                                                    }
+                                                },
+                                                Err(e) => {
+                                                    log::error!("Failed to apply event: {:?}", e);
+                                                },
+                                            }
                                         }
                                     },
                                     EventOrPipeMessage::PipeMessage(pipe_message) => {
@@ -1173,16 +1198,24 @@ impl WasmBridge {
     }
     pub fn apply_cached_worker_messages(&mut self, plugin_id: PluginId) -> Result<()> {
         if let Some(mut messages) = self.cached_worker_messages.remove(&plugin_id) {
-            let mut worker_messages: HashMap<(ClientId, String), Vec<(String, String)>> =
-                HashMap::new();
-            for (client_id, worker_name, message, payload) in messages.drain(..) {
+            let mut worker_messages: HashMap<
+                (ClientId, String, Option<u32>),
+                Vec<(String, String)>,
+            > = HashMap::new();
+            for (client_id, worker_name, instance_id, message, payload) in messages.drain(..) {
                 worker_messages
-                    .entry((client_id, worker_name))
+                    .entry((client_id, worker_name, instance_id))
                     .or_default()
                     .push((message, payload));
             }
-            for ((client_id, worker_name), messages) in worker_messages.drain() {
-                self.post_messages_to_plugin_worker(plugin_id, client_id, worker_name, messages)?;
+            for ((client_id, worker_name, instance_id), messages) in worker_messages.drain() {
+                self.post_messages_to_plugin_worker(
+                    plugin_id,
+                    client_id,
+                    worker_name,
+                    instance_id,
+                    messages,
+                )?;
             }
         }
         Ok(())
@@ -1276,13 +1309,15 @@ impl WasmBridge {
         plugin_id: PluginId,
         client_id: ClientId,
         worker_name: String,
+        instance_id: Option<u32>,
         mut messages: Vec<(String, String)>,
     ) -> Result<()> {
-        let worker =
-            self.plugin_map
-                .lock()
-                .unwrap()
-                .worker_sender(plugin_id, client_id, &worker_name);
+        let worker = self.plugin_map.lock().unwrap().worker_sender(
+            plugin_id,
+            client_id,
+            &worker_name,
+            instance_id,
+        );
         match worker {
             Some(worker) => {
                 for (message, payload) in messages.drain(..) {
@@ -1297,7 +1332,13 @@ impl WasmBridge {
                     self.cached_worker_messages
                         .entry(plugin_id)
                         .or_default()
-                        .push((client_id, worker_name.clone(), message, payload));
+                        .push((
+                            client_id,
+                            worker_name.clone(),
+                            instance_id,
+                            message,
+                            payload,
+                        ));
                 }
             },
         }
@@ -1498,6 +1539,17 @@ fn handle_plugin_successful_loading(
     let _ = senders.send_to_background_jobs(BackgroundJob::ReportPluginList(plugin_list));
 }
 
+fn emit_plugin_load_time(senders: &ThreadSenders, plugin: &PluginConfig, load_start_time: Instant) {
+    let mut labels = BTreeMap::new();
+    labels.insert("plugin".to_owned(), plugin.location.to_string());
+    let load_time_ms = load_start_time.elapsed().as_secs_f64() * 1000.0;
+    let _ = senders.send_to_background_jobs(BackgroundJob::EmitMetric(Metric::new(
+        "zellij_plugin_load_time_ms",
+        load_time_ms,
+        labels,
+    )));
+}
+
 fn handle_plugin_loading_failure(
     senders: &ThreadSenders,
     plugin_id: PluginId,
@@ -1536,7 +1588,9 @@ fn check_event_permission(
         | Event::PaneUpdate(..)
         | Event::SessionUpdate(..)
         | Event::CopyToClipboard(..)
+        | Event::PaneSelection { .. }
         | Event::SystemClipboardFailure
+        | Event::PaneCountExceeded
         | Event::CommandPaneOpened(..)
         | Event::CommandPaneExited(..)
         | Event::PaneClosed(..)
@@ -1544,7 +1598,9 @@ fn check_event_permission(
         | Event::EditPaneExited(..)
         | Event::FailedToWriteConfigToDisk(..)
         | Event::CommandPaneReRun(..)
-        | Event::InputReceived => PermissionType::ReadApplicationState,
+        | Event::InputReceived
+        | Event::SessionRenamed(..)
+        | Event::ConfigUpdate(..) => PermissionType::ReadApplicationState,
         _ => return (PermissionStatus::Granted, None),
     };
 
@@ -1583,6 +1639,8 @@ pub fn apply_event_to_plugin(
                 // would be a breaking change.
                 mode_info.keybinds = running_plugin.store.data().keybinds.to_keybinds_vec();
                 mode_info.base_mode = Some(running_plugin.store.data().default_mode);
+                // plugins are instantiated per client, so this is the client the mode is of
+                mode_info.client_id = Some(client_id);
             }
             let protobuf_event: ProtobufEvent = event
                 .clone()
@@ -1603,12 +1661,8 @@ pub fn apply_event_to_plugin(
                 should_render = true;
             }
             if rows > 0 && columns > 0 && should_render {
-                let rendered_bytes = instance
-                    .get_typed_func::<(i32, i32), ()>(&mut running_plugin.store, "render")
-                    .and_then(|render| {
-                        render.call(&mut running_plugin.store, (rows as i32, columns as i32))
-                    })
-                    .and_then(|_| wasi_read_string(running_plugin.store.data()))
+                let rendered_bytes = running_plugin
+                    .render(rows, columns)
                     .with_context(err_context)?;
                 let pipes_to_block_or_unblock = pipes_to_block_or_unblock(running_plugin, None);
                 let plugin_render_asset = PluginRenderAsset::new(
@@ -1645,6 +1699,54 @@ pub fn apply_event_to_plugin(
     Ok(())
 }
 
+// plugins that are not subscribed to pasted text receive it as keys, the way they did before it
+// had an event of its own
+fn events_for_subscriptions(event: &Event, subscriptions: &Subscriptions) -> Vec<Event> {
+    match event {
+        Event::PastedText(pasted_text) if !subscriptions.contains(&EventType::PastedText) => {
+            parse_keys(pasted_text.as_bytes())
+                .into_iter()
+                .map(Event::Key)
+                .collect()
+        },
+        _ => vec![event.clone()],
+    }
+}
+
+// remote plugins are cached under a hash of their url
+fn cached_file_name_for_url(url: &str) -> String {
+    PortableHash::default()
+        .hash128(url.as_bytes())
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+// remote plugins are downloaded into the cache, so that loading them afterwards does not
+// download them again
+async fn read_plugin_dependencies(
+    url: &str,
+    cwd: PathBuf,
+    downloader: &Downloader,
+) -> std::result::Result<Vec<PluginDependency>, String> {
+    let plugin_path = match RunPluginLocation::parse(url, Some(cwd)).map_err(|e| e.to_string())? {
+        RunPluginLocation::File(path) => path,
+        RunPluginLocation::Remote(url) => {
+            let file_name = cached_file_name_for_url(&url);
+            downloader
+                .download(&url, Some(&file_name))
+                .await
+                .map_err(|e| e.to_string())?;
+            ZELLIJ_CACHE_DIR.join(&file_name)
+        },
+        // built-in plugins do not have dependencies
+        RunPluginLocation::Zellij(_) => return Ok(vec![]),
+    };
+    let wasm_bytes =
+        std::fs::read(&plugin_path).map_err(|e| format!("{}: {}", plugin_path.display(), e))?;
+    plugin_dependencies(&wasm_bytes)
+}
+
 pub fn handle_plugin_crash(plugin_id: PluginId, message: String, senders: ThreadSenders) {
     let mut loading_indication = LoadingIndication::new("Panic!".to_owned());
     loading_indication.indicate_loading_error(message);
@@ -1,16 +1,21 @@
 use super::PluginInstruction;
-use crate::background_jobs::BackgroundJob;
+use crate::background_jobs::{
+    BackgroundJob, DEFAULT_COMMAND_PIPE_TIMEOUT, MAX_COMMAND_PIPE_TIMEOUT,
+};
+use crate::metrics::Metric;
 use crate::plugins::plugin_map::PluginEnv;
 use crate::plugins::wasm_bridge::handle_plugin_crash;
 use crate::pty::{ClientTabIndexOrPaneId, PtyInstruction};
 use crate::route::route_action;
-use crate::ServerInstruction;
+use crate::{ClientId, ServerInstruction};
+use highway::{HighwayHash, PortableHash};
 use log::warn;
 use serde::Serialize;
 use std::{
     collections::{BTreeMap, HashSet},
-    io::{Read, Write},
-    path::PathBuf,
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
     process,
     str::FromStr,
     thread,
@@ -19,11 +24,13 @@ use std::{
 use wasmtime::{Caller, Linker};
 use zellij_utils::data::{
     CommandType, ConnectToSession, FloatingPaneCoordinates, HttpVerb, KeyWithModifier, LayoutInfo,
-    MessageToPlugin, OriginatingPlugin, PermissionStatus, PermissionType, PluginPermission,
+    MessageToPlugin, OpenLocation, OriginatingPlugin, PermissionStatus, PermissionType,
+    PluginError, PluginPermission, SessionInfo,
 };
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::{
     async_std::task,
+    channels, envs,
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientToServerMsg, IpcSenderWithContext},
 };
@@ -31,10 +38,13 @@ use zellij_utils::{
 use crate::{panes::PaneId, screen::ScreenInstruction};
 
 use zellij_utils::{
-    consts::{VERSION, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR},
+    consts::{
+        session_folder_within, session_tags_file_name, DEBUG_MODE, VERSION, ZELLIJ_BLOB_DIR,
+        ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+    },
     data::{
-        CommandToRun, Direction, Event, EventType, FileToOpen, InputMode, PluginCommand, PluginIds,
-        PluginMessage, Resize, ResizeStrategy,
+        CommandToRun, Direction, Event, EventType, FileToOpen, GroupId, InputMode, PluginCommand,
+        PluginIds, PluginMessage, Resize, ResizeStrategy,
     },
     errors::prelude::*,
     input::{
@@ -43,13 +53,21 @@ use zellij_utils::{
         layout::{Layout, RunPluginOrAlias},
     },
     plugin_api::{
-        plugin_command::ProtobufPluginCommand,
+        event::ProtobufEvent,
+        input_mode::ProtobufInputModeMessage,
+        plugin_command::{
+            BlobContent, BlobHash, BlobInfo, BlobList, ProtobufPluginCommand,
+            ProtobufPluginCommandResponse, SynchronizedGroup,
+        },
         plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
+        style::ProtobufStyle,
     },
     prost::Message,
     serde,
 };
 
+const SCREEN_QUERY_TIMEOUT_MS: u64 = 1000;
+
 macro_rules! apply_action {
     ($action:ident, $error_message:ident, $env: ident) => {
         if let Err(e) = route_action(
@@ -74,10 +92,33 @@ pub fn zellij_exports(linker: &mut Linker<PluginEnv>) {
     linker
         .func_wrap("zellij", "host_run_plugin_command", host_run_plugin_command)
         .unwrap();
+    linker
+        .func_wrap(
+            "zellij",
+            "host_run_plugin_command_with_response",
+            host_run_plugin_command_with_response,
+        )
+        .unwrap();
+}
+
+// plugins built against a zellij-tile that predates PluginCommandResponse only import
+// host_run_plugin_command and read the bare payload of a response, so they are still answered
+// that way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseFormat {
+    Bare,
+    Envelope,
 }
 
 fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
-    let env = caller.data();
+    run_plugin_command(caller.data(), ResponseFormat::Bare);
+}
+
+fn host_run_plugin_command_with_response(caller: Caller<'_, PluginEnv>) {
+    run_plugin_command(caller.data(), ResponseFormat::Envelope);
+}
+
+fn run_plugin_command(env: &PluginEnv, response_format: ResponseFormat) {
     let err_context = || format!("failed to run plugin command {}", env.name());
     wasi_read_bytes(env)
         .and_then(|bytes| {
@@ -90,8 +131,40 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                     PluginCommand::Subscribe(event_list) => subscribe(env, event_list)?,
                     PluginCommand::Unsubscribe(event_list) => unsubscribe(env, event_list)?,
                     PluginCommand::SetSelectable(selectable) => set_selectable(env, selectable),
-                    PluginCommand::GetPluginIds => get_plugin_ids(env),
-                    PluginCommand::GetZellijVersion => get_zellij_version(env),
+                    PluginCommand::GetPluginIds => {
+                        write_response_to_plugin(env, get_plugin_ids(env), response_format)
+                    },
+                    PluginCommand::GetZellijVersion => {
+                        write_response_to_plugin(env, get_zellij_version(), response_format)
+                    },
+                    PluginCommand::GetCurrentTabs => {
+                        write_response_to_plugin(env, get_current_tabs(env), response_format)
+                    },
+                    PluginCommand::GetAllPanes => {
+                        write_response_to_plugin(env, get_all_panes(env), response_format)
+                    },
+                    PluginCommand::GetCurrentStyling => {
+                        write_response_to_plugin(env, get_current_styling(env), response_format)
+                    },
+                    PluginCommand::ListSessions => {
+                        write_response_to_plugin(env, list_sessions(env), response_format)
+                    },
+                    PluginCommand::StoreBlob(key, data) => {
+                        write_response_to_plugin(env, store_blob(env, key, data), response_format)
+                    },
+                    PluginCommand::LoadBlob(hash) => {
+                        write_response_to_plugin(env, load_blob(env, hash), response_format)
+                    },
+                    PluginCommand::ListBlobs => {
+                        write_response_to_plugin(env, list_blobs(env), response_format)
+                    },
+                    PluginCommand::DeleteBlob(hash) => delete_blob(env, hash),
+                    PluginCommand::EmitMetric(name, value, labels) => {
+                        emit_metric(env, name, value, labels)
+                    },
+                    PluginCommand::SetPaneContent(pane_id, content) => {
+                        set_pane_content(env, pane_id.into(), content)
+                    },
                     PluginCommand::OpenFile(file_to_open, context) => {
                         open_file(env, file_to_open, context)
                     },
@@ -127,7 +200,10 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                         web_request(env, url, verb, headers, body, context)
                     },
                     PluginCommand::PostMessageTo(plugin_message) => {
-                        post_message_to(env, plugin_message)?
+                        post_message_to(env, plugin_message, None)?
+                    },
+                    PluginCommand::PostMessageToWorkerInstance(plugin_message, instance_id) => {
+                        post_message_to(env, plugin_message, Some(instance_id))?
                     },
                     PluginCommand::PostMessageToPlugin(plugin_message) => {
                         post_message_to_plugin(env, plugin_message)?
@@ -136,6 +212,10 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                     PluginCommand::ShowSelf(should_float_if_hidden) => {
                         show_self(env, should_float_if_hidden)
                     },
+                    PluginCommand::ToggleSelf(should_float_if_hidden) => {
+                        toggle_self(env, should_float_if_hidden)?
+                    },
+                    PluginCommand::RunAction(action, context) => run_action(env, action, context)?,
                     PluginCommand::SwitchToMode(input_mode) => {
                         switch_to_mode(env, input_mode.try_into()?)
                     },
@@ -173,6 +253,8 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                     PluginCommand::PageScrollUp => page_scroll_up(env),
                     PluginCommand::PageScrollDown => page_scroll_down(env),
                     PluginCommand::ToggleFocusFullscreen => toggle_focus_fullscreen(env),
+                    PluginCommand::TogglePanePinned => toggle_pane_pinned(env),
+                    PluginCommand::GetPluginDependencies(url) => get_plugin_dependencies(env, url),
                     PluginCommand::TogglePaneFrames => toggle_pane_frames(env),
                     PluginCommand::TogglePaneEmbedOrEject => toggle_pane_embed_or_eject(env),
                     PluginCommand::UndoRenamePane => undo_rename_pane(env),
@@ -221,6 +303,7 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                         connect_to_session.pane_id,
                         connect_to_session.layout,
                         connect_to_session.cwd,
+                        connect_to_session.tags,
                     )?,
                     PluginCommand::DeleteDeadSession(session_name) => {
                         delete_dead_session(session_name)?
@@ -247,6 +330,9 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                     PluginCommand::CliPipeOutput(pipe_name, output) => {
                         cli_pipe_output(env, pipe_name, output)?
                     },
+                    PluginCommand::CliPipeChunk(pipe_name, chunk, is_last) => {
+                        cli_pipe_chunk(env, pipe_name, chunk, is_last)?
+                    },
                     PluginCommand::MessageToPlugin(message) => message_to_plugin(env, message)?,
                     PluginCommand::DisconnectOtherClients => disconnect_other_clients(env),
                     PluginCommand::KillSessions(session_list) => kill_sessions(session_list),
@@ -301,6 +387,12 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                     PluginCommand::ScrollToTopInPaneId(pane_id) => {
                         scroll_to_top_in_pane_id(env, pane_id.into())
                     },
+                    PluginCommand::ScrollToLineInPaneId(pane_id, line) => {
+                        scroll_to_line_in_pane_id(env, pane_id.into(), line)
+                    },
+                    PluginCommand::SetPaneScrollbackSize(pane_id, scrollback_size) => {
+                        set_pane_scrollback_size(env, pane_id.into(), scrollback_size)
+                    },
                     PluginCommand::ScrollToBottomInPaneId(pane_id) => {
                         scroll_to_bottom_in_pane_id(env, pane_id.into())
                     },
@@ -319,6 +411,8 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                     PluginCommand::CloseTabWithIndex(tab_index) => {
                         close_tab_with_index(env, tab_index)
                     },
+                    PluginCommand::PinTab(tab_index) => set_tab_pinned(env, tab_index, true),
+                    PluginCommand::UnpinTab(tab_index) => set_tab_pinned(env, tab_index, false),
                     PluginCommand::BreakPanesToNewTab(
                         pane_ids,
                         new_tab_name,
@@ -351,6 +445,19 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                         keys_to_unbind,
                         write_config_to_disk,
                     } => rebind_keys(env, keys_to_rebind, keys_to_unbind, write_config_to_disk)?,
+                    PluginCommand::RegisterGlobalHotkey(key, action_id) => {
+                        register_global_hotkey(env, key, action_id)?
+                    },
+                    PluginCommand::DuplicatePane(pane_id) => duplicate_pane(env, pane_id.into()),
+                    PluginCommand::SetClientMode(client_id, input_mode) => {
+                        set_client_mode(env, client_id, input_mode)
+                    },
+                    PluginCommand::AnnotateScrollbackLine(pane_id, line_index, annotation) => {
+                        annotate_scrollback_line(env, pane_id.into(), line_index, annotation)
+                    },
+                    PluginCommand::ClearScrollbackAnnotations(pane_id) => {
+                        clear_scrollback_annotations(env, pane_id.into())
+                    },
                     PluginCommand::ListClients => list_clients(env),
                     PluginCommand::ChangeHostFolder(new_host_folder) => {
                         change_host_folder(env, new_host_folder)
@@ -358,11 +465,70 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                     PluginCommand::SetFloatingPanePinned(pane_id, should_be_pinned) => {
                         set_floating_pane_pinned(env, pane_id.into(), should_be_pinned)
                     },
+                    PluginCommand::SetPaneZIndex(pane_id, z_index) => {
+                        set_pane_z_index(env, pane_id.into(), z_index)
+                    },
                     PluginCommand::StackPanes(pane_ids) => {
                         stack_panes(env, pane_ids.into_iter().map(|p_id| p_id.into()).collect())
                     },
+                    PluginCommand::CreateSynchronizedGroup(name, pane_ids) => {
+                        let response = create_synchronized_group(
+                            env,
+                            name,
+                            pane_ids.into_iter().map(|p_id| p_id.into()).collect(),
+                        );
+                        write_response_to_plugin(env, response, response_format)
+                    },
+                    PluginCommand::DissolveSynchronizedGroup(group_id) => {
+                        dissolve_synchronized_group(env, group_id)
+                    },
+                    PluginCommand::SetFocusFollowsMouse(enable) => {
+                        set_focus_follows_mouse(env, enable)
+                    },
+                    PluginCommand::GetCurrentInputMode => {
+                        write_response_to_plugin(env, get_current_input_mode(env), response_format)
+                    },
+                    PluginCommand::PipeTextToCommand(text, command_to_run, timeout, context) => {
+                        pipe_text_to_command(env, text, command_to_run, timeout, context)
+                    },
+                    PluginCommand::RunCommandInPane(pane_id, command_to_run, env_variables) => {
+                        run_command_in_pane(env, pane_id.into(), command_to_run, env_variables)
+                    },
+                    PluginCommand::OpenFileInEditor(path, open_location) => {
+                        open_file_in_editor(env, path, open_location)
+                    },
+                    PluginCommand::SetSelfMinSize(rows, cols) => set_self_min_size(env, rows, cols),
+                    PluginCommand::SearchPane(pane_id, query, case_sensitive, regex) => {
+                        search_pane(env, pane_id.into(), query, case_sensitive, regex)
+                    },
+                    PluginCommand::PaneSnapshot(pane_id) => pane_snapshot(env, pane_id.into()),
+                    PluginCommand::OpenUrl(url) => open_url(url),
+                    PluginCommand::CreateFloatingPaneAt {
+                        url,
+                        config,
+                        coordinates,
+                    } => create_floating_pane_at(env, url, config, coordinates),
+                    PluginCommand::SetSessionTags(session_name, tags) => {
+                        set_session_tags(session_name, tags)?
+                    },
+                    PluginCommand::DragPaneToCoordinates(pane_id, x, y) => {
+                        drag_pane_to_coordinates(env, pane_id.into(), x, y)
+                    },
+                    PluginCommand::SubscribeToChannel(channel) => {
+                        subscribe_to_channel(env, channel)?
+                    },
+                    PluginCommand::PublishToChannel(channel, payload) => {
+                        publish_to_channel(env, channel, payload)?
+                    },
                 },
                 (PermissionStatus::Denied, permission) => {
+                    if command_expects_response(&command) {
+                        write_response_to_plugin(
+                            env,
+                            Err(PluginError::PermissionDenied),
+                            response_format,
+                        );
+                    }
                     log::error!(
                         "Plugin '{}' permission '{}' denied - Command '{:?}' denied",
                         env.name(),
@@ -406,6 +572,12 @@ fn cli_pipe_output(env: &PluginEnv, pipe_name: String, output: String) -> Result
         .context("failed to send pipe output")
 }
 
+fn cli_pipe_chunk(env: &PluginEnv, pipe_name: String, chunk: Vec<u8>, is_last: bool) -> Result<()> {
+    env.senders
+        .send_to_server(ServerInstruction::CliPipeChunk(pipe_name, chunk, is_last))
+        .context("failed to send pipe output chunk")
+}
+
 fn message_to_plugin(env: &PluginEnv, mut message_to_plugin: MessageToPlugin) -> Result<()> {
     if message_to_plugin.plugin_url.as_ref().map(|s| s.as_str()) == Some("zellij:OWN_URL") {
         message_to_plugin.plugin_url = Some(env.plugin.location.display());
@@ -418,6 +590,24 @@ fn message_to_plugin(env: &PluginEnv, mut message_to_plugin: MessageToPlugin) ->
         .context("failed to send message to plugin")
 }
 
+fn set_self_min_size(env: &PluginEnv, min_rows: usize, min_cols: usize) {
+    env.senders
+        .send_to_screen(ScreenInstruction::SetPaneMinSize {
+            pane_id: PaneId::Plugin(env.plugin_id),
+            min_rows,
+            min_cols,
+        })
+        .with_context(|| {
+            format!(
+                "failed to set min size of plugin {} to {}x{}",
+                env.name(),
+                min_rows,
+                min_cols
+            )
+        })
+        .non_fatal();
+}
+
 fn unsubscribe(env: &PluginEnv, event_list: HashSet<EventType>) -> Result<()> {
     env.subscriptions
         .lock()
@@ -472,41 +662,308 @@ fn request_permission(env: &PluginEnv, permissions: Vec<PermissionType>) -> Resu
         ))
 }
 
-fn get_plugin_ids(env: &PluginEnv) {
+fn get_plugin_ids(env: &PluginEnv) -> Result<Vec<u8>, PluginError> {
     let ids = PluginIds {
         plugin_id: env.plugin_id,
         zellij_pid: process::id(),
         initial_cwd: env.plugin_cwd.clone(),
+        session_name: envs::get_session_name().unwrap_or_default(),
+        client_id: env.client_id,
     };
-    ProtobufPluginIds::try_from(ids)
-        .map_err(|e| anyhow!("Failed to serialized plugin ids: {}", e))
-        .and_then(|serialized| {
-            wasi_write_object(env, &serialized.encode_to_vec())?;
-            Ok(())
-        })
-        .with_context(|| {
-            format!(
-                "failed to query plugin IDs from host for plugin {}",
-                env.name()
-            )
-        })
-        .non_fatal();
+    let response = ProtobufPluginIds::try_from(ids)
+        .map(|serialized| serialized.encode_to_vec())
+        .map_err(|e| PluginError::HostError(format!("Failed to serialize plugin ids: {}", e)));
+    response
 }
 
-fn get_zellij_version(env: &PluginEnv) {
+fn get_zellij_version() -> Result<Vec<u8>, PluginError> {
     let protobuf_zellij_version = ProtobufZellijVersion {
         version: VERSION.to_owned(),
     };
-    wasi_write_object(env, &protobuf_zellij_version.encode_to_vec())
-        .with_context(|| {
-            format!(
-                "failed to request zellij version from host for plugin {}",
-                env.name()
-            )
+    Ok(protobuf_zellij_version.encode_to_vec())
+}
+
+// the plugin is blocked until the screen answers, so one that does not answer in time is reported
+// to it as an error rather than waited for
+fn query_screen<T>(
+    env: &PluginEnv,
+    query: &str,
+    instruction: impl FnOnce(channels::Sender<T>) -> ScreenInstruction,
+    serialize: impl FnOnce(T) -> Result<Vec<u8>>,
+) -> Result<Vec<u8>, PluginError> {
+    let (response_sender, response_receiver) = channels::bounded(1);
+    env.senders
+        .send_to_screen(instruction(response_sender))
+        .and_then(|_| {
+            response_receiver
+                .recv_timeout(Duration::from_millis(SCREEN_QUERY_TIMEOUT_MS))
+                .context("timed out waiting for the screen to answer")
+        })
+        .and_then(serialize)
+        .with_context(|| format!("failed to {} for plugin {}", query, env.name()))
+        .map_err(|e| {
+            log::error!("{:?}", e);
+            PluginError::HostError(e.to_string())
+        })
+}
+
+fn get_current_tabs(env: &PluginEnv) -> Result<Vec<u8>, PluginError> {
+    let client_id = env.client_id;
+    let response = query_screen(
+        env,
+        "get current tabs",
+        |sender| ScreenInstruction::GetTabInfosForClient(client_id, sender),
+        |tab_infos| serialize_event(Event::TabUpdate(tab_infos)),
+    );
+    response
+}
+
+fn get_all_panes(env: &PluginEnv) -> Result<Vec<u8>, PluginError> {
+    let response = query_screen(
+        env,
+        "get all panes",
+        ScreenInstruction::GetPaneManifest,
+        |pane_manifest| serialize_event(Event::PaneUpdate(pane_manifest)),
+    );
+    response
+}
+
+fn get_current_styling(env: &PluginEnv) -> Result<Vec<u8>, PluginError> {
+    let client_id = env.client_id;
+    let response = query_screen(
+        env,
+        "get current styling",
+        |sender| ScreenInstruction::GetStyleForClient(client_id, sender),
+        |style| {
+            ProtobufStyle::try_from(style)
+                .map(|serialized| serialized.encode_to_vec())
+                .map_err(|e| anyhow!("Failed to serialize style: {}", e))
+        },
+    );
+    response
+}
+
+fn get_current_input_mode(env: &PluginEnv) -> Result<Vec<u8>, PluginError> {
+    let client_id = env.client_id;
+    let response = query_screen(
+        env,
+        "get current input mode",
+        |sender| ScreenInstruction::GetInputModeForClient(client_id, sender),
+        |input_mode| {
+            ProtobufInputModeMessage::try_from(input_mode)
+                .map(|serialized| serialized.encode_to_vec())
+                .map_err(|e| anyhow!("Failed to serialize input mode: {}", e))
+        },
+    );
+    response
+}
+
+fn list_sessions(env: &PluginEnv) -> Result<Vec<u8>, PluginError> {
+    let response = query_screen(
+        env,
+        "list sessions",
+        ScreenInstruction::GetSessionInfos,
+        |session_infos| serialize_event(Event::SessionUpdate(session_infos, vec![])),
+    );
+    response
+}
+
+const MAX_BLOB_STORE_SIZE_PER_PLUGIN: u64 = 64 * 1024 * 1024; // 64 MB
+
+// blobs are shared between all plugins and sessions, the key is hashed together with the data so
+// that identical content stored under different keys does not collide
+fn blob_hash(key: &str, data: &[u8]) -> String {
+    let mut hasher = PortableHash::default();
+    hasher.append(&(key.len() as u64).to_le_bytes());
+    hasher.append(key.as_bytes());
+    hasher.append(data);
+    hasher
+        .finalize256()
+        .iter()
+        .map(|part| format!("{:016x}", part))
+        .collect()
+}
+
+// hashes come from plugins and are used as file names, so anything else is rejected to make sure
+// they can't point outside the blob folder
+fn is_valid_blob_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// blobs are stored in a folder per plugin so that the room each plugin takes can be limited, they
+// can still be loaded by any plugin that knows their hash
+fn plugin_blob_dir(env: &PluginEnv) -> PathBuf {
+    let mut hasher = PortableHash::default();
+    hasher.append(env.plugin.location.to_string().as_bytes());
+    ZELLIJ_BLOB_DIR.join(format!("{:016x}", hasher.finalize64()))
+}
+
+fn blobs_in_dir(blob_dir: &Path) -> impl Iterator<Item = BlobInfo> {
+    fs::read_dir(blob_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let hash = entry.file_name().into_string().ok()?;
+            let size = entry.metadata().ok()?.len();
+            if is_valid_blob_hash(&hash) {
+                Some(BlobInfo { hash, size })
+            } else {
+                None
+            }
         })
+}
+
+fn plugin_blob_dirs(blob_root: &Path) -> impl Iterator<Item = PathBuf> {
+    fs::read_dir(blob_root)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+}
+
+fn write_blob(blob_dir: &Path, hash: &str, data: &[u8], max_size: u64) -> Result<(), PluginError> {
+    let blob_path = blob_dir.join(hash);
+    if blob_path.exists() {
+        return Ok(());
+    }
+    let used_size: u64 = blobs_in_dir(blob_dir).map(|blob| blob.size).sum();
+    if used_size + data.len() as u64 > max_size {
+        return Err(PluginError::InvalidArgument(format!(
+            "blob store is limited to {} bytes per plugin, {} are already used",
+            max_size, used_size
+        )));
+    }
+    // other sessions might be reading this blob, so we write it to a temporary file and
+    // atomically move it into place
+    let tmp_path = blob_path.with_extension(format!("tmp-{}", process::id()));
+    fs::create_dir_all(blob_dir)
+        .and_then(|_| fs::write(&tmp_path, data))
+        .and_then(|_| fs::rename(&tmp_path, &blob_path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&tmp_path);
+            PluginError::HostError(e.to_string())
+        })
+}
+
+fn find_blob(blob_root: &Path, hash: &str) -> Option<PathBuf> {
+    plugin_blob_dirs(blob_root)
+        .map(|blob_dir| blob_dir.join(hash))
+        .find(|blob_path| blob_path.is_file())
+}
+
+fn store_blob(env: &PluginEnv, key: String, data: Vec<u8>) -> Result<Vec<u8>, PluginError> {
+    let hash = blob_hash(&key, &data);
+    let response = write_blob(
+        &plugin_blob_dir(env),
+        &hash,
+        &data,
+        MAX_BLOB_STORE_SIZE_PER_PLUGIN,
+    )
+    .map(|_| BlobHash { hash }.encode_to_vec())
+    .map_err(|e| {
+        log::error!("Failed to store blob for plugin {}: {}", env.name(), e);
+        e
+    });
+    response
+}
+
+fn load_blob(env: &PluginEnv, hash: String) -> Result<Vec<u8>, PluginError> {
+    if !is_valid_blob_hash(&hash) {
+        return Err(PluginError::InvalidArgument(format!(
+            "invalid blob hash: {}",
+            hash
+        )));
+    }
+    let response = match find_blob(&ZELLIJ_BLOB_DIR, &hash).map(fs::read) {
+        Some(Ok(data)) => Ok(BlobContent { data: Some(data) }.encode_to_vec()),
+        None => Ok(BlobContent { data: None }.encode_to_vec()),
+        Some(Err(e)) if e.kind() == io::ErrorKind::NotFound => {
+            Ok(BlobContent { data: None }.encode_to_vec())
+        },
+        Some(Err(e)) => {
+            log::error!("Failed to load blob for plugin {}: {:?}", env.name(), e);
+            Err(PluginError::HostError(e.to_string()))
+        },
+    };
+    response
+}
+
+fn list_blobs(_env: &PluginEnv) -> Result<Vec<u8>, PluginError> {
+    // the same blob can be stored by more than one plugin
+    let blobs: BTreeMap<String, u64> = plugin_blob_dirs(&ZELLIJ_BLOB_DIR)
+        .flat_map(|blob_dir| blobs_in_dir(&blob_dir))
+        .map(|blob| (blob.hash, blob.size))
+        .collect();
+    let blobs = blobs
+        .into_iter()
+        .map(|(hash, size)| BlobInfo { hash, size })
+        .collect();
+    Ok(BlobList { blobs }.encode_to_vec())
+}
+
+// plugins can only delete the blobs they stored themselves
+fn delete_blob(env: &PluginEnv, hash: String) {
+    if !is_valid_blob_hash(&hash) {
+        log::error!(
+            "Plugin {} tried to delete an invalid blob hash: {}",
+            env.name(),
+            hash
+        );
+        return;
+    }
+    if let Err(e) = fs::remove_file(plugin_blob_dir(env).join(&hash)) {
+        if e.kind() != io::ErrorKind::NotFound {
+            log::error!("Failed to delete blob for plugin {}: {:?}", env.name(), e);
+        }
+    }
+}
+
+// used to answer synchronous queries with the same payload the equivalent event would have had
+fn serialize_event(event: Event) -> Result<Vec<u8>> {
+    ProtobufEvent::try_from(event)
+        .map(|serialized| serialized.encode_to_vec())
+        .map_err(|e| anyhow!("Failed to serialize event: {}", e))
+}
+
+// commands that return a value to the plugin (see command_expects_response) must always write
+// exactly one response, otherwise the plugin will block waiting for it
+fn write_response_to_plugin(
+    env: &PluginEnv,
+    response: Result<Vec<u8>, PluginError>,
+    response_format: ResponseFormat,
+) {
+    let bytes = match (response_format, response) {
+        (ResponseFormat::Envelope, response) => {
+            ProtobufPluginCommandResponse::from(response).encode_to_vec()
+        },
+        (ResponseFormat::Bare, Ok(payload)) => payload,
+        // before the envelope failed queries were not answered at all
+        (ResponseFormat::Bare, Err(_)) => return,
+    };
+    wasi_write_object(env, &bytes)
+        .with_context(|| format!("failed to write command response to plugin {}", env.name()))
         .non_fatal();
 }
 
+fn command_expects_response(command: &PluginCommand) -> bool {
+    matches!(
+        command,
+        PluginCommand::GetPluginIds
+            | PluginCommand::GetZellijVersion
+            | PluginCommand::GetCurrentTabs
+            | PluginCommand::GetAllPanes
+            | PluginCommand::GetCurrentStyling
+            | PluginCommand::GetCurrentInputMode
+            | PluginCommand::ListSessions
+            | PluginCommand::StoreBlob(..)
+            | PluginCommand::LoadBlob(..)
+            | PluginCommand::ListBlobs
+            | PluginCommand::CreateSynchronizedGroup(..)
+    )
+}
+
 fn open_file(env: &PluginEnv, file_to_open: FileToOpen, context: BTreeMap<String, String>) {
     let error_msg = || format!("failed to open file in plugin {}", env.name());
     let floating = false;
@@ -586,6 +1043,30 @@ fn open_file_in_place(
     apply_action!(action, error_msg, env);
 }
 
+fn open_file_in_editor(env: &PluginEnv, path: PathBuf, open_location: OpenLocation) {
+    let error_msg = || format!("failed to open file in editor in plugin {}", env.name());
+    let (floating, in_place) = match open_location {
+        OpenLocation::FloatingPane => (true, false),
+        OpenLocation::CurrentPane => (false, true),
+    };
+    let start_suppressed = false;
+    let path = env.plugin_cwd.join(path);
+    let cwd = Some(env.plugin_cwd.clone());
+    let action = Action::EditFile(
+        OpenFilePayload::new(path, None, cwd).with_originating_plugin(OriginatingPlugin::new(
+            env.plugin_id,
+            env.client_id,
+            BTreeMap::new(),
+        )),
+        None,
+        floating,
+        in_place,
+        start_suppressed,
+        None,
+    );
+    apply_action!(action, error_msg, env);
+}
+
 fn open_terminal(env: &PluginEnv, cwd: PathBuf) {
     let error_msg = || format!("failed to open file in plugin {}", env.name());
     let cwd = env.plugin_cwd.join(cwd);
@@ -877,6 +1358,32 @@ fn run_command(
     }
 }
 
+fn pipe_text_to_command(
+    env: &PluginEnv,
+    text: String,
+    command_to_run: CommandToRun,
+    timeout: Option<Duration>,
+    context: BTreeMap<String, String>,
+) {
+    let cwd = env
+        .plugin_cwd
+        .join(command_to_run.cwd.unwrap_or_else(|| PathBuf::from(".")));
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::PipeTextToCommand(
+            env.plugin_id,
+            env.client_id,
+            text,
+            command_to_run.path,
+            command_to_run.args,
+            cwd,
+            timeout
+                .unwrap_or(DEFAULT_COMMAND_PIPE_TIMEOUT)
+                .min(MAX_COMMAND_PIPE_TIMEOUT),
+            context,
+        ));
+}
+
 fn web_request(
     env: &PluginEnv,
     url: String,
@@ -898,7 +1405,89 @@ fn web_request(
         ));
 }
 
-fn post_message_to(env: &PluginEnv, plugin_message: PluginMessage) -> Result<()> {
+fn emit_metric(env: &PluginEnv, name: String, value: f64, mut labels: BTreeMap<String, String>) {
+    // the plugin label can't be set by the plugin itself so that it can't pass for another one
+    labels.insert("plugin".to_owned(), env.plugin.location.to_string());
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::EmitMetric(Metric::new(&name, value, labels)));
+}
+
+const OPENABLE_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+// other schemes (eg. file://) can hand arbitrary local files to whatever handles them on the
+// user's system
+fn url_has_openable_scheme(url: &str) -> bool {
+    match url.split_once(':') {
+        Some((scheme, _)) => OPENABLE_URL_SCHEMES
+            .iter()
+            .any(|openable_scheme| scheme.eq_ignore_ascii_case(openable_scheme)),
+        None => false,
+    }
+}
+
+// the url is passed as a single argument rather than through a shell, but on windows cmd's
+// start still interprets some characters, so we refuse anything that is not a plain url
+fn url_has_special_characters(url: &str) -> bool {
+    let is_special_character =
+        |c: char| c.is_whitespace() || c.is_control() || (cfg!(windows) && "\"^&|<>%".contains(c));
+    url.chars().any(is_special_character)
+}
+
+fn open_url(url: String) {
+    if !url_has_openable_scheme(&url) {
+        log::error!(
+            "Refusing to open {:?}, only http, https and mailto urls can be opened",
+            url
+        );
+        return;
+    }
+    if url_has_special_characters(&url) {
+        log::error!(
+            "Refusing to open {:?}, it contains characters that are not allowed",
+            url
+        );
+        return;
+    }
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("open");
+        command.arg(&url);
+        command
+    };
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", "", &url]);
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = {
+        let mut command = std::process::Command::new("xdg-open");
+        command.arg(&url);
+        command
+    };
+    match command
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    {
+        Ok(mut child) => {
+            // reap the opener once it exits so that it does not linger as a zombie
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        },
+        Err(e) => log::error!("Failed to open {:?}: {}", url, e),
+    }
+}
+
+fn post_message_to(
+    env: &PluginEnv,
+    plugin_message: PluginMessage,
+    instance_id: Option<u32>,
+) -> Result<()> {
     let worker_name = plugin_message
         .worker_name
         .ok_or(anyhow!("Worker name not specified in message to worker"))?;
@@ -907,6 +1496,7 @@ fn post_message_to(env: &PluginEnv, plugin_message: PluginMessage) -> Result<()>
             env.plugin_id,
             env.client_id,
             worker_name,
+            instance_id,
             vec![(plugin_message.name, plugin_message.payload)],
         ))
 }
@@ -948,6 +1538,43 @@ fn show_self(env: &PluginEnv, should_float_if_hidden: bool) {
     apply_action!(action, error_msg, env);
 }
 
+fn toggle_self(env: &PluginEnv, should_float_if_hidden: bool) -> Result<()> {
+    env.senders
+        .send_to_screen(ScreenInstruction::TogglePaneWithId(
+            PaneId::Plugin(env.plugin_id),
+            should_float_if_hidden,
+            env.client_id,
+        ))
+        .with_context(|| format!("failed to toggle self"))
+}
+
+fn run_action(env: &PluginEnv, action: Action, context: BTreeMap<String, String>) -> Result<()> {
+    let pane_id = PaneId::Plugin(env.plugin_id);
+    if let Err(e) = route_action(
+        action.clone(),
+        env.client_id,
+        Some(pane_id),
+        env.senders.clone(),
+        env.capabilities.clone(),
+        env.client_attributes.clone(),
+        env.default_shell.clone(),
+        env.default_layout.clone(),
+        None,
+        env.keybinds.clone(),
+        env.default_mode.clone(),
+    ) {
+        log::error!("Failed to run action for plugin {}: {:?}", env.name(), e);
+        return Ok(());
+    }
+    env.senders
+        .send_to_plugin(PluginInstruction::Update(vec![(
+            Some(env.plugin_id),
+            Some(env.client_id),
+            Event::ActionComplete(action, Some(pane_id.into()), context),
+        )]))
+        .with_context(|| format!("failed to report completed action to plugin {}", env.name()))
+}
+
 fn show_pane_with_id(env: &PluginEnv, pane_id: PaneId, should_float_if_hidden: bool) {
     let _ = env
         .senders
@@ -1004,12 +1631,48 @@ fn rebind_keys(
     Ok(())
 }
 
+fn register_global_hotkey(env: &PluginEnv, key: KeyWithModifier, action_id: String) -> Result<()> {
+    let err_context = || "Failed to register global hotkey";
+    env.senders
+        .send_to_server(ServerInstruction::RegisterGlobalHotkey(
+            key,
+            env.plugin_id,
+            action_id,
+        ))
+        .with_context(err_context)?;
+    Ok(())
+}
+
 fn switch_to_mode(env: &PluginEnv, input_mode: InputMode) {
     let action = Action::SwitchToMode(input_mode);
     let error_msg = || format!("failed to switch to mode in plugin {}", env.name());
     apply_action!(action, error_msg, env);
 }
 
+fn set_client_mode(env: &PluginEnv, client_id: ClientId, input_mode: InputMode) {
+    let action = Action::SwitchToMode(input_mode);
+    if let Err(e) = route_action(
+        action,
+        client_id,
+        Some(PaneId::Plugin(env.plugin_id)),
+        env.senders.clone(),
+        env.capabilities.clone(),
+        env.client_attributes.clone(),
+        env.default_shell.clone(),
+        env.default_layout.clone(),
+        None,
+        env.keybinds.clone(),
+        env.default_mode.clone(),
+    ) {
+        log::error!(
+            "failed to switch client {} to mode in plugin {}: {:?}",
+            client_id,
+            env.name(),
+            e
+        );
+    }
+}
+
 fn new_tabs_with_layout(env: &PluginEnv, raw_layout: &str) -> Result<()> {
     // TODO: cwd
     let layout = Layout::from_str(
@@ -1138,6 +1801,7 @@ fn switch_session(
     pane_id: Option<(u32, bool)>,
     layout: Option<LayoutInfo>,
     cwd: Option<PathBuf>,
+    tags: Vec<String>,
 ) -> Result<()> {
     // pane_id is (id, is_plugin)
     let err_context = || format!("Failed to switch session");
@@ -1157,12 +1821,18 @@ fn switch_session(
     } else {
         let client_id = env.client_id;
         let tab_position = tab_position.map(|p| p + 1); // ¯\_()_/¯
+        if let Some(session_name) = session_name.as_ref() {
+            if !tags.is_empty() {
+                set_session_tags(session_name.clone(), tags.clone()).with_context(err_context)?;
+            }
+        }
         let connect_to_session = ConnectToSession {
             name: session_name,
             tab_position,
             pane_id,
             layout,
             cwd,
+            tags,
         };
         env.senders
             .send_to_server(ServerInstruction::SwitchSession(
@@ -1179,6 +1849,22 @@ fn delete_dead_session(session_name: String) -> Result<()> {
         .with_context(|| format!("Failed to delete dead session: {:?}", &session_name))
 }
 
+fn set_session_tags(session_name: String, tags: Vec<String>) -> Result<()> {
+    let Some(session_folder) = session_folder_within(&ZELLIJ_SESSION_INFO_CACHE_DIR, &session_name)
+    else {
+        return Err(anyhow!("Invalid session name: {:?}", session_name));
+    };
+    let tags = SessionInfo::normalize_tags(tags);
+    std::fs::create_dir_all(&session_folder)
+        .and_then(|_| {
+            std::fs::write(
+                session_tags_file_name(&session_name),
+                SessionInfo::tags_to_string(&tags),
+            )
+        })
+        .with_context(|| format!("Failed to set tags for session: {:?}", &session_name))
+}
+
 fn delete_all_dead_sessions() -> Result<()> {
     use std::os::unix::fs::FileTypeExt;
     let mut live_sessions = vec![];
@@ -1303,6 +1989,22 @@ fn toggle_focus_fullscreen(env: &PluginEnv) {
     apply_action!(action, error_msg, env);
 }
 
+fn get_plugin_dependencies(env: &PluginEnv, url: String) {
+    let _ = env
+        .senders
+        .send_to_plugin(PluginInstruction::GetPluginDependencies(
+            url,
+            env.plugin_id,
+            env.client_id,
+        ));
+}
+
+fn toggle_pane_pinned(env: &PluginEnv) {
+    let error_msg = || format!("failed to toggle pane pinned in plugin {}", env.name());
+    let action = Action::TogglePanePinned;
+    apply_action!(action, error_msg, env);
+}
+
 fn toggle_pane_frames(env: &PluginEnv) {
     let error_msg = || format!("failed to toggle full screen in plugin {}", env.name());
     let action = Action::TogglePaneFrames;
@@ -1471,7 +2173,9 @@ fn kill_sessions(session_names: Vec<String>) {
         let path = &*ZELLIJ_SOCK_DIR.join(&session_name);
         match LocalSocketStream::connect(path) {
             Ok(stream) => {
-                let _ = IpcSenderWithContext::new(stream).send(ClientToServerMsg::KillSession);
+                let mut sender = IpcSenderWithContext::new(stream);
+                let _ = sender.authenticate();
+                let _ = sender.send(ClientToServerMsg::KillSession);
             },
             Err(e) => {
                 log::error!("Failed to kill session {}: {:?}", session_name, e);
@@ -1524,12 +2228,123 @@ fn set_floating_pane_pinned(env: &PluginEnv, pane_id: PaneId, should_be_pinned:
     });
 }
 
+fn set_pane_z_index(env: &PluginEnv, pane_id: PaneId, z_index: usize) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::SetFloatingPaneZIndex(pane_id, z_index));
+}
+
+fn drag_pane_to_coordinates(env: &PluginEnv, pane_id: PaneId, x: usize, y: usize) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::DragPaneToCoordinates(pane_id, x, y));
+}
+
+fn subscribe_to_channel(env: &PluginEnv, channel: String) -> Result<()> {
+    // subscribing to a channel implies wanting to receive its messages
+    let mut event_list = HashSet::new();
+    event_list.insert(EventType::ChannelMessage);
+    subscribe(env, event_list)?;
+    env.senders
+        .send_to_plugin(PluginInstruction::SubscribeToChannel(
+            env.plugin_id,
+            channel,
+        ))
+}
+
+fn publish_to_channel(env: &PluginEnv, channel: String, payload: Vec<u8>) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::PublishToChannel(channel, payload))
+}
+
+fn set_pane_content(env: &PluginEnv, pane_id: PaneId, content: String) {
+    match pane_id {
+        PaneId::Terminal(terminal_pane_id) => {
+            // this goes through the same path as output from the pane's pty
+            let _ = env.senders.send_to_screen(ScreenInstruction::PtyBytes(
+                terminal_pane_id,
+                content.into_bytes(),
+            ));
+        },
+        PaneId::Plugin(_) => {
+            log::error!(
+                "Plugin {} tried to set the content of plugin pane {:?}, only terminal panes are supported",
+                env.name(),
+                pane_id
+            );
+        },
+    }
+}
+
 fn stack_panes(env: &PluginEnv, pane_ids: Vec<PaneId>) {
     let _ = env
         .senders
         .send_to_screen(ScreenInstruction::StackPanes(pane_ids));
 }
 
+fn create_synchronized_group(
+    env: &PluginEnv,
+    name: String,
+    pane_ids: Vec<PaneId>,
+) -> Result<Vec<u8>, PluginError> {
+    let response = query_screen(
+        env,
+        "create synchronized group",
+        |sender| ScreenInstruction::CreateSynchronizedGroup(name, pane_ids, sender),
+        |group_id| Ok(SynchronizedGroup { group_id }.encode_to_vec()),
+    );
+    response
+}
+
+fn dissolve_synchronized_group(env: &PluginEnv, group_id: GroupId) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::DissolveSynchronizedGroup(group_id));
+}
+
+fn set_focus_follows_mouse(env: &PluginEnv, enable: bool) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::SetFocusFollowsMouse(enable));
+}
+
+fn run_command_in_pane(
+    env: &PluginEnv,
+    pane_id: PaneId,
+    command_to_run: CommandToRun,
+    env_variables: BTreeMap<String, String>,
+) {
+    let command = RunCommand {
+        command: command_to_run.path,
+        args: command_to_run.args,
+        cwd: command_to_run.cwd,
+        env: env_variables.into_iter().collect(),
+        ..Default::default()
+    };
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::RunCommandInPane(pane_id, command));
+}
+
+fn search_pane(env: &PluginEnv, pane_id: PaneId, query: String, case_sensitive: bool, regex: bool) {
+    let _ = env.senders.send_to_screen(ScreenInstruction::SearchPane(
+        pane_id,
+        query,
+        case_sensitive,
+        regex,
+        env.plugin_id,
+        env.client_id,
+    ));
+}
+
+fn pane_snapshot(env: &PluginEnv, pane_id: PaneId) {
+    let _ = env.senders.send_to_screen(ScreenInstruction::PaneSnapshot(
+        pane_id,
+        env.plugin_id,
+        env.client_id,
+    ));
+}
+
 fn scan_host_folder(env: &PluginEnv, folder_to_scan: PathBuf) {
     if !folder_to_scan.starts_with("/host") {
         log::error!(
@@ -1660,6 +2475,39 @@ fn scroll_to_top_in_pane_id(env: &PluginEnv, pane_id: PaneId) {
         .send_to_screen(ScreenInstruction::ScrollToTopInPaneId(pane_id));
 }
 
+fn scroll_to_line_in_pane_id(env: &PluginEnv, pane_id: PaneId, line: usize) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::ScrollToLineInPaneId(pane_id, line));
+}
+
+fn set_pane_scrollback_size(env: &PluginEnv, pane_id: PaneId, size: usize) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::SetPaneScrollbackSize { pane_id, size });
+}
+
+fn annotate_scrollback_line(
+    env: &PluginEnv,
+    pane_id: PaneId,
+    line_index: usize,
+    annotation: String,
+) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::AnnotateScrollbackLineInPaneId(
+            pane_id, line_index, annotation,
+        ));
+}
+
+fn clear_scrollback_annotations(env: &PluginEnv, pane_id: PaneId) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::ClearScrollbackAnnotationsInPaneId(
+            pane_id,
+        ));
+}
+
 fn scroll_to_bottom_in_pane_id(env: &PluginEnv, pane_id: PaneId) {
     let _ = env
         .senders
@@ -1693,7 +2541,24 @@ fn toggle_pane_embed_or_eject_for_pane_id(env: &PluginEnv, pane_id: PaneId) {
 fn close_tab_with_index(env: &PluginEnv, tab_index: usize) {
     let _ = env
         .senders
-        .send_to_screen(ScreenInstruction::CloseTabWithIndex(tab_index));
+        .send_to_screen(ScreenInstruction::CloseTabWithIndex(
+            tab_index,
+            env.client_id,
+        ));
+}
+
+fn set_tab_pinned(env: &PluginEnv, tab_index: usize, pinned: bool) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::SetTabPinned(tab_index, pinned));
+}
+
+fn duplicate_pane(env: &PluginEnv, pane_id: PaneId) {
+    let _ = env.senders.send_to_screen(ScreenInstruction::DuplicatePane(
+        pane_id,
+        env.plugin_id,
+        env.client_id,
+    ));
 }
 
 fn break_panes_to_new_tab(
@@ -1799,6 +2664,39 @@ fn load_new_plugin(
     }
 }
 
+fn create_floating_pane_at(
+    env: &PluginEnv,
+    url: String,
+    config: BTreeMap<String, String>,
+    coordinates: FloatingPaneCoordinates,
+) {
+    let url = if &url == "zellij:OWN_URL" {
+        env.plugin.location.display()
+    } else {
+        url
+    };
+    match RunPluginOrAlias::from_url(&url, &Some(config), None, Some(env.plugin_cwd.clone())) {
+        Ok(run_plugin_or_alias) => {
+            let pane_title = None;
+            let skip_cache = false;
+            let cwd = Some(env.plugin_cwd.clone());
+            let _ = env
+                .senders
+                .send_to_screen(ScreenInstruction::NewFloatingPluginPane(
+                    run_plugin_or_alias,
+                    pane_title,
+                    skip_cache,
+                    cwd,
+                    Some(coordinates),
+                    env.client_id,
+                ));
+        },
+        Err(e) => {
+            log::error!("Failed to create floating plugin pane: {:?}", e);
+        },
+    }
+}
+
 // Custom panic handler for plugins.
 //
 // This is called when a panic occurs in a plugin. Since most panics will likely originate in the
@@ -1849,6 +2747,121 @@ pub fn wasi_read_bytes(plugin_env: &PluginEnv) -> Result<Vec<u8>> {
 }
 
 // TODO: move to permissions?
+// an action run through RunAction needs the permission of the plugin command doing the same thing,
+// actions that no plugin command can do are refused
+fn permission_for_action(action: &Action) -> Option<PermissionType> {
+    match action {
+        Action::Write(..) | Action::WriteChars(..) | Action::RunCommandInPane { .. } => {
+            Some(PermissionType::WriteToStdin)
+        },
+        Action::Run(..)
+        | Action::NewFloatingPane(Some(_), ..)
+        | Action::NewTiledPane(_, Some(_), _)
+        | Action::NewInPlacePane(Some(_), _) => Some(PermissionType::RunCommands),
+        // layouts can run commands
+        Action::NewTab(
+            tiled_layout,
+            floating_layouts,
+            swap_tiled_layouts,
+            swap_floating_layouts,
+            ..,
+        ) if tiled_layout.is_some()
+            || !floating_layouts.is_empty()
+            || swap_tiled_layouts.is_some()
+            || swap_floating_layouts.is_some() =>
+        {
+            Some(PermissionType::RunCommands)
+        },
+        Action::EditFile(..) | Action::OpenFile { .. } => Some(PermissionType::OpenFiles),
+        Action::NewPane(..)
+        | Action::NewFloatingPane(None, ..)
+        | Action::NewTiledPane(_, None, _)
+        | Action::NewInPlacePane(None, _)
+        | Action::LaunchOrFocusPlugin(..)
+        | Action::LaunchPlugin(..)
+        | Action::NewTiledPluginPane(..)
+        | Action::NewFloatingPluginPane(..)
+        | Action::NewInPlacePluginPane(..)
+        | Action::StartOrReloadPlugin(..) => Some(PermissionType::OpenTerminalsOrPlugins),
+        Action::CliPipe { .. } | Action::KeybindPipe { .. } => {
+            Some(PermissionType::MessageAndLaunchOtherPlugins)
+        },
+        Action::ListClients | Action::DumpLayout => Some(PermissionType::ReadApplicationState),
+        Action::SkipConfirm(action) => permission_for_action(action),
+        Action::Quit
+        | Action::SwitchToMode(..)
+        | Action::SwitchModeForAllClients(..)
+        | Action::Resize(..)
+        | Action::FocusNextPane
+        | Action::FocusPreviousPane
+        | Action::SwitchFocus
+        | Action::MoveFocus(..)
+        | Action::MoveFocusOrTab(..)
+        | Action::MovePane(..)
+        | Action::MovePaneBackwards
+        | Action::ClearScreen
+        | Action::EditScrollback
+        | Action::ScrollUp
+        | Action::ScrollUpAt(..)
+        | Action::ScrollDown
+        | Action::ScrollDownAt(..)
+        | Action::ScrollToBottom
+        | Action::ScrollToTop
+        | Action::PageScrollUp
+        | Action::PageScrollDown
+        | Action::HalfPageScrollUp
+        | Action::HalfPageScrollDown
+        | Action::ToggleFocusFullscreen
+        | Action::TogglePaneFrames
+        | Action::ToggleActiveSyncTab
+        | Action::TogglePaneEmbedOrFloating
+        | Action::ToggleFloatingPanes
+        | Action::ToggleExclusiveFloatingPanes
+        | Action::CloseFocus
+        | Action::NewTab(..)
+        | Action::NoOp
+        | Action::GoToNextTab
+        | Action::GoToPreviousTab
+        | Action::CloseTab
+        | Action::GoToTab(..)
+        | Action::GoToTabName(..)
+        | Action::ToggleTab
+        | Action::MoveTab(..)
+        | Action::Detach
+        | Action::PreviousSwapLayout
+        | Action::NextSwapLayout
+        | Action::CloseTerminalPane(..)
+        | Action::ClosePluginPane(..)
+        | Action::FocusTerminalPaneWithId(..)
+        | Action::FocusPluginPaneWithId(..)
+        | Action::RenameTerminalPane(..)
+        | Action::RenamePluginPane(..)
+        | Action::RenameTab(..)
+        | Action::BreakPane
+        | Action::BreakPaneRight
+        | Action::BreakPaneLeft
+        | Action::RenameSession(..)
+        | Action::TogglePanePinned
+        | Action::StackPanes(..) => Some(PermissionType::ChangeApplicationState),
+        Action::DumpScreen(..)
+        | Action::PaneNameInput(..)
+        | Action::UndoRenamePane
+        | Action::TabNameInput(..)
+        | Action::UndoRenameTab
+        | Action::MouseEvent(..)
+        | Action::Copy
+        | Action::Confirm
+        | Action::Deny
+        | Action::SearchInput(..)
+        | Action::Search(..)
+        | Action::SearchToggleOption(..)
+        | Action::ToggleMouseMode
+        | Action::QueryTabNames
+        | Action::SuspendSession
+        | Action::ResumeSession => None,
+    }
+}
+
 fn check_command_permission(
     plugin_env: &PluginEnv,
     command: &PluginCommand,
@@ -1858,10 +2871,18 @@ fn check_command_permission(
         // there's no use to deny them anything
         return (PermissionStatus::Granted, None);
     }
+    // this fakes the output of a pane's process and is only meant for plugin integration tests, so
+    // outside of debug mode (`zellij --debug`) it is refused whatever the plugin's permissions
+    if let PluginCommand::SetPaneContent(..) = command {
+        if !*DEBUG_MODE.get().unwrap_or(&true) {
+            return (PermissionStatus::Denied, None);
+        }
+    }
     let permission = match command {
         PluginCommand::OpenFile(..)
         | PluginCommand::OpenFileFloating(..)
-        | PluginCommand::OpenFileInPlace(..) => PermissionType::OpenFiles,
+        | PluginCommand::OpenFileInPlace(..)
+        | PluginCommand::OpenFileInEditor(..) => PermissionType::OpenFiles,
         PluginCommand::OpenTerminal(..)
         | PluginCommand::StartOrReloadPlugin(..)
         | PluginCommand::OpenTerminalFloating(..)
@@ -1871,14 +2892,19 @@ fn check_command_permission(
         | PluginCommand::OpenCommandPaneInPlace(..)
         | PluginCommand::OpenCommandPaneBackground(..)
         | PluginCommand::RunCommand(..)
-        | PluginCommand::ExecCmd(..) => PermissionType::RunCommands,
+        | PluginCommand::PipeTextToCommand(..)
+        | PluginCommand::ExecCmd(..)
+        | PluginCommand::DuplicatePane(..) => PermissionType::RunCommands,
         PluginCommand::WebRequest(..) => PermissionType::WebAccess,
         PluginCommand::Write(..)
         | PluginCommand::WriteChars(..)
         | PluginCommand::WriteToPaneId(..)
-        | PluginCommand::WriteCharsToPaneId(..) => PermissionType::WriteToStdin,
+        | PluginCommand::WriteCharsToPaneId(..)
+        | PluginCommand::RunCommandInPane(..)
+        | PluginCommand::CreateSynchronizedGroup(..) => PermissionType::WriteToStdin,
         PluginCommand::SwitchTabTo(..)
         | PluginCommand::SwitchToMode(..)
+        | PluginCommand::SetClientMode(..)
         | PluginCommand::NewTabsWithLayout(..)
         | PluginCommand::NewTabsWithLayoutInfo(..)
         | PluginCommand::NewTab
@@ -1905,6 +2931,8 @@ fn check_command_permission(
         | PluginCommand::ScrollDownInPaneId(..)
         | PluginCommand::ScrollToTop
         | PluginCommand::ScrollToTopInPaneId(..)
+        | PluginCommand::ScrollToLineInPaneId(..)
+        | PluginCommand::SetPaneScrollbackSize(..)
         | PluginCommand::ScrollToBottom
         | PluginCommand::ScrollToBottomInPaneId(..)
         | PluginCommand::PageScrollUp
@@ -1912,6 +2940,7 @@ fn check_command_permission(
         | PluginCommand::PageScrollDown
         | PluginCommand::PageScrollDownInPaneId(..)
         | PluginCommand::ToggleFocusFullscreen
+        | PluginCommand::TogglePanePinned
         | PluginCommand::TogglePaneIdFullscreen(..)
         | PluginCommand::TogglePaneFrames
         | PluginCommand::TogglePaneEmbedOrEject
@@ -1944,24 +2973,54 @@ fn check_command_permission(
         | PluginCommand::RerunCommandPane(..)
         | PluginCommand::ResizePaneIdWithDirection(..)
         | PluginCommand::CloseTabWithIndex(..)
+        | PluginCommand::PinTab(..)
+        | PluginCommand::UnpinTab(..)
         | PluginCommand::BreakPanesToNewTab(..)
         | PluginCommand::BreakPanesToTabWithIndex(..)
         | PluginCommand::ReloadPlugin(..)
         | PluginCommand::LoadNewPlugin { .. }
+        | PluginCommand::GetPluginDependencies(..)
+        | PluginCommand::CreateFloatingPaneAt { .. }
+        | PluginCommand::SetSessionTags(..)
         | PluginCommand::SetFloatingPanePinned(..)
+        | PluginCommand::SetPaneZIndex(..)
+        | PluginCommand::DragPaneToCoordinates(..)
+        | PluginCommand::SetPaneContent(..)
         | PluginCommand::StackPanes(..)
-        | PluginCommand::KillSessions(..) => PermissionType::ChangeApplicationState,
+        | PluginCommand::DissolveSynchronizedGroup(..)
+        | PluginCommand::SetFocusFollowsMouse(..)
+        | PluginCommand::KillSessions(..)
+        | PluginCommand::AnnotateScrollbackLine(..)
+        | PluginCommand::ClearScrollbackAnnotations(..) => PermissionType::ChangeApplicationState,
         PluginCommand::UnblockCliPipeInput(..)
         | PluginCommand::BlockCliPipeInput(..)
-        | PluginCommand::CliPipeOutput(..) => PermissionType::ReadCliPipes,
-        PluginCommand::MessageToPlugin(..) => PermissionType::MessageAndLaunchOtherPlugins,
-        PluginCommand::ListClients | PluginCommand::DumpSessionLayout => {
-            PermissionType::ReadApplicationState
-        },
-        PluginCommand::RebindKeys { .. } | PluginCommand::Reconfigure(..) => {
-            PermissionType::Reconfigure
-        },
+        | PluginCommand::CliPipeOutput(..)
+        | PluginCommand::CliPipeChunk(..) => PermissionType::ReadCliPipes,
+        PluginCommand::MessageToPlugin(..)
+        | PluginCommand::SubscribeToChannel(..)
+        | PluginCommand::PublishToChannel(..) => PermissionType::MessageAndLaunchOtherPlugins,
+        PluginCommand::ListClients
+        | PluginCommand::DumpSessionLayout
+        | PluginCommand::SearchPane(..)
+        | PluginCommand::PaneSnapshot(..)
+        | PluginCommand::GetCurrentTabs
+        | PluginCommand::GetAllPanes
+        | PluginCommand::GetCurrentInputMode
+        | PluginCommand::ListSessions => PermissionType::ReadApplicationState,
+        PluginCommand::RebindKeys { .. }
+        | PluginCommand::Reconfigure(..)
+        | PluginCommand::RegisterGlobalHotkey(..) => PermissionType::Reconfigure,
         PluginCommand::ChangeHostFolder(..) => PermissionType::FullHdAccess,
+        PluginCommand::OpenUrl(..) => PermissionType::OpenUrls,
+        PluginCommand::StoreBlob(..)
+        | PluginCommand::LoadBlob(..)
+        | PluginCommand::ListBlobs
+        | PluginCommand::DeleteBlob(..) => PermissionType::StoreBlobs,
+        PluginCommand::EmitMetric(..) => PermissionType::EmitMetrics,
+        PluginCommand::RunAction(action, _) => match permission_for_action(action) {
+            Some(permission) => permission,
+            None => return (PermissionStatus::Denied, None),
+        },
         _ => return (PermissionStatus::Granted, None),
     };
 
@@ -1973,3 +3032,7 @@ fn check_command_permission(
 
     (PermissionStatus::Denied, Some(permission))
 }
+
+#[cfg(test)]
+#[path = "./unit/zellij_exports_tests.rs"]
+mod zellij_exports_tests;
@@ -10,10 +10,12 @@ use crate::{
 };
 use async_std::task::{self, JoinHandle};
 use std::sync::Arc;
+use std::time::Duration;
 use std::{collections::HashMap, os::unix::io::RawFd, path::PathBuf};
 use zellij_utils::nix::unistd::Pid;
 use zellij_utils::{
     async_std,
+    channels::Sender,
     data::{Event, FloatingPaneCoordinates, OriginatingPlugin},
     errors::prelude::*,
     errors::{ContextType, PtyContext},
@@ -28,6 +30,8 @@ use zellij_utils::{
 pub type VteBytes = Vec<u8>;
 pub type TabIndex = u32;
 
+pub(crate) const DEFAULT_SESSION_MAX_PANES: usize = 200;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ClientTabIndexOrPaneId {
     ClientId(ClientId),
@@ -83,6 +87,8 @@ pub enum PtyInstruction {
     DumpLayout(SessionLayoutMetadata, ClientId),
     DumpLayoutToPlugin(SessionLayoutMetadata, PluginId),
     LogLayoutToHd(SessionLayoutMetadata),
+    // PathBuf is the file to save the layout to, the Sender is notified once it is written
+    SaveLayout(SessionLayoutMetadata, PathBuf, Sender<()>),
     FillPluginCwd(
         Option<bool>,   // should float
         bool,           // should be opened in place
@@ -102,6 +108,16 @@ pub enum PtyInstruction {
         default_editor: Option<PathBuf>,
     },
     ListClientsToPlugin(SessionLayoutMetadata, PluginId, ClientId),
+    DuplicateTerminal {
+        terminal_id: u32,
+        invoked_with: Option<Run>,
+        should_float: bool,
+        tab_index: usize,
+        plugin_id: PluginId, // the plugin that asked for the duplicate
+        client_id: ClientId,
+    },
+    SuspendAll, // stop the processes of all terminals (SIGSTOP)
+    ResumeAll,  // continue the processes stopped with SuspendAll (SIGCONT)
     Exit,
 }
 
@@ -123,10 +139,14 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::DumpLayout(..) => PtyContext::DumpLayout,
             PtyInstruction::DumpLayoutToPlugin(..) => PtyContext::DumpLayoutToPlugin,
             PtyInstruction::LogLayoutToHd(..) => PtyContext::LogLayoutToHd,
+            PtyInstruction::SaveLayout(..) => PtyContext::SaveLayout,
             PtyInstruction::FillPluginCwd(..) => PtyContext::FillPluginCwd,
             PtyInstruction::ListClientsMetadata(..) => PtyContext::ListClientsMetadata,
             PtyInstruction::Reconfigure { .. } => PtyContext::Reconfigure,
             PtyInstruction::ListClientsToPlugin(..) => PtyContext::ListClientsToPlugin,
+            PtyInstruction::DuplicateTerminal { .. } => PtyContext::DuplicateTerminal,
+            PtyInstruction::SuspendAll => PtyContext::SuspendAll,
+            PtyInstruction::ResumeAll => PtyContext::ResumeAll,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -140,6 +160,7 @@ pub(crate) struct Pty {
     debug_to_file: bool,
     task_handles: HashMap<u32, JoinHandle<()>>, // terminal_id to join-handle
     default_editor: Option<PathBuf>,
+    session_max_panes: usize,
 }
 
 pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
@@ -157,6 +178,16 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             ) => {
                 let err_context =
                     || format!("failed to spawn terminal for {:?}", client_or_tab_index);
+                let requesting_client_id = match client_or_tab_index {
+                    ClientTabIndexOrPaneId::ClientId(client_id) => Some(client_id),
+                    _ => None,
+                };
+                if pty
+                    .refuse_terminal_over_max_panes(requesting_client_id)
+                    .with_context(err_context)?
+                {
+                    continue;
+                }
 
                 let (hold_on_close, run_command, pane_title, open_file_payload) =
                     match &terminal_action {
@@ -386,6 +417,12 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             PtyInstruction::SpawnTerminalVertically(terminal_action, name, client_id) => {
                 let err_context =
                     || format!("failed to spawn terminal vertically for client {client_id}");
+                if pty
+                    .refuse_terminal_over_max_panes(Some(client_id))
+                    .with_context(err_context)?
+                {
+                    continue;
+                }
 
                 let (hold_on_close, run_command, pane_title) = match &terminal_action {
                     Some(TerminalAction::RunCommand(run_command)) => (
@@ -455,6 +492,12 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             PtyInstruction::SpawnTerminalHorizontally(terminal_action, name, client_id) => {
                 let err_context =
                     || format!("failed to spawn terminal horizontally for client {client_id}");
+                if pty
+                    .refuse_terminal_over_max_panes(Some(client_id))
+                    .with_context(err_context)?
+                {
+                    continue;
+                }
 
                 let (hold_on_close, run_command, pane_title) = match &terminal_action {
                     Some(TerminalAction::RunCommand(run_command)) => (
@@ -733,6 +776,30 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     .with_context(err_context)
                     .non_fatal();
             },
+            PtyInstruction::DuplicateTerminal {
+                terminal_id,
+                invoked_with,
+                should_float,
+                tab_index,
+                plugin_id,
+                client_id,
+            } => {
+                if pty
+                    .refuse_terminal_over_max_panes(Some(client_id))
+                    .context("failed to duplicate terminal")?
+                {
+                    continue;
+                }
+                pty.duplicate_terminal(
+                    terminal_id,
+                    invoked_with,
+                    should_float,
+                    tab_index,
+                    plugin_id,
+                    client_id,
+                )
+                .non_fatal();
+            },
             PtyInstruction::LogLayoutToHd(mut session_layout_metadata) => {
                 let err_context = || format!("Failed to dump layout");
                 pty.populate_session_layout_metadata(&mut session_layout_metadata);
@@ -754,6 +821,38 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     }
                 }
             },
+            PtyInstruction::SaveLayout(
+                mut session_layout_metadata,
+                file_name,
+                completion_sender,
+            ) => {
+                pty.populate_session_layout_metadata(&mut session_layout_metadata);
+                // a session whose last pane exited has nothing worth saving
+                if session_layout_metadata.has_panes() {
+                    // pane contents are left out, they would only be kept in files next to the
+                    // layout which are not saved with it
+                    let saved = session_serialization::serialize_session_layout(
+                        session_layout_metadata.into(),
+                    )
+                    .map_err(|e| anyhow!(e))
+                    .and_then(|(kdl_layout, _pane_contents)| {
+                        file_name
+                            .parent()
+                            .map(std::fs::create_dir_all)
+                            .transpose()
+                            .and_then(|_| std::fs::write(&file_name, kdl_layout))
+                            .map_err(|e| anyhow!(e))
+                    });
+                    if let Err(e) = saved {
+                        log::error!(
+                            "Failed to save the layout to {}: {}",
+                            file_name.display(),
+                            e
+                        );
+                    }
+                }
+                let _ = completion_sender.send(());
+            },
             PtyInstruction::FillPluginCwd(
                 should_float,
                 should_be_open_in_place,
@@ -787,6 +886,12 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             } => {
                 pty.reconfigure(default_editor);
             },
+            PtyInstruction::SuspendAll => {
+                pty.set_all_stopped(true);
+            },
+            PtyInstruction::ResumeAll => {
+                pty.set_all_stopped(false);
+            },
             PtyInstruction::Exit => break,
         }
     }
@@ -798,6 +903,7 @@ impl Pty {
         bus: Bus<PtyInstruction>,
         debug_to_file: bool,
         default_editor: Option<PathBuf>,
+        session_max_panes: usize,
     ) -> Self {
         Pty {
             active_panes: HashMap::new(),
@@ -807,8 +913,21 @@ impl Pty {
             task_handles: HashMap::new(),
             default_editor,
             originating_plugins: HashMap::new(),
+            session_max_panes,
         }
     }
+    /// Whether another terminal would take the session beyond its maximum pane count, in which
+    /// case it is not spawned and the screen is told so instead
+    fn refuse_terminal_over_max_panes(&self, client_id: Option<ClientId>) -> Result<bool> {
+        if self.id_to_child_pid.len() < self.session_max_panes {
+            return Ok(false);
+        }
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::PaneCountExceeded(client_id))
+            .context("failed to refuse a terminal over the maximum pane count")?;
+        Ok(true)
+    }
     pub fn get_default_terminal(
         &self,
         cwd: Option<PathBuf>,
@@ -870,15 +989,90 @@ impl Pty {
     fn fill_cwd_from_pane_id(&self, terminal_action: &mut TerminalAction, pane_id: &u32) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
-                run_command.cwd = self.id_to_child_pid.get(pane_id).and_then(|&id| {
-                    self.bus
-                        .os_input
-                        .as_ref()
-                        .and_then(|input| input.get_cwd(Pid::from_raw(id)))
-                });
+                run_command.cwd = self.get_cwd_of_terminal(pane_id);
             };
         };
     }
+    fn get_cwd_of_terminal(&self, terminal_id: &u32) -> Option<PathBuf> {
+        self.id_to_child_pid.get(terminal_id).and_then(|&id| {
+            self.bus
+                .os_input
+                .as_ref()
+                .and_then(|input| input.get_cwd(Pid::from_raw(id)))
+        })
+    }
+    pub fn duplicate_terminal(
+        &mut self,
+        terminal_id: u32,
+        invoked_with: Option<Run>,
+        should_float: bool,
+        tab_index: usize,
+        plugin_id: PluginId,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to duplicate terminal pane {terminal_id}");
+        // the duplicate starts where the original is now rather than where it started
+        let current_cwd = self.get_cwd_of_terminal(&terminal_id);
+        let (terminal_action, run_command, pane_title, invoked_with) =
+            match terminal_action_for_duplicate(invoked_with, current_cwd) {
+                DuplicateTerminalAction::Command(run_command) => (
+                    TerminalAction::RunCommand(run_command.clone()),
+                    Some(run_command.clone()),
+                    Some(run_command.to_string()),
+                    Some(Run::Command(run_command)),
+                ),
+                DuplicateTerminalAction::EditFile(payload) => {
+                    let invoked_with = Run::EditFile(
+                        payload.path.clone(),
+                        payload.line_number,
+                        payload.cwd.clone(),
+                    );
+                    (
+                        TerminalAction::OpenFile(payload),
+                        None,
+                        None,
+                        Some(invoked_with),
+                    )
+                },
+                // like any other shell, the duplicate has neither a command title nor is it held
+                // when it exits
+                DuplicateTerminalAction::Shell(cwd) => {
+                    (self.get_default_terminal(cwd, None), None, None, None)
+                },
+            };
+        let (pid, starts_held) = self
+            .spawn_terminal(
+                Some(terminal_action),
+                ClientTabIndexOrPaneId::TabIndex(tab_index),
+            )
+            .with_context(err_context)?;
+        let hold_for_command = if starts_held { run_command } else { None };
+        let start_suppressed = false;
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::NewPane(
+                PaneId::Terminal(pid),
+                pane_title,
+                Some(should_float),
+                hold_for_command,
+                invoked_with,
+                None,
+                start_suppressed,
+                ClientTabIndexOrPaneId::TabIndex(tab_index),
+            ))
+            .with_context(err_context)?;
+        self.bus
+            .senders
+            .send_to_plugin(PluginInstruction::Update(vec![(
+                Some(plugin_id),
+                Some(client_id),
+                Event::PaneDuplicated {
+                    original_id: PaneId::Terminal(terminal_id).into(),
+                    new_id: PaneId::Terminal(pid).into(),
+                },
+            )]))
+            .with_context(err_context)
+    }
     pub fn spawn_terminal(
         &mut self,
         terminal_action: Option<TerminalAction>,
@@ -1127,6 +1321,16 @@ impl Pty {
                         }
                     });
                     self.task_handles.insert(terminal_id, terminal_bytes);
+                    if let Some(timeout_ms) = run_command
+                        .as_ref()
+                        .and_then(|run_command| run_command.startup_command_timeout_ms)
+                    {
+                        start_startup_command_timer(
+                            self.bus.senders.clone(),
+                            terminal_id,
+                            timeout_ms,
+                        );
+                    }
                 },
                 _ => match run_command {
                     Some(run_command) => {
@@ -1563,6 +1767,16 @@ impl Pty {
     pub fn reconfigure(&mut self, default_editor: Option<PathBuf>) {
         self.default_editor = default_editor;
     }
+    pub fn set_all_stopped(&self, stopped: bool) {
+        if let Some(os_input) = self.bus.os_input.as_ref() {
+            for child_pid in self.id_to_child_pid.values() {
+                os_input
+                    .set_process_tree_stopped(Pid::from_raw(*child_pid), stopped)
+                    .with_context(|| format!("failed to suspend or resume pid {child_pid}"))
+                    .non_fatal();
+            }
+        }
+    }
 }
 
 impl Drop for Pty {
@@ -1600,9 +1814,70 @@ fn send_command_not_found_to_screen(
     Ok(())
 }
 
+// the pane itself knows whether its command printed anything by the time this fires
+fn start_startup_command_timer(senders: ThreadSenders, terminal_id: u32, timeout_ms: u64) {
+    task::spawn(async move {
+        task::sleep(Duration::from_millis(timeout_ms)).await;
+        let _ = senders.send_to_screen(ScreenInstruction::StartupCommandTimedOut(
+            PaneId::Terminal(terminal_id),
+        ));
+    });
+}
+
 pub fn get_default_shell() -> PathBuf {
     PathBuf::from(std::env::var("SHELL").unwrap_or_else(|_| {
         log::warn!("Cannot read SHELL env, falling back to use /bin/sh");
         "/bin/sh".to_string()
     }))
 }
+
+#[derive(Debug)]
+enum DuplicateTerminalAction {
+    Command(RunCommand),
+    EditFile(OpenFilePayload),
+    Shell(Option<PathBuf>), // cwd
+}
+
+/// How many terminals opening a tab with these layouts spawns, its plugin panes aside
+pub(crate) fn terminal_count_in_layout(
+    layout: &TiledPaneLayout,
+    floating_panes_layout: &[FloatingPaneLayout],
+) -> usize {
+    let is_terminal = |run: &Option<Run>| !matches!(run, Some(Run::Plugin(_)));
+    let tiled_terminal_count = layout
+        .extract_run_instructions()
+        .iter()
+        .filter(|run| is_terminal(run))
+        .count();
+    let floating_terminal_count = floating_panes_layout
+        .iter()
+        .filter(|f| !f.already_running && is_terminal(&f.run))
+        .count();
+    tiled_terminal_count + floating_terminal_count
+}
+
+// shells are duplicated as shells rather than as commands running the shell
+fn terminal_action_for_duplicate(
+    invoked_with: Option<Run>,
+    current_cwd: Option<PathBuf>,
+) -> DuplicateTerminalAction {
+    match invoked_with {
+        Some(Run::Command(mut run_command)) => {
+            if current_cwd.is_some() {
+                run_command.cwd = current_cwd;
+            }
+            run_command.hold_on_start = false;
+            run_command.originating_plugin = None;
+            DuplicateTerminalAction::Command(run_command)
+        },
+        Some(Run::EditFile(path, line_number, cwd)) => {
+            DuplicateTerminalAction::EditFile(OpenFilePayload::new(path, line_number, cwd))
+        },
+        Some(Run::Cwd(cwd)) => DuplicateTerminalAction::Shell(current_cwd.or(Some(cwd))),
+        _ => DuplicateTerminalAction::Shell(current_cwd),
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/pty_tests.rs"]
+mod pty_tests;
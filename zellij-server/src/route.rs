@@ -3,6 +3,7 @@ use std::sync::{Arc, RwLock};
 
 use crate::thread_bus::ThreadSenders;
 use crate::{
+    background_jobs::BackgroundJob,
     os_input_output::ServerOsApi,
     panes::PaneId,
     plugins::PluginInstruction,
@@ -16,10 +17,10 @@ use uuid::Uuid;
 use zellij_utils::{
     channels::SenderWithContext,
     data::{Direction, Event, InputMode, PluginCapabilities, ResizeStrategy},
-    errors::prelude::*,
+    errors::{prelude::*, ErrorContext},
     input::{
         actions::{Action, SearchDirection, SearchOption},
-        command::TerminalAction,
+        command::{OpenFilePayload, TerminalAction},
         get_mode_info,
         keybinds::Keybinds,
         layout::Layout,
@@ -586,6 +587,16 @@ pub(crate) fn route_action(
                 .with_context(err_context)?;
             should_break = true;
         },
+        Action::SuspendSession => {
+            senders
+                .send_to_server(ServerInstruction::SuspendSession(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ResumeSession => {
+            senders
+                .send_to_server(ServerInstruction::ResumeSession(client_id))
+                .with_context(err_context)?;
+        },
         Action::MouseEvent(event) => {
             senders
                 .send_to_screen(ScreenInstruction::MouseEvent(event, client_id))
@@ -828,6 +839,8 @@ pub(crate) fn route_action(
             skip_cache,
             cwd,
             pane_title,
+            plugin_id,
+            is_last,
             ..
         } => {
             if let Some(seen_cli_pipes) = seen_cli_pipes.as_mut() {
@@ -861,6 +874,8 @@ pub(crate) fn route_action(
                         pane_title,
                         skip_cache,
                         cli_client_id: client_id,
+                        plugin_id,
+                        is_last,
                     })
                     .with_context(err_context)?;
             } else {
@@ -923,6 +938,11 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::ToggleExclusiveFloatingPanes => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleExclusiveFloatingPanes)
+                .with_context(err_context)?;
+        },
         Action::TogglePanePinned => {
             senders
                 .send_to_screen(ScreenInstruction::TogglePanePinned(client_id))
@@ -935,6 +955,35 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::RunCommandInPane { pane_id, command } => {
+            senders
+                .send_to_screen(ScreenInstruction::RunCommandInPane(
+                    PaneId::from(pane_id),
+                    command.into(),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::OpenFile {
+            path,
+            pane_id: pane_id_to_replace,
+        } => {
+            let title = format!("Editing: {}", path.display());
+            let cwd = path.parent().map(|parent| parent.to_path_buf());
+            let open_file = TerminalAction::OpenFile(OpenFilePayload::new(path, None, cwd));
+            let client_tab_index_or_pane_id = match pane_id_to_replace {
+                Some(pane_id_to_replace) => {
+                    ClientTabIndexOrPaneId::PaneId(PaneId::from(pane_id_to_replace))
+                },
+                None => ClientTabIndexOrPaneId::ClientId(client_id),
+            };
+            senders
+                .send_to_pty(PtyInstruction::SpawnInPlaceTerminal(
+                    Some(open_file),
+                    Some(title),
+                    client_tab_index_or_pane_id,
+                ))
+                .with_context(err_context)?;
+        },
     }
     Ok(should_break)
 }
@@ -955,6 +1004,79 @@ macro_rules! send_to_screen_or_retry_queue {
     }};
 }
 
+fn client_is_authenticated(
+    instruction: &ClientToServerMsg,
+    expected_auth_token: Option<&String>,
+    presented_auth_token: Option<&String>,
+) -> bool {
+    match (instruction, expected_auth_token) {
+        // checking whether the session is alive (eg. `zellij ls`) does not require a token
+        (ClientToServerMsg::Authenticate(_) | ClientToServerMsg::ConnStatus, _) => true,
+        (_, Some(expected_auth_token)) => presented_auth_token
+            .map(|presented_auth_token| {
+                auth_tokens_match(expected_auth_token, presented_auth_token)
+            })
+            .unwrap_or(false),
+        (_, None) => true,
+    }
+}
+
+// looks at every byte no matter where the first difference is, so that the time it takes does not
+// tell a client how much of the token it guessed right
+fn auth_tokens_match(expected_auth_token: &str, presented_auth_token: &str) -> bool {
+    let expected_auth_token = expected_auth_token.as_bytes();
+    let presented_auth_token = presented_auth_token.as_bytes();
+    expected_auth_token.len() == presented_auth_token.len()
+        && expected_auth_token
+            .iter()
+            .zip(presented_auth_token)
+            .fold(0, |difference, (expected, presented)| {
+                difference | (expected ^ presented)
+            })
+            == 0
+}
+
+fn should_deduplicate_resize(
+    session_data: &Arc<RwLock<Option<SessionMetaData>>>,
+    client_id: ClientId,
+) -> bool {
+    session_data
+        .read()
+        .ok()
+        .and_then(|session_data| {
+            session_data.as_ref().and_then(|session_data| {
+                session_data
+                    .session_configuration
+                    .get_client_configuration(&client_id)
+                    .options
+                    .deduplicate_resize
+            })
+        })
+        .unwrap_or(true)
+}
+
+// when the terminal is resized quickly, the client sends a resize for every intermediate size -
+// we only handle the last of those that already arrived, returning it along with the message we
+// read after it (if any) so that it is handled next
+fn last_of_consecutive_resizes(
+    resize: ClientToServerMsg,
+    receiver: &mut IpcReceiverWithContext<ClientToServerMsg>,
+) -> (
+    ClientToServerMsg,
+    Option<Option<(ClientToServerMsg, ErrorContext)>>,
+) {
+    let mut last_resize = resize;
+    while receiver.has_buffered_messages() {
+        match receiver.recv() {
+            Some((resize @ ClientToServerMsg::TerminalResize(_), _err_ctx)) => {
+                last_resize = resize;
+            },
+            message => return (last_resize, Some(message)),
+        }
+    }
+    (last_resize, None)
+}
+
 pub(crate) fn route_thread_main(
     session_data: Arc<RwLock<Option<SessionMetaData>>>,
     session_state: Arc<RwLock<SessionState>>,
@@ -966,9 +1088,26 @@ pub(crate) fn route_thread_main(
     let mut retry_queue = VecDeque::new();
     let err_context = || format!("failed to handle instruction for client {client_id}");
     let mut seen_cli_pipes = HashSet::new();
+    let mut presented_auth_token: Option<String> = None;
+    let mut next_message = None;
     'route_loop: loop {
-        match receiver.recv() {
+        let message = match next_message.take() {
+            Some(message) => message,
+            None => receiver.recv(),
+        };
+        match message {
             Some((instruction, err_ctx)) => {
+                let instruction = match instruction {
+                    ClientToServerMsg::TerminalResize(_)
+                        if should_deduplicate_resize(&session_data, client_id) =>
+                    {
+                        let (last_resize, message_after_resizes) =
+                            last_of_consecutive_resizes(instruction, &mut receiver);
+                        next_message = message_after_resizes;
+                        last_resize
+                    },
+                    instruction => instruction,
+                };
                 err_ctx.update_thread_ctx();
                 let mut handle_instruction = |instruction: ClientToServerMsg,
                                               mut retry_queue: Option<
@@ -978,20 +1117,64 @@ pub(crate) fn route_thread_main(
                     let mut should_break = false;
                     let rlocked_sessions =
                         session_data.read().to_anyhow().with_context(err_context)?;
+                    let expected_auth_token = rlocked_sessions
+                        .as_ref()
+                        .and_then(|session| session.auth_token.as_ref());
+                    if !client_is_authenticated(
+                        &instruction,
+                        expected_auth_token,
+                        presented_auth_token.as_ref(),
+                    ) {
+                        log::error!(
+                            "Client {} failed to authenticate, logging it out.",
+                            client_id
+                        );
+                        let _ = os_input.send_to_client(
+                            client_id,
+                            ServerToClientMsg::Exit(ExitReason::Error(
+                                "Failed to authenticate, this session requires an auth token (see --auth-token)".to_string(),
+                            )),
+                        );
+                        let _ = to_server.send(ServerInstruction::RemoveClient(client_id));
+                        return Ok(true);
+                    }
                     match instruction {
+                        ClientToServerMsg::Authenticate(auth_token) => {
+                            presented_auth_token = Some(auth_token);
+                        },
                         ClientToServerMsg::Key(key, raw_bytes, is_kitty_keyboard_protocol) => {
+                            session_state
+                                .write()
+                                .to_anyhow()
+                                .with_context(err_context)?
+                                .set_last_active_client(client_id);
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
                                 match rlocked_sessions.get_client_keybinds_and_mode(&client_id) {
                                     Some((keybinds, input_mode, default_input_mode)) => {
-                                        for action in keybinds
-                                            .get_actions_for_key_in_mode_or_default_action(
-                                                &input_mode,
-                                                &key,
-                                                raw_bytes,
-                                                default_input_mode,
-                                                is_kitty_keyboard_protocol,
-                                            )
+                                        // keys registered by plugins take precedence over both
+                                        // the keybindings and the focused pane
+                                        let actions = match rlocked_sessions
+                                            .global_hotkeys
+                                            .action_for_key(&key)
                                         {
+                                            Some(global_hotkey_action) => {
+                                                vec![global_hotkey_action]
+                                            },
+                                            None => keybinds
+                                                .get_actions_for_key_in_mode_or_default_action(
+                                                    &input_mode,
+                                                    &key,
+                                                    raw_bytes,
+                                                    default_input_mode,
+                                                    is_kitty_keyboard_protocol,
+                                                ),
+                                        };
+                                        for action in actions {
+                                            if let Some(audit_log) =
+                                                rlocked_sessions.audit_log.as_ref()
+                                            {
+                                                audit_log.log_action(client_id, &action);
+                                            }
                                             if route_action(
                                                 action,
                                                 client_id,
@@ -1023,7 +1206,15 @@ pub(crate) fn route_thread_main(
                         },
                         ClientToServerMsg::Action(action, maybe_pane_id, maybe_client_id) => {
                             let client_id = maybe_client_id.unwrap_or(client_id);
+                            session_state
+                                .write()
+                                .to_anyhow()
+                                .with_context(err_context)?
+                                .set_last_active_client(client_id);
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
+                                if let Some(audit_log) = rlocked_sessions.audit_log.as_ref() {
+                                    audit_log.log_action(client_id, &action);
+                                }
                                 if route_action(
                                     action,
                                     client_id,
@@ -1177,6 +1368,13 @@ pub(crate) fn route_thread_main(
                         ClientToServerMsg::ListClients => {
                             let _ = to_server.send(ServerInstruction::ActiveClients(client_id));
                         },
+                        ClientToServerMsg::ServerStatus => {
+                            if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
+                                let _ = rlocked_sessions.senders.send_to_background_jobs(
+                                    BackgroundJob::ReportServerStatus(client_id),
+                                );
+                            }
+                        },
                         ClientToServerMsg::ConfigWrittenToDisk(config) => {
                             let _ = to_server
                                 .send(ServerInstruction::ConfigWrittenToDisk(client_id, config));
@@ -1222,3 +1420,7 @@ pub(crate) fn route_thread_main(
     }
     Ok(())
 }
+
+#[cfg(test)]
+#[path = "./unit/route_tests.rs"]
+mod route_tests;
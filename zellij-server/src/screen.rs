@@ -5,11 +5,12 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::str;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use log::{debug, warn};
 use zellij_utils::data::{
-    Direction, KeyWithModifier, PaneManifest, PluginPermission, Resize, ResizeStrategy, SessionInfo,
+    Direction, GroupId, KeyWithModifier, PaneManifest, PluginPermission, Resize, ResizeStrategy,
+    SessionInfo,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -19,7 +20,8 @@ use zellij_utils::input::mouse::MouseEvent;
 use zellij_utils::input::options::Clipboard;
 use zellij_utils::pane_size::{Size, SizeInPixels};
 use zellij_utils::{
-    consts::{session_info_folder_for_session, ZELLIJ_SOCK_DIR},
+    channels::Sender,
+    consts::{session_info_folder_for_session, SCROLL_BUFFER_SIZE, ZELLIJ_SOCK_DIR},
     envs::set_session_name,
     input::command::TerminalAction,
     input::layout::{
@@ -30,6 +32,7 @@ use zellij_utils::{
 };
 
 use crate::background_jobs::BackgroundJob;
+use crate::metrics::Metric;
 use crate::os_input_output::ResizeCache;
 use crate::panes::alacritty_functions::xparse_color;
 use crate::panes::terminal_character::AnsiCode;
@@ -40,8 +43,11 @@ use crate::{
     panes::sixel::SixelImageStore,
     panes::PaneId,
     plugins::{PluginId, PluginInstruction, PluginRenderAsset},
-    pty::{ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
-    tab::{SuppressedPanes, Tab},
+    pty::{
+        terminal_count_in_layout, ClientTabIndexOrPaneId, PtyInstruction, VteBytes,
+        DEFAULT_SESSION_MAX_PANES,
+    },
+    tab::{Pane, SuppressedPanes, Tab},
     thread_bus::Bus,
     ui::{
         loading_indication::LoadingIndication,
@@ -59,6 +65,8 @@ use zellij_utils::{
     ipc::{ClientAttributes, PixelDimensions, ServerToClientMsg},
 };
 
+const RENDER_LATENCY_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Get the active tab and call a closure on it
 ///
 /// If no active tab can be found, an error is logged instead.
@@ -160,6 +168,7 @@ pub enum ScreenInstruction {
     OpenInPlaceEditor(PaneId, ClientTabIndexOrPaneId),
     TogglePaneEmbedOrFloating(ClientId),
     ToggleFloatingPanes(ClientId, Option<TerminalAction>),
+    ToggleExclusiveFloatingPanes,
     HorizontalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
     VerticalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
     WriteCharacter(Option<KeyWithModifier>, Vec<u8>, bool, ClientId), // bool ->
@@ -270,6 +279,9 @@ pub enum ScreenInstruction {
     SearchToggleWrap(ClientId),
     AddRedPaneFrameColorOverride(Vec<PaneId>, Option<String>), // Option<String> => optional error text
     ClearPaneFrameColorOverride(Vec<PaneId>),
+    StartupCommandTimedOut(PaneId),
+    PaneCountExceeded(Option<ClientId>),
+    SetFocusFollowsMouse(bool),
     PreviousSwapLayout(ClientId),
     NextSwapLayout(ClientId),
     QueryTabNames(ClientId),
@@ -328,8 +340,9 @@ pub enum ScreenInstruction {
         Option<PathBuf>,
         ClientId,
     ), // bools are: should_float, should_open_in_place Option<PaneId> is the pane id to replace, Option<PathBuf> is an optional cwd, bool after is skip_cache
-    SuppressPane(PaneId, ClientId),          // bool is should_float
-    FocusPaneWithId(PaneId, bool, ClientId), // bool is should_float
+    SuppressPane(PaneId, ClientId),           // bool is should_float
+    FocusPaneWithId(PaneId, bool, ClientId),  // bool is should_float
+    TogglePaneWithId(PaneId, bool, ClientId), // bool is should_float
     RenamePane(PaneId, Vec<u8>),
     RenameTab(usize, Vec<u8>),
     RequestPluginPermissions(
@@ -351,6 +364,7 @@ pub enum ScreenInstruction {
         ClientTabIndexOrPaneId,
     ),
     DumpLayoutToHd,
+    SaveLayout(PathBuf, Sender<()>), // PathBuf - the file to save the layout to
     RenameSession(String, ClientId), // String -> new name
     ListClientsMetadata(Option<PathBuf>, ClientId), // Option<PathBuf> - default shell
     Reconfigure {
@@ -378,12 +392,16 @@ pub enum ScreenInstruction {
     ScrollUpInPaneId(PaneId),
     ScrollDownInPaneId(PaneId),
     ScrollToTopInPaneId(PaneId),
+    ScrollToLineInPaneId(PaneId, usize), // usize - line, 0 being the oldest line in the scrollback
+    AnnotateScrollbackLineInPaneId(PaneId, usize, String), // usize - line, String - annotation
+    ClearScrollbackAnnotationsInPaneId(PaneId),
     ScrollToBottomInPaneId(PaneId),
     PageScrollUpInPaneId(PaneId),
     PageScrollDownInPaneId(PaneId),
     TogglePaneIdFullscreen(PaneId),
     TogglePaneEmbedOrEjectForPaneId(PaneId),
-    CloseTabWithIndex(usize),
+    CloseTabWithIndex(usize, ClientId),
+    SetTabPinned(usize, bool), // usize - tab position
     BreakPanesToNewTab {
         pane_ids: Vec<PaneId>,
         default_shell: Option<TerminalAction>,
@@ -400,7 +418,30 @@ pub enum ScreenInstruction {
     ListClientsToPlugin(PluginId, ClientId),
     TogglePanePinned(ClientId),
     SetFloatingPanePinned(PaneId, bool),
+    SetFloatingPaneZIndex(PaneId, usize), // usize - z_index, higher is in front
+    DragPaneToCoordinates(PaneId, usize, usize), // usize, usize - x, y relative to the viewport
     StackPanes(Vec<PaneId>),
+    CreateSynchronizedGroup(String, Vec<PaneId>, Sender<GroupId>), // String - group name
+    DissolveSynchronizedGroup(GroupId),
+    RunCommandInPane(PaneId, RunCommand),
+    SetPaneMinSize {
+        pane_id: PaneId,
+        min_rows: usize,
+        min_cols: usize,
+    },
+    SetPaneScrollbackSize {
+        pane_id: PaneId,
+        size: usize,
+    },
+    // String - query, bool - case sensitive, bool - the query is a regular expression
+    SearchPane(PaneId, String, bool, bool, PluginId, ClientId),
+    GetTabInfosForClient(ClientId, Sender<Vec<TabInfo>>),
+    GetPaneManifest(Sender<PaneManifest>),
+    GetStyleForClient(ClientId, Sender<Style>),
+    GetInputModeForClient(ClientId, Sender<InputMode>),
+    GetSessionInfos(Sender<Vec<SessionInfo>>),
+    PaneSnapshot(PaneId, PluginId, ClientId),
+    DuplicatePane(PaneId, PluginId, ClientId),
 }
 
 impl From<&ScreenInstruction> for ScreenContext {
@@ -415,6 +456,9 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::TogglePaneEmbedOrFloating
             },
             ScreenInstruction::ToggleFloatingPanes(..) => ScreenContext::ToggleFloatingPanes,
+            ScreenInstruction::ToggleExclusiveFloatingPanes => {
+                ScreenContext::ToggleExclusiveFloatingPanes
+            },
             ScreenInstruction::HorizontalSplit(..) => ScreenContext::HorizontalSplit,
             ScreenInstruction::VerticalSplit(..) => ScreenContext::VerticalSplit,
             ScreenInstruction::WriteCharacter(..) => ScreenContext::WriteCharacter,
@@ -538,6 +582,9 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ClearPaneFrameColorOverride(..) => {
                 ScreenContext::ClearPaneFrameColorOverride
             },
+            ScreenInstruction::StartupCommandTimedOut(..) => ScreenContext::StartupCommandTimedOut,
+            ScreenInstruction::PaneCountExceeded(..) => ScreenContext::PaneCountExceeded,
+            ScreenInstruction::SetFocusFollowsMouse(..) => ScreenContext::SetFocusFollowsMouse,
             ScreenInstruction::PreviousSwapLayout(..) => ScreenContext::PreviousSwapLayout,
             ScreenInstruction::NextSwapLayout(..) => ScreenContext::NextSwapLayout,
             ScreenInstruction::QueryTabNames(..) => ScreenContext::QueryTabNames,
@@ -563,6 +610,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::LaunchPlugin(..) => ScreenContext::LaunchPlugin,
             ScreenInstruction::SuppressPane(..) => ScreenContext::SuppressPane,
             ScreenInstruction::FocusPaneWithId(..) => ScreenContext::FocusPaneWithId,
+            ScreenInstruction::TogglePaneWithId(..) => ScreenContext::TogglePaneWithId,
             ScreenInstruction::RenamePane(..) => ScreenContext::RenamePane,
             ScreenInstruction::RenameTab(..) => ScreenContext::RenameTab,
             ScreenInstruction::RequestPluginPermissions(..) => {
@@ -575,6 +623,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ReplacePane(..) => ScreenContext::ReplacePane,
             ScreenInstruction::NewInPlacePluginPane(..) => ScreenContext::NewInPlacePluginPane,
             ScreenInstruction::DumpLayoutToHd => ScreenContext::DumpLayoutToHd,
+            ScreenInstruction::SaveLayout(..) => ScreenContext::SaveLayout,
             ScreenInstruction::RenameSession(..) => ScreenContext::RenameSession,
             ScreenInstruction::ListClientsMetadata(..) => ScreenContext::ListClientsMetadata,
             ScreenInstruction::Reconfigure { .. } => ScreenContext::Reconfigure,
@@ -592,6 +641,13 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ScrollUpInPaneId(..) => ScreenContext::ScrollUpInPaneId,
             ScreenInstruction::ScrollDownInPaneId(..) => ScreenContext::ScrollDownInPaneId,
             ScreenInstruction::ScrollToTopInPaneId(..) => ScreenContext::ScrollToTopInPaneId,
+            ScreenInstruction::ScrollToLineInPaneId(..) => ScreenContext::ScrollToLineInPaneId,
+            ScreenInstruction::AnnotateScrollbackLineInPaneId(..) => {
+                ScreenContext::AnnotateScrollbackLineInPaneId
+            },
+            ScreenInstruction::ClearScrollbackAnnotationsInPaneId(..) => {
+                ScreenContext::ClearScrollbackAnnotationsInPaneId
+            },
             ScreenInstruction::ScrollToBottomInPaneId(..) => ScreenContext::ScrollToBottomInPaneId,
             ScreenInstruction::PageScrollUpInPaneId(..) => ScreenContext::PageScrollUpInPaneId,
             ScreenInstruction::PageScrollDownInPaneId(..) => ScreenContext::PageScrollDownInPaneId,
@@ -600,6 +656,7 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::TogglePaneEmbedOrEjectForPaneId
             },
             ScreenInstruction::CloseTabWithIndex(..) => ScreenContext::CloseTabWithIndex,
+            ScreenInstruction::SetTabPinned(..) => ScreenContext::SetTabPinned,
             ScreenInstruction::BreakPanesToNewTab { .. } => ScreenContext::BreakPanesToNewTab,
             ScreenInstruction::BreakPanesToTabWithIndex { .. } => {
                 ScreenContext::BreakPanesToTabWithIndex
@@ -607,7 +664,26 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ListClientsToPlugin(..) => ScreenContext::ListClientsToPlugin,
             ScreenInstruction::TogglePanePinned(..) => ScreenContext::TogglePanePinned,
             ScreenInstruction::SetFloatingPanePinned(..) => ScreenContext::SetFloatingPanePinned,
+            ScreenInstruction::SetFloatingPaneZIndex(..) => ScreenContext::SetFloatingPaneZIndex,
+            ScreenInstruction::DragPaneToCoordinates(..) => ScreenContext::DragPaneToCoordinates,
             ScreenInstruction::StackPanes(..) => ScreenContext::StackPanes,
+            ScreenInstruction::CreateSynchronizedGroup(..) => {
+                ScreenContext::CreateSynchronizedGroup
+            },
+            ScreenInstruction::DissolveSynchronizedGroup(..) => {
+                ScreenContext::DissolveSynchronizedGroup
+            },
+            ScreenInstruction::RunCommandInPane(..) => ScreenContext::RunCommandInPane,
+            ScreenInstruction::SetPaneMinSize { .. } => ScreenContext::SetPaneMinSize,
+            ScreenInstruction::SetPaneScrollbackSize { .. } => ScreenContext::SetPaneScrollbackSize,
+            ScreenInstruction::SearchPane(..) => ScreenContext::SearchPane,
+            ScreenInstruction::GetTabInfosForClient(..) => ScreenContext::GetTabInfosForClient,
+            ScreenInstruction::GetPaneManifest(..) => ScreenContext::GetPaneManifest,
+            ScreenInstruction::GetStyleForClient(..) => ScreenContext::GetStyleForClient,
+            ScreenInstruction::GetInputModeForClient(..) => ScreenContext::GetInputModeForClient,
+            ScreenInstruction::GetSessionInfos(..) => ScreenContext::GetSessionInfos,
+            ScreenInstruction::PaneSnapshot(..) => ScreenContext::PaneSnapshot,
+            ScreenInstruction::DuplicatePane(..) => ScreenContext::DuplicatePane,
         }
     }
 }
@@ -687,6 +763,13 @@ pub(crate) struct Screen {
     layout_dir: Option<PathBuf>,
     default_layout_name: Option<String>,
     explicitly_disable_kitty_keyboard_protocol: bool,
+    exclusive_floating_panes: bool,
+    last_render_latency_report: Option<Instant>,
+    synchronized_groups: BTreeMap<GroupId, (String, Vec<PaneId>)>, // String is the group name
+    next_synchronized_group_id: GroupId,
+    /// The maximum amount of terminal panes in all of this [`Screen`]'s tabs together.
+    session_max_panes: usize,
+    focus_follows_mouse: bool,
 }
 
 impl Screen {
@@ -711,6 +794,9 @@ impl Screen {
         arrow_fonts: bool,
         layout_dir: Option<PathBuf>,
         explicitly_disable_kitty_keyboard_protocol: bool,
+        exclusive_floating_panes: bool,
+        session_max_panes: usize,
+        focus_follows_mouse: bool,
     ) -> Self {
         let session_name = mode_info.session_name.clone().unwrap_or_default();
         let session_info = SessionInfo::new(session_name.clone());
@@ -752,6 +838,12 @@ impl Screen {
             resurrectable_sessions,
             layout_dir,
             explicitly_disable_kitty_keyboard_protocol,
+            exclusive_floating_panes,
+            last_render_latency_report: None,
+            synchronized_groups: BTreeMap::new(),
+            next_synchronized_group_id: 0,
+            session_max_panes,
+            focus_follows_mouse,
         }
     }
 
@@ -950,6 +1042,16 @@ impl Screen {
                     if let Some(current_tab) = self.get_indexed_tab_mut(current_tab_index) {
                         if current_tab.has_no_connected_clients() {
                             current_tab.visible(false).with_context(err_context)?;
+                            // pinned floating panes belong to the session rather than to a
+                            // tab, so they follow the last client out of the tab
+                            let pinned_panes = current_tab.extract_pinned_floating_panes();
+                            if let Some(new_tab) = self.get_indexed_tab_mut(new_tab_index) {
+                                for pane in pinned_panes {
+                                    new_tab
+                                        .add_pinned_floating_pane(pane)
+                                        .with_context(err_context)?;
+                                }
+                            }
                         }
                     } else {
                         Err::<(), _>(anyhow!("Tab index {:?} not found", current_tab_index))
@@ -1079,6 +1181,7 @@ impl Screen {
                 .map(|p_id| (None, None, Event::PaneClosed(p_id.into())))
                 .collect(),
         ));
+        self.prune_synchronized_groups();
 
         // below we don't check the result of sending the CloseTab instruction to the pty thread
         // because this might be happening when the app is closing, at which point the pty thread
@@ -1132,6 +1235,9 @@ impl Screen {
                     .active_tab_indices
                     .get(&client_id)
                     .with_context(err_context)?;
+                if self.refuse_to_close_pinned_tab(active_tab_index, client_id) {
+                    return Ok(());
+                }
                 self.close_tab_at_index(active_tab_index)
                     .with_context(err_context)
             },
@@ -1139,6 +1245,36 @@ impl Screen {
         }
     }
 
+    // returns true (and lets the client know) if the tab is pinned and so should not be closed
+    fn refuse_to_close_pinned_tab(&mut self, tab_index: usize, client_id: ClientId) -> bool {
+        let is_pinned = self
+            .tabs
+            .get(&tab_index)
+            .map(|tab| tab.pinned)
+            .unwrap_or(false);
+        if is_pinned {
+            let error_text = "Tab is pinned";
+            log::error!("{}", error_text);
+            if let Some(os_input) = &mut self.bus.os_input {
+                let _ = os_input.send_to_client(
+                    client_id,
+                    ServerToClientMsg::LogError(vec![error_text.to_owned()]),
+                );
+            }
+        }
+        is_pinned
+    }
+
+    pub fn set_tab_pinned(&mut self, tab_position: usize, pinned: bool) -> Result<()> {
+        let err_context = || format!("failed to set pinned of tab at position {tab_position}");
+        match self.tabs.values_mut().find(|t| t.position == tab_position) {
+            Some(tab) => tab.pinned = pinned,
+            None => log::error!("Tab at position {} not found", tab_position),
+        }
+        self.log_and_report_session_state()
+            .with_context(err_context)
+    }
+
     pub fn resize_to_screen(&mut self, new_screen_size: Size) -> Result<()> {
         let err_context = || format!("failed to resize to screen size: {new_screen_size:#?}");
 
@@ -1200,6 +1336,7 @@ impl Screen {
     /// Renders this [`Screen`], which amounts to rendering its active [`Tab`].
     pub fn render(&mut self, plugin_render_assets: Option<Vec<PluginRenderAsset>>) -> Result<()> {
         let err_context = "failed to render screen";
+        let render_start_time = Instant::now();
 
         let mut output = Output::new(
             self.sixel_image_store.clone(),
@@ -1209,6 +1346,13 @@ impl Screen {
         let mut tabs_to_close = vec![];
         for (tab_index, tab) in &mut self.tabs {
             if tab.has_selectable_tiled_panes() {
+                if !tab.has_no_connected_clients() {
+                    // output of panes in tabs no client was looking at is parsed lazily
+                    for pane_id in tab.panes_with_pending_bytes() {
+                        tab.parse_pending_bytes_for_pane(pane_id)
+                            .context(err_context)?;
+                    }
+                }
                 tab.render(&mut output).context(err_context)?;
             } else if !tab.is_pending() {
                 tabs_to_close.push(*tab_index);
@@ -1235,9 +1379,30 @@ impl Screen {
                 .send_to_plugin(PluginInstruction::UnblockCliPipes(plugin_render_assets))
                 .context("failed to unblock input pipe");
         }
+        self.report_render_latency(render_start_time);
         Ok(())
     }
 
+    // we render very often, so we only report a sample of the render latency
+    fn report_render_latency(&mut self, render_start_time: Instant) {
+        let should_report = self
+            .last_render_latency_report
+            .map(|last_report| last_report.elapsed() >= RENDER_LATENCY_REPORT_INTERVAL)
+            .unwrap_or(true);
+        if should_report {
+            let render_latency_ms = render_start_time.elapsed().as_secs_f64() * 1000.0;
+            let _ = self
+                .bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::EmitMetric(Metric::new(
+                    "zellij_render_latency_ms",
+                    render_latency_ms,
+                    BTreeMap::new(),
+                )));
+            self.last_render_latency_report = Some(Instant::now());
+        }
+    }
+
     /// Returns a mutable reference to this [`Screen`]'s tabs.
     pub fn get_tabs_mut(&mut self) -> &mut BTreeMap<usize, Tab> {
         &mut self.tabs
@@ -1350,6 +1515,7 @@ impl Screen {
             self.arrow_fonts,
             self.styled_underlines,
             self.explicitly_disable_kitty_keyboard_protocol,
+            self.exclusive_floating_panes,
         );
         for (client_id, mode_info) in &self.mode_info {
             tab.change_mode_info(mode_info.clone(), *client_id);
@@ -1454,6 +1620,11 @@ impl Screen {
             self.add_client(client_id).with_context(err_context)?;
         }
 
+        // only now are all panes in the tab spawned and all its clients in place
+        if let Some(tab) = self.tabs.get_mut(&tab_index) {
+            tab.focus_auto_focus_pane();
+        }
+
         self.log_and_report_session_state()
             .and_then(|_| self.render(None))
             .with_context(err_context)
@@ -1534,39 +1705,12 @@ impl Screen {
                 other_focused_clients: all_focused_clients,
                 active_swap_layout_name,
                 is_swap_layout_dirty,
+                pinned: tab.pinned,
             };
             tab_infos_for_screen_state.insert(tab.position, tab_info_for_screen);
         }
         for (client_id, active_tab_index) in self.active_tab_indices.iter() {
-            let mut plugin_tab_updates = vec![];
-            for tab in self.tabs.values() {
-                let other_focused_clients: Vec<ClientId> = if self.session_is_mirrored {
-                    vec![]
-                } else {
-                    self.active_tab_indices
-                        .iter()
-                        .filter(|(c_id, tab_position)| {
-                            **tab_position == tab.index && *c_id != client_id
-                        })
-                        .map(|(c_id, _)| c_id)
-                        .copied()
-                        .collect()
-                };
-                let (active_swap_layout_name, is_swap_layout_dirty) = tab.swap_layout_info();
-                let tab_info_for_plugins = TabInfo {
-                    position: tab.position,
-                    name: tab.name.clone(),
-                    active: *active_tab_index == tab.index,
-                    panes_to_hide: tab.panes_to_hide_count(),
-                    is_fullscreen_active: tab.is_fullscreen_active(),
-                    is_sync_panes_active: tab.is_sync_panes_active(),
-                    are_floating_panes_visible: tab.are_floating_panes_visible(),
-                    other_focused_clients,
-                    active_swap_layout_name,
-                    is_swap_layout_dirty,
-                };
-                plugin_tab_updates.push(tab_info_for_plugins);
-            }
+            let plugin_tab_updates = self.tab_infos_for_client(*client_id, *active_tab_index);
             plugin_updates.push((None, Some(*client_id), Event::TabUpdate(plugin_tab_updates)));
         }
         self.bus
@@ -1575,11 +1719,76 @@ impl Screen {
             .context("failed to update tabs")?;
         Ok(tab_infos_for_screen_state.values().cloned().collect())
     }
-    fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
+    fn tab_infos_for_client(&self, client_id: ClientId, active_tab_index: usize) -> Vec<TabInfo> {
+        let mut tab_infos = vec![];
+        for tab in self.tabs.values() {
+            let other_focused_clients: Vec<ClientId> = if self.session_is_mirrored {
+                vec![]
+            } else {
+                self.active_tab_indices
+                    .iter()
+                    .filter(|(c_id, tab_position)| {
+                        **tab_position == tab.index && **c_id != client_id
+                    })
+                    .map(|(c_id, _)| c_id)
+                    .copied()
+                    .collect()
+            };
+            let (active_swap_layout_name, is_swap_layout_dirty) = tab.swap_layout_info();
+            tab_infos.push(TabInfo {
+                position: tab.position,
+                name: tab.name.clone(),
+                active: active_tab_index == tab.index,
+                panes_to_hide: tab.panes_to_hide_count(),
+                is_fullscreen_active: tab.is_fullscreen_active(),
+                is_sync_panes_active: tab.is_sync_panes_active(),
+                are_floating_panes_visible: tab.are_floating_panes_visible(),
+                other_focused_clients,
+                active_swap_layout_name,
+                is_swap_layout_dirty,
+                pinned: tab.pinned,
+            });
+        }
+        tab_infos
+    }
+    pub fn current_tab_infos_for_client(&self, client_id: ClientId) -> Vec<TabInfo> {
+        // plugins might be asking on behalf of a client that has not yet been added to the screen
+        // (eg. on session start), in which case we answer for the first connected client
+        let active_tab_index = self
+            .active_tab_indices
+            .get(&client_id)
+            .or_else(|| {
+                self.get_first_client_id()
+                    .and_then(|c_id| self.active_tab_indices.get(&c_id))
+            })
+            .copied();
+        match active_tab_index {
+            Some(active_tab_index) => self.tab_infos_for_client(client_id, active_tab_index),
+            None => vec![],
+        }
+    }
+    pub fn style_for_client(&self, client_id: ClientId) -> Style {
+        // this is the style of the most recent ModeUpdate this client got
+        self.mode_info
+            .get(&client_id)
+            .unwrap_or(&self.default_mode_info)
+            .style
+    }
+    pub fn input_mode_for_client(&self, client_id: ClientId) -> InputMode {
+        self.mode_info
+            .get(&client_id)
+            .unwrap_or(&self.default_mode_info)
+            .mode
+    }
+    pub fn pane_manifest(&self) -> PaneManifest {
         let mut pane_manifest = PaneManifest::default();
         for tab in self.tabs.values() {
             pane_manifest.panes.insert(tab.position, tab.pane_infos());
         }
+        pane_manifest
+    }
+    fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
+        let pane_manifest = self.pane_manifest();
         self.bus
             .senders
             .send_to_plugin(PluginInstruction::Update(vec![(
@@ -1612,6 +1821,7 @@ impl Screen {
             is_current_session: true,
             available_layouts,
             plugins: Default::default(), // these are filled in by the wasm thread
+            tags: Default::default(),    // these are filled in by the background jobs thread
         };
         self.bus
             .senders
@@ -2086,7 +2296,10 @@ impl Screen {
                 self.tabs
                     .iter_mut()
                     .find(|(_, t)| t.position == tab_index)
-                    .map(|(_, t)| t.focus_pane_with_id(pane_id, should_float_if_hidden, client_id))
+                    .map(|(_, t)| {
+                        t.focus_pane_with_id(pane_id, should_float_if_hidden, client_id)
+                            .and_then(|_| t.swap_layout_for_focused_pane(client_id))
+                    })
                     .with_context(err_context)
                     .non_fatal();
             },
@@ -2096,6 +2309,24 @@ impl Screen {
         };
         Ok(())
     }
+    pub fn toggle_pane_with_id(
+        &mut self,
+        pane_id: PaneId,
+        should_float_if_hidden: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let is_visible_to_client = self
+            .get_active_tab(client_id)
+            .map(|tab| tab.has_visible_pane_with_pid(&pane_id))
+            .unwrap_or(false);
+        if is_visible_to_client {
+            let active_tab = self.get_active_tab_mut(client_id)?;
+            active_tab.suppress_pane(pane_id, Some(client_id));
+            self.render(None)
+        } else {
+            self.focus_pane_with_id(pane_id, should_float_if_hidden, client_id)
+        }
+    }
     pub fn rerun_command_pane_with_id(&mut self, terminal_pane_id: u32) {
         let mut found = false;
         for tab in self.tabs.values_mut() {
@@ -2492,6 +2723,32 @@ impl Screen {
         );
         self.unblock_input().non_fatal();
     }
+    pub fn drag_pane_to_coordinates(&mut self, pane_id: PaneId, x: usize, y: usize) -> Result<()> {
+        let err_context = || format!("failed to drag pane {pane_id:?} to ({x}, {y})");
+        match self
+            .tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+        {
+            Some(tab) => tab
+                .drag_pane_to_coordinates(pane_id, x, y)
+                .with_context(err_context),
+            None => Err(anyhow!("pane not found")).with_context(err_context),
+        }
+    }
+    pub fn set_floating_pane_z_index(&mut self, pane_id: PaneId, z_index: usize) -> Result<()> {
+        let err_context = || format!("failed to set z-index of pane {pane_id:?}");
+        match self
+            .tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+        {
+            Some(tab) => tab
+                .set_floating_pane_z_index(pane_id, z_index)
+                .with_context(err_context),
+            None => Err(anyhow!("pane not found")).with_context(err_context),
+        }
+    }
     pub fn set_floating_pane_pinned(&mut self, pane_id: PaneId, should_be_pinned: bool) {
         let mut found = false;
         for tab in self.tabs.values_mut() {
@@ -2564,13 +2821,254 @@ impl Screen {
             .get_mut(&root_tab_id)
             .map(|t| t.stack_panes(root_pane_id, panes_to_stack));
     }
+    pub fn create_synchronized_group(&mut self, name: String, pane_ids: Vec<PaneId>) -> GroupId {
+        let group_id = self.next_synchronized_group_id;
+        self.next_synchronized_group_id += 1;
+        self.synchronized_groups
+            .insert(group_id, (name, pane_ids.clone()));
+        self.update_synchronized_groups(&pane_ids);
+        group_id
+    }
+    pub fn dissolve_synchronized_group(&mut self, group_id: GroupId) {
+        if let Some((_name, pane_ids)) = self.synchronized_groups.remove(&group_id) {
+            self.update_synchronized_groups(&pane_ids);
+        }
+    }
+    // closed panes leave their groups, groups left without panes are dissolved
+    fn prune_synchronized_groups(&mut self) {
+        if self.synchronized_groups.is_empty() {
+            return;
+        }
+        let all_pane_ids: HashSet<PaneId> = self
+            .tabs
+            .values()
+            .flat_map(|tab| tab.get_all_pane_ids())
+            .collect();
+        for (_name, pane_ids) in self.synchronized_groups.values_mut() {
+            pane_ids.retain(|pane_id| all_pane_ids.contains(pane_id));
+        }
+        self.synchronized_groups
+            .retain(|_group_id, (_name, pane_ids)| !pane_ids.is_empty());
+        self.update_synchronized_groups(&[]);
+    }
+    fn session_terminal_count(&self) -> usize {
+        self.tabs
+            .values()
+            .flat_map(|tab| tab.get_all_pane_ids())
+            .filter(|pane_id| matches!(pane_id, PaneId::Terminal(_)))
+            .count()
+    }
+    /// Whether opening a tab with this layout would take the session beyond its maximum pane
+    /// count, the first tab is always opened so that the session has one
+    fn new_tab_exceeds_max_panes(
+        &self,
+        layout: &Option<TiledPaneLayout>,
+        floating_panes_layout: &[FloatingPaneLayout],
+    ) -> bool {
+        if self.tabs.is_empty() {
+            return false;
+        }
+        // the same defaults the pty thread uses when spawning the terminals of the tab
+        let (default_tiled_layout, default_floating_panes_layout) = self.default_layout.new_tab();
+        let tiled_layout = layout.as_ref().unwrap_or(&default_tiled_layout);
+        let floating_panes_layout = if floating_panes_layout.is_empty() {
+            &default_floating_panes_layout
+        } else {
+            floating_panes_layout
+        };
+        self.session_terminal_count()
+            + terminal_count_in_layout(tiled_layout, floating_panes_layout)
+            > self.session_max_panes
+    }
+    // a pane in more than one group shows the name of the first one, the tabs write the input of
+    // each pane in a group to the other panes of the group they have
+    fn update_synchronized_groups(&mut self, changed_pane_ids: &[PaneId]) {
+        for pane_id in changed_pane_ids {
+            let group_name = self
+                .synchronized_groups
+                .values()
+                .find(|(_name, group_pane_ids)| group_pane_ids.contains(pane_id))
+                .map(|(name, _)| name.clone());
+            for tab in self.tabs.values_mut() {
+                if tab.has_pane_with_pid(pane_id) {
+                    tab.set_pane_synchronized_group(*pane_id, group_name);
+                    break;
+                }
+            }
+        }
+        for tab in self.tabs.values_mut() {
+            let tab_synchronized_groups = self
+                .synchronized_groups
+                .values()
+                .map(|(_name, group_pane_ids)| {
+                    group_pane_ids
+                        .iter()
+                        .copied()
+                        .filter(|pane_id| tab.has_pane_with_pid(pane_id))
+                        .collect::<Vec<_>>()
+                })
+                .filter(|tab_group_pane_ids| tab_group_pane_ids.len() > 1)
+                .collect();
+            tab.set_synchronized_groups(tab_synchronized_groups);
+        }
+    }
+    pub fn run_command_in_pane(&mut self, pane_id: PaneId, command: RunCommand) -> Result<()> {
+        let err_context = || format!("failed to run command in pane {:?}", pane_id);
+        if let PaneId::Plugin(_) = pane_id {
+            return Err(anyhow!("Cannot run a command in a plugin pane")).with_context(err_context);
+        }
+        let mut command_bytes = command
+            .to_shell_command_line()
+            .with_context(err_context)?
+            .into_bytes();
+        command_bytes.push(b'\n');
+        let tab = self
+            .tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+            .ok_or_else(|| anyhow!("Failed to find pane"))
+            .with_context(err_context)?;
+        tab.write_to_pane_id_without_preprocessing(command_bytes, pane_id)
+            .with_context(err_context)?;
+        Ok(())
+    }
+    pub fn parse_pending_bytes_for_pane(&mut self, pane_id: PaneId) -> Result<()> {
+        match self
+            .tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+        {
+            Some(tab) => tab.parse_pending_bytes_for_pane(pane_id),
+            None => Ok(()),
+        }
+    }
+    fn parse_all_pending_bytes(&mut self) -> Result<()> {
+        for tab in self.tabs.values_mut() {
+            for pane_id in tab.panes_with_pending_bytes() {
+                tab.parse_pending_bytes_for_pane(pane_id)?;
+            }
+        }
+        Ok(())
+    }
+    pub fn search_pane(
+        &self,
+        pane_id: PaneId,
+        query: String,
+        case_sensitive: bool,
+        regex: bool,
+        plugin_id: PluginId,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to search pane {:?}", pane_id);
+        // we only collect the pane contents here, the search itself happens in the background
+        // jobs thread so as not to block rendering
+        let pane_contents = self
+            .tabs
+            .values()
+            .find_map(|tab| tab.get_pane_with_id(pane_id))
+            .map(|pane| pane.dump_screen(true))
+            .unwrap_or_default();
+        self.bus
+            .senders
+            .send_to_background_jobs(BackgroundJob::SearchPane(
+                plugin_id,
+                client_id,
+                pane_id,
+                query,
+                case_sensitive,
+                regex,
+                pane_contents,
+            ))
+            .with_context(err_context)
+    }
+    pub fn pane_snapshot(
+        &self,
+        pane_id: PaneId,
+        plugin_id: PluginId,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to take a snapshot of pane {:?}", pane_id);
+        // we copy the visible cells here without going through the pty thread, so the pane's
+        // program is not held up by this
+        let terminal_emulator_colors = *self.terminal_emulator_colors.borrow();
+        let rows = self
+            .tabs
+            .values()
+            .find_map(|tab| tab.get_pane_with_id(pane_id))
+            .map(|pane| pane.snapshot(terminal_emulator_colors.fg, terminal_emulator_colors.bg))
+            .unwrap_or_default();
+        self.bus
+            .senders
+            .send_to_plugin(PluginInstruction::Update(vec![(
+                Some(plugin_id),
+                Some(client_id),
+                Event::PaneSnapshot {
+                    pane_id: pane_id.into(),
+                    rows,
+                },
+            )]))
+            .with_context(err_context)
+    }
+    pub fn duplicate_pane(
+        &self,
+        pane_id: PaneId,
+        plugin_id: PluginId,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to duplicate pane {:?}", pane_id);
+        let Some((tab_index, should_float, invoked_with)) = self.tabs.values().find_map(|tab| {
+            tab.get_pane_with_id(pane_id).map(|pane| {
+                (
+                    tab.index,
+                    tab.has_floating_pane_with_pid(&pane_id),
+                    pane.invoked_with().clone(),
+                )
+            })
+        }) else {
+            log::error!("Could not find pane with id {:?} to duplicate", pane_id);
+            return Ok(());
+        };
+        match (pane_id, invoked_with) {
+            (PaneId::Terminal(terminal_id), invoked_with) => self
+                .bus
+                .senders
+                .send_to_pty(PtyInstruction::DuplicateTerminal {
+                    terminal_id,
+                    invoked_with,
+                    should_float,
+                    tab_index,
+                    plugin_id,
+                    client_id,
+                })
+                .with_context(err_context),
+            (PaneId::Plugin(original_id), Some(Run::Plugin(run_plugin_or_alias))) => self
+                .bus
+                .senders
+                .send_to_plugin(PluginInstruction::DuplicatePlugin {
+                    original_id,
+                    run_plugin_or_alias,
+                    should_float,
+                    tab_index,
+                    size: self.size,
+                    plugin_id,
+                    client_id,
+                })
+                .with_context(err_context),
+            (PaneId::Plugin(_), _) => {
+                log::error!("Could not find the plugin running in pane {:?}", pane_id);
+                Ok(())
+            },
+        }
+    }
     fn unblock_input(&self) -> Result<()> {
         self.bus
             .senders
             .send_to_server(ServerInstruction::UnblockInputThread)
             .context("failed to unblock input")
     }
-    fn get_layout_metadata(&self, default_shell: Option<PathBuf>) -> SessionLayoutMetadata {
+    fn get_layout_metadata(&mut self, default_shell: Option<PathBuf>) -> SessionLayoutMetadata {
+        // lazily parsed output would otherwise be missing from the serialized pane contents
+        self.parse_all_pending_bytes().non_fatal();
         let mut session_layout_metadata = SessionLayoutMetadata::new(self.default_layout.clone());
         if let Some(default_shell) = default_shell {
             session_layout_metadata.update_default_shell(default_shell);
@@ -2578,6 +3076,12 @@ impl Screen {
         let first_client_id = self.get_first_client_id();
         let active_tab_index =
             first_client_id.and_then(|client_id| self.active_tab_indices.get(&client_id));
+        // panes using the configured scroll_buffer_size keep using it when resurrected, even if it
+        // changed in the meantime
+        let scrollback_size_override = |p: &Box<dyn Pane>| {
+            p.scrollback_size()
+                .filter(|scrollback_size| Some(scrollback_size) != SCROLL_BUFFER_SIZE.get())
+        };
 
         for (tab_index, tab) in self.tabs.iter() {
             let tab_is_focused = active_tab_index == Some(&tab_index);
@@ -2638,6 +3142,7 @@ impl Screen {
                             None
                         },
                         focused_clients,
+                        scrollback_size_override(p),
                     )
                 })
                 .collect();
@@ -2676,6 +3181,7 @@ impl Screen {
                             None
                         },
                         focused_clients,
+                        scrollback_size_override(p),
                     )
                 })
                 .collect();
@@ -2683,6 +3189,7 @@ impl Screen {
                 tab.name.clone(),
                 tab_is_focused,
                 hide_floating_panes,
+                tab.pinned,
                 tiled_panes,
                 floating_panes,
             );
@@ -2747,6 +3254,11 @@ pub(crate) fn screen_thread_main(
         // explicitly_disable_kitty_keyboard_protocol is false and vice versa
         .unwrap_or(false); // by default, we try to support this if the terminal supports it and
                            // the program running inside a pane requests it
+    let exclusive_floating_panes = config_options.exclusive_floating_panes.unwrap_or(false);
+    let session_max_panes = config_options
+        .session_max_panes
+        .unwrap_or(DEFAULT_SESSION_MAX_PANES);
+    let focus_follows_mouse = config_options.focus_follows_mouse.unwrap_or(false);
 
     let thread_senders = bus.senders.clone();
     let mut screen = Screen::new(
@@ -2778,6 +3290,9 @@ pub(crate) fn screen_thread_main(
         arrow_fonts,
         layout_dir,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
+        session_max_panes,
+        focus_follows_mouse,
     );
 
     let mut pending_tab_ids: HashSet<usize> = HashSet::new();
@@ -2940,6 +3455,15 @@ pub(crate) fn screen_thread_main(
 
                 screen.render(None)?;
             },
+            ScreenInstruction::ToggleExclusiveFloatingPanes => {
+                screen.exclusive_floating_panes = !screen.exclusive_floating_panes;
+                for tab in screen.tabs.values_mut() {
+                    tab.set_exclusive_floating_panes(screen.exclusive_floating_panes);
+                }
+                screen.render(None)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
             ScreenInstruction::HorizontalSplit(
                 pid,
                 initial_pane_title,
@@ -3184,6 +3708,22 @@ pub(crate) fn screen_thread_main(
                     .with_context(err_context)
                     .non_fatal();
             },
+            ScreenInstruction::GetTabInfosForClient(client_id, response_sender) => {
+                let _ = response_sender.send(screen.current_tab_infos_for_client(client_id));
+            },
+            ScreenInstruction::GetPaneManifest(response_sender) => {
+                let _ = response_sender.send(screen.pane_manifest());
+            },
+            ScreenInstruction::GetStyleForClient(client_id, response_sender) => {
+                let _ = response_sender.send(screen.style_for_client(client_id));
+            },
+            ScreenInstruction::GetInputModeForClient(client_id, response_sender) => {
+                let _ = response_sender.send(screen.input_mode_for_client(client_id));
+            },
+            ScreenInstruction::GetSessionInfos(response_sender) => {
+                let _ = response_sender
+                    .send(screen.session_infos_on_machine.values().cloned().collect());
+            },
             ScreenInstruction::ListClientsToPlugin(plugin_id, client_id) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata =
@@ -3383,6 +3923,7 @@ pub(crate) fn screen_thread_main(
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab.close_focused_pane(client_id), ?
                 );
+                screen.prune_synchronized_groups();
                 screen.render(None)?;
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
@@ -3419,6 +3960,7 @@ pub(crate) fn screen_thread_main(
                         }
                     },
                 }
+                screen.prune_synchronized_groups();
 
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
@@ -3498,6 +4040,13 @@ pub(crate) fn screen_thread_main(
                 should_change_focus_to_new_tab,
                 client_id,
             ) => {
+                if screen.new_tab_exceeds_max_panes(&layout, &floating_panes_layout) {
+                    screen
+                        .bus
+                        .senders
+                        .send_to_screen(ScreenInstruction::PaneCountExceeded(Some(client_id)))?;
+                    continue;
+                }
                 let tab_index = screen.get_new_tab_index();
                 pending_tab_ids.insert(tab_index);
                 let client_id_for_new_tab = if should_change_focus_to_new_tab {
@@ -3739,9 +4288,14 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
             },
             ScreenInstruction::MouseEvent(event, client_id) => {
-                let state_changed = screen
+                let mut state_changed = screen
                     .get_active_tab_mut(client_id)
                     .and_then(|tab| tab.handle_mouse_event(&event, client_id))?;
+                if screen.focus_follows_mouse && event.is_hover() {
+                    state_changed |= screen
+                        .get_active_tab_mut(client_id)
+                        .and_then(|tab| tab.focus_pane_under_mouse(&event.position, client_id))?;
+                }
                 if state_changed {
                     screen.log_and_report_session_state()?;
                 }
@@ -3877,6 +4431,29 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.render(None)?;
             },
+            ScreenInstruction::StartupCommandTimedOut(pane_id) => {
+                for tab in screen.get_tabs_mut().values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.startup_command_timed_out(pane_id);
+                        break;
+                    }
+                }
+                screen.render(None)?;
+            },
+            ScreenInstruction::PaneCountExceeded(client_id) => {
+                screen
+                    .bus
+                    .senders
+                    .send_to_plugin(PluginInstruction::Update(vec![(
+                        None,
+                        client_id,
+                        Event::PaneCountExceeded,
+                    )]))?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::SetFocusFollowsMouse(focus_follows_mouse) => {
+                screen.focus_follows_mouse = focus_follows_mouse;
+            },
             ScreenInstruction::ClearPaneFrameColorOverride(pane_ids) => {
                 let all_tabs = screen.get_tabs_mut();
                 for pane_id in pane_ids {
@@ -4337,6 +4914,10 @@ pub(crate) fn screen_thread_main(
                 screen.focus_pane_with_id(pane_id, should_float_if_hidden, client_id)?;
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::TogglePaneWithId(pane_id, should_float_if_hidden, client_id) => {
+                screen.toggle_pane_with_id(pane_id, should_float_if_hidden, client_id)?;
+                screen.log_and_report_session_state()?;
+            },
             ScreenInstruction::RenamePane(pane_id, new_name) => {
                 let all_tabs = screen.get_tabs_mut();
                 for tab in all_tabs.values_mut() {
@@ -4416,6 +4997,19 @@ pub(crate) fn screen_thread_main(
                     screen.dump_layout_to_hd()?;
                 }
             },
+            ScreenInstruction::SaveLayout(file_name, completion_sender) => {
+                let session_layout_metadata =
+                    screen.get_layout_metadata(screen.default_shell.clone());
+                screen
+                    .bus
+                    .senders
+                    .send_to_plugin(PluginInstruction::SaveLayout(
+                        session_layout_metadata,
+                        file_name,
+                        completion_sender,
+                    ))
+                    .context("failed to save layout")?;
+            },
             ScreenInstruction::RenameSession(name, client_id) => {
                 if screen.session_infos_on_machine.contains_key(&name) {
                     let error_text = "A session by this name already exists.";
@@ -4474,6 +5068,14 @@ pub(crate) fn screen_thread_main(
                         .log_and_report_session_state()
                         .with_context(err_context)?;
 
+                    screen
+                        .bus
+                        .senders
+                        .send_to_server(ServerInstruction::BroadcastEvent(Event::SessionRenamed(
+                            name.clone(),
+                        )))
+                        .with_context(err_context)?;
+
                     // set the env variable
                     set_session_name(name);
                 }
@@ -4628,6 +5230,52 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.render(None)?;
             },
+            ScreenInstruction::ScrollToLineInPaneId(pane_id, line) => {
+                let all_tabs = screen.get_tabs_mut();
+                for tab in all_tabs.values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        if let PaneId::Terminal(terminal_pane_id) = pane_id {
+                            tab.scroll_terminal_to_line(terminal_pane_id, line);
+                        } else {
+                            log::error!(
+                                "Currently only terminal panes are supported for scrolling to a line"
+                            );
+                        }
+                        break;
+                    }
+                }
+                screen.render(None)?;
+            },
+            ScreenInstruction::AnnotateScrollbackLineInPaneId(pane_id, line, annotation) => {
+                let all_tabs = screen.get_tabs_mut();
+                for tab in all_tabs.values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        if let PaneId::Terminal(terminal_pane_id) = pane_id {
+                            tab.annotate_terminal_scrollback_line(
+                                terminal_pane_id,
+                                line,
+                                &annotation,
+                            );
+                        } else {
+                            log::error!("Only terminal panes have a scrollback to annotate");
+                        }
+                        break;
+                    }
+                }
+                screen.render(None)?;
+            },
+            ScreenInstruction::ClearScrollbackAnnotationsInPaneId(pane_id) => {
+                let all_tabs = screen.get_tabs_mut();
+                for tab in all_tabs.values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        if let PaneId::Terminal(terminal_pane_id) = pane_id {
+                            tab.clear_terminal_scrollback_annotations(terminal_pane_id);
+                        }
+                        break;
+                    }
+                }
+                screen.render(None)?;
+            },
             ScreenInstruction::ScrollToBottomInPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
                 for tab in all_tabs.values_mut() {
@@ -4701,8 +5349,14 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.render(None)?;
             },
-            ScreenInstruction::CloseTabWithIndex(tab_index) => {
-                screen.close_tab_at_index(tab_index).non_fatal()
+            ScreenInstruction::CloseTabWithIndex(tab_index, client_id) => {
+                if !screen.refuse_to_close_pinned_tab(tab_index, client_id) {
+                    screen.close_tab_at_index(tab_index).non_fatal()
+                }
+            },
+            ScreenInstruction::SetTabPinned(tab_position, pinned) => {
+                screen.set_tab_pinned(tab_position, pinned)?;
+                screen.render(None)?;
             },
             ScreenInstruction::BreakPanesToNewTab {
                 pane_ids,
@@ -4738,11 +5392,94 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::SetFloatingPanePinned(pane_id, should_be_pinned) => {
                 screen.set_floating_pane_pinned(pane_id, should_be_pinned);
             },
+            ScreenInstruction::SetFloatingPaneZIndex(pane_id, z_index) => {
+                screen
+                    .set_floating_pane_z_index(pane_id, z_index)
+                    .non_fatal();
+                let _ = screen.render(None);
+            },
+            ScreenInstruction::DragPaneToCoordinates(pane_id, x, y) => {
+                screen.drag_pane_to_coordinates(pane_id, x, y).non_fatal();
+                let _ = screen.render(None);
+            },
             ScreenInstruction::StackPanes(pane_ids_to_stack) => {
                 screen.stack_panes(pane_ids_to_stack);
                 let _ = screen.unblock_input();
                 let _ = screen.render(None);
             },
+            ScreenInstruction::CreateSynchronizedGroup(name, pane_ids, response_sender) => {
+                let group_id = screen.create_synchronized_group(name, pane_ids);
+                let _ = response_sender.send(group_id);
+                screen.render(None)?;
+            },
+            ScreenInstruction::DissolveSynchronizedGroup(group_id) => {
+                screen.dissolve_synchronized_group(group_id);
+                screen.render(None)?;
+            },
+            ScreenInstruction::RunCommandInPane(pane_id, command) => {
+                screen.run_command_in_pane(pane_id, command).non_fatal();
+                let _ = screen.unblock_input();
+            },
+            ScreenInstruction::SetPaneMinSize {
+                pane_id,
+                min_rows,
+                min_cols,
+            } => {
+                let mut found_pane = false;
+                for tab in screen.get_tabs_mut().values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.set_pane_min_size(pane_id, min_rows, min_cols);
+                        found_pane = true;
+                        break;
+                    }
+                }
+                if !found_pane {
+                    pending_events_waiting_for_tab.push(ScreenInstruction::SetPaneMinSize {
+                        pane_id,
+                        min_rows,
+                        min_cols,
+                    });
+                }
+            },
+            ScreenInstruction::SetPaneScrollbackSize { pane_id, size } => {
+                let mut found_pane = false;
+                for tab in screen.get_tabs_mut().values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.set_pane_scrollback_size(pane_id, size);
+                        found_pane = true;
+                        break;
+                    }
+                }
+                if !found_pane {
+                    pending_events_waiting_for_tab
+                        .push(ScreenInstruction::SetPaneScrollbackSize { pane_id, size });
+                }
+                let _ = screen.render(None);
+            },
+            ScreenInstruction::SearchPane(
+                pane_id,
+                query,
+                case_sensitive,
+                regex,
+                plugin_id,
+                client_id,
+            ) => {
+                screen.parse_pending_bytes_for_pane(pane_id).non_fatal();
+                screen
+                    .search_pane(pane_id, query, case_sensitive, regex, plugin_id, client_id)
+                    .non_fatal();
+            },
+            ScreenInstruction::PaneSnapshot(pane_id, plugin_id, client_id) => {
+                screen.parse_pending_bytes_for_pane(pane_id).non_fatal();
+                screen
+                    .pane_snapshot(pane_id, plugin_id, client_id)
+                    .non_fatal();
+            },
+            ScreenInstruction::DuplicatePane(pane_id, plugin_id, client_id) => {
+                screen
+                    .duplicate_pane(pane_id, plugin_id, client_id)
+                    .non_fatal();
+            },
         }
     }
     Ok(())
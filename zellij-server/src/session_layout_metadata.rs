@@ -141,6 +141,9 @@ impl SessionLayoutMetadata {
         }
         false
     }
+    pub fn has_panes(&self) -> bool {
+        self.pane_count() > 0
+    }
     fn pane_count(&self) -> usize {
         let mut pane_count = 0;
         for tab in &self.tabs {
@@ -173,6 +176,7 @@ impl SessionLayoutMetadata {
         name: String,
         is_focused: bool,
         hide_floating_panes: bool,
+        pin_to_session: bool,
         tiled_panes: Vec<PaneLayoutMetadata>,
         floating_panes: Vec<PaneLayoutMetadata>,
     ) {
@@ -180,6 +184,7 @@ impl SessionLayoutMetadata {
             name: Some(name),
             is_focused,
             hide_floating_panes,
+            pin_to_session,
             tiled_panes,
             floating_panes,
         })
@@ -329,6 +334,7 @@ impl Into<TabLayoutManifest> for TabLayoutMetadata {
             floating_panes: self.floating_panes.into_iter().map(|t| t.into()).collect(),
             is_focused: self.is_focused,
             hide_floating_panes: self.hide_floating_panes,
+            pin_to_session: self.pin_to_session,
         }
     }
 }
@@ -343,6 +349,7 @@ impl Into<PaneLayoutManifest> for PaneLayoutMetadata {
             title: self.title,
             is_focused: self.is_focused,
             pane_contents: self.pane_contents,
+            scrollback_size: self.scrollback_size,
         }
     }
 }
@@ -354,6 +361,7 @@ pub struct TabLayoutMetadata {
     floating_panes: Vec<PaneLayoutMetadata>,
     is_focused: bool,
     hide_floating_panes: bool,
+    pin_to_session: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -367,6 +375,7 @@ pub struct PaneLayoutMetadata {
     is_focused: bool,
     pane_contents: Option<String>,
     focused_clients: Vec<ClientId>,
+    scrollback_size: Option<usize>,
 }
 
 impl PaneLayoutMetadata {
@@ -379,6 +388,7 @@ impl PaneLayoutMetadata {
         is_focused: bool,
         pane_contents: Option<String>,
         focused_clients: Vec<ClientId>,
+        scrollback_size: Option<usize>,
     ) -> Self {
         PaneLayoutMetadata {
             id,
@@ -390,6 +400,7 @@ impl PaneLayoutMetadata {
             is_focused,
             pane_contents,
             focused_clients,
+            scrollback_size,
         }
     }
 }
@@ -37,6 +37,7 @@ pub struct LayoutApplier<'a> {
     floating_panes: &'a mut FloatingPanes,
     draw_pane_frames: bool,
     focus_pane_id: &'a mut Option<PaneId>,
+    auto_focus_pane_id: Option<PaneId>,
     os_api: Box<dyn ServerOsApi>,
     debug: bool,
     arrow_fonts: bool,
@@ -92,6 +93,7 @@ impl<'a> LayoutApplier<'a> {
             floating_panes,
             draw_pane_frames,
             focus_pane_id,
+            auto_focus_pane_id: None,
             os_api,
             debug,
             arrow_fonts,
@@ -119,6 +121,10 @@ impl<'a> LayoutApplier<'a> {
         let should_show_floating_panes = layout_has_floating_panes && !hide_floating_panes;
         return Ok(should_show_floating_panes);
     }
+    /// The pane marked with `auto_focus` in the last applied layout, if any
+    pub fn auto_focus_pane_id(&self) -> Option<PaneId> {
+        self.auto_focus_pane_id
+    }
     pub fn apply_tiled_panes_layout_to_existing_panes(
         &mut self,
         layout: &TiledPaneLayout,
@@ -290,10 +296,14 @@ impl<'a> LayoutApplier<'a> {
         // we got from the plugin thread and pty thread
         // let positions_and_size = positions_in_layout.iter();
         let mut focus_pane_id: Option<PaneId> = None;
+        let mut auto_focus_pane_id: Option<PaneId> = None;
         let mut set_focus_pane_id = |layout: &TiledPaneLayout, pane_id: PaneId| {
             if layout.focus.unwrap_or(false) && focus_pane_id.is_none() {
                 focus_pane_id = Some(pane_id);
             }
+            if layout.auto_focus.unwrap_or(false) && auto_focus_pane_id.is_none() {
+                auto_focus_pane_id = Some(pane_id);
+            }
         };
         for (layout, position_and_size) in positions_in_layout {
             if let Some(Run::Plugin(run)) = layout.run.clone() {
@@ -307,6 +317,7 @@ impl<'a> LayoutApplier<'a> {
                 set_focus_pane_id(&layout, PaneId::Terminal(pid));
             }
         }
+        self.auto_focus_pane_id = auto_focus_pane_id;
         Ok(focus_pane_id)
     }
     fn handle_run_instructions_without_a_location(
@@ -368,6 +379,9 @@ impl<'a> LayoutApplier<'a> {
         if let Some(exclude_from_sync) = layout.exclude_from_sync {
             new_plugin.set_exclude_from_sync(exclude_from_sync);
         }
+        if let Some(min_size) = layout.min_size {
+            new_plugin.set_min_size(min_size.rows, min_size.cols);
+        }
         self.tiled_panes
             .add_pane_with_existing_geom(PaneId::Plugin(pid), Box::new(new_plugin));
         Ok(pid)
@@ -459,6 +473,9 @@ impl<'a> LayoutApplier<'a> {
             self.styled_underlines,
             self.explicitly_disable_kitty_keyboard_protocol,
         );
+        if let Some(scrollback_size) = floating_pane_layout.scrollback_size {
+            new_pane.set_scrollback_size(scrollback_size);
+        }
         if let Some(pane_initial_contents) = &floating_pane_layout.pane_initial_contents {
             new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
             new_pane.handle_pty_bytes("\n\r".as_bytes().into());
@@ -512,6 +529,9 @@ impl<'a> LayoutApplier<'a> {
             self.styled_underlines,
             self.explicitly_disable_kitty_keyboard_protocol,
         );
+        if let Some(scrollback_size) = layout.scrollback_size {
+            new_pane.set_scrollback_size(scrollback_size);
+        }
         if let Some(pane_initial_contents) = &layout.pane_initial_contents {
             new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
             new_pane.handle_pty_bytes("\n\r".as_bytes().into());
@@ -520,6 +540,9 @@ impl<'a> LayoutApplier<'a> {
         if let Some(exclude_from_sync) = layout.exclude_from_sync {
             new_pane.set_exclude_from_sync(exclude_from_sync);
         }
+        if let Some(min_size) = layout.min_size {
+            new_pane.set_min_size(min_size.rows, min_size.cols);
+        }
         if let Some(held_command) = hold_for_command {
             new_pane.hold(None, true, held_command.clone());
         }
@@ -11,7 +11,7 @@ use std::env::temp_dir;
 use std::path::PathBuf;
 use uuid::Uuid;
 use zellij_utils::data::{
-    Direction, KeyWithModifier, PaneInfo, PermissionStatus, PermissionType, PluginPermission,
+    Cell, Direction, KeyWithModifier, PaneInfo, PermissionStatus, PermissionType, PluginPermission,
     ResizeStrategy,
 };
 use zellij_utils::errors::prelude::*;
@@ -135,12 +135,17 @@ pub const MIN_TERMINAL_HEIGHT: usize = 5;
 pub const MIN_TERMINAL_WIDTH: usize = 5;
 
 const MAX_PENDING_VTE_EVENTS: usize = 7000;
+// how many chunks of output we hold for a pane in a tab no client is looking at before parsing
+// them anyway, so that busy background panes do not pile up unbounded memory
+const MAX_LAZILY_PARSED_VTE_EVENTS: usize = 64;
 
 type HoldForCommand = Option<RunCommand>;
 pub type SuppressedPanes = HashMap<PaneId, (bool, Box<dyn Pane>)>; // bool => is scrollback editor
 
 enum BufferedTabInstruction {
     SetPaneSelectable(PaneId, bool),
+    SetPaneMinSize(PaneId, usize, usize),
+    SetPaneScrollbackSize(PaneId, usize),
     HandlePtyBytes(u32, VteBytes),
     HoldPane(PaneId, Option<i32>, bool, RunCommand), // Option<i32> is the exit status, bool is is_first_run
 }
@@ -150,6 +155,7 @@ pub(crate) struct Tab {
     pub position: usize,
     pub name: String,
     pub prev_name: String,
+    pub pinned: bool, // pinned tabs cannot be closed by the user
     tiled_panes: TiledPanes,
     floating_panes: FloatingPanes,
     suppressed_panes: SuppressedPanes,
@@ -161,6 +167,7 @@ pub(crate) struct Tab {
     os_api: Box<dyn ServerOsApi>,
     pub senders: ThreadSenders,
     synchronize_is_active: bool,
+    synchronized_groups: Vec<Vec<PaneId>>, // the panes of each synchronized group that are in this tab
     should_clear_display_before_rendering: bool,
     mode_info: Rc<RefCell<HashMap<ClientId, ModeInfo>>>,
     default_mode_info: ModeInfo,
@@ -175,6 +182,7 @@ pub(crate) struct Tab {
     // TODO: used only to focus the pane when the layout is loaded
     // it seems that optimization is possible using `active_panes`
     focus_pane_id: Option<PaneId>,
+    auto_focus_pane_id: Option<PaneId>,
     copy_on_select: bool,
     last_mouse_hold_position: Option<Position>,
     terminal_emulator_colors: Rc<RefCell<Palette>>,
@@ -220,6 +228,10 @@ pub trait Pane {
     fn handle_pty_bytes(&mut self, _bytes: VteBytes) {}
     fn handle_plugin_bytes(&mut self, _client_id: ClientId, _bytes: VteBytes) {}
     fn cursor_coordinates(&self) -> Option<(usize, usize)>;
+    fn cursor_coordinates_for_client(&self, _client_id: ClientId) -> Option<(usize, usize)> {
+        // panes that render differently for each client (eg. plugins) can override this
+        self.cursor_coordinates()
+    }
     fn is_mid_frame(&self) -> bool {
         false
     }
@@ -239,6 +251,7 @@ pub trait Pane {
     fn set_should_render_boundaries(&mut self, _should_render: bool) {}
     fn selectable(&self) -> bool;
     fn set_selectable(&mut self, selectable: bool);
+    fn set_min_size(&mut self, min_rows: usize, min_cols: usize);
     fn request_permissions_from_user(&mut self, _permissions: Option<PluginPermission>) {}
     fn render(
         &mut self,
@@ -270,6 +283,9 @@ pub trait Pane {
     fn dump_screen(&self, _full: bool) -> String {
         "".to_owned()
     }
+    fn snapshot(&self, _default_fg: PaletteColor, _default_bg: PaletteColor) -> Vec<Vec<Cell>> {
+        vec![]
+    }
     fn scroll_up(&mut self, count: usize, client_id: ClientId);
     fn scroll_down(&mut self, count: usize, client_id: ClientId);
     fn clear_scroll(&mut self);
@@ -456,6 +472,15 @@ pub trait Pane {
     fn get_line_number(&self) -> Option<usize> {
         None
     }
+    fn scroll_to_line(&mut self, _line: usize) {
+        // No-op by default (only terminal-panes have a scrollback)
+    }
+    fn annotate_scrollback_line(&mut self, _line: usize, _annotation: &str) {
+        // No-op by default (only terminal-panes have a scrollback)
+    }
+    fn clear_scrollback_annotations(&mut self) {
+        // No-op by default (only terminal-panes have a scrollback)
+    }
     fn update_search_term(&mut self, _needle: &str) {
         // No-op by default (only terminal-panes currently have search capability)
     }
@@ -486,6 +511,8 @@ pub trait Pane {
     }
     fn add_red_pane_frame_color_override(&mut self, _error_text: Option<String>);
     fn clear_pane_frame_color_override(&mut self);
+    fn startup_command_timed_out(&mut self) {} // only relevant for terminal panes
+    fn set_synchronized_group(&mut self, _group_name: Option<String>) {} // only relevant for terminal panes
     fn frame_color_override(&self) -> Option<PaletteColor>;
     fn invoked_with(&self) -> &Option<Run>;
     fn set_title(&mut self, title: String);
@@ -510,6 +537,10 @@ pub trait Pane {
     fn rerun(&mut self) -> Option<RunCommand> {
         None
     } // only relevant to terminal panes
+    fn scrollback_size(&self) -> Option<usize> {
+        None
+    } // only relevant to terminal panes
+    fn set_scrollback_size(&mut self, _scrollback_size: usize) {} // only relevant to terminal panes
     fn update_theme(&mut self, _theme: Palette) {}
     fn update_arrow_fonts(&mut self, _should_support_arrow_fonts: bool) {}
     fn update_rounded_corners(&mut self, _rounded_corners: bool) {}
@@ -533,6 +564,7 @@ pub enum AdjustedInput {
     CloseThisPane,
     DropToShellInThisPane { working_dir: Option<PathBuf> },
     WriteKeyToPlugin(KeyWithModifier),
+    PasteToPlugin(String),
 }
 pub fn get_next_terminal_position(
     tiled_panes: &TiledPanes,
@@ -584,6 +616,7 @@ impl Tab {
         arrow_fonts: bool,
         styled_underlines: bool,
         explicitly_disable_kitty_keyboard_protocol: bool,
+        exclusive_floating_panes: bool,
     ) -> Self {
         let name = if name.is_empty() {
             format!("Tab #{}", index + 1)
@@ -615,7 +648,7 @@ impl Tab {
             os_api.clone(),
             senders.clone(),
         );
-        let floating_panes = FloatingPanes::new(
+        let mut floating_panes = FloatingPanes::new(
             display_area.clone(),
             viewport.clone(),
             connected_clients.clone(),
@@ -628,6 +661,7 @@ impl Tab {
             os_api.clone(),
             senders.clone(),
         );
+        floating_panes.set_exclusive_panes(exclusive_floating_panes);
 
         let clipboard_provider = match copy_options.command {
             Some(command) => ClipboardProvider::Command(CopyCommand::new(command)),
@@ -643,12 +677,14 @@ impl Tab {
             suppressed_panes: HashMap::new(),
             name: name.clone(),
             prev_name: name,
+            pinned: false,
             max_panes,
             viewport,
             display_area,
             character_cell_size,
             sixel_image_store,
             synchronize_is_active: false,
+            synchronized_groups: vec![],
             os_api,
             senders,
             should_clear_display_before_rendering: false,
@@ -663,6 +699,7 @@ impl Tab {
             link_handler: Rc::new(RefCell::new(LinkHandler::new())),
             clipboard_provider,
             focus_pane_id: None,
+            auto_focus_pane_id: None,
             copy_on_select: copy_options.copy_on_select,
             last_mouse_hold_position: None,
             terminal_emulator_colors,
@@ -691,7 +728,8 @@ impl Tab {
     ) -> Result<()> {
         self.swap_layouts
             .set_base_layout((layout.clone(), floating_panes_layout.clone()));
-        let should_show_floating_panes = LayoutApplier::new(
+        self.pinned = layout.pin_to_session;
+        let mut layout_applier = LayoutApplier::new(
             &self.viewport,
             &self.senders,
             &self.sixel_image_store,
@@ -711,8 +749,8 @@ impl Tab {
             self.arrow_fonts,
             self.styled_underlines,
             self.explicitly_disable_kitty_keyboard_protocol,
-        )
-        .apply_layout(
+        );
+        let should_show_floating_panes = layout_applier.apply_layout(
             layout,
             floating_panes_layout,
             new_terminal_ids,
@@ -720,6 +758,7 @@ impl Tab {
             new_plugin_ids,
             client_id,
         )?;
+        self.auto_focus_pane_id = layout_applier.auto_focus_pane_id();
         #[allow(clippy::if_same_then_else)]
         if should_show_floating_panes && !self.floating_panes.panes_are_visible() {
             self.toggle_floating_panes(Some(client_id), None)?;
@@ -731,6 +770,19 @@ impl Tab {
         self.apply_buffered_instructions()?;
         Ok(())
     }
+    /// Focuses the pane marked with `auto_focus` in the layout for all clients in this tab, this
+    /// should happen once the layout was applied and all the relevant clients were moved here
+    pub fn focus_auto_focus_pane(&mut self) {
+        if let Some(pane_id) = self.auto_focus_pane_id.take() {
+            if self.tiled_panes.panes_contain(&pane_id) {
+                self.tiled_panes.focus_pane_for_all_clients(pane_id);
+                // so that clients joining later also land on this pane
+                self.focus_pane_id = Some(pane_id);
+            } else {
+                log::warn!("Could not find pane {:?} to auto-focus", pane_id);
+            }
+        }
+    }
     pub fn swap_layout_info(&self) -> (Option<String>, bool) {
         if self.floating_panes.panes_are_visible() {
             self.swap_layouts.floating_layout_info()
@@ -780,10 +832,13 @@ impl Tab {
         if self.tiled_panes.fullscreen_is_active() {
             self.tiled_panes.unset_fullscreen();
         }
-        if let Some(layout_candidate) = self
+        let layout_candidate = self
             .swap_layouts
-            .swap_tiled_panes(&self.tiled_panes, search_backwards)
-        {
+            .swap_tiled_panes(&self.tiled_panes, search_backwards);
+        self.apply_tiled_swap_layout(layout_candidate)
+    }
+    fn apply_tiled_swap_layout(&mut self, layout_candidate: Option<TiledPaneLayout>) -> Result<()> {
+        if let Some(layout_candidate) = layout_candidate {
             LayoutApplier::new(
                 &self.viewport,
                 &self.senders,
@@ -814,6 +869,34 @@ impl Tab {
         self.set_should_clear_display_before_rendering();
         Ok(())
     }
+    /// Applies the tiled swap layout triggered by the type or title of the pane this client
+    /// focuses (eg. `swap_layout_when_pane_type="plugin"`), if there is one
+    pub fn swap_layout_for_focused_pane(&mut self, client_id: ClientId) -> Result<()> {
+        if self.floating_panes.panes_are_visible() {
+            return Ok(());
+        }
+        let (is_plugin, title) = match self.get_active_pane(client_id) {
+            Some(pane) => (
+                matches!(pane.pid(), PaneId::Plugin(_)),
+                pane.current_title(),
+            ),
+            None => return Ok(()),
+        };
+        if let Some(layout_candidate) = self.swap_layouts.swap_tiled_panes_for_focused_pane(
+            &self.tiled_panes,
+            is_plugin,
+            &title,
+        ) {
+            if self.tiled_panes.fullscreen_is_active() {
+                self.tiled_panes.unset_fullscreen();
+            }
+            self.apply_tiled_swap_layout(Some(layout_candidate))?;
+            self.senders
+                .send_to_pty_writer(PtyWriteInstruction::ApplyCachedResizes)
+                .with_context(|| format!("failed to apply cached resizes"))?;
+        }
+        Ok(())
+    }
     pub fn previous_swap_layout(&mut self) -> Result<()> {
         let search_backwards = true;
         if self.floating_panes.panes_are_visible() {
@@ -846,6 +929,12 @@ impl Tab {
                 BufferedTabInstruction::SetPaneSelectable(pane_id, selectable) => {
                     self.set_pane_selectable(pane_id, selectable);
                 },
+                BufferedTabInstruction::SetPaneMinSize(pane_id, min_rows, min_cols) => {
+                    self.set_pane_min_size(pane_id, min_rows, min_cols);
+                },
+                BufferedTabInstruction::SetPaneScrollbackSize(pane_id, scrollback_size) => {
+                    self.set_pane_scrollback_size(pane_id, scrollback_size);
+                },
                 BufferedTabInstruction::HandlePtyBytes(terminal_id, bytes) => {
                     self.handle_pty_bytes(terminal_id, bytes)?;
                 },
@@ -1620,9 +1709,16 @@ impl Tab {
                 .values()
                 .any(|s_p| s_p.1.pid() == *pid)
     }
+    pub fn has_floating_pane_with_pid(&self, pid: &PaneId) -> bool {
+        self.floating_panes.panes_contain(pid)
+    }
     pub fn has_non_suppressed_pane_with_pid(&self, pid: &PaneId) -> bool {
         self.tiled_panes.panes_contain(pid) || self.floating_panes.panes_contain(pid)
     }
+    pub fn has_visible_pane_with_pid(&self, pid: &PaneId) -> bool {
+        self.tiled_panes.panes_contain(pid)
+            || (self.are_floating_panes_visible() && self.floating_panes.panes_contain(pid))
+    }
     pub fn handle_pty_bytes(&mut self, pid: u32, bytes: VteBytes) -> Result<()> {
         if self.is_pending {
             self.pending_instructions
@@ -1655,8 +1751,45 @@ impl Tab {
                 return Ok(());
             }
         }
+        if self.has_no_connected_clients() && !may_contain_terminal_query(&bytes) {
+            // no client is looking at this tab, so we only parse the bytes once it is rendered
+            // or once the pane's contents are queried
+            let pending_vte_events = self.pending_vte_events.entry(pid).or_default();
+            pending_vte_events.push(bytes);
+            if pending_vte_events.len() >= MAX_LAZILY_PARSED_VTE_EVENTS {
+                self.process_pending_vte_events(pid)
+                    .with_context(err_context)?;
+            }
+            return Ok(());
+        }
+        // bytes we held back before must be parsed before these ones
+        self.process_pending_vte_events(pid)
+            .with_context(err_context)?;
         self.process_pty_bytes(pid, bytes).with_context(err_context)
     }
+    pub fn panes_with_pending_bytes(&self) -> Vec<PaneId> {
+        self.pending_vte_events
+            .iter()
+            .filter(|(_pid, pending_vte_events)| !pending_vte_events.is_empty())
+            .map(|(pid, _pending_vte_events)| PaneId::Terminal(*pid))
+            .collect()
+    }
+    /// Parse the bytes we held back for a pane while no client was looking at its tab, panes
+    /// that are scrolled keep holding them until their scroll is cleared
+    pub fn parse_pending_bytes_for_pane(&mut self, pane_id: PaneId) -> Result<()> {
+        let PaneId::Terminal(pid) = pane_id else {
+            return Ok(());
+        };
+        let is_scrolled = self
+            .get_pane_with_id(pane_id)
+            .map(|pane| pane.is_scrolled())
+            .unwrap_or(false);
+        if !is_scrolled {
+            self.process_pending_vte_events(pid)
+                .with_context(|| format!("failed to parse pending bytes for pane {:?}", pane_id))?;
+        }
+        Ok(())
+    }
     pub fn handle_plugin_bytes(
         &mut self,
         pid: u32,
@@ -1786,14 +1919,59 @@ impl Tab {
                 .with_context(err_context)?
         };
         // Can't use 'err_context' here since it borrows 'raw_input_bytes'
-        self.write_to_pane_id(
-            key_with_modifier,
-            raw_input_bytes,
-            raw_input_bytes_are_kitty,
-            pane_id,
-            Some(client_id),
-        )
-        .with_context(|| format!("failed to write to active terminal for client {client_id}"))
+        let mut should_trigger_ui_change = self
+            .write_to_pane_id(
+                key_with_modifier,
+                raw_input_bytes.clone(),
+                raw_input_bytes_are_kitty,
+                pane_id,
+                Some(client_id),
+            )
+            .with_context(|| {
+                format!("failed to write to active terminal for client {client_id}")
+            })?;
+        for synchronized_pane_id in self.synchronized_terminals(pane_id) {
+            let ui_change_triggered = self
+                .write_to_pane_id(
+                    key_with_modifier,
+                    raw_input_bytes.clone(),
+                    raw_input_bytes_are_kitty,
+                    synchronized_pane_id,
+                    Some(client_id),
+                )
+                .with_context(|| {
+                    format!("failed to write to synchronized terminal {synchronized_pane_id:?}")
+                })?;
+            if ui_change_triggered {
+                should_trigger_ui_change = true;
+            }
+        }
+        Ok(should_trigger_ui_change)
+    }
+
+    pub fn set_synchronized_groups(&mut self, synchronized_groups: Vec<Vec<PaneId>>) {
+        self.synchronized_groups = synchronized_groups;
+    }
+
+    // the other terminals sharing a synchronized group with this pane, each of them once - only
+    // terminals take input, plugin panes are shown as part of their group nonetheless
+    fn synchronized_terminals(&self, pane_id: PaneId) -> Vec<PaneId> {
+        let mut synchronized_terminals = vec![];
+        for group in &self.synchronized_groups {
+            if !group.contains(&pane_id) {
+                continue;
+            }
+            for group_pane_id in group {
+                if matches!(group_pane_id, PaneId::Terminal(_))
+                    && *group_pane_id != pane_id
+                    && !synchronized_terminals.contains(group_pane_id)
+                    && self.has_pane_with_pid(group_pane_id)
+                {
+                    synchronized_terminals.push(*group_pane_id);
+                }
+            }
+        }
+        synchronized_terminals
     }
 
     pub fn write_to_terminal_at(
@@ -1917,6 +2095,15 @@ impl Tab {
                         )]))
                         .with_context(err_context)?;
                 },
+                Some(AdjustedInput::PasteToPlugin(pasted_text)) => {
+                    self.senders
+                        .send_to_plugin(PluginInstruction::Update(vec![(
+                            Some(pid),
+                            client_id,
+                            Event::PastedText(pasted_text),
+                        )]))
+                        .with_context(err_context)?;
+                },
                 Some(AdjustedInput::WriteBytesToTerminal(adjusted_input)) => {
                     let mut plugin_updates = vec![];
                     for key in parse_keys(&adjusted_input) {
@@ -2006,7 +2193,7 @@ impl Tab {
             .get(&active_pane_id)
             .or_else(|| self.tiled_panes.get_pane(active_pane_id))?;
         active_terminal
-            .cursor_coordinates()
+            .cursor_coordinates_for_client(client_id)
             .map(|(x_in_terminal, y_in_terminal)| {
                 let x = active_terminal.x() + x_in_terminal;
                 let y = active_terminal.y() + y_in_terminal;
@@ -2700,6 +2887,31 @@ impl Tab {
             self.draw_pane_frames,
         );
     }
+    pub fn set_pane_min_size(&mut self, id: PaneId, min_rows: usize, min_cols: usize) {
+        if self.is_pending {
+            self.pending_instructions
+                .push(BufferedTabInstruction::SetPaneMinSize(
+                    id, min_rows, min_cols,
+                ));
+            return;
+        }
+        if let Some(pane) = self.get_pane_with_id_mut(id) {
+            pane.set_min_size(min_rows, min_cols);
+        }
+    }
+    pub fn set_pane_scrollback_size(&mut self, id: PaneId, scrollback_size: usize) {
+        if self.is_pending {
+            self.pending_instructions
+                .push(BufferedTabInstruction::SetPaneScrollbackSize(
+                    id,
+                    scrollback_size,
+                ));
+            return;
+        }
+        if let Some(pane) = self.get_pane_with_id_mut(id) {
+            pane.set_scrollback_size(scrollback_size);
+        }
+    }
     pub fn close_pane(&mut self, id: PaneId, ignore_suppressed_panes: bool) {
         // we need to ignore suppressed panes when we toggle a pane to be floating/embedded(tiled)
         // this is because in that case, while we do use this logic, we're not actually closing the
@@ -2756,6 +2968,8 @@ impl Tab {
         id: PaneId,
         dont_swap_if_suppressed: bool,
     ) -> Option<Box<dyn Pane>> {
+        // the pending bytes of the pane stay with this tab, so we parse them before it leaves
+        self.parse_pending_bytes_for_pane(id).non_fatal();
         if !dont_swap_if_suppressed && self.suppressed_panes.contains_key(&id) {
             // this is done for the scrollback editor
             return match self.replace_pane_with_suppressed_pane(id) {
@@ -2951,6 +3165,7 @@ impl Tab {
         pane_id: PaneId,
         full: bool,
     ) -> Result<()> {
+        self.parse_pending_bytes_for_pane(pane_id).non_fatal();
         if let Some(pane) = self.get_pane_with_id(pane_id) {
             let dump = pane.dump_screen(full);
             self.os_api.write_to_file(dump, file).non_fatal()
@@ -3162,6 +3377,29 @@ impl Tab {
         }
     }
 
+    pub fn scroll_terminal_to_line(&mut self, terminal_pane_id: u32, line: usize) {
+        if let Some(terminal_pane) = self.get_pane_with_id_mut(PaneId::Terminal(terminal_pane_id)) {
+            terminal_pane.scroll_to_line(line);
+        }
+    }
+
+    pub fn annotate_terminal_scrollback_line(
+        &mut self,
+        terminal_pane_id: u32,
+        line: usize,
+        annotation: &str,
+    ) {
+        if let Some(terminal_pane) = self.get_pane_with_id_mut(PaneId::Terminal(terminal_pane_id)) {
+            terminal_pane.annotate_scrollback_line(line, annotation);
+        }
+    }
+
+    pub fn clear_terminal_scrollback_annotations(&mut self, terminal_pane_id: u32) {
+        if let Some(terminal_pane) = self.get_pane_with_id_mut(PaneId::Terminal(terminal_pane_id)) {
+            terminal_pane.clear_scrollback_annotations();
+        }
+    }
+
     pub fn clear_active_terminal_scroll(&mut self, client_id: ClientId) -> Result<()> {
         // TODO: is this a thing?
         let err_context =
@@ -3560,7 +3798,8 @@ impl Tab {
             } else {
                 let relative_position = pane_with_selection.relative_position(&event.position);
                 pane_with_selection.end_selection(&relative_position, client_id);
-                if let PaneId::Terminal(_) = pane_with_selection.pid() {
+                let pane_id = pane_with_selection.pid();
+                if let PaneId::Terminal(_) = pane_id {
                     if copy_on_release {
                         let selected_text = pane_with_selection.get_selected_text();
                         pane_with_selection.reset_selection();
@@ -3568,6 +3807,7 @@ impl Tab {
                         if let Some(selected_text) = selected_text {
                             self.write_selection_to_clipboard(&selected_text)
                                 .with_context(err_context)?;
+                            self.report_pane_selection(pane_id, selected_text);
                         }
                     }
                 }
@@ -3708,6 +3948,32 @@ impl Tab {
         }
         None
     }
+    // for focus follows mouse, unlike clicking this does not hide the floating panes when hovering
+    // outside of them - returns true if the focus changed
+    pub fn focus_pane_under_mouse(
+        &mut self,
+        point: &Position,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        let err_context =
+            || format!("failed to focus pane under mouse at {point:?} for client {client_id}");
+
+        let hovered_pane_id = if self.floating_panes.panes_are_visible() {
+            self.floating_panes
+                .get_pane_id_at(point, true)
+                .with_context(err_context)?
+        } else {
+            self.get_pane_id_at(point, true).with_context(err_context)?
+        };
+        match hovered_pane_id {
+            Some(pane_id) if Some(pane_id) != self.get_active_pane_id(client_id) => {
+                self.focus_pane_with_id(pane_id, false, client_id)
+                    .with_context(err_context)?;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
     fn focus_pane_at(&mut self, point: &Position, client_id: ClientId) -> Result<()> {
         let err_context =
             || format!("failed to focus pane at position {point:?} for client {client_id}");
@@ -3808,6 +4074,9 @@ impl Tab {
                 .with_context(|| {
                     format!("failed to write selection to clipboard for client {client_id}")
                 })?;
+            if let Some(pane_id) = self.get_active_pane_id(client_id) {
+                self.report_pane_selection(pane_id, selected_text);
+            }
             self.senders
                 .send_to_plugin(PluginInstruction::Update(vec![(
                     None,
@@ -3822,6 +4091,23 @@ impl Tab {
         Ok(())
     }
 
+    // only plugins subscribed to PaneSelection (and allowed to read the application state) get
+    // the text, unlike CopyToClipboard which only tells them that something was copied
+    fn report_pane_selection(&self, pane_id: PaneId, selected_text: String) {
+        if let PaneId::Terminal(_) = pane_id {
+            self.senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    None,
+                    None,
+                    Event::PaneSelection {
+                        pane_id: pane_id.into(),
+                        selected_text,
+                    },
+                )]))
+                .context("failed to notify plugins about a pane selection")
+                .non_fatal();
+        }
+    }
     fn write_selection_to_clipboard(&self, selection: &str) -> Result<()> {
         let err_context = || format!("failed to write selection to clipboard: '{}'", selection);
 
@@ -3960,6 +4246,11 @@ impl Tab {
         self.set_should_clear_display_before_rendering();
         self.set_force_render();
     }
+    pub fn set_exclusive_floating_panes(&mut self, exclusive_floating_panes: bool) {
+        self.floating_panes
+            .set_exclusive_panes(exclusive_floating_panes);
+        self.set_force_render();
+    }
     pub fn panes_to_hide_count(&self) -> usize {
         self.tiled_panes.panes_to_hide_count()
     }
@@ -4054,6 +4345,36 @@ impl Tab {
             pane.clear_pane_frame_color_override();
         }
     }
+    pub fn startup_command_timed_out(&mut self, pane_id: PaneId) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values_mut()
+                    .find(|s_p| s_p.1.pid() == pane_id)
+                    .map(|s_p| &mut s_p.1)
+            })
+        {
+            pane.startup_command_timed_out();
+        }
+    }
+    pub fn set_pane_synchronized_group(&mut self, pane_id: PaneId, group_name: Option<String>) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values_mut()
+                    .find(|s_p| s_p.1.pid() == pane_id)
+                    .map(|s_p| &mut s_p.1)
+            })
+        {
+            pane.set_synchronized_group(group_name);
+        }
+    }
     pub fn update_plugin_loading_stage(&mut self, pid: u32, loading_indication: LoadingIndication) {
         if let Some(plugin_pane) = self
             .tiled_panes
@@ -4203,6 +4524,25 @@ impl Tab {
         }
         pane_info
     }
+    pub fn extract_pinned_floating_panes(&mut self) -> Vec<Box<dyn Pane>> {
+        self.floating_panes
+            .pinned_pane_ids()
+            .into_iter()
+            .filter_map(|pane_id| self.extract_pane(pane_id, true))
+            .collect()
+    }
+    // unlike add_floating_pane, this keeps the place of the pane on screen and does not focus it
+    pub fn add_pinned_floating_pane(&mut self, mut pane: Box<dyn Pane>) -> Result<()> {
+        let err_context = || format!("failed to add pinned floating pane");
+        let pane_id = pane.pid();
+        pane.set_content_offset(Offset::frame(1)); // floating panes always have a frame
+        resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+            .with_context(err_context)?;
+        self.floating_panes.add_pane(pane_id, pane);
+        self.set_force_render();
+        self.floating_panes.set_force_render();
+        Ok(())
+    }
     pub fn add_floating_pane(
         &mut self,
         mut pane: Box<dyn Pane>,
@@ -4433,6 +4773,25 @@ impl Tab {
             self.set_force_render();
         }
     }
+    pub fn set_floating_pane_z_index(&mut self, pane_id: PaneId, z_index: usize) -> Result<()> {
+        let err_context = || format!("failed to set z-index of pane {pane_id:?} to {z_index}");
+        if !self.floating_panes.panes_contain(&pane_id) {
+            return Err(anyhow!("only floating panes have a z-index")).with_context(err_context);
+        }
+        self.floating_panes.set_pane_z_index(pane_id, z_index);
+        Ok(())
+    }
+    pub fn drag_pane_to_coordinates(&mut self, pane_id: PaneId, x: usize, y: usize) -> Result<()> {
+        let err_context = || format!("failed to drag pane {pane_id:?} to ({x}, {y})");
+        if !self.floating_panes.panes_contain(&pane_id) {
+            return Err(anyhow!("only floating panes can be dragged")).with_context(err_context);
+        }
+        self.floating_panes
+            .move_pane_to_coordinates(pane_id, x, y)
+            .with_context(err_context)?;
+        self.swap_layouts.set_is_floating_damaged();
+        Ok(())
+    }
     pub fn has_room_for_stack(&self, root_pane_id: PaneId, stack_size: usize) -> bool {
         if self.floating_panes.panes_contain(&root_pane_id)
             || self.suppressed_panes.contains_key(&root_pane_id)
@@ -4520,6 +4879,46 @@ impl Tab {
     }
 }
 
+// terminal queries (eg. the cursor position or device attributes) are answered by the parser, a
+// program waiting for the answer would be stuck until its tab is shown if we deferred parsing them
+fn may_contain_terminal_query(bytes: &[u8]) -> bool {
+    let mut index = 0;
+    while let Some(offset) = bytes[index..].iter().position(|b| *b == 0x1b) {
+        index += offset + 1;
+        match bytes.get(index) {
+            Some(b'[') => {
+                // CSI: parameter and intermediate bytes followed by a final byte
+                let final_byte = bytes[index + 1..]
+                    .iter()
+                    .find(|b| !(0x20..=0x3f).contains(*b));
+                match final_byte {
+                    Some(b'c' | b'n' | b'p' | b'q' | b't' | b'u') => return true,
+                    Some(_) => {},
+                    None => return true, // the rest of the sequence is in the next chunk
+                }
+            },
+            Some(b']' | b'P') => {
+                // OSC and DCS queries ask for a value with a '?'
+                let sequence_end = bytes[index + 1..]
+                    .iter()
+                    .position(|b| *b == 0x07 || *b == 0x1b)
+                    .map(|position| index + 1 + position);
+                match sequence_end {
+                    Some(sequence_end) => {
+                        if bytes[index + 1..sequence_end].contains(&b'?') {
+                            return true;
+                        }
+                    },
+                    None => return true,
+                }
+            },
+            Some(_) => {},
+            None => return true,
+        }
+    }
+    false
+}
+
 pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
     let mut pane_info = PaneInfo::default();
     pane_info.pane_x = pane.x();
@@ -4536,6 +4935,7 @@ pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
     pane_info.exited = pane.exited();
     pane_info.exit_status = pane.exit_status();
     pane_info.is_held = pane.is_held();
+    pane_info.scrollback_size = pane.scrollback_size();
 
     match pane_id {
         PaneId::Terminal(terminal_id) => {
@@ -4545,6 +4945,20 @@ pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
                 Run::Command(run_command) => Some(run_command.to_string()),
                 _ => None,
             });
+            pane_info.terminal_command_argv = match pane.invoked_with() {
+                Some(Run::Command(run_command)) => {
+                    std::iter::once(run_command.command.display().to_string())
+                        .chain(run_command.args.iter().cloned())
+                        .collect()
+                },
+                _ => vec![],
+            };
+            pane_info.cwd = pane.invoked_with().as_ref().and_then(|c| match c {
+                Run::Command(run_command) => run_command.cwd.clone(),
+                Run::Cwd(cwd) => Some(cwd.clone()),
+                Run::EditFile(_, _, cwd) => cwd.clone(),
+                Run::Plugin(_) => None,
+            });
         },
         PaneId::Plugin(plugin_id) => {
             pane_info.id = *plugin_id;
@@ -1,4 +1,4 @@
-use crate::panes::{FloatingPanes, TiledPanes};
+use crate::panes::{FloatingPanes, PaneId, TiledPanes};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::rc::Rc;
@@ -170,6 +170,25 @@ impl SwapLayouts {
         tiled_panes: &TiledPanes,
     ) -> bool {
         match constraint {
+            LayoutConstraint::FocusedPaneIsPlugin
+            | LayoutConstraint::FocusedPaneIsTerminal
+            | LayoutConstraint::FocusedPaneTitleMatches(..) => tiled_panes
+                .first_active_pane_id()
+                .and_then(|pane_id| tiled_panes.get_pane(pane_id))
+                .and_then(|pane| {
+                    let is_plugin = matches!(pane.pid(), PaneId::Plugin(_));
+                    constraint.matches_focused_pane(is_plugin, &pane.current_title())
+                })
+                .unwrap_or(false),
+            LayoutConstraint::MaxPluginPanes(max_plugin_panes) => {
+                tiled_panes.visible_plugin_panes_count() <= *max_plugin_panes
+            },
+            LayoutConstraint::MinPluginPanes(min_plugin_panes) => {
+                tiled_panes.visible_plugin_panes_count() >= *min_plugin_panes
+            },
+            LayoutConstraint::ExactPluginPanes(plugin_pane_count) => {
+                tiled_panes.visible_plugin_panes_count() == *plugin_pane_count
+            },
             LayoutConstraint::MaxPanes(max_panes) => {
                 tiled_panes.visible_panes_count() <= *max_panes
             },
@@ -188,6 +207,25 @@ impl SwapLayouts {
         floating_panes: &FloatingPanes,
     ) -> bool {
         match constraint {
+            LayoutConstraint::FocusedPaneIsPlugin
+            | LayoutConstraint::FocusedPaneIsTerminal
+            | LayoutConstraint::FocusedPaneTitleMatches(..) => floating_panes
+                .first_active_floating_pane_id()
+                .and_then(|pane_id| floating_panes.get_pane(pane_id))
+                .and_then(|pane| {
+                    let is_plugin = matches!(pane.pid(), PaneId::Plugin(_));
+                    constraint.matches_focused_pane(is_plugin, &pane.current_title())
+                })
+                .unwrap_or(false),
+            LayoutConstraint::MaxPluginPanes(max_plugin_panes) => {
+                floating_panes.visible_plugin_panes_count() <= *max_plugin_panes
+            },
+            LayoutConstraint::MinPluginPanes(min_plugin_panes) => {
+                floating_panes.visible_plugin_panes_count() >= *min_plugin_panes
+            },
+            LayoutConstraint::ExactPluginPanes(plugin_pane_count) => {
+                floating_panes.visible_plugin_panes_count() == *plugin_pane_count
+            },
             LayoutConstraint::MaxPanes(max_panes) => {
                 floating_panes.visible_panes_count() <= *max_panes
             },
@@ -243,23 +281,10 @@ impl SwapLayouts {
             {
                 Some(swap_layout) => {
                     for (constraint, layout) in swap_layout.0.iter() {
-                        if self.state_fits_tiled_panes_constraint(constraint, tiled_panes) {
-                            let focus_layout_if_not_focused = true;
-                            let display_area = self.display_area.borrow();
-                            // TODO: reuse the assets from position_panes_in_space here?
-                            let pane_count = tiled_panes.visible_panes_count();
-                            let display_area = PaneGeom::from(&*display_area);
-                            if layout
-                                .position_panes_in_space(
-                                    &display_area,
-                                    Some(pane_count),
-                                    false,
-                                    focus_layout_if_not_focused,
-                                )
-                                .is_ok()
-                            {
-                                return Some(layout.clone());
-                            }
+                        if self.state_fits_tiled_panes_constraint(constraint, tiled_panes)
+                            && self.tiled_layout_fits_in_display_area(layout, tiled_panes)
+                        {
+                            return Some(layout.clone());
                         };
                     }
                     progress_layout!();
@@ -274,6 +299,62 @@ impl SwapLayouts {
         }
         None
     }
+    /// Looks for a tiled swap layout with a focused pane trigger (eg.
+    /// `swap_layout_when_pane_type="plugin"`) matching the given focused pane, returns it if it's
+    /// not the one already applied
+    pub fn swap_tiled_panes_for_focused_pane(
+        &mut self,
+        tiled_panes: &TiledPanes,
+        is_plugin: bool,
+        title: &str,
+    ) -> Option<TiledPaneLayout> {
+        let matching_layout_position =
+            self.swap_tiled_layouts
+                .iter()
+                .enumerate()
+                .find_map(|(position, swap_layout)| {
+                    swap_layout
+                        .0
+                        .iter()
+                        .find(|(constraint, layout)| {
+                            constraint.matches_focused_pane(is_plugin, title) == Some(true)
+                                && self.tiled_layout_fits_in_display_area(layout, tiled_panes)
+                        })
+                        .map(|(_constraint, layout)| (position, layout.clone()))
+                });
+        match matching_layout_position {
+            Some((position, _layout))
+                if position == self.current_tiled_layout_position && !self.is_tiled_damaged =>
+            {
+                None
+            },
+            Some((position, layout)) => {
+                self.current_tiled_layout_position = position;
+                self.is_tiled_damaged = false;
+                Some(layout)
+            },
+            None => None,
+        }
+    }
+    fn tiled_layout_fits_in_display_area(
+        &self,
+        layout: &TiledPaneLayout,
+        tiled_panes: &TiledPanes,
+    ) -> bool {
+        let focus_layout_if_not_focused = true;
+        let display_area = self.display_area.borrow();
+        // TODO: reuse the assets from position_panes_in_space here?
+        let pane_count = tiled_panes.visible_panes_count();
+        let display_area = PaneGeom::from(&*display_area);
+        layout
+            .position_panes_in_space(
+                &display_area,
+                Some(pane_count),
+                false,
+                focus_layout_if_not_focused,
+            )
+            .is_ok()
+    }
     pub fn best_effort_tiled_layout(
         &mut self,
         tiled_panes: &TiledPanes,
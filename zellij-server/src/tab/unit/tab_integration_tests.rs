@@ -228,6 +228,7 @@ fn create_new_tab(size: Size, default_mode: ModeInfo) -> Tab {
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -254,6 +255,7 @@ fn create_new_tab(size: Size, default_mode: ModeInfo) -> Tab {
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -307,6 +309,7 @@ fn create_new_tab_with_swap_layouts(
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -333,6 +336,7 @@ fn create_new_tab_with_swap_layouts(
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     let (
         base_layout,
@@ -388,6 +392,7 @@ fn create_new_tab_with_os_api(
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -414,6 +419,7 @@ fn create_new_tab_with_os_api(
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -455,6 +461,7 @@ fn create_new_tab_with_layout(size: Size, default_mode: ModeInfo, layout: &str)
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -481,6 +488,7 @@ fn create_new_tab_with_layout(size: Size, default_mode: ModeInfo, layout: &str)
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     let pane_ids = tab_layout
         .extract_run_instructions()
@@ -536,6 +544,7 @@ fn create_new_tab_with_mock_pty_writer(
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -562,6 +571,7 @@ fn create_new_tab_with_mock_pty_writer(
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -608,6 +618,7 @@ fn create_new_tab_with_sixel_support(
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -634,6 +645,7 @@ fn create_new_tab_with_sixel_support(
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -793,6 +805,48 @@ fn dump_screen() {
     );
 }
 
+#[test]
+fn pane_output_is_parsed_lazily_while_no_client_is_looking_at_the_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let map = Arc::new(Mutex::new(HashMap::new()));
+    tab.os_api = Box::new(FakeInputOutput {
+        file_dumps: map.clone(),
+        ..Default::default()
+    });
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, None, None, None, false, Some(client_id))
+        .unwrap();
+    tab.remove_client(client_id);
+    tab.handle_pty_bytes(2, Vec::from("scratch".as_bytes()))
+        .unwrap();
+    assert_eq!(
+        tab.panes_with_pending_bytes(),
+        vec![new_pane_id],
+        "bytes were held back"
+    );
+    tab.handle_pty_bytes(2, Vec::from("\u{1b}[6n".as_bytes()))
+        .unwrap();
+    assert!(
+        tab.panes_with_pending_bytes().is_empty(),
+        "bytes were parsed to answer the cursor position query"
+    );
+    tab.handle_pty_bytes(2, Vec::from(" more".as_bytes()))
+        .unwrap();
+    let file = "/tmp/log-lazy-parsing.sh";
+    tab.dump_terminal_screen(Some(file.to_string()), new_pane_id, false)
+        .unwrap();
+    assert_eq!(
+        map.lock().unwrap().get(file).unwrap(),
+        "scratch more",
+        "pending bytes were parsed before dumping the screen"
+    );
+}
+
 #[test]
 fn clear_screen() {
     let size = Size {
@@ -850,6 +904,80 @@ fn new_floating_pane() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn drag_floating_pane_to_coordinates() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    let mut output = Output::default();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(new_pane_id, None, None, None, None, false, Some(client_id))
+        .unwrap();
+    tab.handle_pty_bytes(
+        2,
+        Vec::from("\n\n\n                   I am scratch terminal".as_bytes()),
+    )
+    .unwrap();
+    tab.drag_pane_to_coordinates(new_pane_id, 0, 0).unwrap();
+    tab.render(&mut output).unwrap();
+    let pane_geom = tab
+        .get_pane_with_id(new_pane_id)
+        .unwrap()
+        .position_and_size();
+    assert_eq!(
+        (pane_geom.x, pane_geom.y),
+        (0, 0),
+        "pane moved to coordinates"
+    );
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+}
+
+#[test]
+fn drag_floating_pane_out_of_viewport_bounds_is_rejected() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(new_pane_id, None, None, None, None, false, Some(client_id))
+        .unwrap();
+    let geom_before = tab
+        .get_pane_with_id(new_pane_id)
+        .unwrap()
+        .position_and_size();
+    assert!(
+        tab.drag_pane_to_coordinates(new_pane_id, 120, 0).is_err(),
+        "pane would overflow horizontally"
+    );
+    assert!(
+        tab.drag_pane_to_coordinates(new_pane_id, 0, 19).is_err(),
+        "pane would overflow vertically"
+    );
+    assert!(
+        tab.drag_pane_to_coordinates(PaneId::Terminal(1), 0, 0)
+            .is_err(),
+        "tiled panes cannot be dragged"
+    );
+    let geom_after = tab
+        .get_pane_with_id(new_pane_id)
+        .unwrap()
+        .position_and_size();
+    assert_eq!(geom_before, geom_after, "pane did not move");
+}
+
 #[test]
 fn floating_panes_persist_across_toggles() {
     let size = Size {
@@ -1029,6 +1157,116 @@ fn five_new_floating_panes() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn exclusive_floating_panes_show_only_the_focused_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.set_exclusive_floating_panes(true);
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    let new_pane_id_3 = PaneId::Terminal(4);
+    let mut output = Output::default();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(
+        new_pane_id_1,
+        None,
+        None,
+        None,
+        None,
+        false,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.new_pane(
+        new_pane_id_2,
+        None,
+        None,
+        None,
+        None,
+        false,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.new_pane(
+        new_pane_id_3,
+        None,
+        None,
+        None,
+        None,
+        false,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.handle_pty_bytes(2, Vec::from("\u{1b}#8".as_bytes()))
+        .unwrap();
+    tab.handle_pty_bytes(3, Vec::from("\u{1b}#8".as_bytes()))
+        .unwrap();
+    tab.handle_pty_bytes(4, Vec::from("I am the focused floating pane".as_bytes()))
+        .unwrap();
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+}
+
+#[test]
+fn floating_panes_are_stacked_by_z_index_and_then_by_focus() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    let new_pane_id_3 = PaneId::Terminal(4);
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    for pane_id in [new_pane_id_1, new_pane_id_2, new_pane_id_3] {
+        tab.new_pane(pane_id, None, None, None, None, false, Some(client_id))
+            .unwrap();
+    }
+    // new floating panes are cascaded, so this point is inside all three of them
+    let last_pane_geom = tab
+        .get_pane_with_id(new_pane_id_3)
+        .unwrap()
+        .position_and_size();
+    let point = Position::new(last_pane_geom.y as i32 + 1, last_pane_geom.x as u16 + 1);
+    let pane_in_front = |tab: &Tab| tab.floating_panes.get_pane_id_at(&point, false).unwrap();
+    assert_eq!(pane_in_front(&tab), Some(new_pane_id_3));
+    tab.set_floating_pane_z_index(new_pane_id_1, 1).unwrap();
+    assert_eq!(
+        pane_in_front(&tab),
+        Some(new_pane_id_1),
+        "pane with a higher z-index is in front of the focused pane"
+    );
+    tab.set_floating_pane_z_index(new_pane_id_2, 1).unwrap();
+    tab.floating_panes.focus_pane(new_pane_id_2, client_id);
+    assert_eq!(
+        pane_in_front(&tab),
+        Some(new_pane_id_2),
+        "last focused pane is in front among panes with the same z-index"
+    );
+    tab.floating_panes.focus_pane(new_pane_id_3, client_id);
+    assert_eq!(
+        pane_in_front(&tab),
+        Some(new_pane_id_2),
+        "focusing a pane does not bring it in front of panes with a higher z-index"
+    );
+    assert!(
+        tab.set_floating_pane_z_index(PaneId::Terminal(1), 1)
+            .is_err(),
+        "tiled panes have no z-index"
+    );
+}
+
 #[test]
 fn increase_floating_pane_size() {
     let size = Size {
@@ -3741,6 +3979,55 @@ fn pane_bracketed_paste_ignored_when_not_in_bracketed_paste_mode() {
     assert_eq!(pty_instruction_bus.clone_output(), vec!["", "test", ""]);
 }
 
+#[test]
+fn input_is_written_to_the_other_panes_of_a_synchronized_group() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id: u16 = 1;
+
+    let mut pty_instruction_bus = MockPtyInstructionBus::new();
+    let mut tab = create_new_tab_with_mock_pty_writer(
+        size,
+        ModeInfo::default(),
+        pty_instruction_bus.pty_write_sender(),
+    );
+    pty_instruction_bus.start();
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        None,
+        None,
+        None,
+        false,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.new_pane(
+        PaneId::Terminal(3),
+        None,
+        None,
+        None,
+        None,
+        false,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.set_synchronized_groups(vec![vec![PaneId::Terminal(1), PaneId::Terminal(3)]]);
+
+    tab.write_to_active_terminal(&None, "test".as_bytes().to_vec(), false, client_id)
+        .unwrap();
+
+    pty_instruction_bus.exit();
+
+    assert_eq!(
+        pty_instruction_bus.clone_output(),
+        vec!["test", "test"],
+        "input is written to the focused pane and to the other pane of its group only"
+    );
+}
+
 #[test]
 fn pane_faux_scrolling_in_alternate_mode() {
     let size = Size {
@@ -6879,6 +7166,81 @@ fn layout_with_plugins_and_commands_swaped_properly() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn plugin_can_show_cursor_within_its_bounds() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let base_layout = r#"
+        layout {
+            pane {
+                plugin location="zellij:tab-bar"
+            }
+        }
+    "#;
+    let (base_layout, base_floating_layout) =
+        Layout::from_kdl(base_layout, Some("file_name.kdl".into()), None, None)
+            .unwrap()
+            .template
+            .unwrap();
+    let mut new_plugin_ids = HashMap::new();
+    new_plugin_ids.insert(
+        RunPluginOrAlias::from_url("zellij:tab-bar", &None, None, None).unwrap(),
+        vec![1],
+    );
+    let mut tab = create_new_tab_with_swap_layouts(
+        size,
+        ModeInfo::default(),
+        (vec![], vec![]),
+        Some((
+            base_layout,
+            base_floating_layout,
+            vec![],
+            vec![],
+            new_plugin_ids,
+        )),
+        true,
+    );
+    let _ = tab.handle_plugin_bytes(1, client_id, "I am an input field".as_bytes().to_vec());
+    assert_eq!(
+        tab.get_active_terminal_cursor_position(client_id),
+        None,
+        "cursor hidden by default"
+    );
+    let _ = tab.handle_plugin_bytes(
+        1,
+        client_id,
+        "I am an input field\u{1b}[3;5H\u{1b}[?25h"
+            .as_bytes()
+            .to_vec(),
+    );
+    assert_eq!(
+        tab.get_active_terminal_cursor_position(client_id),
+        Some((5, 3)),
+        "cursor shown at the requested coordinates (offset by the pane frame)"
+    );
+    let _ = tab.handle_plugin_bytes(
+        1,
+        client_id,
+        "I am an input field\u{1b}[3;500H\u{1b}[?25h"
+            .as_bytes()
+            .to_vec(),
+    );
+    assert_eq!(
+        tab.get_active_terminal_cursor_position(client_id),
+        Some((119, 3)),
+        "cursor clamped to the pane bounds"
+    );
+    let _ = tab.handle_plugin_bytes(1, client_id, "I am an input field".as_bytes().to_vec());
+    assert_eq!(
+        tab.get_active_terminal_cursor_position(client_id),
+        None,
+        "cursor hidden again when not explicitly shown"
+    );
+}
+
 #[test]
 fn base_layout_is_included_in_swap_layouts() {
     let size = Size {
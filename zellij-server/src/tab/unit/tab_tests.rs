@@ -13,6 +13,7 @@ use zellij_utils::errors::prelude::*;
 use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout};
 use zellij_utils::ipc::IpcReceiverWithContext;
 use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::position::Position;
 
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -168,6 +169,7 @@ fn create_new_tab(size: Size) -> Tab {
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -194,6 +196,7 @@ fn create_new_tab(size: Size) -> Tab {
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -232,6 +235,7 @@ fn create_new_tab_with_layout(size: Size, layout: TiledPaneLayout) -> Tab {
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -258,6 +262,7 @@ fn create_new_tab_with_layout(size: Size, layout: TiledPaneLayout) -> Tab {
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     let mut new_terminal_ids = vec![];
     for i in 0..layout.extract_run_instructions().len() {
@@ -302,6 +307,7 @@ fn create_new_tab_with_cell_size(
     let arrow_fonts = true;
     let styled_underlines = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
     let mut tab = Tab::new(
         index,
         position,
@@ -328,6 +334,7 @@ fn create_new_tab_with_cell_size(
         arrow_fonts,
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -5986,6 +5993,49 @@ pub fn cannot_resize_down_when_pane_below_is_at_minimum_height() {
     );
 }
 
+#[test]
+pub fn cannot_resize_down_when_pane_below_is_at_its_custom_minimum_height() {
+    // ┌───────────┐                  ┌───────────┐
+    // │███████████│                  │███████████│
+    // ├───────────┤ ==resize=down==> ├───────────┤
+    // │           │                  │           │
+    // └───────────┘                  └───────────┘
+    // █ == focused pane
+
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.horizontal_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.set_pane_min_size(PaneId::Terminal(2), 10, 0);
+    tab.move_focus_up(1).unwrap();
+    tab_resize_down(&mut tab, 1);
+
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        10,
+        "pane 1 height stayed the same"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        10,
+        "pane 2 height stayed the same"
+    );
+}
+
 #[test]
 pub fn cannot_resize_down_when_pane_has_fixed_rows() {
     // ┌───────────┐                  ┌───────────┐
@@ -14436,3 +14486,29 @@ fn correctly_resize_frameless_panes_on_pane_close() {
     let content_size = (pane.get_content_columns(), pane.get_content_rows());
     assert_eq!(content_size, (cols, rows));
 }
+
+#[test]
+fn focus_pane_under_mouse() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(2)));
+
+    let focus_changed = tab
+        .focus_pane_under_mouse(&Position::new(5, 10), 1)
+        .unwrap();
+    assert!(focus_changed, "hovering over another pane focuses it");
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(1)));
+
+    let focus_changed = tab
+        .focus_pane_under_mouse(&Position::new(6, 12), 1)
+        .unwrap();
+    assert!(
+        !focus_changed,
+        "hovering over the focused pane does nothing"
+    );
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(1)));
+}
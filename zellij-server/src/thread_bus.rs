@@ -5,9 +5,68 @@ use crate::{
     pty::PtyInstruction, pty_writer::PtyWriteInstruction, screen::ScreenInstruction,
     ServerInstruction,
 };
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use zellij_utils::errors::prelude::*;
+use zellij_utils::lazy_static::lazy_static;
 use zellij_utils::{channels, channels::SenderWithContext, errors::ErrorContext};
 
+lazy_static! {
+    static ref ACTIVITY_EPOCH: Instant = Instant::now();
+    // when each thread last received a message (in milliseconds since ACTIVITY_EPOCH), a thread
+    // that stops receiving them while messages wait in its channel is likely stuck
+    //
+    // the lock is only taken when a thread first registers and when the activity is reported, each
+    // thread then updates its own atomic so that receiving messages never contends on it
+    static ref THREAD_LAST_ACTIVITY: Mutex<BTreeMap<String, Arc<AtomicU64>>> =
+        Mutex::new(BTreeMap::new());
+}
+
+thread_local! {
+    static LAST_ACTIVITY: Option<Arc<AtomicU64>> = {
+        std::thread::current().name().map(|thread_name| {
+            let last_activity = Arc::new(AtomicU64::new(millis_since_activity_epoch()));
+            THREAD_LAST_ACTIVITY
+                .lock()
+                .unwrap()
+                .insert(thread_name.to_owned(), last_activity.clone());
+            last_activity
+        })
+    };
+}
+
+fn millis_since_activity_epoch() -> u64 {
+    ACTIVITY_EPOCH.elapsed().as_millis() as u64
+}
+
+fn record_thread_activity() {
+    LAST_ACTIVITY.with(|last_activity| {
+        if let Some(last_activity) = last_activity {
+            last_activity.store(millis_since_activity_epoch(), Ordering::Relaxed);
+        }
+    });
+}
+
+/// (thread name, time since it last received a message) for each thread receiving through a
+/// [`Bus`]
+pub fn thread_last_activity() -> Vec<(String, Duration)> {
+    let now = millis_since_activity_epoch();
+    THREAD_LAST_ACTIVITY
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(thread_name, last_activity)| {
+            let last_activity = last_activity.load(Ordering::Relaxed);
+            (
+                thread_name.clone(),
+                Duration::from_millis(now.saturating_sub(last_activity)),
+            )
+        })
+        .collect()
+}
+
 /// A container for senders to the different threads in zellij on the server side
 #[derive(Default, Clone)]
 pub struct ThreadSenders {
@@ -23,6 +82,30 @@ pub struct ThreadSenders {
 }
 
 impl ThreadSenders {
+    /// (channel name, messages waiting, capacity) for each of the channels we can send to
+    pub fn channel_fill_levels(&self) -> Vec<(&'static str, usize, Option<usize>)> {
+        let mut fill_levels = vec![];
+        if let Some(sender) = &self.to_screen {
+            fill_levels.push(("screen", sender.len(), sender.capacity()));
+        }
+        if let Some(sender) = &self.to_pty {
+            fill_levels.push(("pty", sender.len(), sender.capacity()));
+        }
+        if let Some(sender) = &self.to_plugin {
+            fill_levels.push(("plugin", sender.len(), sender.capacity()));
+        }
+        if let Some(sender) = &self.to_server {
+            fill_levels.push(("server", sender.len(), sender.capacity()));
+        }
+        if let Some(sender) = &self.to_pty_writer {
+            fill_levels.push(("pty_writer", sender.len(), sender.capacity()));
+        }
+        if let Some(sender) = &self.to_background_jobs {
+            fill_levels.push(("background_jobs", sender.len(), sender.capacity()));
+        }
+        fill_levels
+    }
+
     pub fn send_to_screen(&self, instruction: ScreenInstruction) -> Result<()> {
         if self.should_silently_fail {
             let _ = self
@@ -215,6 +298,17 @@ impl<T> Bus<T> {
         });
         let oper = selector.select();
         let idx = oper.index();
-        oper.recv(&self.receivers[idx])
+        let received = oper.recv(&self.receivers[idx]);
+        record_thread_activity();
+        received
+    }
+
+    /// Receive a message that is already waiting in one of the channels, without blocking
+    pub fn try_recv(&self) -> Option<(T, ErrorContext)> {
+        let received = self.receivers.iter().find_map(|r| r.try_recv().ok());
+        if received.is_some() {
+            record_thread_activity();
+        }
+        received
     }
 }
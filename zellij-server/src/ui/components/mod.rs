@@ -1,5 +1,6 @@
 mod component_coordinates;
 mod nested_list;
+mod progress_bar;
 mod ribbon;
 mod table;
 mod text;
@@ -10,6 +11,7 @@ use zellij_utils::{data::Style, lazy_static::lazy_static, regex::Regex, vte};
 
 use component_coordinates::{is_too_high, is_too_wide, Coordinates};
 use nested_list::{nested_list, parse_nested_list_items};
+use progress_bar::{progress_bar, ProgressBarStyle};
 use ribbon::{emphasis_variants_for_ribbon, emphasis_variants_for_selected_ribbon, ribbon};
 use table::table;
 use text::{parse_text, parse_text_params, stringify_text, text, Text};
@@ -112,6 +114,22 @@ impl<'a> UiComponentParser<'a> {
                 nested_list(nested_list_items, &self.style, component_coordinates);
             parse_vte_bytes!(self, encoded_nested_list);
             Ok(())
+        } else if component_name == &"progress_bar" {
+            let bar_style = params_iter
+                .next()
+                .and_then(|s| ProgressBarStyle::from_str(s))
+                .with_context(|| format!("progress_bar must have a style"))?;
+            let percent = parse_next_param!(params_iter.next(), f64, "progress_bar", "percent");
+            let label = parse_text_params(params_iter).into_iter().next();
+            let encoded_progress_bar = progress_bar(
+                bar_style,
+                percent,
+                label,
+                &self.style,
+                component_coordinates,
+            );
+            parse_vte_bytes!(self, encoded_progress_bar);
+            Ok(())
         } else if component_name == &"text" {
             let stringified_params = parse_text_params(params_iter)
                 .into_iter()
@@ -155,6 +173,19 @@ impl<'a> UiComponentParser<'a> {
     }
 }
 
+fn parse_url(stringified: &mut String) -> Option<String> {
+    if stringified.chars().next() != Some('u') {
+        return None;
+    }
+    let url_end = stringified.find('#')?;
+    let url_bytes = stringified.drain(0..=url_end).collect::<String>();
+    let url_bytes = url_bytes[1..url_bytes.len() - 1]
+        .split(',')
+        .filter_map(|s| s.parse::<u8>().ok())
+        .collect::<Vec<u8>>();
+    Some(String::from_utf8_lossy(&url_bytes).to_string())
+}
+
 fn parse_selected(stringified: &mut String) -> bool {
     let mut selected = false;
     if stringified.chars().next() == Some('x') {
@@ -1,6 +1,6 @@
 use super::{
-    is_too_high, parse_indices, parse_opaque, parse_selected, parse_text, stringify_text,
-    Coordinates, Text,
+    is_too_high, parse_indices, parse_opaque, parse_selected, parse_text, parse_url,
+    stringify_text, Coordinates, Text,
 };
 use crate::panes::terminal_character::{AnsiCode, RESET_STYLES};
 use zellij_utils::data::Style;
@@ -95,6 +95,7 @@ pub fn parse_nested_list_items<'a>(
     params_iter
         .flat_map(|mut stringified| {
             let indentation_level = parse_indentation_level(&mut stringified);
+            let url = parse_url(&mut stringified);
             let selected = parse_selected(&mut stringified);
             let opaque = parse_opaque(&mut stringified);
             let indices = parse_indices(&mut stringified);
@@ -104,6 +105,7 @@ pub fn parse_nested_list_items<'a>(
                 opaque,
                 selected,
                 indices,
+                url,
             };
             Ok::<NestedListItem, String>(NestedListItem {
                 text,
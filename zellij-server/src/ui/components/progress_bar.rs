@@ -0,0 +1,137 @@
+use super::{text::stringify_text, Coordinates, Text};
+use crate::panes::terminal_character::{AnsiCode, CharacterStyles, RESET_STYLES};
+use std::time::{SystemTime, UNIX_EPOCH};
+use unicode_width::UnicodeWidthStr;
+use zellij_utils::data::{PaletteColor, Style};
+
+static SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+static SPINNER_FRAME_DURATION_MS: u128 = 100;
+static DEFAULT_BAR_WIDTH: usize = 20;
+static PERCENTAGE_WIDTH: usize = 5; // eg. " 100%"
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressBarStyle {
+    Filled,
+    Gradient,
+    Spinner,
+}
+
+impl ProgressBarStyle {
+    pub fn from_str(stringified: &str) -> Option<Self> {
+        match stringified {
+            "filled" => Some(ProgressBarStyle::Filled),
+            "gradient" => Some(ProgressBarStyle::Gradient),
+            "spinner" => Some(ProgressBarStyle::Spinner),
+            _ => None,
+        }
+    }
+}
+
+pub fn progress_bar(
+    bar_style: ProgressBarStyle,
+    percent: f64,
+    label: Option<Text>,
+    style: &Style,
+    component_coordinates: Option<Coordinates>,
+) -> Vec<u8> {
+    let text_style = RESET_STYLES
+        .bold(Some(AnsiCode::On))
+        .foreground(Some(style.colors.white.into()));
+    let total_width = component_coordinates.as_ref().and_then(|c| c.width);
+    let mut stringified = component_coordinates
+        .as_ref()
+        .map(|c| c.to_string())
+        .unwrap_or_else(String::new);
+    stringified.push_str(&format!("{}", RESET_STYLES));
+
+    // a NaN percentage means the progress is unknown, so we can only show that we're busy
+    let bar_style = if percent.is_nan() {
+        ProgressBarStyle::Spinner
+    } else {
+        bar_style
+    };
+    let percent = percent.clamp(0.0, 100.0);
+
+    let mut used_width = 0;
+    if bar_style == ProgressBarStyle::Spinner {
+        let spinner_style = RESET_STYLES.foreground(Some(style.colors.green.into()));
+        stringified.push_str(&format!("{}{} ", spinner_style, current_spinner_frame()));
+        used_width += 2;
+    }
+    if let Some(label) = label.filter(|l| !l.text.is_empty()) {
+        // stringify_text pads the text to the width it is given, so we give it no more than it needs
+        let label_coordinates = Some(Coordinates {
+            x: 0,
+            y: 0,
+            width: Some(match total_width {
+                Some(total_width) => label
+                    .text
+                    .width()
+                    .min(total_width.saturating_sub(used_width)),
+                None => label.text.width(),
+            }),
+            height: None,
+        });
+        let (label, label_width) =
+            stringify_text(&label, None, &label_coordinates, style, text_style, false);
+        stringified.push_str(&format!("{}{}{} ", text_style, label, RESET_STYLES));
+        used_width += label_width + 1;
+    }
+    match bar_style {
+        ProgressBarStyle::Spinner => {
+            if !percent.is_nan() {
+                stringified.push_str(&format!("{}{:.0}%", text_style, percent));
+            }
+        },
+        ProgressBarStyle::Filled | ProgressBarStyle::Gradient => {
+            let bar_width = total_width
+                .map(|total_width| total_width.saturating_sub(used_width + PERCENTAGE_WIDTH))
+                .unwrap_or(DEFAULT_BAR_WIDTH);
+            let filled_width =
+                ((bar_width as f64 * percent / 100.0).round() as usize).min(bar_width);
+            let mut previous_cell_color = None;
+            for i in 0..bar_width {
+                let cell_color = if i >= filled_width {
+                    style.colors.black
+                } else if bar_style == ProgressBarStyle::Gradient {
+                    gradient_color(i, bar_width, style)
+                } else {
+                    style.colors.green
+                };
+                if previous_cell_color != Some(cell_color) {
+                    stringified.push_str(&format!("{}", cell_style(cell_color)));
+                    previous_cell_color = Some(cell_color);
+                }
+                stringified.push(' ');
+            }
+            stringified.push_str(&format!("{}{}{:>4.0}%", RESET_STYLES, text_style, percent));
+        },
+    }
+    stringified.push_str(&format!("{}", RESET_STYLES));
+    stringified.as_bytes().to_vec()
+}
+
+fn cell_style(background: PaletteColor) -> CharacterStyles {
+    RESET_STYLES.background(Some(background.into()))
+}
+
+// the filled part of the bar goes from red through orange and yellow to green along its width
+fn gradient_color(cell_index: usize, bar_width: usize, style: &Style) -> PaletteColor {
+    let gradient = [
+        style.colors.red,
+        style.colors.orange,
+        style.colors.yellow,
+        style.colors.green,
+    ];
+    let position = cell_index * gradient.len() / bar_width.max(1);
+    gradient[position.min(gradient.len() - 1)]
+}
+
+// plugins re-render periodically while they're busy, so the spinner animates with the clock
+fn current_spinner_frame() -> &'static str {
+    let elapsed_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    SPINNER_FRAMES[(elapsed_ms / SPINNER_FRAME_DURATION_MS) as usize % SPINNER_FRAMES.len()]
+}
@@ -1,6 +1,6 @@
 use super::{
     emphasis_variants_for_ribbon, emphasis_variants_for_selected_ribbon, is_too_wide,
-    parse_indices, parse_opaque, parse_selected, Coordinates,
+    parse_indices, parse_opaque, parse_selected, parse_url, Coordinates,
 };
 use crate::panes::terminal_character::{AnsiCode, CharacterStyles, RESET_STYLES};
 use zellij_utils::{
@@ -47,6 +47,9 @@ pub fn stringify_text(
 ) -> (String, usize) {
     let mut text_width = 0;
     let mut stringified = String::new();
+    if let Some(url) = &text.url {
+        stringified.push_str(&format!("\u{1b}]8;;{}\u{7}", url));
+    }
     for (i, character) in text.text.chars().enumerate() {
         let character_width = character.width().unwrap_or(0);
         if is_too_wide(
@@ -65,6 +68,9 @@ pub fn stringify_text(
             stringified.push(character);
         }
     }
+    if text.url.is_some() {
+        stringified.push_str("\u{1b}]8;;\u{7}");
+    }
     let coordinates_width = coordinates.as_ref().and_then(|c| c.width);
     match (coordinates_width, text_style.background) {
         (Some(coordinates_width), Some(_background_style)) => {
@@ -120,6 +126,7 @@ pub fn emphasis_variants(style: &Style) -> [PaletteColor; 4] {
 pub fn parse_text_params<'a>(params_iter: impl Iterator<Item = &'a mut String>) -> Vec<Text> {
     params_iter
         .flat_map(|mut stringified| {
+            let url = parse_url(&mut stringified);
             let selected = parse_selected(&mut stringified);
             let opaque = parse_opaque(&mut stringified);
             let indices = parse_indices(&mut stringified);
@@ -129,6 +136,7 @@ pub fn parse_text_params<'a>(params_iter: impl Iterator<Item = &'a mut String>)
                 opaque,
                 selected,
                 indices,
+                url,
             })
         })
         .collect::<Vec<Text>>()
@@ -140,6 +148,7 @@ pub struct Text {
     pub selected: bool,
     pub opaque: bool,
     pub indices: Vec<Vec<usize>>,
+    pub url: Option<String>,
 }
 
 impl Text {
@@ -82,6 +82,7 @@ pub struct PaneFrame {
     should_draw_pane_frames: bool,
     is_pinned: bool,
     is_floating: bool,
+    synchronized_group: Option<String>,
 }
 
 impl PaneFrame {
@@ -108,12 +109,17 @@ impl PaneFrame {
             should_draw_pane_frames: frame_params.should_draw_pane_frames,
             is_pinned: false,
             is_floating: frame_params.pane_is_floating,
+            synchronized_group: None,
         }
     }
     pub fn is_pinned(mut self, is_pinned: bool) -> Self {
         self.is_pinned = is_pinned;
         self
     }
+    pub fn synchronized_group(mut self, synchronized_group: Option<String>) -> Self {
+        self.synchronized_group = synchronized_group;
+        self
+    }
     pub fn add_exit_status(&mut self, exit_status: Option<i32>) {
         self.exit_status = match exit_status {
             Some(exit_status) => Some(ExitStatus::Code(exit_status)),
@@ -159,6 +165,32 @@ impl PaneFrame {
         max_length: usize,
     ) -> Option<(Vec<TerminalCharacter>, usize)> {
         // string and length because of color
+        let sync_indication = self.render_sync_indication(max_length);
+        let space_for_other_indications = sync_indication
+            .as_ref()
+            .map(|(_, length)| max_length.saturating_sub(*length + 1))
+            .unwrap_or(max_length);
+        let other_indications = self.render_scroll_and_pin_indication(space_for_other_indications);
+        match (other_indications, sync_indication) {
+            (
+                Some((mut other_indications, other_indications_len)),
+                Some((mut sync_indication, sync_indication_len)),
+            ) => {
+                let mut characters: Vec<_> = other_indications.drain(..).collect();
+                let mut separator = foreground_color(&format!("|"), self.color);
+                characters.append(&mut separator);
+                characters.append(&mut sync_indication);
+                Some((characters, other_indications_len + sync_indication_len + 1))
+            },
+            (Some(other_indications), None) => Some(other_indications),
+            (None, Some(sync_indication)) => Some(sync_indication),
+            _ => None,
+        }
+    }
+    fn render_scroll_and_pin_indication(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
         let has_scroll = self.scroll_position.0 > 0 || self.scroll_position.1 > 0;
         if has_scroll {
             let pin_indication = if self.is_floating {
@@ -237,6 +269,26 @@ impl PaneFrame {
             None
         }
     }
+    fn render_sync_indication(&self, max_length: usize) -> Option<(Vec<TerminalCharacter>, usize)> {
+        let group_name = self.synchronized_group.as_ref()?;
+        let full_indication = format!(" SYNC: {} ", group_name);
+        let short_indication = " SYNC ";
+        let full_indication_len = full_indication.width();
+        let short_indication_len = short_indication.width();
+        if full_indication_len <= max_length {
+            Some((
+                foreground_color(&full_indication, self.color),
+                full_indication_len,
+            ))
+        } else if short_indication_len <= max_length {
+            Some((
+                foreground_color(short_indication, self.color),
+                short_indication_len,
+            ))
+        } else {
+            None
+        }
+    }
     fn render_my_focus(&self, max_length: usize) -> Option<(Vec<TerminalCharacter>, usize)> {
         let mut left_separator = foreground_color(boundary_type::VERTICAL_LEFT, self.color);
         let mut right_separator = foreground_color(boundary_type::VERTICAL_RIGHT, self.color);
@@ -0,0 +1,95 @@
+use super::*;
+use std::time::{Duration, UNIX_EPOCH};
+use zellij_utils::data::Direction;
+
+fn entry(action: Action) -> AuditLogEntry {
+    AuditLogEntry {
+        timestamp: UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+        client_id: 2,
+        action,
+    }
+}
+
+#[test]
+fn audit_log_entry_as_json_line() {
+    let line = entry(Action::MoveFocus(Direction::Left)).to_json_line("my-session");
+    let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert!(line.ends_with('\n'));
+    assert_eq!(json["timestamp"], "2023-11-14T22:13:20.123Z");
+    assert_eq!(json["client_id"], 2);
+    assert_eq!(json["session_name"], "my-session");
+    assert_eq!(json["action_type"], "MoveFocus");
+    assert_eq!(json["action_details"], "MoveFocus(Left)");
+}
+
+#[test]
+fn audit_log_does_not_record_what_is_written_to_panes() {
+    let line = entry(Action::WriteChars("hunter2".to_owned())).to_json_line("my-session");
+    let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(json["action_type"], "WriteChars");
+    assert_eq!(json["action_details"], "7 characters");
+    let line = entry(Action::Write(None, b"hunter2".to_vec(), false)).to_json_line("my-session");
+    assert!(!line.contains("104")); // 'h'
+}
+
+#[test]
+fn audit_log_is_rotated_past_its_max_size() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("audit.ndjson");
+    let mut writer = AuditLogWriter::new(path.clone(), Some(1));
+    let line = format!("{}\n", "a".repeat(600 * 1024));
+    writer.write(&line).unwrap();
+    writer.write(&line).unwrap();
+    writer.write("last\n").unwrap();
+    assert_eq!(fs::read_to_string(rotated_path(&path)).unwrap(), line);
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        format!("{}last\n", line)
+    );
+}
+
+#[test]
+fn audit_log_writes_entries_sent_before_it_is_dropped() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("logs").join("audit.ndjson");
+    let audit_log = AuditLog::start(path.clone(), None, "my-session".to_owned());
+    audit_log.log_action(1, &Action::NewTab(None, vec![], None, None, None, true));
+    audit_log.log_action(1, &Action::Detach);
+    drop(audit_log);
+    let contents = fs::read_to_string(&path).unwrap();
+    let action_types: Vec<String> = contents
+        .lines()
+        .map(|line| {
+            let json: serde_json::Value = serde_json::from_str(line).unwrap();
+            json["action_type"].as_str().unwrap().to_owned()
+        })
+        .collect();
+    assert_eq!(action_types, vec!["NewTab", "Detach"]);
+}
+
+#[test]
+fn audit_log_does_not_record_what_is_piped_to_plugins() {
+    let action = Action::CliPipe {
+        pipe_id: "pipe-id".to_owned(),
+        name: Some("secrets".to_owned()),
+        payload: Some("hunter2".to_owned()),
+        args: None,
+        plugin: None,
+        configuration: None,
+        launch_new: false,
+        skip_cache: false,
+        floating: None,
+        in_place: None,
+        cwd: None,
+        pane_title: None,
+        plugin_id: None,
+        is_last: true,
+    };
+    let line = entry(action).to_json_line("my-session");
+    let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(json["action_type"], "CliPipe");
+    let action_details = json["action_details"].as_str().unwrap();
+    assert!(!action_details.contains("hunter2"));
+    assert!(action_details.contains("payload: Some(\"<7 bytes>\")"));
+    assert!(action_details.contains("name: Some(\"secrets\")"));
+}
@@ -0,0 +1,167 @@
+use super::*;
+
+#[test]
+fn pipe_text_to_command_passes_text_as_stdin() {
+    let (exit_code, stdout, stderr) = pipe_text_to_command(
+        "first\nsecond match\nthird match\n",
+        &PathBuf::from("grep"),
+        &["match".to_owned()],
+        &std::env::temp_dir(),
+        Duration::from_secs(5),
+    );
+    assert_eq!(exit_code, 0);
+    assert_eq!(stdout, "second match\nthird match\n");
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn pipe_text_to_command_reports_exit_code_and_stderr() {
+    let (exit_code, stdout, stderr) = pipe_text_to_command(
+        "",
+        &PathBuf::from("sh"),
+        &["-c".to_owned(), "echo oops >&2; exit 3".to_owned()],
+        &std::env::temp_dir(),
+        Duration::from_secs(5),
+    );
+    assert_eq!(exit_code, 3);
+    assert_eq!(stdout, "");
+    assert_eq!(stderr, "oops\n");
+}
+
+#[test]
+fn pipe_text_to_command_kills_commands_that_time_out() {
+    let started = Instant::now();
+    let (exit_code, _stdout, _stderr) = pipe_text_to_command(
+        "",
+        &PathBuf::from("sleep"),
+        &["10".to_owned()],
+        &std::env::temp_dir(),
+        Duration::from_millis(100),
+    );
+    assert_eq!(exit_code, COMMAND_TIMED_OUT_EXIT_CODE);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn pipe_text_to_command_kills_what_commands_that_time_out_started() {
+    let started = Instant::now();
+    let (exit_code, _stdout, _stderr) = pipe_text_to_command(
+        "",
+        &PathBuf::from("sh"),
+        // the sleep holds on to the output of the shell until it is killed along with it
+        &["-c".to_owned(), "sleep 10; sleep 10".to_owned()],
+        &std::env::temp_dir(),
+        Duration::from_millis(100),
+    );
+    assert_eq!(exit_code, COMMAND_TIMED_OUT_EXIT_CODE);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn pipe_text_to_command_does_not_wait_for_output_past_the_deadline() {
+    let started = Instant::now();
+    let (exit_code, _stdout, _stderr) = pipe_text_to_command(
+        "",
+        &PathBuf::from("sh"),
+        // the shell exits right away, leaving the sleep holding on to its output
+        &["-c".to_owned(), "sleep 10 &".to_owned()],
+        &std::env::temp_dir(),
+        Duration::from_millis(100),
+    );
+    assert_eq!(exit_code, 0);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn pipe_text_to_command_reports_commands_that_cannot_start() {
+    let (exit_code, _stdout, stderr) = pipe_text_to_command(
+        "",
+        &PathBuf::from("/no/such/command"),
+        &[],
+        &std::env::temp_dir(),
+        Duration::from_secs(5),
+    );
+    assert_eq!(exit_code, COMMAND_FAILED_TO_START_EXIT_CODE);
+    assert!(!stderr.is_empty());
+}
+
+#[test]
+fn session_hooks_that_hang_are_killed_after_the_timeout() {
+    let started = Instant::now();
+    let hooks = vec![RunCommand {
+        // the sleep spawned by the shell is killed along with it
+        command: PathBuf::from("sleep 10; sleep 10"),
+        ..Default::default()
+    }];
+    run_session_hooks(&hooks, Some(Duration::from_millis(100)));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+fn queue_jobs(jobs: Vec<BackgroundJob>) -> BinaryHeap<QueuedJob> {
+    jobs.into_iter()
+        .enumerate()
+        .map(|(sequence_number, job)| QueuedJob {
+            job,
+            err_ctx: ErrorContext::new(),
+            sequence_number: sequence_number as u64,
+        })
+        .collect()
+}
+
+fn pop_order(mut queued_jobs: BinaryHeap<QueuedJob>) -> Vec<BackgroundJobContext> {
+    let mut pop_order = vec![];
+    while let Some(queued_job) = queued_jobs.pop() {
+        pop_order.push((&queued_job.job).into());
+    }
+    pop_order
+}
+
+#[test]
+fn urgent_jobs_are_picked_up_before_earlier_ones() {
+    let queued_jobs = queue_jobs(vec![
+        BackgroundJob::ReadAllSessionInfosOnMachine,
+        BackgroundJob::RunSessionHooks(vec![], 1, None),
+        BackgroundJob::AnimatePluginLoading(1),
+    ]);
+    assert_eq!(
+        pop_order(queued_jobs),
+        vec![
+            BackgroundJobContext::AnimatePluginLoading,
+            BackgroundJobContext::RunSessionHooks,
+            BackgroundJobContext::ReadAllSessionInfosOnMachine,
+        ]
+    );
+}
+
+#[test]
+fn jobs_of_the_same_priority_are_picked_up_in_order() {
+    let queued_jobs = queue_jobs(vec![
+        BackgroundJob::AnimatePluginLoading(1),
+        BackgroundJob::StopPluginLoadingAnimation(1),
+        BackgroundJob::ReportServerStatus(1),
+    ]);
+    assert_eq!(
+        pop_order(queued_jobs),
+        vec![
+            BackgroundJobContext::AnimatePluginLoading,
+            BackgroundJobContext::StopPluginLoadingAnimation,
+            BackgroundJobContext::ReportServerStatus,
+        ]
+    );
+}
+
+#[test]
+fn exit_is_not_starved_by_a_steady_stream_of_jobs() {
+    let mut jobs = vec![BackgroundJob::Exit];
+    for _ in 0..1000 {
+        jobs.push(BackgroundJob::AnimatePluginLoading(1));
+    }
+    let exit_position = pop_order(queue_jobs(jobs))
+        .iter()
+        .position(|job| *job == BackgroundJobContext::Exit)
+        .unwrap();
+    assert!(
+        exit_position <= JobPriority::Low.max_overtaking_jobs() as usize,
+        "only a limited amount of later jobs is picked up before exiting"
+    );
+}
@@ -0,0 +1,55 @@
+use super::*;
+use zellij_utils::data::BareKey;
+
+fn key(character: char) -> KeyWithModifier {
+    KeyWithModifier::new(BareKey::Char(character)).with_ctrl_modifier()
+}
+
+fn piped_to(action: Option<Action>) -> Option<(u32, String)> {
+    match action {
+        Some(Action::KeybindPipe {
+            plugin_id: Some(plugin_id),
+            payload: Some(action_id),
+            ..
+        }) => Some((plugin_id, action_id)),
+        _ => None,
+    }
+}
+
+#[test]
+fn registered_keys_are_piped_to_their_plugin() {
+    let mut global_hotkeys = GlobalHotkeys::default();
+    global_hotkeys.register(key('k'), 1, "open_palette".to_owned());
+    assert_eq!(
+        piped_to(global_hotkeys.action_for_key(&key('k'))),
+        Some((1, "open_palette".to_owned()))
+    );
+    assert!(global_hotkeys.action_for_key(&key('j')).is_none());
+}
+
+#[test]
+fn the_first_plugin_to_register_a_key_gets_it() {
+    let mut global_hotkeys = GlobalHotkeys::default();
+    global_hotkeys.register(key('k'), 1, "first".to_owned());
+    global_hotkeys.register(key('k'), 2, "second".to_owned());
+    global_hotkeys.register(key('k'), 1, "first_again".to_owned());
+    assert_eq!(
+        piped_to(global_hotkeys.action_for_key(&key('k'))),
+        Some((1, "first_again".to_owned())),
+        "re-registering a key changes its action but keeps its place in line"
+    );
+}
+
+#[test]
+fn keys_of_unregistered_plugins_go_to_the_next_plugin() {
+    let mut global_hotkeys = GlobalHotkeys::default();
+    global_hotkeys.register(key('k'), 1, "first".to_owned());
+    global_hotkeys.register(key('k'), 2, "second".to_owned());
+    global_hotkeys.unregister_plugin(1);
+    assert_eq!(
+        piped_to(global_hotkeys.action_for_key(&key('k'))),
+        Some((2, "second".to_owned()))
+    );
+    global_hotkeys.unregister_plugin(2);
+    assert!(global_hotkeys.action_for_key(&key('k')).is_none());
+}
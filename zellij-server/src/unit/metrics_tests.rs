@@ -0,0 +1,80 @@
+use super::*;
+
+fn labels(labels: &[(&str, &str)]) -> BTreeMap<String, String> {
+    labels
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn statsd_line_with_labels() {
+    let line = statsd_line(
+        "plugin_load_time_ms",
+        &labels(&[("plugin", "file:/tmp/a|b.wasm"), ("session", "dev")]),
+        12.5,
+    );
+    assert_eq!(
+        line,
+        "plugin_load_time_ms:12.5|g|#plugin:file_/tmp/a_b.wasm,session:dev"
+    );
+}
+
+#[test]
+fn prometheus_line_with_labels() {
+    let line = prometheus_line(
+        "render-latency.ms",
+        &labels(&[("plugin", "my \"plugin\""), ("session", "dev")]),
+        3.0,
+    );
+    assert_eq!(
+        line,
+        "render_latency_ms{plugin=\"my \\\"plugin\\\"\",session=\"dev\"} 3\n"
+    );
+}
+
+#[test]
+fn json_flush_only_includes_series_updated_since_last_flush() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let metrics_file = temp_dir.path().join("metrics.json");
+    let metrics_config = MetricsConfig {
+        format: MetricsFormat::Json,
+        endpoint: Some(metrics_file.display().to_string()),
+        flush_interval_seconds: None,
+    };
+    let mut metrics_registry = MetricsRegistry::default();
+    metrics_registry.record(Metric::new("first", 1.0, BTreeMap::new()));
+    metrics_registry.record(Metric::new("second", 2.0, BTreeMap::new()));
+    metrics_registry.flush(&metrics_config, "dev").unwrap();
+    metrics_registry.record(Metric::new("second", 3.0, BTreeMap::new()));
+    metrics_registry.flush(&metrics_config, "dev").unwrap();
+
+    let contents = fs::read_to_string(&metrics_file).unwrap();
+    let lines: Vec<serde_json::Value> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3, "second flush only wrote the updated series");
+    assert_eq!(lines[2]["name"], "second");
+    assert_eq!(lines[2]["value"], 3.0);
+    assert_eq!(lines[2]["labels"]["session"], "dev");
+}
+
+#[test]
+fn series_beyond_the_limit_are_dropped() {
+    let mut metrics_registry = MetricsRegistry::default();
+    for i in 0..MAX_METRIC_SERIES + 10 {
+        metrics_registry.record(Metric::new(
+            "requests",
+            i as f64,
+            labels(&[("id", &i.to_string())]),
+        ));
+    }
+    assert_eq!(metrics_registry.series.len(), MAX_METRIC_SERIES);
+    // series that are already known are still updated
+    metrics_registry.record(Metric::new("requests", 42.0, labels(&[("id", "0")])));
+    assert_eq!(
+        metrics_registry.series[&("requests".to_owned(), labels(&[("id", "0")]))].value,
+        42.0
+    );
+}
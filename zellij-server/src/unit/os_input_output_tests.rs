@@ -12,7 +12,6 @@ impl TestTerminal {
         TestTerminal { openpty }
     }
 
-    #[allow(dead_code)]
     pub fn master(&self) -> RawFd {
         self.openpty.master
     }
@@ -49,3 +48,133 @@ fn get_cwd() {
         pid
     );
 }
+
+fn test_server() -> ServerOsInputOutput {
+    ServerOsInputOutput {
+        orig_termios: Arc::new(Mutex::new(None)),
+        client_senders: Arc::default(),
+        terminal_id_to_raw_fd: Arc::default(),
+        cached_resizes: Arc::default(),
+    }
+}
+
+fn is_stopped(pid: Pid) -> bool {
+    // the state is the first field after the parenthesized process name
+    std::fs::read_to_string(format!("/proc/{}/stat", pid))
+        .ok()
+        .and_then(|stat| {
+            stat.rsplit_once(')')
+                .and_then(|(_, fields)| fields.trim_start().chars().next())
+        })
+        == Some('T')
+}
+
+fn wait_until(condition: impl Fn() -> bool) -> bool {
+    for _ in 0..100 {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    false
+}
+
+#[test]
+fn process_tree_lists_parents_before_their_children() {
+    let mut shell = Command::new("sh")
+        .args(["-c", "sleep 30; true"])
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let shell_pid = Pid::from_raw(shell.id() as i32);
+    assert!(wait_until(|| process_tree(shell_pid).len() == 2));
+    let pids_in_tree = process_tree(shell_pid);
+    let _ = kill(pids_in_tree[1], Some(Signal::SIGKILL));
+    let _ = shell.kill();
+    let _ = shell.wait();
+    assert_eq!(pids_in_tree[0], shell_pid);
+}
+
+#[test]
+fn suspending_and_resuming_a_process_tree() {
+    let server = test_server();
+    let mut shell = Command::new("sh")
+        .args(["-c", "sleep 30; true"])
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let shell_pid = Pid::from_raw(shell.id() as i32);
+    assert!(wait_until(|| process_tree(shell_pid).len() == 2));
+    let sleep_pid = process_tree(shell_pid)[1];
+
+    server.set_process_tree_stopped(shell_pid, true).unwrap();
+    let stopped = wait_until(|| is_stopped(shell_pid) && is_stopped(sleep_pid));
+    server.set_process_tree_stopped(shell_pid, false).unwrap();
+    let resumed = wait_until(|| !is_stopped(shell_pid) && !is_stopped(sleep_pid));
+
+    let _ = kill(sleep_pid, Some(Signal::SIGKILL));
+    let _ = shell.kill();
+    let _ = shell.wait();
+    assert!(stopped, "the shell and its child are stopped");
+    assert!(resumed, "the shell and its child are continued");
+}
+
+#[test]
+fn resuming_an_interactive_shell_keeps_its_foreground_job_in_the_foreground() {
+    use std::os::unix::io::FromRawFd;
+    use std::process::Stdio;
+
+    let server = test_server();
+    let test_terminal = TestTerminal::new();
+    let slave = test_terminal.slave();
+    let stdio = || unsafe { Stdio::from_raw_fd(unistd::dup(slave).unwrap()) };
+    let mut shell = Command::new("bash");
+    shell
+        .args(["--norc", "--noprofile", "-i"])
+        .stdin(stdio())
+        .stdout(stdio())
+        .stderr(stdio());
+    unsafe {
+        shell.pre_exec(|| {
+            // make the test terminal the controlling terminal of the shell, like a pane's
+            unistd::setsid().map_err(|e| std::io::Error::from_raw_os_error(e as i32))?;
+            if libc::ioctl(0, libc::TIOCSCTTY, 0) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let mut shell = shell.spawn().unwrap();
+    let shell_pid = Pid::from_raw(shell.id() as i32);
+    let foreground_job = || {
+        process_tree(shell_pid)
+            .get(1)
+            .copied()
+            .filter(|job_pid| unistd::tcgetpgrp(test_terminal.master()) == Ok(*job_pid))
+    };
+    unistd::write(test_terminal.master(), b"sleep 30\n").unwrap();
+    assert!(
+        wait_until(|| foreground_job().is_some()),
+        "the shell runs sleep as its foreground job"
+    );
+    let sleep_pid = foreground_job().unwrap();
+
+    server.set_process_tree_stopped(shell_pid, true).unwrap();
+    let stopped = wait_until(|| is_stopped(shell_pid) && is_stopped(sleep_pid));
+    server.set_process_tree_stopped(shell_pid, false).unwrap();
+    // give the shell a chance to (wrongly) notice that its job was stopped
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let job_after_resuming = foreground_job();
+    let job_is_running = !is_stopped(sleep_pid);
+
+    let _ = kill(sleep_pid, Some(Signal::SIGKILL));
+    let _ = shell.kill();
+    let _ = shell.wait();
+    assert!(stopped, "the shell and its job are stopped");
+    assert_eq!(
+        job_after_resuming,
+        Some(sleep_pid),
+        "sleep is still the foreground job"
+    );
+    assert!(job_is_running, "sleep is running");
+}
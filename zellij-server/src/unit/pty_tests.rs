@@ -0,0 +1,99 @@
+use super::*;
+
+#[test]
+fn shells_are_duplicated_as_shells_in_the_current_cwd() {
+    let current_cwd = Some(PathBuf::from("/tmp/now"));
+    match terminal_action_for_duplicate(None, current_cwd.clone()) {
+        DuplicateTerminalAction::Shell(cwd) => assert_eq!(cwd, current_cwd),
+        other => panic!("expected a shell, got {:?}", other),
+    }
+    match terminal_action_for_duplicate(
+        Some(Run::Cwd(PathBuf::from("/tmp/started"))),
+        current_cwd.clone(),
+    ) {
+        DuplicateTerminalAction::Shell(cwd) => assert_eq!(cwd, current_cwd),
+        other => panic!("expected a shell, got {:?}", other),
+    }
+}
+
+#[test]
+fn shells_whose_current_cwd_is_unknown_start_where_the_original_started() {
+    match terminal_action_for_duplicate(Some(Run::Cwd(PathBuf::from("/tmp/started"))), None) {
+        DuplicateTerminalAction::Shell(cwd) => {
+            assert_eq!(cwd, Some(PathBuf::from("/tmp/started")))
+        },
+        other => panic!("expected a shell, got {:?}", other),
+    }
+}
+
+#[test]
+fn commands_are_duplicated_as_commands_that_start_right_away() {
+    let run_command = RunCommand {
+        command: PathBuf::from("htop"),
+        args: vec!["-d".to_owned(), "10".to_owned()],
+        cwd: Some(PathBuf::from("/tmp/started")),
+        hold_on_start: true,
+        ..Default::default()
+    };
+    match terminal_action_for_duplicate(
+        Some(Run::Command(run_command)),
+        Some(PathBuf::from("/tmp/now")),
+    ) {
+        DuplicateTerminalAction::Command(duplicate) => {
+            assert_eq!(duplicate.command, PathBuf::from("htop"));
+            assert_eq!(duplicate.args, vec!["-d".to_owned(), "10".to_owned()]);
+            assert_eq!(duplicate.cwd, Some(PathBuf::from("/tmp/now")));
+            assert!(!duplicate.hold_on_start);
+        },
+        other => panic!("expected a command, got {:?}", other),
+    }
+}
+
+#[test]
+fn edited_files_are_duplicated_as_edited_files() {
+    match terminal_action_for_duplicate(
+        Some(Run::EditFile(PathBuf::from("src/main.rs"), Some(10), None)),
+        Some(PathBuf::from("/tmp/now")),
+    ) {
+        DuplicateTerminalAction::EditFile(payload) => {
+            assert_eq!(payload.path, PathBuf::from("src/main.rs"));
+            assert_eq!(payload.line_number, Some(10));
+        },
+        other => panic!("expected an edited file, got {:?}", other),
+    }
+}
+
+#[test]
+fn only_terminals_that_are_not_running_yet_count_towards_the_max_panes() {
+    let plugin = || {
+        Some(Run::Plugin(
+            RunPluginOrAlias::from_url("zellij:tab-bar", &None, None, None).unwrap(),
+        ))
+    };
+    let layout = TiledPaneLayout {
+        children: vec![
+            TiledPaneLayout {
+                run: plugin(),
+                ..Default::default()
+            },
+            TiledPaneLayout::default(),
+            TiledPaneLayout {
+                run: Some(Run::Cwd(PathBuf::from("/tmp"))),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let floating_panes_layout = vec![
+        FloatingPaneLayout::default(),
+        FloatingPaneLayout {
+            run: plugin(),
+            ..Default::default()
+        },
+        FloatingPaneLayout {
+            already_running: true,
+            ..Default::default()
+        },
+    ];
+    assert_eq!(terminal_count_in_layout(&layout, &floating_panes_layout), 3);
+}
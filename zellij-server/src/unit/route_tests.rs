@@ -0,0 +1,109 @@
+use super::*;
+use zellij_utils::pane_size::Size;
+
+#[test]
+fn auth_tokens_match_only_identical_tokens() {
+    assert!(auth_tokens_match("secret-token", "secret-token"));
+    assert!(!auth_tokens_match("secret-token", "secret-tokem"));
+    assert!(!auth_tokens_match("secret-token", "secret"));
+    assert!(!auth_tokens_match("secret-token", "secret-token-and-more"));
+    assert!(!auth_tokens_match("secret-token", ""));
+}
+
+#[test]
+fn sessions_without_a_token_accept_every_client() {
+    assert!(client_is_authenticated(
+        &ClientToServerMsg::ListClients,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn sessions_with_a_token_require_the_right_one() {
+    let expected_auth_token = "secret-token".to_owned();
+    assert!(!client_is_authenticated(
+        &ClientToServerMsg::ListClients,
+        Some(&expected_auth_token),
+        None
+    ));
+    assert!(!client_is_authenticated(
+        &ClientToServerMsg::ListClients,
+        Some(&expected_auth_token),
+        Some(&"wrong-token".to_owned())
+    ));
+    assert!(client_is_authenticated(
+        &ClientToServerMsg::ListClients,
+        Some(&expected_auth_token),
+        Some(&"secret-token".to_owned())
+    ));
+}
+
+#[test]
+fn authenticating_and_checking_the_connection_do_not_require_a_token() {
+    let expected_auth_token = "secret-token".to_owned();
+    assert!(client_is_authenticated(
+        &ClientToServerMsg::Authenticate("wrong-token".to_owned()),
+        Some(&expected_auth_token),
+        None
+    ));
+    assert!(client_is_authenticated(
+        &ClientToServerMsg::ConnStatus,
+        Some(&expected_auth_token),
+        None
+    ));
+}
+
+fn connected_client_and_route_ends() -> (
+    zellij_utils::ipc::IpcSenderWithContext<ClientToServerMsg>,
+    IpcReceiverWithContext<ClientToServerMsg>,
+) {
+    use std::os::unix::io::{FromRawFd, IntoRawFd};
+    use std::os::unix::net::UnixStream;
+    use zellij_utils::interprocess::local_socket::LocalSocketStream;
+    let (client_end, route_end) = UnixStream::pair().unwrap();
+    let client_end = unsafe { LocalSocketStream::from_raw_fd(client_end.into_raw_fd()) };
+    let route_end = unsafe { LocalSocketStream::from_raw_fd(route_end.into_raw_fd()) };
+    (
+        zellij_utils::ipc::IpcSenderWithContext::new(client_end),
+        IpcReceiverWithContext::new(route_end),
+    )
+}
+
+fn resize(cols: usize) -> ClientToServerMsg {
+    ClientToServerMsg::TerminalResize(Size { rows: 20, cols })
+}
+
+#[test]
+fn consecutive_resizes_are_coalesced_into_the_last_one() {
+    let (mut client_sender, mut route_receiver) = connected_client_and_route_ends();
+    for cols in [100, 110, 120] {
+        client_sender.send(resize(cols)).unwrap();
+    }
+    client_sender.send(ClientToServerMsg::ListClients).unwrap();
+    let (first_resize, _err_ctx) = route_receiver.recv().unwrap();
+    let (last_resize, message_after_resizes) =
+        last_of_consecutive_resizes(first_resize, &mut route_receiver);
+    assert!(
+        matches!(last_resize, ClientToServerMsg::TerminalResize(size) if size.cols == 120),
+        "only the last resize is handled"
+    );
+    assert!(
+        matches!(
+            message_after_resizes,
+            Some(Some((ClientToServerMsg::ListClients, _)))
+        ),
+        "the message following the resizes is handed back to be handled next"
+    );
+}
+
+#[test]
+fn a_single_resize_is_handled_as_is() {
+    let (mut client_sender, mut route_receiver) = connected_client_and_route_ends();
+    client_sender.send(resize(100)).unwrap();
+    let (first_resize, _err_ctx) = route_receiver.recv().unwrap();
+    let (last_resize, message_after_resizes) =
+        last_of_consecutive_resizes(first_resize, &mut route_receiver);
+    assert!(matches!(last_resize, ClientToServerMsg::TerminalResize(size) if size.cols == 100));
+    assert!(message_after_resizes.is_none());
+}
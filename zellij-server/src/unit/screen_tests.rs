@@ -271,6 +271,9 @@ fn create_new_screen(size: Size) -> Screen {
     let styled_underlines = true;
     let arrow_fonts = true;
     let explicitly_disable_kitty_keyboard_protocol = false;
+    let exclusive_floating_panes = false;
+    let session_max_panes = 200;
+    let focus_follows_mouse = false;
     let screen = Screen::new(
         bus,
         &client_attributes,
@@ -291,6 +294,9 @@ fn create_new_screen(size: Size) -> Screen {
         arrow_fonts,
         layout_dir,
         explicitly_disable_kitty_keyboard_protocol,
+        exclusive_floating_panes,
+        session_max_panes,
+        focus_follows_mouse,
     );
     screen
 }
@@ -541,6 +547,11 @@ impl MockScreen {
             session_configuration: self.session_metadata.session_configuration.clone(),
             layout,
             current_input_modes: self.session_metadata.current_input_modes.clone(),
+            auth_token: self.session_metadata.auth_token.clone(),
+            global_hotkeys: self.session_metadata.global_hotkeys.clone(),
+            auto_save_layout_on_exit: false,
+            audit_log: None,
+            is_suspended: false,
         }
     }
 }
@@ -599,6 +610,11 @@ impl MockScreen {
             layout,
             session_configuration: Default::default(),
             current_input_modes: HashMap::new(),
+            auth_token: None,
+            global_hotkeys: Default::default(),
+            auto_save_layout_on_exit: false,
+            audit_log: None,
+            is_suspended: false,
         };
 
         let os_input = FakeInputOutput::default();
@@ -693,6 +709,38 @@ fn open_new_tab() {
     );
 }
 
+#[test]
+fn pending_bytes_are_parsed_before_the_session_is_serialized() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    let background_tab = screen.tabs.get_mut(&0).unwrap();
+    background_tab
+        .handle_pty_bytes(1, Vec::from("scratch".as_bytes()))
+        .expect("TEST");
+    assert_eq!(
+        background_tab.panes_with_pending_bytes(),
+        vec![PaneId::Terminal(1)],
+        "bytes of a pane in a tab no client is looking at were held back"
+    );
+
+    screen.get_layout_metadata(None);
+    assert!(
+        screen
+            .tabs
+            .get(&0)
+            .unwrap()
+            .panes_with_pending_bytes()
+            .is_empty(),
+        "held back bytes were parsed before serializing the session"
+    );
+}
+
 #[test]
 pub fn switch_to_prev_tab() {
     let size = Size {
@@ -732,6 +780,126 @@ pub fn switch_to_next_tab() {
     );
 }
 
+#[test]
+pub fn pinned_floating_panes_follow_the_client_to_the_new_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    screen
+        .new_tab(1, (vec![], vec![]), None, Some(client_id))
+        .expect("TEST");
+    let mut pinned_floating_pane = FloatingPaneLayout::default();
+    pinned_floating_pane.pinned = Some(true);
+    screen
+        .apply_layout(
+            TiledPaneLayout::default(),
+            vec![pinned_floating_pane],
+            vec![(2, None)],
+            vec![(3, None)], // new floating terminal ids
+            HashMap::new(),
+            1,
+            true,
+            client_id,
+        )
+        .expect("TEST");
+    new_tab(&mut screen, 4, 2);
+    screen.switch_tab_prev(None, true, client_id).expect("TEST");
+    screen.switch_tab_next(None, true, client_id).expect("TEST");
+
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(active_tab.position, 1, "Active tab switched to next tab");
+    assert!(
+        active_tab.has_floating_pane_with_pid(&PaneId::Terminal(3)),
+        "Pinned floating pane moved to the new active tab"
+    );
+}
+
+#[test]
+pub fn synchronized_groups_are_created_and_dissolved() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+
+    let first_group = screen.create_synchronized_group(
+        "servers".to_owned(),
+        vec![PaneId::Terminal(1), PaneId::Terminal(2)],
+    );
+    let second_group =
+        screen.create_synchronized_group("logs".to_owned(), vec![PaneId::Terminal(2)]);
+    assert_ne!(first_group, second_group, "Each group has its own id");
+    screen.dissolve_synchronized_group(first_group);
+    assert_eq!(
+        screen
+            .synchronized_groups
+            .keys()
+            .copied()
+            .collect::<Vec<_>>(),
+        vec![second_group],
+        "Only the dissolved group was removed"
+    );
+}
+
+#[test]
+pub fn closed_panes_leave_their_synchronized_groups() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+
+    let first_group = screen.create_synchronized_group(
+        "servers".to_owned(),
+        vec![PaneId::Terminal(1), PaneId::Terminal(2)],
+    );
+    let _second_group =
+        screen.create_synchronized_group("logs".to_owned(), vec![PaneId::Terminal(2)]);
+    screen.close_tab_at_index(2).expect("TEST");
+    assert_eq!(
+        screen.synchronized_groups.get(&first_group),
+        Some(&("servers".to_owned(), vec![PaneId::Terminal(1)])),
+        "The closed pane left its group"
+    );
+    assert_eq!(
+        screen.synchronized_groups.len(),
+        1,
+        "The group left without panes was dissolved"
+    );
+}
+
+#[test]
+pub fn new_tabs_over_the_maximum_pane_count_are_refused() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    screen.session_max_panes = 2;
+    assert!(
+        !screen.new_tab_exceeds_max_panes(&None, &[]),
+        "The first tab is always opened"
+    );
+    new_tab(&mut screen, 1, 0);
+    assert!(
+        !screen.new_tab_exceeds_max_panes(&None, &[]),
+        "A tab with one pane fits"
+    );
+    new_tab(&mut screen, 2, 1);
+    assert!(
+        screen.new_tab_exceeds_max_panes(&None, &[]),
+        "A tab beyond the maximum is refused"
+    );
+}
+
 #[test]
 pub fn switch_to_tab_name() {
     let size = Size {
@@ -786,6 +954,28 @@ pub fn close_tab() {
     );
 }
 
+#[test]
+pub fn pinned_tab_is_not_closed() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    screen.set_tab_pinned(1, true).expect("TEST");
+    screen.close_tab(1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "Pinned tab was not closed");
+    assert!(screen.get_active_tab(1).unwrap().pinned, "Tab is pinned");
+
+    screen.set_tab_pinned(1, false).expect("TEST");
+    screen.close_tab(1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 1, "Unpinned tab was closed");
+}
+
 #[test]
 pub fn close_the_middle_tab() {
     let size = Size {
@@ -808,6 +998,80 @@ pub fn close_the_middle_tab() {
     );
 }
 
+#[test]
+pub fn current_tab_infos_and_pane_manifest_reflect_open_tabs() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    screen.switch_tab_prev(None, true, 1).expect("TEST");
+
+    let tab_infos = screen.current_tab_infos_for_client(1);
+    assert_eq!(tab_infos.len(), 2, "Both tabs reported");
+    assert!(tab_infos[0].active, "First tab reported as active");
+    assert!(!tab_infos[1].active, "Second tab reported as inactive");
+    assert_eq!(
+        screen.pane_manifest().panes.len(),
+        2,
+        "Panes reported for both tabs"
+    );
+}
+
+#[test]
+pub fn style_for_client_reflects_its_last_mode_update() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+
+    let mut mode_info = ModeInfo::default();
+    mode_info.style.rounded_corners = true;
+    mode_info.style.colors.green = zellij_utils::data::PaletteColor::EightBit(2);
+    screen.mode_info.insert(1, mode_info.clone());
+
+    assert_eq!(
+        screen.style_for_client(1),
+        mode_info.style,
+        "Style of the client's last mode update reported"
+    );
+    assert_eq!(
+        screen.style_for_client(2),
+        screen.default_mode_info.style,
+        "Clients without a mode update get the default style"
+    );
+}
+
+#[test]
+pub fn input_mode_for_client_reflects_its_last_mode_update() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+
+    let mut mode_info = ModeInfo::default();
+    mode_info.mode = InputMode::Locked;
+    screen.mode_info.insert(1, mode_info);
+
+    assert_eq!(
+        screen.input_mode_for_client(1),
+        InputMode::Locked,
+        "Input mode of the client's last mode update reported"
+    );
+    assert_eq!(
+        screen.input_mode_for_client(2),
+        screen.default_mode_info.mode,
+        "Clients without a mode update get the default input mode"
+    );
+}
+
 #[test]
 fn move_focus_left_at_left_screen_edge_changes_tab() {
     let size = Size {
@@ -2460,6 +2724,49 @@ pub fn send_cli_edit_action_with_line_number() {
     assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
 }
 
+#[test]
+pub fn send_cli_open_file_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_action = CliAction::OpenFile {
+        path: PathBuf::from("/file/to/open"),
+        pane_id: Some("terminal_1".to_owned()),
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let mut found_instruction = false;
+    for instruction in received_pty_instructions.lock().unwrap().iter() {
+        if let PtyInstruction::SpawnInPlaceTerminal(
+            Some(TerminalAction::OpenFile(open_file_payload)),
+            _,
+            client_tab_index_or_pane_id,
+        ) = instruction
+        {
+            assert_eq!(open_file_payload.path, PathBuf::from("/file/to/open"));
+            assert_eq!(
+                client_tab_index_or_pane_id,
+                &ClientTabIndexOrPaneId::PaneId(PaneId::Terminal(1))
+            );
+            found_instruction = true;
+        }
+    }
+    assert!(found_instruction);
+}
+
 #[test]
 pub fn send_cli_edit_action_with_split_direction() {
     let size = Size {
@@ -3687,3 +3994,141 @@ pub fn send_cli_stack_panes_action() {
     }
     assert_snapshot!(format!("{}", snapshot_count));
 }
+
+#[test]
+pub fn run_command_in_existing_terminal_pane() {
+    let size = Size { cols: 80, rows: 10 };
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let command = RunCommand {
+        command: PathBuf::from("ls"),
+        args: vec!["-la".to_owned()],
+        ..Default::default()
+    };
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RunCommandInPane(
+            PaneId::Terminal(0),
+            command.clone(),
+        ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RunCommandInPane(
+            PaneId::Plugin(0),
+            command,
+        ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let received_pty_instructions = received_pty_instructions.lock().unwrap();
+    let written_commands: Vec<_> = received_pty_instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, terminal_id) => Some((bytes.clone(), *terminal_id)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_commands,
+        vec![(b"ls -la\n".to_vec(), 0)],
+        "command only written to the terminal pane, followed by a newline"
+    );
+}
+
+#[test]
+pub fn run_command_in_existing_terminal_pane_with_quoted_args_cwd_and_env() {
+    let size = Size { cols: 80, rows: 10 };
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let command = RunCommand {
+        command: PathBuf::from("grep"),
+        args: vec!["it's here".to_owned(), "".to_owned(), "$HOME".to_owned()],
+        cwd: Some(PathBuf::from("/tmp/my dir")),
+        env: HashMap::from([("FOO".to_owned(), "bar baz".to_owned())]),
+        ..Default::default()
+    };
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RunCommandInPane(
+            PaneId::Terminal(0),
+            command,
+        ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let received_pty_instructions = received_pty_instructions.lock().unwrap();
+    let written_commands: Vec<_> = received_pty_instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, terminal_id) => {
+                Some((String::from_utf8_lossy(bytes).to_string(), *terminal_id))
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_commands,
+        vec![(
+            "(cd '/tmp/my dir' && FOO='bar baz' grep 'it'\\''s here' '' '$HOME')\n".to_owned(),
+            0
+        )],
+    );
+}
+
+#[test]
+pub fn suspending_and_resuming_a_session_stops_and_continues_its_panes_and_background_jobs() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let background_jobs_receiver = mock_screen.background_jobs_receiver.take().unwrap();
+    let session_metadata = &mut mock_screen.session_metadata;
+
+    session_metadata.set_suspended(true);
+    session_metadata.set_suspended(true); // already suspended, nothing is sent again
+    assert!(session_metadata.is_suspended);
+    let pty_instructions: Vec<_> = pty_receiver.try_iter().map(|(i, _)| i).collect();
+    let background_jobs: Vec<_> = background_jobs_receiver
+        .try_iter()
+        .map(|(j, _)| j)
+        .collect();
+    assert!(matches!(
+        pty_instructions.as_slice(),
+        [PtyInstruction::SuspendAll]
+    ));
+    assert!(matches!(
+        background_jobs.as_slice(),
+        [BackgroundJob::SuspendSession]
+    ));
+
+    session_metadata.set_suspended(false);
+    session_metadata.set_suspended(false); // already resumed, nothing is sent again
+    assert!(!session_metadata.is_suspended);
+    let pty_instructions: Vec<_> = pty_receiver.try_iter().map(|(i, _)| i).collect();
+    let background_jobs: Vec<_> = background_jobs_receiver
+        .try_iter()
+        .map(|(j, _)| j)
+        .collect();
+    assert!(matches!(
+        pty_instructions.as_slice(),
+        [PtyInstruction::ResumeAll]
+    ));
+    assert!(matches!(
+        background_jobs.as_slice(),
+        [BackgroundJob::ResumeSession]
+    ));
+}
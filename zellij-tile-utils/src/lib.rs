@@ -1,3 +1,5 @@
+use unicode_segmentation::UnicodeSegmentation;
+
 #[macro_export]
 macro_rules! rgb {
     ($a:expr) => {
@@ -29,3 +31,62 @@ macro_rules! style {
             })
     };
 }
+
+/// Returns the longest prefix of `s` containing at most `max_graphemes` grapheme clusters.
+///
+/// Unlike slicing by byte or `char` count, this never splits a multi-byte character or a
+/// multi-codepoint grapheme (eg. an emoji with modifiers or a letter with combining marks).
+pub fn safe_truncate(s: &str, max_graphemes: usize) -> &str {
+    match s.grapheme_indices(true).nth(max_graphemes) {
+        Some((byte_index, _)) => &s[..byte_index],
+        None => s,
+    }
+}
+
+/// Returns the longest suffix of `s` containing at most `max_graphemes` grapheme clusters.
+pub fn safe_truncate_start(s: &str, max_graphemes: usize) -> &str {
+    if max_graphemes == 0 {
+        return &s[s.len()..];
+    }
+    match s.grapheme_indices(true).rev().nth(max_graphemes - 1) {
+        Some((byte_index, _)) => &s[byte_index..],
+        None => s,
+    }
+}
+
+/// The number of grapheme clusters in `s`, ie. the unit `safe_truncate` counts in.
+pub fn grapheme_count(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_truncate_keeps_ascii_prefix() {
+        assert_eq!(safe_truncate("hello world", 5), "hello");
+        assert_eq!(safe_truncate("hello", 10), "hello");
+        assert_eq!(safe_truncate("hello", 0), "");
+    }
+
+    #[test]
+    fn safe_truncate_never_splits_graphemes() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let text = format!("a{}e\u{301}日本", family);
+        assert_eq!(safe_truncate(&text, 1), "a");
+        assert_eq!(safe_truncate(&text, 2), format!("a{}", family));
+        assert_eq!(safe_truncate(&text, 3), format!("a{}e\u{301}", family));
+        assert_eq!(safe_truncate(&text, 4), format!("a{}e\u{301}日", family));
+        assert_eq!(grapheme_count(&text), 5);
+    }
+
+    #[test]
+    fn safe_truncate_start_keeps_suffix() {
+        let text = "/home/用户/e\u{301}tude";
+        assert_eq!(safe_truncate_start(text, 5), "e\u{301}tude");
+        assert_eq!(safe_truncate_start(text, 8), "用户/e\u{301}tude");
+        assert_eq!(safe_truncate_start(text, 100), text);
+        assert_eq!(safe_truncate_start(text, 0), "");
+    }
+}
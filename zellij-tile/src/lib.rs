@@ -5,7 +5,9 @@
 //!
 //! ### Interesting things in this libary:
 //! - The [`ZellijPlugin`] trait for implementing plugins combined with the
-//! [`register_plugin!`](register_plugin) macro to register them.
+//! [`register_plugin!`](register_plugin) macro to register them, or with the
+//! [`register_plugin_with_config!`](register_plugin_with_config) macro to receive a typed
+//! configuration.
 //! - The list of [commands](shim) representing what a plugin can do.
 //! - The list of [`Events`](prelude::Event) a plugin can subscribe to
 //! - The [`ZellijWorker`] trait for implementing background workers combined with the
@@ -21,7 +23,7 @@ pub mod ui_components;
 
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use zellij_utils::data::{Event, PipeMessage};
+use zellij_utils::data::{Event, PaneId, PipeMessage, RenderRegion};
 
 // use zellij_tile::shim::plugin_api::event::ProtobufEvent;
 
@@ -46,6 +48,49 @@ pub trait ZellijPlugin: Default {
     /// Will be called either after an `update` that requested it, or when the plugin otherwise needs to be re-rendered (eg. on startup, or when the plugin is resized).
     /// The `rows` and `cols` values represent the "content size" of the plugin (this will not include its surrounding frame if the user has pane frames enabled).
     fn render(&mut self, rows: usize, cols: usize) {}
+    /// An alternative to `render` for plugins that would like to draw several independent areas of
+    /// their content, each [`RenderRegion`] is clipped to its own bounds and composited by Zellij.
+    /// If this returns an empty `Vec` (the default), Zellij will fall back to calling `render`.
+    fn render_regions(&mut self, rows: usize, cols: usize) -> Vec<RenderRegion> {
+        vec![]
+    }
+    /// Will be called when an action sent with
+    /// [`run_action_with_callback`](shim::run_action_with_callback) was applied, with the
+    /// `callback_id` given to it and the pane the action was run on behalf of. The
+    /// [`ActionComplete`](prelude::Event::ActionComplete) event is then also passed to `update`
+    /// as usual, so the plugin needs to be [`subscribe`](shim::subscribe)d to it.
+    ///
+    /// `pane_id` is always the plugin's own pane, not a pane the action opened (eg. with
+    /// `NewPane`), those can be found in the next [`PaneUpdate`](prelude::Event::PaneUpdate).
+    fn on_action_complete(&mut self, callback_id: u32, pane_id: Option<PaneId>) {}
+}
+
+// the key under which `run_action_with_callback` stores its callback_id in the action's context
+pub(crate) const CALLBACK_ID_CONTEXT_KEY: &str = "callback_id";
+
+/// Passes `event` to [`ZellijPlugin::update`], first calling
+/// [`ZellijPlugin::on_action_complete`] if it completes an action sent with
+/// [`run_action_with_callback`](shim::run_action_with_callback), used by the
+/// [`register_plugin!`](register_plugin) macro.
+pub fn dispatch_event<T: ZellijPlugin>(plugin: &mut T, event: Event) -> bool {
+    if let Event::ActionComplete(_action, pane_id, context) = &event {
+        if let Some(callback_id) = context
+            .get(CALLBACK_ID_CONTEXT_KEY)
+            .and_then(|callback_id| callback_id.parse::<u32>().ok())
+        {
+            plugin.on_action_complete(callback_id, *pane_id);
+        }
+    }
+    plugin.update(event)
+}
+
+/// Implemented alongside [`ZellijPlugin`] by plugins registered with the
+/// [`register_plugin_with_config!`](register_plugin_with_config) macro, so that they receive their
+/// configuration as `C` rather than as the raw `BTreeMap` of the plugin's KDL configuration.
+pub trait ZellijPluginWithConfig<C>: ZellijPlugin {
+    /// Will be called instead of [`ZellijPlugin::load`] when the plugin is loaded, with its
+    /// configuration already converted to `C`.
+    fn load(&mut self, config: C);
 }
 
 /// This trait is used to create workers. Workers can be used by plugins to run longer running
@@ -54,7 +99,8 @@ pub trait ZellijPlugin: Default {
 ///
 /// ## Starting workers on plugin load
 /// Implement this trait on a struct (typically representing the worker state) and register it with
-/// the [`register_worker!`](register_worker) macro.
+/// the [`register_worker!`](register_worker) macro, or with the
+/// [`register_worker_pool!`](register_worker_pool) macro to run several instances of it.
 ///
 /// ## Sending messages to workers and back to the plugin
 /// Send messages to workers with the [`post_message_to`](shim::post_message_to) method.
@@ -96,8 +142,18 @@ Please refer to the documentation for further information:
 ///
 /// register_plugin!(MyPlugin);
 /// ```
+///
+/// A plugin that relies on APIs added in a specific version of Zellij can declare it, and older
+/// versions of Zellij will refuse to load it with an error rather than have it misbehave:
+/// ```rust,ignore
+/// register_plugin!(MyPlugin, min_zellij_version = "0.42.0");
+/// ```
 #[macro_export]
 macro_rules! register_plugin {
+    ($t:ty, min_zellij_version = $min_zellij_version:literal) => {
+        $crate::register_plugin!($t);
+        $crate::register_plugin!(@min_version $min_zellij_version);
+    };
     ($t:ty) => {
         thread_local! {
             static STATE: std::cell::RefCell<$t> = std::cell::RefCell::new(Default::default());
@@ -127,6 +183,16 @@ macro_rules! register_plugin {
             });
         }
 
+        $crate::register_plugin!(@exports $t);
+    };
+    (@min_version $min_zellij_version:literal) => {
+        #[no_mangle]
+        pub fn plugin_min_version() {
+            const MIN_ZELLIJ_VERSION: &str = $min_zellij_version;
+            println!("{}", MIN_ZELLIJ_VERSION);
+        }
+    };
+    (@exports $t:ty) => {
         #[no_mangle]
         pub fn update() -> bool {
             let err_context = "Failed to deserialize event";
@@ -137,8 +203,8 @@ macro_rules! register_plugin {
                 let protobuf_bytes: Vec<u8> = $crate::shim::object_from_stdin().unwrap();
                 let protobuf_event: ProtobufEvent =
                     ProtobufEvent::decode(protobuf_bytes.as_slice()).unwrap();
-                let event = protobuf_event.try_into().unwrap();
-                state.borrow_mut().update(event)
+                let event: $crate::prelude::Event = protobuf_event.try_into().unwrap();
+                $crate::dispatch_event(&mut *state.borrow_mut(), event)
             })
         }
 
@@ -164,6 +230,21 @@ macro_rules! register_plugin {
             });
         }
 
+        #[no_mangle]
+        pub fn render_regions(rows: i32, cols: i32) -> bool {
+            STATE.with(|state| {
+                let mut state = state.borrow_mut();
+                let regions = state.render_regions(rows as usize, cols as usize);
+                if regions.is_empty() {
+                    state.render(rows as usize, cols as usize);
+                    false
+                } else {
+                    $crate::shim::object_to_stdout(&regions);
+                    true
+                }
+            })
+        }
+
         #[no_mangle]
         pub fn plugin_version() {
             println!("{}", $crate::prelude::VERSION);
@@ -171,6 +252,98 @@ macro_rules! register_plugin {
     };
 }
 
+/// Used to register a plugin implementing the [`ZellijPlugin`] and [`ZellijPluginWithConfig`]
+/// traits, converting its configuration with `TryFrom<BTreeMap<String, String>>` before passing it
+/// to [`ZellijPluginWithConfig::load`]. If the conversion fails, the plugin panics with the error
+/// and the panic is reported to the user.
+///
+/// eg.
+/// ```rust
+/// use zellij_tile::prelude::*;
+/// use std::collections::BTreeMap;
+/// use std::convert::TryFrom;
+///
+/// pub struct MyConfig {
+///     greeting: String,
+/// }
+///
+/// impl TryFrom<BTreeMap<String, String>> for MyConfig {
+///     type Error = String;
+///     fn try_from(configuration: BTreeMap<String, String>) -> Result<Self, String> {
+///         let greeting = configuration
+///             .get("greeting")
+///             .cloned()
+///             .ok_or_else(|| "missing the greeting configuration".to_owned())?;
+///         Ok(MyConfig { greeting })
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// pub struct MyPlugin {}
+///
+/// impl ZellijPlugin for MyPlugin {
+///    // ...
+/// }
+///
+/// impl ZellijPluginWithConfig<MyConfig> for MyPlugin {
+///     fn load(&mut self, config: MyConfig) {
+///         // ...
+///     }
+/// }
+///
+/// register_plugin_with_config!(MyPlugin, MyConfig);
+/// ```
+///
+/// Like [`register_plugin!`](register_plugin), it accepts a `min_zellij_version = "x.y.z"` after
+/// the configuration type.
+#[macro_export]
+macro_rules! register_plugin_with_config {
+    ($t:ty, $config:ty, min_zellij_version = $min_zellij_version:literal) => {
+        $crate::register_plugin_with_config!($t, $config);
+        $crate::register_plugin!(@min_version $min_zellij_version);
+    };
+    ($t:ty, $config:ty) => {
+        thread_local! {
+            static STATE: std::cell::RefCell<$t> = std::cell::RefCell::new(Default::default());
+        }
+
+        fn main() {
+            // Register custom panic handler
+            std::panic::set_hook(Box::new(|info| {
+                report_panic(info);
+            }));
+        }
+
+        #[no_mangle]
+        fn load() {
+            STATE.with(|state| {
+                use std::collections::BTreeMap;
+                use std::convert::TryFrom;
+                use zellij_tile::shim::plugin_api::action::ProtobufPluginConfiguration;
+                use zellij_tile::shim::prost::Message;
+                let protobuf_bytes: Vec<u8> = $crate::shim::object_from_stdin().unwrap();
+                let protobuf_configuration: ProtobufPluginConfiguration =
+                    ProtobufPluginConfiguration::decode(protobuf_bytes.as_slice()).unwrap();
+                let plugin_configuration: BTreeMap<String, String> =
+                    BTreeMap::try_from(&protobuf_configuration).unwrap();
+                let config =
+                    <$config as TryFrom<BTreeMap<String, String>>>::try_from(plugin_configuration)
+                        .unwrap_or_else(|e| {
+                            panic!(
+                                "Failed to read the configuration of {} as {}: {}",
+                                stringify!($t),
+                                stringify!($config),
+                                e
+                            )
+                        });
+                $crate::ZellijPluginWithConfig::<$config>::load(&mut *state.borrow_mut(), config);
+            });
+        }
+
+        $crate::register_plugin!(@exports $t);
+    };
+}
+
 /// Used to register a plugin worker implementing the [`ZellijWorker`] trait.
 ///
 /// eg.
@@ -218,3 +391,51 @@ macro_rules! register_worker {
          }
     };
 }
+
+/// Used to register a pool of plugin workers implementing the [`ZellijWorker`] trait, for work
+/// that benefits from running in parallel.
+///
+/// Each instance of the pool has its own copy of the worker state. Messages sent with
+/// [`post_message_to`](shim::post_message_to) go to the instances in turn, messages sent with
+/// [`post_message_to_worker_instance`](shim::post_message_to_worker_instance) go to a specific
+/// one. Users can override the pool size by adding a `<worker_name>_pool_size` key (eg.
+/// `file_search_worker_pool_size`) to the plugin configuration. Pools have at most 16 instances.
+///
+/// eg.
+/// ```rust
+/// use zellij_tile::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Default, Serialize, Deserialize)]
+/// pub struct FileSearchWorker {}
+///
+/// impl ZellijWorker<'_> for FileSearchWorker {
+///     fn on_message(&mut self, message: String, payload: String) {
+///         // ...
+///     }
+/// }
+///
+/// register_worker_pool!(
+///     FileSearchWorker,
+///     file_search_worker, // registers the worker as the namespace "file_search"
+///     FILE_SEARCH_WORKER, // expanded to a static variable in which the worker state it held
+///     pool_size = 4
+/// );
+/// ```
+#[macro_export]
+macro_rules! register_worker_pool {
+    ($worker:ty, $worker_name:ident, $worker_static_name:ident, pool_size = $pool_size:expr) => {
+        $crate::register_worker!($worker, $worker_name, $worker_static_name);
+        // tells Zellij how many instances of the worker to start
+        const _: () = {
+            #[export_name = concat!(stringify!($worker_name), "_pool")]
+            pub extern "C" fn worker_pool_size() -> u32 {
+                $pool_size
+            }
+        };
+    };
+}
+
+#[cfg(test)]
+#[path = "./unit/lib_tests.rs"]
+mod lib_tests;
@@ -3,13 +3,22 @@ use std::collections::{BTreeMap, HashSet};
 use std::{
     io,
     path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
 };
 use zellij_utils::data::*;
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::actions::Action;
+use zellij_utils::input::layout::{RunPluginOrAlias, SplitSize};
 pub use zellij_utils::plugin_api;
-use zellij_utils::plugin_api::plugin_command::ProtobufPluginCommand;
+use zellij_utils::plugin_api::event::ProtobufEvent;
+use zellij_utils::plugin_api::input_mode::ProtobufInputModeMessage;
+use zellij_utils::plugin_api::plugin_command::{
+    BlobContent, BlobHash, BlobList, ProtobufPluginCommand, ProtobufPluginCommandResponse,
+    SynchronizedGroup,
+};
 use zellij_utils::plugin_api::plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion};
+use zellij_utils::plugin_api::style::ProtobufStyle;
 
 pub use super::ui_components::*;
 pub use zellij_utils::prost::{self, *};
@@ -34,6 +43,12 @@ pub fn unsubscribe(event_types: &[EventType]) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Receive the text the user copies from terminal panes as [`Event::PaneSelection`], requires the
+/// `ReadApplicationState` permission
+pub fn subscribe_to_selection_events() {
+    subscribe(&[EventType::PaneSelection]);
+}
+
 // Plugin Settings
 
 /// Sets the plugin as selectable or unselectable to the user. Unselectable plugins might be desired when they do not accept user input.
@@ -44,6 +59,14 @@ pub fn set_selectable(selectable: bool) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Sets the minimum number of rows and columns this plugin's pane can be resized to when tiled.
+pub fn set_self_min_size(rows: usize, cols: usize) {
+    let plugin_command = PluginCommand::SetSelfMinSize(rows, cols);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 pub fn request_permission(permissions: &[PermissionType]) {
     let plugin_command = PluginCommand::RequestPluginPermissions(permissions.into());
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -52,26 +75,139 @@ pub fn request_permission(permissions: &[PermissionType]) {
 }
 
 // Query Functions
-/// Returns the unique Zellij pane ID for the plugin as well as the Zellij process id.
-pub fn get_plugin_ids() -> PluginIds {
+/// Returns the unique Zellij pane ID for the plugin, the Zellij process id, the folder the plugin
+/// was started in, the name of the session and the id of the client the plugin instance belongs to.
+pub fn get_plugin_ids() -> Result<PluginIds, PluginError> {
     let plugin_command = PluginCommand::GetPluginIds;
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    let protobuf_plugin_ids = ProtobufPluginIds::decode(payload.as_slice())
+        .map_err(|e| PluginError::HostError(e.to_string()))?;
+    PluginIds::try_from(protobuf_plugin_ids).map_err(|e| PluginError::HostError(e.to_string()))
+}
+
+/// Returns the version of the running Zellij instance - can be useful to check plugin compatibility
+pub fn get_zellij_version() -> Result<String, PluginError> {
+    let plugin_command = PluginCommand::GetZellijVersion;
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    let protobuf_zellij_version = ProtobufZellijVersion::decode(payload.as_slice())
+        .map_err(|e| PluginError::HostError(e.to_string()))?;
+    Ok(protobuf_zellij_version.version)
+}
+
+/// Returns the current tabs (as they would appear in the next `Event::TabUpdate`), useful for
+/// getting the initial state in `load` without waiting for the first event.
+/// Requires the `ReadApplicationState` permission, returns `PluginError::PermissionDenied` if it
+/// is not granted.
+pub fn get_current_tabs() -> Result<Vec<TabInfo>, PluginError> {
+    let plugin_command = PluginCommand::GetCurrentTabs;
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    match event_from_bytes(&payload)? {
+        Event::TabUpdate(tab_infos) => Ok(tab_infos),
+        _ => Err(PluginError::HostError(
+            "unexpected response to GetCurrentTabs".to_owned(),
+        )),
+    }
+}
+
+/// Returns the current panes, the same manifest the next `Event::PaneUpdate` will carry.
+/// Requires the `ReadApplicationState` permission.
+pub fn get_all_panes() -> Result<PaneManifest, PluginError> {
+    let plugin_command = PluginCommand::GetAllPanes;
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    match event_from_bytes(&payload)? {
+        Event::PaneUpdate(pane_manifest) => Ok(pane_manifest),
+        _ => Err(PluginError::HostError(
+            "unexpected response to GetAllPanes".to_owned(),
+        )),
+    }
+}
+
+/// Returns the current style (eg. the theme colors) as it would appear in the next
+/// `Event::ModeUpdate`, useful for initializing the plugin's UI in `load` with the right colors.
+pub fn get_current_styling() -> Result<Style, PluginError> {
+    let plugin_command = PluginCommand::GetCurrentStyling;
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    let protobuf_style = ProtobufStyle::decode(payload.as_slice())
+        .map_err(|e| PluginError::HostError(e.to_string()))?;
+    Style::try_from(protobuf_style).map_err(|e| PluginError::HostError(e.to_string()))
+}
+
+/// Returns the input mode of the client this plugin belongs to, as it would appear in the next
+/// `Event::ModeUpdate`. Useful for rendering mode specific UI without subscribing to
+/// `ModeUpdate` and keeping the `ModeInfo` around.
+/// Requires the `ReadApplicationState` permission.
+pub fn get_current_input_mode() -> Result<InputMode, PluginError> {
+    let plugin_command = PluginCommand::GetCurrentInputMode;
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    let protobuf_input_mode = ProtobufInputModeMessage::decode(payload.as_slice())
+        .map_err(|e| PluginError::HostError(e.to_string()))?;
+    InputMode::try_from(protobuf_input_mode).map_err(|e| PluginError::HostError(e.to_string()))
+}
+
+/// Returns the live sessions on this machine, subscribe to `EventType::SessionUpdate` to keep up
+/// with changes. Requires the `ReadApplicationState` permission.
+pub fn list_sessions() -> Result<Vec<SessionInfo>, PluginError> {
+    let plugin_command = PluginCommand::ListSessions;
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    match event_from_bytes(&payload)? {
+        Event::SessionUpdate(session_infos, _resurrectable_sessions) => Ok(session_infos),
+        _ => Err(PluginError::HostError(
+            "unexpected response to ListSessions".to_owned(),
+        )),
+    }
+}
+
+/// Store a blob in the blob store shared by all plugins and sessions, returning its hash.
+/// The hash is computed from both the key and the data, so storing the same data under the same
+/// key always returns the same hash. Each plugin can store up to 64MB of blobs.
+/// Requires the `StoreBlobs` permission
+pub fn store_blob(key: &str, data: &[u8]) -> Result<String, PluginError> {
+    let plugin_command = PluginCommand::StoreBlob(key.to_owned(), data.to_vec());
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    let blob_hash =
+        BlobHash::decode(payload.as_slice()).map_err(|e| PluginError::HostError(e.to_string()))?;
+    Ok(blob_hash.hash)
+}
+
+/// Load a blob previously stored with [store_blob] by any plugin, returns `None` if it does not
+/// exist. Requires the `StoreBlobs` permission
+pub fn load_blob(hash: &str) -> Result<Option<Vec<u8>>, PluginError> {
+    let plugin_command = PluginCommand::LoadBlob(hash.to_owned());
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    let blob_content = BlobContent::decode(payload.as_slice())
+        .map_err(|e| PluginError::HostError(e.to_string()))?;
+    Ok(blob_content.data)
+}
+
+/// List the hashes and sizes (in bytes) of all the blobs in the blob store.
+/// Requires the `StoreBlobs` permission
+pub fn list_blobs() -> Result<Vec<(String, u64)>, PluginError> {
+    let plugin_command = PluginCommand::ListBlobs;
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    let blob_list =
+        BlobList::decode(payload.as_slice()).map_err(|e| PluginError::HostError(e.to_string()))?;
+    Ok(blob_list
+        .blobs
+        .into_iter()
+        .map(|blob_info| (blob_info.hash, blob_info.size))
+        .collect())
+}
+
+/// Delete a blob this plugin stored from the blob store. Requires the `StoreBlobs` permission
+pub fn delete_blob(hash: &str) {
+    let plugin_command = PluginCommand::DeleteBlob(hash.to_owned());
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
-    let protobuf_plugin_ids =
-        ProtobufPluginIds::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
-    PluginIds::try_from(protobuf_plugin_ids).unwrap()
 }
 
-/// Returns the version of the running Zellij instance - can be useful to check plugin compatibility
-pub fn get_zellij_version() -> String {
-    let plugin_command = PluginCommand::GetZellijVersion;
+/// Emit a metric (a gauge) to be reported through the `metrics` configuration block, the plugin's
+/// location is added to its labels as `plugin`. Requires the `EmitMetrics` permission
+pub fn emit_metric(name: &str, value: f64, labels: BTreeMap<String, String>) {
+    let plugin_command = PluginCommand::EmitMetric(name.to_owned(), value, labels);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
-    let protobuf_zellij_version =
-        ProtobufZellijVersion::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
-    protobuf_zellij_version.version
 }
 
 // Host Functions
@@ -104,6 +240,15 @@ pub fn open_file_in_place(file_to_open: FileToOpen, context: BTreeMap<String, St
     unsafe { host_run_plugin_command() };
 }
 
+/// Open a file in the user's configured editor (the `scrollback_editor` option, falling back to
+/// `$EDITOR` or `$VISUAL`), either in a new floating pane or in place of the focused pane
+pub fn open_file_in_editor(path: PathBuf, open_location: OpenLocation) {
+    let plugin_command = PluginCommand::OpenFileInEditor(path, open_location);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Open a new terminal pane to the specified location on the host filesystem
 pub fn open_terminal<P: AsRef<Path>>(path: P) {
     let file_to_open = FileToOpen::new(path.as_ref().to_path_buf());
@@ -233,6 +378,36 @@ pub fn run_command_with_env_variables_and_cwd(
     unsafe { host_run_plugin_command() };
 }
 
+/// Run this command in the background on the host machine with `text` as its stdin, its exit code
+/// and output are sent to this plugin in a `CommandPipeResult` Event along with `context`. The
+/// command is killed if it is still running after 5 seconds
+pub fn pipe_text_to_command(
+    text: &str,
+    command_to_run: CommandToRun,
+    context: BTreeMap<String, String>,
+) {
+    let plugin_command =
+        PluginCommand::PipeTextToCommand(text.to_owned(), command_to_run, None, context);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Like `pipe_text_to_command`, killing the command if it is still running after `timeout`
+/// instead (at most 60 seconds)
+pub fn pipe_text_to_command_with_timeout(
+    text: &str,
+    command_to_run: CommandToRun,
+    timeout: Duration,
+    context: BTreeMap<String, String>,
+) {
+    let plugin_command =
+        PluginCommand::PipeTextToCommand(text.to_owned(), command_to_run, Some(timeout), context);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Make a web request, optionally being notified of its output
 /// if subscribed to the `WebRequestResult` Event, the context will be returned verbatim in this
 /// event and can be used for eg. marking the request_id
@@ -275,6 +450,39 @@ pub fn show_self(should_float_if_hidden: bool) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Hide the plugin pane if it is visible in the current tab, otherwise show it (as in [show_self])
+pub fn toggle_self(should_float_if_hidden: bool) {
+    let plugin_command = PluginCommand::ToggleSelf(should_float_if_hidden);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Apply an [Action] on behalf of this plugin, an [Event::ActionComplete] with the provided context
+/// will be sent to the plugin once it was applied
+pub fn run_action(action: Action, context: BTreeMap<String, String>) {
+    let plugin_command = PluginCommand::RunAction(action, context);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Like [run_action], once the [Action] was applied the plugin's
+/// [ZellijPlugin::on_action_complete](crate::ZellijPlugin::on_action_complete) is called with
+/// `callback_id` and the plugin's own pane id (the plugin should be subscribed to
+/// [Event::ActionComplete])
+pub fn run_action_with_callback(
+    action: Action,
+    callback_id: u32,
+    mut context: BTreeMap<String, String>,
+) {
+    context.insert(
+        crate::CALLBACK_ID_CONTEXT_KEY.to_owned(),
+        callback_id.to_string(),
+    );
+    run_action(action, context);
+}
+
 /// Show the pane (unsuppress it if it is suppressed) with the specified [PaneId], focus it and switch to its tab
 pub fn show_pane_with_id(pane_id: PaneId, should_float_if_hidden: bool) {
     let plugin_command = PluginCommand::ShowPaneWithId(pane_id, should_float_if_hidden);
@@ -299,6 +507,15 @@ pub fn switch_to_input_mode(mode: &InputMode) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Switch the specified client to the given Input Mode, the id of the client a plugin instance
+/// belongs to is the `client_id` of the `ModeInfo` it receives in `Event::ModeUpdate`
+pub fn set_client_mode(client_id: ClientId, mode: InputMode) {
+    let plugin_command = PluginCommand::SetClientMode(client_id, mode);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Provide a stringified [`layout`](https://zellij.dev/documentation/layouts.html) to be applied to the current session. If the layout has multiple tabs, they will all be opened.
 pub fn new_tabs_with_layout(layout: &str) {
     let plugin_command = PluginCommand::NewTabsWithLayout(layout.to_owned());
@@ -752,6 +969,28 @@ pub fn switch_session_with_focus(
     unsafe { host_run_plugin_command() };
 }
 
+/// Switch to a session with the given name (creating it if it does not exist) and tag it with the
+/// given tags
+pub fn switch_session_with_tags(name: &str, tags: Vec<String>) {
+    let plugin_command = PluginCommand::SwitchSession(ConnectToSession {
+        name: Some(name.to_owned()),
+        tags,
+        ..Default::default()
+    });
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Replace the tags of the session with the given name (live or resurrectable), an empty list
+/// removes all its tags
+pub fn set_session_tags(session_name: &str, tags: Vec<String>) {
+    let plugin_command = PluginCommand::SetSessionTags(session_name.to_owned(), tags);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Permanently delete a resurrectable session with the given name
 pub fn delete_dead_session(name: &str) {
     let plugin_command = PluginCommand::DeleteDeadSession(name.to_owned());
@@ -800,6 +1039,24 @@ pub fn cli_pipe_output(pipe_name: &str, output: &str) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Send a chunk of output to the output side of a pipe, the CLI writes it to STDOUT as soon as it
+/// arrives and exits once it receives the chunk marked with `is_last`
+pub fn cli_pipe_chunk(pipe_name: &str, chunk: &[u8], is_last: bool) {
+    let plugin_command = PluginCommand::CliPipeChunk(pipe_name.to_owned(), chunk.to_vec(), is_last);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Open a url in the user's default browser (or whatever handles it on their system), only
+/// `http`, `https` and `mailto` urls are allowed. Requires the `OpenUrls` permission
+pub fn open_url(url: &str) {
+    let plugin_command = PluginCommand::OpenUrl(url.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Send a message to a plugin, it will be launched if it is not already running
 pub fn pipe_message_to_plugin(message_to_plugin: MessageToPlugin) {
     let plugin_command = PluginCommand::MessageToPlugin(message_to_plugin);
@@ -986,6 +1243,44 @@ pub fn scroll_to_top_in_pane_id(pane_id: PaneId) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Scroll the specified pane so that the given line is at the top of its viewport, lines are
+/// counted from the oldest line in the scrollbuffer (as in the `row` of a `SearchMatch`)
+pub fn scroll_to_line_in_pane_id(pane_id: PaneId, line: usize) {
+    let plugin_command = PluginCommand::ScrollToLineInPaneId(pane_id, line);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Change the maximum number of lines the specified terminal pane keeps in its scrollbuffer
+/// (overriding the `scroll_buffer_size` config option for this pane), the oldest lines are dropped
+/// if it is shrunk below its current length
+pub fn set_pane_scrollback_size(pane_id: PaneId, scrollback_size: usize) {
+    let plugin_command = PluginCommand::SetPaneScrollbackSize(pane_id, scrollback_size);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Insert a read-only annotation line into the scrollbuffer of the specified pane, above the given
+/// line (counted as in `scroll_to_line_in_pane_id`). Annotations are shown with a distinct
+/// background and are left out when the scrollbuffer is dumped or edited
+pub fn annotate_scrollback_line(pane_id: PaneId, line_index: usize, annotation: &str) {
+    let plugin_command =
+        PluginCommand::AnnotateScrollbackLine(pane_id, line_index, annotation.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Remove all the annotations from the scrollbuffer of the specified pane
+pub fn clear_scrollback_annotations(pane_id: PaneId) {
+    let plugin_command = PluginCommand::ClearScrollbackAnnotations(pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Scroll the specified pane all the way to the bottom of the scrollbuffer
 pub fn scroll_to_bottom_in_pane_id(pane_id: PaneId) {
     let plugin_command = PluginCommand::ScrollToBottomInPaneId(pane_id);
@@ -1034,6 +1329,22 @@ pub fn close_tab_with_index(tab_index: usize) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Pin the tab at the specified position (0 indexed) to the session, pinned tabs cannot be closed
+pub fn pin_tab(tab_index: usize) {
+    let plugin_command = PluginCommand::PinTab(tab_index);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Unpin the tab at the specified position (0 indexed), allowing it to be closed again
+pub fn unpin_tab(tab_index: usize) {
+    let plugin_command = PluginCommand::UnpinTab(tab_index);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Rename the specified pane
 pub fn rename_pane_with_id<S: AsRef<str>>(pane_id: PaneId, new_name: S)
 where
@@ -1092,6 +1403,18 @@ pub fn reload_plugin_with_id(plugin_id: u32) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Get the dependencies declared in the manifest of the plugin at this url without loading it,
+/// if subscribed to the `PluginDependencies` Event
+pub fn get_plugin_dependencies<S: AsRef<str>>(url: S)
+where
+    S: ToString,
+{
+    let plugin_command = PluginCommand::GetPluginDependencies(url.to_string());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Reload an already-running in this session, optionally skipping the cache
 pub fn load_new_plugin<S: AsRef<str>>(
     url: S,
@@ -1128,6 +1451,17 @@ pub fn rebind_keys(
     unsafe { host_run_plugin_command() };
 }
 
+/// Register a key that is sent to this plugin's `pipe` method (as a message named
+/// "global_hotkey" with `action_id` as its payload) rather than handled by the focused pane or by
+/// the user's keybindings, regardless of whether this plugin is focused or even visible. If more
+/// than one plugin registers the same key, the first one to have done so receives it.
+pub fn register_global_hotkey<S: ToString>(key: KeyWithModifier, action_id: S) {
+    let plugin_command = PluginCommand::RegisterGlobalHotkey(key, action_id.to_string());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 pub fn change_host_folder(new_host_folder: PathBuf) {
     let plugin_command = PluginCommand::ChangeHostFolder(new_host_folder);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -1135,6 +1469,15 @@ pub fn change_host_folder(new_host_folder: PathBuf) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Toggle whether the focused floating pane is pinned, pinned panes are always on top and follow
+/// the user to whichever tab they switch to
+pub fn toggle_pane_pinned() {
+    let plugin_command = PluginCommand::TogglePanePinned;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 pub fn set_floating_pane_pinned(pane_id: PaneId, should_be_pinned: bool) {
     let plugin_command = PluginCommand::SetFloatingPanePinned(pane_id, should_be_pinned);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -1142,6 +1485,16 @@ pub fn set_floating_pane_pinned(pane_id: PaneId, should_be_pinned: bool) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Set the z-index of a floating pane, panes with a higher z-index are rendered in front of panes
+/// with a lower one (the default is 0). Among panes with the same z-index, the last focused one is
+/// in front
+pub fn set_pane_z_index(pane_id: PaneId, z_index: usize) {
+    let plugin_command = PluginCommand::SetPaneZIndex(pane_id, z_index);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 pub fn stack_panes(pane_ids: Vec<PaneId>) {
     let plugin_command = PluginCommand::StackPanes(pane_ids);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -1149,8 +1502,189 @@ pub fn stack_panes(pane_ids: Vec<PaneId>) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Group these panes so that what the user types in any of them is typed in the others of the
+/// group that are in the same tab, the group is named in the frames of its panes
+pub fn create_synchronized_group(
+    name: &str,
+    pane_ids: Vec<PaneId>,
+) -> Result<GroupId, PluginError> {
+    let plugin_command = PluginCommand::CreateSynchronizedGroup(name.to_owned(), pane_ids);
+    let payload = run_plugin_command_with_response(plugin_command)?;
+    let synchronized_group = SynchronizedGroup::decode(payload.as_slice())
+        .map_err(|e| PluginError::HostError(e.to_string()))?;
+    Ok(synchronized_group.group_id)
+}
+
+/// Stop sending input to all the panes of a group created with [create_synchronized_group]
+pub fn dissolve_group(group_id: GroupId) {
+    let plugin_command = PluginCommand::DissolveSynchronizedGroup(group_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Focus panes by hovering over them with the mouse rather than by clicking them, for the rest of
+/// the session (overrides the `focus_follows_mouse` config option)
+pub fn set_focus_follows_mouse(enable: bool) {
+    let plugin_command = PluginCommand::SetFocusFollowsMouse(enable);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Run a command in an existing terminal pane by typing it (followed by a newline) into the pane's
+/// STDIN
+pub fn run_command_in_pane(pane_id: PaneId, command_to_run: CommandToRun) {
+    run_command_in_pane_with_env_variables(pane_id, command_to_run, BTreeMap::new())
+}
+
+/// Like `run_command_in_pane`, setting `env_variables` for this command only
+pub fn run_command_in_pane_with_env_variables(
+    pane_id: PaneId,
+    command_to_run: CommandToRun,
+    env_variables: BTreeMap<String, String>,
+) {
+    let plugin_command = PluginCommand::RunCommandInPane(pane_id, command_to_run, env_variables);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Search the scrollback and viewport of a pane for the given query, the matches will be sent to
+/// this plugin in a `SearchResult` event
+pub fn search_pane(pane_id: PaneId, query: &str, case_sensitive: bool) {
+    let plugin_command =
+        PluginCommand::SearchPane(pane_id, query.to_owned(), case_sensitive, false);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Like `search_pane`, but the query is a regular expression, an invalid expression yields no
+/// matches
+pub fn search_pane_with_regex(pane_id: PaneId, pattern: &str, case_sensitive: bool) {
+    let plugin_command =
+        PluginCommand::SearchPane(pane_id, pattern.to_owned(), case_sensitive, true);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Get the cells currently visible in a pane (characters, colors and boldness), these will be sent
+/// to this plugin in a `PaneSnapshot` event
+pub fn pane_snapshot(pane_id: PaneId) {
+    let plugin_command = PluginCommand::PaneSnapshot(pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Open a copy of a pane next to it in the same tab: terminal panes run the same command in the
+/// folder the original is currently in, plugin panes get a fresh instance of the same plugin with
+/// the same configuration. The id of the new pane will be sent to this plugin in a
+/// `PaneDuplicated` event
+pub fn duplicate_pane(pane_id: PaneId) {
+    let plugin_command = PluginCommand::DuplicatePane(pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Open a new instance of a plugin in a floating pane placed at the given coordinates (eg. for
+/// context menus, tooltips and popups). Each coordinate can be either a number of cells
+/// (eg. `10`) or a percentage of the screen (eg. `"25%"`).
+/// `config` is merged on top of any configuration already present in `run`.
+/// Returns [`PluginError::InvalidArgument`] without opening anything if one of the coordinates
+/// is neither.
+pub fn create_floating_pane_at(
+    x: impl ToString,
+    y: impl ToString,
+    width: impl ToString,
+    height: impl ToString,
+    run: RunPluginOrAlias,
+    config: BTreeMap<String, String>,
+) -> Result<(), PluginError> {
+    let parse_coordinate = |name: &str, value: String| {
+        SplitSize::from_str(&value).map_err(|e| {
+            PluginError::InvalidArgument(format!("invalid {} '{}': {}", name, value, e))
+        })
+    };
+    let coordinates = FloatingPaneCoordinates {
+        x: Some(parse_coordinate("x", x.to_string())?),
+        y: Some(parse_coordinate("y", y.to_string())?),
+        width: Some(parse_coordinate("width", width.to_string())?),
+        height: Some(parse_coordinate("height", height.to_string())?),
+        pinned: None,
+    };
+    let mut plugin_config = run
+        .get_configuration()
+        .map(|c| c.inner().clone())
+        .unwrap_or_default();
+    plugin_config.extend(config);
+    let plugin_command = PluginCommand::CreateFloatingPaneAt {
+        url: run.location_string(),
+        config: plugin_config,
+        coordinates,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    Ok(())
+}
+
+/// Move the floating pane with the given id so that its top-left corner is at the given
+/// coordinates (in cells, relative to the top-left of the tab area). Meant to be called
+/// repeatedly while tracking a mouse drag. Coordinates that would place part of the pane outside
+/// of the viewport are rejected.
+pub fn drag_pane_to_coordinates(pane_id: PaneId, new_x: usize, new_y: usize) {
+    let plugin_command = PluginCommand::DragPaneToCoordinates(pane_id, new_x, new_y);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Subscribe to a named channel, receiving an `Event::ChannelMessage` whenever any plugin
+/// publishes to it (including this one)
+pub fn subscribe_to_channel(channel: &str) {
+    let plugin_command = PluginCommand::SubscribeToChannel(channel.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Publish a payload to all plugins subscribed to the named channel
+pub fn publish_to_channel(channel: &str, payload: &[u8]) {
+    let plugin_command = PluginCommand::PublishToChannel(channel.to_owned(), payload.to_vec());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Write `content` directly into the screen buffer of the terminal pane with the given id, as if
+/// it were output by the pane's own process (ANSI escape sequences are interpreted and `\n` does
+/// not imply a carriage return). Meant for seeding pane content in plugin integration tests,
+/// requires the `testing` feature and is refused unless Zellij runs with `--debug`.
+#[cfg(any(test, feature = "testing"))]
+pub fn set_pane_content(pane_id: PaneId, content: &str) {
+    let plugin_command = PluginCommand::SetPaneContent(pane_id, content.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Utility Functions
 
+/// Show the terminal cursor at the given (row, column) of the plugin pane (0 based, relative to
+/// its top-left corner), or hide it if `None`. Meant to be called at the end of `render` (eg. to
+/// show where text will be typed into an input field) - the cursor is hidden again before each
+/// render. Coordinates past the edges of the pane are clamped to its last row and column.
+pub fn show_cursor(position: Option<(usize, usize)>) {
+    match position {
+        Some((row, col)) => print!("\u{1b}[{};{}H\u{1b}[?25h", row + 1, col + 1),
+        None => print!("\u{1b}[?25l"),
+    }
+}
+
 #[allow(unused)]
 /// Returns the `TabInfo` corresponding to the currently active tab
 pub fn get_focused_tab(tab_infos: &Vec<TabInfo>) -> Option<TabInfo> {
@@ -1195,6 +1729,24 @@ pub fn bytes_from_stdin() -> Result<Vec<u8>> {
     serde_json::from_str(&json).with_context(err_context)
 }
 
+// used by commands that return a value, the host answers them with a `PluginCommandResponse`
+// carrying either the serialized value or the reason the command failed
+fn run_plugin_command_with_response(plugin_command: PluginCommand) -> Result<Vec<u8>, PluginError> {
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command_with_response() };
+    let bytes = bytes_from_stdin().map_err(|e| PluginError::HostError(e.to_string()))?;
+    ProtobufPluginCommandResponse::decode(bytes.as_slice())
+        .map_err(|e| PluginError::HostError(e.to_string()))?
+        .into_result()
+}
+
+fn event_from_bytes(bytes: &[u8]) -> Result<Event, PluginError> {
+    let protobuf_event =
+        ProtobufEvent::decode(bytes).map_err(|e| PluginError::HostError(e.to_string()))?;
+    Event::try_from(protobuf_event).map_err(|e| PluginError::HostError(e.to_string()))
+}
+
 #[doc(hidden)]
 pub fn object_to_stdout(object: &impl Serialize) {
     // TODO: no crashy
@@ -1209,6 +1761,16 @@ pub fn post_message_to(plugin_message: PluginMessage) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Post a message to a specific instance of a worker pool registered with
+/// [`register_worker_pool!`](crate::register_worker_pool), the instance id is taken modulo the
+/// size of the pool
+pub fn post_message_to_worker_instance(instance_id: u32, plugin_message: PluginMessage) {
+    let plugin_command = PluginCommand::PostMessageToWorkerInstance(plugin_message, instance_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Post a message to this plugin, for more information please see [Plugin Workers](https://zellij.dev/documentation/plugin-api-workers.md)
 pub fn post_message_to_plugin(plugin_message: PluginMessage) {
     let plugin_command = PluginCommand::PostMessageToPlugin(plugin_message);
@@ -1220,4 +1782,5 @@ pub fn post_message_to_plugin(plugin_message: PluginMessage) {
 #[link(wasm_import_module = "zellij")]
 extern "C" {
     fn host_run_plugin_command();
+    fn host_run_plugin_command_with_response();
 }
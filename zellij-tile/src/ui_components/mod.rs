@@ -1,4 +1,5 @@
 mod nested_list;
+mod progress_bar;
 mod ribbon;
 mod table;
 mod text;
@@ -7,6 +8,7 @@ pub use zellij_utils::plugin_api;
 pub use zellij_utils::prost::{self, *};
 
 pub use nested_list::*;
+pub use progress_bar::*;
 pub use ribbon::*;
 pub use table::*;
 pub use text::*;
@@ -0,0 +1,57 @@
+use super::Text;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressBarStyle {
+    /// A bar filled with the theme's green color
+    #[default]
+    Filled,
+    /// A bar filled with the theme's colors going from red to green
+    Gradient,
+    /// A spinner, for operations whose progress is not known
+    Spinner,
+}
+
+impl ProgressBarStyle {
+    fn serialize(&self) -> &'static str {
+        match self {
+            ProgressBarStyle::Filled => "filled",
+            ProgressBarStyle::Gradient => "gradient",
+            ProgressBarStyle::Spinner => "spinner",
+        }
+    }
+}
+
+/// Print a progress bar `width` columns wide at the `x`/`y` coordinates, preceded by `label`.
+/// `percent` is clamped between 0.0 and 100.0, a `NaN` percent is rendered as a spinner.
+pub fn print_progress_bar(
+    percent: f64,
+    label: &str,
+    x: usize,
+    y: usize,
+    width: usize,
+    style: ProgressBarStyle,
+) {
+    print!(
+        "{}",
+        serialize_progress_bar(percent, label, x, y, width, style)
+    )
+}
+
+pub fn serialize_progress_bar(
+    percent: f64,
+    label: &str,
+    x: usize,
+    y: usize,
+    width: usize,
+    style: ProgressBarStyle,
+) -> String {
+    format!(
+        "\u{1b}Pzprogress_bar;{}/{}/{}/;{};{};{}\u{1b}\\",
+        x,
+        y,
+        width,
+        style.serialize(),
+        percent.clamp(0.0, 100.0),
+        Text::new(label).serialize()
+    )
+}
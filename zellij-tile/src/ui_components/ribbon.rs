@@ -1,5 +1,6 @@
-use super::Text;
+use super::{print_text_with_coordinates, Text};
 use std::borrow::Borrow;
+use zellij_utils::data::{BareKey, KeyWithModifier};
 
 pub fn print_ribbon(text: Text) {
     print!("\u{1b}Pzribbon;{}\u{1b}\\", text.serialize());
@@ -79,3 +80,104 @@ where
     result.push_str(&serialize_ribbon_line(ribbons));
     result
 }
+
+// the arrows and padding around the text of a ribbon
+const RIBBON_DECORATION_WIDTH: usize = 4;
+
+/// Print a horizontal group of mutually exclusive ribbons at the `x`/`y` coordinates, the one at
+/// `selected_index` rendered as selected. Options are `(label, is_enabled)` pairs, disabled
+/// options are printed as plain text rather than as ribbons and are never rendered as selected.
+pub fn print_ribbon_group(options: Vec<(&str, bool)>, selected_index: usize, x: usize, y: usize) {
+    let mut x = x;
+    for (index, (label, is_enabled)) in options.into_iter().enumerate() {
+        if is_enabled {
+            let text = if index == selected_index {
+                Text::new(label).selected()
+            } else {
+                Text::new(label)
+            };
+            print_ribbon_with_coordinates(text, x, y, None, None);
+        } else {
+            print_text_with_coordinates(Text::new(format!("  {}  ", label)), x, y, None, None);
+        }
+        x += label.chars().count() + RIBBON_DECORATION_WIDTH;
+    }
+}
+
+/// The selection state of a [`print_ribbon_group`], moved between its enabled options with the
+/// left and right arrow keys.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RibbonGroup {
+    options: Vec<(String, bool)>,
+    selected_index: usize,
+}
+
+impl RibbonGroup {
+    /// A group with its first enabled option selected
+    pub fn new(options: Vec<(&str, bool)>) -> Self {
+        let options: Vec<(String, bool)> = options
+            .into_iter()
+            .map(|(label, is_enabled)| (label.to_owned(), is_enabled))
+            .collect();
+        let selected_index = options
+            .iter()
+            .position(|(_, is_enabled)| *is_enabled)
+            .unwrap_or(0);
+        RibbonGroup {
+            options,
+            selected_index,
+        }
+    }
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+    pub fn selected_label(&self) -> Option<&str> {
+        self.options
+            .get(self.selected_index)
+            .map(|(label, _)| label.as_str())
+    }
+    /// Returns `false` if there is no enabled option at `index`
+    pub fn select(&mut self, index: usize) -> bool {
+        match self.options.get(index) {
+            Some((_, true)) => {
+                self.selected_index = index;
+                true
+            },
+            _ => false,
+        }
+    }
+    /// Select the next enabled option to the right, returns `false` if there is none
+    pub fn select_next(&mut self) -> bool {
+        match (self.selected_index + 1..self.options.len()).find(|i| self.options[*i].1) {
+            Some(index) => self.select(index),
+            None => false,
+        }
+    }
+    /// Select the next enabled option to the left, returns `false` if there is none
+    pub fn select_previous(&mut self) -> bool {
+        match (0..self.selected_index).rev().find(|i| self.options[*i].1) {
+            Some(index) => self.select(index),
+            None => false,
+        }
+    }
+    /// Move the selection with the left and right arrow keys, returns `true` if the selection
+    /// changed
+    pub fn handle_key(&mut self, key: &KeyWithModifier) -> bool {
+        if !key.has_no_modifiers() {
+            return false;
+        }
+        match key.bare_key {
+            BareKey::Right => self.select_next(),
+            BareKey::Left => self.select_previous(),
+            _ => false,
+        }
+    }
+    pub fn render(&self, x: usize, y: usize) {
+        let options = self
+            .options
+            .iter()
+            .map(|(label, is_enabled)| (label.as_str(), *is_enabled))
+            .collect();
+        print_ribbon_group(options, self.selected_index, x, y);
+    }
+}
@@ -82,3 +82,237 @@ pub fn serialize_table_with_coordinates(
         table.serialize()
     )
 }
+
+/// How wide a column of a [`TableBuilder`] is, widths are counted in terminal columns (so that eg.
+/// CJK characters and emoji count as two)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnWidth {
+    /// Exactly this many characters
+    Fixed(usize),
+    /// This percentage of the width of the table
+    Percentage(f32),
+    /// An equal share of the width left over by the other columns, a column whose cells are all
+    /// narrower than its share leaves the rest of it to the other `Fill` columns
+    Fill,
+}
+
+/// What to do with the cells of a column that are wider than it
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Cut the cell at the width of the column
+    #[default]
+    Truncate,
+    /// Continue the cell on the following lines of its row
+    Wrap,
+    /// Cut the cell and mark it with a trailing `…`
+    Ellipsis,
+}
+
+/// Build a [`Table`] whose columns have set widths, cells are padded to the width of their column
+/// and the ones that do not fit in it are cut or wrapped according to its [`OverflowPolicy`].
+/// Rows can have more cells than there are columns, those extra columns `Fill` and `Truncate`.
+#[derive(Debug, Default, Clone)]
+pub struct TableBuilder {
+    columns: Vec<(ColumnWidth, OverflowPolicy)>,
+    rows: Vec<Vec<Text>>,
+}
+
+impl TableBuilder {
+    pub fn new() -> Self {
+        TableBuilder::default()
+    }
+    pub fn column(mut self, width: ColumnWidth, overflow: OverflowPolicy) -> Self {
+        self.columns.push((width, overflow));
+        self
+    }
+    pub fn add_row(mut self, row: Vec<impl ToString>) -> Self {
+        self.rows
+            .push(row.iter().map(|c| Text::new(c.to_string())).collect());
+        self
+    }
+    pub fn add_styled_row(mut self, row: Vec<Text>) -> Self {
+        self.rows.push(row);
+        self
+    }
+    /// The widths of the columns of a table `table_width` characters wide, not including the
+    /// space that separates each column from the next one
+    pub fn column_widths(&self, table_width: usize) -> Vec<usize> {
+        let columns = self.column_specs();
+        let content_widths = self.content_widths(columns.len());
+        let available_width = table_width.saturating_sub(columns.len());
+        // first pass: the columns that have a width of their own
+        let mut widths: Vec<Option<usize>> = columns
+            .iter()
+            .map(|(width, _)| match width {
+                ColumnWidth::Fixed(width) => Some(*width),
+                ColumnWidth::Percentage(percentage) => {
+                    Some((available_width as f32 * percentage.clamp(0.0, 100.0) / 100.0) as usize)
+                },
+                ColumnWidth::Fill => None,
+            })
+            .collect();
+        // second pass: if the content of some fill columns is wider than an equal share of the
+        // space left over, the ones whose content is narrower only take its width
+        let used_width: usize = widths.iter().flatten().sum();
+        let mut remaining_width = available_width.saturating_sub(used_width);
+        let fill_columns: Vec<usize> = (0..columns.len())
+            .filter(|i| widths[*i].is_none())
+            .collect();
+        let content_fits_evenly = fill_columns
+            .iter()
+            .all(|i| content_widths[*i] <= remaining_width / fill_columns.len());
+        let mut unmeasured_columns = if content_fits_evenly {
+            vec![]
+        } else {
+            fill_columns.clone()
+        };
+        loop {
+            if unmeasured_columns.is_empty() {
+                break;
+            }
+            let share = remaining_width / unmeasured_columns.len();
+            let (narrow_columns, wide_columns): (Vec<usize>, Vec<usize>) = unmeasured_columns
+                .iter()
+                .partition(|i| content_widths[**i] <= share);
+            if narrow_columns.is_empty() {
+                break;
+            }
+            for i in narrow_columns {
+                widths[i] = Some(content_widths[i]);
+                remaining_width -= content_widths[i];
+            }
+            unmeasured_columns = wide_columns;
+        }
+        // third pass: what is left is split evenly between the columns that need more of it, or
+        // between all fill columns if none of them does
+        let columns_to_fill = if unmeasured_columns.is_empty() {
+            fill_columns
+        } else {
+            unmeasured_columns
+        };
+        for (fill_column_index, i) in columns_to_fill.iter().enumerate() {
+            let mut fill_width = remaining_width / columns_to_fill.len();
+            // the first columns get the characters that cannot be evenly split
+            if fill_column_index < remaining_width % columns_to_fill.len() {
+                fill_width += 1;
+            }
+            widths[*i] = Some(widths[*i].unwrap_or(0) + fill_width);
+        }
+        widths.into_iter().map(|w| w.unwrap_or(0)).collect()
+    }
+    // the width of the widest cell of each column
+    fn content_widths(&self, column_count: usize) -> Vec<usize> {
+        let mut content_widths = vec![0; column_count];
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                let cell_width = cell.char_widths().iter().sum();
+                content_widths[i] = content_widths[i].max(cell_width);
+            }
+        }
+        content_widths
+    }
+    /// Lay out the columns in a table `table_width` characters wide
+    pub fn build(&self, table_width: usize) -> Table {
+        let columns = self.column_specs();
+        let widths = self.column_widths(table_width);
+        let mut table = Table::new();
+        for row in &self.rows {
+            let mut cells: Vec<Vec<Text>> = columns
+                .iter()
+                .zip(widths.iter())
+                .enumerate()
+                .map(|(i, ((_, overflow), width))| {
+                    let cell = row.get(i).cloned().unwrap_or_else(|| Text::new(""));
+                    fit_cell(&cell, *width, *overflow)
+                })
+                .collect();
+            let row_height = cells.iter().map(|c| c.len()).max().unwrap_or(1);
+            for (cell, width) in cells.iter_mut().zip(widths.iter()) {
+                // the lines that only wrapped cells need are blank in the other ones, keeping
+                // their styling so that selected rows are highlighted all the way
+                let blank_line = cell
+                    .first()
+                    .map(|c| c.slice(0, 0))
+                    .unwrap_or_else(|| Text::new(""))
+                    .append(&" ".repeat(*width));
+                cell.resize(row_height, blank_line);
+            }
+            for line_index in 0..row_height {
+                table = table
+                    .add_styled_row(cells.iter().map(|cell| cell[line_index].clone()).collect());
+            }
+        }
+        table
+    }
+    fn column_specs(&self) -> Vec<(ColumnWidth, OverflowPolicy)> {
+        let column_count = self
+            .rows
+            .iter()
+            .map(|row| row.len())
+            .max()
+            .unwrap_or(0)
+            .max(self.columns.len());
+        let mut columns = self.columns.clone();
+        columns.resize(column_count, (ColumnWidth::Fill, OverflowPolicy::Truncate));
+        columns
+    }
+}
+
+// the lines of a cell fitted into a column of the given width
+fn fit_cell(cell: &Text, width: usize, overflow: OverflowPolicy) -> Vec<Text> {
+    let char_widths = cell.char_widths();
+    let cell_width: usize = char_widths.iter().sum();
+    if cell_width <= width {
+        return vec![pad_line(cell.clone(), cell_width, width)];
+    }
+    match overflow {
+        OverflowPolicy::Truncate => {
+            let (end, line_width) = chars_fitting(&char_widths, 0, width);
+            vec![pad_line(cell.slice(0, end), line_width, width)]
+        },
+        OverflowPolicy::Ellipsis if width == 0 => vec![cell.slice(0, 0)],
+        OverflowPolicy::Ellipsis => {
+            let (end, line_width) = chars_fitting(&char_widths, 0, width - 1);
+            vec![pad_line(
+                cell.slice(0, end).append("…"),
+                line_width + 1,
+                width,
+            )]
+        },
+        OverflowPolicy::Wrap if width == 0 => vec![cell.slice(0, 0)],
+        OverflowPolicy::Wrap => {
+            let mut lines = vec![];
+            let mut start = 0;
+            while start < char_widths.len() {
+                let (mut end, mut line_width) = chars_fitting(&char_widths, start, width);
+                if end == start {
+                    // a character wider than the column gets a line of its own
+                    end = start + 1;
+                    line_width = char_widths[start];
+                }
+                lines.push(pad_line(cell.slice(start, end), line_width, width));
+                start = end;
+            }
+            lines
+        },
+    }
+}
+
+// the end (exclusive) and width of the characters from `start` that fit in the given width
+fn chars_fitting(char_widths: &[usize], start: usize, width: usize) -> (usize, usize) {
+    let mut end = start;
+    let mut line_width = 0;
+    while end < char_widths.len() && line_width + char_widths[end] <= width {
+        line_width += char_widths[end];
+        end += 1;
+    }
+    (end, line_width)
+}
+
+fn pad_line(line: Text, line_width: usize, width: usize) -> Text {
+    line.append(&" ".repeat(width.saturating_sub(line_width)))
+}
+
+#[cfg(test)]
+#[path = "./unit/table_tests.rs"]
+mod table_tests;
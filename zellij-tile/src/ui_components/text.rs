@@ -1,5 +1,6 @@
 use std::ops::Bound;
 use std::ops::RangeBounds;
+use unicode_width::UnicodeWidthChar;
 
 #[derive(Debug, Default, Clone)]
 pub struct Text {
@@ -7,6 +8,7 @@ pub struct Text {
     selected: bool,
     opaque: bool,
     indices: Vec<Vec<usize>>,
+    url: Option<String>,
 }
 
 impl Text {
@@ -19,6 +21,7 @@ impl Text {
             selected: false,
             opaque: false,
             indices: vec![],
+            url: None,
         }
     }
     pub fn selected(mut self) -> Self {
@@ -29,6 +32,12 @@ impl Text {
         self.opaque = true;
         self
     }
+    /// Render this text as an OSC 8 hyperlink pointing to `url`. Terminals that do not support
+    /// OSC 8 will display the text without the link.
+    pub fn with_url(mut self, url: &str) -> Self {
+        self.url = Some(url.to_owned());
+        self
+    }
     pub fn color_indices(mut self, index_level: usize, mut indices: Vec<usize>) -> Self {
         self.pad_indices(index_level);
         self.indices
@@ -54,6 +63,39 @@ impl Text {
             .map(|i| i.append(&mut indices.into_iter().collect()));
         self
     }
+    // the number of columns each character of this text takes up in the terminal
+    pub(crate) fn char_widths(&self) -> Vec<usize> {
+        self.text.chars().map(|c| c.width().unwrap_or(0)).collect()
+    }
+    // the characters from `start` to `end` (exclusive) of this text, keeping their styling
+    pub(crate) fn slice(&self, start: usize, end: usize) -> Self {
+        Text {
+            text: self
+                .text
+                .chars()
+                .skip(start)
+                .take(end.saturating_sub(start))
+                .collect(),
+            selected: self.selected,
+            opaque: self.opaque,
+            indices: self
+                .indices
+                .iter()
+                .map(|indices| {
+                    indices
+                        .iter()
+                        .filter(|i| **i >= start && **i < end)
+                        .map(|i| i - start)
+                        .collect()
+                })
+                .collect(),
+            url: self.url.clone(),
+        }
+    }
+    pub(crate) fn append(mut self, text: &str) -> Self {
+        self.text.push_str(text);
+        self
+    }
     fn pad_indices(&mut self, index_level: usize) {
         if self.indices.get(index_level).is_none() {
             for _ in self.indices.len()..=index_level {
@@ -92,6 +134,16 @@ impl Text {
             prefix = format!("x{}", prefix);
         }
 
+        if let Some(url) = &self.url {
+            let url = url
+                .as_bytes()
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            prefix = format!("u{}#{}", url, prefix);
+        }
+
         format!("{}{}{}", prefix, indices, text)
     }
 }
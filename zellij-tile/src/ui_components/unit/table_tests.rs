@@ -0,0 +1,123 @@
+use super::*;
+
+fn lines(cell: &[Text]) -> Vec<String> {
+    cell.iter().map(|line| line.serialize()).collect()
+}
+
+fn expected_lines(expected: &[&str]) -> Vec<String> {
+    expected
+        .iter()
+        .map(|line| Text::new(line).serialize())
+        .collect()
+}
+
+#[test]
+fn fixed_and_percentage_columns_take_their_width_first() {
+    let table_builder = TableBuilder::new()
+        .column(ColumnWidth::Fixed(10), OverflowPolicy::Truncate)
+        .column(ColumnWidth::Percentage(50.0), OverflowPolicy::Truncate)
+        .column(ColumnWidth::Fill, OverflowPolicy::Truncate);
+    // 3 characters separate the columns, leaving 100
+    assert_eq!(table_builder.column_widths(103), vec![10, 50, 40]);
+}
+
+#[test]
+fn fill_columns_split_the_remaining_width_evenly() {
+    let table_builder = TableBuilder::new()
+        .column(ColumnWidth::Fill, OverflowPolicy::Truncate)
+        .column(ColumnWidth::Fill, OverflowPolicy::Truncate)
+        .column(ColumnWidth::Fill, OverflowPolicy::Truncate)
+        .add_row(vec!["a", "b", "c"]);
+    assert_eq!(
+        table_builder.column_widths(14),
+        vec![4, 4, 3],
+        "the first columns get the characters that cannot be evenly split"
+    );
+}
+
+#[test]
+fn narrow_fill_columns_leave_their_space_to_wider_ones() {
+    let table_builder = TableBuilder::new()
+        .column(ColumnWidth::Fill, OverflowPolicy::Truncate)
+        .column(ColumnWidth::Fill, OverflowPolicy::Truncate)
+        .add_row(vec!["id", "a rather long description"])
+        .add_row(vec!["1234", "short"]);
+    assert_eq!(table_builder.column_widths(22), vec![4, 16]);
+}
+
+#[test]
+fn column_widths_account_for_wide_characters() {
+    let table_builder = TableBuilder::new()
+        .column(ColumnWidth::Fill, OverflowPolicy::Truncate)
+        .column(ColumnWidth::Fill, OverflowPolicy::Truncate)
+        .add_row(vec!["日本語", "a rather long description"]);
+    assert_eq!(table_builder.column_widths(22), vec![6, 14]);
+}
+
+#[test]
+fn rows_with_more_cells_than_columns_get_fill_columns() {
+    let table_builder = TableBuilder::new()
+        .column(ColumnWidth::Fixed(5), OverflowPolicy::Truncate)
+        .add_row(vec!["a", "b"]);
+    assert_eq!(table_builder.column_widths(12), vec![5, 5]);
+}
+
+#[test]
+fn cells_narrower_than_their_column_are_padded() {
+    assert_eq!(
+        lines(&fit_cell(&Text::new("abc"), 5, OverflowPolicy::Truncate)),
+        expected_lines(&["abc  "])
+    );
+}
+
+#[test]
+fn cells_wider_than_their_column_are_truncated() {
+    assert_eq!(
+        lines(&fit_cell(&Text::new("abcdef"), 4, OverflowPolicy::Truncate)),
+        expected_lines(&["abcd"])
+    );
+}
+
+#[test]
+fn cells_wider_than_their_column_are_ellipsized() {
+    assert_eq!(
+        lines(&fit_cell(&Text::new("abcdef"), 4, OverflowPolicy::Ellipsis)),
+        expected_lines(&["abc…"])
+    );
+}
+
+#[test]
+fn cells_wider_than_their_column_are_wrapped() {
+    assert_eq!(
+        lines(&fit_cell(&Text::new("abcdefghij"), 4, OverflowPolicy::Wrap)),
+        expected_lines(&["abcd", "efgh", "ij  "])
+    );
+}
+
+#[test]
+fn wide_characters_are_not_split_between_lines() {
+    assert_eq!(
+        lines(&fit_cell(&Text::new("日本語"), 5, OverflowPolicy::Truncate)),
+        expected_lines(&["日本 "])
+    );
+    assert_eq!(
+        lines(&fit_cell(&Text::new("日本語"), 5, OverflowPolicy::Ellipsis)),
+        expected_lines(&["日本…"])
+    );
+    assert_eq!(
+        lines(&fit_cell(&Text::new("日本語"), 3, OverflowPolicy::Wrap)),
+        expected_lines(&["日 ", "本 ", "語 "])
+    );
+}
+
+#[test]
+fn cells_do_not_fit_in_columns_without_width() {
+    assert_eq!(
+        lines(&fit_cell(&Text::new("abc"), 0, OverflowPolicy::Wrap)),
+        expected_lines(&[""])
+    );
+    assert_eq!(
+        lines(&fit_cell(&Text::new("abc"), 0, OverflowPolicy::Ellipsis)),
+        expected_lines(&[""])
+    );
+}
@@ -0,0 +1,58 @@
+use super::*;
+use zellij_utils::input::actions::Action;
+
+#[derive(Default)]
+struct TestPlugin {
+    completed_callbacks: Vec<(u32, Option<PaneId>)>,
+    updates: usize,
+}
+
+impl ZellijPlugin for TestPlugin {
+    fn update(&mut self, _event: Event) -> bool {
+        self.updates += 1;
+        true
+    }
+    fn on_action_complete(&mut self, callback_id: u32, pane_id: Option<PaneId>) {
+        self.completed_callbacks.push((callback_id, pane_id));
+    }
+}
+
+fn action_complete(context: &[(&str, &str)]) -> Event {
+    let context = context
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    Event::ActionComplete(
+        Action::NewPane(None, None, false),
+        Some(PaneId::Plugin(3)),
+        context,
+    )
+}
+
+#[test]
+fn completed_actions_with_a_callback_call_on_action_complete_before_update() {
+    let mut plugin = TestPlugin::default();
+    let should_render = dispatch_event(
+        &mut plugin,
+        action_complete(&[(CALLBACK_ID_CONTEXT_KEY, "7"), ("other", "context")]),
+    );
+    assert_eq!(
+        plugin.completed_callbacks,
+        vec![(7, Some(PaneId::Plugin(3)))]
+    );
+    assert_eq!(plugin.updates, 1, "the event is still passed to update");
+    assert!(should_render);
+}
+
+#[test]
+fn completed_actions_without_a_callback_are_only_passed_to_update() {
+    let mut plugin = TestPlugin::default();
+    dispatch_event(&mut plugin, action_complete(&[]));
+    dispatch_event(
+        &mut plugin,
+        action_complete(&[(CALLBACK_ID_CONTEXT_KEY, "not-a-number")]),
+    );
+    dispatch_event(&mut plugin, Event::Visible(true));
+    assert!(plugin.completed_callbacks.is_empty());
+    assert_eq!(plugin.updates, 3);
+}
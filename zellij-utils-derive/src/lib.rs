@@ -0,0 +1,108 @@
+//! Derive macros used by `zellij-utils`, see `zellij_utils::kdl::schema` for the traits they
+//! implement
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit, Meta};
+
+/// Implements `KdlSchema` for a struct with named fields, each field being a KDL node of the
+/// same name described by the field's doc comment.
+///
+/// Fields can be annotated with `#[kdl_schema(default = <literal>)]` to document their default
+/// value, or with `#[kdl_schema(skip)]` to leave them out of the schema.
+#[proc_macro_derive(KdlSchema, attributes(kdl_schema))]
+pub fn derive_kdl_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match kdl_schema_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn kdl_schema_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "KdlSchema can only be derived for structs with named fields",
+                ))
+            },
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "KdlSchema can only be derived for structs",
+            ))
+        },
+    };
+    let mut properties = vec![];
+    for field in fields {
+        let kdl_name = field
+            .ident
+            .as_ref()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default();
+        let description = doc_comment(&field.attrs);
+        let ty = &field.ty;
+        let mut property = quote! {
+            ::zellij_utils::kdl::schema::KdlSchemaProperty::new(
+                #kdl_name,
+                #description,
+                <#ty as ::zellij_utils::kdl::schema::KdlSchemaType>::kdl_schema(),
+            )
+        };
+        let mut skip = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("kdl_schema"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    let default: Lit = meta.value()?.parse()?;
+                    property = quote! { #property.with_default(#default) };
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported kdl_schema attribute, expected default or skip"))
+                }
+            })?;
+        }
+        if !skip {
+            properties.push(property);
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::zellij_utils::kdl::schema::KdlSchema for #name #ty_generics #where_clause {
+            fn kdl_schema_properties() -> ::std::vec::Vec<::zellij_utils::kdl::schema::KdlSchemaProperty> {
+                ::std::vec![#(#properties),*]
+            }
+        }
+    })
+}
+
+// doc comments are wrapped prose, so their lines are joined into a single paragraph
+fn doc_comment(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(name_value) => match &name_value.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc_line),
+                    ..
+                }) => Some(doc_line.value().trim().to_owned()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|doc_line| !doc_line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -476,6 +476,7 @@ pub enum ActionName {
     KeybindPipe = 84,
     TogglePanePinned = 85,
     MouseEvent = 86,
+    ToggleExclusiveFloatingPanes = 87,
 }
 impl ActionName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -568,6 +569,7 @@ impl ActionName {
             ActionName::KeybindPipe => "KeybindPipe",
             ActionName::TogglePanePinned => "TogglePanePinned",
             ActionName::MouseEvent => "MouseEvent",
+            ActionName::ToggleExclusiveFloatingPanes => "ToggleExclusiveFloatingPanes",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -657,6 +659,7 @@ impl ActionName {
             "KeybindPipe" => Some(Self::KeybindPipe),
             "TogglePanePinned" => Some(Self::TogglePanePinned),
             "MouseEvent" => Some(Self::MouseEvent),
+            "ToggleExclusiveFloatingPanes" => Some(Self::ToggleExclusiveFloatingPanes),
             _ => None,
         }
     }
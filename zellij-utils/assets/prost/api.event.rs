@@ -11,7 +11,7 @@ pub struct Event {
     pub name: i32,
     #[prost(
         oneof = "event::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36"
     )]
     pub payload: ::core::option::Option<event::Payload>,
 }
@@ -68,10 +68,162 @@ pub mod event {
         HostFolderChangedPayload(super::HostFolderChangedPayload),
         #[prost(message, tag = "25")]
         FailedToChangeHostFolderPayload(super::FailedToChangeHostFolderPayload),
+        #[prost(message, tag = "26")]
+        SearchResultPayload(super::SearchResultPayload),
+        #[prost(message, tag = "27")]
+        ChannelMessagePayload(super::ChannelMessagePayload),
+        #[prost(message, tag = "28")]
+        SessionRenamedPayload(super::SessionRenamedPayload),
+        #[prost(message, tag = "29")]
+        ActionCompletePayload(super::ActionCompletePayload),
+        #[prost(message, tag = "30")]
+        ConfigUpdatePayload(super::ConfigUpdatePayload),
+        #[prost(message, tag = "31")]
+        PaneSnapshotPayload(super::PaneSnapshotPayload),
+        #[prost(string, tag = "32")]
+        PastedTextPayload(::prost::alloc::string::String),
+        #[prost(message, tag = "33")]
+        PaneDuplicatedPayload(super::PaneDuplicatedPayload),
+        #[prost(message, tag = "34")]
+        PluginDependenciesPayload(super::PluginInfo),
+        #[prost(message, tag = "35")]
+        PaneSelectionPayload(super::PaneSelectionPayload),
+        #[prost(message, tag = "36")]
+        CommandPipeResultPayload(super::CommandPipeResultPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandPipeResultPayload {
+    #[prost(int32, tag = "1")]
+    pub exit_code: i32,
+    #[prost(string, tag = "2")]
+    pub stdout: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub stderr: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "4")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneSelectionPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag = "2")]
+    pub selected_text: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneDuplicatedPayload {
+    #[prost(message, optional, tag = "1")]
+    pub original_id: ::core::option::Option<PaneId>,
+    #[prost(message, optional, tag = "2")]
+    pub new_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneSnapshotPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(message, repeated, tag = "2")]
+    pub rows: ::prost::alloc::vec::Vec<CellRow>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CellRow {
+    #[prost(message, repeated, tag = "1")]
+    pub cells: ::prost::alloc::vec::Vec<Cell>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Cell {
+    #[prost(string, tag = "1")]
+    pub character: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub fg: ::core::option::Option<super::style::Color>,
+    #[prost(message, optional, tag = "3")]
+    pub bg: ::core::option::Option<super::style::Color>,
+    #[prost(bool, tag = "4")]
+    pub bold: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfigUpdatePayload {
+    #[prost(message, repeated, tag = "1")]
+    pub changed_options: ::prost::alloc::vec::Vec<OptionChange>,
+    #[prost(message, repeated, tag = "2")]
+    pub added_keybinds: ::prost::alloc::vec::Vec<KeybindChange>,
+    #[prost(message, repeated, tag = "3")]
+    pub removed_keybinds: ::prost::alloc::vec::Vec<KeybindChange>,
+    #[prost(bool, tag = "4")]
+    pub changed_theme: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OptionChange {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub old_value: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub new_value: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeybindChange {
+    #[prost(enumeration = "super::input_mode::InputMode", tag = "1")]
+    pub mode: i32,
+    #[prost(message, optional, tag = "2")]
+    pub key_bind: ::core::option::Option<KeyBind>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ActionCompletePayload {
+    #[prost(message, optional, tag = "1")]
+    pub action: ::core::option::Option<super::action::Action>,
+    #[prost(message, optional, tag = "2")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(message, repeated, tag = "3")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionRenamedPayload {
+    #[prost(string, tag = "1")]
+    pub new_session_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChannelMessagePayload {
+    #[prost(string, tag = "1")]
+    pub channel: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub payload: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchResultPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag = "2")]
+    pub query: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub matches: ::prost::alloc::vec::Vec<SearchMatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatch {
+    #[prost(uint32, tag = "1")]
+    pub row: u32,
+    #[prost(uint32, tag = "2")]
+    pub col_start: u32,
+    #[prost(uint32, tag = "3")]
+    pub col_end: u32,
+    #[prost(string, tag = "4")]
+    pub context_line: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FailedToChangeHostFolderPayload {
     #[prost(string, optional, tag = "1")]
     pub error_message: ::core::option::Option<::prost::alloc::string::String>,
@@ -308,6 +460,8 @@ pub struct SessionManifest {
     pub available_layouts: ::prost::alloc::vec::Vec<LayoutInfo>,
     #[prost(message, repeated, tag = "7")]
     pub plugins: ::prost::alloc::vec::Vec<PluginInfo>,
+    #[prost(string, repeated, tag = "8")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -318,6 +472,14 @@ pub struct PluginInfo {
     pub plugin_url: ::prost::alloc::string::String,
     #[prost(message, repeated, tag = "3")]
     pub plugin_config: ::prost::alloc::vec::Vec<ContextItem>,
+    #[prost(message, repeated, tag = "4")]
+    pub dependencies: ::prost::alloc::vec::Vec<PluginDependency>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginDependency {
+    #[prost(string, tag = "1")]
+    pub url: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -382,6 +544,12 @@ pub struct PaneInfo {
     pub plugin_url: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "22")]
     pub is_selectable: bool,
+    #[prost(uint32, optional, tag = "23")]
+    pub scrollback_size: ::core::option::Option<u32>,
+    #[prost(string, optional, tag = "24")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "25")]
+    pub terminal_command_argv: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -406,6 +574,8 @@ pub struct TabInfo {
     pub active_swap_layout_name: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "10")]
     pub is_swap_layout_dirty: bool,
+    #[prost(bool, tag = "11")]
+    pub pinned: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -422,6 +592,8 @@ pub struct ModeUpdatePayload {
     pub session_name: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(enumeration = "super::input_mode::InputMode", optional, tag = "6")]
     pub base_mode: ::core::option::Option<i32>,
+    #[prost(uint32, optional, tag = "7")]
+    pub client_id: ::core::option::Option<u32>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -486,6 +658,18 @@ pub enum EventType {
     ListClients = 26,
     HostFolderChanged = 27,
     FailedToChangeHostFolder = 28,
+    SearchResult = 29,
+    ChannelMessage = 30,
+    SessionRenamed = 31,
+    ConfigUpdate = 32,
+    ActionComplete = 33,
+    PaneSnapshot = 34,
+    PastedText = 35,
+    PaneDuplicated = 36,
+    PluginDependencies = 37,
+    PaneSelection = 38,
+    PaneCountExceeded = 39,
+    CommandPipeResult = 40,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -523,6 +707,18 @@ impl EventType {
             EventType::ListClients => "ListClients",
             EventType::HostFolderChanged => "HostFolderChanged",
             EventType::FailedToChangeHostFolder => "FailedToChangeHostFolder",
+            EventType::SearchResult => "SearchResult",
+            EventType::ChannelMessage => "ChannelMessage",
+            EventType::SessionRenamed => "SessionRenamed",
+            EventType::ConfigUpdate => "ConfigUpdate",
+            EventType::ActionComplete => "ActionComplete",
+            EventType::PaneSnapshot => "PaneSnapshot",
+            EventType::PastedText => "PastedText",
+            EventType::PaneDuplicated => "PaneDuplicated",
+            EventType::PluginDependencies => "PluginDependencies",
+            EventType::PaneSelection => "PaneSelection",
+            EventType::PaneCountExceeded => "PaneCountExceeded",
+            EventType::CommandPipeResult => "CommandPipeResult",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -557,6 +753,18 @@ impl EventType {
             "ListClients" => Some(Self::ListClients),
             "HostFolderChanged" => Some(Self::HostFolderChanged),
             "FailedToChangeHostFolder" => Some(Self::FailedToChangeHostFolder),
+            "SearchResult" => Some(Self::SearchResult),
+            "ChannelMessage" => Some(Self::ChannelMessage),
+            "SessionRenamed" => Some(Self::SessionRenamed),
+            "ConfigUpdate" => Some(Self::ConfigUpdate),
+            "ActionComplete" => Some(Self::ActionComplete),
+            "PaneSnapshot" => Some(Self::PaneSnapshot),
+            "PastedText" => Some(Self::PastedText),
+            "PaneDuplicated" => Some(Self::PaneDuplicated),
+            "PluginDependencies" => Some(Self::PluginDependencies),
+            "PaneSelection" => Some(Self::PaneSelection),
+            "PaneCountExceeded" => Some(Self::PaneCountExceeded),
+            "CommandPipeResult" => Some(Self::CommandPipeResult),
             _ => None,
         }
     }
@@ -15,6 +15,8 @@ pub struct PipeMessage {
     pub args: ::prost::alloc::vec::Vec<Arg>,
     #[prost(bool, tag = "7")]
     pub is_private: bool,
+    #[prost(bool, optional, tag = "8")]
+    pub is_last: ::core::option::Option<bool>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -5,7 +5,7 @@ pub struct PluginCommand {
     pub name: i32,
     #[prost(
         oneof = "plugin_command::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126"
     )]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
@@ -180,10 +180,350 @@ pub mod plugin_command {
         SetFloatingPanePinnedPayload(super::SetFloatingPanePinnedPayload),
         #[prost(message, tag = "91")]
         StackPanesPayload(super::StackPanesPayload),
+        #[prost(message, tag = "92")]
+        RunCommandInPanePayload(super::RunCommandInPanePayload),
+        #[prost(message, tag = "93")]
+        OpenFileInEditorPayload(super::OpenFileInEditorPayload),
+        #[prost(message, tag = "94")]
+        SetSelfMinSizePayload(super::SetSelfMinSizePayload),
+        #[prost(message, tag = "95")]
+        SearchPanePayload(super::SearchPanePayload),
+        #[prost(message, tag = "96")]
+        CreateFloatingPaneAtPayload(super::CreateFloatingPaneAtPayload),
+        #[prost(message, tag = "97")]
+        SetSessionTagsPayload(super::SetSessionTagsPayload),
+        #[prost(message, tag = "98")]
+        DragPaneToCoordinatesPayload(super::DragPaneToCoordinatesPayload),
+        #[prost(message, tag = "99")]
+        SubscribeToChannelPayload(super::SubscribeToChannelPayload),
+        #[prost(message, tag = "100")]
+        PublishToChannelPayload(super::PublishToChannelPayload),
+        #[prost(message, tag = "101")]
+        SetPaneContentPayload(super::SetPaneContentPayload),
+        #[prost(bool, tag = "102")]
+        ToggleSelfPayload(bool),
+        #[prost(message, tag = "103")]
+        RunActionPayload(super::RunActionPayload),
+        #[prost(message, tag = "104")]
+        StoreBlobPayload(super::StoreBlobPayload),
+        #[prost(string, tag = "105")]
+        LoadBlobPayload(::prost::alloc::string::String),
+        #[prost(string, tag = "106")]
+        DeleteBlobPayload(::prost::alloc::string::String),
+        #[prost(message, tag = "107")]
+        EmitMetricPayload(super::EmitMetricPayload),
+        #[prost(message, tag = "108")]
+        PaneSnapshotPayload(super::PaneSnapshotPayload),
+        #[prost(message, tag = "109")]
+        ScrollToLineInPaneIdPayload(super::ScrollToLineInPaneIdPayload),
+        #[prost(message, tag = "110")]
+        SetPaneZIndexPayload(super::SetPaneZIndexPayload),
+        #[prost(message, tag = "111")]
+        CliPipeChunkPayload(super::CliPipeChunkPayload),
+        #[prost(message, tag = "112")]
+        OpenUrlPayload(super::OpenUrlPayload),
+        #[prost(message, tag = "113")]
+        PinTabPayload(super::PinTabPayload),
+        #[prost(message, tag = "114")]
+        UnpinTabPayload(super::UnpinTabPayload),
+        #[prost(message, tag = "115")]
+        RegisterGlobalHotkeyPayload(super::RegisterGlobalHotkeyPayload),
+        #[prost(message, tag = "116")]
+        DuplicatePanePayload(super::DuplicatePanePayload),
+        #[prost(message, tag = "117")]
+        AnnotateScrollbackLinePayload(super::AnnotateScrollbackLinePayload),
+        #[prost(message, tag = "118")]
+        ClearScrollbackAnnotationsPayload(super::ClearScrollbackAnnotationsPayload),
+        #[prost(message, tag = "119")]
+        SetClientModePayload(super::SetClientModePayload),
+        #[prost(string, tag = "120")]
+        GetPluginDependenciesPayload(::prost::alloc::string::String),
+        #[prost(message, tag = "121")]
+        CreateSynchronizedGroupPayload(super::CreateSynchronizedGroupPayload),
+        #[prost(uint32, tag = "122")]
+        DissolveSynchronizedGroupPayload(u32),
+        #[prost(bool, tag = "123")]
+        SetFocusFollowsMousePayload(bool),
+        #[prost(message, tag = "124")]
+        PipeTextToCommandPayload(super::PipeTextToCommandPayload),
+        #[prost(message, tag = "125")]
+        SetPaneScrollbackSizePayload(super::SetPaneScrollbackSizePayload),
+        #[prost(message, tag = "126")]
+        PostMessageToWorkerInstancePayload(super::PostMessageToWorkerInstancePayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPaneScrollbackSizePayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(uint32, tag = "2")]
+    pub scrollback_size: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PostMessageToWorkerInstancePayload {
+    #[prost(message, optional, tag = "1")]
+    pub message: ::core::option::Option<super::message::Message>,
+    #[prost(uint32, tag = "2")]
+    pub instance_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PipeTextToCommandPayload {
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub command_to_run: ::core::option::Option<super::command::Command>,
+    #[prost(uint64, optional, tag = "3")]
+    pub timeout_ms: ::core::option::Option<u64>,
+    #[prost(message, repeated, tag = "4")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateSynchronizedGroupPayload {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub pane_ids: ::prost::alloc::vec::Vec<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SynchronizedGroup {
+    #[prost(uint32, tag = "1")]
+    pub group_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetClientModePayload {
+    #[prost(uint32, tag = "1")]
+    pub client_id: u32,
+    #[prost(enumeration = "super::input_mode::InputMode", tag = "2")]
+    pub mode: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClearScrollbackAnnotationsPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnnotateScrollbackLinePayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(uint32, tag = "2")]
+    pub line_index: u32,
+    #[prost(string, tag = "3")]
+    pub annotation: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DuplicatePanePayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterGlobalHotkeyPayload {
+    #[prost(message, optional, tag = "1")]
+    pub key: ::core::option::Option<super::key::Key>,
+    #[prost(string, tag = "2")]
+    pub action_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PinTabPayload {
+    #[prost(uint32, tag = "1")]
+    pub tab_index: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnpinTabPayload {
+    #[prost(uint32, tag = "1")]
+    pub tab_index: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenUrlPayload {
+    #[prost(string, tag = "1")]
+    pub url: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CliPipeChunkPayload {
+    #[prost(string, tag = "1")]
+    pub pipe_name: ::prost::alloc::string::String,
+    #[prost(bytes, tag = "2")]
+    pub chunk: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "3")]
+    pub is_last: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPaneZIndexPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(uint32, tag = "2")]
+    pub z_index: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScrollToLineInPaneIdPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(uint32, tag = "2")]
+    pub line: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneSnapshotPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EmitMetricPayload {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(double, tag = "2")]
+    pub value: f64,
+    #[prost(message, repeated, tag = "3")]
+    pub labels: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreBlobPayload {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlobHash {
+    #[prost(string, tag = "1")]
+    pub hash: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlobContent {
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub data: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlobList {
+    #[prost(message, repeated, tag = "1")]
+    pub blobs: ::prost::alloc::vec::Vec<BlobInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlobInfo {
+    #[prost(string, tag = "1")]
+    pub hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub size: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunActionPayload {
+    #[prost(message, optional, tag = "1")]
+    pub action: ::core::option::Option<super::action::Action>,
+    #[prost(message, repeated, tag = "2")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPaneContentPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag = "2")]
+    pub content: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeToChannelPayload {
+    #[prost(string, tag = "1")]
+    pub channel: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PublishToChannelPayload {
+    #[prost(string, tag = "1")]
+    pub channel: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub payload: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DragPaneToCoordinatesPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(uint32, tag = "2")]
+    pub x: u32,
+    #[prost(uint32, tag = "3")]
+    pub y: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetSessionTagsPayload {
+    #[prost(string, tag = "1")]
+    pub session_name: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "2")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateFloatingPaneAtPayload {
+    #[prost(string, tag = "1")]
+    pub plugin_url: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub plugin_config: ::prost::alloc::vec::Vec<ContextItem>,
+    #[prost(message, optional, tag = "3")]
+    pub coordinates: ::core::option::Option<FloatingPaneCoordinates>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchPanePayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag = "2")]
+    pub query: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub case_sensitive: bool,
+    #[prost(bool, tag = "4")]
+    pub regex: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetSelfMinSizePayload {
+    #[prost(uint32, tag = "1")]
+    pub rows: u32,
+    #[prost(uint32, tag = "2")]
+    pub cols: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenFileInEditorPayload {
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(enumeration = "OpenLocation", tag = "2")]
+    pub open_location: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunCommandInPanePayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(message, optional, tag = "2")]
+    pub command_to_run: ::core::option::Option<super::command::Command>,
+    #[prost(message, repeated, tag = "3")]
+    pub env_variables: ::prost::alloc::vec::Vec<EnvVariable>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StackPanesPayload {
     #[prost(message, repeated, tag = "1")]
     pub pane_ids: ::prost::alloc::vec::Vec<PaneId>,
@@ -475,6 +815,8 @@ pub struct SwitchSessionPayload {
     pub layout: ::core::option::Option<super::event::LayoutInfo>,
     #[prost(string, optional, tag = "6")]
     pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "7")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -627,6 +969,18 @@ pub struct FixedOrPercentValue {
     #[prost(uint32, tag = "2")]
     pub value: u32,
 }
+/// commands that return a value to the plugin wrap it in this envelope, so that
+/// a failure can be told apart from an empty result
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginCommandResponse {
+    #[prost(enumeration = "PluginErrorCode", tag = "1")]
+    pub error_code: i32,
+    #[prost(string, optional, tag = "2")]
+    pub error_message: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub payload: ::prost::alloc::vec::Vec<u8>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum CommandName {
@@ -747,6 +1101,48 @@ pub enum CommandName {
     ChangeHostFolder = 114,
     SetFloatingPanePinned = 115,
     StackPanes = 116,
+    RunCommandInPane = 117,
+    OpenFileInEditor = 118,
+    SetSelfMinSize = 119,
+    SearchPane = 120,
+    CreateFloatingPaneAt = 121,
+    SetSessionTags = 122,
+    DragPaneToCoordinates = 123,
+    SubscribeToChannel = 124,
+    PublishToChannel = 125,
+    GetCurrentTabs = 126,
+    GetAllPanes = 127,
+    SetPaneContent = 128,
+    GetCurrentStyling = 129,
+    ListSessions = 130,
+    ToggleSelf = 131,
+    RunAction = 132,
+    StoreBlob = 133,
+    LoadBlob = 134,
+    ListBlobs = 135,
+    DeleteBlob = 136,
+    EmitMetric = 137,
+    PaneSnapshot = 138,
+    ScrollToLineInPaneId = 139,
+    SetPaneZIndex = 140,
+    CliPipeChunk = 141,
+    OpenUrl = 142,
+    PinTab = 143,
+    UnpinTab = 144,
+    RegisterGlobalHotkey = 145,
+    DuplicatePane = 146,
+    AnnotateScrollbackLine = 147,
+    ClearScrollbackAnnotations = 148,
+    SetClientMode = 149,
+    TogglePanePinned = 150,
+    GetPluginDependencies = 151,
+    CreateSynchronizedGroup = 152,
+    DissolveSynchronizedGroup = 153,
+    SetFocusFollowsMouse = 154,
+    GetCurrentInputMode = 155,
+    PipeTextToCommand = 156,
+    SetPaneScrollbackSize = 157,
+    PostMessageToWorkerInstance = 158,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -874,6 +1270,48 @@ impl CommandName {
             CommandName::ChangeHostFolder => "ChangeHostFolder",
             CommandName::SetFloatingPanePinned => "SetFloatingPanePinned",
             CommandName::StackPanes => "StackPanes",
+            CommandName::RunCommandInPane => "RunCommandInPane",
+            CommandName::OpenFileInEditor => "OpenFileInEditor",
+            CommandName::SetSelfMinSize => "SetSelfMinSize",
+            CommandName::SearchPane => "SearchPane",
+            CommandName::CreateFloatingPaneAt => "CreateFloatingPaneAt",
+            CommandName::SetSessionTags => "SetSessionTags",
+            CommandName::DragPaneToCoordinates => "DragPaneToCoordinates",
+            CommandName::SubscribeToChannel => "SubscribeToChannel",
+            CommandName::PublishToChannel => "PublishToChannel",
+            CommandName::GetCurrentTabs => "GetCurrentTabs",
+            CommandName::GetAllPanes => "GetAllPanes",
+            CommandName::SetPaneContent => "SetPaneContent",
+            CommandName::GetCurrentStyling => "GetCurrentStyling",
+            CommandName::ListSessions => "ListSessions",
+            CommandName::ToggleSelf => "ToggleSelf",
+            CommandName::RunAction => "RunAction",
+            CommandName::StoreBlob => "StoreBlob",
+            CommandName::LoadBlob => "LoadBlob",
+            CommandName::ListBlobs => "ListBlobs",
+            CommandName::DeleteBlob => "DeleteBlob",
+            CommandName::EmitMetric => "EmitMetric",
+            CommandName::PaneSnapshot => "PaneSnapshot",
+            CommandName::ScrollToLineInPaneId => "ScrollToLineInPaneId",
+            CommandName::SetPaneZIndex => "SetPaneZIndex",
+            CommandName::CliPipeChunk => "CliPipeChunk",
+            CommandName::OpenUrl => "OpenUrl",
+            CommandName::PinTab => "PinTab",
+            CommandName::UnpinTab => "UnpinTab",
+            CommandName::RegisterGlobalHotkey => "RegisterGlobalHotkey",
+            CommandName::DuplicatePane => "DuplicatePane",
+            CommandName::AnnotateScrollbackLine => "AnnotateScrollbackLine",
+            CommandName::ClearScrollbackAnnotations => "ClearScrollbackAnnotations",
+            CommandName::SetClientMode => "SetClientMode",
+            CommandName::TogglePanePinned => "TogglePanePinned",
+            CommandName::GetPluginDependencies => "GetPluginDependencies",
+            CommandName::CreateSynchronizedGroup => "CreateSynchronizedGroup",
+            CommandName::DissolveSynchronizedGroup => "DissolveSynchronizedGroup",
+            CommandName::SetFocusFollowsMouse => "SetFocusFollowsMouse",
+            CommandName::GetCurrentInputMode => "GetCurrentInputMode",
+            CommandName::PipeTextToCommand => "PipeTextToCommand",
+            CommandName::SetPaneScrollbackSize => "SetPaneScrollbackSize",
+            CommandName::PostMessageToWorkerInstance => "PostMessageToWorkerInstance",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -998,6 +1436,74 @@ impl CommandName {
             "ChangeHostFolder" => Some(Self::ChangeHostFolder),
             "SetFloatingPanePinned" => Some(Self::SetFloatingPanePinned),
             "StackPanes" => Some(Self::StackPanes),
+            "RunCommandInPane" => Some(Self::RunCommandInPane),
+            "OpenFileInEditor" => Some(Self::OpenFileInEditor),
+            "SetSelfMinSize" => Some(Self::SetSelfMinSize),
+            "SearchPane" => Some(Self::SearchPane),
+            "CreateFloatingPaneAt" => Some(Self::CreateFloatingPaneAt),
+            "SetSessionTags" => Some(Self::SetSessionTags),
+            "DragPaneToCoordinates" => Some(Self::DragPaneToCoordinates),
+            "SubscribeToChannel" => Some(Self::SubscribeToChannel),
+            "PublishToChannel" => Some(Self::PublishToChannel),
+            "GetCurrentTabs" => Some(Self::GetCurrentTabs),
+            "GetAllPanes" => Some(Self::GetAllPanes),
+            "SetPaneContent" => Some(Self::SetPaneContent),
+            "GetCurrentStyling" => Some(Self::GetCurrentStyling),
+            "ListSessions" => Some(Self::ListSessions),
+            "ToggleSelf" => Some(Self::ToggleSelf),
+            "RunAction" => Some(Self::RunAction),
+            "StoreBlob" => Some(Self::StoreBlob),
+            "LoadBlob" => Some(Self::LoadBlob),
+            "ListBlobs" => Some(Self::ListBlobs),
+            "DeleteBlob" => Some(Self::DeleteBlob),
+            "EmitMetric" => Some(Self::EmitMetric),
+            "PaneSnapshot" => Some(Self::PaneSnapshot),
+            "ScrollToLineInPaneId" => Some(Self::ScrollToLineInPaneId),
+            "SetPaneZIndex" => Some(Self::SetPaneZIndex),
+            "CliPipeChunk" => Some(Self::CliPipeChunk),
+            "OpenUrl" => Some(Self::OpenUrl),
+            "PinTab" => Some(Self::PinTab),
+            "UnpinTab" => Some(Self::UnpinTab),
+            "RegisterGlobalHotkey" => Some(Self::RegisterGlobalHotkey),
+            "DuplicatePane" => Some(Self::DuplicatePane),
+            "AnnotateScrollbackLine" => Some(Self::AnnotateScrollbackLine),
+            "ClearScrollbackAnnotations" => Some(Self::ClearScrollbackAnnotations),
+            "SetClientMode" => Some(Self::SetClientMode),
+            "TogglePanePinned" => Some(Self::TogglePanePinned),
+            "GetPluginDependencies" => Some(Self::GetPluginDependencies),
+            "CreateSynchronizedGroup" => Some(Self::CreateSynchronizedGroup),
+            "DissolveSynchronizedGroup" => Some(Self::DissolveSynchronizedGroup),
+            "SetFocusFollowsMouse" => Some(Self::SetFocusFollowsMouse),
+            "GetCurrentInputMode" => Some(Self::GetCurrentInputMode),
+            "PipeTextToCommand" => Some(Self::PipeTextToCommand),
+            "SetPaneScrollbackSize" => Some(Self::SetPaneScrollbackSize),
+            "PostMessageToWorkerInstance" => Some(Self::PostMessageToWorkerInstance),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum OpenLocation {
+    FloatingPane = 0,
+    CurrentPane = 1,
+}
+impl OpenLocation {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            OpenLocation::FloatingPane => "FloatingPane",
+            OpenLocation::CurrentPane => "CurrentPane",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FloatingPane" => Some(Self::FloatingPane),
+            "CurrentPane" => Some(Self::CurrentPane),
             _ => None,
         }
     }
@@ -1086,3 +1592,38 @@ impl FixedOrPercent {
         }
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PluginErrorCode {
+    NoError = 0,
+    PermissionDenied = 1,
+    PaneNotFound = 2,
+    InvalidArgument = 3,
+    HostError = 4,
+}
+impl PluginErrorCode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PluginErrorCode::NoError => "NoError",
+            PluginErrorCode::PermissionDenied => "PermissionDenied",
+            PluginErrorCode::PaneNotFound => "PaneNotFound",
+            PluginErrorCode::InvalidArgument => "InvalidArgument",
+            PluginErrorCode::HostError => "HostError",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NoError" => Some(Self::NoError),
+            "PermissionDenied" => Some(Self::PermissionDenied),
+            "PaneNotFound" => Some(Self::PaneNotFound),
+            "InvalidArgument" => Some(Self::InvalidArgument),
+            "HostError" => Some(Self::HostError),
+            _ => None,
+        }
+    }
+}
@@ -12,6 +12,9 @@ pub enum PermissionType {
     MessageAndLaunchOtherPlugins = 8,
     Reconfigure = 9,
     FullHdAccess = 10,
+    OpenUrls = 11,
+    StoreBlobs = 12,
+    EmitMetrics = 13,
 }
 impl PermissionType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -33,6 +36,9 @@ impl PermissionType {
             }
             PermissionType::Reconfigure => "Reconfigure",
             PermissionType::FullHdAccess => "FullHdAccess",
+            PermissionType::OpenUrls => "OpenUrls",
+            PermissionType::StoreBlobs => "StoreBlobs",
+            PermissionType::EmitMetrics => "EmitMetrics",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -49,6 +55,9 @@ impl PermissionType {
             "MessageAndLaunchOtherPlugins" => Some(Self::MessageAndLaunchOtherPlugins),
             "Reconfigure" => Some(Self::Reconfigure),
             "FullHdAccess" => Some(Self::FullHdAccess),
+            "OpenUrls" => Some(Self::OpenUrls),
+            "StoreBlobs" => Some(Self::StoreBlobs),
+            "EmitMetrics" => Some(Self::EmitMetrics),
             _ => None,
         }
     }
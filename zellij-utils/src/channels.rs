@@ -11,6 +11,14 @@ pub use crossbeam::channel::{
 /// An [MPSC](mpsc) asynchronous channel with added error context.
 pub type ChannelWithContext<T> = (Sender<(T, ErrorContext)>, Receiver<(T, ErrorContext)>);
 
+/// Creates a channel holding at most `size` messages, or an unbounded one if `size` is `None`.
+pub fn bounded_or_unbounded<T>(size: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    match size {
+        Some(size) => bounded(size),
+        None => unbounded(),
+    }
+}
+
 /// Sends messages on an [MPSC](std::sync::mpsc) channel, along with an [`ErrorContext`],
 /// synchronously or asynchronously depending on the underlying [`SenderType`].
 #[derive(Clone)]
@@ -29,6 +37,20 @@ impl<T: Clone> SenderWithContext<T> {
         let err_ctx = get_current_ctx();
         self.sender.send((event, err_ctx))
     }
+
+    /// The number of messages waiting to be received on this [`SenderWithContext`]'s channel.
+    pub fn len(&self) -> usize {
+        self.sender.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sender.is_empty()
+    }
+
+    /// The maximum number of messages this channel can hold, `None` if it is unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.sender.capacity()
+    }
 }
 
 thread_local!(
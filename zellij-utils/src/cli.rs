@@ -2,6 +2,7 @@ use crate::data::{Direction, InputMode, Resize};
 use crate::setup::Setup;
 use crate::{
     consts::{ZELLIJ_CONFIG_DIR_ENV, ZELLIJ_CONFIG_FILE_ENV},
+    envs::AUTH_TOKEN_ENV_KEY,
     input::{layout::PluginUserConfiguration, options::CliOptions},
 };
 use clap::{Parser, Subcommand};
@@ -77,6 +78,20 @@ pub struct CliArgs {
     /// Specify emitting additional debug information
     #[clap(short, long, value_parser)]
     pub debug: bool,
+
+    /// Require clients connecting to a new session to present this token before being accepted
+    #[clap(long, env = AUTH_TOKEN_ENV_KEY, hide_env_values = true, value_parser)]
+    pub auth_token: Option<String>,
+
+    /// Print the session's authentication token to stdout on startup, generating a random one
+    /// if none was given with --auth-token
+    #[clap(long, value_parser)]
+    pub print_token: bool,
+
+    /// Validate the layout given with --layout (or the default layout) and print its panes as a
+    /// tree, without starting a session. Exits with 1 if the layout has errors
+    #[clap(long, value_parser)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
@@ -101,6 +116,13 @@ pub enum SessionCommand {
     Options(CliOptions),
 }
 
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum ServerCommand {
+    /// Print the memory usage, channel fill levels, loaded plugin count and the last activity of
+    /// each thread of the session's server as JSON
+    Status,
+}
+
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
 pub enum Sessions {
     /// List active sessions
@@ -117,6 +139,21 @@ pub enum Sessions {
         /// List the sessions in reverse order (default is ascending order)
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         reverse: bool,
+
+        /// Only list sessions tagged with this tag (can be specified multiple times, in which
+        /// case sessions must have all the given tags)
+        #[clap(long, value_parser)]
+        tag: Vec<String>,
+
+        /// Only list sessions that have no tags
+        #[clap(
+            long,
+            value_parser,
+            takes_value(false),
+            default_value("false"),
+            conflicts_with("tag")
+        )]
+        no_tag: bool,
     },
     /// List existing plugin aliases
     #[clap(visible_alias = "la")]
@@ -149,6 +186,18 @@ pub enum Sessions {
         force_run_commands: bool,
     },
 
+    /// Resurrect a session that exited
+    Resurrect {
+        /// Name of the session to resurrect
+        #[clap(value_parser)]
+        session_name: String,
+
+        /// Start the session from the layout saved last when it exited (see the
+        /// auto_save_layout_on_exit option) rather than from its resurrection cache
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        latest: bool,
+    },
+
     /// Kill a specific session
     #[clap(visible_alias = "k")]
     KillSession {
@@ -191,6 +240,9 @@ pub enum Sessions {
     #[clap(visible_alias = "ac")]
     #[clap(subcommand)]
     Action(CliAction),
+    /// Inspect the server of a running session
+    #[clap(subcommand)]
+    Server(ServerCommand),
     /// Run a command in a new pane
     #[clap(visible_alias = "r")]
     Run {
@@ -368,6 +420,10 @@ zellij pipe --name my_pipe_name -- my_arbitrary_data
 * Pipe data into this command's STDIN and get output from the plugin on this command's STDOUT
 
 tail -f /tmp/my-live-logfile | zellij pipe --name logs --plugin https://example.com/my-plugin.wasm | wc -l
+
+* To a specific running instance of a plugin:
+
+zellij pipe --name my_pipe_name --plugin-id 3 -- my_arbitrary_data
 "#))]
     Pipe {
         /// The name of the pipe
@@ -388,7 +444,16 @@ tail -f /tmp/my-live-logfile | zellij pipe --name logs --plugin https://example.
         /// considered a different plugin for the purposes of determining the pipe destination)
         #[clap(short('c'), long, value_parser, display_order(4))]
         plugin_configuration: Option<PluginUserConfiguration>,
-    },
+        /// The id of a running plugin to direct this pipe to (eg. one of several instances of the
+        /// same plugin, a plugin can find its own id with `get_plugin_ids`)
+        #[clap(long, value_parser, conflicts_with("plugin"), display_order(5))]
+        plugin_id: Option<u32>,
+    },
+    /// Suspend a session to stop it from using CPU: the processes of all its terminal panes are
+    /// stopped (SIGSTOP) and its periodic background work is paused until it is resumed
+    Suspend,
+    /// Resume a suspended session (attaching to it also resumes it)
+    Resume,
 }
 
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
@@ -594,6 +659,8 @@ pub enum CliAction {
     TogglePaneEmbedOrFloating,
     /// Toggle the visibility of all floating panes in the current Tab, open one if none exist
     ToggleFloatingPanes,
+    /// Toggle whether only the focused floating pane is shown, hiding all others
+    ToggleExclusiveFloatingPanes,
     /// Close the focused pane.
     ClosePane,
     /// Renames the focused pane
@@ -699,6 +766,10 @@ zellij action pipe --name my_pipe_name -- my_arbitrary_data
 * Pipe data into this command's STDIN and get output from the plugin on this command's STDOUT
 
 tail -f /tmp/my-live-logfile | zellij action pipe --name logs --plugin https://example.com/my-plugin.wasm | wc -l
+
+* To a specific running instance of a plugin:
+
+zellij action pipe --name my_pipe_name --plugin-id 3 -- my_arbitrary_data
 "#))]
     Pipe {
         /// The name of the pipe
@@ -757,6 +828,10 @@ tail -f /tmp/my-live-logfile | zellij action pipe --name logs --plugin https://e
         /// If launching a plugin, specify its pane title
         #[clap(short('t'), long, value_parser, display_order(10))]
         plugin_title: Option<String>,
+        /// The id of a running plugin to direct this pipe to (eg. one of several instances of the
+        /// same plugin, a plugin can find its own id with `get_plugin_ids`)
+        #[clap(long, value_parser, conflicts_with("plugin"), display_order(11))]
+        plugin_id: Option<u32>,
     },
     ListClients,
     TogglePanePinned,
@@ -771,4 +846,88 @@ tail -f /tmp/my-live-logfile | zellij action pipe --name logs --plugin https://e
         #[clap(last(true), required(true))]
         pane_ids: Vec<String>,
     },
+    /// Open the specified file with your default EDITOR in place of a pane, temporarily suspending
+    /// it. Unlike `edit --in-place`, which always replaces the focused pane, this can replace any
+    /// pane by its id (eg. the pane a script was started from, see $ZELLIJ_PANE_ID)
+    OpenFile {
+        /// The file to open
+        #[clap(long, value_parser)]
+        path: PathBuf,
+        /// The pane to open the file in, either in the form of `terminal_<int>`, `plugin_<int>` or
+        /// a bare integer (a terminal pane), defaults to the focused pane
+        #[clap(long, value_parser)]
+        pane_id: Option<String>,
+    },
+    /// Suspend the session: stop (SIGSTOP) the processes of all its terminal panes and pause its
+    /// periodic background work until it is resumed
+    SuspendSession,
+    /// Resume a suspended session
+    ResumeSession,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::actions::Action;
+
+    fn cli_action(args: &[&str]) -> Result<CliAction, clap::Error> {
+        let cli_args = CliArgs::try_parse_from(["zellij", "action"].iter().chain(args))?;
+        match cli_args.command {
+            Some(Command::Sessions(Sessions::Action(cli_action))) => Ok(cli_action),
+            command => panic!("expected an action, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn pipe_can_target_a_plugin_id() {
+        let cli_args =
+            CliArgs::try_parse_from(["zellij", "pipe", "--plugin-id", "3", "--", "data"]).unwrap();
+        assert!(matches!(
+            cli_args.command,
+            Some(Command::Sessions(Sessions::Pipe {
+                plugin_id: Some(3),
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn pipe_plugin_id_conflicts_with_plugin() {
+        let pipe_result = CliArgs::try_parse_from([
+            "zellij",
+            "pipe",
+            "--plugin-id",
+            "3",
+            "--plugin",
+            "zellij:strider",
+            "--",
+            "data",
+        ]);
+        assert!(pipe_result.is_err());
+        let action_pipe_result = cli_action(&[
+            "pipe",
+            "--plugin-id",
+            "3",
+            "--plugin",
+            "zellij:strider",
+            "--",
+            "data",
+        ]);
+        assert!(action_pipe_result.is_err());
+    }
+
+    #[test]
+    fn action_pipe_plugin_id_is_passed_to_the_cli_pipe_action() {
+        let cli_action = cli_action(&["pipe", "--plugin-id", "3", "--", "data"]).unwrap();
+        let actions =
+            Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None).unwrap();
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::CliPipe {
+                plugin_id: Some(3),
+                plugin: None,
+                ..
+            }]
+        ));
+    }
 }
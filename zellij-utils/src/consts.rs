@@ -5,7 +5,7 @@ use directories::ProjectDirs;
 use include_dir::{include_dir, Dir};
 use lazy_static::lazy_static;
 use once_cell::sync::OnceCell;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 use uuid::Uuid;
 
 pub const ZELLIJ_CONFIG_FILE_ENV: &str = "ZELLIJ_CONFIG_FILE";
@@ -14,6 +14,8 @@ pub const ZELLIJ_LAYOUT_DIR_ENV: &str = "ZELLIJ_LAYOUT_DIR";
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DEFAULT_SCROLL_BUFFER_SIZE: usize = 10_000;
 pub static SCROLL_BUFFER_SIZE: OnceCell<usize> = OnceCell::new();
+pub static SIXEL_SUPPORT: OnceCell<bool> = OnceCell::new();
+pub const DEFAULT_SCREEN_CHANNEL_SIZE: usize = 50;
 pub static DEBUG_MODE: OnceCell<bool> = OnceCell::new();
 
 pub const SYSTEM_DEFAULT_CONFIG_DIR: &str = "/etc/zellij";
@@ -29,10 +31,88 @@ pub fn session_layout_cache_file_name(session_name: &str) -> PathBuf {
     session_info_folder_for_session(session_name).join("session-layout.kdl")
 }
 
+pub fn session_tags_file_name(session_name: &str) -> PathBuf {
+    session_info_folder_for_session(session_name).join("session-tags.kdl")
+}
+
 pub fn session_info_folder_for_session(session_name: &str) -> PathBuf {
     ZELLIJ_SESSION_INFO_CACHE_DIR.join(session_name)
 }
 
+/// The folder of `session_name` directly inside `root`, or None if the name would resolve to
+/// somewhere else (eg. "..", "a/b" or a symlink pointing out of `root`)
+pub fn session_folder_within(root: &Path, session_name: &str) -> Option<PathBuf> {
+    let mut components = Path::new(session_name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => {},
+        _ => return None,
+    }
+    let folder = root.join(session_name);
+    match (folder.canonicalize(), root.canonicalize()) {
+        (Ok(canonical_folder), Ok(canonical_root))
+            if canonical_folder.parent() != Some(canonical_root.as_path()) =>
+        {
+            None
+        },
+        // a folder that does not exist yet is created inside root
+        _ => Some(folder),
+    }
+}
+
+pub fn saved_layout_file_name(session_name: &str, timestamp: u64) -> PathBuf {
+    ZELLIJ_SAVED_LAYOUTS_DIR.join(format!("{}-{}.kdl", session_name, timestamp))
+}
+
+/// The layouts saved in `dir` when sessions with this name exited, newest first
+fn saved_layouts_for_session(dir: &Path, session_name: &str) -> Vec<(u64, PathBuf)> {
+    let prefix = format!("{}-", session_name);
+    let mut saved_layouts: Vec<(u64, PathBuf)> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                let timestamp: u64 = file_name
+                    .strip_prefix(&prefix)?
+                    .strip_suffix(".kdl")?
+                    .parse()
+                    .ok()?;
+                Some((timestamp, entry.path()))
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+    saved_layouts.sort_by(|(a, _), (b, _)| b.cmp(a));
+    saved_layouts
+}
+
+/// The layout saved last when a session with this name exited
+pub fn latest_saved_layout_file_name(session_name: &str) -> Option<PathBuf> {
+    latest_saved_layout_file_name_in(&ZELLIJ_SAVED_LAYOUTS_DIR, session_name)
+}
+
+fn latest_saved_layout_file_name_in(dir: &Path, session_name: &str) -> Option<PathBuf> {
+    saved_layouts_for_session(dir, session_name)
+        .into_iter()
+        .next()
+        .map(|(_timestamp, path)| path)
+}
+
+/// Deletes all but the `keep` newest layouts saved for sessions with this name
+pub fn prune_saved_layouts(session_name: &str, keep: usize) {
+    prune_saved_layouts_in(&ZELLIJ_SAVED_LAYOUTS_DIR, session_name, keep)
+}
+
+fn prune_saved_layouts_in(dir: &Path, session_name: &str, keep: usize) {
+    for (_timestamp, path) in saved_layouts_for_session(dir, session_name)
+        .into_iter()
+        .skip(keep)
+    {
+        if let Err(e) = std::fs::remove_file(&path) {
+            log::error!("Failed to remove saved layout {}: {}", path.display(), e);
+        }
+    }
+}
+
 pub fn create_config_and_cache_folders() {
     if let Err(e) = std::fs::create_dir_all(&ZELLIJ_CACHE_DIR.as_path()) {
         log::error!("Failed to create cache dir: {:?}", e);
@@ -72,6 +152,9 @@ lazy_static! {
     pub static ref ZELLIJ_STDIN_CACHE_FILE: PathBuf =
         ZELLIJ_CACHE_DIR.join(VERSION).join("stdin_cache");
     pub static ref ZELLIJ_PLUGIN_ARTIFACT_DIR: PathBuf = ZELLIJ_CACHE_DIR.join(VERSION);
+    pub static ref ZELLIJ_BLOB_DIR: PathBuf = ZELLIJ_CACHE_DIR.join("blobs");
+    pub static ref ZELLIJ_SAVED_LAYOUTS_DIR: PathBuf =
+        ZELLIJ_PROJ_DIR.data_dir().join("saved-layouts");
 }
 
 pub const FEATURES: &[&str] = &[
@@ -129,6 +212,9 @@ mod not_wasm {
             add_plugin!(assets, "session-manager.wasm");
             add_plugin!(assets, "configuration.wasm");
             add_plugin!(assets, "plugin-manager.wasm");
+            add_plugin!(assets, "keybind-help.wasm");
+            add_plugin!(assets, "search-all-panes.wasm");
+            add_plugin!(assets, "pane-history.wasm");
             assets
         };
     }
@@ -167,3 +253,113 @@ mod unix_only {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn session_folder_within_rejects_paths_outside_of_root() {
+        let temp_dir = std::env::temp_dir().join(format!("zellij-test-{}", Uuid::new_v4()));
+        let root = temp_dir.join("session_info");
+        fs::create_dir_all(root.join("existing-session")).unwrap();
+        fs::create_dir_all(temp_dir.join("outside")).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(temp_dir.join("outside"), root.join("escaping-link")).unwrap();
+
+        assert_eq!(
+            session_folder_within(&root, "existing-session"),
+            Some(root.join("existing-session"))
+        );
+        assert_eq!(
+            session_folder_within(&root, "new-session"),
+            Some(root.join("new-session"))
+        );
+        for session_name in ["", ".", "..", "../outside", "a/b", "/etc", "escaping-link"] {
+            assert_eq!(
+                session_folder_within(&root, session_name),
+                None,
+                "{:?} is not accepted",
+                session_name
+            );
+        }
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    fn saved_layouts_dir_with(file_names: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("zellij-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        for file_name in file_names {
+            fs::write(dir.join(file_name), "layout {}").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn saved_layouts_are_listed_newest_first() {
+        let dir = saved_layouts_dir_with(&["foo-200.kdl", "foo-1000.kdl", "foo-30.kdl"]);
+        let timestamps: Vec<u64> = saved_layouts_for_session(&dir, "foo")
+            .into_iter()
+            .map(|(timestamp, _path)| timestamp)
+            .collect();
+        assert_eq!(timestamps, vec![1000, 200, 30]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn saved_layouts_only_match_their_own_session_name() {
+        let dir = saved_layouts_dir_with(&[
+            "foo-100.kdl",
+            "foo-bar-300.kdl",
+            "foo-bar.kdl",
+            "foo-200.txt",
+            "foobar-400.kdl",
+        ]);
+        assert_eq!(
+            saved_layouts_for_session(&dir, "foo"),
+            vec![(100, dir.join("foo-100.kdl"))]
+        );
+        assert_eq!(
+            saved_layouts_for_session(&dir, "foo-bar"),
+            vec![(300, dir.join("foo-bar-300.kdl"))]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn latest_saved_layout_is_the_newest_one() {
+        let dir = saved_layouts_dir_with(&["foo-200.kdl", "foo-1000.kdl", "foo-bar-2000.kdl"]);
+        assert_eq!(
+            latest_saved_layout_file_name_in(&dir, "foo"),
+            Some(dir.join("foo-1000.kdl"))
+        );
+        assert_eq!(latest_saved_layout_file_name_in(&dir, "baz"), None);
+        assert_eq!(
+            latest_saved_layout_file_name_in(&dir.join("does-not-exist"), "foo"),
+            None
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn pruning_saved_layouts_keeps_the_newest_ones_of_that_session() {
+        let dir = saved_layouts_dir_with(&[
+            "foo-100.kdl",
+            "foo-200.kdl",
+            "foo-300.kdl",
+            "foo-bar-50.kdl",
+        ]);
+        prune_saved_layouts_in(&dir, "foo", 2);
+        let mut remaining: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec!["foo-200.kdl", "foo-300.kdl", "foo-bar-50.kdl"]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -914,6 +914,85 @@ pub enum Event {
     ListClients(Vec<ClientInfo>),
     HostFolderChanged(PathBuf),               // PathBuf -> new host folder
     FailedToChangeHostFolder(Option<String>), // String -> the error we got when changing
+    /// The results of a search started with `search_pane`
+    SearchResult {
+        pane_id: PaneId,
+        query: String,
+        matches: Vec<SearchMatch>,
+    },
+    /// A payload published with `publish_to_channel` on a channel this plugin subscribed to
+    ChannelMessage {
+        channel: String,
+        payload: Vec<u8>,
+    },
+    SessionRenamed(String), // String -> the new session name
+    /// The session's configuration changed (eg. it was reloaded or changed by a plugin)
+    ConfigUpdate(ConfigDiff),
+    /// An action sent with `run_action` was applied, along with the pane it was run on behalf of
+    /// and the context given to `run_action`
+    ActionComplete(Action, Option<PaneId>, Context),
+    /// The visible contents of a pane requested with `pane_snapshot`, one row of cells per line
+    /// of the pane (empty if the pane could not be found)
+    PaneSnapshot {
+        pane_id: PaneId,
+        rows: Vec<Vec<Cell>>,
+    },
+    /// Text pasted into the plugin's pane in one piece (eg. with bracketed paste), plugins that
+    /// are not subscribed to this event receive it as separate `Key` events instead
+    PastedText(String),
+    /// A pane duplicated with `duplicate_pane` was opened next to the original
+    PaneDuplicated {
+        original_id: PaneId,
+        new_id: PaneId,
+    },
+    /// The dependencies declared by the plugin at a location requested with
+    /// `get_plugin_dependencies`
+    PluginDependencies(PluginInfo),
+    /// Text the user copied from a terminal pane
+    PaneSelection {
+        pane_id: PaneId,
+        selected_text: String,
+    },
+    /// A pane was not opened because the session already has the maximum number of panes
+    PaneCountExceeded,
+    /// The result of `pipe_text_to_command`, the exit code is 124 if the command timed out and
+    /// 127 if it could not be started
+    CommandPipeResult {
+        exit_code: i32,
+        stdout: String,
+        stderr: String,
+        context: BTreeMap<String, String>,
+    },
+}
+
+/// A structured record of what changed between two configurations, sent to plugins with
+/// [Event::ConfigUpdate]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    pub changed_options: Vec<OptionChange>,
+    /// keys that were bound or rebound to different actions
+    pub added_keybinds: Vec<(InputMode, KeyWithModifier, Vec<Action>)>,
+    pub removed_keybinds: Vec<(InputMode, KeyWithModifier)>,
+    /// the selected theme changed or its colors were redefined
+    pub changed_theme: bool,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changed_options.is_empty()
+            && self.added_keybinds.is_empty()
+            && self.removed_keybinds.is_empty()
+            && !self.changed_theme
+    }
+}
+
+/// A single option that changed, its values are as they would appear in the configuration file
+/// (`None` if the option is unset)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptionChange {
+    pub name: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
 }
 
 #[derive(
@@ -945,6 +1024,9 @@ pub enum Permission {
     MessageAndLaunchOtherPlugins,
     Reconfigure,
     FullHdAccess,
+    OpenUrls,
+    StoreBlobs,
+    EmitMetrics,
 }
 
 impl PermissionType {
@@ -967,6 +1049,9 @@ impl PermissionType {
             },
             PermissionType::Reconfigure => "Change Zellij runtime configuration".to_owned(),
             PermissionType::FullHdAccess => "Full access to the hard-drive".to_owned(),
+            PermissionType::OpenUrls => "Open links in the default browser".to_owned(),
+            PermissionType::StoreBlobs => "Store data shared between plugins".to_owned(),
+            PermissionType::EmitMetrics => "Report metrics".to_owned(),
         }
     }
 }
@@ -1149,6 +1234,7 @@ pub struct ModeInfo {
     pub style: Style,
     pub capabilities: PluginCapabilities,
     pub session_name: Option<String>,
+    pub client_id: Option<ClientId>, // the client whose mode this is, if known
 }
 
 impl ModeInfo {
@@ -1195,12 +1281,15 @@ pub struct SessionInfo {
     pub is_current_session: bool,
     pub available_layouts: Vec<LayoutInfo>,
     pub plugins: BTreeMap<u32, PluginInfo>,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PluginInfo {
     pub location: String,
     pub configuration: BTreeMap<String, String>,
+    /// Only known when asked for with `get_plugin_dependencies`
+    pub dependencies: Vec<PluginDependency>,
 }
 
 impl From<RunPlugin> for PluginInfo {
@@ -1208,10 +1297,17 @@ impl From<RunPlugin> for PluginInfo {
         PluginInfo {
             location: run_plugin.location.display(),
             configuration: run_plugin.configuration.inner().clone(),
+            dependencies: vec![],
         }
     }
 }
 
+/// A plugin that has to be loaded before the plugin declaring it in its manifest
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct PluginDependency {
+    pub url: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum LayoutInfo {
     BuiltIn(String),
@@ -1272,6 +1368,23 @@ impl SessionInfo {
         }
         self.plugins = plugin_list;
     }
+    pub fn update_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+    /// Trims tags and removes empty and duplicate ones, preserving their order
+    pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+        let mut normalized: Vec<String> = vec![];
+        for tag in tags {
+            let tag = tag.trim();
+            if !tag.is_empty() && !normalized.iter().any(|t| t == tag) {
+                normalized.push(tag.to_owned());
+            }
+        }
+        normalized
+    }
 }
 
 /// Contains all the information for a currently opened tab.
@@ -1294,6 +1407,8 @@ pub struct TabInfo {
     pub active_swap_layout_name: Option<String>,
     /// Whether the user manually changed the layout, moving out of the swap layout scheme
     pub is_swap_layout_dirty: bool,
+    /// Whether this tab is pinned to the session, pinned tabs cannot be closed
+    pub pinned: bool,
 }
 
 /// The `PaneManifest` contains a dictionary of panes, indexed by the tab position (0 indexed).
@@ -1359,6 +1474,14 @@ pub struct PaneInfo {
     /// Unselectable panes are often used for UI elements that do not have direct user interaction
     /// (eg. the default `status-bar` or `tab-bar`).
     pub is_selectable: bool,
+    /// The maximum number of lines this pane keeps in its scrollback buffer, only relevant to
+    /// terminal panes
+    pub scrollback_size: Option<usize>,
+    /// The folder a terminal pane was opened in, if it was opened in a specific one
+    pub cwd: Option<PathBuf>,
+    /// If this is a command pane, its command followed by its arguments, one per item, so that
+    /// arguments containing spaces can be told apart
+    pub terminal_command_argv: Vec<String>,
 }
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ClientInfo {
@@ -1384,11 +1507,48 @@ impl ClientInfo {
     }
 }
 
+/// A single occurrence of a search query inside a pane's scrollback and viewport
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SearchMatch {
+    /// The line of the match, 0 being the oldest line in the scrollback
+    pub row: usize,
+    pub col_start: usize,
+    /// Exclusive
+    pub col_end: usize,
+    /// The full line in which the match was found
+    pub context_line: String,
+}
+
+/// A single character of a pane snapshot, default colors are replaced by the terminal's own
+/// foreground and background colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Cell {
+    pub character: char,
+    pub fg: PaletteColor,
+    pub bg: PaletteColor,
+    pub bold: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct PluginIds {
     pub plugin_id: u32,
     pub zellij_pid: u32,
     pub initial_cwd: PathBuf,
+    pub session_name: String,
+    pub client_id: ClientId,
+}
+
+/// The reason a plugin command that returns a value to the plugin failed
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, thiserror::Error)]
+pub enum PluginError {
+    #[error("permission denied")]
+    PermissionDenied,
+    #[error("pane not found")]
+    PaneNotFound,
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("host error: {0}")]
+    HostError(String),
 }
 
 /// Tag used to identify the plugin in layout and config kdl files
@@ -1513,6 +1673,9 @@ pub enum PaneId {
     Plugin(u32),
 }
 
+/// A group of panes that all receive the input sent to any one of them
+pub type GroupId = u32;
+
 impl MessageToPlugin {
     pub fn new(message_name: impl Into<String>) -> Self {
         MessageToPlugin {
@@ -1577,6 +1740,7 @@ pub struct ConnectToSession {
     pub pane_id: Option<(u32, bool)>, // (id, is_plugin)
     pub layout: Option<LayoutInfo>,
     pub cwd: Option<PathBuf>,
+    pub tags: Vec<String>, // applied to the target session, only if not empty
 }
 
 impl ConnectToSession {
@@ -1613,6 +1777,13 @@ impl PluginMessage {
     }
 }
 
+/// Where to open a new pane relative to the focused one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OpenLocation {
+    FloatingPane,
+    CurrentPane, // replaces the focused pane until the new one is closed
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HttpVerb {
     Get,
@@ -1621,6 +1792,35 @@ pub enum HttpVerb {
     Delete,
 }
 
+/// A rectangular area of a plugin's content, rendered independently and composited by the host.
+/// Coordinates are relative to the plugin's content area (`x` being the column and `y` the row).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RenderRegion {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+    pub content: String,
+}
+
+impl RenderRegion {
+    pub fn new(
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        content: impl Into<String>,
+    ) -> Self {
+        RenderRegion {
+            x,
+            y,
+            width,
+            height,
+            content: content.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PipeSource {
     Cli(String), // String is the pipe_id of the CLI pipe (used for blocking/unblocking)
@@ -1635,6 +1835,9 @@ pub struct PipeMessage {
     pub payload: Option<String>,
     pub args: BTreeMap<String, String>,
     pub is_private: bool,
+    /// false if more of this payload is on its way in following messages, eg. for the lines
+    /// piped into `zellij pipe` from STDIN
+    pub is_last: bool,
 }
 
 impl PipeMessage {
@@ -1651,6 +1854,7 @@ impl PipeMessage {
             payload: payload.clone(),
             args: args.clone().unwrap_or_else(|| Default::default()),
             is_private,
+            is_last: true,
         }
     }
 }
@@ -1776,6 +1980,7 @@ pub enum PluginCommand {
     SetTimeout(f64),  // seconds
     ExecCmd(Vec<String>),
     PostMessageTo(PluginMessage),
+    PostMessageToWorkerInstance(PluginMessage, u32), // u32 - the instance of the worker pool
     PostMessageToPlugin(PluginMessage),
     HideSelf,
     ShowSelf(bool), // bool - should float if hidden
@@ -1847,10 +2052,11 @@ pub enum PluginCommand {
         Vec<u8>,                  // body
         BTreeMap<String, String>, // context
     ),
-    RenameSession(String),         // String -> new session name
-    UnblockCliPipeInput(String),   // String => pipe name
-    BlockCliPipeInput(String),     // String => pipe name
-    CliPipeOutput(String, String), // String => pipe name, String => output
+    RenameSession(String),               // String -> new session name
+    UnblockCliPipeInput(String),         // String => pipe name
+    BlockCliPipeInput(String),           // String => pipe name
+    CliPipeOutput(String, String),       // String => pipe name, String => output
+    CliPipeChunk(String, Vec<u8>, bool), // String => pipe name, Vec<u8> => chunk, bool => is_last
     MessageToPlugin(MessageToPlugin),
     DisconnectOtherClients,
     KillSessions(Vec<String>), // one or more session names
@@ -1902,5 +2108,56 @@ pub enum PluginCommand {
     ListClients,
     ChangeHostFolder(PathBuf),
     SetFloatingPanePinned(PaneId, bool), // bool -> should be pinned
+    SetPaneZIndex(PaneId, usize),        // usize -> z_index, higher is in front
     StackPanes(Vec<PaneId>),
+    RunCommandInPane(PaneId, CommandToRun, BTreeMap<String, String>), // env_variables
+    OpenFileInEditor(PathBuf, OpenLocation),
+    SetSelfMinSize(usize, usize), // rows, cols
+    // String - query, bool - case sensitive, bool - the query is a regular expression
+    SearchPane(PaneId, String, bool, bool),
+    CreateFloatingPaneAt {
+        url: String,
+        config: BTreeMap<String, String>,
+        coordinates: FloatingPaneCoordinates,
+    },
+    SetSessionTags(String, Vec<String>), // session name, tags
+    DragPaneToCoordinates(PaneId, usize, usize), // usize, usize - x, y
+    SubscribeToChannel(String),          // channel name
+    PublishToChannel(String, Vec<u8>),   // channel name, payload
+    GetCurrentTabs,
+    GetAllPanes,
+    SetPaneContent(PaneId, String), // String - content
+    GetCurrentStyling,
+    ListSessions,
+    ToggleSelf(bool), // bool - should float if hidden
+    RunAction(Action, Context),
+    StoreBlob(String, Vec<u8>), // key, data
+    LoadBlob(String),           // hash
+    ListBlobs,
+    DeleteBlob(String), // hash
+    // name, value, labels
+    EmitMetric(String, f64, BTreeMap<String, String>),
+    PaneSnapshot(PaneId),
+    ScrollToLineInPaneId(PaneId, usize), // usize - line, 0 being the oldest line in the scrollback
+    OpenUrl(String),                     // url, http, https or mailto
+    PinTab(usize),                       // usize - tab position
+    UnpinTab(usize),                     // usize - tab position
+    RegisterGlobalHotkey(KeyWithModifier, String), // String - action_id
+    DuplicatePane(PaneId),
+    SetClientMode(ClientId, InputMode),
+    AnnotateScrollbackLine(PaneId, usize, String), // usize - line index, String - annotation
+    ClearScrollbackAnnotations(PaneId),
+    TogglePanePinned,
+    GetPluginDependencies(String),                // String - plugin url
+    CreateSynchronizedGroup(String, Vec<PaneId>), // String - group name
+    DissolveSynchronizedGroup(GroupId),
+    SetFocusFollowsMouse(bool),
+    GetCurrentInputMode,
+    PipeTextToCommand(
+        String,
+        CommandToRun,
+        Option<Duration>,
+        BTreeMap<String, String>,
+    ), // text, command, timeout, context
+    SetPaneScrollbackSize(PaneId, usize), // usize - the maximum number of lines in the scrollback
 }
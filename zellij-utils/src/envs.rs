@@ -3,7 +3,7 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap},
-    env::{set_var, var},
+    env::{remove_var, set_var, var},
 };
 
 use std::fmt;
@@ -26,6 +26,20 @@ pub fn set_session_name(v: String) {
     set_var(SESSION_NAME_ENV_KEY, v);
 }
 
+pub const AUTH_TOKEN_ENV_KEY: &str = "ZELLIJ_AUTH_TOKEN";
+
+pub fn get_auth_token() -> Result<String> {
+    Ok(var(AUTH_TOKEN_ENV_KEY)?)
+}
+
+pub fn set_auth_token(v: String) {
+    set_var(AUTH_TOKEN_ENV_KEY, v);
+}
+
+pub fn remove_auth_token() {
+    remove_var(AUTH_TOKEN_ENV_KEY);
+}
+
 pub const SOCKET_DIR_ENV_KEY: &str = "ZELLIJ_SOCKET_DIR";
 pub fn get_socket_dir() -> Result<String> {
     Ok(var(SOCKET_DIR_ENV_KEY)?)
@@ -216,6 +216,7 @@ pub enum ScreenContext {
     NewPane,
     OpenInPlaceEditor,
     ToggleFloatingPanes,
+    ToggleExclusiveFloatingPanes,
     ShowFloatingPanes,
     HideFloatingPanes,
     TogglePaneEmbedOrFloating,
@@ -323,6 +324,9 @@ pub enum ScreenContext {
     SearchToggleWrap,
     AddRedPaneFrameColorOverride,
     ClearPaneFrameColorOverride,
+    StartupCommandTimedOut,
+    PaneCountExceeded,
+    SetFocusFollowsMouse,
     PreviousSwapLayout,
     NextSwapLayout,
     QueryTabNames,
@@ -338,6 +342,7 @@ pub enum ScreenContext {
     LaunchPlugin,
     SuppressPane,
     FocusPaneWithId,
+    TogglePaneWithId,
     RenamePane,
     RenameTab,
     RequestPluginPermissions,
@@ -348,6 +353,7 @@ pub enum ScreenContext {
     ReplacePane,
     NewInPlacePluginPane,
     DumpLayoutToHd,
+    SaveLayout,
     RenameSession,
     DumpLayoutToPlugin,
     ListClientsMetadata,
@@ -362,18 +368,37 @@ pub enum ScreenContext {
     ScrollUpInPaneId,
     ScrollDownInPaneId,
     ScrollToTopInPaneId,
+    ScrollToLineInPaneId,
+    AnnotateScrollbackLineInPaneId,
+    ClearScrollbackAnnotationsInPaneId,
     ScrollToBottomInPaneId,
     PageScrollUpInPaneId,
     PageScrollDownInPaneId,
     TogglePaneIdFullscreen,
     TogglePaneEmbedOrEjectForPaneId,
     CloseTabWithIndex,
+    SetTabPinned,
     BreakPanesToNewTab,
     BreakPanesToTabWithIndex,
     ListClientsToPlugin,
     TogglePanePinned,
     SetFloatingPanePinned,
+    SetFloatingPaneZIndex,
+    DragPaneToCoordinates,
     StackPanes,
+    CreateSynchronizedGroup,
+    DissolveSynchronizedGroup,
+    RunCommandInPane,
+    SetPaneMinSize,
+    SetPaneScrollbackSize,
+    SearchPane,
+    GetTabInfosForClient,
+    GetPaneManifest,
+    GetStyleForClient,
+    GetInputModeForClient,
+    GetSessionInfos,
+    PaneSnapshot,
+    DuplicatePane,
 }
 
 /// Stack call representations corresponding to the different types of [`PtyInstruction`]s.
@@ -393,11 +418,15 @@ pub enum PtyContext {
     SpawnInPlaceTerminal,
     DumpLayout,
     LogLayoutToHd,
+    SaveLayout,
     FillPluginCwd,
     DumpLayoutToPlugin,
     ListClientsMetadata,
     Reconfigure,
     ListClientsToPlugin,
+    DuplicateTerminal,
+    SuspendAll,
+    ResumeAll,
     Exit,
 }
 
@@ -424,6 +453,7 @@ pub enum PluginContext {
     PermissionRequestResult,
     DumpLayout,
     LogLayoutToHd,
+    SaveLayout,
     CliPipe,
     Message,
     CachePluginEvents,
@@ -437,6 +467,10 @@ pub enum PluginContext {
     FailedToWriteConfigToDisk,
     ListClientsToPlugin,
     ChangePluginHostDir,
+    SubscribeToChannel,
+    PublishToChannel,
+    DuplicatePlugin,
+    GetPluginDependencies,
 }
 
 /// Stack call representations corresponding to the different types of [`ClientInstruction`]s.
@@ -459,6 +493,7 @@ pub enum ClientContext {
     SetSynchronisedOutput,
     UnblockCliPipeInput,
     CliPipeOutput,
+    CliPipeChunk,
     QueryTerminalSize,
     WriteConfigToDisk,
 }
@@ -474,14 +509,18 @@ pub enum ServerContext {
     Error,
     KillSession,
     DetachSession,
+    DetachIdleClients,
+    BroadcastEvent,
     AttachClient,
     ConnStatus,
+    ServerStatus,
     ActiveClients,
     Log,
     LogError,
     SwitchSession,
     UnblockCliPipeInput,
     CliPipeOutput,
+    CliPipeChunk,
     AssociatePipeWithClient,
     DisconnectAllClientsExcept,
     ChangeMode,
@@ -490,6 +529,11 @@ pub enum ServerContext {
     ConfigWrittenToDisk,
     FailedToWriteConfigToDisk,
     RebindKeys,
+    FlushThrottledRender,
+    RegisterGlobalHotkey,
+    UnregisterGlobalHotkeys,
+    SuspendSession,
+    ResumeSession,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -510,8 +554,15 @@ pub enum BackgroundJobContext {
     ReportSessionInfo,
     ReportLayoutInfo,
     RunCommand,
+    PipeTextToCommand,
     WebRequest,
     ReportPluginList,
+    SearchPane,
+    EmitMetric,
+    RunSessionHooks,
+    ReportServerStatus,
+    SuspendSession,
+    ResumeSession,
     Exit,
 }
 
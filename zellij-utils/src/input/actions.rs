@@ -185,6 +185,8 @@ pub enum Action {
     TogglePaneEmbedOrFloating,
     /// Toggle the visibility of all floating panes (if any) in the current Tab
     ToggleFloatingPanes,
+    /// Toggle whether only the focused floating pane is shown, hiding all others
+    ToggleExclusiveFloatingPanes,
     /// Close the focus pane.
     CloseFocus,
     PaneNameInput(Vec<u8>),
@@ -277,6 +279,8 @@ pub enum Action {
         in_place: Option<bool>,
         cwd: Option<PathBuf>,
         pane_title: Option<String>,
+        plugin_id: Option<u32>, // supercedes plugin if present
+        is_last: bool, // false if more of the payload will follow in the next CliPipe action
     },
     KeybindPipe {
         name: Option<String>,
@@ -295,6 +299,39 @@ pub enum Action {
     ListClients,
     TogglePanePinned,
     StackPanes(Vec<PaneId>),
+    /// Run a command in an existing terminal pane by writing it to the pane's STDIN
+    RunCommandInPane {
+        pane_id: PaneId,
+        command: RunCommandAction,
+    },
+    /// Open a file with the default editor in place of a pane, the focused one if None
+    OpenFile {
+        path: PathBuf,
+        pane_id: Option<PaneId>,
+    },
+    /// Stop the processes of all terminal panes and the session's periodic background work until
+    /// the session is resumed
+    SuspendSession,
+    /// Resume a session stopped with `SuspendSession`
+    ResumeSession,
+}
+
+// pane ids are either in the form of `terminal_<int>`, `plugin_<int>` or a bare integer, in which
+// case they're considered terminals
+fn parse_pane_id(stringified_pane_id: &str) -> Option<PaneId> {
+    if let Some(terminal_pane_id) = stringified_pane_id.strip_prefix("terminal_") {
+        u32::from_str_radix(terminal_pane_id, 10)
+            .ok()
+            .map(|id| PaneId::Terminal(id))
+    } else if let Some(plugin_pane_id) = stringified_pane_id.strip_prefix("plugin_") {
+        u32::from_str_radix(plugin_pane_id, 10)
+            .ok()
+            .map(|id| PaneId::Plugin(id))
+    } else {
+        u32::from_str_radix(stringified_pane_id, 10)
+            .ok()
+            .map(|id| PaneId::Terminal(id))
+    }
 }
 
 impl Action {
@@ -499,6 +536,9 @@ impl Action {
             },
             CliAction::TogglePaneEmbedOrFloating => Ok(vec![Action::TogglePaneEmbedOrFloating]),
             CliAction::ToggleFloatingPanes => Ok(vec![Action::ToggleFloatingPanes]),
+            CliAction::ToggleExclusiveFloatingPanes => {
+                Ok(vec![Action::ToggleExclusiveFloatingPanes])
+            },
             CliAction::ClosePane => Ok(vec![Action::CloseFocus]),
             CliAction::RenamePane { name } => Ok(vec![
                 Action::UndoRenamePane,
@@ -711,6 +751,7 @@ impl Action {
                 in_place_plugin,
                 plugin_cwd,
                 plugin_title,
+                plugin_id,
             } => {
                 let current_dir = get_current_dir();
                 let cwd = plugin_cwd
@@ -732,44 +773,23 @@ impl Action {
                     cwd,
                     pane_title: plugin_title,
                     skip_cache,
+                    plugin_id,
+                    is_last: true,
                 }])
             },
             CliAction::ListClients => Ok(vec![Action::ListClients]),
             CliAction::TogglePanePinned => Ok(vec![Action::TogglePanePinned]),
+            CliAction::SuspendSession => Ok(vec![Action::SuspendSession]),
+            CliAction::ResumeSession => Ok(vec![Action::ResumeSession]),
             CliAction::StackPanes { pane_ids } => {
                 let mut malformed_ids = vec![];
                 let pane_ids = pane_ids
                     .iter()
                     .filter_map(|stringified_pane_id| {
-                        if let Some(terminal_pane_id) =
-                            stringified_pane_id.strip_prefix("terminal_")
-                        {
-                            u32::from_str_radix(terminal_pane_id, 10)
-                                .ok()
-                                .or_else(|| {
-                                    malformed_ids.push(stringified_pane_id.to_owned());
-                                    None
-                                })
-                                .map(|id| PaneId::Terminal(id))
-                        } else if let Some(plugin_pane_id) =
-                            stringified_pane_id.strip_prefix("plugin_")
-                        {
-                            u32::from_str_radix(plugin_pane_id, 10)
-                                .ok()
-                                .or_else(|| {
-                                    malformed_ids.push(stringified_pane_id.to_owned());
-                                    None
-                                })
-                                .map(|id| PaneId::Plugin(id))
-                        } else {
-                            u32::from_str_radix(stringified_pane_id, 10)
-                                .ok()
-                                .or_else(|| {
-                                    malformed_ids.push(stringified_pane_id.to_owned());
-                                    None
-                                })
-                                .map(|id| PaneId::Terminal(id))
-                        }
+                        parse_pane_id(stringified_pane_id).or_else(|| {
+                            malformed_ids.push(stringified_pane_id.to_owned());
+                            None
+                        })
                     })
                     .collect();
                 if !malformed_ids.is_empty() {
@@ -783,6 +803,26 @@ impl Action {
                     Ok(vec![Action::StackPanes(pane_ids)])
                 }
             },
+            CliAction::OpenFile { path, pane_id } => {
+                let pane_id = match pane_id {
+                    Some(stringified_pane_id) => match parse_pane_id(&stringified_pane_id) {
+                        Some(pane_id) => Some(pane_id),
+                        None => {
+                            return Err(format!(
+                                "Malformed pane id: {}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)",
+                                stringified_pane_id
+                            ));
+                        },
+                    },
+                    None => None,
+                };
+                let path = if path.is_relative() {
+                    get_current_dir().join(path)
+                } else {
+                    path
+                };
+                Ok(vec![Action::OpenFile { path, pane_id }])
+            },
         }
     }
     pub fn launches_plugin(&self, plugin_url: &str) -> bool {
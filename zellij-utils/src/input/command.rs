@@ -1,6 +1,8 @@
 //! Trigger a command
 use crate::data::{Direction, OriginatingPlugin};
+use crate::errors::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -59,6 +61,13 @@ pub struct RunCommand {
     pub hold_on_start: bool,
     #[serde(default)]
     pub originating_plugin: Option<OriginatingPlugin>,
+    /// Environment variables set for this command only, on top of the ones of the session
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// If the command has not printed anything this long after it started, its pane shows that
+    /// it timed out (the command keeps running)
+    #[serde(default)]
+    pub startup_command_timeout_ms: Option<u64>,
 }
 
 impl std::fmt::Display for RunCommand {
@@ -77,6 +86,53 @@ impl std::fmt::Display for RunCommand {
     }
 }
 
+impl RunCommand {
+    /// The command as it would be typed into a shell: quoted where needed, preceded by its env
+    /// variables and run in a subshell that first `cd`s to its cwd if it has one, so that the
+    /// cwd of the shell it is typed into does not change
+    pub fn to_shell_command_line(&self) -> Result<String> {
+        let mut env: Vec<_> = self.env.iter().collect();
+        env.sort();
+        let mut words = vec![];
+        for (name, value) in env {
+            if !is_valid_env_var_name(name) {
+                return Err(anyhow!("Invalid environment variable name: {:?}", name));
+            }
+            words.push(format!("{}={}", name, shell_quote(value)));
+        }
+        words.push(shell_quote(&self.command.to_string_lossy()));
+        words.extend(self.args.iter().map(|arg| shell_quote(arg)));
+        let command_line = words.join(" ");
+        match &self.cwd {
+            Some(cwd) => Ok(format!(
+                "(cd {} && {})",
+                shell_quote(&cwd.to_string_lossy()),
+                command_line
+            )),
+            None => Ok(command_line),
+        }
+    }
+}
+
+fn is_valid_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        },
+        _ => false,
+    }
+}
+
+fn shell_quote(word: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !word.is_empty() && word.chars().all(is_safe) {
+        word.to_owned()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
 /// Intermediate representation
 #[derive(Clone, Debug, Deserialize, Default, Serialize, PartialEq, Eq)]
 pub struct RunCommandAction {
@@ -105,6 +161,7 @@ impl From<RunCommandAction> for RunCommand {
             hold_on_close: action.hold_on_close,
             hold_on_start: action.hold_on_start,
             originating_plugin: action.originating_plugin,
+            ..Default::default()
         }
     }
 }
@@ -135,3 +192,7 @@ impl RunCommand {
         self
     }
 }
+
+#[cfg(test)]
+#[path = "./unit/command_test.rs"]
+mod command_test;
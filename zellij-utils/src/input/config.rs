@@ -1,7 +1,7 @@
-use crate::data::Palette;
+use crate::data::{ConfigDiff, OptionChange, Palette};
 use miette::{Diagnostic, LabeledSpan, NamedSource, SourceCode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs::File;
 use std::io::{self, Read};
 use std::path::PathBuf;
@@ -9,8 +9,12 @@ use thiserror::Error;
 
 use std::convert::TryFrom;
 
+use super::hooks::SessionHooks;
 use super::keybinds::Keybinds;
+use super::keyboard_remapping::KeyboardRemapping;
 use super::layout::RunPluginOrAlias;
+use super::metrics::MetricsConfig;
+use super::notifications::NotificationsConfig;
 use super::options::Options;
 use super::plugins::{PluginAliases, PluginsConfigError};
 use super::theme::{Themes, UiConfig};
@@ -32,6 +36,10 @@ pub struct Config {
     pub ui: UiConfig,
     pub env: EnvironmentVariables,
     pub background_plugins: HashSet<RunPluginOrAlias>,
+    pub metrics: MetricsConfig,
+    pub hooks: SessionHooks,
+    pub keyboard_remapping: KeyboardRemapping,
+    pub notifications: NotificationsConfig,
 }
 
 #[derive(Error, Debug)]
@@ -125,6 +133,108 @@ impl ConfigError {
     }
 }
 
+/// The category of a problem found while validating a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticKind {
+    /// The file is not valid KDL
+    ParseError,
+    /// A top level node that is not a known option or section
+    UnknownOption,
+    /// A known option or section with a value that cannot be used
+    InvalidValue,
+    /// A key that used to be supported and has since been replaced
+    DeprecatedKey,
+    /// A keybinding referencing an action that does not exist
+    UnknownAction,
+    /// A key bound more than once in the same mode, only the last of which takes effect
+    DuplicateKeybind,
+}
+
+impl DiagnosticKind {
+    /// Whether the configuration can still be loaded despite this kind of problem
+    pub fn is_warning(&self) -> bool {
+        matches!(self, DiagnosticKind::DuplicateKeybind)
+    }
+}
+
+impl std::fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            DiagnosticKind::ParseError => write!(f, "parse error"),
+            DiagnosticKind::UnknownOption => write!(f, "unknown option"),
+            DiagnosticKind::InvalidValue => write!(f, "invalid value"),
+            DiagnosticKind::DeprecatedKey => write!(f, "deprecated key"),
+            DiagnosticKind::UnknownAction => write!(f, "unknown action"),
+            DiagnosticKind::DuplicateKeybind => write!(f, "duplicate keybind"),
+        }
+    }
+}
+
+/// A single problem found by `Config::validate`, `span` holds the start and end byte offsets
+/// of the offending text in the validated source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigDiagnostic {
+    pub kind: DiagnosticKind,
+    pub span: (usize, usize),
+    pub message: String,
+}
+
+impl ConfigDiagnostic {
+    pub fn new(kind: DiagnosticKind, offset: usize, len: usize, message: String) -> Self {
+        ConfigDiagnostic {
+            kind,
+            span: (offset, offset + len),
+            message,
+        }
+    }
+    /// Converts an error returned while parsing a single node, falling back to the span of that
+    /// node if the error does not carry one
+    pub fn from_config_error(error: ConfigError, node_offset: usize, node_len: usize) -> Self {
+        match error {
+            ConfigError::KdlError(KdlError {
+                error_message,
+                offset: Some(offset),
+                len: Some(len),
+                ..
+            }) => ConfigDiagnostic::new(DiagnosticKind::InvalidValue, offset, len, error_message),
+            ConfigError::KdlError(KdlError { error_message, .. }) => ConfigDiagnostic::new(
+                DiagnosticKind::InvalidValue,
+                node_offset,
+                node_len,
+                error_message,
+            ),
+            ConfigError::KdlDeserializationError(kdl_error) => ConfigDiagnostic::new(
+                DiagnosticKind::ParseError,
+                kdl_error.span.offset(),
+                kdl_error.span.len(),
+                kdl_error
+                    .help
+                    .map(|h| h.to_owned())
+                    .unwrap_or_else(|| kdl_error.to_string()),
+            ),
+            e => ConfigDiagnostic::new(
+                DiagnosticKind::InvalidValue,
+                node_offset,
+                node_len,
+                e.to_string(),
+            ),
+        }
+    }
+    /// The 1-based line and column of the start of this diagnostic in `source`
+    pub fn line_and_column(&self, source: &str) -> (usize, usize) {
+        let start = std::cmp::min(self.span.0, source.len());
+        let preceding = source.get(..start).unwrap_or("");
+        let line = preceding.matches('\n').count() + 1;
+        let column = preceding
+            .rsplit('\n')
+            .next()
+            .map(|l| l.chars().count())
+            .unwrap_or(0)
+            + 1;
+        (line, column)
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ConversionError {
     #[error("{0}")]
@@ -235,8 +345,83 @@ impl Config {
         self.plugins.merge(other.plugins);
         self.ui = self.ui.merge(other.ui);
         self.env = self.env.merge(other.env);
+        self.metrics = self.metrics.merge(other.metrics);
+        self.hooks = self.hooks.merge(other.hooks);
+        self.keyboard_remapping = self.keyboard_remapping.merge(other.keyboard_remapping);
+        self.notifications = self.notifications.merge(other.notifications);
         Ok(())
     }
+    /// The changes between this configuration and `other`, as seen from this one
+    pub fn diff(&self, other: &Config) -> ConfigDiff {
+        let option_values = |options: &Options| -> BTreeMap<String, String> {
+            let add_comments = false;
+            options
+                .to_kdl(add_comments)
+                .into_iter()
+                .map(|node| {
+                    let value = match node.children() {
+                        Some(children) => children.to_string().trim().to_owned(),
+                        None => node
+                            .entries()
+                            .iter()
+                            .map(|e| match e.value().as_string() {
+                                Some(string_value) => string_value.to_owned(),
+                                None => e.value().to_string(),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    };
+                    (node.name().value().to_owned(), value)
+                })
+                .collect()
+        };
+        let old_options = option_values(&self.options);
+        let new_options = option_values(&other.options);
+        let mut option_names: Vec<&String> = old_options.keys().chain(new_options.keys()).collect();
+        option_names.sort();
+        option_names.dedup();
+        let changed_options = option_names
+            .into_iter()
+            .filter(|name| old_options.get(*name) != new_options.get(*name))
+            .map(|name| OptionChange {
+                name: name.clone(),
+                old_value: old_options.get(name).cloned(),
+                new_value: new_options.get(name).cloned(),
+            })
+            .collect();
+
+        let mut added_keybinds = vec![];
+        let mut removed_keybinds = vec![];
+        for (input_mode, new_keys) in &other.keybinds.0 {
+            let old_keys = self.keybinds.0.get(input_mode);
+            for (key, actions) in new_keys {
+                if old_keys.and_then(|k| k.get(key)) != Some(actions) {
+                    added_keybinds.push((*input_mode, key.clone(), actions.clone()));
+                }
+            }
+        }
+        for (input_mode, old_keys) in &self.keybinds.0 {
+            let new_keys = other.keybinds.0.get(input_mode);
+            for key in old_keys.keys() {
+                if new_keys.map(|k| !k.contains_key(key)).unwrap_or(true) {
+                    removed_keybinds.push((*input_mode, key.clone()));
+                }
+            }
+        }
+        added_keybinds.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+        removed_keybinds.sort();
+
+        let changed_theme = self.theme_config(self.options.theme.as_ref())
+            != other.theme_config(other.options.theme.as_ref())
+            || self.options.theme != other.options.theme;
+
+        ConfigDiff {
+            changed_options,
+            added_keybinds,
+            removed_keybinds,
+            changed_theme,
+        }
+    }
     pub fn config_file_path(opts: &CliArgs) -> Option<PathBuf> {
         opts.config.clone().or_else(|| {
             opts.config_dir
@@ -407,7 +592,8 @@ impl Config {
 #[cfg(test)]
 mod config_test {
     use super::*;
-    use crate::data::{InputMode, Palette, PaletteColor};
+    use crate::data::{BareKey, InputMode, KeyWithModifier, Palette, PaletteColor};
+    use crate::input::actions::Action;
     use crate::input::layout::RunPlugin;
     use crate::input::options::{Clipboard, OnForceClose};
     use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
@@ -855,4 +1041,272 @@ mod config_test {
             "Env variables defined in config"
         );
     }
+
+    #[test]
+    fn can_define_session_hooks_in_config_file() {
+        let config_contents = r#"
+            on_attach {
+                command "notify-send attached"
+            }
+            on_exit {
+                command "echo bye >> /tmp/zellij-sessions"
+                command "notify-send exited"
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        assert_eq!(
+            config.hooks,
+            SessionHooks {
+                on_attach: vec!["notify-send attached".to_owned()],
+                on_exit: vec![
+                    "echo bye >> /tmp/zellij-sessions".to_owned(),
+                    "notify-send exited".to_owned(),
+                ],
+            },
+            "Session hooks defined in config"
+        );
+        let serialized = Config::from_kdl(&config.to_string(false), None).unwrap();
+        assert_eq!(
+            serialized.hooks, config.hooks,
+            "Session hooks survive serialization"
+        );
+    }
+
+    #[test]
+    fn can_define_keyboard_remapping_in_config_file() {
+        let config_contents = r#"
+            keyboard_remapping {
+                remap "\u{1b}[Z" "Shift Tab"
+                remap "\u{1b}[27;5;13~" "Ctrl Enter"
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        assert_eq!(
+            config.keyboard_remapping.get(b"\x1b[Z"),
+            Some(&KeyWithModifier::new(BareKey::Tab).with_shift_modifier()),
+            "Remapped sequence found in config"
+        );
+        assert_eq!(
+            config.keyboard_remapping.get(b"\x1b[27;5;13~"),
+            Some(&KeyWithModifier::new(BareKey::Enter).with_ctrl_modifier()),
+            "Second remapped sequence found in config"
+        );
+        let serialized = Config::from_kdl(&config.to_string(false), None).unwrap();
+        assert_eq!(
+            serialized.keyboard_remapping, config.keyboard_remapping,
+            "Keyboard remapping survives serialization"
+        );
+    }
+
+    #[test]
+    fn can_define_notifications_in_config_file() {
+        let config_contents = r#"
+            notifications {
+                show_startup_tips false
+                show_setup_wizard_on_first_run false
+                notification_duration_seconds 10
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        assert_eq!(
+            config.notifications,
+            NotificationsConfig {
+                show_startup_tips: Some(false),
+                show_release_notes: None,
+                show_setup_wizard_on_first_run: Some(false),
+                notification_duration_seconds: Some(10),
+            },
+            "Notifications defined in config"
+        );
+        assert!(
+            config.notifications.should_show_release_notes(),
+            "Notifications not mentioned in config are shown"
+        );
+        let serialized = Config::from_kdl(&config.to_string(false), None).unwrap();
+        assert_eq!(
+            serialized.notifications, config.notifications,
+            "Notifications survive serialization"
+        );
+    }
+
+    #[test]
+    fn notifications_config_rejects_unknown_settings() {
+        let config_contents = r#"
+            notifications {
+                show_tips_of_the_day false
+            }
+        "#;
+        assert!(
+            Config::from_kdl(config_contents, None).is_err(),
+            "Unknown notification setting is an error"
+        );
+    }
+
+    #[test]
+    fn keyboard_remapping_rejects_unknown_keys() {
+        let config_contents = r#"
+            keyboard_remapping {
+                remap "\u{1b}[Z" "Shift Tabb"
+            }
+        "#;
+        assert!(Config::from_kdl(config_contents, None).is_err());
+    }
+
+    #[test]
+    fn channel_sizes_must_be_positive() {
+        let config_contents = r#"
+            channel_buffer_size 0
+        "#;
+        assert!(
+            Config::from_kdl(config_contents, None).is_err(),
+            "A channel of size 0 is an error"
+        );
+        let config_contents = r#"
+            pty_channel_size -1
+        "#;
+        assert!(
+            Config::from_kdl(config_contents, None).is_err(),
+            "A negative channel size is an error"
+        );
+        let config_contents = r#"
+            screen_channel_size 1
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        assert_eq!(config.options.screen_channel_size, Some(1));
+    }
+
+    #[test]
+    fn audit_log_max_size_must_be_positive() {
+        let config_contents = r#"
+            audit_log_max_size_mb 0
+        "#;
+        assert!(
+            Config::from_kdl(config_contents, None).is_err(),
+            "An audit log max size of 0 is an error"
+        );
+        let config_contents = r#"
+            audit_log_max_size_mb 5
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        assert_eq!(config.options.audit_log_max_size_mb, Some(5));
+    }
+
+    #[test]
+    fn validate_reports_parse_errors() {
+        let config_contents = r#"
+            pane_frames true
+            keybinds {
+        "#;
+        let diagnostics = Config::validate(config_contents);
+        assert_eq!(diagnostics.len(), 1, "one diagnostic for unparseable kdl");
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::ParseError);
+    }
+
+    #[test]
+    fn validate_reports_all_semantic_errors() {
+        let config_contents = r#"
+            default_mode "not_a_mode"
+            pane_frames true
+            not_an_option true
+            on_force_close "explode"
+            no_pane_frames true
+        "#;
+        let diagnostics = Config::validate(config_contents);
+        let kinds: Vec<DiagnosticKind> = diagnostics.iter().map(|d| d.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                DiagnosticKind::InvalidValue,
+                DiagnosticKind::UnknownOption,
+                DiagnosticKind::InvalidValue,
+                DiagnosticKind::DeprecatedKey,
+            ],
+            "all problems reported in order"
+        );
+        let (start, end) = diagnostics[1].span;
+        assert_eq!(
+            &config_contents[start..end],
+            "not_an_option",
+            "span points at the unknown option"
+        );
+        assert_eq!(diagnostics[1].line_and_column(config_contents), (4, 13));
+    }
+
+    #[test]
+    fn diff_reports_changed_options_keybinds_and_theme() {
+        let old_config = Config::from_kdl(
+            r#"
+            theme "dracula"
+            pane_frames true
+            keybinds clear-defaults=true {
+                normal {
+                    bind "Ctrl a" { SwitchToMode "Locked"; }
+                    bind "Ctrl b" { SwitchToMode "Pane"; }
+                }
+            }
+        "#,
+            None,
+        )
+        .unwrap();
+        let new_config = Config::from_kdl(
+            r#"
+            theme "nord"
+            pane_frames false
+            mouse_mode false
+            keybinds clear-defaults=true {
+                normal {
+                    bind "Ctrl a" { SwitchToMode "Tab"; }
+                }
+            }
+        "#,
+            None,
+        )
+        .unwrap();
+        let diff = old_config.diff(&new_config);
+        let option_change =
+            |name: &str, old_value: Option<&str>, new_value: Option<&str>| OptionChange {
+                name: name.to_owned(),
+                old_value: old_value.map(|v| v.to_owned()),
+                new_value: new_value.map(|v| v.to_owned()),
+            };
+        assert_eq!(
+            diff.changed_options,
+            vec![
+                option_change("mouse_mode", None, Some("false")),
+                option_change("pane_frames", Some("true"), Some("false")),
+                option_change("theme", Some("dracula"), Some("nord")),
+            ]
+        );
+        assert_eq!(
+            diff.added_keybinds,
+            vec![(
+                InputMode::Normal,
+                KeyWithModifier::new(BareKey::Char('a')).with_ctrl_modifier(),
+                vec![Action::SwitchToMode(InputMode::Tab)]
+            )]
+        );
+        assert_eq!(
+            diff.removed_keybinds,
+            vec![(
+                InputMode::Normal,
+                KeyWithModifier::new(BareKey::Char('b')).with_ctrl_modifier()
+            )]
+        );
+        assert!(diff.changed_theme);
+        assert!(
+            new_config.diff(&new_config).is_empty(),
+            "identical configs have an empty diff"
+        );
+    }
+
+    #[test]
+    fn validate_accepts_default_config() {
+        let default_config = String::from_utf8(setup::DEFAULT_CONFIG.to_vec()).unwrap();
+        let diagnostics = Config::validate(&default_config);
+        assert!(
+            diagnostics.is_empty(),
+            "default config has no diagnostics: {:?}",
+            diagnostics
+        );
+    }
 }
@@ -0,0 +1,28 @@
+//! Shell commands the configuration asks to run at points of a session's lifetime
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionHooks {
+    /// Run (without waiting for them) whenever a client attaches to the session, including the
+    /// first one
+    pub on_attach: Vec<String>,
+    /// Run to completion when the session exits
+    pub on_exit: Vec<String>,
+}
+
+impl SessionHooks {
+    pub fn merge(&self, other: SessionHooks) -> Self {
+        SessionHooks {
+            on_attach: if other.on_attach.is_empty() {
+                self.on_attach.clone()
+            } else {
+                other.on_attach
+            },
+            on_exit: if other.on_exit.is_empty() {
+                self.on_exit.clone()
+            } else {
+                other.on_exit
+            },
+        }
+    }
+}
@@ -1,10 +1,88 @@
 use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
 
 use super::actions::Action;
+use super::config::{ConfigDiagnostic, DiagnosticKind};
 use crate::data::{BareKey, InputMode, KeyWithModifier, KeybindsVec};
 
+use kdl::KdlNode;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use strum::IntoEnumIterator;
+
+/// All the actions that can be bound to keys in the `keybinds` section of the configuration,
+/// along with a short description of what each of them does
+///
+/// The KDL action parser only accepts the actions listed here, so this is also the list of actions
+/// it understands
+pub const KEYBIND_ACTIONS: &[(&str, &str)] = &[
+    ("BreakPane", "Break the focused pane out into a new tab"),
+    ("BreakPaneLeft", "Move the focused pane to the tab on the left"),
+    ("BreakPaneRight", "Move the focused pane to the tab on the right"),
+    ("Clear", "Clear all buffers of the focused pane"),
+    ("CloseFocus", "Close the focused pane"),
+    ("CloseTab", "Close the current tab"),
+    ("Confirm", "Confirm a prompt"),
+    ("Copy", "Copy the current selection to the clipboard"),
+    ("Deny", "Deny a prompt"),
+    ("Detach", "Detach from the session and exit"),
+    ("DumpLayout", "Dump the current layout to STDOUT"),
+    ("DumpScreen", "Dump the focused pane to a file"),
+    ("EditScrollback", "Open the scrollback of the focused pane in the default editor"),
+    ("FocusNextPane", "Switch focus to the next pane"),
+    ("FocusPreviousPane", "Switch focus to the previous pane"),
+    ("GoToNextTab", "Go to the next tab"),
+    ("GoToPreviousTab", "Go to the previous tab"),
+    ("GoToTab", "Go to the tab with the specified index"),
+    ("HalfPageScrollDown", "Scroll down half a page in the focused pane"),
+    ("HalfPageScrollUp", "Scroll up half a page in the focused pane"),
+    ("LaunchOrFocusPlugin", "Launch a plugin, or focus it if it is already running"),
+    ("LaunchPlugin", "Launch a new instance of a plugin"),
+    ("MessagePlugin", "Send a message to a plugin, launching it if needed"),
+    ("MessagePluginId", "Send a message to the plugin with the specified id"),
+    ("MoveFocus", "Move focus in the specified direction"),
+    ("MoveFocusOrTab", "Move focus in the specified direction, or to the next/previous tab if there is no pane there"),
+    ("MovePane", "Move the focused pane in the specified direction"),
+    ("MovePaneBackwards", "Move the focused pane backwards"),
+    ("MoveTab", "Move the current tab in the specified direction"),
+    ("NewPane", "Open a new pane in the specified direction"),
+    ("NewTab", "Create a new tab, optionally with a specified tab layout"),
+    ("NextSwapLayout", "Switch to the next swap layout"),
+    ("NoOp", "Do nothing"),
+    ("PageScrollDown", "Scroll down one page in the focused pane"),
+    ("PageScrollUp", "Scroll up one page in the focused pane"),
+    ("PaneNameInput", "Write the given bytes to the name of the focused pane"),
+    ("PreviousSwapLayout", "Switch to the previous swap layout"),
+    ("Quit", "Quit Zellij"),
+    ("RenameSession", "Rename the current session"),
+    ("Resize", "Shrink or enlarge the focused pane"),
+    ("ResumeSession", "Resume the suspended session"),
+    ("Run", "Run the specified command in a new pane"),
+    ("ScrollDown", "Scroll down in the focused pane"),
+    ("ScrollToBottom", "Scroll down to the bottom of the focused pane"),
+    ("ScrollToTop", "Scroll up to the top of the focused pane"),
+    ("ScrollUp", "Scroll up in the focused pane"),
+    ("Search", "Search for the next or previous match"),
+    ("SearchInput", "Write the given bytes to the search term"),
+    ("SearchToggleOption", "Toggle a search option (eg. case sensitivity)"),
+    ("SwitchFocus", "Switch focus to the next pane"),
+    ("SuspendSession", "Stop the processes of all panes until the session is resumed"),
+    ("SwitchToMode", "Switch to the specified input mode"),
+    ("TabNameInput", "Write the given bytes to the name of the current tab"),
+    ("ToggleActiveSyncTab", "Toggle sending input to all panes in the current tab"),
+    ("ToggleExclusiveFloatingPanes", "Toggle showing only the focused floating pane"),
+    ("ToggleFloatingPanes", "Toggle the visibility of all floating panes in the current tab"),
+    ("ToggleFocusFullscreen", "Toggle between fullscreen focused pane and normal layout"),
+    ("ToggleMouseMode", "Toggle mouse support"),
+    ("TogglePaneEmbedOrFloating", "Embed the focused pane if floating, or float it if embedded"),
+    ("TogglePaneFrames", "Toggle frames around panes"),
+    ("TogglePanePinned", "Toggle whether the focused floating pane is always on top"),
+    ("ToggleTab", "Go to the previously focused tab"),
+    ("UndoRenamePane", "Undo renaming the focused pane"),
+    ("UndoRenameTab", "Undo renaming the current tab"),
+    ("Write", "Write the given bytes to the focused pane"),
+    ("WriteChars", "Write the given characters to the focused pane"),
+];
 
 /// Used in the config struct
 #[derive(Clone, PartialEq, Deserialize, Serialize, Default)]
@@ -112,6 +190,207 @@ impl Keybinds {
     }
 }
 
+impl Keybinds {
+    /// Checks the action and mode names referenced in a `keybinds` node, returning every one
+    /// that is not known along with a suggestion for what might have been meant, as well as every
+    /// key bound more than once in the same mode
+    pub fn validate_kdl(kdl_keybinds: &KdlNode) -> Vec<ConfigDiagnostic> {
+        let mut diagnostics = vec![];
+        // keys bound by the blocks of each mode so far, and the actions they are bound to
+        let mut keys_bound_in_modes: HashMap<InputMode, HashMap<KeyWithModifier, String>> =
+            HashMap::new();
+        let blocks = match kdl_keybinds.children() {
+            Some(children) => children.nodes(),
+            None => return diagnostics,
+        };
+        for block in blocks {
+            let block_name = block.name().value();
+            match block_name {
+                "shared" | "shared_except" | "shared_among" => {
+                    for entry in block.entries().iter().filter(|e| e.name().is_none()) {
+                        if let Some(mode_name) = entry.value().as_string() {
+                            if let Some(diagnostic) = validate_mode_name(mode_name, entry.span()) {
+                                diagnostics.push(diagnostic);
+                            }
+                        }
+                    }
+                },
+                "unbind" => continue,
+                mode_name => {
+                    if let Some(diagnostic) = validate_mode_name(mode_name, block.name().span()) {
+                        diagnostics.push(diagnostic);
+                    }
+                },
+            }
+            // shared blocks are meant to be overridden by the blocks of specific modes, so only
+            // keys bound twice within the same one of them are reported
+            match InputMode::from_str(block_name) {
+                Ok(mode) => {
+                    let keys_bound_in_mode = keys_bound_in_modes.entry(mode).or_default();
+                    let mode_name = format!("mode '{}'", format!("{:?}", mode).to_lowercase());
+                    diagnostics.append(&mut duplicate_keybinds(
+                        block,
+                        &mode_name,
+                        keys_bound_in_mode,
+                    ));
+                },
+                Err(_) => {
+                    let block_args: Vec<String> = block
+                        .entries()
+                        .iter()
+                        .filter_map(|e| e.value().as_string())
+                        .map(|mode_name| format!("\"{}\"", mode_name))
+                        .collect();
+                    let block_description = format!("'{} {}'", block_name, block_args.join(" "));
+                    diagnostics.append(&mut duplicate_keybinds(
+                        block,
+                        &block_description,
+                        &mut HashMap::new(),
+                    ));
+                },
+            }
+            let bind_nodes = block
+                .children()
+                .map(|c| c.nodes())
+                .unwrap_or(&[])
+                .iter()
+                .filter(|n| n.name().value() == "bind");
+            for bind_node in bind_nodes {
+                for action in bind_node.children().map(|c| c.nodes()).unwrap_or(&[]) {
+                    diagnostics.append(&mut validate_action(action));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+fn duplicate_keybinds(
+    block: &KdlNode,
+    block_description: &str,
+    bound_keys: &mut HashMap<KeyWithModifier, String>,
+) -> Vec<ConfigDiagnostic> {
+    let mut diagnostics = vec![];
+    let bind_nodes = block
+        .children()
+        .map(|c| c.nodes())
+        .unwrap_or(&[])
+        .iter()
+        .filter(|n| n.name().value() == "bind");
+    for bind_node in bind_nodes {
+        let actions = bind_node
+            .children()
+            .map(|c| c.nodes())
+            .unwrap_or(&[])
+            .iter()
+            .map(|action| action.to_string().trim().trim_end_matches(';').to_owned())
+            .collect::<Vec<_>>()
+            .join("; ");
+        for entry in bind_node.entries().iter().filter(|e| e.name().is_none()) {
+            // keys that cannot be parsed are reported when parsing the keybinds
+            let Some(key_str) = entry.value().as_string() else {
+                continue;
+            };
+            let Ok(key) = KeyWithModifier::from_str(key_str) else {
+                continue;
+            };
+            if let Some(previous_actions) = bound_keys.insert(key, actions.clone()) {
+                diagnostics.push(ConfigDiagnostic::new(
+                    DiagnosticKind::DuplicateKeybind,
+                    entry.span().offset(),
+                    entry.span().len(),
+                    format!(
+                        "'{}' is bound twice in {}, to '{}' and then to '{}' which overrides it",
+                        key_str, block_description, previous_actions, actions
+                    ),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+fn validate_action(action: &KdlNode) -> Vec<ConfigDiagnostic> {
+    let mut diagnostics = vec![];
+    let action_name = action.name().value();
+    if !KEYBIND_ACTIONS.iter().any(|(name, _)| *name == action_name) {
+        let suggestion = closest_match(action_name, KEYBIND_ACTIONS.iter().map(|(name, _)| *name));
+        let message = match suggestion {
+            Some(suggestion) => format!(
+                "Unknown action '{}', did you mean '{}'?",
+                action_name, suggestion
+            ),
+            None => format!("Unknown action '{}'", action_name),
+        };
+        diagnostics.push(ConfigDiagnostic::new(
+            DiagnosticKind::UnknownAction,
+            action.name().span().offset(),
+            action.name().span().len(),
+            message,
+        ));
+    } else if action_name == "SwitchToMode" {
+        if let Some(entry) = action.entries().iter().next() {
+            if let Some(mode_name) = entry.value().as_string() {
+                if let Some(diagnostic) = validate_mode_name(mode_name, entry.span()) {
+                    diagnostics.push(diagnostic);
+                }
+            }
+        }
+    }
+    diagnostics
+}
+
+fn validate_mode_name(mode_name: &str, span: &miette::SourceSpan) -> Option<ConfigDiagnostic> {
+    if InputMode::from_str(mode_name).is_ok() {
+        return None;
+    }
+    let mode_names: Vec<String> = InputMode::iter()
+        .map(|m| format!("{:?}", m).to_lowercase())
+        .collect();
+    let message = match closest_match(mode_name, mode_names.iter().map(|m| m.as_str())) {
+        Some(suggestion) => format!(
+            "Unknown mode '{}', did you mean '{}'?",
+            mode_name, suggestion
+        ),
+        None => format!("Unknown mode '{}'", mode_name),
+    };
+    Some(ConfigDiagnostic::new(
+        DiagnosticKind::InvalidValue,
+        span.offset(),
+        span.len(),
+        message,
+    ))
+}
+
+fn closest_match<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = std::cmp::max(2, name.chars().count() / 3);
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// case insensitive levenshtein distance
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current_row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = if a_char == b_char { 0 } else { 1 };
+            current_row.push(
+                (previous_row[j] + substitution_cost)
+                    .min(previous_row[j + 1] + 1)
+                    .min(current_row[j] + 1),
+            );
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
 // we need to do this because [10] in standard STDIN, [10] is both Enter (without a carriage
 // return) and ctrl-j - so here, if ctrl-j is bound we return its bound action, and otherwise we
 // just write the raw bytes to the terminal and let whichever program is there decide what they are
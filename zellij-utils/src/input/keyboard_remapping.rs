@@ -0,0 +1,23 @@
+//! Byte sequences sent by terminals for keys they do not encode the standard way, and the keys
+//! they stand for
+use crate::data::KeyWithModifier;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct KeyboardRemapping(pub BTreeMap<Vec<u8>, KeyWithModifier>);
+
+impl KeyboardRemapping {
+    pub fn merge(&self, other: KeyboardRemapping) -> Self {
+        let mut remapping = self.0.clone();
+        remapping.extend(other.0);
+        KeyboardRemapping(remapping)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The key a whole read from STDIN stands for, if it was remapped
+    pub fn get(&self, bytes: &[u8]) -> Option<&KeyWithModifier> {
+        self.0.get(bytes)
+    }
+}
@@ -17,7 +17,7 @@ use crate::{
         command::RunCommand,
         config::{Config, ConfigError},
     },
-    pane_size::{Constraint, Dimension, PaneGeom},
+    pane_size::{Constraint, Dimension, PaneGeom, Size},
     setup::{self},
 };
 #[cfg(not(target_family = "wasm"))]
@@ -28,8 +28,9 @@ use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use super::plugins::{PluginAliases, PluginTag, PluginsConfigError};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::vec::Vec;
 use std::{
     fmt,
@@ -108,23 +109,20 @@ impl RunPluginOrAlias {
             if run_plugin_alias.run_plugin.is_some() {
                 log::warn!("Overriding plugin alias");
             }
-            let merged_run_plugin = plugin_aliases
-                .aliases
-                .get(run_plugin_alias.name.as_str())
-                .map(|r| {
-                    let mut merged_run_plugin = r.clone().merge_configuration(
-                        &run_plugin_alias
-                            .configuration
-                            .as_ref()
-                            .map(|c| c.inner().clone()),
-                    );
-                    // if the alias has its own cwd, it should always override the alias
-                    // value's cwd
-                    if run_plugin_alias.initial_cwd.is_some() {
-                        merged_run_plugin.initial_cwd = run_plugin_alias.initial_cwd.clone();
-                    }
-                    merged_run_plugin
-                });
+            let merged_run_plugin = plugin_aliases.get(run_plugin_alias.name.as_str()).map(|r| {
+                let mut merged_run_plugin = r.merge_configuration(
+                    &run_plugin_alias
+                        .configuration
+                        .as_ref()
+                        .map(|c| c.inner().clone()),
+                );
+                // if the alias has its own cwd, it should always override the alias
+                // value's cwd
+                if run_plugin_alias.initial_cwd.is_some() {
+                    merged_run_plugin.initial_cwd = run_plugin_alias.initial_cwd.clone();
+                }
+                merged_run_plugin
+            });
             run_plugin_alias.run_plugin = merged_run_plugin;
         }
     }
@@ -161,9 +159,8 @@ impl RunPluginOrAlias {
                 let mut plugin_alias = PluginAlias::new(&url, configuration, None);
                 if let Some(alias_dict) = alias_dict {
                     plugin_alias.run_plugin = alias_dict
-                        .aliases
                         .get(url)
-                        .map(|r| r.clone().merge_configuration(configuration));
+                        .map(|r| r.merge_configuration(configuration));
                 }
                 Ok(RunPluginOrAlias::Alias(plugin_alias))
             },
@@ -256,6 +253,12 @@ impl Run {
                 if merged.args.is_empty() && !base_run_command.args.is_empty() {
                     merged.args = base_run_command.args.clone();
                 }
+                let mut merged_env = base_run_command.env.clone();
+                merged_env.extend(merged.env.drain());
+                merged.env = merged_env;
+                if merged.startup_command_timeout_ms.is_none() {
+                    merged.startup_command_timeout_ms = base_run_command.startup_command_timeout_ms;
+                }
                 Some(Run::Command(merged))
             },
             (Some(Run::Command(base_run_command)), Some(Run::Cwd(other_cwd))) => {
@@ -328,6 +331,44 @@ impl Run {
             },
         }
     }
+    pub fn resolve_relative_paths(&mut self, base_cwd: &Path) {
+        // unlike add_cwd, this leaves panes without an explicit cwd alone and only touches
+        // paths that are relative
+        let resolve = |path: &mut PathBuf| {
+            if path.is_relative() {
+                *path = base_cwd.join(&path);
+            }
+        };
+        match self {
+            Run::Command(run_command) => {
+                // bare command names (eg. "htop") are looked up in the PATH, so we only resolve
+                // commands that are paths (eg. "scripts/build.sh")
+                if run_command.command.components().count() > 1 {
+                    resolve(&mut run_command.command);
+                }
+                if let Some(cwd) = run_command.cwd.as_mut() {
+                    resolve(cwd);
+                }
+            },
+            Run::EditFile(path_to_file, _line_number, edit_cwd) => {
+                if let Some(edit_cwd) = edit_cwd.as_mut() {
+                    resolve(edit_cwd);
+                }
+                resolve(path_to_file);
+            },
+            Run::Cwd(path) => resolve(path),
+            Run::Plugin(RunPluginOrAlias::RunPlugin(run_plugin)) => {
+                if let RunPluginLocation::File(path) = &mut run_plugin.location {
+                    resolve(path);
+                }
+                if let Some(initial_cwd) = run_plugin.initial_cwd.as_mut() {
+                    resolve(initial_cwd);
+                }
+            },
+            // aliases are defined in the config rather than in the layout
+            Run::Plugin(RunPluginOrAlias::Alias(_)) => {},
+        }
+    }
     pub fn add_args(&mut self, args: Option<Vec<String>>) {
         // overrides the args of a Run::Command if they are Some
         // and not empty
@@ -339,6 +380,22 @@ impl Run {
             }
         }
     }
+    pub fn add_env(&mut self, env: Option<HashMap<String, String>>) {
+        // adds to the env of a Run::Command if it is Some, overriding variables with the same name
+        if let Some(env) = env {
+            if let Run::Command(run_command) = self {
+                run_command.env.extend(env);
+            }
+        }
+    }
+    pub fn add_startup_command_timeout_ms(&mut self, startup_command_timeout_ms: Option<u64>) {
+        // overrides the startup_command_timeout_ms of a Run::Command if it is Some
+        if let Some(startup_command_timeout_ms) = startup_command_timeout_ms {
+            if let Run::Command(run_command) = self {
+                run_command.startup_command_timeout_ms = Some(startup_command_timeout_ms);
+            }
+        }
+    }
     pub fn add_close_on_exit(&mut self, close_on_exit: Option<bool>) {
         // overrides the hold_on_close of a Run::Command if it is Some
         // and not empty
@@ -639,15 +696,131 @@ impl fmt::Display for RunPluginLocation {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum LayoutConstraint {
+    // focused pane constraints come first so that a layout meant for a specific pane is applied
+    // whenever that pane is focused
+    FocusedPaneIsPlugin,
+    FocusedPaneIsTerminal,
+    FocusedPaneTitleMatches(String), // regex
+    // plugin pane constraints come first so that they are tried before the pane count ones
+    MaxPluginPanes(usize),
+    MinPluginPanes(usize),
+    ExactPluginPanes(usize),
     MaxPanes(usize),
     MinPanes(usize),
     ExactPanes(usize),
     NoConstraint,
 }
 
+impl LayoutConstraint {
+    /// Parses a swap layout trigger condition (eg. "plugin_count > 0")
+    pub fn from_trigger(trigger: &str) -> Result<Self, String> {
+        let condition = trigger
+            .trim()
+            .strip_prefix("plugin_count")
+            .ok_or_else(|| {
+                format!(
+                    "Unknown trigger \"{}\", only plugin_count triggers are supported (eg. \"plugin_count > 0\")",
+                    trigger
+                )
+            })?
+            .trim();
+        let (operator, count) = [">=", "<=", "==", ">", "<"]
+            .iter()
+            .find_map(|operator| {
+                condition
+                    .strip_prefix(operator)
+                    .map(|count| (*operator, count.trim()))
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Trigger \"{}\" should compare plugin_count with one of: >, >=, <, <=, ==",
+                    trigger
+                )
+            })?;
+        let count: usize = count.parse().map_err(|_| {
+            format!(
+                "Trigger \"{}\" should compare plugin_count with a fixed number",
+                trigger
+            )
+        })?;
+        match operator {
+            ">" => Ok(LayoutConstraint::MinPluginPanes(count + 1)),
+            ">=" => Ok(LayoutConstraint::MinPluginPanes(count)),
+            "<" if count == 0 => Err(format!("Trigger \"{}\" can never be met", trigger)),
+            "<" => Ok(LayoutConstraint::MaxPluginPanes(count - 1)),
+            "<=" => Ok(LayoutConstraint::MaxPluginPanes(count)),
+            _ => Ok(LayoutConstraint::ExactPluginPanes(count)),
+        }
+    }
+    /// The trigger condition of plugin pane constraints, None for other constraints
+    pub fn to_trigger(&self) -> Option<String> {
+        match self {
+            LayoutConstraint::MaxPluginPanes(max_plugin_panes) => {
+                Some(format!("plugin_count <= {}", max_plugin_panes))
+            },
+            LayoutConstraint::MinPluginPanes(min_plugin_panes) => {
+                Some(format!("plugin_count >= {}", min_plugin_panes))
+            },
+            LayoutConstraint::ExactPluginPanes(exact_plugin_panes) => {
+                Some(format!("plugin_count == {}", exact_plugin_panes))
+            },
+            _ => None,
+        }
+    }
+    /// Parses the `swap_layout_when_pane_type` trigger ("plugin" or "terminal")
+    pub fn from_focused_pane_type(pane_type: &str) -> Result<Self, String> {
+        match pane_type {
+            "plugin" => Ok(LayoutConstraint::FocusedPaneIsPlugin),
+            "terminal" => Ok(LayoutConstraint::FocusedPaneIsTerminal),
+            _ => Err(format!(
+                "Unknown pane type \"{}\", swap_layout_when_pane_type should be either \"plugin\" or \"terminal\"",
+                pane_type
+            )),
+        }
+    }
+    /// Parses the `swap_layout_when_pane_title_matches` trigger, making sure it's a valid regex
+    pub fn from_focused_pane_title_regex(regex: &str) -> Result<Self, String> {
+        Regex::new(regex).map_err(|e| {
+            format!(
+                "swap_layout_when_pane_title_matches should be a valid regex: {}",
+                e
+            )
+        })?;
+        Ok(LayoutConstraint::FocusedPaneTitleMatches(regex.to_owned()))
+    }
+    /// Whether the focused pane meets this constraint, None if this is not a focused pane
+    /// constraint
+    pub fn matches_focused_pane(&self, is_plugin: bool, title: &str) -> Option<bool> {
+        match self {
+            LayoutConstraint::FocusedPaneIsPlugin => Some(is_plugin),
+            LayoutConstraint::FocusedPaneIsTerminal => Some(!is_plugin),
+            LayoutConstraint::FocusedPaneTitleMatches(regex) => Some(
+                Regex::new(regex)
+                    .map(|regex| regex.is_match(title))
+                    .unwrap_or(false),
+            ),
+            _ => None,
+        }
+    }
+}
+
 impl Display for LayoutConstraint {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
+            LayoutConstraint::FocusedPaneIsPlugin => {
+                write!(f, "swap_layout_when_pane_type=\"plugin\"")
+            },
+            LayoutConstraint::FocusedPaneIsTerminal => {
+                write!(f, "swap_layout_when_pane_type=\"terminal\"")
+            },
+            LayoutConstraint::FocusedPaneTitleMatches(regex) => {
+                write!(f, "swap_layout_when_pane_title_matches={:?}", regex)
+            },
+            LayoutConstraint::MaxPluginPanes(..)
+            | LayoutConstraint::MinPluginPanes(..)
+            | LayoutConstraint::ExactPluginPanes(..) => {
+                write!(f, "trigger=\"{}\"", self.to_trigger().unwrap_or_default())
+            },
             LayoutConstraint::MaxPanes(max_panes) => write!(f, "max_panes={}", max_panes),
             LayoutConstraint::MinPanes(min_panes) => write!(f, "min_panes={}", min_panes),
             LayoutConstraint::ExactPanes(exact_panes) => write!(f, "exact_panes={}", exact_panes),
@@ -670,6 +843,9 @@ pub struct Layout {
     pub swap_layouts: Vec<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     pub swap_tiled_layouts: Vec<SwapTiledLayout>,
     pub swap_floating_layouts: Vec<SwapFloatingLayout>,
+    pub start_hooks: Vec<RunCommand>, // run when the session starts
+    pub stop_hooks: Vec<RunCommand>,  // run when the session ends
+    pub default_shell: Option<PathBuf>, // overrides the configured default_shell
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -744,6 +920,7 @@ pub struct FloatingPaneLayout {
     pub already_running: bool,
     pub pane_initial_contents: Option<String>,
     pub logical_position: Option<usize>,
+    pub scrollback_size: Option<usize>, // None means the global scroll_buffer_size
 }
 
 impl FloatingPaneLayout {
@@ -760,6 +937,7 @@ impl FloatingPaneLayout {
             already_running: false,
             pane_initial_contents: None,
             logical_position: None,
+            scrollback_size: None,
         }
     }
     pub fn add_cwd_to_layout(&mut self, cwd: &PathBuf) {
@@ -775,6 +953,11 @@ impl FloatingPaneLayout {
             run.add_start_suspended(start_suspended);
         }
     }
+    pub fn resolve_relative_paths(&mut self, base_cwd: &Path) {
+        if let Some(run) = self.run.as_mut() {
+            run.resolve_relative_paths(base_cwd);
+        }
+    }
 }
 
 impl From<&TiledPaneLayout> for FloatingPaneLayout {
@@ -783,6 +966,7 @@ impl From<&TiledPaneLayout> for FloatingPaneLayout {
             name: pane_layout.name.clone(),
             run: pane_layout.run.clone(),
             focus: pane_layout.focus,
+            scrollback_size: pane_layout.scrollback_size,
             ..Default::default()
         }
     }
@@ -803,7 +987,11 @@ pub struct TiledPaneLayout {
     pub exclude_from_sync: Option<bool>,
     pub run_instructions_to_ignore: Vec<Option<Run>>,
     pub hide_floating_panes: bool, // only relevant if this is the base layout
+    pub pin_to_session: bool,      // only relevant if this is the base layout
     pub pane_initial_contents: Option<String>,
+    pub min_size: Option<Size>,
+    pub scrollback_size: Option<usize>, // None means the global scroll_buffer_size
+    pub auto_focus: Option<bool>, // focus this pane once all the panes in its tab have spawned
 }
 
 impl TiledPaneLayout {
@@ -982,6 +1170,14 @@ impl TiledPaneLayout {
             child.add_cwd_to_layout(cwd);
         }
     }
+    pub fn resolve_relative_paths(&mut self, base_cwd: &Path) {
+        if let Some(run) = self.run.as_mut() {
+            run.resolve_relative_paths(base_cwd);
+        }
+        for child in self.children.iter_mut() {
+            child.resolve_relative_paths(base_cwd);
+        }
+    }
     pub fn populate_plugin_aliases_in_layout(&mut self, plugin_aliases: &PluginAliases) {
         match self.run.as_mut() {
             Some(run) => run.populate_run_plugin_if_needed(plugin_aliases),
@@ -1060,6 +1256,19 @@ impl TiledPaneLayout {
             1 // just me
         }
     }
+    pub fn auto_focused_pane_count(&self) -> usize {
+        let own_count = if self.auto_focus.unwrap_or(false) {
+            1
+        } else {
+            0
+        };
+        own_count
+            + self
+                .children
+                .iter()
+                .map(|child| child.auto_focused_pane_count())
+                .sum::<usize>()
+    }
     pub fn has_focused_node(&self) -> bool {
         if self.focus.map(|f| f).unwrap_or(false) {
             return true;
@@ -1207,10 +1416,10 @@ impl Layout {
             None,
         );
         if should_start_layout_commands_suspended {
-            layout
-                .iter_mut()
-                .next()
-                .map(|l| l.recursively_add_start_suspended_including_template(Some(true)));
+            layout.iter_mut().next().map(|l| {
+                l.recursively_add_start_suspended_including_template(Some(true));
+                l.remove_session_hooks();
+            });
         }
         layout
     }
@@ -1262,7 +1471,7 @@ impl Layout {
     ) -> Result<(Layout, Config), ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
             Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+        let mut layout = Layout::from_kdl(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
@@ -1271,6 +1480,7 @@ impl Layout {
             None,
         )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
+        layout.resolve_relative_paths_against_config(&config);
         Ok((layout, config))
     }
     #[cfg(not(target_family = "wasm"))]
@@ -1284,15 +1494,18 @@ impl Layout {
         })?;
         let mut layout = Layout::from_kdl(&raw_layout, Some(url.into()), None, None)?;
         layout.recursively_add_start_suspended_including_template(Some(true));
+        layout.remove_session_hooks();
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
+        layout.resolve_relative_paths_against_config(&config);
         Ok((layout, config))
     }
     pub fn from_stringified_layout(
         stringified_layout: &str,
         config: Config,
     ) -> Result<(Layout, Config), ConfigError> {
-        let layout = Layout::from_kdl(&stringified_layout, None, None, None)?;
+        let mut layout = Layout::from_kdl(&stringified_layout, None, None, None)?;
         let config = Config::from_kdl(&stringified_layout, Some(config))?; // this merges the two config, with
+        layout.resolve_relative_paths_against_config(&config);
         Ok((layout, config))
     }
     #[cfg(target_family = "wasm")]
@@ -1324,7 +1537,7 @@ impl Layout {
     ) -> Result<(Layout, Config), ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
             Layout::stringified_from_default_assets(layout_name)?;
-        let layout = Layout::from_kdl(
+        let mut layout = Layout::from_kdl(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
@@ -1333,6 +1546,7 @@ impl Layout {
             None,
         )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
+        layout.resolve_relative_paths_against_config(&config);
         Ok((layout, config))
     }
     pub fn from_str(
@@ -1510,6 +1724,15 @@ impl Layout {
             }
         }
     }
+    /// Session hooks run without asking as soon as the session starts or ends, so they are removed
+    /// from layouts loaded from a url (whose commands only start once confirmed)
+    pub fn remove_session_hooks(&mut self) {
+        if !self.start_hooks.is_empty() || !self.stop_hooks.is_empty() {
+            log::warn!("Ignoring the session hooks of a layout loaded from a url");
+        }
+        self.start_hooks.clear();
+        self.stop_hooks.clear();
+    }
     pub fn recursively_add_start_suspended_including_template(
         &mut self,
         start_suspended: Option<bool>,
@@ -1579,6 +1802,27 @@ impl Layout {
             }
         }
     }
+    pub fn resolve_relative_paths(&mut self, base_cwd: &Path) {
+        for (_, tiled_pane_layout, floating_panes) in self.tabs.iter_mut() {
+            tiled_pane_layout.resolve_relative_paths(base_cwd);
+            for floating_pane in floating_panes {
+                floating_pane.resolve_relative_paths(base_cwd);
+            }
+        }
+        if let Some((tiled_pane_layout, floating_panes)) = self.template.as_mut() {
+            tiled_pane_layout.resolve_relative_paths(base_cwd);
+            for floating_pane in floating_panes {
+                floating_pane.resolve_relative_paths(base_cwd);
+            }
+        }
+    }
+    fn resolve_relative_paths_against_config(&mut self, config: &Config) {
+        if let Some(default_layout_cwd) = &config.options.default_layout_cwd {
+            let default_layout_cwd = default_layout_cwd.display().to_string();
+            let default_layout_cwd = shellexpand::tilde(&default_layout_cwd);
+            self.resolve_relative_paths(Path::new(default_layout_cwd.as_ref()));
+        }
+    }
     pub fn pane_count(&self) -> usize {
         let mut pane_count = 0;
         if let Some((tiled_pane_layout, floating_panes)) = self.template.as_ref() {
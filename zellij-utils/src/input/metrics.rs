@@ -0,0 +1,62 @@
+//! Configuration of where and how the metrics emitted by Zellij and its plugins are reported
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MetricsFormat {
+    #[default]
+    Disabled,
+    Statsd,
+    Prometheus,
+    Json,
+}
+
+impl FromStr for MetricsFormat {
+    type Err = String;
+    fn from_str(format: &str) -> Result<Self, String> {
+        match format {
+            "disabled" => Ok(MetricsFormat::Disabled),
+            "statsd" => Ok(MetricsFormat::Statsd),
+            "prometheus" => Ok(MetricsFormat::Prometheus),
+            "json" => Ok(MetricsFormat::Json),
+            _ => Err(format!(
+                "Unknown metrics format: {}, expected one of: statsd, prometheus, json, disabled",
+                format
+            )),
+        }
+    }
+}
+
+impl fmt::Display for MetricsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MetricsFormat::Disabled => write!(f, "disabled"),
+            MetricsFormat::Statsd => write!(f, "statsd"),
+            MetricsFormat::Prometheus => write!(f, "prometheus"),
+            MetricsFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MetricsConfig {
+    pub format: MetricsFormat,
+    /// A `host:port` address for statsd, a file path for prometheus (in the text exposition
+    /// format, eg. for the node_exporter textfile collector) and json (one object per line)
+    pub endpoint: Option<String>,
+    pub flush_interval_seconds: Option<u64>,
+}
+
+impl MetricsConfig {
+    pub fn merge(&self, other: MetricsConfig) -> Self {
+        MetricsConfig {
+            format: other.format,
+            endpoint: other.endpoint.or_else(|| self.endpoint.clone()),
+            flush_interval_seconds: other.flush_interval_seconds.or(self.flush_interval_seconds),
+        }
+    }
+    pub fn is_enabled(&self) -> bool {
+        self.format != MetricsFormat::Disabled
+    }
+}
@@ -1,9 +1,13 @@
 pub mod actions;
 pub mod command;
 pub mod config;
+pub mod hooks;
 pub mod keybinds;
+pub mod keyboard_remapping;
 pub mod layout;
+pub mod metrics;
 pub mod mouse;
+pub mod notifications;
 pub mod options;
 pub mod permission;
 pub mod plugins;
@@ -43,6 +47,7 @@ mod not_wasm {
             style: attributes.style,
             capabilities,
             session_name,
+            client_id: None,
         }
     }
 
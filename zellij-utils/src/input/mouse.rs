@@ -208,4 +208,9 @@ impl MouseEvent {
         };
         event
     }
+    /// The mouse moved without any of its buttons held down
+    pub fn is_hover(&self) -> bool {
+        self.event_type == MouseEventType::Motion
+            && !(self.left || self.right || self.middle || self.wheel_up || self.wheel_down)
+    }
 }
@@ -0,0 +1,41 @@
+//! Configuration of the popups Zellij shows on its own (eg. the setup wizard on first run)
+//!
+//! Only `show_setup_wizard_on_first_run` is read for now: Zellij has no startup tips, release
+//! notes or popups that dismiss themselves yet. The other keys are accepted so that these popups
+//! can read their settings from here once they exist.
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct NotificationsConfig {
+    /// Not read yet, there are no startup tips
+    pub show_startup_tips: Option<bool>,
+    /// Not read yet, there are no release notes
+    pub show_release_notes: Option<bool>,
+    pub show_setup_wizard_on_first_run: Option<bool>,
+    /// How long popups that dismiss themselves stay on screen, not read yet as there are none
+    pub notification_duration_seconds: Option<u64>,
+}
+
+impl NotificationsConfig {
+    pub fn merge(&self, other: NotificationsConfig) -> Self {
+        NotificationsConfig {
+            show_startup_tips: other.show_startup_tips.or(self.show_startup_tips),
+            show_release_notes: other.show_release_notes.or(self.show_release_notes),
+            show_setup_wizard_on_first_run: other
+                .show_setup_wizard_on_first_run
+                .or(self.show_setup_wizard_on_first_run),
+            notification_duration_seconds: other
+                .notification_duration_seconds
+                .or(self.notification_duration_seconds),
+        }
+    }
+    pub fn should_show_startup_tips(&self) -> bool {
+        self.show_startup_tips.unwrap_or(true)
+    }
+    pub fn should_show_release_notes(&self) -> bool {
+        self.show_release_notes.unwrap_or(true)
+    }
+    pub fn should_show_setup_wizard_on_first_run(&self) -> bool {
+        self.show_setup_wizard_on_first_run.unwrap_or(true)
+    }
+}
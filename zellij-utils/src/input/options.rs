@@ -1,6 +1,7 @@
 //! Handles cli and configuration options
 use crate::cli::Command;
 use crate::data::InputMode;
+use crate::kdl::schema::KdlSchema;
 use clap::{ArgEnum, Args};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -32,7 +33,7 @@ impl FromStr for OnForceClose {
     }
 }
 
-#[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, Args)]
+#[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, Args, KdlSchema)]
 /// Options that can be set either through the config file,
 /// or cli flags - cli flags should take precedence over the config file
 /// TODO: In order to correctly parse boolean flags, this is currently split
@@ -42,12 +43,15 @@ pub struct Options {
     /// that is compatible with more fonts (true or false)
     #[clap(long, value_parser)]
     #[serde(default)]
+    #[kdl_schema(default = false)]
     pub simplified_ui: Option<bool>,
     /// Set the default theme
     #[clap(long, value_parser)]
+    #[kdl_schema(default = "default")]
     pub theme: Option<String>,
     /// Set the default mode
     #[clap(long, arg_enum, hide_possible_values = true, value_parser)]
+    #[kdl_schema(default = "normal")]
     pub default_mode: Option<InputMode>,
     /// Set the default shell
     #[clap(long, value_parser)]
@@ -57,6 +61,7 @@ pub struct Options {
     pub default_cwd: Option<PathBuf>,
     /// Set the default layout
     #[clap(long, value_parser)]
+    #[kdl_schema(default = "default")]
     pub default_layout: Option<PathBuf>,
     /// Set the layout_dir, defaults to
     /// subdirectory of config dir
@@ -70,19 +75,25 @@ pub struct Options {
     #[serde(default)]
     /// Set the handling of mouse events (true or false)
     /// Can be temporarily bypassed by the [SHIFT] key
+    #[kdl_schema(default = true)]
     pub mouse_mode: Option<bool>,
     #[clap(long, value_parser)]
     #[serde(default)]
     /// Set display of the pane frames (true or false)
+    #[kdl_schema(default = true)]
     pub pane_frames: Option<bool>,
     #[clap(long, value_parser)]
     #[serde(default)]
     /// Mirror session when multiple users are connected (true or false)
+    #[kdl_schema(default = false)]
     pub mirror_session: Option<bool>,
     /// Set behaviour on force close (quit or detach)
     #[clap(long, arg_enum, hide_possible_values = true, value_parser)]
+    #[kdl_schema(default = "detach")]
     pub on_force_close: Option<OnForceClose>,
+    /// The number of lines kept in the scrollback buffer of each pane
     #[clap(long, value_parser)]
+    #[kdl_schema(default = 10000)]
     pub scroll_buffer_size: Option<usize>,
 
     /// Switch to using a user supplied command for clipboard instead of OSC52
@@ -99,11 +110,13 @@ pub struct Options {
         value_parser
     )]
     #[serde(default)]
+    #[kdl_schema(default = "system")]
     pub copy_clipboard: Option<Clipboard>,
 
     /// Automatically copy when selecting text (true or false)
     #[clap(long, value_parser)]
     #[serde(default)]
+    #[kdl_schema(default = true)]
     pub copy_on_select: Option<bool>,
 
     /// Explicit full path to open the scrollback editor (default is $EDITOR or $VISUAL)
@@ -118,22 +131,26 @@ pub struct Options {
     /// Whether to attach to a session specified in "session-name" if it exists
     #[clap(long, value_parser)]
     #[serde(default)]
+    #[kdl_schema(default = false)]
     pub attach_to_session: Option<bool>,
 
     /// Whether to lay out panes in a predefined set of layouts whenever possible
     #[clap(long, value_parser)]
     #[serde(default)]
+    #[kdl_schema(default = true)]
     pub auto_layout: Option<bool>,
 
     /// Whether sessions should be serialized to the HD so that they can be later resurrected,
     /// default is true
     #[clap(long, value_parser)]
     #[serde(default)]
+    #[kdl_schema(default = true)]
     pub session_serialization: Option<bool>,
 
     /// Whether pane viewports are serialized along with the session, default is false
     #[clap(long, value_parser)]
     #[serde(default)]
+    #[kdl_schema(default = false)]
     pub serialize_pane_viewport: Option<bool>,
 
     /// Scrollback lines to serialize along with the pane viewport when serializing sessions, 0
@@ -146,21 +163,133 @@ pub struct Options {
     /// Whether to use ANSI styled underlines
     #[clap(long, value_parser)]
     #[serde(default)]
+    #[kdl_schema(default = true)]
     pub styled_underlines: Option<bool>,
 
-    /// The interval at which to serialize sessions for resurrection (in seconds)
+    /// The interval at which to serialize sessions for resurrection (in seconds), 0 disables
+    /// periodic serialization, unset uses the default of 60 seconds
     #[clap(long, value_parser)]
+    #[kdl_schema(default = 60)]
     pub serialization_interval: Option<u64>,
 
-    /// If true, will disable writing session metadata to disk
+    /// If true, will disable writing session metadata to disk, this also disables periodic
+    /// serialization regardless of `serialization_interval`
     #[clap(long, value_parser)]
+    #[kdl_schema(default = false)]
     pub disable_session_metadata: Option<bool>,
 
     /// Whether to enable support for the Kitty keyboard protocol (must also be supported by the
     /// host terminal), defaults to true if the terminal supports it
     #[clap(long, value_parser)]
     #[serde(default)]
+    #[kdl_schema(default = true)]
     pub support_kitty_keyboard_protocol: Option<bool>,
+
+    /// If true, the client will try to reconnect to its session (with an exponential backoff)
+    /// when the connection to the server is lost, rather than exiting
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    #[kdl_schema(default = false)]
+    pub auto_reconnect: Option<bool>,
+
+    /// Detach clients that have not sent any input for this many seconds, unset (the default)
+    /// never detaches idle clients
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub client_idle_timeout_seconds: Option<u64>,
+
+    /// Bound the size of the channels used to communicate between the server threads, unset (the
+    /// default) keeps the current sizes (unbounded except for the pty to screen channel)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub channel_buffer_size: Option<usize>,
+
+    /// Size of the channel through which terminal output reaches the screen thread, overrides
+    /// `channel_buffer_size` (default: 50)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    #[kdl_schema(default = 50)]
+    pub screen_channel_size: Option<usize>,
+
+    /// Size of the pty thread's channel, overrides `channel_buffer_size` (default: unbounded)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub pty_channel_size: Option<usize>,
+
+    /// Log how full each of the server's channels is at this interval, unset (the default) logs
+    /// nothing
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub channel_metrics_interval_seconds: Option<u64>,
+
+    /// If true, only one floating pane is shown at a time: focusing a floating pane hides the
+    /// previously focused one (default: false)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    #[kdl_schema(default = false)]
+    pub exclusive_floating_panes: Option<bool>,
+
+    /// Whether to advertise sixel graphics support to the programs running in panes, images are
+    /// approximated with half-block characters if the terminal Zellij runs in cannot display
+    /// them (default: true)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    #[kdl_schema(default = true)]
+    pub sixel_support: Option<bool>,
+
+    /// Whether to only handle the last of several terminal resizes a client sends in a row, so that
+    /// quickly resizing the terminal does not recompute the layout for every intermediate size
+    /// (default: true)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    #[kdl_schema(default = true)]
+    pub deduplicate_resize: Option<bool>,
+
+    /// Folders to look for plugins in when a plugin is referred to by its bare name (eg. my-plugin
+    /// for my-plugin.wasm), before the plugins folder of the data dir
+    #[clap(long, value_parser, multiple_values = true)]
+    #[serde(default)]
+    pub plugin_search_paths: Option<Vec<PathBuf>>,
+
+    /// The maximum number of terminal panes in a session, panes beyond it are not opened and
+    /// plugins are sent the PaneCountExceeded event (default: 200)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    #[kdl_schema(default = 200)]
+    pub session_max_panes: Option<usize>,
+
+    /// Whether to save the session's layout when the session exits, to
+    /// `$XDG_DATA_HOME/zellij/saved-layouts/<session-name>-<timestamp>.kdl`, keeping the 10
+    /// newest per session name (default: false)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    #[kdl_schema(default = false)]
+    pub auto_save_layout_on_exit: Option<bool>,
+
+    /// Whether hovering over a pane with the mouse focuses it, without having to click it
+    /// (default: false)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    #[kdl_schema(default = false)]
+    pub focus_follows_mouse: Option<bool>,
+
+    /// The directory relative `cwd`, `command` and `edit` paths in layouts are resolved
+    /// against, `~` expands to the home directory (default: none, they are left relative)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub default_layout_cwd: Option<PathBuf>,
+
+    /// A file every action sent by the session's clients is appended to, one JSON object
+    /// per line (default: none, no audit log is written)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub audit_log_path: Option<PathBuf>,
+
+    /// The size in megabytes after which the audit log is rotated to `<audit_log_path>.1`
+    /// (default: none, the audit log is never rotated)
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    #[serde(default)]
+    pub audit_log_max_size_mb: Option<u64>,
 }
 
 #[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
@@ -239,6 +368,34 @@ impl Options {
         let support_kitty_keyboard_protocol = other
             .support_kitty_keyboard_protocol
             .or(self.support_kitty_keyboard_protocol);
+        let auto_reconnect = other.auto_reconnect.or(self.auto_reconnect);
+        let client_idle_timeout_seconds = other
+            .client_idle_timeout_seconds
+            .or(self.client_idle_timeout_seconds);
+        let channel_buffer_size = other.channel_buffer_size.or(self.channel_buffer_size);
+        let screen_channel_size = other.screen_channel_size.or(self.screen_channel_size);
+        let pty_channel_size = other.pty_channel_size.or(self.pty_channel_size);
+        let channel_metrics_interval_seconds = other
+            .channel_metrics_interval_seconds
+            .or(self.channel_metrics_interval_seconds);
+        let exclusive_floating_panes = other
+            .exclusive_floating_panes
+            .or(self.exclusive_floating_panes);
+        let sixel_support = other.sixel_support.or(self.sixel_support);
+        let deduplicate_resize = other.deduplicate_resize.or(self.deduplicate_resize);
+        let plugin_search_paths = other
+            .plugin_search_paths
+            .or_else(|| self.plugin_search_paths.clone());
+        let session_max_panes = other.session_max_panes.or(self.session_max_panes);
+        let auto_save_layout_on_exit = other
+            .auto_save_layout_on_exit
+            .or(self.auto_save_layout_on_exit);
+        let focus_follows_mouse = other.focus_follows_mouse.or(self.focus_follows_mouse);
+        let default_layout_cwd = other
+            .default_layout_cwd
+            .or_else(|| self.default_layout_cwd.clone());
+        let audit_log_path = other.audit_log_path.or_else(|| self.audit_log_path.clone());
+        let audit_log_max_size_mb = other.audit_log_max_size_mb.or(self.audit_log_max_size_mb);
 
         Options {
             simplified_ui,
@@ -268,6 +425,22 @@ impl Options {
             serialization_interval,
             disable_session_metadata,
             support_kitty_keyboard_protocol,
+            auto_reconnect,
+            client_idle_timeout_seconds,
+            channel_buffer_size,
+            screen_channel_size,
+            pty_channel_size,
+            channel_metrics_interval_seconds,
+            exclusive_floating_panes,
+            sixel_support,
+            deduplicate_resize,
+            plugin_search_paths,
+            session_max_panes,
+            auto_save_layout_on_exit,
+            focus_follows_mouse,
+            default_layout_cwd,
+            audit_log_path,
+            audit_log_max_size_mb,
         }
     }
 
@@ -326,6 +499,34 @@ impl Options {
         let support_kitty_keyboard_protocol = other
             .support_kitty_keyboard_protocol
             .or(self.support_kitty_keyboard_protocol);
+        let auto_reconnect = other.auto_reconnect.or(self.auto_reconnect);
+        let client_idle_timeout_seconds = other
+            .client_idle_timeout_seconds
+            .or(self.client_idle_timeout_seconds);
+        let channel_buffer_size = other.channel_buffer_size.or(self.channel_buffer_size);
+        let screen_channel_size = other.screen_channel_size.or(self.screen_channel_size);
+        let pty_channel_size = other.pty_channel_size.or(self.pty_channel_size);
+        let channel_metrics_interval_seconds = other
+            .channel_metrics_interval_seconds
+            .or(self.channel_metrics_interval_seconds);
+        let exclusive_floating_panes = other
+            .exclusive_floating_panes
+            .or(self.exclusive_floating_panes);
+        let sixel_support = other.sixel_support.or(self.sixel_support);
+        let deduplicate_resize = other.deduplicate_resize.or(self.deduplicate_resize);
+        let plugin_search_paths = other
+            .plugin_search_paths
+            .or_else(|| self.plugin_search_paths.clone());
+        let session_max_panes = other.session_max_panes.or(self.session_max_panes);
+        let auto_save_layout_on_exit = other
+            .auto_save_layout_on_exit
+            .or(self.auto_save_layout_on_exit);
+        let focus_follows_mouse = other.focus_follows_mouse.or(self.focus_follows_mouse);
+        let default_layout_cwd = other
+            .default_layout_cwd
+            .or_else(|| self.default_layout_cwd.clone());
+        let audit_log_path = other.audit_log_path.or_else(|| self.audit_log_path.clone());
+        let audit_log_max_size_mb = other.audit_log_max_size_mb.or(self.audit_log_max_size_mb);
 
         Options {
             simplified_ui,
@@ -355,6 +556,22 @@ impl Options {
             serialization_interval,
             disable_session_metadata,
             support_kitty_keyboard_protocol,
+            auto_reconnect,
+            client_idle_timeout_seconds,
+            channel_buffer_size,
+            screen_channel_size,
+            pty_channel_size,
+            channel_metrics_interval_seconds,
+            exclusive_floating_panes,
+            sixel_support,
+            deduplicate_resize,
+            plugin_search_paths,
+            session_max_panes,
+            auto_save_layout_on_exit,
+            focus_follows_mouse,
+            default_layout_cwd,
+            audit_log_path,
+            audit_log_max_size_mb,
         }
     }
 
@@ -420,6 +637,22 @@ impl From<CliOptions> for Options {
             styled_underlines: opts.styled_underlines,
             serialization_interval: opts.serialization_interval,
             support_kitty_keyboard_protocol: opts.support_kitty_keyboard_protocol,
+            auto_reconnect: opts.auto_reconnect,
+            client_idle_timeout_seconds: opts.client_idle_timeout_seconds,
+            channel_buffer_size: opts.channel_buffer_size,
+            screen_channel_size: opts.screen_channel_size,
+            pty_channel_size: opts.pty_channel_size,
+            channel_metrics_interval_seconds: opts.channel_metrics_interval_seconds,
+            exclusive_floating_panes: opts.exclusive_floating_panes,
+            sixel_support: opts.sixel_support,
+            deduplicate_resize: opts.deduplicate_resize,
+            plugin_search_paths: opts.plugin_search_paths,
+            session_max_panes: opts.session_max_panes,
+            auto_save_layout_on_exit: opts.auto_save_layout_on_exit,
+            focus_follows_mouse: opts.focus_follows_mouse,
+            default_layout_cwd: opts.default_layout_cwd,
+            audit_log_path: opts.audit_log_path,
+            audit_log_max_size_mb: opts.audit_log_max_size_mb,
             ..Default::default()
         }
     }
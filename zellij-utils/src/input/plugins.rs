@@ -16,6 +16,9 @@ use crate::errors::prelude::*;
 #[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct PluginAliases {
     pub aliases: BTreeMap<String, RunPlugin>,
+    /// Folders in which plugins that are not aliases can be found by their bare name
+    #[serde(default)]
+    pub search_paths: Vec<PathBuf>,
 }
 
 impl PluginAliases {
@@ -23,7 +26,31 @@ impl PluginAliases {
         self.aliases.extend(other.aliases);
     }
     pub fn from_data(aliases: BTreeMap<String, RunPlugin>) -> Self {
-        PluginAliases { aliases }
+        PluginAliases {
+            aliases,
+            ..Default::default()
+        }
+    }
+    /// The alias with this name, or else the first `<name>.wasm` plugin found in the search paths
+    pub fn get(&self, name: &str) -> Option<RunPlugin> {
+        self.aliases
+            .get(name)
+            .cloned()
+            .or_else(|| self.find_in_search_paths(name))
+    }
+    fn find_in_search_paths(&self, name: &str) -> Option<RunPlugin> {
+        // only bare names, so that an alias cannot be used to reach outside the search paths
+        if name.is_empty() || Path::new(name).file_name() != Some(name.as_ref()) {
+            return None;
+        }
+        self.search_paths
+            .iter()
+            .map(|search_path| search_path.join(name).with_extension("wasm"))
+            .find(|plugin_path| plugin_path.is_file())
+            .map(|plugin_path| RunPlugin {
+                location: RunPluginLocation::File(plugin_path),
+                ..Default::default()
+            })
     }
     pub fn list(&self) -> Vec<String> {
         self.aliases.keys().cloned().collect()
@@ -64,6 +91,9 @@ impl PluginConfig {
                     || tag == "session-manager"
                     || tag == "configuration"
                     || tag == "plugin-manager"
+                    || tag == "keybind-help"
+                    || tag == "search-all-panes"
+                    || tag == "pane-history"
                 {
                     Some(PluginConfig {
                         path: PathBuf::from(&tag),
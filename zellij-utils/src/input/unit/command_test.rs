@@ -0,0 +1,31 @@
+use super::*;
+use std::collections::HashMap;
+
+#[test]
+fn shell_command_line_runs_in_a_subshell_when_it_has_a_cwd() {
+    let command = RunCommand {
+        command: PathBuf::from("ls"),
+        cwd: Some(PathBuf::from("/tmp/some dir")),
+        ..Default::default()
+    };
+    assert_eq!(
+        command.to_shell_command_line().unwrap(),
+        "(cd '/tmp/some dir' && ls)"
+    );
+}
+
+#[test]
+fn shell_command_line_rejects_invalid_env_variable_names() {
+    for name in ["1VAR", "MY-VAR", "A B", "$(rm -rf ~)", ""] {
+        let command = RunCommand {
+            command: PathBuf::from("ls"),
+            env: HashMap::from([(name.to_owned(), "value".to_owned())]),
+            ..Default::default()
+        };
+        assert!(
+            command.to_shell_command_line().is_err(),
+            "{:?} should be rejected",
+            name
+        );
+    }
+}
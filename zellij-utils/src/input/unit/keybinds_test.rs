@@ -1,7 +1,7 @@
 use super::super::actions::*;
 use super::super::keybinds::*;
 use crate::data::{BareKey, Direction, KeyWithModifier};
-use crate::input::config::Config;
+use crate::input::config::{Config, DiagnosticKind};
 use insta::assert_snapshot;
 use strum::IntoEnumIterator;
 
@@ -554,3 +554,119 @@ fn error_received_on_unknown_key_instruction() {
     let config_error = Config::from_kdl(config_contents, None).unwrap_err();
     assert_snapshot!(format!("{:?}", config_error));
 }
+
+#[test]
+fn validate_keybinds_suggests_misspelled_actions_and_modes() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Ctrl g" { SwitchToMod "Locked"; }
+                bind "Ctrl p" { SwitchToMode "pain"; }
+                bind "Ctrl z" { IAmNotAnActionAtAll; }
+            }
+            shared_except "lockd" {
+                bind "Ctrl q" { Quit; }
+            }
+            scrol {
+                bind "j" { ScrollDown; }
+            }
+        }
+    "#;
+    let diagnostics = Config::validate_keybinds(config_contents);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Unknown action 'SwitchToMod', did you mean 'SwitchToMode'?",
+            "Unknown mode 'pain', did you mean 'pane'?",
+            "Unknown action 'IAmNotAnActionAtAll'",
+            "Unknown mode 'lockd', did you mean 'locked'?",
+            "Unknown mode 'scrol', did you mean 'scroll'?",
+        ],
+        "All invalid actions and modes reported with suggestions"
+    );
+}
+
+#[test]
+fn validate_keybinds_reports_keys_bound_twice_in_the_same_mode() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Ctrl g" { SwitchToMode "Locked"; }
+                bind "Ctrl h" { FocusNextPane; }
+            }
+            normal {
+                bind "ctrl g" { Quit; }
+            }
+            shared_except "locked" {
+                bind "Ctrl h" { Detach; }
+                bind "Alt n" { NewPane; }
+                bind "Alt n" "Alt m" { NewTab; }
+            }
+        }
+    "#;
+    let diagnostics = Config::validate_keybinds(config_contents);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "'ctrl g' is bound twice in mode 'normal', to 'SwitchToMode \"Locked\"' and then to 'Quit' which overrides it",
+            "'Alt n' is bound twice in 'shared_except \"locked\"', to 'NewPane' and then to 'NewTab' which overrides it",
+        ],
+        "keys bound twice in the same mode reported, overriding a shared block is not"
+    );
+    assert!(diagnostics
+        .iter()
+        .all(|d| d.kind == DiagnosticKind::DuplicateKeybind && d.kind.is_warning()));
+    let (start, end) = diagnostics[0].span;
+    assert_eq!(&config_contents[start..end], "\"ctrl g\"");
+}
+
+#[test]
+fn unlisted_keybind_actions_are_not_parsed() {
+    let config_contents = r#"
+        keybinds {
+            resize {
+                bind "H" { ResizeNew "Decrease Left"; }
+            }
+        }
+    "#;
+    let error = Config::from_kdl(config_contents, None).unwrap_err();
+    assert!(
+        format!("{:?}", error).contains("Unsupported action: ResizeNew"),
+        "got {:?}",
+        error
+    );
+    let diagnostics = Config::validate_keybinds(config_contents);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::UnknownAction);
+}
+
+#[test]
+fn validate_keybinds_accepts_default_keybinds() {
+    let default_config = Config::default().to_string(false);
+    assert_eq!(Config::validate_keybinds(&default_config), vec![]);
+}
+
+#[test]
+fn all_listed_keybind_actions_can_be_parsed() {
+    for (action_name, _description) in KEYBIND_ACTIONS {
+        let config_contents = format!(
+            r#"
+            keybinds {{
+                normal {{
+                    bind "Ctrl g" {{ {}; }}
+                }}
+            }}
+        "#,
+            action_name
+        );
+        if let Err(e) = Config::from_kdl(&config_contents, None) {
+            assert!(
+                !format!("{:?}", e).contains("Unsupported action"),
+                "{} is listed but not supported",
+                action_name
+            );
+        }
+    }
+}
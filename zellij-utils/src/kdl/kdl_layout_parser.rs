@@ -1,3 +1,4 @@
+use crate::envs::EnvironmentVariables;
 use crate::input::{
     command::RunCommand,
     config::ConfigError,
@@ -13,6 +14,7 @@ use kdl::*;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
 
+use crate::pane_size::Size;
 use crate::{
     kdl_child_with_name, kdl_children_nodes, kdl_first_entry_as_bool, kdl_first_entry_as_i64,
     kdl_first_entry_as_string, kdl_get_bool_property_or_child_value,
@@ -75,9 +77,11 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "tab"
             || word == "args"
             || word == "close_on_exit"
+            || word == "hold_on_close"
             || word == "start_suspended"
             || word == "borderless"
             || word == "focus"
+            || word == "auto_focus"
             || word == "name"
             || word == "size"
             || word == "cwd"
@@ -85,11 +89,16 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "swap_tiled_layout"
             || word == "swap_floating_layout"
             || word == "hide_floating_panes"
+            || word == "pin_to_session"
             || word == "contents_file"
+            || word == "start_hook"
+            || word == "stop_hook"
+            || word == "default_shell"
     }
     fn is_a_valid_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
             || property_name == "focus"
+            || property_name == "auto_focus"
             || property_name == "name"
             || property_name == "size"
             || property_name == "plugin"
@@ -97,7 +106,10 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
+            || property_name == "startup_command_timeout_ms"
             || property_name == "close_on_exit"
+            || property_name == "hold_on_close"
             || property_name == "start_suspended"
             || property_name == "split_direction"
             || property_name == "pane"
@@ -106,6 +118,8 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
             || property_name == "contents_file"
+            || property_name == "min_size"
+            || property_name == "scrollback_size"
     }
     fn is_a_valid_floating_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -116,7 +130,10 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
+            || property_name == "startup_command_timeout_ms"
             || property_name == "close_on_exit"
+            || property_name == "hold_on_close"
             || property_name == "start_suspended"
             || property_name == "x"
             || property_name == "y"
@@ -124,6 +141,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "height"
             || property_name == "pinned"
             || property_name == "contents_file"
+            || property_name == "scrollback_size"
     }
     fn is_a_valid_tab_property(&self, property_name: &str) -> bool {
         property_name == "focus"
@@ -135,7 +153,12 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "max_panes"
             || property_name == "min_panes"
             || property_name == "exact_panes"
+            || property_name == "trigger"
+            || property_name == "swap_layout_when_pane_type"
+            || property_name == "swap_layout_when_pane_title_matches"
             || property_name == "hide_floating_panes"
+            || property_name == "pin_to_session"
+            || property_name == "use_template"
     }
     pub fn is_a_reserved_plugin_property(property_name: &str) -> bool {
         property_name == "location"
@@ -210,6 +233,59 @@ impl<'a> KdlLayoutParser<'a> {
         }
         Ok(())
     }
+    fn parse_min_size(&self, kdl_node: &KdlNode) -> Result<Option<Size>, ConfigError> {
+        match kdl_get_child!(kdl_node, "min_size") {
+            Some(min_size_node) => {
+                let rows = kdl_get_int_property_or_child_value!(min_size_node, "rows");
+                let cols = kdl_get_int_property_or_child_value!(min_size_node, "cols");
+                if rows.is_none() && cols.is_none() {
+                    return Err(kdl_parsing_error!(
+                        format!("min_size should have rows and/or cols (eg. 'min_size {{ rows 5; cols 20; }}')"),
+                        min_size_node
+                    ));
+                }
+                if rows.map(|r| r < 0).unwrap_or(false) || cols.map(|c| c < 0).unwrap_or(false) {
+                    return Err(kdl_parsing_error!(
+                        format!("min_size rows and cols cannot be negative"),
+                        min_size_node
+                    ));
+                }
+                Ok(Some(Size {
+                    rows: rows.unwrap_or(0) as usize,
+                    cols: cols.unwrap_or(0) as usize,
+                }))
+            },
+            None => Ok(None),
+        }
+    }
+    fn parse_scrollback_size(&self, kdl_node: &KdlNode) -> Result<Option<usize>, ConfigError> {
+        match kdl_get_int_property_or_child_value!(kdl_node, "scrollback_size") {
+            Some(scrollback_size) if scrollback_size < 0 => Err(kdl_parsing_error!(
+                format!("scrollback_size cannot be negative"),
+                kdl_node
+            )),
+            Some(scrollback_size) => Ok(Some(scrollback_size as usize)),
+            None => Ok(None),
+        }
+    }
+    // hold_on_close is the inverse of close_on_exit, a command pane can be configured with either
+    fn parse_close_on_exit(&self, kdl_node: &KdlNode) -> Result<Option<bool>, ConfigError> {
+        let close_on_exit =
+            kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
+        let hold_on_close =
+            kdl_get_bool_property_or_child_value_with_error!(kdl_node, "hold_on_close");
+        match (close_on_exit, hold_on_close) {
+            (Some(close_on_exit), Some(hold_on_close)) if close_on_exit == hold_on_close => {
+                Err(kdl_parsing_error!(
+                    format!("close_on_exit and hold_on_close contradict each other"),
+                    kdl_node
+                ))
+            },
+            (Some(close_on_exit), _) => Ok(Some(close_on_exit)),
+            (None, Some(hold_on_close)) => Ok(Some(!hold_on_close)),
+            (None, None) => Ok(None),
+        }
+    }
     fn parse_split_size(&self, kdl_node: &KdlNode) -> Result<Option<SplitSize>, ConfigError> {
         if let Some(size) = kdl_get_string_property_or_child_value!(kdl_node, "size") {
             match SplitSize::from_str(size) {
@@ -403,6 +479,30 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_env(
+        &self,
+        pane_node: &KdlNode,
+    ) -> Result<Option<HashMap<String, String>>, ConfigError> {
+        match kdl_get_child!(pane_node, "env") {
+            Some(kdl_env) => Ok(Some(
+                EnvironmentVariables::from_kdl(kdl_env)?.inner().clone(),
+            )),
+            None => Ok(None),
+        }
+    }
+    fn parse_startup_command_timeout_ms(
+        &self,
+        kdl_node: &KdlNode,
+    ) -> Result<Option<u64>, ConfigError> {
+        match kdl_get_int_property_or_child_value!(kdl_node, "startup_command_timeout_ms") {
+            Some(timeout_ms) if timeout_ms <= 0 => Err(kdl_parsing_error!(
+                format!("startup_command_timeout_ms must be a positive number"),
+                kdl_node
+            )),
+            Some(timeout_ms) => Ok(Some(timeout_ms as u64)),
+            None => Ok(None),
+        }
+    }
     fn cwd_prefix(&self, tab_cwd: Option<&PathBuf>) -> Result<Option<PathBuf>, ConfigError> {
         Ok(match (&self.global_cwd, tab_cwd) {
             (Some(global_cwd), Some(tab_cwd)) => Some(global_cwd.join(tab_cwd)),
@@ -433,14 +533,17 @@ impl<'a> KdlLayoutParser<'a> {
         let edit = self.parse_path(pane_node, "edit")?;
         let cwd = self.parse_path(pane_node, "cwd")?;
         let args = self.parse_args(pane_node)?;
-        let close_on_exit =
-            kdl_get_bool_property_or_child_value_with_error!(pane_node, "close_on_exit");
+        let env = self.parse_env(pane_node)?;
+        let startup_command_timeout_ms = self.parse_startup_command_timeout_ms(pane_node)?;
+        let close_on_exit = self.parse_close_on_exit(pane_node)?;
         let start_suspended =
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "start_suspended");
         if !is_template {
             self.assert_no_bare_attributes_in_pane_node(
                 &command,
                 &args,
+                &env,
+                &startup_command_timeout_ms,
                 &close_on_exit,
                 &start_suspended,
                 pane_node,
@@ -454,6 +557,8 @@ impl<'a> KdlLayoutParser<'a> {
                 command,
                 args: args.unwrap_or_else(|| vec![]),
                 cwd,
+                env: env.unwrap_or_default(),
+                startup_command_timeout_ms,
                 hold_on_close,
                 hold_on_start,
                 ..Default::default()
@@ -528,6 +633,7 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded").unwrap_or(false);
         let borderless = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "borderless");
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
+        let auto_focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "auto_focus");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
             .map(|name| name.to_string());
         let exclude_from_sync =
@@ -535,6 +641,8 @@ impl<'a> KdlLayoutParser<'a> {
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
         let split_size = self.parse_split_size(kdl_node)?;
+        let min_size = self.parse_min_size(kdl_node)?;
+        let scrollback_size = self.parse_scrollback_size(kdl_node)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let (external_children_index, children) = match kdl_children_nodes!(kdl_node) {
@@ -585,6 +693,9 @@ impl<'a> KdlLayoutParser<'a> {
             children_are_stacked,
             is_expanded_in_stack,
             pane_initial_contents,
+            min_size,
+            scrollback_size,
+            auto_focus,
             ..Default::default()
         })
     }
@@ -598,6 +709,7 @@ impl<'a> KdlLayoutParser<'a> {
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
         let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
+        let scrollback_size = self.parse_scrollback_size(kdl_node)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
@@ -623,6 +735,7 @@ impl<'a> KdlLayoutParser<'a> {
             focus,
             pinned,
             pane_initial_contents,
+            scrollback_size,
             ..Default::default()
         })
     }
@@ -704,6 +817,8 @@ impl<'a> KdlLayoutParser<'a> {
                 let borderless =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "borderless");
                 let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
+                let auto_focus =
+                    kdl_get_bool_property_or_child_value_with_error!(kdl_node, "auto_focus");
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let children_are_stacked =
@@ -711,11 +826,14 @@ impl<'a> KdlLayoutParser<'a> {
                 let is_expanded_in_stack =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded");
                 let args = self.parse_args(kdl_node)?;
-                let close_on_exit =
-                    kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
+                let env = self.parse_env(kdl_node)?;
+                let startup_command_timeout_ms = self.parse_startup_command_timeout_ms(kdl_node)?;
+                let close_on_exit = self.parse_close_on_exit(kdl_node)?;
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let split_size = self.parse_split_size(kdl_node)?;
+                let min_size = self.parse_min_size(kdl_node)?;
+                let scrollback_size = self.parse_scrollback_size(kdl_node)?;
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 let exclude_from_sync =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
@@ -729,6 +847,8 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
+                    &startup_command_timeout_ms,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -743,6 +863,9 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
+                    pane_template_run_command
+                        .add_startup_command_timeout_ms(startup_command_timeout_ms);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -752,6 +875,9 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(focus) = focus {
                     pane_template.focus = Some(focus);
                 }
+                if let Some(auto_focus) = auto_focus {
+                    pane_template.auto_focus = Some(auto_focus);
+                }
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
@@ -761,6 +887,12 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(split_size) = split_size {
                     pane_template.split_size = Some(split_size);
                 }
+                if let Some(min_size) = min_size {
+                    pane_template.min_size = Some(min_size);
+                }
+                if let Some(scrollback_size) = scrollback_size {
+                    pane_template.scrollback_size = Some(scrollback_size);
+                }
                 if let Some(index_of_children) = pane_template.external_children_index {
                     pane_template.children.insert(
                         index_of_children,
@@ -817,8 +949,9 @@ impl<'a> KdlLayoutParser<'a> {
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let args = self.parse_args(kdl_node)?;
-                let close_on_exit =
-                    kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
+                let env = self.parse_env(kdl_node)?;
+                let startup_command_timeout_ms = self.parse_startup_command_timeout_ms(kdl_node)?;
+                let close_on_exit = self.parse_close_on_exit(kdl_node)?;
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
@@ -826,6 +959,8 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
+                    &startup_command_timeout_ms,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -835,6 +970,9 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
+                    pane_template_run_command
+                        .add_startup_command_timeout_ms(startup_command_timeout_ms);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -849,6 +987,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
                 let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
                 let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
+                let scrollback_size = self.parse_scrollback_size(kdl_node)?;
                 if let Some(height) = height {
                     pane_template.height = Some(height);
                 }
@@ -864,6 +1003,9 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(pinned) = pinned {
                     pane_template.pinned = Some(pinned);
                 }
+                if let Some(scrollback_size) = scrollback_size {
+                    pane_template.scrollback_size = Some(scrollback_size);
+                }
                 Ok(pane_template)
             },
             PaneOrFloatingPane::Either(mut pane_template) => {
@@ -871,8 +1013,9 @@ impl<'a> KdlLayoutParser<'a> {
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let args = self.parse_args(kdl_node)?;
-                let close_on_exit =
-                    kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
+                let env = self.parse_env(kdl_node)?;
+                let startup_command_timeout_ms = self.parse_startup_command_timeout_ms(kdl_node)?;
+                let close_on_exit = self.parse_close_on_exit(kdl_node)?;
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
@@ -880,6 +1023,8 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
+                    &startup_command_timeout_ms,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -889,6 +1034,9 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
+                    pane_template_run_command
+                        .add_startup_command_timeout_ms(startup_command_timeout_ms);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -903,6 +1051,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
                 let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
                 let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
+                let scrollback_size = self.parse_scrollback_size(kdl_node)?;
                 let mut floating_pane = FloatingPaneLayout::from(&pane_template);
                 if let Some(height) = height {
                     floating_pane.height = Some(height);
@@ -919,6 +1068,9 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(pinned) = pinned {
                     floating_pane.pinned = Some(pinned);
                 }
+                if let Some(scrollback_size) = scrollback_size {
+                    floating_pane.scrollback_size = Some(scrollback_size);
+                }
                 Ok(floating_pane)
             },
         }
@@ -1069,6 +1221,7 @@ impl<'a> KdlLayoutParser<'a> {
         self.assert_legal_node_name(&template_name, kdl_node)?;
         self.assert_legal_template_name(&template_name, kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
+        let scrollback_size = self.parse_scrollback_size(kdl_node)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
 
         let is_floating = self.differentiate_pane_and_floating_pane_template(&kdl_node)?;
@@ -1082,6 +1235,7 @@ impl<'a> KdlLayoutParser<'a> {
                     PaneOrFloatingPane::Either(TiledPaneLayout {
                         focus,
                         run,
+                        scrollback_size,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1106,6 +1260,7 @@ impl<'a> KdlLayoutParser<'a> {
                         x,
                         y,
                         pinned,
+                        scrollback_size,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1122,7 +1277,10 @@ impl<'a> KdlLayoutParser<'a> {
             let is_expanded_in_stack =
                 kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded")
                     .unwrap_or(false);
+            let auto_focus =
+                kdl_get_bool_property_or_child_value_with_error!(kdl_node, "auto_focus");
             let split_size = self.parse_split_size(kdl_node)?;
+            let min_size = self.parse_min_size(kdl_node)?;
             let children_split_direction = self.parse_split_direction(kdl_node)?;
             let (external_children_index, pane_parts) = match kdl_children_nodes!(kdl_node) {
                 Some(children) => {
@@ -1144,6 +1302,9 @@ impl<'a> KdlLayoutParser<'a> {
                         children: pane_parts,
                         children_are_stacked,
                         is_expanded_in_stack,
+                        min_size,
+                        scrollback_size,
+                        auto_focus,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1173,6 +1334,8 @@ impl<'a> KdlLayoutParser<'a> {
         let is_focused = kdl_get_bool_property_or_child_value!(kdl_node, "focus").unwrap_or(false);
         let hide_floating_panes =
             kdl_get_bool_property_or_child_value!(kdl_node, "hide_floating_panes").unwrap_or(false);
+        let pin_to_session =
+            kdl_get_bool_property_or_child_value!(kdl_node, "pin_to_session").unwrap_or(false);
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let mut child_floating_panes = vec![];
         let children = match kdl_children_nodes!(kdl_node) {
@@ -1190,6 +1353,7 @@ impl<'a> KdlLayoutParser<'a> {
             children_split_direction,
             children,
             hide_floating_panes,
+            pin_to_session,
             ..Default::default()
         };
         if let Some(cwd_prefix) = &self.cwd_prefix(tab_cwd.as_ref())? {
@@ -1325,6 +1489,8 @@ impl<'a> KdlLayoutParser<'a> {
         pane_run: &Option<Run>,
         pane_template_run: &Option<Run>,
         args: &Option<Vec<String>>,
+        env: &Option<HashMap<String, String>>,
+        startup_command_timeout_ms: &Option<u64>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
@@ -1335,9 +1501,25 @@ impl<'a> KdlLayoutParser<'a> {
                 pane_node
             ));
         }
+        if let (None, None, true) = (pane_run, pane_template_run, env.is_some()) {
+            return Err(kdl_parsing_error!(
+                format!("env can only be specified if a command was specified either in the pane_template or in the pane"),
+                pane_node
+            ));
+        }
+        if let (None, None, true) = (
+            pane_run,
+            pane_template_run,
+            startup_command_timeout_ms.is_some(),
+        ) {
+            return Err(kdl_parsing_error!(
+                format!("startup_command_timeout_ms can only be specified if a command was specified either in the pane_template or in the pane"),
+                pane_node
+            ));
+        }
         if let (None, None, true) = (pane_run, pane_template_run, close_on_exit.is_some()) {
             return Err(kdl_parsing_error!(
-                format!("close_on_exit can only be specified if a command was specified either in the pane_template or in the pane"),
+                format!("close_on_exit and hold_on_close can only be specified if a command was specified either in the pane_template or in the pane"),
                 pane_node
             ));
         }
@@ -1353,6 +1535,8 @@ impl<'a> KdlLayoutParser<'a> {
         &self,
         command: &Option<PathBuf>,
         args: &Option<Vec<String>>,
+        env: &Option<HashMap<String, String>>,
+        startup_command_timeout_ms: &Option<u64>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
@@ -1360,7 +1544,8 @@ impl<'a> KdlLayoutParser<'a> {
         if command.is_none() {
             if close_on_exit.is_some() {
                 return Err(ConfigError::new_layout_kdl_error(
-                    "close_on_exit can only be set if a command was specified".into(),
+                    "close_on_exit and hold_on_close can only be set if a command was specified"
+                        .into(),
                     pane_node.span().offset(),
                     pane_node.span().len(),
                 ));
@@ -1379,6 +1564,20 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_node.span().len(),
                 ));
             }
+            if env.is_some() {
+                return Err(ConfigError::new_layout_kdl_error(
+                    "env can only be set if a command was specified".into(),
+                    pane_node.span().offset(),
+                    pane_node.span().len(),
+                ));
+            }
+            if startup_command_timeout_ms.is_some() {
+                return Err(ConfigError::new_layout_kdl_error(
+                    "startup_command_timeout_ms can only be set if a command was specified".into(),
+                    pane_node.span().offset(),
+                    pane_node.span().len(),
+                ));
+            }
         }
         Ok(())
     }
@@ -1616,7 +1815,15 @@ impl<'a> KdlLayoutParser<'a> {
         ))
     }
     fn populate_one_tab_template(&mut self, kdl_node: &KdlNode) -> Result<(), ConfigError> {
+        // the name can be given either as a property (name="dev") or as the first argument ("dev")
         let template_name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
+            .or_else(|| {
+                kdl_node
+                    .entries()
+                    .iter()
+                    .find(|e| e.name().is_none())
+                    .and_then(|e| e.value().as_string())
+            })
             .map(|s| s.to_string())
             .ok_or(ConfigError::new_layout_kdl_error(
                 "Tab templates must have a name".into(),
@@ -1649,6 +1856,88 @@ impl<'a> KdlLayoutParser<'a> {
         );
         Ok(())
     }
+    fn parse_tab_node_with_named_template(
+        &self,
+        kdl_node: &KdlNode,
+        template_name: &str,
+    ) -> Result<
+        (
+            bool,
+            Option<String>,
+            TiledPaneLayout,
+            Vec<FloatingPaneLayout>,
+        ),
+        ConfigError,
+    > {
+        // (is_focused, Option<tab_name>, PaneLayout, Vec<FloatingPaneLayout>)
+        let (tab_template, tab_template_floating_panes, tab_template_kdl_node) =
+            self.tab_templates.get(template_name).cloned().ok_or(
+                ConfigError::new_layout_kdl_error(
+                    format!("No tab_template named \"{}\" was found", template_name),
+                    kdl_node.span().offset(),
+                    kdl_node.span().len(),
+                ),
+            )?;
+        let template_args = self.parse_template_args(kdl_node)?;
+        let (tab_template, tab_template_floating_panes, tab_template_kdl_node) =
+            if template_args.is_empty() {
+                (
+                    tab_template,
+                    tab_template_floating_panes,
+                    tab_template_kdl_node,
+                )
+            } else {
+                // the template is parsed again with its parameters substituted
+                let mut tab_template_kdl_node = tab_template_kdl_node;
+                substitute_template_args(&mut tab_template_kdl_node, &template_args);
+                let (tab_template, tab_template_floating_panes) =
+                    self.parse_tab_template_node(&tab_template_kdl_node)?;
+                (
+                    tab_template,
+                    tab_template_floating_panes,
+                    tab_template_kdl_node,
+                )
+            };
+        let mut kdl_node = kdl_node.clone();
+        if let Some(children) = kdl_node.children_mut() {
+            if children.get("args").is_some() {
+                children.nodes_mut().retain(|n| kdl_name!(n) != "args");
+                if children.nodes().is_empty() {
+                    // the args were the only children, so the template has nothing to insert
+                    *kdl_node.children_mut() = None;
+                }
+            }
+        }
+        let should_mark_external_children_index = false;
+        self.parse_tab_node_with_template(
+            &kdl_node,
+            tab_template,
+            tab_template_floating_panes,
+            should_mark_external_children_index,
+            &tab_template_kdl_node,
+        )
+    }
+    fn parse_template_args(
+        &self,
+        kdl_node: &KdlNode,
+    ) -> Result<BTreeMap<String, String>, ConfigError> {
+        let mut template_args = BTreeMap::new();
+        if let Some(args) = kdl_get_child!(kdl_node, "args") {
+            for arg in args.children().map(|c| c.nodes()).unwrap_or(&[]) {
+                let value =
+                    kdl_first_entry_as_string!(arg).ok_or(ConfigError::new_layout_kdl_error(
+                        format!(
+                            "Template argument '{}' must have a string value",
+                            kdl_name!(arg)
+                        ),
+                        arg.span().offset(),
+                        arg.span().len(),
+                    ))?;
+                template_args.insert(kdl_name!(arg).to_owned(), value.to_owned());
+            }
+        }
+        Ok(template_args)
+    }
     fn populate_default_tab_template(&mut self, kdl_node: &KdlNode) -> Result<(), ConfigError> {
         let (tab_template, tab_template_floating_panes) = self.parse_tab_template_node(kdl_node)?;
         self.default_tab_template =
@@ -2011,8 +2300,36 @@ impl<'a> KdlLayoutParser<'a> {
         let max_panes = kdl_get_int_property_or_child_value!(layout_node, "max_panes");
         let min_panes = kdl_get_int_property_or_child_value!(layout_node, "min_panes");
         let exact_panes = kdl_get_int_property_or_child_value!(layout_node, "exact_panes");
+        let trigger = kdl_get_string_property_or_child_value!(layout_node, "trigger");
+        let focused_pane_type =
+            kdl_get_string_property_or_child_value!(layout_node, "swap_layout_when_pane_type");
+        let focused_pane_title_regex = kdl_get_string_property_or_child_value!(
+            layout_node,
+            "swap_layout_when_pane_title_matches"
+        );
         let mut constraint_count = 0;
         let mut constraint = None;
+        if let Some(focused_pane_type) = focused_pane_type {
+            constraint_count += 1;
+            constraint = Some(
+                LayoutConstraint::from_focused_pane_type(focused_pane_type)
+                    .map_err(|e| kdl_parsing_error!(e, layout_node))?,
+            );
+        }
+        if let Some(focused_pane_title_regex) = focused_pane_title_regex {
+            constraint_count += 1;
+            constraint = Some(
+                LayoutConstraint::from_focused_pane_title_regex(focused_pane_title_regex)
+                    .map_err(|e| kdl_parsing_error!(e, layout_node))?,
+            );
+        }
+        if let Some(trigger) = trigger {
+            constraint_count += 1;
+            constraint = Some(
+                LayoutConstraint::from_trigger(trigger)
+                    .map_err(|e| kdl_parsing_error!(e, layout_node))?,
+            );
+        }
         if let Some(max_panes) = max_panes {
             constraint_count += 1;
             constraint = Some(LayoutConstraint::MaxPanes(max_panes as usize));
@@ -2215,6 +2532,12 @@ impl<'a> KdlLayoutParser<'a> {
                     child.span().len(),
                 ));
             }
+            if let Some(template_name) =
+                kdl_get_string_property_or_child_value_with_error!(child, "use_template")
+            {
+                child_tabs.push(self.parse_tab_node_with_named_template(child, template_name)?);
+                return Ok(());
+            }
             match &self.default_tab_template {
                 Some((
                     default_tab_template,
@@ -2235,9 +2558,7 @@ impl<'a> KdlLayoutParser<'a> {
                     child_tabs.push(self.parse_tab_node(child)?);
                 },
             }
-        } else if let Some((tab_template, tab_template_floating_panes, tab_template_kdl_node)) =
-            self.tab_templates.get(child_name).cloned()
-        {
+        } else if self.tab_templates.contains_key(child_name) {
             if !child_panes.is_empty() {
                 return Err(ConfigError::new_layout_kdl_error(
                     "Cannot have both tabs and panes in the same node".into(),
@@ -2245,14 +2566,7 @@ impl<'a> KdlLayoutParser<'a> {
                     child.span().len(),
                 ));
             }
-            let should_mark_external_children_index = false;
-            child_tabs.push(self.parse_tab_node_with_template(
-                child,
-                tab_template,
-                tab_template_floating_panes,
-                should_mark_external_children_index,
-                &tab_template_kdl_node,
-            )?);
+            child_tabs.push(self.parse_tab_node_with_named_template(child, child_name)?);
         } else if let Some((pane_template, pane_template_kdl_node)) =
             self.pane_templates.get(child_name).cloned()
         {
@@ -2407,8 +2721,12 @@ impl<'a> KdlLayoutParser<'a> {
         let mut child_floating_panes = vec![];
         let mut swap_tiled_layouts = vec![];
         let mut swap_floating_layouts = vec![];
+        let mut start_hooks = vec![];
+        let mut stop_hooks = vec![];
+        let default_shell = self.parse_path(layout_node, "default_shell")?;
         if let Some(children) = kdl_children_nodes!(layout_node) {
             self.populate_global_cwd(layout_node)?;
+            self.populate_session_hooks(children, &mut start_hooks, &mut stop_hooks)?;
             self.populate_pane_templates(children, &kdl_layout)?;
             self.populate_tab_templates(children)?;
             self.populate_swap_tiled_layouts(children, &mut swap_tiled_layouts)?;
@@ -2467,5 +2785,99 @@ impl<'a> KdlLayoutParser<'a> {
                 swap_floating_layouts,
             )
         }
+        .map(|layout| {
+            self.warn_on_multiple_auto_focused_panes(&layout);
+            Layout {
+                start_hooks,
+                stop_hooks,
+                default_shell,
+                ..layout
+            }
+        })
+    }
+    fn warn_on_multiple_auto_focused_panes(&self, layout: &Layout) {
+        let tab_layouts = layout
+            .tabs
+            .iter()
+            .map(|(tab_name, tiled_panes, _)| (tab_name.as_ref(), tiled_panes))
+            .chain(
+                layout
+                    .template
+                    .iter()
+                    .map(|(tiled_panes, _)| (None, tiled_panes)),
+            );
+        for (tab_name, tiled_panes) in tab_layouts {
+            if tiled_panes.auto_focused_pane_count() > 1 {
+                log::warn!(
+                    "Only one pane per tab can have auto_focus, the first one will be focused ({})",
+                    tab_name
+                        .map(|n| format!("tab: {}", n))
+                        .unwrap_or_else(|| "default tab".to_owned())
+                );
+            }
+        }
+    }
+    fn populate_session_hooks(
+        &self,
+        layout_children: &[KdlNode],
+        start_hooks: &mut Vec<RunCommand>,
+        stop_hooks: &mut Vec<RunCommand>,
+    ) -> Result<(), ConfigError> {
+        for child in layout_children {
+            match kdl_name!(child) {
+                "start_hook" => start_hooks.push(self.parse_session_hook(child)?),
+                "stop_hook" => stop_hooks.push(self.parse_session_hook(child)?),
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+    fn parse_session_hook(&self, hook_node: &KdlNode) -> Result<RunCommand, ConfigError> {
+        // the command is not expanded here, hooks without args are passed as is to the shell
+        let command = kdl_get_string_property_or_child_value_with_error!(hook_node, "command")
+            .ok_or_else(|| {
+                kdl_parsing_error!(
+                    format!(
+                        "{} must have a command (eg. command \"notify-send hi\")",
+                        kdl_name!(hook_node)
+                    ),
+                    hook_node
+                )
+            })?;
+        let args = self.parse_args(hook_node)?;
+        let env = self.parse_env(hook_node)?;
+        let cwd = self.parse_path(hook_node, "cwd")?;
+        let cwd = match (&self.global_cwd, cwd) {
+            (Some(global_cwd), Some(cwd)) => Some(global_cwd.join(cwd)),
+            (Some(global_cwd), None) => Some(global_cwd.clone()),
+            (None, cwd) => cwd,
+        };
+        Ok(RunCommand {
+            command: PathBuf::from(command),
+            args: args.unwrap_or_default(),
+            cwd,
+            env: env.unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+}
+
+// replaces every "{arg_name}" in the string values of this node and its descendants
+fn substitute_template_args(kdl_node: &mut KdlNode, template_args: &BTreeMap<String, String>) {
+    for entry in kdl_node.entries_mut() {
+        if let Some(value) = entry.value().as_string() {
+            let mut substituted = value.to_owned();
+            for (arg_name, arg_value) in template_args {
+                substituted = substituted.replace(&format!("{{{}}}", arg_name), arg_value);
+            }
+            if substituted != value {
+                entry.set_value(substituted);
+            }
+        }
+    }
+    if let Some(children) = kdl_node.children_mut() {
+        for child in children.nodes_mut() {
+            substitute_template_args(child, template_args);
+        }
     }
 }
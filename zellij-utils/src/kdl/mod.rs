@@ -1,15 +1,20 @@
 mod kdl_layout_parser;
+pub mod schema;
 use crate::data::{
     BareKey, Direction, FloatingPaneCoordinates, InputMode, KeyWithModifier, LayoutInfo, Palette,
     PaletteColor, PaneInfo, PaneManifest, PermissionType, Resize, SessionInfo, TabInfo,
 };
 use crate::envs::EnvironmentVariables;
 use crate::home::{find_default_config_dir, get_layout_dir};
-use crate::input::config::{Config, ConfigError, KdlError};
-use crate::input::keybinds::Keybinds;
+use crate::input::config::{Config, ConfigDiagnostic, ConfigError, DiagnosticKind, KdlError};
+use crate::input::hooks::SessionHooks;
+use crate::input::keybinds::{Keybinds, KEYBIND_ACTIONS};
+use crate::input::keyboard_remapping::KeyboardRemapping;
 use crate::input::layout::{
     Layout, PluginUserConfiguration, RunPlugin, RunPluginOrAlias, SplitSize,
 };
+use crate::input::metrics::{MetricsConfig, MetricsFormat};
+use crate::input::notifications::NotificationsConfig;
 use crate::input::options::{Clipboard, OnForceClose, Options};
 use crate::input::permission::{GrantedPermission, PermissionCache};
 use crate::input::plugins::PluginAliases;
@@ -58,6 +63,9 @@ macro_rules! parse_kdl_action_arguments {
                 "ToggleActiveSyncTab" => Ok(Action::ToggleActiveSyncTab),
                 "TogglePaneEmbedOrFloating" => Ok(Action::TogglePaneEmbedOrFloating),
                 "ToggleFloatingPanes" => Ok(Action::ToggleFloatingPanes),
+                "ToggleExclusiveFloatingPanes" => Ok(Action::ToggleExclusiveFloatingPanes),
+                "SuspendSession" => Ok(Action::SuspendSession),
+                "ResumeSession" => Ok(Action::ResumeSession),
                 "CloseFocus" => Ok(Action::CloseFocus),
                 "UndoRenamePane" => Ok(Action::UndoRenamePane),
                 "NoOp" => Ok(Action::NoOp),
@@ -682,7 +690,12 @@ impl Action {
             },
             Action::TogglePaneEmbedOrFloating => Some(KdlNode::new("TogglePaneEmbedOrFloating")),
             Action::ToggleFloatingPanes => Some(KdlNode::new("ToggleFloatingPanes")),
+            Action::ToggleExclusiveFloatingPanes => {
+                Some(KdlNode::new("ToggleExclusiveFloatingPanes"))
+            },
             Action::CloseFocus => Some(KdlNode::new("CloseFocus")),
+            Action::SuspendSession => Some(KdlNode::new("SuspendSession")),
+            Action::ResumeSession => Some(KdlNode::new("ResumeSession")),
             Action::PaneNameInput(bytes) => {
                 let mut node = KdlNode::new("PaneNameInput");
                 for byte in bytes {
@@ -1247,6 +1260,13 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
         let action_name = kdl_name!(kdl_action);
         let action_arguments: Vec<&KdlEntry> = kdl_argument_values!(kdl_action);
         let action_children: Vec<&KdlDocument> = kdl_children!(kdl_action);
+        if !KEYBIND_ACTIONS.iter().any(|(name, _)| *name == action_name) {
+            return Err(ConfigError::new_kdl_error(
+                format!("Unsupported action: {}", action_name),
+                kdl_action.span().offset(),
+                kdl_action.span().len(),
+            ));
+        }
         match action_name {
             "Quit" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "FocusNextPane" => {
@@ -1294,6 +1314,12 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "ToggleFloatingPanes" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ToggleExclusiveFloatingPanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "SuspendSession" | "ResumeSession" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "CloseFocus" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "UndoRenamePane" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
@@ -1337,11 +1363,6 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
-            "ResizeNew" => parse_kdl_action_char_or_string_arguments!(
-                action_name,
-                action_arguments,
-                kdl_action
-            ),
             "MoveFocus" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -2241,6 +2262,80 @@ impl Options {
             "support_kitty_keyboard_protocol"
         )
         .map(|(v, _)| v);
+        let auto_reconnect =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "auto_reconnect").map(|(v, _)| v);
+        let client_idle_timeout_seconds =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "client_idle_timeout_seconds")
+                .map(|(client_idle_timeout_seconds, _entry)| client_idle_timeout_seconds as u64);
+        // a channel of size 0 would make every send wait for the receiving thread
+        let channel_size = |property_name: &str| -> Result<Option<usize>, ConfigError> {
+            match kdl_property_first_arg_as_i64_or_error!(kdl_options, property_name) {
+                Some((channel_size, entry)) if channel_size < 1 => Err(kdl_parsing_error!(
+                    format!(
+                        "{} must be a positive number, found {}",
+                        property_name, channel_size
+                    ),
+                    entry
+                )),
+                Some((channel_size, _entry)) => Ok(Some(channel_size as usize)),
+                None => Ok(None),
+            }
+        };
+        let channel_buffer_size = channel_size("channel_buffer_size")?;
+        let screen_channel_size = channel_size("screen_channel_size")?;
+        let pty_channel_size = channel_size("pty_channel_size")?;
+        let channel_metrics_interval_seconds = kdl_property_first_arg_as_i64_or_error!(
+            kdl_options,
+            "channel_metrics_interval_seconds"
+        )
+        .map(|(channel_metrics_interval_seconds, _entry)| channel_metrics_interval_seconds as u64);
+        let exclusive_floating_panes =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "exclusive_floating_panes")
+                .map(|(v, _)| v);
+        let sixel_support =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "sixel_support").map(|(v, _)| v);
+        let deduplicate_resize =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "deduplicate_resize")
+                .map(|(v, _)| v);
+        let plugin_search_paths = match kdl_options.get("plugin_search_paths") {
+            Some(node) => Some(
+                kdl_string_arguments!(node)
+                    .into_iter()
+                    .map(PathBuf::from)
+                    .collect(),
+            ),
+            None => None,
+        };
+        let session_max_panes =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "session_max_panes")
+                .map(|(v, _)| v as usize);
+        let auto_save_layout_on_exit =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "auto_save_layout_on_exit")
+                .map(|(v, _)| v);
+        let focus_follows_mouse =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "focus_follows_mouse")
+                .map(|(v, _)| v);
+        let default_layout_cwd =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "default_layout_cwd")
+                .map(|(string, _entry)| PathBuf::from(string));
+        let audit_log_path =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "audit_log_path")
+                .map(|(string, _entry)| PathBuf::from(string));
+        // a max size of 0 would rotate the audit log on every entry
+        let audit_log_max_size_mb =
+            match kdl_property_first_arg_as_i64_or_error!(kdl_options, "audit_log_max_size_mb") {
+                Some((size_mb, entry)) if size_mb < 1 => {
+                    return Err(kdl_parsing_error!(
+                        format!(
+                            "audit_log_max_size_mb must be a positive number, found {}",
+                            size_mb
+                        ),
+                        entry
+                    ));
+                },
+                Some((size_mb, _entry)) => Some(size_mb as u64),
+                None => None,
+            };
         Ok(Options {
             simplified_ui,
             theme,
@@ -2269,6 +2364,22 @@ impl Options {
             serialization_interval,
             disable_session_metadata,
             support_kitty_keyboard_protocol,
+            auto_reconnect,
+            client_idle_timeout_seconds,
+            channel_buffer_size,
+            screen_channel_size,
+            pty_channel_size,
+            channel_metrics_interval_seconds,
+            exclusive_floating_panes,
+            sixel_support,
+            deduplicate_resize,
+            plugin_search_paths,
+            session_max_panes,
+            auto_save_layout_on_exit,
+            focus_follows_mouse,
+            default_layout_cwd,
+            audit_log_path,
+            audit_log_max_size_mb,
         })
     }
     pub fn from_string(stringified_keybindings: &String) -> Result<Self, ConfigError> {
@@ -2988,8 +3099,12 @@ impl Options {
     }
     fn serialization_interval_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
         let comment_text = format!(
-            "{}\n{}\n{}",
-            " ", "// How often in seconds sessions are serialized", "// ",
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// How often in seconds sessions are serialized, 0 disables periodic serialization",
+            "// (has no effect if disable_session_metadata is true)",
+            "// Default: 60",
+            "// ",
         );
 
         let create_node = |node_value: u64| -> KdlNode {
@@ -3015,7 +3130,7 @@ impl Options {
         let comment_text = format!("{}\n{}\n{}\n{}\n{}\n{}",
             " ",
             "// Enable or disable writing of session metadata to disk (if disabled, other sessions might not know",
-            "// metadata info on this session)",
+            "// metadata info on this session, and periodic session serialization is also disabled)",
             "// (Requires restart)",
             "// Default: false",
             "// ",
@@ -3068,34 +3183,506 @@ impl Options {
             None
         }
     }
-    pub fn to_kdl(&self, add_comments: bool) -> Vec<KdlNode> {
-        let mut nodes = vec![];
-        if let Some(simplified_ui_node) = self.simplified_ui_to_kdl(add_comments) {
-            nodes.push(simplified_ui_node);
+    fn auto_reconnect_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Try to reconnect to the session (with an exponential backoff) if the connection to the server is lost",
+            "// (Requires restart)",
+            "// Default: false",
+            "// ",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("auto_reconnect");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(auto_reconnect) = self.auto_reconnect {
+            let mut node = create_node(auto_reconnect);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(true);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
         }
-        if let Some(theme_node) = self.theme_to_kdl(add_comments) {
-            nodes.push(theme_node);
+    }
+    fn client_idle_timeout_seconds_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Detach clients that have not sent any input for this many seconds",
+            "// (Requires restart)",
+            "// Default: unset (idle clients are never detached)",
+            "// ",
+        );
+
+        let create_node = |node_value: u64| -> KdlNode {
+            let mut node = KdlNode::new("client_idle_timeout_seconds");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(client_idle_timeout_seconds) = self.client_idle_timeout_seconds {
+            let mut node = create_node(client_idle_timeout_seconds);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(3600);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
         }
-        if let Some(default_mode) = self.default_mode_to_kdl(add_comments) {
-            nodes.push(default_mode);
+    }
+    fn channel_buffer_size_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Bound the size of the channels used to communicate between the server threads",
+            "// (a full channel blocks its senders until there is room, applying back-pressure)",
+            "// (the plugin thread and the screen thread send messages to themselves, so their own channels stay",
+            "// unbounded)",
+            "// (Requires restart)",
+            "// Default: unset (unbounded, except for the pty to screen channel which holds 50 messages)",
+            "// ",
+        );
+
+        let create_node = |node_value: usize| -> KdlNode {
+            let mut node = KdlNode::new("channel_buffer_size");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(channel_buffer_size) = self.channel_buffer_size {
+            let mut node = create_node(channel_buffer_size);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(1000);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
         }
-        if let Some(default_shell) = self.default_shell_to_kdl(add_comments) {
-            nodes.push(default_shell);
+    }
+    fn screen_channel_size_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Size of the channel through which terminal output reaches the screen thread,",
+            "// overrides channel_buffer_size",
+            "// (Requires restart)",
+            "// Default: 50",
+            "// ",
+        );
+
+        let create_node = |node_value: usize| -> KdlNode {
+            let mut node = KdlNode::new("screen_channel_size");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(screen_channel_size) = self.screen_channel_size {
+            let mut node = create_node(screen_channel_size);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(200);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
         }
-        if let Some(default_cwd) = self.default_cwd_to_kdl(add_comments) {
-            nodes.push(default_cwd);
+    }
+    fn pty_channel_size_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Size of the pty thread's channel, overrides channel_buffer_size",
+            "// (Requires restart)",
+            "// Default: unset (unbounded)",
+            "// ",
+        );
+
+        let create_node = |node_value: usize| -> KdlNode {
+            let mut node = KdlNode::new("pty_channel_size");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(pty_channel_size) = self.pty_channel_size {
+            let mut node = create_node(pty_channel_size);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(1000);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
         }
-        if let Some(default_layout) = self.default_layout_to_kdl(add_comments) {
-            nodes.push(default_layout);
+    }
+    fn plugin_search_paths_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Folders to look for plugins in when a plugin is referred to by its bare name (eg. `my-plugin`",
+            "// for `my-plugin.wasm`), before the plugins folder of the data dir",
+            "// Default: no folders",
+            "// ",
+        );
+
+        let create_node = |node_value: &[PathBuf]| -> KdlNode {
+            let mut node = KdlNode::new("plugin_search_paths");
+            for path in node_value {
+                node.push(path.display().to_string());
+            }
+            node
+        };
+        if let Some(plugin_search_paths) = &self.plugin_search_paths {
+            let mut node = create_node(plugin_search_paths);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(&[PathBuf::from("/path/to/my/plugins")]);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
         }
-        if let Some(layout_dir) = self.layout_dir_to_kdl(add_comments) {
-            nodes.push(layout_dir);
+    }
+    fn deduplicate_resize_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Only handle the last of several terminal resizes sent in a row, to avoid recomputing the",
+            "// layout for every intermediate size while the terminal is being resized",
+            "// Default: true",
+            "// ",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("deduplicate_resize");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(deduplicate_resize) = self.deduplicate_resize {
+            let mut node = create_node(deduplicate_resize);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(false);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
         }
-        if let Some(theme_dir) = self.theme_dir_to_kdl(add_comments) {
-            nodes.push(theme_dir);
+    }
+    fn sixel_support_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Whether to advertise sixel graphics support to the programs running in panes, when the",
+            "// terminal Zellij runs in cannot display sixel images they are approximated with half-block",
+            "// characters",
+            "// (Requires restart)",
+            "// Default: true",
+            "// ",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("sixel_support");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(sixel_support) = self.sixel_support {
+            let mut node = create_node(sixel_support);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(false);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
         }
-        if let Some(mouse_mode) = self.mouse_mode_to_kdl(add_comments) {
-            nodes.push(mouse_mode);
+    }
+    fn exclusive_floating_panes_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// When enabled, only one floating pane is shown at a time, focusing a floating pane",
+            "// hides the previously focused one, can be toggled with the ToggleExclusiveFloatingPanes action",
+            "// (Requires restart)",
+            "// Default: false",
+            "// ",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("exclusive_floating_panes");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(exclusive_floating_panes) = self.exclusive_floating_panes {
+            let mut node = create_node(exclusive_floating_panes);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(true);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn channel_metrics_interval_seconds_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Log how full each of the server's channels is every this many seconds",
+            "// (Requires restart)",
+            "// Default: unset (nothing is logged)",
+            "// ",
+        );
+
+        let create_node = |node_value: u64| -> KdlNode {
+            let mut node = KdlNode::new("channel_metrics_interval_seconds");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(channel_metrics_interval_seconds) = self.channel_metrics_interval_seconds {
+            let mut node = create_node(channel_metrics_interval_seconds);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(60);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn session_max_panes_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// The maximum number of terminal panes in a session, panes beyond it are not opened",
+            "// and \"Maximum pane count reached\" is shown in the status bar",
+            "// Default: 200",
+            "// ",
+        );
+
+        let create_node = |node_value: usize| -> KdlNode {
+            let mut node = KdlNode::new("session_max_panes");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(session_max_panes) = self.session_max_panes {
+            let mut node = create_node(session_max_panes);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(50);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn auto_save_layout_on_exit_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Save the session's layout when the session exits, so that it can be resurrected later with",
+            "// `zellij resurrect --latest <session-name>`",
+            "// Default: false",
+            "// ",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("auto_save_layout_on_exit");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(auto_save_layout_on_exit) = self.auto_save_layout_on_exit {
+            let mut node = create_node(auto_save_layout_on_exit);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(true);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn focus_follows_mouse_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}",
+            " ",
+            "// Focus the pane under the mouse when hovering over it, without having to click it",
+            "// Default: false",
+            "// ",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("focus_follows_mouse");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(focus_follows_mouse) = self.focus_follows_mouse {
+            let mut node = create_node(focus_follows_mouse);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(true);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn default_layout_cwd_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// The directory relative `cwd`, `command` and `edit` paths in layouts are resolved against",
+            "// (`~` expands to the home directory)",
+            "// Default: none, relative paths are resolved against the cwd of the new pane",
+            "// ",
+        );
+
+        let create_node = |node_value: &str| -> KdlNode {
+            let mut node = KdlNode::new("default_layout_cwd");
+            node.push(node_value.to_owned());
+            node
+        };
+        if let Some(default_layout_cwd) = &self.default_layout_cwd {
+            let mut node = create_node(&default_layout_cwd.display().to_string());
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node("~/projects");
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn audit_log_path_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Append every action the session's clients send (keys resolved to actions and `zellij action`",
+            "// commands) to this file as newline delimited JSON",
+            "// Text written to panes is not recorded, only its length",
+            "// Default: none, no audit log is written",
+            "// ",
+        );
+
+        let create_node = |node_value: &str| -> KdlNode {
+            let mut node = KdlNode::new("audit_log_path");
+            node.push(node_value.to_owned());
+            node
+        };
+        if let Some(audit_log_path) = &self.audit_log_path {
+            let mut node = create_node(&audit_log_path.display().to_string());
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node("/var/log/zellij/audit.ndjson");
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn audit_log_max_size_mb_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}",
+            " ",
+            "// Rotate the audit log to `<audit_log_path>.1` once it grows past this many megabytes",
+            "// Default: none, the audit log is never rotated",
+            "// ",
+        );
+
+        let create_node = |node_value: u64| -> KdlNode {
+            let mut node = KdlNode::new("audit_log_max_size_mb");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(audit_log_max_size_mb) = self.audit_log_max_size_mb {
+            let mut node = create_node(audit_log_max_size_mb);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(100);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    pub fn to_kdl(&self, add_comments: bool) -> Vec<KdlNode> {
+        let mut nodes = vec![];
+        if let Some(simplified_ui_node) = self.simplified_ui_to_kdl(add_comments) {
+            nodes.push(simplified_ui_node);
+        }
+        if let Some(theme_node) = self.theme_to_kdl(add_comments) {
+            nodes.push(theme_node);
+        }
+        if let Some(default_mode) = self.default_mode_to_kdl(add_comments) {
+            nodes.push(default_mode);
+        }
+        if let Some(default_shell) = self.default_shell_to_kdl(add_comments) {
+            nodes.push(default_shell);
+        }
+        if let Some(default_cwd) = self.default_cwd_to_kdl(add_comments) {
+            nodes.push(default_cwd);
+        }
+        if let Some(default_layout) = self.default_layout_to_kdl(add_comments) {
+            nodes.push(default_layout);
+        }
+        if let Some(layout_dir) = self.layout_dir_to_kdl(add_comments) {
+            nodes.push(layout_dir);
+        }
+        if let Some(theme_dir) = self.theme_dir_to_kdl(add_comments) {
+            nodes.push(theme_dir);
+        }
+        if let Some(mouse_mode) = self.mouse_mode_to_kdl(add_comments) {
+            nodes.push(mouse_mode);
         }
         if let Some(pane_frames) = self.pane_frames_to_kdl(add_comments) {
             nodes.push(pane_frames);
@@ -3155,6 +3742,58 @@ impl Options {
         {
             nodes.push(support_kitty_keyboard_protocol);
         }
+        if let Some(auto_reconnect) = self.auto_reconnect_to_kdl(add_comments) {
+            nodes.push(auto_reconnect);
+        }
+        if let Some(client_idle_timeout_seconds) =
+            self.client_idle_timeout_seconds_to_kdl(add_comments)
+        {
+            nodes.push(client_idle_timeout_seconds);
+        }
+        if let Some(channel_buffer_size) = self.channel_buffer_size_to_kdl(add_comments) {
+            nodes.push(channel_buffer_size);
+        }
+        if let Some(screen_channel_size) = self.screen_channel_size_to_kdl(add_comments) {
+            nodes.push(screen_channel_size);
+        }
+        if let Some(pty_channel_size) = self.pty_channel_size_to_kdl(add_comments) {
+            nodes.push(pty_channel_size);
+        }
+        if let Some(channel_metrics_interval_seconds) =
+            self.channel_metrics_interval_seconds_to_kdl(add_comments)
+        {
+            nodes.push(channel_metrics_interval_seconds);
+        }
+        if let Some(exclusive_floating_panes) = self.exclusive_floating_panes_to_kdl(add_comments) {
+            nodes.push(exclusive_floating_panes);
+        }
+        if let Some(sixel_support) = self.sixel_support_to_kdl(add_comments) {
+            nodes.push(sixel_support);
+        }
+        if let Some(deduplicate_resize) = self.deduplicate_resize_to_kdl(add_comments) {
+            nodes.push(deduplicate_resize);
+        }
+        if let Some(plugin_search_paths) = self.plugin_search_paths_to_kdl(add_comments) {
+            nodes.push(plugin_search_paths);
+        }
+        if let Some(session_max_panes) = self.session_max_panes_to_kdl(add_comments) {
+            nodes.push(session_max_panes);
+        }
+        if let Some(auto_save_layout_on_exit) = self.auto_save_layout_on_exit_to_kdl(add_comments) {
+            nodes.push(auto_save_layout_on_exit);
+        }
+        if let Some(focus_follows_mouse) = self.focus_follows_mouse_to_kdl(add_comments) {
+            nodes.push(focus_follows_mouse);
+        }
+        if let Some(default_layout_cwd) = self.default_layout_cwd_to_kdl(add_comments) {
+            nodes.push(default_layout_cwd);
+        }
+        if let Some(audit_log_path) = self.audit_log_path_to_kdl(add_comments) {
+            nodes.push(audit_log_path);
+        }
+        if let Some(audit_log_max_size_mb) = self.audit_log_max_size_mb_to_kdl(add_comments) {
+            nodes.push(audit_log_max_size_mb);
+        }
         nodes
     }
 }
@@ -3610,6 +4249,59 @@ impl BareKey {
     }
 }
 
+// top level nodes of the configuration that are parsed by Options::from_kdl
+const CONFIG_OPTION_NAMES: &[&str] = &[
+    "simplified_ui",
+    "theme",
+    "default_mode",
+    "default_shell",
+    "default_cwd",
+    "default_layout",
+    "layout_dir",
+    "theme_dir",
+    "mouse_mode",
+    "pane_frames",
+    "mirror_session",
+    "on_force_close",
+    "scroll_buffer_size",
+    "copy_command",
+    "copy_clipboard",
+    "copy_on_select",
+    "scrollback_editor",
+    "session_name",
+    "attach_to_session",
+    "auto_layout",
+    "session_serialization",
+    "serialize_pane_viewport",
+    "scrollback_lines_to_serialize",
+    "styled_underlines",
+    "serialization_interval",
+    "disable_session_metadata",
+    "support_kitty_keyboard_protocol",
+    "auto_reconnect",
+    "client_idle_timeout_seconds",
+    "channel_buffer_size",
+    "screen_channel_size",
+    "pty_channel_size",
+    "channel_metrics_interval_seconds",
+    "exclusive_floating_panes",
+    "sixel_support",
+    "deduplicate_resize",
+    "plugin_search_paths",
+    "session_max_panes",
+    "auto_save_layout_on_exit",
+    "focus_follows_mouse",
+    "default_layout_cwd",
+    "audit_log_path",
+    "audit_log_max_size_mb",
+];
+
+// (deprecated key, what to use instead)
+const DEPRECATED_CONFIG_KEYS: &[(&str, &str)] = &[
+    ("disable_mouse_mode", "mouse_mode false"),
+    ("no_pane_frames", "pane_frames false"),
+];
+
 impl Config {
     pub fn from_kdl(kdl_config: &str, base_config: Option<Config>) -> Result<Config, ConfigError> {
         let mut config = base_config.unwrap_or_else(|| Config::default());
@@ -3644,8 +4336,136 @@ impl Config {
             let config_env = EnvironmentVariables::from_kdl(&env_config)?;
             config.env = config.env.merge(config_env);
         }
+        if let Some(kdl_metrics_config) = kdl_config.get("metrics") {
+            let config_metrics = MetricsConfig::from_kdl(&kdl_metrics_config)?;
+            config.metrics = config.metrics.merge(config_metrics);
+        }
+        if let Some(on_attach) = kdl_config.get("on_attach") {
+            config.hooks.on_attach = SessionHooks::commands_from_kdl(&on_attach)?;
+        }
+        if let Some(on_exit) = kdl_config.get("on_exit") {
+            config.hooks.on_exit = SessionHooks::commands_from_kdl(&on_exit)?;
+        }
+        if let Some(kdl_keyboard_remapping) = kdl_config.get("keyboard_remapping") {
+            let keyboard_remapping = KeyboardRemapping::from_kdl(&kdl_keyboard_remapping)?;
+            config.keyboard_remapping = config.keyboard_remapping.merge(keyboard_remapping);
+        }
+        if let Some(kdl_notifications_config) = kdl_config.get("notifications") {
+            let config_notifications = NotificationsConfig::from_kdl(&kdl_notifications_config)?;
+            config.notifications = config.notifications.merge(config_notifications);
+        }
         Ok(config)
     }
+    /// Checks the whole configuration and reports every problem found in it, rather than
+    /// stopping at the first one like `from_kdl` does
+    pub fn validate(kdl_config: &str) -> Vec<ConfigDiagnostic> {
+        let kdl_config: KdlDocument = match kdl_config.parse() {
+            Ok(kdl_config) => kdl_config,
+            Err(e) => {
+                return vec![ConfigDiagnostic::from_config_error(
+                    ConfigError::KdlDeserializationError(e),
+                    0,
+                    0,
+                )];
+            },
+        };
+        // keybinds are parsed relative to the configured options (eg. the default mode), so we
+        // need these even if some of them are invalid
+        let config_options = Options::from_kdl(&kdl_config).unwrap_or_default();
+        let mut diagnostics = vec![];
+        for node in kdl_config.nodes() {
+            let node_name = kdl_name!(node);
+            let (node_offset, node_len) = (node.span().offset(), node.span().len());
+            let result = match node_name {
+                "keybinds" => {
+                    diagnostics.append(&mut Config::keybinds_diagnostics(node, &config_options));
+                    Ok(())
+                },
+                "themes" => Themes::from_kdl(node, false).map(|_| ()),
+                "plugins" => PluginAliases::from_kdl(node).map(|_| ()),
+                "load_plugins" => load_plugins_from_kdl(node).map(|_| ()),
+                "ui" => UiConfig::from_kdl(node).map(|_| ()),
+                "env" => EnvironmentVariables::from_kdl(node).map(|_| ()),
+                "metrics" => MetricsConfig::from_kdl(node).map(|_| ()),
+                "on_attach" | "on_exit" => SessionHooks::commands_from_kdl(node).map(|_| ()),
+                "keyboard_remapping" => KeyboardRemapping::from_kdl(node).map(|_| ()),
+                "notifications" => NotificationsConfig::from_kdl(node).map(|_| ()),
+                option_name if CONFIG_OPTION_NAMES.contains(&option_name) => {
+                    let mut option_document = KdlDocument::new();
+                    option_document.nodes_mut().push(node.clone());
+                    Options::from_kdl(&option_document).map(|_| ())
+                },
+                deprecated_key => {
+                    let name_span = node.name().span();
+                    let diagnostic = match DEPRECATED_CONFIG_KEYS
+                        .iter()
+                        .find(|(key, _)| *key == deprecated_key)
+                    {
+                        Some((key, replacement)) => ConfigDiagnostic::new(
+                            DiagnosticKind::DeprecatedKey,
+                            name_span.offset(),
+                            name_span.len(),
+                            format!("'{}' is deprecated, use '{}' instead", key, replacement),
+                        ),
+                        None => ConfigDiagnostic::new(
+                            DiagnosticKind::UnknownOption,
+                            name_span.offset(),
+                            name_span.len(),
+                            format!("'{}' is not a known option or section", node_name),
+                        ),
+                    };
+                    diagnostics.push(diagnostic);
+                    Ok(())
+                },
+            };
+            if let Err(e) = result {
+                diagnostics.push(ConfigDiagnostic::from_config_error(
+                    e,
+                    node_offset,
+                    node_len,
+                ));
+            }
+        }
+        diagnostics
+    }
+    /// Like `Config::validate`, but only checks the `keybinds` section(s) of the configuration
+    pub fn validate_keybinds(kdl_config: &str) -> Vec<ConfigDiagnostic> {
+        let kdl_config: KdlDocument = match kdl_config.parse() {
+            Ok(kdl_config) => kdl_config,
+            Err(e) => {
+                return vec![ConfigDiagnostic::from_config_error(
+                    ConfigError::KdlDeserializationError(e),
+                    0,
+                    0,
+                )];
+            },
+        };
+        let config_options = Options::from_kdl(&kdl_config).unwrap_or_default();
+        kdl_config
+            .nodes()
+            .iter()
+            .filter(|n| kdl_name!(n) == "keybinds")
+            .flat_map(|n| Config::keybinds_diagnostics(n, &config_options))
+            .collect()
+    }
+    fn keybinds_diagnostics(
+        kdl_keybinds: &KdlNode,
+        config_options: &Options,
+    ) -> Vec<ConfigDiagnostic> {
+        let mut diagnostics = Keybinds::validate_kdl(kdl_keybinds);
+        if diagnostics.iter().any(|d| !d.kind.is_warning()) {
+            // parsing would only report the first of these, without a suggestion
+            return diagnostics;
+        }
+        if let Err(e) = Keybinds::from_kdl(kdl_keybinds, Keybinds::default(), config_options) {
+            diagnostics.push(ConfigDiagnostic::from_config_error(
+                e,
+                kdl_keybinds.span().offset(),
+                kdl_keybinds.span().len(),
+            ));
+        }
+        diagnostics
+    }
     pub fn to_string(&self, add_comments: bool) -> String {
         let mut document = KdlDocument::new();
 
@@ -3671,6 +4491,20 @@ impl Config {
             document.nodes_mut().push(env);
         }
 
+        if let Some(metrics_config) = self.metrics.to_kdl() {
+            document.nodes_mut().push(metrics_config);
+        }
+
+        document.nodes_mut().append(&mut self.hooks.to_kdl());
+
+        if let Some(keyboard_remapping) = self.keyboard_remapping.to_kdl() {
+            document.nodes_mut().push(keyboard_remapping);
+        }
+
+        if let Some(notifications_config) = self.notifications.to_kdl() {
+            document.nodes_mut().push(notifications_config);
+        }
+
         document
             .nodes_mut()
             .append(&mut self.options.to_kdl(add_comments));
@@ -3700,7 +4534,7 @@ impl PluginAliases {
                 }
             }
         }
-        Ok(PluginAliases { aliases })
+        Ok(PluginAliases::from_data(aliases))
     }
     pub fn to_kdl(&self, add_comments: bool) -> KdlNode {
         let mut plugins = KdlNode::new("plugins");
@@ -3895,6 +4729,228 @@ impl UiConfig {
     }
 }
 
+impl MetricsConfig {
+    pub fn from_kdl(kdl_metrics_config: &KdlNode) -> Result<MetricsConfig, ConfigError> {
+        let mut metrics_config = MetricsConfig::default();
+        if let Some(format) = kdl_get_child!(kdl_metrics_config, "format") {
+            metrics_config.format = kdl_first_entry_as_string!(format)
+                .ok_or_else(|| "metrics format must be a string".to_owned())
+                .and_then(MetricsFormat::from_str)
+                .map_err(|e| {
+                    ConfigError::new_kdl_error(e, format.span().offset(), format.span().len())
+                })?;
+        }
+        metrics_config.endpoint =
+            kdl_get_child_entry_string_value!(kdl_metrics_config, "endpoint").map(|e| e.to_owned());
+        if let Some(flush_interval) = kdl_get_child!(kdl_metrics_config, "flush_interval_seconds") {
+            let flush_interval_seconds = kdl_first_entry_as_i64!(flush_interval)
+                .filter(|seconds| *seconds > 0)
+                .ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        "flush_interval_seconds must be a positive integer".into(),
+                        flush_interval.span().offset(),
+                        flush_interval.span().len(),
+                    )
+                })?;
+            metrics_config.flush_interval_seconds = Some(flush_interval_seconds as u64);
+        }
+        Ok(metrics_config)
+    }
+    pub fn to_kdl(&self) -> Option<KdlNode> {
+        if self == &MetricsConfig::default() {
+            return None;
+        }
+        let mut metrics_config = KdlNode::new("metrics");
+        let mut metrics_config_children = KdlDocument::new();
+        let mut format = KdlNode::new("format");
+        format.push(self.format.to_string());
+        metrics_config_children.nodes_mut().push(format);
+        if let Some(endpoint) = &self.endpoint {
+            let mut endpoint_node = KdlNode::new("endpoint");
+            endpoint_node.push(endpoint.clone());
+            metrics_config_children.nodes_mut().push(endpoint_node);
+        }
+        if let Some(flush_interval_seconds) = self.flush_interval_seconds {
+            let mut flush_interval = KdlNode::new("flush_interval_seconds");
+            flush_interval.push(KdlValue::Base10(flush_interval_seconds as i64));
+            metrics_config_children.nodes_mut().push(flush_interval);
+        }
+        metrics_config.set_children(metrics_config_children);
+        Some(metrics_config)
+    }
+}
+
+impl SessionHooks {
+    pub fn commands_from_kdl(kdl_hook: &KdlNode) -> Result<Vec<String>, ConfigError> {
+        let mut commands = vec![];
+        for child in kdl_children_nodes_or_error!(kdl_hook, "no commands found") {
+            if kdl_name!(child) != "command" {
+                return Err(ConfigError::new_kdl_error(
+                    format!(
+                        "{} can only contain commands (eg. command \"notify-send hi\")",
+                        kdl_name!(kdl_hook)
+                    ),
+                    child.span().offset(),
+                    child.span().len(),
+                ));
+            }
+            let command = kdl_first_entry_as_string!(child).ok_or_else(|| {
+                ConfigError::new_kdl_error(
+                    "command must be a string".into(),
+                    child.span().offset(),
+                    child.span().len(),
+                )
+            })?;
+            commands.push(command.to_owned());
+        }
+        Ok(commands)
+    }
+    pub fn to_kdl(&self) -> Vec<KdlNode> {
+        let mut nodes = vec![];
+        for (name, commands) in [("on_attach", &self.on_attach), ("on_exit", &self.on_exit)] {
+            if commands.is_empty() {
+                continue;
+            }
+            let mut hook = KdlNode::new(name);
+            let mut hook_children = KdlDocument::new();
+            for command in commands {
+                let mut command_node = KdlNode::new("command");
+                command_node.push(command.clone());
+                hook_children.nodes_mut().push(command_node);
+            }
+            hook.set_children(hook_children);
+            nodes.push(hook);
+        }
+        nodes
+    }
+}
+
+impl KeyboardRemapping {
+    pub fn from_kdl(kdl_keyboard_remapping: &KdlNode) -> Result<Self, ConfigError> {
+        let mut remapping = BTreeMap::new();
+        for child in kdl_children_nodes_or_error!(kdl_keyboard_remapping, "no remapped keys found")
+        {
+            let error = || {
+                ConfigError::new_kdl_error(
+                    "keyboard_remapping can only contain remapped sequences (eg. remap \"\\u{1b}[Z\" \"Shift Tab\")".into(),
+                    child.span().offset(),
+                    child.span().len(),
+                )
+            };
+            if kdl_name!(child) != "remap" {
+                return Err(error());
+            }
+            let (sequence, key) = match &kdl_string_arguments!(child)[..] {
+                [sequence, key] => (*sequence, *key),
+                _ => return Err(error()),
+            };
+            let key = KeyWithModifier::from_str(key).map_err(|e| {
+                ConfigError::new_kdl_error(
+                    format!("Failed to parse key {:?}: {}", key, e),
+                    child.span().offset(),
+                    child.span().len(),
+                )
+            })?;
+            remapping.insert(sequence.as_bytes().to_vec(), key);
+        }
+        Ok(KeyboardRemapping(remapping))
+    }
+    pub fn to_kdl(&self) -> Option<KdlNode> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut keyboard_remapping = KdlNode::new("keyboard_remapping");
+        let mut remapped_keys = KdlDocument::new();
+        for (sequence, key) in &self.0 {
+            let mut remap = KdlNode::new("remap");
+            remap.push(String::from_utf8_lossy(sequence).to_string());
+            remap.push(key.to_string());
+            remapped_keys.nodes_mut().push(remap);
+        }
+        keyboard_remapping.set_children(remapped_keys);
+        Some(keyboard_remapping)
+    }
+}
+
+impl NotificationsConfig {
+    pub fn from_kdl(
+        kdl_notifications_config: &KdlNode,
+    ) -> Result<NotificationsConfig, ConfigError> {
+        let mut notifications_config = NotificationsConfig::default();
+        for child in
+            kdl_children_nodes_or_error!(kdl_notifications_config, "no notification settings found")
+        {
+            let bool_value = || {
+                kdl_first_entry_as_bool!(child).ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!("{} must be true or false", kdl_name!(child)),
+                        child.span().offset(),
+                        child.span().len(),
+                    )
+                })
+            };
+            match kdl_name!(child) {
+                "show_startup_tips" => notifications_config.show_startup_tips = Some(bool_value()?),
+                "show_release_notes" => {
+                    notifications_config.show_release_notes = Some(bool_value()?)
+                },
+                "show_setup_wizard_on_first_run" => {
+                    notifications_config.show_setup_wizard_on_first_run = Some(bool_value()?)
+                },
+                "notification_duration_seconds" => {
+                    let notification_duration_seconds = kdl_first_entry_as_i64!(child)
+                        .filter(|seconds| *seconds > 0)
+                        .ok_or_else(|| {
+                            ConfigError::new_kdl_error(
+                                "notification_duration_seconds must be a positive integer".into(),
+                                child.span().offset(),
+                                child.span().len(),
+                            )
+                        })?;
+                    notifications_config.notification_duration_seconds =
+                        Some(notification_duration_seconds as u64);
+                },
+                unknown => {
+                    return Err(ConfigError::new_kdl_error(
+                        format!("Unknown notification setting: {}", unknown),
+                        child.span().offset(),
+                        child.span().len(),
+                    ));
+                },
+            }
+        }
+        Ok(notifications_config)
+    }
+    pub fn to_kdl(&self) -> Option<KdlNode> {
+        if self == &NotificationsConfig::default() {
+            return None;
+        }
+        let mut notifications_config = KdlNode::new("notifications");
+        let mut notifications_config_children = KdlDocument::new();
+        for (name, value) in [
+            ("show_startup_tips", self.show_startup_tips),
+            ("show_release_notes", self.show_release_notes),
+            (
+                "show_setup_wizard_on_first_run",
+                self.show_setup_wizard_on_first_run,
+            ),
+        ] {
+            if let Some(value) = value {
+                let mut node = KdlNode::new(name);
+                node.push(KdlValue::Bool(value));
+                notifications_config_children.nodes_mut().push(node);
+            }
+        }
+        if let Some(notification_duration_seconds) = self.notification_duration_seconds {
+            let mut node = KdlNode::new("notification_duration_seconds");
+            node.push(KdlValue::Base10(notification_duration_seconds as i64));
+            notifications_config_children.nodes_mut().push(node);
+        }
+        notifications_config.set_children(notifications_config_children);
+        Some(notifications_config)
+    }
+}
+
 impl Themes {
     pub fn from_kdl(
         themes_from_kdl: &KdlNode,
@@ -4135,6 +5191,7 @@ impl SessionInfo {
                     .collect()
             })
             .ok_or("Failed to parse available_layouts")?;
+        let tags = SessionInfo::tags_from_kdl_document(&kdl_document);
         let is_current_session = name == current_session_name;
         Ok(SessionInfo {
             name,
@@ -4144,8 +5201,45 @@ impl SessionInfo {
             is_current_session,
             available_layouts,
             plugins: Default::default(), // we do not serialize plugin information
+            tags,
         })
     }
+    pub fn tags_from_string(raw_session_tags: &str) -> Result<Vec<String>, String> {
+        let kdl_document: KdlDocument = raw_session_tags
+            .parse()
+            .map_err(|e| format!("Failed to parse kdl document: {}", e))?;
+        Ok(SessionInfo::tags_from_kdl_document(&kdl_document))
+    }
+    pub fn tags_to_string(tags: &[String]) -> String {
+        let mut kdl_document = KdlDocument::new();
+        if let Some(tags_node) = SessionInfo::tags_to_kdl_node(tags) {
+            kdl_document.nodes_mut().push(tags_node);
+        }
+        kdl_document.fmt();
+        kdl_document.to_string()
+    }
+    fn tags_from_kdl_document(kdl_document: &KdlDocument) -> Vec<String> {
+        kdl_document
+            .get("tags")
+            .map(|n| {
+                n.entries()
+                    .iter()
+                    .filter_map(|e| e.value().as_string())
+                    .map(|s| s.to_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+    fn tags_to_kdl_node(tags: &[String]) -> Option<KdlNode> {
+        if tags.is_empty() {
+            return None;
+        }
+        let mut tags_node = KdlNode::new("tags");
+        for tag in tags {
+            tags_node.push(tag.clone());
+        }
+        Some(tags_node)
+    }
     pub fn to_string(&self) -> String {
         let mut kdl_document = KdlDocument::new();
 
@@ -4189,6 +5283,9 @@ impl SessionInfo {
         kdl_document.nodes_mut().push(panes);
         kdl_document.nodes_mut().push(connected_clients);
         kdl_document.nodes_mut().push(available_layouts);
+        if let Some(tags) = SessionInfo::tags_to_kdl_node(&self.tags) {
+            kdl_document.nodes_mut().push(tags);
+        }
         kdl_document.fmt();
         kdl_document.to_string()
     }
@@ -4255,6 +5352,12 @@ impl TabInfo {
         }
         let active_swap_layout_name = optional_string_node!("active_swap_layout_name");
         let is_swap_layout_dirty = bool_node!("is_swap_layout_dirty");
+        // session info written before tabs could be pinned does not have this node
+        let pinned = kdl_document
+            .get("pinned")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_bool())
+            .unwrap_or(false);
         Ok(TabInfo {
             position,
             name,
@@ -4266,6 +5369,7 @@ impl TabInfo {
             other_focused_clients,
             active_swap_layout_name,
             is_swap_layout_dirty,
+            pinned,
         })
     }
     pub fn encode_to_kdl(&self) -> KdlDocument {
@@ -4317,6 +5421,12 @@ impl TabInfo {
         is_swap_layout_dirty.push(self.is_swap_layout_dirty);
         kdl_doucment.nodes_mut().push(is_swap_layout_dirty);
 
+        if self.pinned {
+            let mut pinned = KdlNode::new("pinned");
+            pinned.push(self.pinned);
+            kdl_doucment.nodes_mut().push(pinned);
+        }
+
         kdl_doucment
     }
 }
@@ -4444,6 +5554,17 @@ impl PaneInfo {
         let terminal_command = optional_string_node!("terminal_command");
         let plugin_url = optional_string_node!("plugin_url");
         let is_selectable = bool_node!("is_selectable");
+        let scrollback_size = optional_int_node!("scrollback_size", usize);
+        let cwd = optional_string_node!("cwd").map(PathBuf::from);
+        let terminal_command_argv = kdl_document
+            .get("terminal_command_argv")
+            .map(|n| {
+                n.entries()
+                    .iter()
+                    .filter_map(|e| e.value().as_string().map(|s| s.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
 
         let pane_info = PaneInfo {
             id,
@@ -4468,6 +5589,9 @@ impl PaneInfo {
             terminal_command,
             plugin_url,
             is_selectable,
+            scrollback_size,
+            cwd,
+            terminal_command_argv,
         };
         Ok((tab_position, pane_info))
     }
@@ -4524,10 +5648,23 @@ impl PaneInfo {
         if let Some(terminal_command) = &self.terminal_command {
             string_node!("terminal_command", terminal_command.to_string());
         }
+        if !self.terminal_command_argv.is_empty() {
+            let mut terminal_command_argv = KdlNode::new("terminal_command_argv");
+            for arg in &self.terminal_command_argv {
+                terminal_command_argv.push(arg.as_str());
+            }
+            kdl_doucment.nodes_mut().push(terminal_command_argv);
+        }
         if let Some(plugin_url) = &self.plugin_url {
             string_node!("plugin_url", plugin_url.to_string());
         }
         bool_node!("is_selectable", self.is_selectable);
+        if let Some(scrollback_size) = self.scrollback_size {
+            int_node!("scrollback_size", scrollback_size);
+        }
+        if let Some(cwd) = &self.cwd {
+            string_node!("cwd", cwd.display().to_string());
+        }
         kdl_doucment
     }
 }
@@ -4615,6 +5752,9 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            scrollback_size: None,
+            cwd: Some(PathBuf::from("/tmp")),
+            terminal_command_argv: vec!["foo".to_owned()],
         },
         PaneInfo {
             id: 1,
@@ -4639,6 +5779,9 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            scrollback_size: None,
+            cwd: None,
+            terminal_command_argv: vec![],
         },
     ];
     let mut panes = HashMap::new();
@@ -4657,6 +5800,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 other_focused_clients: vec![2, 3],
                 active_swap_layout_name: Some("BASE".to_owned()),
                 is_swap_layout_dirty: true,
+                pinned: false,
             },
             TabInfo {
                 position: 1,
@@ -4669,6 +5813,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 other_focused_clients: vec![2, 3],
                 active_swap_layout_name: None,
                 is_swap_layout_dirty: false,
+                pinned: false,
             },
         ],
         panes: PaneManifest { panes },
@@ -4680,6 +5825,7 @@ fn serialize_and_deserialize_session_info_with_data() {
             LayoutInfo::File("layout3".to_owned()),
         ],
         plugins: Default::default(),
+        tags: vec!["work".to_owned(), "rust".to_owned()],
     };
     let serialized = session_info.to_string();
     let deserealized = SessionInfo::from_string(&serialized, "not this session").unwrap();
@@ -4687,6 +5833,18 @@ fn serialize_and_deserialize_session_info_with_data() {
     insta::assert_snapshot!(serialized);
 }
 
+#[test]
+fn serialize_and_deserialize_session_tags() {
+    let tags = vec!["work".to_owned(), "rust".to_owned()];
+    let serialized = SessionInfo::tags_to_string(&tags);
+    let deserialized = SessionInfo::tags_from_string(&serialized).unwrap();
+    assert_eq!(tags, deserialized);
+    assert_eq!(
+        SessionInfo::tags_from_string("").unwrap(),
+        Vec::<String>::new()
+    );
+}
+
 #[test]
 fn keybinds_to_string() {
     let fake_config = r#"
@@ -5442,6 +6600,22 @@ fn config_options_to_string() {
         serialization_interval 1
         disable_session_metadata true
         support_kitty_keyboard_protocol false
+        auto_reconnect true
+        client_idle_timeout_seconds 3600
+        channel_buffer_size 1000
+        screen_channel_size 200
+        pty_channel_size 1000
+        channel_metrics_interval_seconds 60
+        exclusive_floating_panes true
+        sixel_support false
+        deduplicate_resize false
+        plugin_search_paths "/tmp/plugins" "/tmp/more-plugins"
+        session_max_panes 100
+        auto_save_layout_on_exit true
+        focus_follows_mouse true
+        default_layout_cwd "/tmp/layouts"
+        audit_log_path "/tmp/zellij-audit.ndjson"
+        audit_log_max_size_mb 10
     "##;
     let document: KdlDocument = fake_config.parse().unwrap();
     let deserialized = Options::from_kdl(&document).unwrap();
@@ -5487,6 +6661,22 @@ fn config_options_to_string_with_comments() {
         serialization_interval 1
         disable_session_metadata true
         support_kitty_keyboard_protocol false
+        auto_reconnect true
+        client_idle_timeout_seconds 3600
+        channel_buffer_size 1000
+        screen_channel_size 200
+        pty_channel_size 1000
+        channel_metrics_interval_seconds 60
+        exclusive_floating_panes true
+        sixel_support false
+        deduplicate_resize false
+        plugin_search_paths "/tmp/plugins" "/tmp/more-plugins"
+        session_max_panes 100
+        auto_save_layout_on_exit true
+        focus_follows_mouse true
+        default_layout_cwd "/tmp/layouts"
+        audit_log_path "/tmp/zellij-audit.ndjson"
+        audit_log_max_size_mb 10
     "##;
     let document: KdlDocument = fake_config.parse().unwrap();
     let deserialized = Options::from_kdl(&document).unwrap();
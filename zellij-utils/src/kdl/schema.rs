@@ -0,0 +1,239 @@
+//! A JSON Schema description of the configuration file, for editors to complete and validate it
+use crate::data::InputMode;
+use crate::input::options::{Clipboard, OnForceClose, Options};
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+
+pub use zellij_utils_derive::KdlSchema;
+
+/// A struct whose fields are configured through top level KDL nodes named after them, derive it
+/// with `#[derive(KdlSchema)]`
+pub trait KdlSchema {
+    fn kdl_schema_properties() -> Vec<KdlSchemaProperty>;
+}
+
+/// The JSON Schema of the argument of a KDL node
+pub trait KdlSchemaType {
+    fn kdl_schema() -> Value;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KdlSchemaProperty {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub schema: Value,
+    pub default: Option<Value>,
+}
+
+impl KdlSchemaProperty {
+    pub fn new(name: &'static str, description: &'static str, schema: Value) -> Self {
+        KdlSchemaProperty {
+            name,
+            description,
+            schema,
+            default: None,
+        }
+    }
+    pub fn with_default(mut self, default: impl Into<Value>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+    pub fn to_json_schema(&self) -> Value {
+        let mut schema = match &self.schema {
+            Value::Object(schema) => schema.clone(),
+            _ => Map::new(),
+        };
+        if !self.description.is_empty() {
+            schema.insert("description".to_owned(), self.description.into());
+        }
+        if let Some(default) = &self.default {
+            schema.insert("default".to_owned(), default.clone());
+        }
+        Value::Object(schema)
+    }
+}
+
+// top level blocks of the configuration that are not options, (name, description)
+const CONFIG_BLOCKS: &[(&str, &str)] = &[
+    ("keybinds", "Keybindings for each input mode"),
+    (
+        "themes",
+        "Color themes that can be selected with the theme option",
+    ),
+    (
+        "plugins",
+        "Aliases for plugin locations and their configuration",
+    ),
+    (
+        "load_plugins",
+        "Plugins to load in the background when a session starts",
+    ),
+    ("ui", "Configuration of the pane frames"),
+    ("env", "Environment variables to set for new panes"),
+    (
+        "metrics",
+        "Where and how to report the metrics of the session and of plugins",
+    ),
+    (
+        "on_attach",
+        "Shell commands to run when a client attaches to the session",
+    ),
+    ("on_exit", "Shell commands to run when the session exits"),
+    (
+        "keyboard_remapping",
+        "Keys to read from the byte sequences a terminal sends for them in a non-standard way",
+    ),
+    (
+        "notifications",
+        "Whether to show the popups Zellij opens on its own (eg. the setup wizard on first run)",
+    ),
+];
+
+/// The JSON Schema document of the configuration file, printed by `zellij setup --dump-schema`
+pub fn config_json_schema() -> Value {
+    let mut properties = Map::new();
+    for property in Options::kdl_schema_properties() {
+        properties.insert(property.name.to_owned(), property.to_json_schema());
+    }
+    for (name, description) in CONFIG_BLOCKS {
+        properties.insert(
+            name.to_string(),
+            json!({ "type": "object", "description": description }),
+        );
+    }
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Zellij configuration",
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+impl<T: KdlSchemaType> KdlSchemaType for Option<T> {
+    fn kdl_schema() -> Value {
+        T::kdl_schema()
+    }
+}
+
+// the values of a list are the arguments of its node
+impl<T: KdlSchemaType> KdlSchemaType for Vec<T> {
+    fn kdl_schema() -> Value {
+        json!({ "type": "array", "items": T::kdl_schema() })
+    }
+}
+
+impl KdlSchemaType for bool {
+    fn kdl_schema() -> Value {
+        json!({ "type": "boolean" })
+    }
+}
+
+impl KdlSchemaType for String {
+    fn kdl_schema() -> Value {
+        json!({ "type": "string" })
+    }
+}
+
+impl KdlSchemaType for PathBuf {
+    fn kdl_schema() -> Value {
+        json!({ "type": "string" })
+    }
+}
+
+impl KdlSchemaType for usize {
+    fn kdl_schema() -> Value {
+        json!({ "type": "integer", "minimum": 0 })
+    }
+}
+
+impl KdlSchemaType for u64 {
+    fn kdl_schema() -> Value {
+        json!({ "type": "integer", "minimum": 0 })
+    }
+}
+
+impl KdlSchemaType for InputMode {
+    fn kdl_schema() -> Value {
+        json!({
+            "type": "string",
+            "enum": [
+                "normal",
+                "locked",
+                "resize",
+                "pane",
+                "tab",
+                "search",
+                "scroll",
+                "renametab",
+                "renamepane",
+                "session",
+                "move",
+                "prompt",
+                "tmux",
+                "entersearch",
+            ],
+        })
+    }
+}
+
+impl KdlSchemaType for OnForceClose {
+    fn kdl_schema() -> Value {
+        json!({ "type": "string", "enum": ["quit", "detach"] })
+    }
+}
+
+impl KdlSchemaType for Clipboard {
+    fn kdl_schema() -> Value {
+        json!({ "type": "string", "enum": ["system", "primary"] })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kdl::CONFIG_OPTION_NAMES;
+
+    #[test]
+    fn schema_describes_every_config_option() {
+        let property_names: Vec<&str> = Options::kdl_schema_properties()
+            .iter()
+            .map(|property| property.name)
+            .collect();
+        assert_eq!(property_names, CONFIG_OPTION_NAMES);
+    }
+
+    #[test]
+    fn schema_defaults_are_valid_values() {
+        for property in Options::kdl_schema_properties() {
+            let (Some(default), Some(allowed_values)) =
+                (&property.default, property.schema.get("enum"))
+            else {
+                continue;
+            };
+            assert!(
+                allowed_values.as_array().unwrap().contains(default),
+                "default of {} is not one of its allowed values",
+                property.name
+            );
+        }
+    }
+
+    #[test]
+    fn schema_includes_descriptions_and_defaults() {
+        let schema = config_json_schema();
+        let mouse_mode = &schema["properties"]["mouse_mode"];
+        assert_eq!(mouse_mode["type"], "boolean");
+        assert_eq!(mouse_mode["default"], true);
+        assert!(mouse_mode["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("Set the handling of mouse events"));
+        assert_eq!(
+            schema["properties"]["copy_clipboard"]["enum"],
+            json!(["system", "primary"])
+        );
+        assert_eq!(schema["properties"]["keybinds"]["type"], "object");
+        assert_eq!(schema["properties"]["notifications"]["type"], "object");
+    }
+}
@@ -1,3 +1,6 @@
+// lets the code generated by zellij-utils-derive refer to zellij_utils from within this crate
+extern crate self as zellij_utils;
+
 pub mod cli;
 pub mod consts;
 pub mod data;
@@ -8,6 +11,7 @@ pub mod input;
 pub mod kdl;
 pub mod pane_size;
 pub mod plugin_api;
+pub mod plugin_manifest;
 pub mod position;
 pub mod session_serialization;
 pub mod setup;
@@ -688,6 +688,12 @@ impl TryFrom<ProtobufAction> for Action {
                 },
                 _ => Err("Wrong payload for Action::RenameSession"),
             },
+            Some(ProtobufActionName::ToggleExclusiveFloatingPanes) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ToggleExclusiveFloatingPanes should not have a payload"),
+                    None => Ok(Action::ToggleExclusiveFloatingPanes),
+                }
+            },
             Some(ProtobufActionName::TogglePanePinned) => match protobuf_action.optional_payload {
                 Some(_) => Err("TogglePanePinned should not have a payload"),
                 None => Ok(Action::TogglePanePinned),
@@ -1232,6 +1238,10 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::KeybindPipe as i32,
                 optional_payload: None,
             }),
+            Action::ToggleExclusiveFloatingPanes => Ok(ProtobufAction {
+                name: ProtobufActionName::ToggleExclusiveFloatingPanes as i32,
+                optional_payload: None,
+            }),
             Action::TogglePanePinned { .. } => Ok(ProtobufAction {
                 name: ProtobufActionName::TogglePanePinned as i32,
                 optional_payload: None,
@@ -1246,6 +1256,10 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::CliPipe { .. }
             | Action::ListClients
             | Action::StackPanes(..)
+            | Action::RunCommandInPane { .. }
+            | Action::OpenFile { .. }
+            | Action::SuspendSession
+            | Action::ResumeSession
             | Action::SkipConfirm(..) => Err("Unsupported action"),
         }
     }
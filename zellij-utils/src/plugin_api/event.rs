@@ -1,26 +1,29 @@
 pub use super::generated_api::api::{
     action::{Action as ProtobufAction, Position as ProtobufPosition},
     event::{
-        event::Payload as ProtobufEventPayload, ClientInfo as ProtobufClientInfo,
-        CopyDestination as ProtobufCopyDestination, Event as ProtobufEvent,
-        EventNameList as ProtobufEventNameList, EventType as ProtobufEventType,
-        FileMetadata as ProtobufFileMetadata, InputModeKeybinds as ProtobufInputModeKeybinds,
-        KeyBind as ProtobufKeyBind, LayoutInfo as ProtobufLayoutInfo,
-        ModeUpdatePayload as ProtobufModeUpdatePayload, PaneId as ProtobufPaneId,
-        PaneInfo as ProtobufPaneInfo, PaneManifest as ProtobufPaneManifest,
-        PaneType as ProtobufPaneType, PluginInfo as ProtobufPluginInfo,
-        ResurrectableSession as ProtobufResurrectableSession,
+        event::Payload as ProtobufEventPayload, Cell as ProtobufCell,
+        ClientInfo as ProtobufClientInfo, CopyDestination as ProtobufCopyDestination,
+        Event as ProtobufEvent, EventNameList as ProtobufEventNameList,
+        EventType as ProtobufEventType, FileMetadata as ProtobufFileMetadata,
+        InputModeKeybinds as ProtobufInputModeKeybinds, KeyBind as ProtobufKeyBind,
+        KeybindChange as ProtobufKeybindChange, LayoutInfo as ProtobufLayoutInfo,
+        ModeUpdatePayload as ProtobufModeUpdatePayload, OptionChange as ProtobufOptionChange,
+        PaneId as ProtobufPaneId, PaneInfo as ProtobufPaneInfo,
+        PaneManifest as ProtobufPaneManifest, PaneType as ProtobufPaneType,
+        PluginDependency as ProtobufPluginDependency, PluginInfo as ProtobufPluginInfo,
+        ResurrectableSession as ProtobufResurrectableSession, SearchMatch as ProtobufSearchMatch,
         SessionManifest as ProtobufSessionManifest, TabInfo as ProtobufTabInfo, *,
     },
     input_mode::InputMode as ProtobufInputMode,
     key::Key as ProtobufKey,
-    style::Style as ProtobufStyle,
+    style::{Color as ProtobufColor, Style as ProtobufStyle},
 };
 #[allow(hidden_glob_reexports)]
 use crate::data::{
-    ClientInfo, CopyDestination, Event, EventType, FileMetadata, InputMode, KeyWithModifier,
-    LayoutInfo, ModeInfo, Mouse, PaneId, PaneInfo, PaneManifest, PermissionStatus,
-    PluginCapabilities, PluginInfo, SessionInfo, Style, TabInfo,
+    Cell, ClientId, ClientInfo, ConfigDiff, CopyDestination, Event, EventType, FileMetadata,
+    InputMode, KeyWithModifier, LayoutInfo, ModeInfo, Mouse, OptionChange, PaletteColor, PaneId,
+    PaneInfo, PaneManifest, PermissionStatus, PluginCapabilities, PluginDependency, PluginInfo,
+    SearchMatch, SessionInfo, Style, TabInfo,
 };
 
 use crate::errors::prelude::*;
@@ -349,11 +352,167 @@ impl TryFrom<ProtobufEvent> for Event {
                 )),
                 _ => Err("Malformed payload for the FailedToChangeHostFolder Event"),
             },
+            Some(ProtobufEventType::SearchResult) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::SearchResultPayload(search_result_payload)) => {
+                    let pane_id = search_result_payload
+                        .pane_id
+                        .ok_or("Malformed payload for the SearchResult Event")?;
+                    Ok(Event::SearchResult {
+                        pane_id: PaneId::try_from(pane_id)?,
+                        query: search_result_payload.query,
+                        matches: search_result_payload
+                            .matches
+                            .into_iter()
+                            .map(|m| m.into())
+                            .collect(),
+                    })
+                },
+                _ => Err("Malformed payload for the SearchResult Event"),
+            },
+            Some(ProtobufEventType::ChannelMessage) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ChannelMessagePayload(channel_message_payload)) => {
+                    Ok(Event::ChannelMessage {
+                        channel: channel_message_payload.channel,
+                        payload: channel_message_payload.payload,
+                    })
+                },
+                _ => Err("Malformed payload for the ChannelMessage Event"),
+            },
+            Some(ProtobufEventType::SessionRenamed) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::SessionRenamedPayload(session_renamed_payload)) => Ok(
+                    Event::SessionRenamed(session_renamed_payload.new_session_name),
+                ),
+                _ => Err("Malformed payload for the SessionRenamed Event"),
+            },
+            Some(ProtobufEventType::ConfigUpdate) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ConfigUpdatePayload(config_update_payload)) => {
+                    Ok(Event::ConfigUpdate(config_update_payload.try_into()?))
+                },
+                _ => Err("Malformed payload for the ConfigUpdate Event"),
+            },
+            Some(ProtobufEventType::ActionComplete) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::ActionCompletePayload(action_complete_payload)) => {
+                    let action: Action = action_complete_payload
+                        .action
+                        .ok_or("Malformed payload for the ActionComplete Event")?
+                        .try_into()?;
+                    let pane_id = match action_complete_payload.pane_id {
+                        Some(pane_id) => Some(PaneId::try_from(pane_id)?),
+                        None => None,
+                    };
+                    Ok(Event::ActionComplete(
+                        action,
+                        pane_id,
+                        action_complete_payload
+                            .context
+                            .into_iter()
+                            .map(|c_i| (c_i.name, c_i.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Malformed payload for the ActionComplete Event"),
+            },
+            Some(ProtobufEventType::PaneSnapshot) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneSnapshotPayload(pane_snapshot_payload)) => {
+                    let pane_id = pane_snapshot_payload
+                        .pane_id
+                        .ok_or("Malformed payload for the PaneSnapshot Event")?;
+                    let mut rows = vec![];
+                    for row in pane_snapshot_payload.rows {
+                        let cells: Result<Vec<Cell>, &'static str> =
+                            row.cells.into_iter().map(|c| c.try_into()).collect();
+                        rows.push(cells?);
+                    }
+                    Ok(Event::PaneSnapshot {
+                        pane_id: PaneId::try_from(pane_id)?,
+                        rows,
+                    })
+                },
+                _ => Err("Malformed payload for the PaneSnapshot Event"),
+            },
+            Some(ProtobufEventType::PastedText) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PastedTextPayload(text)) => Ok(Event::PastedText(text)),
+                _ => Err("Malformed payload for the PastedText Event"),
+            },
+            Some(ProtobufEventType::PaneDuplicated) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneDuplicatedPayload(pane_duplicated_payload)) => {
+                    match (
+                        pane_duplicated_payload.original_id,
+                        pane_duplicated_payload.new_id,
+                    ) {
+                        (Some(original_id), Some(new_id)) => Ok(Event::PaneDuplicated {
+                            original_id: PaneId::try_from(original_id)?,
+                            new_id: PaneId::try_from(new_id)?,
+                        }),
+                        _ => Err("Malformed payload for the PaneDuplicated Event"),
+                    }
+                },
+                _ => Err("Malformed payload for the PaneDuplicated Event"),
+            },
+            Some(ProtobufEventType::PluginDependencies) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PluginDependenciesPayload(plugin_info)) => {
+                    Ok(Event::PluginDependencies(plugin_info.into()))
+                },
+                _ => Err("Malformed payload for the PluginDependencies Event"),
+            },
+            Some(ProtobufEventType::PaneSelection) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneSelectionPayload(pane_selection_payload)) => {
+                    match pane_selection_payload.pane_id {
+                        Some(pane_id) => Ok(Event::PaneSelection {
+                            pane_id: PaneId::try_from(pane_id)?,
+                            selected_text: pane_selection_payload.selected_text,
+                        }),
+                        None => Err("Malformed payload for the PaneSelection Event"),
+                    }
+                },
+                _ => Err("Malformed payload for the PaneSelection Event"),
+            },
+            Some(ProtobufEventType::PaneCountExceeded) => match protobuf_event.payload {
+                None => Ok(Event::PaneCountExceeded),
+                _ => Err("Malformed payload for the PaneCountExceeded Event"),
+            },
+            Some(ProtobufEventType::CommandPipeResult) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::CommandPipeResultPayload(
+                    command_pipe_result_payload,
+                )) => Ok(Event::CommandPipeResult {
+                    exit_code: command_pipe_result_payload.exit_code,
+                    stdout: command_pipe_result_payload.stdout,
+                    stderr: command_pipe_result_payload.stderr,
+                    context: command_pipe_result_payload
+                        .context
+                        .into_iter()
+                        .map(|c_i| (c_i.name, c_i.value))
+                        .collect(),
+                }),
+                _ => Err("Malformed payload for the CommandPipeResult Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
 }
 
+impl From<ProtobufSearchMatch> for SearchMatch {
+    fn from(protobuf_search_match: ProtobufSearchMatch) -> SearchMatch {
+        SearchMatch {
+            row: protobuf_search_match.row as usize,
+            col_start: protobuf_search_match.col_start as usize,
+            col_end: protobuf_search_match.col_end as usize,
+            context_line: protobuf_search_match.context_line,
+        }
+    }
+}
+
+impl From<SearchMatch> for ProtobufSearchMatch {
+    fn from(search_match: SearchMatch) -> ProtobufSearchMatch {
+        ProtobufSearchMatch {
+            row: search_match.row as u32,
+            col_start: search_match.col_start as u32,
+            col_end: search_match.col_end as u32,
+            context_line: search_match.context_line,
+        }
+    }
+}
+
 impl TryFrom<ProtobufClientInfo> for ClientInfo {
     type Error = &'static str;
     fn try_from(protobuf_client_info: ProtobufClientInfo) -> Result<Self, &'static str> {
@@ -713,10 +872,249 @@ impl TryFrom<Event> for ProtobufEvent {
                     FailedToChangeHostFolderPayload { error_message },
                 )),
             }),
+            Event::SearchResult {
+                pane_id,
+                query,
+                matches,
+            } => Ok(ProtobufEvent {
+                name: ProtobufEventType::SearchResult as i32,
+                payload: Some(event::Payload::SearchResultPayload(SearchResultPayload {
+                    pane_id: Some(pane_id.try_into()?),
+                    query,
+                    matches: matches.into_iter().map(|m| m.into()).collect(),
+                })),
+            }),
+            Event::ChannelMessage { channel, payload } => Ok(ProtobufEvent {
+                name: ProtobufEventType::ChannelMessage as i32,
+                payload: Some(event::Payload::ChannelMessagePayload(
+                    ChannelMessagePayload { channel, payload },
+                )),
+            }),
+            Event::SessionRenamed(new_session_name) => Ok(ProtobufEvent {
+                name: ProtobufEventType::SessionRenamed as i32,
+                payload: Some(event::Payload::SessionRenamedPayload(
+                    SessionRenamedPayload { new_session_name },
+                )),
+            }),
+            Event::ConfigUpdate(config_diff) => Ok(ProtobufEvent {
+                name: ProtobufEventType::ConfigUpdate as i32,
+                payload: Some(event::Payload::ConfigUpdatePayload(config_diff.try_into()?)),
+            }),
+            Event::ActionComplete(action, pane_id, context) => {
+                let action_complete_payload = ActionCompletePayload {
+                    action: Some(action.try_into()?),
+                    pane_id: pane_id.map(|p| p.try_into()).transpose()?,
+                    context: context
+                        .into_iter()
+                        .map(|(name, value)| ContextItem { name, value })
+                        .collect(),
+                };
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::ActionComplete as i32,
+                    payload: Some(event::Payload::ActionCompletePayload(
+                        action_complete_payload,
+                    )),
+                })
+            },
+            Event::PaneSnapshot { pane_id, rows } => {
+                let mut protobuf_rows = vec![];
+                for row in rows {
+                    let cells: Result<Vec<ProtobufCell>, &'static str> =
+                        row.into_iter().map(|c| c.try_into()).collect();
+                    protobuf_rows.push(CellRow { cells: cells? });
+                }
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::PaneSnapshot as i32,
+                    payload: Some(event::Payload::PaneSnapshotPayload(PaneSnapshotPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        rows: protobuf_rows,
+                    })),
+                })
+            },
+            Event::PastedText(text) => Ok(ProtobufEvent {
+                name: ProtobufEventType::PastedText as i32,
+                payload: Some(event::Payload::PastedTextPayload(text)),
+            }),
+            Event::PaneDuplicated {
+                original_id,
+                new_id,
+            } => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneDuplicated as i32,
+                payload: Some(event::Payload::PaneDuplicatedPayload(
+                    PaneDuplicatedPayload {
+                        original_id: Some(original_id.try_into()?),
+                        new_id: Some(new_id.try_into()?),
+                    },
+                )),
+            }),
+            Event::PluginDependencies(plugin_info) => Ok(ProtobufEvent {
+                name: ProtobufEventType::PluginDependencies as i32,
+                // the plugin is not loaded yet, so it does not have an id
+                payload: Some(event::Payload::PluginDependenciesPayload(
+                    ProtobufPluginInfo::from((0, plugin_info)),
+                )),
+            }),
+            Event::PaneSelection {
+                pane_id,
+                selected_text,
+            } => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneSelection as i32,
+                payload: Some(event::Payload::PaneSelectionPayload(PaneSelectionPayload {
+                    pane_id: Some(pane_id.try_into()?),
+                    selected_text,
+                })),
+            }),
+            Event::PaneCountExceeded => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneCountExceeded as i32,
+                payload: None,
+            }),
+            Event::CommandPipeResult {
+                exit_code,
+                stdout,
+                stderr,
+                context,
+            } => Ok(ProtobufEvent {
+                name: ProtobufEventType::CommandPipeResult as i32,
+                payload: Some(event::Payload::CommandPipeResultPayload(
+                    CommandPipeResultPayload {
+                        exit_code,
+                        stdout,
+                        stderr,
+                        context: context
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ProtobufCell> for Cell {
+    type Error = &'static str;
+    fn try_from(protobuf_cell: ProtobufCell) -> Result<Self, &'static str> {
+        let color = |color: Option<ProtobufColor>| -> Result<PaletteColor, &'static str> {
+            color.ok_or("Malformed color in Cell")?.try_into()
+        };
+        Ok(Cell {
+            character: protobuf_cell.character.chars().next().unwrap_or(' '),
+            fg: color(protobuf_cell.fg)?,
+            bg: color(protobuf_cell.bg)?,
+            bold: protobuf_cell.bold,
+        })
+    }
+}
+
+impl TryFrom<Cell> for ProtobufCell {
+    type Error = &'static str;
+    fn try_from(cell: Cell) -> Result<Self, &'static str> {
+        Ok(ProtobufCell {
+            character: cell.character.to_string(),
+            fg: Some(cell.fg.try_into()?),
+            bg: Some(cell.bg.try_into()?),
+            bold: cell.bold,
+        })
+    }
+}
+
+impl TryFrom<ConfigDiff> for ConfigUpdatePayload {
+    type Error = &'static str;
+    fn try_from(config_diff: ConfigDiff) -> Result<Self, &'static str> {
+        let mut added_keybinds = vec![];
+        for (input_mode, key, actions) in config_diff.added_keybinds {
+            added_keybinds.push(keybind_change_to_protobuf(input_mode, key, actions)?);
+        }
+        let mut removed_keybinds = vec![];
+        for (input_mode, key) in config_diff.removed_keybinds {
+            removed_keybinds.push(keybind_change_to_protobuf(input_mode, key, vec![])?);
         }
+        Ok(ConfigUpdatePayload {
+            changed_options: config_diff
+                .changed_options
+                .into_iter()
+                .map(|o| ProtobufOptionChange {
+                    name: o.name,
+                    old_value: o.old_value,
+                    new_value: o.new_value,
+                })
+                .collect(),
+            added_keybinds,
+            removed_keybinds,
+            changed_theme: config_diff.changed_theme,
+        })
     }
 }
 
+impl TryFrom<ConfigUpdatePayload> for ConfigDiff {
+    type Error = &'static str;
+    fn try_from(config_update_payload: ConfigUpdatePayload) -> Result<Self, &'static str> {
+        let mut added_keybinds = vec![];
+        for added_keybind in config_update_payload.added_keybinds {
+            added_keybinds.push(keybind_change_from_protobuf(added_keybind)?);
+        }
+        let mut removed_keybinds = vec![];
+        for removed_keybind in config_update_payload.removed_keybinds {
+            let (input_mode, key, _actions) = keybind_change_from_protobuf(removed_keybind)?;
+            removed_keybinds.push((input_mode, key));
+        }
+        Ok(ConfigDiff {
+            changed_options: config_update_payload
+                .changed_options
+                .into_iter()
+                .map(|o| OptionChange {
+                    name: o.name,
+                    old_value: o.old_value,
+                    new_value: o.new_value,
+                })
+                .collect(),
+            added_keybinds,
+            removed_keybinds,
+            changed_theme: config_update_payload.changed_theme,
+        })
+    }
+}
+
+fn keybind_change_to_protobuf(
+    input_mode: InputMode,
+    key: KeyWithModifier,
+    actions: Vec<Action>,
+) -> Result<ProtobufKeybindChange, &'static str> {
+    let mode: ProtobufInputMode = input_mode.try_into()?;
+    Ok(ProtobufKeybindChange {
+        mode: mode as i32,
+        key_bind: Some(ProtobufKeyBind {
+            key: Some(key.try_into()?),
+            // actions that cannot be serialized are omitted, as in the ModeUpdate Event
+            action: actions
+                .into_iter()
+                .filter_map(|a| a.try_into().ok())
+                .collect(),
+        }),
+    })
+}
+
+fn keybind_change_from_protobuf(
+    keybind_change: ProtobufKeybindChange,
+) -> Result<(InputMode, KeyWithModifier, Vec<Action>), &'static str> {
+    let input_mode: InputMode = ProtobufInputMode::from_i32(keybind_change.mode)
+        .ok_or("Malformed InputMode in the ConfigUpdate Event")?
+        .try_into()?;
+    let key_bind = keybind_change
+        .key_bind
+        .ok_or("Malformed keybind in the ConfigUpdate Event")?;
+    let key: KeyWithModifier = key_bind
+        .key
+        .ok_or("Malformed keybind in the ConfigUpdate Event")?
+        .try_into()?;
+    let actions = key_bind
+        .action
+        .into_iter()
+        .filter_map(|a| a.try_into().ok())
+        .collect();
+    Ok((input_mode, key, actions))
+}
+
 impl TryFrom<SessionInfo> for ProtobufSessionManifest {
     type Error = &'static str;
     fn try_from(session_info: SessionInfo) -> Result<Self, &'static str> {
@@ -751,6 +1149,7 @@ impl TryFrom<SessionInfo> for ProtobufSessionManifest {
                 .into_iter()
                 .map(|p| ProtobufPluginInfo::from(p))
                 .collect(),
+            tags: session_info.tags,
         })
     }
 }
@@ -765,6 +1164,33 @@ impl From<(u32, PluginInfo)> for ProtobufPluginInfo {
                 .into_iter()
                 .map(|(name, value)| ContextItem { name, value })
                 .collect(),
+            dependencies: plugin_info
+                .dependencies
+                .into_iter()
+                .map(|dependency| ProtobufPluginDependency {
+                    url: dependency.url,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<ProtobufPluginInfo> for PluginInfo {
+    fn from(protobuf_plugin_info: ProtobufPluginInfo) -> PluginInfo {
+        PluginInfo {
+            location: protobuf_plugin_info.plugin_url,
+            configuration: protobuf_plugin_info
+                .plugin_config
+                .into_iter()
+                .map(|context_item| (context_item.name, context_item.value))
+                .collect(),
+            dependencies: protobuf_plugin_info
+                .dependencies
+                .into_iter()
+                .map(|dependency| PluginDependency {
+                    url: dependency.url,
+                })
+                .collect(),
         }
     }
 }
@@ -789,17 +1215,7 @@ impl TryFrom<ProtobufSessionManifest> for SessionInfo {
         };
         let mut plugins = BTreeMap::new();
         for plugin_info in protobuf_session_manifest.plugins.into_iter() {
-            let mut configuration = BTreeMap::new();
-            for context_item in plugin_info.plugin_config.into_iter() {
-                configuration.insert(context_item.name, context_item.value);
-            }
-            plugins.insert(
-                plugin_info.plugin_id,
-                PluginInfo {
-                    location: plugin_info.plugin_url,
-                    configuration,
-                },
-            );
+            plugins.insert(plugin_info.plugin_id, plugin_info.into());
         }
         Ok(SessionInfo {
             name: protobuf_session_manifest.name,
@@ -817,6 +1233,7 @@ impl TryFrom<ProtobufSessionManifest> for SessionInfo {
                 .filter_map(|l| LayoutInfo::try_from(l).ok())
                 .collect(),
             plugins,
+            tags: protobuf_session_manifest.tags,
         })
     }
 }
@@ -1013,6 +1430,9 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
             terminal_command: protobuf_pane_info.terminal_command,
             plugin_url: protobuf_pane_info.plugin_url,
             is_selectable: protobuf_pane_info.is_selectable,
+            scrollback_size: protobuf_pane_info.scrollback_size.map(|s| s as usize),
+            cwd: protobuf_pane_info.cwd.map(PathBuf::from),
+            terminal_command_argv: protobuf_pane_info.terminal_command_argv,
         })
     }
 }
@@ -1048,6 +1468,9 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
             terminal_command: pane_info.terminal_command,
             plugin_url: pane_info.plugin_url,
             is_selectable: pane_info.is_selectable,
+            scrollback_size: pane_info.scrollback_size.map(|s| s as u32),
+            cwd: pane_info.cwd.map(|cwd| cwd.to_string_lossy().into_owned()),
+            terminal_command_argv: pane_info.terminal_command_argv,
         })
     }
 }
@@ -1070,6 +1493,7 @@ impl TryFrom<ProtobufTabInfo> for TabInfo {
                 .collect(),
             active_swap_layout_name: protobuf_tab_info.active_swap_layout_name,
             is_swap_layout_dirty: protobuf_tab_info.is_swap_layout_dirty,
+            pinned: protobuf_tab_info.pinned,
         })
     }
 }
@@ -1092,6 +1516,7 @@ impl TryFrom<TabInfo> for ProtobufTabInfo {
                 .collect(),
             active_swap_layout_name: tab_info.active_swap_layout_name,
             is_swap_layout_dirty: tab_info.is_swap_layout_dirty,
+            pinned: tab_info.pinned,
         })
     }
 }
@@ -1147,6 +1572,9 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
             capabilities,
             session_name,
             base_mode,
+            client_id: protobuf_mode_update_payload
+                .client_id
+                .map(|client_id| client_id as ClientId),
         };
         Ok(mode_info)
     }
@@ -1193,6 +1621,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
             arrow_fonts_support,
             session_name,
             base_mode: base_mode.map(|b_m| b_m as i32),
+            client_id: mode_info.client_id.map(|client_id| client_id as u32),
         })
     }
 }
@@ -1260,6 +1689,18 @@ impl TryFrom<ProtobufEventType> for EventType {
             ProtobufEventType::ListClients => EventType::ListClients,
             ProtobufEventType::HostFolderChanged => EventType::HostFolderChanged,
             ProtobufEventType::FailedToChangeHostFolder => EventType::FailedToChangeHostFolder,
+            ProtobufEventType::SearchResult => EventType::SearchResult,
+            ProtobufEventType::ChannelMessage => EventType::ChannelMessage,
+            ProtobufEventType::SessionRenamed => EventType::SessionRenamed,
+            ProtobufEventType::ConfigUpdate => EventType::ConfigUpdate,
+            ProtobufEventType::ActionComplete => EventType::ActionComplete,
+            ProtobufEventType::PaneSnapshot => EventType::PaneSnapshot,
+            ProtobufEventType::PastedText => EventType::PastedText,
+            ProtobufEventType::PaneDuplicated => EventType::PaneDuplicated,
+            ProtobufEventType::PluginDependencies => EventType::PluginDependencies,
+            ProtobufEventType::PaneSelection => EventType::PaneSelection,
+            ProtobufEventType::PaneCountExceeded => EventType::PaneCountExceeded,
+            ProtobufEventType::CommandPipeResult => EventType::CommandPipeResult,
         })
     }
 }
@@ -1297,6 +1738,18 @@ impl TryFrom<EventType> for ProtobufEventType {
             EventType::ListClients => ProtobufEventType::ListClients,
             EventType::HostFolderChanged => ProtobufEventType::HostFolderChanged,
             EventType::FailedToChangeHostFolder => ProtobufEventType::FailedToChangeHostFolder,
+            EventType::SearchResult => ProtobufEventType::SearchResult,
+            EventType::ChannelMessage => ProtobufEventType::ChannelMessage,
+            EventType::SessionRenamed => ProtobufEventType::SessionRenamed,
+            EventType::ConfigUpdate => ProtobufEventType::ConfigUpdate,
+            EventType::ActionComplete => ProtobufEventType::ActionComplete,
+            EventType::PaneSnapshot => ProtobufEventType::PaneSnapshot,
+            EventType::PastedText => ProtobufEventType::PastedText,
+            EventType::PaneDuplicated => ProtobufEventType::PaneDuplicated,
+            EventType::PluginDependencies => ProtobufEventType::PluginDependencies,
+            EventType::PaneSelection => ProtobufEventType::PaneSelection,
+            EventType::PaneCountExceeded => ProtobufEventType::PaneCountExceeded,
+            EventType::CommandPipeResult => ProtobufEventType::CommandPipeResult,
         })
     }
 }
@@ -1433,6 +1886,7 @@ fn serialize_mode_update_event_with_non_default_values() {
         capabilities: PluginCapabilities { arrow_fonts: false },
         session_name: Some("my awesome test session".to_owned()),
         base_mode: Some(InputMode::Locked),
+        client_id: Some(2),
     });
     let protobuf_event: ProtobufEvent = mode_update_event.clone().try_into().unwrap();
     let serialized_protobuf_event = protobuf_event.encode_to_vec();
@@ -1475,6 +1929,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             other_focused_clients: vec![2, 3, 4],
             active_swap_layout_name: Some("my cool swap layout".to_owned()),
             is_swap_layout_dirty: false,
+            pinned: false,
         },
         TabInfo {
             position: 1,
@@ -1487,6 +1942,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             other_focused_clients: vec![1, 5, 111],
             active_swap_layout_name: None,
             is_swap_layout_dirty: true,
+            pinned: true,
         },
         TabInfo::default(),
     ]);
@@ -1724,6 +2180,155 @@ fn serialize_file_system_delete_event() {
     );
 }
 
+#[test]
+fn serialize_search_result_event() {
+    use prost::Message;
+    let search_result_event = Event::SearchResult {
+        pane_id: PaneId::Terminal(2),
+        query: "foo".to_owned(),
+        matches: vec![
+            SearchMatch {
+                row: 0,
+                col_start: 4,
+                col_end: 7,
+                context_line: "let foo = 1;".to_owned(),
+            },
+            SearchMatch {
+                row: 12,
+                col_start: 0,
+                col_end: 3,
+                context_line: "Foo bar".to_owned(),
+            },
+        ],
+    };
+    let protobuf_event: ProtobufEvent = search_result_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        search_result_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_channel_message_event() {
+    use prost::Message;
+    let channel_message_event = Event::ChannelMessage {
+        channel: "build-status".to_owned(),
+        payload: vec![0, 1, 2, 255],
+    };
+    let protobuf_event: ProtobufEvent = channel_message_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        channel_message_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_session_renamed_event() {
+    use prost::Message;
+    let session_renamed_event = Event::SessionRenamed("new-session-name".to_owned());
+    let protobuf_event: ProtobufEvent = session_renamed_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        session_renamed_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_config_update_event() {
+    use crate::data::BareKey;
+    use prost::Message;
+    let config_update_event = Event::ConfigUpdate(ConfigDiff {
+        changed_options: vec![OptionChange {
+            name: "theme".to_owned(),
+            old_value: Some("dracula".to_owned()),
+            new_value: None,
+        }],
+        added_keybinds: vec![(
+            InputMode::Normal,
+            KeyWithModifier::new(BareKey::Char('a')).with_ctrl_modifier(),
+            vec![Action::SwitchToMode(InputMode::Locked)],
+        )],
+        removed_keybinds: vec![(InputMode::Locked, KeyWithModifier::new(BareKey::Esc))],
+        changed_theme: true,
+    });
+    let protobuf_event: ProtobufEvent = config_update_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        config_update_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_action_complete_event() {
+    use prost::Message;
+    let mut context = BTreeMap::new();
+    context.insert("key".to_owned(), "value".to_owned());
+    let action_complete_event = Event::ActionComplete(
+        Action::SwitchToMode(InputMode::Locked),
+        Some(PaneId::Plugin(1)),
+        context,
+    );
+    let protobuf_event: ProtobufEvent = action_complete_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        action_complete_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_snapshot_event() {
+    use prost::Message;
+    let pane_snapshot_event = Event::PaneSnapshot {
+        pane_id: PaneId::Terminal(1),
+        rows: vec![
+            vec![
+                Cell {
+                    character: 'h',
+                    fg: PaletteColor::Rgb((255, 255, 255)),
+                    bg: PaletteColor::EightBit(0),
+                    bold: true,
+                },
+                Cell {
+                    character: 'i',
+                    fg: PaletteColor::EightBit(2),
+                    bg: PaletteColor::Rgb((0, 0, 0)),
+                    bold: false,
+                },
+            ],
+            vec![],
+        ],
+    };
+    let protobuf_event: ProtobufEvent = pane_snapshot_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_snapshot_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
 #[test]
 fn serialize_session_update_event() {
     use prost::Message;
@@ -1754,6 +2359,7 @@ fn serialize_session_update_event_with_non_default_values() {
             other_focused_clients: vec![2, 3, 4],
             active_swap_layout_name: Some("my cool swap layout".to_owned()),
             is_swap_layout_dirty: false,
+            pinned: false,
         },
         TabInfo {
             position: 1,
@@ -1766,6 +2372,7 @@ fn serialize_session_update_event_with_non_default_values() {
             other_focused_clients: vec![1, 5, 111],
             active_swap_layout_name: None,
             is_swap_layout_dirty: true,
+            pinned: true,
         },
         TabInfo::default(),
     ];
@@ -1794,6 +2401,9 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            scrollback_size: None,
+            cwd: None,
+            terminal_command_argv: vec!["foo".to_owned(), "--bar baz".to_owned()],
         },
         PaneInfo {
             id: 1,
@@ -1818,6 +2428,9 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            scrollback_size: None,
+            cwd: None,
+            terminal_command_argv: vec![],
         },
     ];
     panes.insert(0, panes_list);
@@ -1829,6 +2442,7 @@ fn serialize_session_update_event_with_non_default_values() {
         PluginInfo {
             location: "https://example.com/my-plugin.wasm".to_owned(),
             configuration: plugin_configuration,
+            dependencies: vec![],
         },
     );
     let session_info_1 = SessionInfo {
@@ -1843,6 +2457,7 @@ fn serialize_session_update_event_with_non_default_values() {
             LayoutInfo::File("layout3".to_owned()),
         ],
         plugins,
+        tags: vec!["work".to_owned(), "rust".to_owned()],
     };
     let session_info_2 = SessionInfo {
         name: "session 2".to_owned(),
@@ -1858,6 +2473,7 @@ fn serialize_session_update_event_with_non_default_values() {
             LayoutInfo::File("layout3".to_owned()),
         ],
         plugins: Default::default(),
+        tags: vec![],
     };
     let session_infos = vec![session_info_1, session_info_2];
     let resurrectable_sessions = vec![];
@@ -1906,3 +2522,112 @@ impl TryFrom<PaneId> for ProtobufPaneId {
         }
     }
 }
+
+#[test]
+fn serialize_pasted_text_event() {
+    use prost::Message;
+    let pasted_text_event = Event::PastedText("first line\nsecond line".to_owned());
+    let protobuf_event: ProtobufEvent = pasted_text_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pasted_text_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_duplicated_event() {
+    use prost::Message;
+    let pane_duplicated_event = Event::PaneDuplicated {
+        original_id: PaneId::Terminal(1),
+        new_id: PaneId::Terminal(2),
+    };
+    let protobuf_event: ProtobufEvent = pane_duplicated_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_duplicated_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_plugin_dependencies_event() {
+    use prost::Message;
+    let plugin_dependencies_event = Event::PluginDependencies(PluginInfo {
+        location: "https://example.com/my-plugin.wasm".to_owned(),
+        configuration: BTreeMap::new(),
+        dependencies: vec![PluginDependency {
+            url: "https://example.com/my-dependency.wasm".to_owned(),
+        }],
+    });
+    let protobuf_event: ProtobufEvent = plugin_dependencies_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        plugin_dependencies_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_selection_event() {
+    use prost::Message;
+    let pane_selection_event = Event::PaneSelection {
+        pane_id: PaneId::Terminal(1),
+        selected_text: "selected text".to_owned(),
+    };
+    let protobuf_event: ProtobufEvent = pane_selection_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_selection_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_count_exceeded_event() {
+    use prost::Message;
+    let pane_count_exceeded_event = Event::PaneCountExceeded;
+    let protobuf_event: ProtobufEvent = pane_count_exceeded_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_count_exceeded_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_command_pipe_result_event() {
+    use prost::Message;
+    let mut context = BTreeMap::new();
+    context.insert("request".to_owned(), "1".to_owned());
+    let command_pipe_result_event = Event::CommandPipeResult {
+        exit_code: 1,
+        stdout: "matched line\n".to_owned(),
+        stderr: "warning\n".to_owned(),
+        context,
+    };
+    let protobuf_event: ProtobufEvent = command_pipe_result_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        command_pipe_result_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
@@ -30,12 +30,15 @@ impl TryFrom<ProtobufPipeMessage> for PipeMessage {
             .map(|arg| (arg.key, arg.value))
             .collect();
         let is_private = protobuf_pipe_message.is_private;
+        // messages from hosts that do not stream payloads are always complete
+        let is_last = protobuf_pipe_message.is_last.unwrap_or(true);
         Ok(PipeMessage {
             source,
             name,
             payload,
             args,
             is_private,
+            is_last,
         })
     }
 }
@@ -60,6 +63,7 @@ impl TryFrom<PipeMessage> for ProtobufPipeMessage {
             .map(|(key, value)| ProtobufArg { key, value })
             .collect();
         let is_private = pipe_message.is_private;
+        let is_last = Some(pipe_message.is_last);
         Ok(ProtobufPipeMessage {
             source,
             cli_source_id,
@@ -68,6 +72,46 @@ impl TryFrom<PipeMessage> for ProtobufPipeMessage {
             payload,
             args,
             is_private,
+            is_last,
         })
     }
 }
+
+#[test]
+fn serialize_partial_pipe_message() {
+    use prost::Message;
+    let mut pipe_message = PipeMessage::new(
+        PipeSource::Cli("pipe_id".to_owned()),
+        "pipe_name",
+        &Some("first line\n".to_owned()),
+        &None,
+        false,
+    );
+    pipe_message.is_last = false;
+    let protobuf_pipe_message: ProtobufPipeMessage = pipe_message.clone().try_into().unwrap();
+    let serialized_protobuf_pipe_message = protobuf_pipe_message.encode_to_vec();
+    let deserialized_protobuf_pipe_message: ProtobufPipeMessage =
+        Message::decode(serialized_protobuf_pipe_message.as_slice()).unwrap();
+    let deserialized_pipe_message: PipeMessage =
+        deserialized_protobuf_pipe_message.try_into().unwrap();
+    assert_eq!(
+        pipe_message, deserialized_pipe_message,
+        "PipeMessage properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn pipe_messages_without_is_last_are_complete() {
+    let protobuf_pipe_message = ProtobufPipeMessage {
+        source: ProtobufPipeSource::Keybind as i32,
+        cli_source_id: None,
+        plugin_source_id: None,
+        name: "pipe_name".to_owned(),
+        payload: None,
+        args: vec![],
+        is_private: false,
+        is_last: None,
+    };
+    let pipe_message: PipeMessage = protobuf_pipe_message.try_into().unwrap();
+    assert!(pipe_message.is_last);
+}
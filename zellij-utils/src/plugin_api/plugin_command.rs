@@ -3,25 +3,37 @@ pub use super::generated_api::api::{
     event::{EventNameList as ProtobufEventNameList, Header},
     input_mode::InputMode as ProtobufInputMode,
     plugin_command::{
-        plugin_command::Payload, BreakPanesToNewTabPayload, BreakPanesToTabWithIndexPayload,
-        ChangeHostFolderPayload, ClearScreenForPaneIdPayload, CliPipeOutputPayload,
-        CloseTabWithIndexPayload, CommandName, ContextItem, EditScrollbackForPaneWithIdPayload,
-        EnvVariable, ExecCmdPayload, FixedOrPercent as ProtobufFixedOrPercent,
+        plugin_command::Payload, AnnotateScrollbackLinePayload, BlobContent, BlobHash, BlobInfo,
+        BlobList, BreakPanesToNewTabPayload, BreakPanesToTabWithIndexPayload,
+        ChangeHostFolderPayload, ClearScreenForPaneIdPayload, ClearScrollbackAnnotationsPayload,
+        CliPipeChunkPayload, CliPipeOutputPayload, CloseTabWithIndexPayload, CommandName,
+        ContextItem, CreateFloatingPaneAtPayload, CreateSynchronizedGroupPayload,
+        DragPaneToCoordinatesPayload, DuplicatePanePayload, EditScrollbackForPaneWithIdPayload,
+        EmitMetricPayload, EnvVariable, ExecCmdPayload, FixedOrPercent as ProtobufFixedOrPercent,
         FixedOrPercentValue as ProtobufFixedOrPercentValue,
         FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates, HidePaneWithIdPayload,
         HttpVerb as ProtobufHttpVerb, IdAndNewName, KeyToRebind, KeyToUnbind, KillSessionsPayload,
         LoadNewPluginPayload, MessageToPluginPayload, MovePaneWithPaneIdInDirectionPayload,
         MovePaneWithPaneIdPayload, MovePayload, NewPluginArgs as ProtobufNewPluginArgs,
-        NewTabsWithLayoutInfoPayload, OpenCommandPanePayload, OpenFilePayload,
+        NewTabsWithLayoutInfoPayload, OpenCommandPanePayload, OpenFileInEditorPayload,
+        OpenFilePayload, OpenLocation as ProtobufOpenLocation, OpenUrlPayload,
         PageScrollDownInPaneIdPayload, PageScrollUpInPaneIdPayload, PaneId as ProtobufPaneId,
-        PaneType as ProtobufPaneType, PluginCommand as ProtobufPluginCommand, PluginMessagePayload,
-        RebindKeysPayload, ReconfigurePayload, ReloadPluginPayload, RequestPluginPermissionPayload,
-        RerunCommandPanePayload, ResizePaneIdWithDirectionPayload, ResizePayload,
-        RunCommandPayload, ScrollDownInPaneIdPayload, ScrollToBottomInPaneIdPayload,
-        ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload, SetFloatingPanePinnedPayload,
-        SetTimeoutPayload, ShowPaneWithIdPayload, StackPanesPayload, SubscribePayload,
-        SwitchSessionPayload, SwitchTabToPayload, TogglePaneEmbedOrEjectForPaneIdPayload,
-        TogglePaneIdFullscreenPayload, UnsubscribePayload, WebRequestPayload,
+        PaneSnapshotPayload, PaneType as ProtobufPaneType, PinTabPayload, PipeTextToCommandPayload,
+        PluginCommand as ProtobufPluginCommand,
+        PluginCommandResponse as ProtobufPluginCommandResponse,
+        PluginErrorCode as ProtobufPluginErrorCode, PluginMessagePayload,
+        PostMessageToWorkerInstancePayload, PublishToChannelPayload, RebindKeysPayload,
+        ReconfigurePayload, RegisterGlobalHotkeyPayload, ReloadPluginPayload,
+        RequestPluginPermissionPayload, RerunCommandPanePayload, ResizePaneIdWithDirectionPayload,
+        ResizePayload, RunActionPayload, RunCommandInPanePayload, RunCommandPayload,
+        ScrollDownInPaneIdPayload, ScrollToBottomInPaneIdPayload, ScrollToLineInPaneIdPayload,
+        ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload, SearchPanePayload,
+        SetClientModePayload, SetFloatingPanePinnedPayload, SetPaneContentPayload,
+        SetPaneScrollbackSizePayload, SetPaneZIndexPayload, SetSelfMinSizePayload,
+        SetSessionTagsPayload, SetTimeoutPayload, ShowPaneWithIdPayload, StackPanesPayload,
+        StoreBlobPayload, SubscribePayload, SubscribeToChannelPayload, SwitchSessionPayload,
+        SwitchTabToPayload, SynchronizedGroup, TogglePaneEmbedOrEjectForPaneIdPayload,
+        TogglePaneIdFullscreenPayload, UnpinTabPayload, UnsubscribePayload, WebRequestPayload,
         WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
     },
     plugin_permission::PermissionType as ProtobufPermissionType,
@@ -29,8 +41,9 @@ pub use super::generated_api::api::{
 };
 
 use crate::data::{
-    ConnectToSession, FloatingPaneCoordinates, HttpVerb, InputMode, KeyWithModifier,
-    MessageToPlugin, NewPluginArgs, PaneId, PermissionType, PluginCommand,
+    ClientId, ConnectToSession, FloatingPaneCoordinates, HttpVerb, InputMode, KeyWithModifier,
+    MessageToPlugin, NewPluginArgs, OpenLocation, PaneId, PermissionType, PluginCommand,
+    PluginError,
 };
 use crate::input::actions::Action;
 use crate::input::layout::SplitSize;
@@ -38,6 +51,7 @@ use crate::input::layout::SplitSize;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::time::Duration;
 
 impl Into<FloatingPaneCoordinates> for ProtobufFloatingPaneCoordinates {
     fn into(self) -> FloatingPaneCoordinates {
@@ -161,6 +175,24 @@ impl Into<ProtobufHttpVerb> for HttpVerb {
     }
 }
 
+impl Into<OpenLocation> for ProtobufOpenLocation {
+    fn into(self) -> OpenLocation {
+        match self {
+            ProtobufOpenLocation::FloatingPane => OpenLocation::FloatingPane,
+            ProtobufOpenLocation::CurrentPane => OpenLocation::CurrentPane,
+        }
+    }
+}
+
+impl Into<ProtobufOpenLocation> for OpenLocation {
+    fn into(self) -> ProtobufOpenLocation {
+        match self {
+            OpenLocation::FloatingPane => ProtobufOpenLocation::FloatingPane,
+            OpenLocation::CurrentPane => ProtobufOpenLocation::CurrentPane,
+        }
+    }
+}
+
 impl TryFrom<ProtobufPaneId> for PaneId {
     type Error = &'static str;
     fn try_from(protobuf_pane_id: ProtobufPaneId) -> Result<Self, &'static str> {
@@ -422,6 +454,19 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for PostMessageTo"),
             },
+            Some(CommandName::PostMessageToWorkerInstance) => match protobuf_plugin_command.payload
+            {
+                Some(Payload::PostMessageToWorkerInstancePayload(payload)) => {
+                    match payload.message {
+                        Some(message) => Ok(PluginCommand::PostMessageToWorkerInstance(
+                            message.try_into()?,
+                            payload.instance_id,
+                        )),
+                        None => Err("Malformed post message to worker instance payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for PostMessageToWorkerInstance"),
+            },
             Some(CommandName::PostMessageToPlugin) => match protobuf_plugin_command.payload {
                 Some(Payload::PostMessageToPluginPayload(post_message_to_payload)) => {
                     match post_message_to_payload.message {
@@ -609,6 +654,84 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 }
                 Ok(PluginCommand::PageScrollDown)
             },
+            Some(CommandName::GetPluginDependencies) => match protobuf_plugin_command.payload {
+                Some(Payload::GetPluginDependenciesPayload(url)) => {
+                    Ok(PluginCommand::GetPluginDependencies(url))
+                },
+                _ => Err("Mismatched payload for GetPluginDependencies"),
+            },
+            Some(CommandName::CreateSynchronizedGroup) => match protobuf_plugin_command.payload {
+                Some(Payload::CreateSynchronizedGroupPayload(
+                    create_synchronized_group_payload,
+                )) => Ok(PluginCommand::CreateSynchronizedGroup(
+                    create_synchronized_group_payload.name,
+                    create_synchronized_group_payload
+                        .pane_ids
+                        .into_iter()
+                        .filter_map(|p_id| p_id.try_into().ok())
+                        .collect(),
+                )),
+                _ => Err("Mismatched payload for CreateSynchronizedGroup"),
+            },
+            Some(CommandName::DissolveSynchronizedGroup) => match protobuf_plugin_command.payload {
+                Some(Payload::DissolveSynchronizedGroupPayload(group_id)) => {
+                    Ok(PluginCommand::DissolveSynchronizedGroup(group_id))
+                },
+                _ => Err("Mismatched payload for DissolveSynchronizedGroup"),
+            },
+            Some(CommandName::SetFocusFollowsMouse) => match protobuf_plugin_command.payload {
+                Some(Payload::SetFocusFollowsMousePayload(enable)) => {
+                    Ok(PluginCommand::SetFocusFollowsMouse(enable))
+                },
+                _ => Err("Mismatched payload for SetFocusFollowsMouse"),
+            },
+            Some(CommandName::GetCurrentInputMode) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("GetCurrentInputMode should not have a payload")
+                } else {
+                    Ok(PluginCommand::GetCurrentInputMode)
+                }
+            },
+            Some(CommandName::PipeTextToCommand) => match protobuf_plugin_command.payload {
+                Some(Payload::PipeTextToCommandPayload(pipe_text_to_command_payload)) => {
+                    let command_to_run = pipe_text_to_command_payload
+                        .command_to_run
+                        .ok_or("Malformed pipe_text_to_command payload")?
+                        .try_into()?;
+                    let context: BTreeMap<String, String> = pipe_text_to_command_payload
+                        .context
+                        .into_iter()
+                        .map(|e| (e.name, e.value))
+                        .collect();
+                    Ok(PluginCommand::PipeTextToCommand(
+                        pipe_text_to_command_payload.text,
+                        command_to_run,
+                        pipe_text_to_command_payload
+                            .timeout_ms
+                            .map(Duration::from_millis),
+                        context,
+                    ))
+                },
+                _ => Err("Mismatched payload for PipeTextToCommand"),
+            },
+            Some(CommandName::SetPaneScrollbackSize) => match protobuf_plugin_command.payload {
+                Some(Payload::SetPaneScrollbackSizePayload(set_pane_scrollback_size_payload)) => {
+                    match set_pane_scrollback_size_payload.pane_id {
+                        Some(pane_id) => Ok(PluginCommand::SetPaneScrollbackSize(
+                            pane_id.try_into()?,
+                            set_pane_scrollback_size_payload.scrollback_size as usize,
+                        )),
+                        _ => Err("Malformed set_pane_scrollback_size_payload payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for SetPaneScrollbackSize"),
+            },
+            Some(CommandName::TogglePanePinned) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    return Err("TogglePanePinned should not have a payload");
+                }
+                Ok(PluginCommand::TogglePanePinned)
+            },
             Some(CommandName::ToggleFocusFullscreen) => {
                 if protobuf_plugin_command.payload.is_some() {
                     return Err("ToggleFocusFullscreen should not have a payload");
@@ -785,6 +908,7 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                         pane_id,
                         layout: payload.layout.and_then(|l| l.try_into().ok()),
                         cwd: payload.cwd.map(|c| PathBuf::from(c)),
+                        tags: payload.tags,
                     }))
                 },
                 _ => Err("Mismatched payload for SwitchSession"),
@@ -918,6 +1042,14 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for PipeOutput"),
             },
+            Some(CommandName::CliPipeChunk) => match protobuf_plugin_command.payload {
+                Some(Payload::CliPipeChunkPayload(CliPipeChunkPayload {
+                    pipe_name,
+                    chunk,
+                    is_last,
+                })) => Ok(PluginCommand::CliPipeChunk(pipe_name, chunk, is_last)),
+                _ => Err("Mismatched payload for CliPipeChunk"),
+            },
             Some(CommandName::MessageToPlugin) => match protobuf_plugin_command.payload {
                 Some(Payload::MessageToPluginPayload(MessageToPluginPayload {
                     plugin_url,
@@ -1175,6 +1307,18 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for ScrollToTopInPaneId"),
             },
+            Some(CommandName::ScrollToLineInPaneId) => match protobuf_plugin_command.payload {
+                Some(Payload::ScrollToLineInPaneIdPayload(scroll_to_line_in_pane_id_payload)) => {
+                    match scroll_to_line_in_pane_id_payload.pane_id {
+                        Some(pane_id) => Ok(PluginCommand::ScrollToLineInPaneId(
+                            pane_id.try_into()?,
+                            scroll_to_line_in_pane_id_payload.line as usize,
+                        )),
+                        _ => Err("Malformed scroll_to_line_in_pane_id_payload payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for ScrollToLineInPaneId"),
+            },
             Some(CommandName::ScrollToBottomInPaneId) => match protobuf_plugin_command.payload {
                 Some(Payload::ScrollToBottomInPaneIdPayload(
                     scroll_to_bottom_in_pane_id_payload,
@@ -1329,6 +1473,21 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for SetFloatingPanePinned"),
             },
+            Some(CommandName::SetPaneZIndex) => match protobuf_plugin_command.payload {
+                Some(Payload::SetPaneZIndexPayload(set_pane_z_index_payload)) => {
+                    match set_pane_z_index_payload
+                        .pane_id
+                        .and_then(|p| p.try_into().ok())
+                    {
+                        Some(pane_id) => Ok(PluginCommand::SetPaneZIndex(
+                            pane_id,
+                            set_pane_z_index_payload.z_index as usize,
+                        )),
+                        None => Err("PaneId not found!"),
+                    }
+                },
+                _ => Err("Mismatched payload for SetPaneZIndex"),
+            },
             Some(CommandName::StackPanes) => match protobuf_plugin_command.payload {
                 Some(Payload::StackPanesPayload(stack_panes_payload)) => {
                     Ok(PluginCommand::StackPanes(
@@ -1341,6 +1500,299 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for SetFloatingPanePinned"),
             },
+            Some(CommandName::RunCommandInPane) => match protobuf_plugin_command.payload {
+                Some(Payload::RunCommandInPanePayload(run_command_in_pane_payload)) => {
+                    match (
+                        run_command_in_pane_payload.pane_id,
+                        run_command_in_pane_payload.command_to_run,
+                    ) {
+                        (Some(pane_id), Some(command_to_run)) => {
+                            let env_variables: BTreeMap<String, String> =
+                                run_command_in_pane_payload
+                                    .env_variables
+                                    .into_iter()
+                                    .map(|e| (e.name, e.value))
+                                    .collect();
+                            Ok(PluginCommand::RunCommandInPane(
+                                pane_id.try_into()?,
+                                command_to_run.try_into()?,
+                                env_variables,
+                            ))
+                        },
+                        _ => Err("Malformed run_command_in_pane payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for RunCommandInPane"),
+            },
+            Some(CommandName::OpenFileInEditor) => match protobuf_plugin_command.payload {
+                Some(Payload::OpenFileInEditorPayload(open_file_in_editor_payload)) => {
+                    let open_location: OpenLocation = match ProtobufOpenLocation::from_i32(
+                        open_file_in_editor_payload.open_location,
+                    ) {
+                        Some(open_location) => open_location.into(),
+                        None => return Err("Unrecognized open location"),
+                    };
+                    Ok(PluginCommand::OpenFileInEditor(
+                        PathBuf::from(open_file_in_editor_payload.path),
+                        open_location,
+                    ))
+                },
+                _ => Err("Mismatched payload for OpenFileInEditor"),
+            },
+            Some(CommandName::SetSelfMinSize) => match protobuf_plugin_command.payload {
+                Some(Payload::SetSelfMinSizePayload(set_self_min_size_payload)) => {
+                    Ok(PluginCommand::SetSelfMinSize(
+                        set_self_min_size_payload.rows as usize,
+                        set_self_min_size_payload.cols as usize,
+                    ))
+                },
+                _ => Err("Mismatched payload for SetSelfMinSize"),
+            },
+            Some(CommandName::SearchPane) => match protobuf_plugin_command.payload {
+                Some(Payload::SearchPanePayload(search_pane_payload)) => {
+                    match search_pane_payload.pane_id {
+                        Some(pane_id) => Ok(PluginCommand::SearchPane(
+                            pane_id.try_into()?,
+                            search_pane_payload.query,
+                            search_pane_payload.case_sensitive,
+                            search_pane_payload.regex,
+                        )),
+                        None => Err("PaneId not found!"),
+                    }
+                },
+                _ => Err("Mismatched payload for SearchPane"),
+            },
+            Some(CommandName::CreateFloatingPaneAt) => match protobuf_plugin_command.payload {
+                Some(Payload::CreateFloatingPaneAtPayload(create_floating_pane_at_payload)) => {
+                    match create_floating_pane_at_payload.coordinates {
+                        Some(coordinates) => Ok(PluginCommand::CreateFloatingPaneAt {
+                            url: create_floating_pane_at_payload.plugin_url,
+                            config: create_floating_pane_at_payload
+                                .plugin_config
+                                .into_iter()
+                                .map(|e| (e.name, e.value))
+                                .collect(),
+                            coordinates: coordinates.into(),
+                        }),
+                        None => Err("Coordinates not found!"),
+                    }
+                },
+                _ => Err("Mismatched payload for CreateFloatingPaneAt"),
+            },
+            Some(CommandName::SetSessionTags) => match protobuf_plugin_command.payload {
+                Some(Payload::SetSessionTagsPayload(set_session_tags_payload)) => {
+                    Ok(PluginCommand::SetSessionTags(
+                        set_session_tags_payload.session_name,
+                        set_session_tags_payload.tags,
+                    ))
+                },
+                _ => Err("Mismatched payload for SetSessionTags"),
+            },
+            Some(CommandName::DragPaneToCoordinates) => match protobuf_plugin_command.payload {
+                Some(Payload::DragPaneToCoordinatesPayload(drag_pane_to_coordinates_payload)) => {
+                    match drag_pane_to_coordinates_payload.pane_id {
+                        Some(pane_id) => Ok(PluginCommand::DragPaneToCoordinates(
+                            pane_id.try_into()?,
+                            drag_pane_to_coordinates_payload.x as usize,
+                            drag_pane_to_coordinates_payload.y as usize,
+                        )),
+                        None => Err("PaneId not found!"),
+                    }
+                },
+                _ => Err("Mismatched payload for DragPaneToCoordinates"),
+            },
+            Some(CommandName::SubscribeToChannel) => match protobuf_plugin_command.payload {
+                Some(Payload::SubscribeToChannelPayload(subscribe_to_channel_payload)) => Ok(
+                    PluginCommand::SubscribeToChannel(subscribe_to_channel_payload.channel),
+                ),
+                _ => Err("Mismatched payload for SubscribeToChannel"),
+            },
+            Some(CommandName::PublishToChannel) => match protobuf_plugin_command.payload {
+                Some(Payload::PublishToChannelPayload(publish_to_channel_payload)) => {
+                    Ok(PluginCommand::PublishToChannel(
+                        publish_to_channel_payload.channel,
+                        publish_to_channel_payload.payload,
+                    ))
+                },
+                _ => Err("Mismatched payload for PublishToChannel"),
+            },
+            Some(CommandName::GetCurrentTabs) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("GetCurrentTabs should not have a payload")
+                } else {
+                    Ok(PluginCommand::GetCurrentTabs)
+                }
+            },
+            Some(CommandName::GetAllPanes) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("GetAllPanes should not have a payload")
+                } else {
+                    Ok(PluginCommand::GetAllPanes)
+                }
+            },
+            Some(CommandName::GetCurrentStyling) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("GetCurrentStyling should not have a payload")
+                } else {
+                    Ok(PluginCommand::GetCurrentStyling)
+                }
+            },
+            Some(CommandName::ListSessions) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("ListSessions should not have a payload")
+                } else {
+                    Ok(PluginCommand::ListSessions)
+                }
+            },
+            Some(CommandName::ToggleSelf) => match protobuf_plugin_command.payload {
+                Some(Payload::ToggleSelfPayload(should_float_if_hidden)) => {
+                    Ok(PluginCommand::ToggleSelf(should_float_if_hidden))
+                },
+                _ => Err("Mismatched payload for ToggleSelf"),
+            },
+            Some(CommandName::RunAction) => match protobuf_plugin_command.payload {
+                Some(Payload::RunActionPayload(run_action_payload)) => {
+                    let action: Action = run_action_payload
+                        .action
+                        .ok_or("Malformed payload for RunAction")?
+                        .try_into()?;
+                    Ok(PluginCommand::RunAction(
+                        action,
+                        run_action_payload
+                            .context
+                            .into_iter()
+                            .map(|c_i| (c_i.name, c_i.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Mismatched payload for RunAction"),
+            },
+            Some(CommandName::StoreBlob) => match protobuf_plugin_command.payload {
+                Some(Payload::StoreBlobPayload(store_blob_payload)) => Ok(
+                    PluginCommand::StoreBlob(store_blob_payload.key, store_blob_payload.data),
+                ),
+                _ => Err("Mismatched payload for StoreBlob"),
+            },
+            Some(CommandName::LoadBlob) => match protobuf_plugin_command.payload {
+                Some(Payload::LoadBlobPayload(hash)) => Ok(PluginCommand::LoadBlob(hash)),
+                _ => Err("Mismatched payload for LoadBlob"),
+            },
+            Some(CommandName::ListBlobs) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    Err("ListBlobs should not have a payload")
+                } else {
+                    Ok(PluginCommand::ListBlobs)
+                }
+            },
+            Some(CommandName::DeleteBlob) => match protobuf_plugin_command.payload {
+                Some(Payload::DeleteBlobPayload(hash)) => Ok(PluginCommand::DeleteBlob(hash)),
+                _ => Err("Mismatched payload for DeleteBlob"),
+            },
+            Some(CommandName::EmitMetric) => match protobuf_plugin_command.payload {
+                Some(Payload::EmitMetricPayload(emit_metric_payload)) => {
+                    Ok(PluginCommand::EmitMetric(
+                        emit_metric_payload.name,
+                        emit_metric_payload.value,
+                        emit_metric_payload
+                            .labels
+                            .into_iter()
+                            .map(|label| (label.name, label.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Mismatched payload for EmitMetric"),
+            },
+            Some(CommandName::OpenUrl) => match protobuf_plugin_command.payload {
+                Some(Payload::OpenUrlPayload(OpenUrlPayload { url })) => {
+                    Ok(PluginCommand::OpenUrl(url))
+                },
+                _ => Err("Mismatched payload for OpenUrl"),
+            },
+            Some(CommandName::PinTab) => match protobuf_plugin_command.payload {
+                Some(Payload::PinTabPayload(PinTabPayload { tab_index })) => {
+                    Ok(PluginCommand::PinTab(tab_index as usize))
+                },
+                _ => Err("Mismatched payload for PinTab"),
+            },
+            Some(CommandName::UnpinTab) => match protobuf_plugin_command.payload {
+                Some(Payload::UnpinTabPayload(UnpinTabPayload { tab_index })) => {
+                    Ok(PluginCommand::UnpinTab(tab_index as usize))
+                },
+                _ => Err("Mismatched payload for UnpinTab"),
+            },
+            Some(CommandName::RegisterGlobalHotkey) => match protobuf_plugin_command.payload {
+                Some(Payload::RegisterGlobalHotkeyPayload(RegisterGlobalHotkeyPayload {
+                    key: Some(key),
+                    action_id,
+                })) => Ok(PluginCommand::RegisterGlobalHotkey(
+                    key.try_into()?,
+                    action_id,
+                )),
+                _ => Err("Mismatched payload for RegisterGlobalHotkey"),
+            },
+            Some(CommandName::DuplicatePane) => match protobuf_plugin_command.payload {
+                Some(Payload::DuplicatePanePayload(DuplicatePanePayload {
+                    pane_id: Some(pane_id),
+                })) => Ok(PluginCommand::DuplicatePane(pane_id.try_into()?)),
+                _ => Err("Mismatched payload for DuplicatePane"),
+            },
+            Some(CommandName::AnnotateScrollbackLine) => match protobuf_plugin_command.payload {
+                Some(Payload::AnnotateScrollbackLinePayload(AnnotateScrollbackLinePayload {
+                    pane_id: Some(pane_id),
+                    line_index,
+                    annotation,
+                })) => Ok(PluginCommand::AnnotateScrollbackLine(
+                    pane_id.try_into()?,
+                    line_index as usize,
+                    annotation,
+                )),
+                _ => Err("Mismatched payload for AnnotateScrollbackLine"),
+            },
+            Some(CommandName::ClearScrollbackAnnotations) => {
+                match protobuf_plugin_command.payload {
+                    Some(Payload::ClearScrollbackAnnotationsPayload(
+                        ClearScrollbackAnnotationsPayload {
+                            pane_id: Some(pane_id),
+                        },
+                    )) => Ok(PluginCommand::ClearScrollbackAnnotations(
+                        pane_id.try_into()?,
+                    )),
+                    _ => Err("Mismatched payload for ClearScrollbackAnnotations"),
+                }
+            },
+            Some(CommandName::SetClientMode) => match protobuf_plugin_command.payload {
+                Some(Payload::SetClientModePayload(SetClientModePayload { client_id, mode })) => {
+                    match ProtobufInputMode::from_i32(mode) {
+                        Some(protobuf_input_mode) => Ok(PluginCommand::SetClientMode(
+                            client_id as ClientId,
+                            protobuf_input_mode.try_into()?,
+                        )),
+                        None => Err("Malformed set client mode payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for SetClientMode"),
+            },
+            Some(CommandName::PaneSnapshot) => match protobuf_plugin_command.payload {
+                Some(Payload::PaneSnapshotPayload(pane_snapshot_payload)) => {
+                    match pane_snapshot_payload.pane_id {
+                        Some(pane_id) => Ok(PluginCommand::PaneSnapshot(pane_id.try_into()?)),
+                        _ => Err("Malformed pane_snapshot_payload payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for PaneSnapshot"),
+            },
+            Some(CommandName::SetPaneContent) => match protobuf_plugin_command.payload {
+                Some(Payload::SetPaneContentPayload(set_pane_content_payload)) => {
+                    match set_pane_content_payload.pane_id {
+                        Some(pane_id) => Ok(PluginCommand::SetPaneContent(
+                            pane_id.try_into()?,
+                            set_pane_content_payload.content,
+                        )),
+                        None => Err("PaneId not found!"),
+                    }
+                },
+                _ => Err("Mismatched payload for SetPaneContent"),
+            },
             None => Err("Unrecognized plugin command"),
         }
     }
@@ -1391,6 +1843,97 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                         .collect(),
                 })),
             }),
+            PluginCommand::StoreBlob(key, data) => Ok(ProtobufPluginCommand {
+                name: CommandName::StoreBlob as i32,
+                payload: Some(Payload::StoreBlobPayload(StoreBlobPayload { key, data })),
+            }),
+            PluginCommand::LoadBlob(hash) => Ok(ProtobufPluginCommand {
+                name: CommandName::LoadBlob as i32,
+                payload: Some(Payload::LoadBlobPayload(hash)),
+            }),
+            PluginCommand::ListBlobs => Ok(ProtobufPluginCommand {
+                name: CommandName::ListBlobs as i32,
+                payload: None,
+            }),
+            PluginCommand::DeleteBlob(hash) => Ok(ProtobufPluginCommand {
+                name: CommandName::DeleteBlob as i32,
+                payload: Some(Payload::DeleteBlobPayload(hash)),
+            }),
+            PluginCommand::EmitMetric(name, value, labels) => Ok(ProtobufPluginCommand {
+                name: CommandName::EmitMetric as i32,
+                payload: Some(Payload::EmitMetricPayload(EmitMetricPayload {
+                    name,
+                    value,
+                    labels: labels
+                        .into_iter()
+                        .map(|(name, value)| ContextItem { name, value })
+                        .collect(),
+                })),
+            }),
+            PluginCommand::OpenUrl(url) => Ok(ProtobufPluginCommand {
+                name: CommandName::OpenUrl as i32,
+                payload: Some(Payload::OpenUrlPayload(OpenUrlPayload { url })),
+            }),
+            PluginCommand::PinTab(tab_index) => Ok(ProtobufPluginCommand {
+                name: CommandName::PinTab as i32,
+                payload: Some(Payload::PinTabPayload(PinTabPayload {
+                    tab_index: tab_index as u32,
+                })),
+            }),
+            PluginCommand::UnpinTab(tab_index) => Ok(ProtobufPluginCommand {
+                name: CommandName::UnpinTab as i32,
+                payload: Some(Payload::UnpinTabPayload(UnpinTabPayload {
+                    tab_index: tab_index as u32,
+                })),
+            }),
+            PluginCommand::RegisterGlobalHotkey(key, action_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::RegisterGlobalHotkey as i32,
+                payload: Some(Payload::RegisterGlobalHotkeyPayload(
+                    RegisterGlobalHotkeyPayload {
+                        key: Some(key.try_into()?),
+                        action_id,
+                    },
+                )),
+            }),
+            PluginCommand::DuplicatePane(pane_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::DuplicatePane as i32,
+                payload: Some(Payload::DuplicatePanePayload(DuplicatePanePayload {
+                    pane_id: Some(pane_id.try_into()?),
+                })),
+            }),
+            PluginCommand::AnnotateScrollbackLine(pane_id, line_index, annotation) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::AnnotateScrollbackLine as i32,
+                    payload: Some(Payload::AnnotateScrollbackLinePayload(
+                        AnnotateScrollbackLinePayload {
+                            pane_id: Some(pane_id.try_into()?),
+                            line_index: line_index as u32,
+                            annotation,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::SetClientMode(client_id, input_mode) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetClientMode as i32,
+                payload: Some(Payload::SetClientModePayload(SetClientModePayload {
+                    client_id: client_id as u32,
+                    mode: ProtobufInputMode::try_from(input_mode)? as i32,
+                })),
+            }),
+            PluginCommand::ClearScrollbackAnnotations(pane_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::ClearScrollbackAnnotations as i32,
+                payload: Some(Payload::ClearScrollbackAnnotationsPayload(
+                    ClearScrollbackAnnotationsPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                    },
+                )),
+            }),
+            PluginCommand::PaneSnapshot(pane_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::PaneSnapshot as i32,
+                payload: Some(Payload::PaneSnapshotPayload(PaneSnapshotPayload {
+                    pane_id: Some(pane_id.try_into()?),
+                })),
+            }),
             PluginCommand::OpenFileFloating(file_to_open, floating_pane_coordinates, context) => {
                 Ok(ProtobufPluginCommand {
                     name: CommandName::OpenFileFloating as i32,
@@ -1476,6 +2019,17 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     message: Some(plugin_message.try_into()?),
                 })),
             }),
+            PluginCommand::PostMessageToWorkerInstance(plugin_message, instance_id) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::PostMessageToWorkerInstance as i32,
+                    payload: Some(Payload::PostMessageToWorkerInstancePayload(
+                        PostMessageToWorkerInstancePayload {
+                            message: Some(plugin_message.try_into()?),
+                            instance_id,
+                        },
+                    )),
+                })
+            },
             PluginCommand::PostMessageToPlugin(plugin_message) => Ok(ProtobufPluginCommand {
                 name: CommandName::PostMessageToPlugin as i32,
                 payload: Some(Payload::PostMessageToPluginPayload(PluginMessagePayload {
@@ -1602,6 +2156,66 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                 name: CommandName::PageScrollDown as i32,
                 payload: None,
             }),
+            PluginCommand::GetPluginDependencies(url) => Ok(ProtobufPluginCommand {
+                name: CommandName::GetPluginDependencies as i32,
+                payload: Some(Payload::GetPluginDependenciesPayload(url)),
+            }),
+            PluginCommand::CreateSynchronizedGroup(name, pane_ids) => Ok(ProtobufPluginCommand {
+                name: CommandName::CreateSynchronizedGroup as i32,
+                payload: Some(Payload::CreateSynchronizedGroupPayload(
+                    CreateSynchronizedGroupPayload {
+                        name,
+                        pane_ids: pane_ids
+                            .into_iter()
+                            .filter_map(|p_id| p_id.try_into().ok())
+                            .collect(),
+                    },
+                )),
+            }),
+            PluginCommand::DissolveSynchronizedGroup(group_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::DissolveSynchronizedGroup as i32,
+                payload: Some(Payload::DissolveSynchronizedGroupPayload(group_id)),
+            }),
+            PluginCommand::SetFocusFollowsMouse(enable) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetFocusFollowsMouse as i32,
+                payload: Some(Payload::SetFocusFollowsMousePayload(enable)),
+            }),
+            PluginCommand::GetCurrentInputMode => Ok(ProtobufPluginCommand {
+                name: CommandName::GetCurrentInputMode as i32,
+                payload: None,
+            }),
+            PluginCommand::PipeTextToCommand(text, command_to_run, timeout, context) => {
+                let context: Vec<_> = context
+                    .into_iter()
+                    .map(|(name, value)| ContextItem { name, value })
+                    .collect();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::PipeTextToCommand as i32,
+                    payload: Some(Payload::PipeTextToCommandPayload(
+                        PipeTextToCommandPayload {
+                            text,
+                            command_to_run: Some(command_to_run.try_into()?),
+                            timeout_ms: timeout.map(|t| t.as_millis() as u64),
+                            context,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::SetPaneScrollbackSize(pane_id, scrollback_size) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::SetPaneScrollbackSize as i32,
+                    payload: Some(Payload::SetPaneScrollbackSizePayload(
+                        SetPaneScrollbackSizePayload {
+                            pane_id: Some(pane_id.try_into()?),
+                            scrollback_size: scrollback_size as u32,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::TogglePanePinned => Ok(ProtobufPluginCommand {
+                name: CommandName::TogglePanePinned as i32,
+                payload: None,
+            }),
             PluginCommand::ToggleFocusFullscreen => Ok(ProtobufPluginCommand {
                 name: CommandName::ToggleFocusFullscreen as i32,
                 payload: None,
@@ -1734,6 +2348,7 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     pane_id_is_plugin: switch_to_session.pane_id.map(|p| p.1),
                     layout: switch_to_session.layout.and_then(|l| l.try_into().ok()),
                     cwd: switch_to_session.cwd.map(|c| c.display().to_string()),
+                    tags: switch_to_session.tags,
                 })),
             }),
             PluginCommand::OpenTerminalInPlace(cwd) => Ok(ProtobufPluginCommand {
@@ -1839,6 +2454,14 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     output,
                 })),
             }),
+            PluginCommand::CliPipeChunk(pipe_name, chunk, is_last) => Ok(ProtobufPluginCommand {
+                name: CommandName::CliPipeChunk as i32,
+                payload: Some(Payload::CliPipeChunkPayload(CliPipeChunkPayload {
+                    pipe_name,
+                    chunk,
+                    is_last,
+                })),
+            }),
             PluginCommand::MessageToPlugin(message_to_plugin) => {
                 let plugin_config: Vec<_> = message_to_plugin
                     .plugin_config
@@ -2041,6 +2664,15 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     },
                 )),
             }),
+            PluginCommand::ScrollToLineInPaneId(pane_id, line) => Ok(ProtobufPluginCommand {
+                name: CommandName::ScrollToLineInPaneId as i32,
+                payload: Some(Payload::ScrollToLineInPaneIdPayload(
+                    ScrollToLineInPaneIdPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        line: line as u32,
+                    },
+                )),
+            }),
             PluginCommand::ScrollToBottomInPaneId(pane_id) => Ok(ProtobufPluginCommand {
                 name: CommandName::ScrollToBottomInPaneId as i32,
                 payload: Some(Payload::ScrollToBottomInPaneIdPayload(
@@ -2185,6 +2817,13 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     )),
                 })
             },
+            PluginCommand::SetPaneZIndex(pane_id, z_index) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetPaneZIndex as i32,
+                payload: Some(Payload::SetPaneZIndexPayload(SetPaneZIndexPayload {
+                    pane_id: pane_id.try_into().ok(),
+                    z_index: z_index as u32,
+                })),
+            }),
             PluginCommand::StackPanes(pane_ids) => Ok(ProtobufPluginCommand {
                 name: CommandName::StackPanes as i32,
                 payload: Some(Payload::StackPanesPayload(StackPanesPayload {
@@ -2194,6 +2833,230 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                         .collect(),
                 })),
             }),
+            PluginCommand::RunCommandInPane(pane_id, command_to_run, env_variables) => {
+                let env_variables: Vec<_> = env_variables
+                    .into_iter()
+                    .map(|(name, value)| EnvVariable { name, value })
+                    .collect();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::RunCommandInPane as i32,
+                    payload: Some(Payload::RunCommandInPanePayload(RunCommandInPanePayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        command_to_run: Some(command_to_run.try_into()?),
+                        env_variables,
+                    })),
+                })
+            },
+            PluginCommand::OpenFileInEditor(path, open_location) => {
+                let open_location: ProtobufOpenLocation = open_location.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::OpenFileInEditor as i32,
+                    payload: Some(Payload::OpenFileInEditorPayload(OpenFileInEditorPayload {
+                        path: path.display().to_string(),
+                        open_location: open_location as i32,
+                    })),
+                })
+            },
+            PluginCommand::SetSelfMinSize(rows, cols) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetSelfMinSize as i32,
+                payload: Some(Payload::SetSelfMinSizePayload(SetSelfMinSizePayload {
+                    rows: rows as u32,
+                    cols: cols as u32,
+                })),
+            }),
+            PluginCommand::SearchPane(pane_id, query, case_sensitive, regex) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::SearchPane as i32,
+                    payload: Some(Payload::SearchPanePayload(SearchPanePayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        query,
+                        case_sensitive,
+                        regex,
+                    })),
+                })
+            },
+            PluginCommand::CreateFloatingPaneAt {
+                url,
+                config,
+                coordinates,
+            } => Ok(ProtobufPluginCommand {
+                name: CommandName::CreateFloatingPaneAt as i32,
+                payload: Some(Payload::CreateFloatingPaneAtPayload(
+                    CreateFloatingPaneAtPayload {
+                        plugin_url: url,
+                        plugin_config: config
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                        coordinates: Some(coordinates.into()),
+                    },
+                )),
+            }),
+            PluginCommand::SetSessionTags(session_name, tags) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetSessionTags as i32,
+                payload: Some(Payload::SetSessionTagsPayload(SetSessionTagsPayload {
+                    session_name,
+                    tags,
+                })),
+            }),
+            PluginCommand::DragPaneToCoordinates(pane_id, x, y) => Ok(ProtobufPluginCommand {
+                name: CommandName::DragPaneToCoordinates as i32,
+                payload: Some(Payload::DragPaneToCoordinatesPayload(
+                    DragPaneToCoordinatesPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        x: x as u32,
+                        y: y as u32,
+                    },
+                )),
+            }),
+            PluginCommand::SubscribeToChannel(channel) => Ok(ProtobufPluginCommand {
+                name: CommandName::SubscribeToChannel as i32,
+                payload: Some(Payload::SubscribeToChannelPayload(
+                    SubscribeToChannelPayload { channel },
+                )),
+            }),
+            PluginCommand::PublishToChannel(channel, payload) => Ok(ProtobufPluginCommand {
+                name: CommandName::PublishToChannel as i32,
+                payload: Some(Payload::PublishToChannelPayload(PublishToChannelPayload {
+                    channel,
+                    payload,
+                })),
+            }),
+            PluginCommand::GetCurrentTabs => Ok(ProtobufPluginCommand {
+                name: CommandName::GetCurrentTabs as i32,
+                payload: None,
+            }),
+            PluginCommand::GetAllPanes => Ok(ProtobufPluginCommand {
+                name: CommandName::GetAllPanes as i32,
+                payload: None,
+            }),
+            PluginCommand::GetCurrentStyling => Ok(ProtobufPluginCommand {
+                name: CommandName::GetCurrentStyling as i32,
+                payload: None,
+            }),
+            PluginCommand::ListSessions => Ok(ProtobufPluginCommand {
+                name: CommandName::ListSessions as i32,
+                payload: None,
+            }),
+            PluginCommand::ToggleSelf(should_float_if_hidden) => Ok(ProtobufPluginCommand {
+                name: CommandName::ToggleSelf as i32,
+                payload: Some(Payload::ToggleSelfPayload(should_float_if_hidden)),
+            }),
+            PluginCommand::RunAction(action, context) => Ok(ProtobufPluginCommand {
+                name: CommandName::RunAction as i32,
+                payload: Some(Payload::RunActionPayload(RunActionPayload {
+                    action: Some(action.try_into()?),
+                    context: context
+                        .into_iter()
+                        .map(|(name, value)| ContextItem { name, value })
+                        .collect(),
+                })),
+            }),
+            PluginCommand::SetPaneContent(pane_id, content) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetPaneContent as i32,
+                payload: Some(Payload::SetPaneContentPayload(SetPaneContentPayload {
+                    pane_id: Some(pane_id.try_into()?),
+                    content,
+                })),
+            }),
+        }
+    }
+}
+
+impl From<Result<Vec<u8>, PluginError>> for ProtobufPluginCommandResponse {
+    fn from(result: Result<Vec<u8>, PluginError>) -> Self {
+        match result {
+            Ok(payload) => ProtobufPluginCommandResponse {
+                error_code: ProtobufPluginErrorCode::NoError as i32,
+                error_message: None,
+                payload,
+            },
+            Err(plugin_error) => {
+                let (error_code, error_message) = match plugin_error {
+                    PluginError::PermissionDenied => {
+                        (ProtobufPluginErrorCode::PermissionDenied, None)
+                    },
+                    PluginError::PaneNotFound => (ProtobufPluginErrorCode::PaneNotFound, None),
+                    PluginError::InvalidArgument(message) => {
+                        (ProtobufPluginErrorCode::InvalidArgument, Some(message))
+                    },
+                    PluginError::HostError(message) => {
+                        (ProtobufPluginErrorCode::HostError, Some(message))
+                    },
+                };
+                ProtobufPluginCommandResponse {
+                    error_code: error_code as i32,
+                    error_message,
+                    payload: vec![],
+                }
+            },
         }
     }
 }
+
+impl ProtobufPluginCommandResponse {
+    pub fn into_result(self) -> Result<Vec<u8>, PluginError> {
+        let error_message = self.error_message.unwrap_or_default();
+        match ProtobufPluginErrorCode::from_i32(self.error_code) {
+            Some(ProtobufPluginErrorCode::NoError) => Ok(self.payload),
+            Some(ProtobufPluginErrorCode::PermissionDenied) => Err(PluginError::PermissionDenied),
+            Some(ProtobufPluginErrorCode::PaneNotFound) => Err(PluginError::PaneNotFound),
+            Some(ProtobufPluginErrorCode::InvalidArgument) => {
+                Err(PluginError::InvalidArgument(error_message))
+            },
+            Some(ProtobufPluginErrorCode::HostError) => Err(PluginError::HostError(error_message)),
+            None => Err(PluginError::HostError(format!(
+                "unknown error code: {}",
+                self.error_code
+            ))),
+        }
+    }
+}
+
+#[test]
+fn serialize_plugin_command_response() {
+    use prost::Message;
+    let responses = vec![
+        Ok(vec![1, 2, 3]),
+        Err(PluginError::PermissionDenied),
+        Err(PluginError::PaneNotFound),
+        Err(PluginError::InvalidArgument("no such tab".to_owned())),
+        Err(PluginError::HostError("timed out".to_owned())),
+    ];
+    for response in responses {
+        let protobuf_response = ProtobufPluginCommandResponse::from(response.clone());
+        let serialized_protobuf_response = protobuf_response.encode_to_vec();
+        let deserialized_protobuf_response: ProtobufPluginCommandResponse =
+            Message::decode(serialized_protobuf_response.as_slice()).unwrap();
+        assert_eq!(
+            response,
+            deserialized_protobuf_response.into_result(),
+            "Response properly serialized/deserialized without change"
+        );
+    }
+}
+
+#[test]
+fn serialize_cli_pipe_chunk_plugin_command() {
+    use prost::Message;
+    let plugin_command =
+        PluginCommand::CliPipeChunk("pipe_name".to_owned(), vec![0, 159, 146, 150], false);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    let serialized_protobuf_plugin_command = protobuf_plugin_command.encode_to_vec();
+    let deserialized_protobuf_plugin_command: ProtobufPluginCommand =
+        Message::decode(serialized_protobuf_plugin_command.as_slice()).unwrap();
+    let deserialized_plugin_command: Result<PluginCommand, _> =
+        deserialized_protobuf_plugin_command.try_into();
+    match deserialized_plugin_command {
+        Ok(PluginCommand::CliPipeChunk(pipe_name, chunk, is_last)) => {
+            assert_eq!(pipe_name, "pipe_name");
+            assert_eq!(
+                chunk,
+                vec![0, 159, 146, 150],
+                "chunks are not required to be UTF-8"
+            );
+            assert!(!is_last);
+        },
+        _ => panic!("CliPipeChunk not properly serialized/deserialized"),
+    }
+}
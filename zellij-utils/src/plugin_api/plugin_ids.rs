@@ -1,7 +1,7 @@
 pub use super::generated_api::api::plugin_ids::{
     PluginIds as ProtobufPluginIds, ZellijVersion as ProtobufZellijVersion,
 };
-use crate::data::PluginIds;
+use crate::data::{ClientId, PluginIds};
 
 use std::convert::TryFrom;
 use std::path::PathBuf;
@@ -13,6 +13,8 @@ impl TryFrom<ProtobufPluginIds> for PluginIds {
             plugin_id: protobuf_plugin_ids.plugin_id as u32,
             zellij_pid: protobuf_plugin_ids.zellij_pid as u32,
             initial_cwd: PathBuf::from(protobuf_plugin_ids.initial_cwd),
+            session_name: protobuf_plugin_ids.session_name,
+            client_id: protobuf_plugin_ids.client_id as ClientId,
         })
     }
 }
@@ -24,6 +26,8 @@ impl TryFrom<PluginIds> for ProtobufPluginIds {
             plugin_id: plugin_ids.plugin_id as i32,
             zellij_pid: plugin_ids.zellij_pid as i32,
             initial_cwd: plugin_ids.initial_cwd.display().to_string(),
+            session_name: plugin_ids.session_name,
+            client_id: plugin_ids.client_id as u32,
         })
     }
 }
@@ -26,6 +26,9 @@ impl TryFrom<ProtobufPermissionType> for PermissionType {
             },
             ProtobufPermissionType::Reconfigure => Ok(PermissionType::Reconfigure),
             ProtobufPermissionType::FullHdAccess => Ok(PermissionType::FullHdAccess),
+            ProtobufPermissionType::OpenUrls => Ok(PermissionType::OpenUrls),
+            ProtobufPermissionType::StoreBlobs => Ok(PermissionType::StoreBlobs),
+            ProtobufPermissionType::EmitMetrics => Ok(PermissionType::EmitMetrics),
         }
     }
 }
@@ -53,6 +56,9 @@ impl TryFrom<PermissionType> for ProtobufPermissionType {
             },
             PermissionType::Reconfigure => Ok(ProtobufPermissionType::Reconfigure),
             PermissionType::FullHdAccess => Ok(ProtobufPermissionType::FullHdAccess),
+            PermissionType::OpenUrls => Ok(ProtobufPermissionType::OpenUrls),
+            PermissionType::StoreBlobs => Ok(ProtobufPermissionType::StoreBlobs),
+            PermissionType::EmitMetrics => Ok(ProtobufPermissionType::EmitMetrics),
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::generated_api::api::style::{
+pub use super::generated_api::api::style::{
     color::Payload as ProtobufColorPayload, Color as ProtobufColor, ColorType as ProtobufColorType,
     Palette as ProtobufPalette, RgbColorPayload as ProtobufRgbColorPayload, Style as ProtobufStyle,
     ThemeHue as ProtobufThemeHue,
@@ -0,0 +1,135 @@
+//! The manifest a plugin can embed in its wasm binary as a custom section named `zellij-plugin`,
+//! eg.:
+//!
+//! ```kdl
+//! dependency "https://example.com/some-plugin.wasm"
+//! ```
+use crate::data::PluginDependency;
+use kdl::KdlDocument;
+
+const MANIFEST_SECTION_NAME: &str = "zellij-plugin";
+const WASM_MAGIC_AND_VERSION_LEN: usize = 8;
+const CUSTOM_SECTION_ID: u8 = 0;
+
+/// The dependencies declared in the manifest of a plugin binary, plugins without a manifest have
+/// none
+pub fn plugin_dependencies(wasm_bytes: &[u8]) -> Result<Vec<PluginDependency>, String> {
+    match manifest_section(wasm_bytes)? {
+        Some(manifest) => parse_manifest(manifest),
+        None => Ok(vec![]),
+    }
+}
+
+fn manifest_section(wasm_bytes: &[u8]) -> Result<Option<&[u8]>, String> {
+    if !wasm_bytes.starts_with(b"\0asm") || wasm_bytes.len() < WASM_MAGIC_AND_VERSION_LEN {
+        return Err("Not a wasm binary".to_owned());
+    }
+    let mut position = WASM_MAGIC_AND_VERSION_LEN;
+    while position < wasm_bytes.len() {
+        let section_id = wasm_bytes[position];
+        position += 1;
+        let section_len = read_leb128(wasm_bytes, &mut position)?;
+        let section_end = position
+            .checked_add(section_len)
+            .filter(|end| *end <= wasm_bytes.len())
+            .ok_or_else(|| "Truncated wasm section".to_owned())?;
+        if section_id == CUSTOM_SECTION_ID {
+            let name_len = read_leb128(wasm_bytes, &mut position)?;
+            let name_end = position
+                .checked_add(name_len)
+                .filter(|end| *end <= section_end)
+                .ok_or_else(|| "Truncated wasm custom section name".to_owned())?;
+            if &wasm_bytes[position..name_end] == MANIFEST_SECTION_NAME.as_bytes() {
+                return Ok(Some(&wasm_bytes[name_end..section_end]));
+            }
+        }
+        position = section_end;
+    }
+    Ok(None)
+}
+
+// section lengths are unsigned LEB128 encoded u32s
+fn read_leb128(bytes: &[u8], position: &mut usize) -> Result<usize, String> {
+    let mut value: usize = 0;
+    for shift in (0..32).step_by(7) {
+        let byte = *bytes
+            .get(*position)
+            .ok_or_else(|| "Truncated wasm section length".to_owned())?;
+        *position += 1;
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("Malformed wasm section length".to_owned())
+}
+
+fn parse_manifest(manifest: &[u8]) -> Result<Vec<PluginDependency>, String> {
+    let manifest = std::str::from_utf8(manifest)
+        .map_err(|e| format!("Plugin manifest is not valid UTF-8: {}", e))?;
+    let document: KdlDocument = manifest
+        .parse()
+        .map_err(|e| format!("Failed to parse plugin manifest: {}", e))?;
+    let mut dependencies = vec![];
+    for node in document.nodes() {
+        if node.name().value() != "dependency" {
+            continue;
+        }
+        let url = node
+            .get(0)
+            .and_then(|url| url.value().as_string())
+            .ok_or_else(|| "Plugin dependency must have a url".to_owned())?;
+        dependencies.push(PluginDependency {
+            url: url.to_owned(),
+        });
+    }
+    Ok(dependencies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wasm_with_custom_section(name: &str, payload: &str) -> Vec<u8> {
+        let mut section = vec![name.len() as u8];
+        section.extend_from_slice(name.as_bytes());
+        section.extend_from_slice(payload.as_bytes());
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        wasm.push(CUSTOM_SECTION_ID);
+        wasm.push(section.len() as u8);
+        wasm.extend(section);
+        wasm
+    }
+
+    #[test]
+    fn reads_dependencies_from_manifest_section() {
+        let wasm = wasm_with_custom_section(
+            MANIFEST_SECTION_NAME,
+            "dependency \"https://example.com/a.wasm\"\ndependency \"file:/tmp/b.wasm\"",
+        );
+        assert_eq!(
+            plugin_dependencies(&wasm),
+            Ok(vec![
+                PluginDependency {
+                    url: "https://example.com/a.wasm".to_owned(),
+                },
+                PluginDependency {
+                    url: "file:/tmp/b.wasm".to_owned(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn plugins_without_a_manifest_have_no_dependencies() {
+        let wasm = wasm_with_custom_section("name", "some-plugin");
+        assert_eq!(plugin_dependencies(&wasm), Ok(vec![]));
+    }
+
+    #[test]
+    fn truncated_binaries_are_an_error() {
+        let mut wasm = wasm_with_custom_section(MANIFEST_SECTION_NAME, "dependency \"a\"");
+        wasm.truncate(wasm.len() - 3);
+        assert!(plugin_dependencies(&wasm).is_err());
+    }
+}
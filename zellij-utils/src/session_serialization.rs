@@ -8,7 +8,7 @@ use crate::{
         FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, Run, RunPluginOrAlias,
         SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
-    pane_size::{Constraint, PaneGeom},
+    pane_size::{Constraint, PaneGeom, Size},
 };
 
 #[derive(Default, Debug, Clone)]
@@ -25,6 +25,7 @@ pub struct TabLayoutManifest {
     pub floating_panes: Vec<PaneLayoutManifest>,
     pub is_focused: bool,
     pub hide_floating_panes: bool,
+    pub pin_to_session: bool,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -36,6 +37,7 @@ pub struct PaneLayoutManifest {
     pub title: Option<String>,
     pub is_focused: bool,
     pub pane_contents: Option<String>,
+    pub scrollback_size: Option<usize>,
 }
 
 pub fn serialize_session_layout(
@@ -84,6 +86,7 @@ fn serialize_tab(
     tab_name: String,
     is_focused: bool,
     hide_floating_panes: bool,
+    pin_to_session: bool,
     tiled_panes: &Vec<PaneLayoutManifest>,
     floating_panes: &Vec<PaneLayoutManifest>,
     pane_contents: &mut BTreeMap<String, String>,
@@ -96,6 +99,8 @@ fn serialize_tab(
             let tiled_panes = if &tiled_panes_layout.children_split_direction
                 != &SplitDirection::default()
                 || tiled_panes_layout.children_are_stacked
+                || (tiled_panes_layout.children.is_empty()
+                    && tiled_panes_layout.scrollback_size.is_some())
             {
                 vec![tiled_panes_layout]
             } else {
@@ -115,6 +120,11 @@ fn serialize_tab(
                     KdlValue::Bool(true),
                 ));
             }
+            if pin_to_session {
+                serialized_tab
+                    .entries_mut()
+                    .push(KdlEntry::new_prop("pin_to_session", KdlValue::Bool(true)));
+            }
 
             serialize_tiled_and_floating_panes(
                 &tiled_panes,
@@ -182,16 +192,21 @@ fn serialize_tiled_pane(
     );
 
     serialize_tiled_layout_attributes(&layout, ignore_size, &mut tiled_pane_node);
+    serialize_scrollback_size(&layout.scrollback_size, &mut tiled_pane_node);
     let has_child_attributes = !layout.children.is_empty()
         || layout.external_children_index.is_some()
         || !args.is_empty()
         || plugin.is_some()
-        || command.is_some();
+        || command.is_some()
+        || layout.min_size.is_some();
     if has_child_attributes {
         let mut tiled_pane_node_children = KdlDocument::new();
         serialize_args(args, &mut tiled_pane_node_children);
+        serialize_env(&layout.run, &mut tiled_pane_node_children);
+        serialize_startup_command_timeout_ms(&layout.run, &mut tiled_pane_node_children);
         serialize_start_suspended(&command, &mut tiled_pane_node_children);
         serialize_plugin(plugin, plugin_config, &mut tiled_pane_node_children);
+        serialize_min_size(&layout.min_size, &mut tiled_pane_node_children);
         if layout.children.is_empty() && layout.external_children_index.is_some() {
             tiled_pane_node_children
                 .nodes_mut()
@@ -320,6 +335,38 @@ fn serialize_args(args: Vec<String>, pane_node_children: &mut KdlDocument) {
     }
 }
 
+fn serialize_env(layout_run: &Option<Run>, pane_node_children: &mut KdlDocument) {
+    if let Some(Run::Command(run_command)) = layout_run {
+        if !run_command.env.is_empty() {
+            // sorted so that the serialized layout is stable
+            let env: BTreeMap<&String, &String> = run_command.env.iter().collect();
+            let mut env_node = KdlNode::new("env");
+            let mut env_vars = KdlDocument::new();
+            for (name, value) in env {
+                let mut env_var = KdlNode::new(name.as_str());
+                env_var.push(value.as_str());
+                env_vars.nodes_mut().push(env_var);
+            }
+            env_node.set_children(env_vars);
+            pane_node_children.nodes_mut().push(env_node);
+        }
+    }
+}
+
+fn serialize_startup_command_timeout_ms(
+    layout_run: &Option<Run>,
+    pane_node_children: &mut KdlDocument,
+) {
+    if let Some(Run::Command(run_command)) = layout_run {
+        if let Some(timeout_ms) = run_command.startup_command_timeout_ms {
+            let mut node = KdlNode::new("startup_command_timeout_ms");
+            node.entries_mut()
+                .push(KdlEntry::new(KdlValue::Base10(timeout_ms as i64)));
+            pane_node_children.nodes_mut().push(node);
+        }
+    }
+}
+
 fn serialize_plugin(
     plugin: Option<String>,
     plugin_config: Option<PluginUserConfiguration>,
@@ -347,6 +394,30 @@ fn serialize_plugin(
     }
 }
 
+fn serialize_min_size(min_size: &Option<Size>, pane_node_children: &mut KdlDocument) {
+    if let Some(min_size) = min_size {
+        let mut min_size_node = KdlNode::new("min_size");
+        let mut min_size_children = KdlDocument::new();
+        let mut rows_node = KdlNode::new("rows");
+        rows_node.push(KdlValue::Base10(min_size.rows as i64));
+        let mut cols_node = KdlNode::new("cols");
+        cols_node.push(KdlValue::Base10(min_size.cols as i64));
+        min_size_children.nodes_mut().push(rows_node);
+        min_size_children.nodes_mut().push(cols_node);
+        min_size_node.set_children(min_size_children);
+        pane_node_children.nodes_mut().push(min_size_node);
+    }
+}
+
+fn serialize_scrollback_size(scrollback_size: &Option<usize>, kdl_node: &mut KdlNode) {
+    if let Some(scrollback_size) = scrollback_size {
+        kdl_node.entries_mut().push(KdlEntry::new_prop(
+            "scrollback_size",
+            KdlValue::Base10(*scrollback_size as i64),
+        ));
+    }
+}
+
 fn serialize_tiled_layout_attributes(
     layout: &TiledPaneLayout,
     ignore_size: bool,
@@ -566,6 +637,23 @@ fn serialize_layout_constraint(layout_constraint: LayoutConstraint) -> Option<Kd
             "exact_panes",
             KdlValue::Base10(exact_panes as i64),
         )),
+        LayoutConstraint::MaxPluginPanes(..)
+        | LayoutConstraint::MinPluginPanes(..)
+        | LayoutConstraint::ExactPluginPanes(..) => layout_constraint
+            .to_trigger()
+            .map(|trigger| KdlEntry::new_prop("trigger", trigger)),
+        LayoutConstraint::FocusedPaneIsPlugin => Some(KdlEntry::new_prop(
+            "swap_layout_when_pane_type",
+            KdlValue::String("plugin".to_owned()),
+        )),
+        LayoutConstraint::FocusedPaneIsTerminal => Some(KdlEntry::new_prop(
+            "swap_layout_when_pane_type",
+            KdlValue::String("terminal".to_owned()),
+        )),
+        LayoutConstraint::FocusedPaneTitleMatches(regex) => Some(KdlEntry::new_prop(
+            "swap_layout_when_pane_title_matches",
+            KdlValue::String(regex),
+        )),
         LayoutConstraint::NoConstraint => None,
     }
 }
@@ -624,6 +712,7 @@ fn serialize_multiple_tabs(
             tab_name.clone(),
             tab_layout_manifest.is_focused,
             hide_floating_panes,
+            tab_layout_manifest.pin_to_session,
             &tiled_panes,
             &floating_panes,
             pane_contents,
@@ -657,9 +746,12 @@ fn serialize_floating_pane(
         has_children,
         &mut floating_pane_node,
     );
+    serialize_scrollback_size(&layout.scrollback_size, &mut floating_pane_node);
     serialize_start_suspended(&command, &mut floating_pane_node_children);
     serialize_floating_layout_attributes(&layout, &mut floating_pane_node_children);
     serialize_args(args, &mut floating_pane_node_children);
+    serialize_env(&layout.run, &mut floating_pane_node_children);
+    serialize_startup_command_timeout_ms(&layout.run, &mut floating_pane_node_children);
     serialize_plugin(plugin, plugin_config, &mut floating_pane_node_children);
     floating_pane_node.set_children(floating_pane_node_children);
     floating_pane_node
@@ -669,7 +761,15 @@ fn tiled_pane_layout_from_manifest(
     manifest: Option<&PaneLayoutManifest>,
     split_size: Option<SplitSize>,
 ) -> TiledPaneLayout {
-    let (run, borderless, is_expanded_in_stack, name, focus, pane_initial_contents) = manifest
+    let (
+        run,
+        borderless,
+        is_expanded_in_stack,
+        name,
+        focus,
+        pane_initial_contents,
+        scrollback_size,
+    ) = manifest
         .map(|g| {
             let mut run = g.run.clone();
             if let Some(cwd) = &g.cwd {
@@ -686,9 +786,10 @@ fn tiled_pane_layout_from_manifest(
                 g.title.clone(),
                 Some(g.is_focused),
                 g.pane_contents.clone(),
+                g.scrollback_size,
             )
         })
-        .unwrap_or((None, false, false, None, None, None));
+        .unwrap_or((None, false, false, None, None, None, None));
     TiledPaneLayout {
         split_size,
         run,
@@ -697,6 +798,7 @@ fn tiled_pane_layout_from_manifest(
         name,
         focus,
         pane_initial_contents,
+        scrollback_size,
         ..Default::default()
     }
 }
@@ -788,6 +890,7 @@ fn get_floating_panes_layout_from_panegeoms(
                 already_running: false,
                 pane_initial_contents: m.pane_contents.clone(),
                 logical_position: None,
+                scrollback_size: m.scrollback_size,
             }
         })
         .collect()
@@ -1270,6 +1373,19 @@ mod tests {
         assert_snapshot!(kdl.0);
     }
     #[test]
+    fn can_serialize_tab_pinned_to_session() {
+        let tab_layout_manifest = TabLayoutManifest {
+            pin_to_session: true,
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
     fn can_serialize_tab_with_tiled_panes() {
         use crate::input::command::RunCommand;
         use crate::input::layout::RunPlugin;
@@ -1428,6 +1544,53 @@ mod tests {
         assert_snapshot!(kdl.0);
     }
     #[test]
+    fn can_serialize_command_pane_env() {
+        use crate::input::command::RunCommand;
+        let mut env = HashMap::new();
+        env.insert("MY_VAR".to_owned(), "my \"value\"".to_owned());
+        env.insert("ANOTHER_VAR".to_owned(), "1".to_owned());
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    run: Some(Run::Command(RunCommand {
+                        command: PathBuf::from("htop"),
+                        env,
+                        ..Default::default()
+                    })),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        is_stacked: false,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 10,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        is_stacked: false,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
     fn can_serialize_tab_with_floating_panes() {
         use crate::input::command::RunCommand;
         use crate::input::layout::RunPlugin;
@@ -1697,6 +1860,44 @@ mod tests {
         assert_snapshot!(kdl.0);
     }
     #[test]
+    fn can_serialize_pane_scrollback_size() {
+        let geom = PaneGeom {
+            x: 0,
+            y: 0,
+            rows: Dimension::fixed(10),
+            cols: Dimension::fixed(10),
+            is_stacked: false,
+            is_pinned: false,
+            logical_position: None,
+        };
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![PaneLayoutManifest {
+                geom,
+                scrollback_size: Some(500),
+                ..Default::default()
+            }],
+            floating_panes: vec![PaneLayoutManifest {
+                geom,
+                scrollback_size: Some(100000),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_eq!(kdl.0.matches("scrollback_size=500").count(), 1, "{}", kdl.0);
+        assert_eq!(
+            kdl.0.matches("scrollback_size=100000").count(),
+            1,
+            "{}",
+            kdl.0
+        );
+        assert_eq!(kdl.0.matches("scrollback_size").count(), 2, "{}", kdl.0);
+    }
+    #[test]
     fn can_serialize_new_tab_template() {
         let tiled_panes_layout = TiledPaneLayout {
             children: vec![TiledPaneLayout::default(), TiledPaneLayout::default()],
@@ -10,10 +10,12 @@ use crate::{
     errors::prelude::*,
     home::*,
     input::{
-        config::{Config, ConfigError},
+        config::{Config, ConfigDiagnostic, ConfigError},
+        keybinds::KEYBIND_ACTIONS,
         layout::Layout,
         options::Options,
     },
+    kdl::schema::config_json_schema,
 };
 use clap::{Args, IntoApp};
 use clap_complete::Shell;
@@ -237,6 +239,22 @@ pub fn dump_default_config() -> std::io::Result<()> {
     dump_asset(DEFAULT_CONFIG)
 }
 
+pub fn dump_config_schema() -> std::io::Result<()> {
+    let schema = serde_json::to_string_pretty(&config_json_schema())?;
+    dump_asset(format!("{}\n", schema).as_bytes())
+}
+
+pub fn list_keybind_actions() {
+    let name_width = KEYBIND_ACTIONS
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    for (name, description) in KEYBIND_ACTIONS {
+        println!("{:width$}  {}", name, description, width = name_width);
+    }
+}
+
 pub fn dump_specified_layout(layout: &str) -> std::io::Result<()> {
     match layout {
         "strider" => dump_asset(STRIDER_LAYOUT),
@@ -319,6 +337,11 @@ pub struct Setup {
     #[clap(long, value_parser)]
     pub dump_config: bool,
 
+    /// Dump a JSON Schema of the configuration file to stdout, for editors to complete and
+    /// validate it
+    #[clap(long, value_parser)]
+    pub dump_schema: bool,
+
     /// Disables loading of configuration file at default location,
     /// loads the defaults that zellij ships with
     #[clap(long, value_parser)]
@@ -329,6 +352,28 @@ pub struct Setup {
     #[clap(long, value_parser)]
     pub check: bool,
 
+    /// Validates the configuration file and reports every problem found in it
+    #[clap(long, value_parser)]
+    pub check_config: bool,
+
+    /// Validates the keybinds of the configuration file, suggesting fixes for misspelled actions
+    /// and modes
+    #[clap(long, value_parser)]
+    pub check_keybinds: bool,
+
+    /// Print the result of --check-config or --check-keybinds as JSON
+    #[clap(long, value_parser)]
+    pub json: bool,
+
+    /// Fail --check-config or --check-keybinds on warnings (eg. keys bound twice in the same
+    /// mode) as well as on errors
+    #[clap(long, value_parser)]
+    pub strict: bool,
+
+    /// List all the actions that can be bound to keys in the configuration
+    #[clap(long, value_parser)]
+    pub list_actions: bool,
+
     /// Dump specified layout to stdout
     #[clap(long, value_parser)]
     pub dump_layout: Option<String>,
@@ -444,6 +489,16 @@ impl Setup {
             std::process::exit(0);
         }
 
+        if self.dump_schema {
+            dump_config_schema()?;
+            std::process::exit(0);
+        }
+
+        if self.list_actions {
+            list_keybind_actions();
+            std::process::exit(0);
+        }
+
         if let Some(shell) = &self.generate_completion {
             Self::generate_completion(shell);
             std::process::exit(0);
@@ -489,6 +544,63 @@ impl Setup {
         Ok(())
     }
 
+    /// Validates the configuration file, printing every diagnostic found in it.
+    /// Returns `true` if the configuration has no problems, other than warnings unless `strict`.
+    pub fn check_config(opts: &CliArgs, json: bool, strict: bool) -> Result<bool> {
+        Setup::report_config_diagnostics(opts, json, strict, Config::validate)
+    }
+
+    /// Validates the keybinds in the configuration file, printing every diagnostic found in them.
+    /// Returns `true` if the keybinds have no problems, other than warnings unless `strict`.
+    pub fn check_keybinds(opts: &CliArgs, json: bool, strict: bool) -> Result<bool> {
+        Setup::report_config_diagnostics(opts, json, strict, Config::validate_keybinds)
+    }
+
+    fn report_config_diagnostics(
+        opts: &CliArgs,
+        json: bool,
+        strict: bool,
+        validate: fn(&str) -> Vec<ConfigDiagnostic>,
+    ) -> Result<bool> {
+        let config_file =
+            Config::config_file_path(opts).context("failed to find the configuration file")?;
+        let kdl_config = fs::read_to_string(&config_file).with_context(|| {
+            format!(
+                "failed to read configuration file '{}'",
+                config_file.display()
+            )
+        })?;
+        let diagnostics = validate(&kdl_config);
+        if json {
+            let serialized = serde_json::to_string_pretty(&diagnostics)
+                .context("failed to serialize configuration diagnostics")?;
+            println!("{}", serialized);
+        } else if diagnostics.is_empty() {
+            println!("{}: no problems found", config_file.display());
+        } else {
+            for diagnostic in &diagnostics {
+                let (line, column) = diagnostic.line_and_column(&kdl_config);
+                let severity = if diagnostic.kind.is_warning() {
+                    "warning: "
+                } else {
+                    ""
+                };
+                println!(
+                    "{}:{}:{}: {}{}: {}",
+                    config_file.display(),
+                    line,
+                    column,
+                    severity,
+                    diagnostic.kind,
+                    diagnostic.message
+                );
+            }
+        }
+        Ok(diagnostics
+            .iter()
+            .all(|diagnostic| !strict && diagnostic.kind.is_warning()))
+    }
+
     pub fn check_defaults_config(opts: &CliArgs, config_options: &Options) -> std::io::Result<()> {
         let data_dir = opts.data_dir.clone().unwrap_or_else(get_default_data_dir);
         let config_dir = opts.config_dir.clone().or_else(find_default_config_dir);
@@ -714,6 +826,24 @@ impl Setup {
                 },
                 |_| {},
             );
+            // this happens before the configuration is loaded so that an invalid configuration
+            // can still be checked
+            let check_result = if setup.check_config {
+                Some(Setup::check_config(cli_args, setup.json, setup.strict))
+            } else if setup.check_keybinds {
+                Some(Setup::check_keybinds(cli_args, setup.json, setup.strict))
+            } else {
+                None
+            };
+            match check_result {
+                Some(Ok(true)) => process::exit(0),
+                Some(Ok(false)) => process::exit(1),
+                Some(Err(e)) => {
+                    eprintln!("{:?}", e);
+                    process::exit(1);
+                },
+                None => {},
+            }
         };
     }
 }